@@ -0,0 +1,149 @@
+//! Accessibility module for exposing synced entities to screen readers.
+//!
+//! Sprites and texts synced with accessibility metadata get an
+//! `AccessibilityNode`, which `bevy_a11y` forwards to the platform's
+//! accessibility API through AccessKit.
+
+#[cfg(feature = "rendering")]
+use bevy_a11y::AccessibilityNode;
+#[cfg(feature = "rendering")]
+use bevy_ecs::component::Component;
+#[cfg(feature = "rendering")]
+use bevy_ecs::entity::Entity;
+#[cfg(feature = "rendering")]
+use bevy_ecs::world::World;
+
+/// Role announced by assistive technologies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessibleRole {
+    Button,
+    Label,
+    Image,
+}
+
+impl AccessibleRole {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "button" => Some(AccessibleRole::Button),
+            "label" => Some(AccessibleRole::Label),
+            "image" => Some(AccessibleRole::Image),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AccessibleRole::Button => "button",
+            AccessibleRole::Label => "label",
+            AccessibleRole::Image => "image",
+        }
+    }
+}
+
+/// Accessibility metadata attached to a synced sprite or text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessibilityData {
+    pub label: String,
+    pub role: AccessibleRole,
+}
+
+impl AccessibilityData {
+    pub fn new(label: impl Into<String>, role: AccessibleRole) -> Self {
+        Self {
+            label: label.into(),
+            role,
+        }
+    }
+
+    /// Builds the AccessKit node; buttons advertise the click action.
+    #[cfg(feature = "rendering")]
+    pub fn to_node(&self) -> accesskit::Node {
+        let role = match self.role {
+            AccessibleRole::Button => accesskit::Role::Button,
+            AccessibleRole::Label => accesskit::Role::Label,
+            AccessibleRole::Image => accesskit::Role::Image,
+        };
+        let mut node = accesskit::Node::new(role);
+        node.set_label(self.label.as_str());
+        if self.role == AccessibleRole::Button {
+            node.add_action(accesskit::Action::Click);
+        }
+        node
+    }
+
+    /// Reads the label and role back from a node built by `to_node`.
+    #[cfg(feature = "rendering")]
+    pub fn from_node(node: &accesskit::Node) -> Option<Self> {
+        let role = match node.role() {
+            accesskit::Role::Button => AccessibleRole::Button,
+            accesskit::Role::Label => AccessibleRole::Label,
+            accesskit::Role::Image => AccessibleRole::Image,
+            _ => return None,
+        };
+        Some(Self::new(node.label().unwrap_or_default(), role))
+    }
+}
+
+/// The metadata an entity's `AccessibilityNode` was built from.
+#[cfg(feature = "rendering")]
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct AccessibleMetadata(pub AccessibilityData);
+
+/// Change needed to bring an entity's node in line with its synced metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessibilityUpdate {
+    Unchanged,
+    Insert(AccessibilityData),
+    Remove,
+}
+
+impl AccessibilityUpdate {
+    /// Compares the metadata a node was built from with the newly synced one.
+    pub fn between(current: Option<&AccessibilityData>, synced: Option<&AccessibilityData>) -> Self {
+        match (current, synced) {
+            (Some(current), Some(synced)) if current == synced => AccessibilityUpdate::Unchanged,
+            (_, Some(synced)) => AccessibilityUpdate::Insert(synced.clone()),
+            (Some(_), None) => AccessibilityUpdate::Remove,
+            (None, None) => AccessibilityUpdate::Unchanged,
+        }
+    }
+}
+
+/// Inserts, rebuilds or removes the entity's `AccessibilityNode` to match `synced`.
+///
+/// Nodes are only rebuilt when the metadata changed; despawning the entity
+/// removes its node along with it.
+#[cfg(feature = "rendering")]
+pub fn sync_accessibility_node(world: &mut World, entity: Entity, synced: Option<&AccessibilityData>) {
+    let current = world.get::<AccessibleMetadata>(entity).map(|metadata| &metadata.0);
+    match AccessibilityUpdate::between(current, synced) {
+        AccessibilityUpdate::Unchanged => {}
+        AccessibilityUpdate::Insert(data) => {
+            let node = AccessibilityNode(data.to_node());
+            world.entity_mut(entity).insert((AccessibleMetadata(data), node));
+        }
+        AccessibilityUpdate::Remove => {
+            world
+                .entity_mut(entity)
+                .remove::<(AccessibleMetadata, AccessibilityNode)>();
+        }
+    }
+}
+
+/// Label and role of the `AccessibilityNode` of each of `entities` that has
+/// one, sorted by Ruby entity ID.
+#[cfg(feature = "rendering")]
+pub fn capture_accessibility_nodes(
+    world: &World,
+    entities: impl IntoIterator<Item = (u64, Entity)>,
+) -> Vec<(u64, AccessibilityData)> {
+    let mut nodes: Vec<_> = entities
+        .into_iter()
+        .filter_map(|(ruby_entity_id, entity)| {
+            let node = world.get::<AccessibilityNode>(entity)?;
+            Some((ruby_entity_id, AccessibilityData::from_node(&node.0)?))
+        })
+        .collect();
+    nodes.sort_unstable_by_key(|(ruby_entity_id, _)| *ruby_entity_id);
+    nodes
+}
@@ -0,0 +1,30 @@
+//! Audio playback commands queued from Ruby for the render app.
+//!
+//! Track bookkeeping (channels, fades, volumes) lives with the Ruby-facing
+//! mixer; the render app only receives the resulting per-track changes.
+
+/// A playback change for one track, identified by the ID `Bevy::Audio` returned.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioCommand {
+    Play {
+        track_id: u32,
+        path: String,
+        volume: f32,
+        speed: f32,
+        looping: bool,
+        paused: bool,
+    },
+    SetVolume {
+        track_id: u32,
+        volume: f32,
+    },
+    Pause {
+        track_id: u32,
+    },
+    Resume {
+        track_id: u32,
+    },
+    Stop {
+        track_id: u32,
+    },
+}
@@ -0,0 +1,400 @@
+//! Behavior module for lightweight per-entity transform animations.
+//!
+//! Behaviors (spin, pulse, bob) are attached to Ruby entity IDs and composed on
+//! top of the transform authored by the sprite/text/mesh sync layers. An
+//! entity whose behaviors stop changing its offset falls asleep and is skipped
+//! until something wakes it.
+
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+
+use crate::sleep::SleepTracker;
+
+#[cfg(feature = "rendering")]
+use crate::sleep::SleepConfig;
+#[cfg(feature = "rendering")]
+use bevy_ecs::entity::Entity;
+#[cfg(feature = "rendering")]
+use bevy_ecs::world::World;
+#[cfg(feature = "rendering")]
+use bevy_transform::components::Transform;
+
+/// Axis used by the bob behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BehaviorAxis {
+    X,
+    Y,
+    Z,
+}
+
+impl BehaviorAxis {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "x" | "X" => Some(BehaviorAxis::X),
+            "y" | "Y" => Some(BehaviorAxis::Y),
+            "z" | "Z" => Some(BehaviorAxis::Z),
+            _ => None,
+        }
+    }
+}
+
+/// A persistent transform animation attached to a synced entity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Behavior {
+    Spin {
+        radians_per_second: f32,
+    },
+    Pulse {
+        scale_min: f32,
+        scale_max: f32,
+        period: f32,
+    },
+    Bob {
+        amplitude: f32,
+        period: f32,
+        axis: BehaviorAxis,
+    },
+}
+
+impl Behavior {
+    fn same_kind(&self, other: &Behavior) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// Pending behavior operation.
+#[derive(Debug, Clone)]
+pub enum BehaviorOperation {
+    Add {
+        ruby_entity_id: u64,
+        behavior: Behavior,
+    },
+    Clear {
+        ruby_entity_id: u64,
+    },
+}
+
+/// Combined offset of all behaviors on one entity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BehaviorOffset {
+    pub translation: (f32, f32, f32),
+    pub rotation_z: f32,
+    pub scale: f32,
+}
+
+impl BehaviorOffset {
+    /// Largest change in any part of the offset, as motion for sleeping.
+    pub fn distance(&self, other: &BehaviorOffset) -> f32 {
+        let (dx, dy, dz) = (
+            self.translation.0 - other.translation.0,
+            self.translation.1 - other.translation.1,
+            self.translation.2 - other.translation.2,
+        );
+        (dx * dx + dy * dy + dz * dz)
+            .sqrt()
+            .max((self.rotation_z - other.rotation_z).abs())
+            .max((self.scale - other.scale).abs())
+    }
+}
+
+impl Default for BehaviorOffset {
+    fn default() -> Self {
+        Self {
+            translation: (0.0, 0.0, 0.0),
+            rotation_z: 0.0,
+            scale: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ActiveBehavior {
+    behavior: Behavior,
+    phase: f32,
+}
+
+impl ActiveBehavior {
+    fn new(behavior: Behavior) -> Self {
+        Self { behavior, phase: 0.0 }
+    }
+
+    /// Advances the phase, wrapping it so long sessions don't lose precision.
+    fn advance(&mut self, delta_secs: f32) {
+        self.phase = match self.behavior {
+            Behavior::Spin { radians_per_second } => {
+                (self.phase + radians_per_second * delta_secs).rem_euclid(TAU)
+            }
+            Behavior::Pulse { period, .. } | Behavior::Bob { period, .. } => {
+                if period > 0.0 {
+                    (self.phase + delta_secs).rem_euclid(period)
+                } else {
+                    0.0
+                }
+            }
+        };
+    }
+
+    fn cycle(&self, period: f32) -> f32 {
+        if period > 0.0 {
+            self.phase / period * TAU
+        } else {
+            0.0
+        }
+    }
+
+    /// Folds this behavior into an offset: rotations and translations add, scales multiply.
+    fn compose_into(&self, offset: &mut BehaviorOffset) {
+        match self.behavior {
+            Behavior::Spin { .. } => {
+                offset.rotation_z += self.phase;
+            }
+            Behavior::Pulse {
+                scale_min,
+                scale_max,
+                period,
+            } => {
+                let t = 0.5 - 0.5 * self.cycle(period).cos();
+                offset.scale *= scale_min + (scale_max - scale_min) * t;
+            }
+            Behavior::Bob {
+                amplitude,
+                period,
+                axis,
+            } => {
+                let displacement = amplitude * self.cycle(period).sin();
+                match axis {
+                    BehaviorAxis::X => offset.translation.0 += displacement,
+                    BehaviorAxis::Y => offset.translation.1 += displacement,
+                    BehaviorAxis::Z => offset.translation.2 += displacement,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rendering")]
+#[derive(Debug, Clone, Copy)]
+struct AppliedTransform {
+    authored: Transform,
+    written: Transform,
+}
+
+fn composed_offset(behaviors: &[ActiveBehavior]) -> BehaviorOffset {
+    let mut offset = BehaviorOffset::default();
+    for behavior in behaviors {
+        behavior.compose_into(&mut offset);
+    }
+    offset
+}
+
+/// Tracks active behaviors per Ruby entity and applies them to Bevy transforms.
+pub struct EntityBehaviors {
+    active: HashMap<u64, Vec<ActiveBehavior>>,
+    /// Entities with behaviors, split into the ones stepped each frame and
+    /// the ones asleep.
+    sleep: SleepTracker,
+    /// Pending operations to apply on next update.
+    pub pending_operations: Vec<BehaviorOperation>,
+    #[cfg(feature = "rendering")]
+    applied: HashMap<u64, AppliedTransform>,
+}
+
+impl EntityBehaviors {
+    pub fn new() -> Self {
+        Self {
+            active: HashMap::new(),
+            sleep: SleepTracker::new(),
+            pending_operations: Vec::new(),
+            #[cfg(feature = "rendering")]
+            applied: HashMap::new(),
+        }
+    }
+
+    /// Queues attaching a behavior (standalone, no World needed).
+    pub fn add_standalone(&mut self, ruby_entity_id: u64, behavior: Behavior) {
+        self.pending_operations.push(BehaviorOperation::Add {
+            ruby_entity_id,
+            behavior,
+        });
+    }
+
+    /// Queues removing all behaviors from an entity (standalone, no World needed).
+    pub fn clear_standalone(&mut self, ruby_entity_id: u64) {
+        self.pending_operations
+            .push(BehaviorOperation::Clear { ruby_entity_id });
+    }
+
+    /// Attaches a behavior, replacing any existing behavior of the same kind,
+    /// and wakes the entity.
+    pub fn add(&mut self, ruby_entity_id: u64, behavior: Behavior) {
+        self.sleep.insert(ruby_entity_id);
+        let behaviors = self.active.entry(ruby_entity_id).or_default();
+        if let Some(existing) = behaviors.iter_mut().find(|b| b.behavior.same_kind(&behavior)) {
+            existing.behavior = behavior;
+        } else {
+            behaviors.push(ActiveBehavior::new(behavior));
+        }
+    }
+
+    /// Removes every behavior from an entity. Returns false if it had none.
+    pub fn clear(&mut self, ruby_entity_id: u64) -> bool {
+        self.sleep.remove(ruby_entity_id);
+        self.active.remove(&ruby_entity_id).is_some()
+    }
+
+    /// Advances every behavior by `delta_secs`.
+    pub fn advance(&mut self, delta_secs: f32) {
+        for behaviors in self.active.values_mut() {
+            for behavior in behaviors.iter_mut() {
+                behavior.advance(delta_secs);
+            }
+        }
+    }
+
+    /// Returns the combined offset for an entity, if it has behaviors.
+    pub fn offset(&self, ruby_entity_id: u64) -> Option<BehaviorOffset> {
+        self.active.get(&ruby_entity_id).map(|behaviors| composed_offset(behaviors))
+    }
+
+    /// Wakes an entity so its transform is composed again this frame; call
+    /// it whenever something else writes the entity's transform.
+    pub fn wake(&mut self, ruby_entity_id: u64) -> bool {
+        self.sleep.wake(ruby_entity_id)
+    }
+
+    pub fn wake_all(&mut self) -> usize {
+        self.sleep.wake_all()
+    }
+
+    pub fn is_asleep(&self, ruby_entity_id: u64) -> bool {
+        self.sleep.is_asleep(ruby_entity_id)
+    }
+
+    /// Returns the number of entities whose behaviors are asleep.
+    pub fn sleeping_count(&self) -> usize {
+        self.sleep.sleeping_count()
+    }
+
+    /// Returns true if the entity has any active behavior.
+    pub fn has_behaviors(&self, ruby_entity_id: u64) -> bool {
+        self.active.contains_key(&ruby_entity_id)
+    }
+
+    /// Returns the number of entities with active behaviors.
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Returns true if no entity has behaviors.
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Applies pending operations, advances phases, and writes composed
+    /// transforms for the entities that are awake.
+    ///
+    /// `resolve` maps a Ruby entity ID to its synced Bevy entity. The authored
+    /// transform is re-captured whenever a sync layer overwrote the value this
+    /// system wrote last frame, so behaviors survive re-syncs. Sleeping
+    /// entities keep the transform written last, so whatever overwrites it
+    /// has to wake them first.
+    #[cfg(feature = "rendering")]
+    pub fn apply_pending<F>(&mut self, world: &mut World, delta_secs: f32, sleep: &SleepConfig, resolve: F)
+    where
+        F: Fn(u64) -> Option<Entity>,
+    {
+        let ops: Vec<_> = self.pending_operations.drain(..).collect();
+        for op in ops {
+            match op {
+                BehaviorOperation::Add {
+                    ruby_entity_id,
+                    behavior,
+                } => self.add(ruby_entity_id, behavior),
+                BehaviorOperation::Clear { ruby_entity_id } => {
+                    self.clear(ruby_entity_id);
+                    // Restore the authored transform unless a sync already replaced it.
+                    if let Some(applied) = self.applied.remove(&ruby_entity_id)
+                        && let Some(mut transform) = resolve(ruby_entity_id)
+                            .and_then(|entity| world.get_mut::<Transform>(entity))
+                        && *transform == applied.written
+                    {
+                        *transform = applied.authored;
+                    }
+                }
+            }
+        }
+
+        for ruby_entity_id in self.sleep.awake_ids() {
+            let Some(behaviors) = self.active.get_mut(&ruby_entity_id) else {
+                self.sleep.remove(ruby_entity_id);
+                continue;
+            };
+            let previous = composed_offset(behaviors);
+            for behavior in behaviors.iter_mut() {
+                behavior.advance(delta_secs);
+            }
+            let offset = composed_offset(behaviors);
+
+            let Some(mut transform) =
+                resolve(ruby_entity_id).and_then(|entity| world.get_mut::<Transform>(entity))
+            else {
+                if self.applied.remove(&ruby_entity_id).is_some() {
+                    // The synced entity was removed; drop its behaviors too.
+                    self.active.remove(&ruby_entity_id);
+                    self.sleep.remove(ruby_entity_id);
+                }
+                continue;
+            };
+
+            *transform = self.compose_over(ruby_entity_id, &transform, &offset);
+            self.sleep
+                .observe(ruby_entity_id, offset.distance(&previous), sleep);
+        }
+    }
+}
+
+#[cfg(feature = "rendering")]
+impl EntityBehaviors {
+    /// Returns the transform to write over `current`, the one an entity has
+    /// now, or None if it has no behaviors. Like `apply_pending`, it keeps
+    /// the authored transform unless `current` differs from the transform
+    /// returned last time.
+    pub fn compose(&mut self, ruby_entity_id: u64, current: &Transform) -> Option<Transform> {
+        let offset = self.offset(ruby_entity_id)?;
+        Some(self.compose_over(ruby_entity_id, current, &offset))
+    }
+
+    /// Composes `offset` over the authored transform, re-captured from
+    /// `current` when a sync layer replaced the transform written last.
+    fn compose_over(&mut self, ruby_entity_id: u64, current: &Transform, offset: &BehaviorOffset) -> Transform {
+        let authored = match self.applied.get(&ruby_entity_id) {
+            Some(applied) if applied.written == *current => applied.authored,
+            _ => *current,
+        };
+        let written = compose_transform(&authored, offset);
+        self.applied
+            .insert(ruby_entity_id, AppliedTransform { authored, written });
+        written
+    }
+}
+
+impl Default for EntityBehaviors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Composes a behavior offset on top of an authored transform.
+#[cfg(feature = "rendering")]
+pub fn compose_transform(authored: &Transform, offset: &BehaviorOffset) -> Transform {
+    Transform {
+        translation: authored.translation
+            + bevy_math::Vec3::new(
+                offset.translation.0,
+                offset.translation.1,
+                offset.translation.2,
+            ),
+        rotation: authored.rotation * bevy_math::Quat::from_rotation_z(offset.rotation_z),
+        scale: authored.scale * offset.scale,
+    }
+}
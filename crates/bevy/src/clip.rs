@@ -0,0 +1,373 @@
+//! Clip module for playing keyframe tracks on synced entities.
+//!
+//! A clip bundles keyframe tracks that each animate one property of one Ruby
+//! entity. Clips advance on the render thread and write their values after the
+//! sprite/text/mesh sync layers, so behaviors still compose on top.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::easing::Ease;
+
+#[cfg(feature = "rendering")]
+use bevy_color::Alpha;
+#[cfg(feature = "rendering")]
+use bevy_ecs::entity::Entity;
+#[cfg(feature = "rendering")]
+use bevy_ecs::world::World;
+#[cfg(feature = "rendering")]
+use bevy_sprite::Sprite;
+#[cfg(feature = "rendering")]
+use bevy_transform::components::Transform;
+
+/// Entity property animated by a clip track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipProperty {
+    X,
+    Y,
+    Rotation,
+    Scale,
+    /// Sprite color alpha.
+    Alpha,
+}
+
+impl ClipProperty {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "x" => Some(ClipProperty::X),
+            "y" => Some(ClipProperty::Y),
+            "rotation" => Some(ClipProperty::Rotation),
+            "scale" => Some(ClipProperty::Scale),
+            "alpha" => Some(ClipProperty::Alpha),
+            _ => None,
+        }
+    }
+}
+
+/// Easing applied to every segment of a track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipEasing {
+    Curve(Ease),
+    /// Holds each keyframe value until the next keyframe.
+    Step,
+}
+
+impl ClipEasing {
+    /// Names of `Step`, accepted alongside every `Ease` name.
+    pub const STEP_NAMES: [&'static str; 2] = ["step", "hold"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        if Self::STEP_NAMES.contains(&name) {
+            return Some(ClipEasing::Step);
+        }
+        Ease::from_name(name).map(ClipEasing::Curve)
+    }
+
+    /// Every accepted canonical name: the `Ease` names, then the step names.
+    pub fn names() -> Vec<&'static str> {
+        Ease::NAMES.iter().chain(Self::STEP_NAMES.iter()).copied().collect()
+    }
+
+    /// Maps linear progress `t` in `0.0..=1.0` to eased progress.
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            ClipEasing::Curve(ease) => ease.apply(t),
+            ClipEasing::Step => 0.0,
+        }
+    }
+}
+
+impl Default for ClipEasing {
+    fn default() -> Self {
+        ClipEasing::Curve(Ease::Linear)
+    }
+}
+
+/// Keyframes for one property of one entity, sorted by time.
+#[derive(Debug, Clone)]
+pub struct ClipTrack {
+    pub ruby_entity_id: u64,
+    pub property: ClipProperty,
+    pub easing: ClipEasing,
+    keyframes: Vec<(f32, f32)>,
+}
+
+impl ClipTrack {
+    /// Builds a track; keyframes with non-finite times are dropped.
+    pub fn new(
+        ruby_entity_id: u64,
+        property: ClipProperty,
+        easing: ClipEasing,
+        keyframes: Vec<(f32, f32)>,
+    ) -> Self {
+        let mut keyframes: Vec<_> = keyframes
+            .into_iter()
+            .filter(|(time, _)| time.is_finite())
+            .map(|(time, value)| (time.max(0.0), value))
+            .collect();
+        keyframes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            ruby_entity_id,
+            property,
+            easing,
+            keyframes,
+        }
+    }
+
+    pub fn keyframes(&self) -> &[(f32, f32)] {
+        &self.keyframes
+    }
+
+    /// Time of the last keyframe.
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |(time, _)| *time)
+    }
+
+    /// Samples the track, holding the first and last values outside the keyframe range.
+    pub fn sample(&self, time: f32) -> Option<f32> {
+        let (first, last) = (self.keyframes.first()?, self.keyframes.last()?);
+        if time <= first.0 {
+            return Some(first.1);
+        }
+        if time >= last.0 {
+            return Some(last.1);
+        }
+
+        let next_index = self.keyframes.partition_point(|(key_time, _)| *key_time <= time);
+        let (start_time, start_value) = self.keyframes[next_index - 1];
+        let (end_time, end_value) = self.keyframes[next_index];
+        let span = end_time - start_time;
+        if span <= 0.0 {
+            return Some(end_value);
+        }
+        let t = self.easing.apply((time - start_time) / span);
+        Some(start_value + (end_value - start_value) * t)
+    }
+}
+
+/// A compiled set of tracks played together.
+#[derive(Debug, Clone)]
+pub struct KeyframeClip {
+    pub tracks: Vec<ClipTrack>,
+    /// Playback rate multiplier applied to the frame delta.
+    pub speed: f32,
+}
+
+impl KeyframeClip {
+    /// Builds a clip; negative speeds are clamped to zero.
+    pub fn new(tracks: Vec<ClipTrack>, speed: f32) -> Self {
+        Self {
+            tracks,
+            speed: speed.max(0.0),
+        }
+    }
+
+    /// Length of the longest track.
+    pub fn duration(&self) -> f32 {
+        self.tracks
+            .iter()
+            .map(ClipTrack::duration)
+            .fold(0.0, f32::max)
+    }
+}
+
+/// A value written by a clip this frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipSample {
+    pub handle: u64,
+    /// Index of the track within its clip.
+    pub track: usize,
+    pub ruby_entity_id: u64,
+    pub property: ClipProperty,
+    pub value: f32,
+}
+
+/// Pending clip operation.
+#[derive(Debug, Clone)]
+pub enum ClipOperation {
+    Play { handle: u64, clip: KeyframeClip },
+    Stop { handle: u64 },
+    Seek { handle: u64, time: f32 },
+}
+
+#[derive(Debug, Clone)]
+struct ActiveClip {
+    clip: KeyframeClip,
+    playhead: f32,
+}
+
+/// Plays keyframe clips and reports the ones that finished.
+pub struct ClipPlayer {
+    active: HashMap<u64, ActiveClip>,
+    /// Pending operations to apply on next update.
+    pub pending_operations: Vec<ClipOperation>,
+    /// Handles of clips that reached their end, until drained.
+    pub finished: Vec<u64>,
+    /// Number of tracks skipped because their entity is no longer synced.
+    pub skipped_tracks: u64,
+    skipped: HashSet<(u64, usize)>,
+}
+
+impl ClipPlayer {
+    pub fn new() -> Self {
+        Self {
+            active: HashMap::new(),
+            pending_operations: Vec::new(),
+            finished: Vec::new(),
+            skipped_tracks: 0,
+            skipped: HashSet::new(),
+        }
+    }
+
+    /// Queues starting a clip (standalone, no World needed).
+    pub fn play_standalone(&mut self, handle: u64, clip: KeyframeClip) {
+        self.pending_operations
+            .push(ClipOperation::Play { handle, clip });
+    }
+
+    /// Queues stopping a clip without completing it (standalone, no World needed).
+    pub fn stop_standalone(&mut self, handle: u64) {
+        self.pending_operations.push(ClipOperation::Stop { handle });
+    }
+
+    /// Queues moving a clip's playhead (standalone, no World needed).
+    pub fn seek_standalone(&mut self, handle: u64, time: f32) {
+        self.pending_operations
+            .push(ClipOperation::Seek { handle, time });
+    }
+
+    /// Applies queued play/stop/seek operations.
+    pub fn apply_operations(&mut self) {
+        let ops: Vec<_> = self.pending_operations.drain(..).collect();
+        for op in ops {
+            match op {
+                ClipOperation::Play { handle, clip } => {
+                    self.skipped.retain(|(skipped_handle, _)| *skipped_handle != handle);
+                    self.active.insert(
+                        handle,
+                        ActiveClip {
+                            clip,
+                            playhead: 0.0,
+                        },
+                    );
+                }
+                ClipOperation::Stop { handle } => {
+                    self.active.remove(&handle);
+                }
+                ClipOperation::Seek { handle, time } => {
+                    if let Some(active) = self.active.get_mut(&handle) {
+                        active.playhead = time.clamp(0.0, active.clip.duration());
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn is_playing(&self, handle: u64) -> bool {
+        self.active.contains_key(&handle)
+    }
+
+    /// Returns the current playhead of a clip.
+    pub fn playhead(&self, handle: u64) -> Option<f32> {
+        self.active.get(&handle).map(|active| active.playhead)
+    }
+
+    /// Advances every clip by `delta_secs` scaled by its speed and samples all tracks.
+    ///
+    /// A clip that reaches its end is sampled at its duration, so the final
+    /// keyframes are always written, then removed and reported in `finished`.
+    /// Zero-duration clips therefore write their values once and finish.
+    pub fn advance(&mut self, delta_secs: f32) -> Vec<ClipSample> {
+        let mut handles: Vec<u64> = self.active.keys().copied().collect();
+        handles.sort_unstable();
+
+        let mut samples = Vec::new();
+        for handle in handles {
+            let Some(active) = self.active.get_mut(&handle) else {
+                continue;
+            };
+            let duration = active.clip.duration();
+            active.playhead = (active.playhead + delta_secs * active.clip.speed).clamp(0.0, duration);
+
+            for (index, track) in active.clip.tracks.iter().enumerate() {
+                if let Some(value) = track.sample(active.playhead) {
+                    samples.push(ClipSample {
+                        handle,
+                        track: index,
+                        ruby_entity_id: track.ruby_entity_id,
+                        property: track.property,
+                        value,
+                    });
+                }
+            }
+
+            if active.playhead >= duration {
+                self.active.remove(&handle);
+                self.finished.push(handle);
+            }
+        }
+        samples
+    }
+
+    /// Records a track whose entity is gone, counting each track once per play.
+    #[cfg(feature = "rendering")]
+    fn skip_track(&mut self, sample: &ClipSample) {
+        if self.skipped.insert((sample.handle, sample.track)) {
+            self.skipped_tracks += 1;
+        }
+    }
+
+    /// Applies pending operations, advances clips and writes their values.
+    /// Returns the Ruby entity IDs whose transform was written, in ID order.
+    ///
+    /// `resolve` maps a Ruby entity ID to its synced Bevy entity. Tracks whose
+    /// entity is no longer synced are skipped and counted in `skipped_tracks`.
+    #[cfg(feature = "rendering")]
+    pub fn apply_pending<F>(&mut self, world: &mut World, delta_secs: f32, resolve: F) -> Vec<u64>
+    where
+        F: Fn(u64) -> Option<Entity>,
+    {
+        self.apply_operations();
+
+        let mut moved = Vec::new();
+        for sample in self.advance(delta_secs) {
+            let Some(entity) = resolve(sample.ruby_entity_id) else {
+                self.skip_track(&sample);
+                continue;
+            };
+
+            if sample.property == ClipProperty::Alpha {
+                if let Some(mut sprite) = world.get_mut::<Sprite>(entity) {
+                    sprite.color.set_alpha(sample.value);
+                }
+                continue;
+            }
+
+            let Some(mut transform) = world.get_mut::<Transform>(entity) else {
+                self.skip_track(&sample);
+                continue;
+            };
+            match sample.property {
+                ClipProperty::X => transform.translation.x = sample.value,
+                ClipProperty::Y => transform.translation.y = sample.value,
+                ClipProperty::Rotation => {
+                    transform.rotation = bevy_math::Quat::from_rotation_z(sample.value);
+                }
+                ClipProperty::Scale => {
+                    transform.scale.x = sample.value;
+                    transform.scale.y = sample.value;
+                }
+                ClipProperty::Alpha => {}
+            }
+            moved.push(sample.ruby_entity_id);
+        }
+        moved.sort_unstable();
+        moved.dedup();
+        moved
+    }
+}
+
+impl Default for ClipPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,363 @@
+//! Debug bundle module: a directory of diagnostics to attach to a bug report.
+//!
+//! A bundle is made of sections, one file each: JSON documents, log tails
+//! and PNG screenshots. Each section is collected on its own, and a
+//! collector that fails or panics leaves its file out and its error in
+//! `manifest.json` instead of aborting the bundle. Log sections keep only
+//! their newest lines within `max_log_bytes`, so bundles stay small enough
+//! to mail.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::journal::write_json_string;
+
+/// Bytes each log section keeps by default.
+pub const DEFAULT_BUNDLE_LOG_BYTES: usize = 256 * 1024;
+
+/// File listing the sections, versions and capabilities of a bundle.
+pub const BUNDLE_MANIFEST_FILE: &str = "manifest.json";
+
+/// Version of the manifest layout.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Largest block a stored (uncompressed) deflate stream can hold.
+const STORED_BLOCK_BYTES: usize = 0xFFFF;
+
+/// Contents collected for one section.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BundleContent {
+    /// A JSON document, written as `<name>.json`.
+    Json(String),
+    /// Log lines, oldest first, written as `<name>.log`.
+    Lines(Vec<String>),
+    /// Top-to-bottom RGBA8 rows, written as `<name>.png`.
+    Image { width: u32, height: u32, rgba: Vec<u8> },
+}
+
+impl BundleContent {
+    fn extension(&self) -> &'static str {
+        match self {
+            BundleContent::Json(_) => "json",
+            BundleContent::Lines(_) => "log",
+            BundleContent::Image { .. } => "png",
+        }
+    }
+}
+
+/// What became of one section, as listed in the manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SectionStatus {
+    Written {
+        file: String,
+        bytes: usize,
+        /// Oldest log lines left out to stay within the cap.
+        dropped_lines: usize,
+    },
+    /// The collector had nothing to report, e.g. no screenshot was read back.
+    Skipped(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+struct Section {
+    name: String,
+    content: Result<Option<BundleContent>, String>,
+}
+
+/// Sections collected for one bundle, written together by `write`.
+#[derive(Debug, Clone)]
+pub struct DebugBundle {
+    sections: Vec<Section>,
+    info: Vec<(String, String)>,
+    capabilities: Vec<(String, bool)>,
+    max_log_bytes: usize,
+}
+
+impl Default for DebugBundle {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUNDLE_LOG_BYTES)
+    }
+}
+
+impl DebugBundle {
+    pub fn new(max_log_bytes: usize) -> Self {
+        Self {
+            sections: Vec::new(),
+            info: vec![
+                ("bevy_ruby_version".to_string(), crate::VERSION.to_string()),
+                ("bevy_version".to_string(), crate::BEVY_VERSION.to_string()),
+            ],
+            capabilities: Vec::new(),
+            max_log_bytes,
+        }
+    }
+
+    pub fn max_log_bytes(&self) -> usize {
+        self.max_log_bytes
+    }
+
+    /// Adds a `key: value` pair to the manifest's `info`, replacing an earlier one.
+    pub fn set_info(&mut self, key: &str, value: impl Into<String>) {
+        let value = value.into();
+        match self.info.iter_mut().find(|(existing, _)| existing == key) {
+            Some(entry) => entry.1 = value,
+            None => self.info.push((key.to_string(), value)),
+        }
+    }
+
+    pub fn set_capabilities(&mut self, capabilities: Vec<(String, bool)>) {
+        self.capabilities = capabilities;
+    }
+
+    /// Adds a section from an outcome collected elsewhere: `Ok(None)` is
+    /// skipped, `Err` failed. A later section of the same name replaces it.
+    pub fn add(&mut self, name: &str, content: Result<Option<BundleContent>, String>) {
+        self.sections.retain(|section| section.name != name);
+        self.sections.push(Section {
+            name: name.to_string(),
+            content,
+        });
+    }
+
+    /// Runs `collector` for section `name`; a panic fails the section like an error.
+    pub fn collect(&mut self, name: &str, collector: impl FnOnce() -> Result<Option<BundleContent>, String>) {
+        let content = panic::catch_unwind(AssertUnwindSafe(collector))
+            .unwrap_or_else(|payload| Err(format!("collector panicked: {}", panic_message(payload.as_ref()))));
+        self.add(name, content);
+    }
+
+    pub fn section_names(&self) -> Vec<&str> {
+        self.sections.iter().map(|section| section.name.as_str()).collect()
+    }
+
+    /// Writes every section and then the manifest into `dir`, creating it.
+    /// A section that fails to write is recorded as failed; only creating
+    /// the directory or writing the manifest aborts.
+    pub fn write(&self, dir: &Path) -> io::Result<Vec<(String, SectionStatus)>> {
+        fs::create_dir_all(dir)?;
+        let statuses: Vec<(String, SectionStatus)> = self
+            .sections
+            .iter()
+            .map(|section| (section.name.clone(), self.write_section(dir, section)))
+            .collect();
+        fs::write(dir.join(BUNDLE_MANIFEST_FILE), self.manifest_json(&statuses))?;
+        Ok(statuses)
+    }
+
+    fn write_section(&self, dir: &Path, section: &Section) -> SectionStatus {
+        let content = match &section.content {
+            Ok(Some(content)) => content,
+            Ok(None) => return SectionStatus::Skipped("not available".to_string()),
+            Err(error) => return SectionStatus::Failed(error.clone()),
+        };
+        let file = format!("{}.{}", file_stem(&section.name), content.extension());
+        let (bytes, dropped_lines) = match content {
+            BundleContent::Json(json) => (json.clone().into_bytes(), 0),
+            BundleContent::Lines(lines) => {
+                let (text, dropped) = log_tail(lines, self.max_log_bytes);
+                (text.into_bytes(), dropped)
+            }
+            BundleContent::Image { width, height, rgba } => match encode_png(*width, *height, rgba) {
+                Some(png) => (png, 0),
+                None => {
+                    return SectionStatus::Failed(format!(
+                        "{} bytes of pixels do not make a {}x{} RGBA image",
+                        rgba.len(),
+                        width,
+                        height
+                    ));
+                }
+            },
+        };
+        match fs::write(dir.join(&file), &bytes) {
+            Ok(()) => SectionStatus::Written {
+                file,
+                bytes: bytes.len(),
+                dropped_lines,
+            },
+            Err(error) => SectionStatus::Failed(format!("cannot write {}: {}", file, error)),
+        }
+    }
+
+    /// `{format, created_at, info, capabilities, sections}` with one entry
+    /// per section in the order they were added.
+    pub fn manifest_json(&self, statuses: &[(String, SectionStatus)]) -> String {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "{{\n  \"format\": {},\n  \"created_at\": {},\n  \"info\": {{",
+            BUNDLE_FORMAT_VERSION, created_at
+        );
+        for (index, (key, value)) in self.info.iter().enumerate() {
+            out.push_str(if index == 0 { "\n    " } else { ",\n    " });
+            write_json_string(&mut out, key);
+            out.push_str(": ");
+            write_json_string(&mut out, value);
+        }
+        out.push_str("\n  },\n  \"capabilities\": {");
+        for (index, (name, available)) in self.capabilities.iter().enumerate() {
+            out.push_str(if index == 0 { "\n    " } else { ",\n    " });
+            write_json_string(&mut out, name);
+            let _ = write!(out, ": {}", available);
+        }
+        out.push_str("\n  },\n  \"sections\": [");
+        for (index, (name, status)) in statuses.iter().enumerate() {
+            out.push_str(if index == 0 { "\n    {\"name\": " } else { ",\n    {\"name\": " });
+            write_json_string(&mut out, name);
+            match status {
+                SectionStatus::Written {
+                    file,
+                    bytes,
+                    dropped_lines,
+                } => {
+                    out.push_str(", \"status\": \"written\", \"file\": ");
+                    write_json_string(&mut out, file);
+                    let _ = write!(out, ", \"bytes\": {}, \"dropped_lines\": {}", bytes, dropped_lines);
+                }
+                SectionStatus::Skipped(reason) => {
+                    out.push_str(", \"status\": \"skipped\", \"reason\": ");
+                    write_json_string(&mut out, reason);
+                }
+                SectionStatus::Failed(error) => {
+                    out.push_str(", \"status\": \"failed\", \"error\": ");
+                    write_json_string(&mut out, error);
+                }
+            }
+            out.push('}');
+        }
+        out.push_str("\n  ]\n}\n");
+        out
+    }
+}
+
+/// Section names become file names with anything but ASCII letters, digits,
+/// `-` and `_` replaced by `_`.
+fn file_stem(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' { ch } else { '_' })
+        .collect();
+    if stem.is_empty() { "section".to_string() } else { stem }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// The newest `lines` that fit in `max_bytes`, one per line, and how many
+/// older lines were left out. A single line longer than the cap keeps its end.
+pub fn log_tail(lines: &[String], max_bytes: usize) -> (String, usize) {
+    let mut kept = 0;
+    let mut bytes = 0;
+    for line in lines.iter().rev() {
+        let line_bytes = line.len() + 1;
+        if bytes + line_bytes > max_bytes {
+            break;
+        }
+        bytes += line_bytes;
+        kept += 1;
+    }
+
+    let dropped = lines.len() - kept;
+    let mut text = String::with_capacity(bytes);
+    if kept == 0
+        && max_bytes > 1
+        && let Some(last) = lines.last()
+    {
+        let mut start = last.len().saturating_sub(max_bytes - 1);
+        while !last.is_char_boundary(start) {
+            start += 1;
+        }
+        text.push_str(&last[start..]);
+        text.push('\n');
+        return (text, dropped - 1);
+    }
+    for line in &lines[dropped..] {
+        text.push_str(line);
+        text.push('\n');
+    }
+    (text, dropped)
+}
+
+/// Encodes top-to-bottom RGBA8 rows as a PNG with stored (uncompressed)
+/// deflate blocks; `None` when `rgba` is not `width * height * 4` bytes.
+pub fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Option<Vec<u8>> {
+    let row_bytes = (width as usize).checked_mul(4)?;
+    if width == 0 || height == 0 || rgba.len() != row_bytes.checked_mul(height as usize)? {
+        return None;
+    }
+
+    // Filter type 0 (none) before every row.
+    let mut raw = Vec::with_capacity((row_bytes + 1) * height as usize);
+    for row in rgba.chunks(row_bytes) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    let blocks = raw.chunks(STORED_BLOCK_BYTES).count();
+    for (index, block) in raw.chunks(STORED_BLOCK_BYTES).enumerate() {
+        zlib.push(u8::from(index + 1 == blocks));
+        let len = block.len() as u16;
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8-bit RGBA, deflate, adaptive filtering, no interlace.
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    push_png_chunk(&mut png, b"IHDR", &header);
+    push_png_chunk(&mut png, b"IDAT", &zlib);
+    push_png_chunk(&mut png, b"IEND", &[]);
+    Some(png)
+}
+
+fn push_png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    png.extend_from_slice(&crc32(&[kind.as_slice(), data]).to_be_bytes());
+}
+
+fn crc32(parts: &[&[u8]]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in parts.iter().flat_map(|part| part.iter()) {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65_521;
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for byte in chunk {
+            a += u32::from(*byte);
+            b += a;
+        }
+        a %= MOD_ADLER;
+        b %= MOD_ADLER;
+    }
+    (b << 16) | a
+}
@@ -0,0 +1,611 @@
+//! Determinism audit module for tracking down replay desyncs.
+//!
+//! With the `determinism_audit` feature, the sync layers and the input bridge
+//! store their entities and input in ordered collections, and the render
+//! bridge folds every delivered input and queued sync operation into a
+//! per-frame checksum. Streams of checksums from two runs can then be compared
+//! to find the first frame where they diverged.
+//!
+//! Checksums hash canonicalized data explicitly as little-endian bytes with
+//! FNV-1a, so they are stable across platforms and builds.
+
+#[cfg(not(feature = "determinism_audit"))]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "determinism_audit")]
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::accessibility::AccessibilityData;
+use crate::draw_order::DrawOrderChange;
+use crate::error::BevyRubyError;
+use crate::hash::FrameChecksum;
+use crate::input_bridge::InputState;
+use crate::jelly::JellyConfig;
+use crate::mesh_renderer::MeshOperation;
+use crate::sprite_renderer::{SpriteData, SpriteOperation, TransformData};
+use crate::text_renderer::{TextData, TextGlow, TextOperation, TextTransformData};
+
+#[cfg(feature = "rendering")]
+use crate::render_app::{PickingEventData, TouchData};
+
+/// Map used by the sync layers and input bridge; ordered under `determinism_audit`.
+#[cfg(not(feature = "determinism_audit"))]
+pub type AuditMap<K, V> = HashMap<K, V>;
+#[cfg(feature = "determinism_audit")]
+pub type AuditMap<K, V> = BTreeMap<K, V>;
+
+/// Set used by the input bridge; ordered under `determinism_audit`.
+#[cfg(not(feature = "determinism_audit"))]
+pub type AuditSet<T> = HashSet<T>;
+#[cfg(feature = "determinism_audit")]
+pub type AuditSet<T> = BTreeSet<T>;
+
+/// Data that can be folded into a frame checksum.
+pub trait Checksum {
+    fn checksum(&self, hasher: &mut FrameChecksum);
+}
+
+impl<T: Checksum> Checksum for Option<T> {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        hasher.write_bool(self.is_some());
+        if let Some(value) = self {
+            value.checksum(hasher);
+        }
+    }
+}
+
+// The data impls below destructure without `..`, so a new field does not
+// compile until it is hashed too.
+
+impl Checksum for SpriteData {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        let SpriteData {
+            color_r,
+            color_g,
+            color_b,
+            color_a,
+            flip_x,
+            flip_y,
+            anchor_x,
+            anchor_y,
+            has_custom_size,
+            custom_size_x,
+            custom_size_y,
+            group,
+            accessibility,
+            texture_path,
+            mesh_mode,
+            jelly,
+            z_index,
+            atlas_rect,
+        } = self;
+        for value in [
+            color_r,
+            color_g,
+            color_b,
+            color_a,
+            anchor_x,
+            anchor_y,
+            custom_size_x,
+            custom_size_y,
+        ] {
+            hasher.write_f32(*value);
+        }
+        hasher.write_bool(*flip_x);
+        hasher.write_bool(*flip_y);
+        hasher.write_bool(*has_custom_size);
+        hasher.write_option_str(group.as_deref());
+        accessibility.checksum(hasher);
+        hasher.write_option_str(texture_path.as_deref());
+        hasher.write_bool(*mesh_mode);
+        jelly.checksum(hasher);
+        hasher.write_bool(z_index.is_some());
+        if let Some(z_index) = z_index {
+            hasher.write_u32(*z_index as u32);
+        }
+        hasher.write_bool(atlas_rect.is_some());
+        if let Some((x, y, width, height)) = atlas_rect {
+            for value in [x, y, width, height] {
+                hasher.write_f32(*value);
+            }
+        }
+    }
+}
+
+impl Checksum for TransformData {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        let TransformData {
+            translation_x,
+            translation_y,
+            translation_z,
+            rotation_x,
+            rotation_y,
+            rotation_z,
+            rotation_w,
+            scale_x,
+            scale_y,
+            scale_z,
+        } = self;
+        for value in [
+            translation_x,
+            translation_y,
+            translation_z,
+            rotation_x,
+            rotation_y,
+            rotation_z,
+            rotation_w,
+            scale_x,
+            scale_y,
+            scale_z,
+        ] {
+            hasher.write_f32(*value);
+        }
+    }
+}
+
+impl Checksum for JellyConfig {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        let JellyConfig {
+            subdivisions,
+            stiffness,
+            damping,
+        } = self;
+        hasher.write_u32(*subdivisions);
+        hasher.write_f32(*stiffness);
+        hasher.write_f32(*damping);
+    }
+}
+
+impl Checksum for AccessibilityData {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        let AccessibilityData { label, role } = self;
+        hasher.write_str(label);
+        hasher.write_str(role.name());
+    }
+}
+
+impl Checksum for TextData {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        let TextData {
+            content,
+            font_size,
+            color_r,
+            color_g,
+            color_b,
+            color_a,
+            accessibility,
+            glow,
+            blend,
+            justify,
+            linebreak,
+        } = self;
+        hasher.write_str(content);
+        for value in [font_size, color_r, color_g, color_b, color_a] {
+            hasher.write_f32(*value);
+        }
+        accessibility.checksum(hasher);
+        glow.checksum(hasher);
+        hasher.write_str(blend.name());
+        hasher.write_str(justify.name());
+        hasher.write_str(linebreak.name());
+    }
+}
+
+impl Checksum for TextGlow {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        let TextGlow {
+            color_r,
+            color_g,
+            color_b,
+            color_a,
+            radius,
+            intensity,
+        } = self;
+        for value in [color_r, color_g, color_b, color_a, radius, intensity] {
+            hasher.write_f32(*value);
+        }
+    }
+}
+
+impl Checksum for TextTransformData {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        let TextTransformData {
+            translation_x,
+            translation_y,
+            translation_z,
+            scale_x,
+            scale_y,
+            scale_z,
+        } = self;
+        for value in [translation_x, translation_y, translation_z, scale_x, scale_y, scale_z] {
+            hasher.write_f32(*value);
+        }
+    }
+}
+
+impl Checksum for SpriteOperation {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        match self {
+            SpriteOperation::Sync {
+                ruby_entity_id,
+                sprite_data,
+                transform_data,
+            } => {
+                hasher.write_u8(0);
+                hasher.write_u64(*ruby_entity_id);
+                sprite_data.checksum(hasher);
+                transform_data.checksum(hasher);
+            }
+            SpriteOperation::Remove { ruby_entity_id } => {
+                hasher.write_u8(1);
+                hasher.write_u64(*ruby_entity_id);
+            }
+            SpriteOperation::RemoveDeferred {
+                ruby_entity_id,
+                frames,
+            } => {
+                hasher.write_u8(2);
+                hasher.write_u64(*ruby_entity_id);
+                hasher.write_u32(*frames);
+            }
+            SpriteOperation::SetRemoveDefer { frames } => {
+                hasher.write_u8(3);
+                hasher.write_u32(*frames);
+            }
+            SpriteOperation::SetColor {
+                ruby_entity_id,
+                color,
+            } => {
+                hasher.write_u8(4);
+                hasher.write_u64(*ruby_entity_id);
+                for value in color {
+                    hasher.write_f32(*value);
+                }
+            }
+            SpriteOperation::ClearColor { ruby_entity_id } => {
+                hasher.write_u8(5);
+                hasher.write_u64(*ruby_entity_id);
+            }
+            SpriteOperation::Clear => hasher.write_u8(6),
+            SpriteOperation::SetLod {
+                ruby_entity_id,
+                sprite_data,
+                hidden,
+            } => {
+                hasher.write_u8(7);
+                hasher.write_u64(*ruby_entity_id);
+                hasher.write_bool(*hidden);
+                sprite_data.checksum(hasher);
+            }
+            SpriteOperation::ClearLod { ruby_entity_id } => {
+                hasher.write_u8(8);
+                hasher.write_u64(*ruby_entity_id);
+            }
+            SpriteOperation::ApplyImpulse {
+                ruby_entity_id,
+                direction,
+                strength,
+            } => {
+                hasher.write_u8(9);
+                hasher.write_u64(*ruby_entity_id);
+                hasher.write_f32(direction[0]);
+                hasher.write_f32(direction[1]);
+                hasher.write_f32(*strength);
+            }
+            SpriteOperation::SetDrawOrder {
+                ruby_entity_id,
+                change,
+            } => {
+                hasher.write_u8(10);
+                hasher.write_u64(*ruby_entity_id);
+                match change {
+                    DrawOrderChange::Front => hasher.write_u8(0),
+                    DrawOrderChange::Back => hasher.write_u8(1),
+                    DrawOrderChange::Index(index) => {
+                        hasher.write_u8(2);
+                        hasher.write_u64(*index as u64);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Checksum for TextOperation {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        match self {
+            TextOperation::Sync {
+                ruby_entity_id,
+                text_data,
+                transform_data,
+            } => {
+                hasher.write_u8(16);
+                hasher.write_u64(*ruby_entity_id);
+                text_data.checksum(hasher);
+                transform_data.checksum(hasher);
+            }
+            TextOperation::Remove { ruby_entity_id } => {
+                hasher.write_u8(17);
+                hasher.write_u64(*ruby_entity_id);
+            }
+            TextOperation::Clear => hasher.write_u8(18),
+        }
+    }
+}
+
+impl Checksum for MeshOperation {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        match self {
+            MeshOperation::Sync {
+                ruby_entity_id,
+                mesh_data,
+                transform_data,
+            } => {
+                hasher.write_u8(32);
+                hasher.write_u64(*ruby_entity_id);
+                hasher.write_str(&format!("{:?}", mesh_data.shape_type));
+                for value in [
+                    mesh_data.color_r,
+                    mesh_data.color_g,
+                    mesh_data.color_b,
+                    mesh_data.color_a,
+                    mesh_data.width,
+                    mesh_data.height,
+                    mesh_data.radius,
+                    mesh_data.line_start_x,
+                    mesh_data.line_start_y,
+                    mesh_data.line_end_x,
+                    mesh_data.line_end_y,
+                    mesh_data.thickness,
+                ] {
+                    hasher.write_f32(value);
+                }
+                hasher.write_u32(mesh_data.sides);
+                hasher.write_bool(mesh_data.fill);
+                hasher.write_bool(mesh_data.gradient_color.is_some());
+                if let Some(gradient_color) = mesh_data.gradient_color {
+                    for value in gradient_color {
+                        hasher.write_f32(value);
+                    }
+                    hasher.write_str(mesh_data.gradient_direction.name());
+                }
+                for value in [
+                    transform_data.translation_x,
+                    transform_data.translation_y,
+                    transform_data.translation_z,
+                    transform_data.rotation_x,
+                    transform_data.rotation_y,
+                    transform_data.rotation_z,
+                    transform_data.rotation_w,
+                    transform_data.scale_x,
+                    transform_data.scale_y,
+                    transform_data.scale_z,
+                ] {
+                    hasher.write_f32(value);
+                }
+            }
+            MeshOperation::Remove { ruby_entity_id } => {
+                hasher.write_u8(33);
+                hasher.write_u64(*ruby_entity_id);
+            }
+            MeshOperation::Clear => hasher.write_u8(34),
+        }
+    }
+}
+
+impl Checksum for InputState {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        hasher.write_u8(48);
+        hasher.write_sorted_strs(&self.keys_pressed);
+        hasher.write_sorted_strs(&self.keys_just_pressed);
+        hasher.write_sorted_strs(&self.keys_just_released);
+        hasher.write_sorted_strs(&self.mouse_buttons_pressed);
+        hasher.write_sorted_strs(&self.mouse_buttons_just_pressed);
+        hasher.write_f32(self.mouse_position.0);
+        hasher.write_f32(self.mouse_position.1);
+        hasher.write_f32(self.mouse_delta.0);
+        hasher.write_f32(self.mouse_delta.1);
+        hasher.write_f32(self.scroll_line_delta.0);
+        hasher.write_f32(self.scroll_line_delta.1);
+        hasher.write_f32(self.scroll_pixel_delta.0);
+        hasher.write_f32(self.scroll_pixel_delta.1);
+
+        let mut gamepads: Vec<_> = self.gamepads.values().collect();
+        gamepads.sort_unstable_by_key(|gamepad| gamepad.id);
+        hasher.write_u64(gamepads.len() as u64);
+        for gamepad in gamepads {
+            hasher.write_u64(gamepad.id);
+            hasher.write_sorted_strs(&gamepad.buttons_pressed);
+            hasher.write_sorted_strs(&gamepad.buttons_just_pressed);
+            hasher.write_sorted_strs(&gamepad.buttons_just_released);
+            hasher.write_u64(gamepad.axes.len() as u64);
+            for (name, value) in &gamepad.axes {
+                hasher.write_str(name);
+                hasher.write_f32(*value);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rendering")]
+impl Checksum for PickingEventData {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        hasher.write_u8(64);
+        hasher.write_str(&self.kind);
+        hasher.write_u64(self.target_id);
+        hasher.write_bool(self.hit_region);
+        hasher.write_str(&self.pointer_id);
+        hasher.write_f32(self.pointer_position.0);
+        hasher.write_f32(self.pointer_position.1);
+        hasher.write_option_str(self.button.as_deref());
+        for vector in [self.delta, self.distance] {
+            hasher.write_bool(vector.is_some());
+            if let Some((x, y)) = vector {
+                hasher.write_f32(x);
+                hasher.write_f32(y);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rendering")]
+impl Checksum for TouchData {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        hasher.write_u8(65);
+        hasher.write_u64(self.id);
+        hasher.write_f32(self.position.0);
+        hasher.write_f32(self.position.1);
+        hasher.write_str(&self.phase);
+    }
+}
+
+/// First frame where two checksum streams disagree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecksumDivergence {
+    pub frame: usize,
+    /// Recorded checksum, or `None` if the recording ended first.
+    pub expected: Option<u64>,
+    /// Live checksum, or `None` if the live run ended first.
+    pub actual: Option<u64>,
+}
+
+/// Compares two streams frame by frame.
+///
+/// A length mismatch counts as a divergence only when `require_same_length` is set,
+/// so a still-running session can be compared against a longer recording.
+pub fn compare_checksum_streams(
+    expected: &[u64],
+    actual: &[u64],
+    require_same_length: bool,
+) -> Option<ChecksumDivergence> {
+    if let Some(frame) = expected.iter().zip(actual).position(|(a, b)| a != b) {
+        return Some(ChecksumDivergence {
+            frame,
+            expected: Some(expected[frame]),
+            actual: Some(actual[frame]),
+        });
+    }
+    if require_same_length && expected.len() != actual.len() {
+        let frame = expected.len().min(actual.len());
+        return Some(ChecksumDivergence {
+            frame,
+            expected: expected.get(frame).copied(),
+            actual: actual.get(frame).copied(),
+        });
+    }
+    None
+}
+
+/// Formats a stream as one 16-digit hex checksum per line.
+pub fn format_checksum_stream(checksums: &[u64]) -> String {
+    checksums
+        .iter()
+        .map(|checksum| format!("{:016x}\n", checksum))
+        .collect()
+}
+
+/// Parses a stream written by `format_checksum_stream`; blank lines are ignored.
+pub fn parse_checksum_stream(text: &str) -> Result<Vec<u64>, BevyRubyError> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            u64::from_str_radix(line, 16).map_err(|_| BevyRubyError::InvalidType {
+                expected: "hex checksum".to_string(),
+                actual: format!("line {}: {}", index + 1, line),
+            })
+        })
+        .collect()
+}
+
+/// Per-frame checksum recorder with optional comparison against a recorded stream.
+///
+/// Only the latest checksum is kept; callers that need the whole stream
+/// collect it from `drain_finished`.
+#[derive(Debug, Clone, Default)]
+pub struct DeterminismAudit {
+    current: FrameChecksum,
+    /// Number of frames finished so far.
+    frames: usize,
+    last: Option<u64>,
+    /// Checksums finished since the last `drain_finished`.
+    finished: Vec<u64>,
+    recorded: Option<Vec<u64>>,
+    divergence: Option<ChecksumDivergence>,
+}
+
+impl DeterminismAudit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record<T: Checksum>(&mut self, value: &T) {
+        value.checksum(&mut self.current);
+    }
+
+    pub fn record_all<'a, T, I>(&mut self, values: I)
+    where
+        T: Checksum + 'a,
+        I: IntoIterator<Item = &'a T>,
+    {
+        for value in values {
+            value.checksum(&mut self.current);
+        }
+    }
+
+    /// Closes the frame, compares it against the recording and returns its checksum.
+    pub fn finish_frame(&mut self) -> u64 {
+        let checksum = self.current.finish();
+        self.current = FrameChecksum::new();
+        self.check_frame(self.frames, checksum);
+        self.frames += 1;
+        self.last = Some(checksum);
+        self.finished.push(checksum);
+        checksum
+    }
+
+    /// Checksum of the last finished frame.
+    pub fn last_checksum(&self) -> Option<u64> {
+        self.last
+    }
+
+    /// Number of frames finished so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Takes the checksums finished since the previous call.
+    pub fn drain_finished(&mut self) -> Vec<u64> {
+        std::mem::take(&mut self.finished)
+    }
+
+    /// Compares `finished`, the checksums of the frames finished so far, and
+    /// every future frame against `recorded`.
+    pub fn set_recorded(&mut self, recorded: Vec<u64>, finished: &[u64]) {
+        self.divergence = compare_checksum_streams(&recorded, finished, false);
+        self.recorded = Some(recorded);
+    }
+
+    /// First divergence from the recorded stream, if any.
+    pub fn divergence(&self) -> Option<ChecksumDivergence> {
+        self.divergence
+    }
+
+    /// Compares one newly finished frame; a recording that ended earlier
+    /// does not count as diverged.
+    fn check_frame(&mut self, frame: usize, checksum: u64) {
+        if self.divergence.is_some() {
+            return;
+        }
+        let Some(&expected) = self.recorded.as_ref().and_then(|recorded| recorded.get(frame)) else {
+            return;
+        };
+        if expected != checksum {
+            self.divergence = Some(ChecksumDivergence {
+                frame,
+                expected: Some(expected),
+                actual: Some(checksum),
+            });
+        }
+    }
+}
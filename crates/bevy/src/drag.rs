@@ -0,0 +1,312 @@
+//! Drag module for moving synced sprites with the pointer.
+//!
+//! A draggable sprite is picked up when the primary button goes down over
+//! it, keeping the offset between the pointer and its position, so it never
+//! jumps under the pointer. While held it follows the pointer, clamped to its
+//! constraint rectangle, and on release it either stays where it was dropped
+//! or snaps back to where it was picked up. Every release is reported as a
+//! `DragEvent` with the topmost drop target under the pointer.
+//!
+//! Positions are in world units (y up). `DragGestures` only tracks the
+//! gesture; `render_app` feeds it the pointer and moves the sprite.
+
+use std::collections::BTreeMap;
+
+use crate::clip::{ClipEasing, ClipProperty, ClipTrack, KeyframeClip};
+use crate::easing::Ease;
+
+/// Layer a held sprite draws on unless `enable` names another.
+pub const DEFAULT_DRAG_LAYER: i32 = 100;
+
+/// Seconds a snap-back takes to bring the sprite back to its origin.
+pub const DRAG_SNAP_BACK_SECS: f32 = 0.25;
+
+/// Set on the handles of snap-back clips, which play on the `ClipPlayer`
+/// beside the clips started from Ruby.
+pub const DRAG_SNAP_BACK_CLIP_FLAG: u64 = 1 << 63;
+
+/// Rectangle a held sprite's position stays inside, with its minimum corner
+/// at `(x, y)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragBounds {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl DragBounds {
+    /// Closest point inside the rectangle, edges included.
+    pub fn clamp(&self, point: (f32, f32)) -> (f32, f32) {
+        (
+            point.0.max(self.x).min(self.x + self.width),
+            point.1.max(self.y).min(self.y + self.height),
+        )
+    }
+}
+
+/// How one sprite drags.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragConfig {
+    pub constrain_to: Option<DragBounds>,
+    /// Return to the pickup position on release instead of staying put.
+    pub snap_back: bool,
+    /// Sprite layer the sprite draws on while held.
+    pub drag_layer: i32,
+}
+
+impl Default for DragConfig {
+    fn default() -> Self {
+        Self {
+            constrain_to: None,
+            snap_back: false,
+            drag_layer: DEFAULT_DRAG_LAYER,
+        }
+    }
+}
+
+/// What was under the pointer when a drag ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DropTarget {
+    /// Ruby entity ID, or the region ID when `hit_region` is set.
+    pub id: u64,
+    pub hit_region: bool,
+}
+
+/// The sprite being held.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActiveDrag {
+    pub id: u64,
+    /// Sprite translation at pickup; its z is restored on release.
+    pub origin: (f32, f32, f32),
+    /// Sprite position minus pointer position at pickup.
+    pub offset: (f32, f32),
+    pub position: (f32, f32),
+    pub config: DragConfig,
+}
+
+/// A finished drag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragEvent {
+    pub id: u64,
+    /// Where the sprite comes to rest: `dropped_at`, or the origin after a snap-back.
+    pub position: (f32, f32),
+    /// Where the pointer let go of it, after the constraint.
+    pub dropped_at: (f32, f32),
+    pub origin: (f32, f32),
+    pub drop_target: Option<DropTarget>,
+    pub snapped_back: bool,
+}
+
+impl DragEvent {
+    /// Handle of this drag's snap-back clip.
+    pub fn snap_back_handle(&self) -> u64 {
+        DRAG_SNAP_BACK_CLIP_FLAG | self.id
+    }
+
+    /// Clip easing the sprite from `dropped_at` back to its origin, for
+    /// snapped-back drags.
+    pub fn snap_back_clip(&self) -> Option<KeyframeClip> {
+        if !self.snapped_back {
+            return None;
+        }
+        let easing = ClipEasing::Curve(Ease::CubicOut);
+        let track = |property, from: f32, to: f32| {
+            ClipTrack::new(self.id, property, easing, vec![(0.0, from), (DRAG_SNAP_BACK_SECS, to)])
+        };
+        Some(KeyframeClip::new(
+            vec![
+                track(ClipProperty::X, self.dropped_at.0, self.origin.0),
+                track(ClipProperty::Y, self.dropped_at.1, self.origin.1),
+            ],
+            1.0,
+        ))
+    }
+}
+
+/// Pending drag operation.
+#[derive(Debug, Clone)]
+pub enum DragOperation {
+    Enable { id: u64, config: DragConfig },
+    Disable { id: u64 },
+}
+
+/// World position of a pointer at `pointer`, measured from the window center
+/// with y up, seen through a 2D camera.
+pub fn pointer_world_position(
+    pointer: (f32, f32),
+    camera_position: (f32, f32),
+    camera_scale: f32,
+    camera_rotation: f32,
+) -> (f32, f32) {
+    let (sin, cos) = camera_rotation.sin_cos();
+    let (x, y) = (pointer.0 * camera_scale, pointer.1 * camera_scale);
+    (
+        camera_position.0 + x * cos - y * sin,
+        camera_position.1 + x * sin + y * cos,
+    )
+}
+
+/// Draggable sprites and the one gesture in progress.
+pub struct DragGestures {
+    configs: BTreeMap<u64, DragConfig>,
+    active: Option<ActiveDrag>,
+    /// Pending operations to apply on next update.
+    pub pending_operations: Vec<DragOperation>,
+    /// Finished drags, until drained.
+    pub events: Vec<DragEvent>,
+}
+
+impl DragGestures {
+    pub fn new() -> Self {
+        Self {
+            configs: BTreeMap::new(),
+            active: None,
+            pending_operations: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Queues making a sprite draggable (standalone, no World needed).
+    pub fn enable_standalone(&mut self, id: u64, config: DragConfig) {
+        self.pending_operations
+            .push(DragOperation::Enable { id, config });
+    }
+
+    /// Queues making a sprite no longer draggable (standalone, no World needed).
+    pub fn disable_standalone(&mut self, id: u64) {
+        self.pending_operations.push(DragOperation::Disable { id });
+    }
+
+    /// Applies pending operations. Returns the held drag if one of them
+    /// disabled its sprite, so the caller can put the sprite back down.
+    pub fn apply_operations(&mut self) -> Option<ActiveDrag> {
+        let mut dropped = None;
+        for op in std::mem::take(&mut self.pending_operations) {
+            match op {
+                DragOperation::Enable { id, config } => self.enable(id, config),
+                DragOperation::Disable { id } => {
+                    if let Some(active) = self.disable(id) {
+                        dropped = Some(active);
+                    }
+                }
+            }
+        }
+        dropped
+    }
+
+    /// Makes `id` draggable, replacing its config; a held sprite uses the
+    /// new config from its next move.
+    pub fn enable(&mut self, id: u64, config: DragConfig) {
+        self.configs.insert(id, config);
+        if let Some(active) = self.active.as_mut().filter(|active| active.id == id) {
+            active.config = config;
+        }
+    }
+
+    /// Makes `id` no longer draggable. A drag of it in progress ends without
+    /// an event and is returned.
+    pub fn disable(&mut self, id: u64) -> Option<ActiveDrag> {
+        self.configs.remove(&id);
+        if self.active.is_some_and(|active| active.id == id) {
+            return self.active.take();
+        }
+        None
+    }
+
+    pub fn is_enabled(&self, id: u64) -> bool {
+        self.configs.contains_key(&id)
+    }
+
+    pub fn config(&self, id: u64) -> Option<DragConfig> {
+        self.configs.get(&id).copied()
+    }
+
+    /// Draggable sprite IDs, in ID order.
+    pub fn enabled_ids(&self) -> Vec<u64> {
+        self.configs.keys().copied().collect()
+    }
+
+    pub fn active(&self) -> Option<&ActiveDrag> {
+        self.active.as_ref()
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// True when there is nothing to pick up or hold.
+    pub fn is_idle(&self) -> bool {
+        self.configs.is_empty() && self.active.is_none()
+    }
+
+    /// Picks up `id`, whose translation is `origin`, under a pointer at
+    /// `pointer`. Returns false if a drag is already in progress or `id`
+    /// isn't draggable.
+    pub fn press(&mut self, id: u64, origin: (f32, f32, f32), pointer: (f32, f32)) -> bool {
+        if self.active.is_some() {
+            return false;
+        }
+        let Some(config) = self.config(id) else {
+            return false;
+        };
+        self.active = Some(ActiveDrag {
+            id,
+            origin,
+            offset: (origin.0 - pointer.0, origin.1 - pointer.1),
+            position: (origin.0, origin.1),
+            config,
+        });
+        true
+    }
+
+    /// Moves the held sprite with a pointer at `pointer`, keeping the pickup
+    /// offset and the constraint. Returns the sprite and its new position.
+    pub fn drag_to(&mut self, pointer: (f32, f32)) -> Option<(u64, (f32, f32))> {
+        let active = self.active.as_mut()?;
+        let position = (pointer.0 + active.offset.0, pointer.1 + active.offset.1);
+        active.position = match active.config.constrain_to {
+            Some(bounds) => bounds.clamp(position),
+            None => position,
+        };
+        Some((active.id, active.position))
+    }
+
+    /// Ends the drag with the pointer at `pointer`, or where it last moved
+    /// the sprite when `None` (a pointer outside the window). The event is
+    /// queued in `events` and returned; a drop target that is the held
+    /// sprite itself is ignored.
+    pub fn release(&mut self, pointer: Option<(f32, f32)>, drop_target: Option<DropTarget>) -> Option<DragEvent> {
+        if let Some(pointer) = pointer {
+            self.drag_to(pointer);
+        }
+        let active = self.active.take()?;
+        let origin = (active.origin.0, active.origin.1);
+        let snapped_back = active.config.snap_back;
+        let event = DragEvent {
+            id: active.id,
+            position: if snapped_back { origin } else { active.position },
+            dropped_at: active.position,
+            origin,
+            drop_target: drop_target.filter(|target| target.hit_region || target.id != active.id),
+            snapped_back,
+        };
+        self.events.push(event);
+        Some(event)
+    }
+
+    /// Ends the drag without an event, for a sprite that went away.
+    pub fn cancel(&mut self) -> Option<ActiveDrag> {
+        self.active.take()
+    }
+
+    pub fn drain_events(&mut self) -> Vec<DragEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+impl Default for DragGestures {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,267 @@
+//! Draw order module for ordering sprites within their layer.
+//!
+//! Sprites on one layer draw in creation order when their transform z ties.
+//! Once Ruby reorders a sprite, its layer becomes explicitly ordered: the
+//! layer keeps a back-to-front list of its sprites, and each list position
+//! maps to a z offset inside `DRAW_ORDER_BAND`, added on top of the layered
+//! z. Offsets are only reassigned for layers whose list changed, once per
+//! frame, and only the sprites whose offset actually moved are reported.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Z range the offsets of one explicitly ordered layer stay under.
+///
+/// Transform z plus this band must stay inside ±`SPRITE_LAYER_STEP / 2` for
+/// every higher layer to stay on top.
+pub const DRAW_ORDER_BAND: f32 = 1.0;
+
+/// Z distance between neighbouring positions of a layer with up to
+/// `DRAW_ORDER_BAND / DRAW_ORDER_STEP` sprites; larger layers share the band
+/// evenly.
+pub const DRAW_ORDER_STEP: f32 = 1.0 / 1024.0;
+
+/// How one sprite moves within its layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawOrderChange {
+    /// Above every other sprite of the layer.
+    Front,
+    /// Below every other sprite of the layer.
+    Back,
+    /// At this position counted from the back, clamped to the layer.
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Member {
+    layer: i32,
+    /// Creation sequence, the order of layers never reordered.
+    sequence: u64,
+}
+
+/// Layer membership and fine order of every synced sprite.
+#[derive(Debug, Clone, Default)]
+pub struct DrawOrder {
+    members: BTreeMap<u64, Member>,
+    next_sequence: u64,
+    /// Back-to-front lists of the explicitly ordered layers.
+    ordered: BTreeMap<i32, Vec<u64>>,
+    /// Offsets as last reported by `take_changes`.
+    applied: BTreeMap<u64, f32>,
+    /// Ordered layers whose offsets need reassigning.
+    dirty: BTreeSet<i32>,
+    /// Sprites that left an ordered layer and still carry an offset.
+    resets: BTreeSet<u64>,
+    /// Bumped on every change, so readers can skip unchanged snapshots.
+    revision: u64,
+}
+
+impl DrawOrder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tracks `id` on `layer`. A new sprite, or one moving from another
+    /// layer, goes on top of the layer. Returns false if it was already there.
+    pub fn insert(&mut self, id: u64, layer: i32) -> bool {
+        if self.members.get(&id).is_some_and(|member| member.layer == layer) {
+            return false;
+        }
+        self.detach(id);
+
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.members.insert(id, Member { layer, sequence });
+        if let Some(list) = self.ordered.get_mut(&layer) {
+            list.push(id);
+            self.dirty.insert(layer);
+        }
+        self.revision += 1;
+        true
+    }
+
+    /// Stops tracking `id`. Returns false if it was not tracked.
+    pub fn remove(&mut self, id: u64) -> bool {
+        if !self.members.contains_key(&id) {
+            return false;
+        }
+        self.detach(id);
+        self.resets.remove(&id);
+        self.applied.remove(&id);
+        self.revision += 1;
+        true
+    }
+
+    /// Moves `id` within its layer, making the layer explicitly ordered.
+    /// Returns false if `id` is not tracked.
+    pub fn apply(&mut self, id: u64, change: DrawOrderChange) -> bool {
+        let Some(layer) = self.layer(id) else {
+            return false;
+        };
+        let list = self.ordered_list(layer);
+        let Some(from) = list.iter().position(|member| *member == id) else {
+            return false;
+        };
+        let to = match change {
+            DrawOrderChange::Front => list.len() - 1,
+            DrawOrderChange::Back => 0,
+            DrawOrderChange::Index(index) => index.min(list.len() - 1),
+        };
+        if from != to {
+            let member = list.remove(from);
+            list.insert(to, member);
+            self.dirty.insert(layer);
+            self.revision += 1;
+        }
+        true
+    }
+
+    /// Layer `id` is tracked on.
+    pub fn layer(&self, id: u64) -> Option<i32> {
+        self.members.get(&id).map(|member| member.layer)
+    }
+
+    /// Position of `id` in its layer counted from the back, 0 drawing below
+    /// the rest.
+    pub fn index(&self, id: u64) -> Option<usize> {
+        let member = self.members.get(&id)?;
+        match self.ordered.get(&member.layer) {
+            Some(list) => list.iter().position(|other| *other == id),
+            None => Some(
+                self.members
+                    .values()
+                    .filter(|other| other.layer == member.layer && other.sequence < member.sequence)
+                    .count(),
+            ),
+        }
+    }
+
+    /// Sprites of `layer` from back to front.
+    pub fn layer_ids(&self, layer: i32) -> Vec<u64> {
+        if let Some(list) = self.ordered.get(&layer) {
+            return list.clone();
+        }
+        let mut members: Vec<_> = self
+            .members
+            .iter()
+            .filter(|(_, member)| member.layer == layer)
+            .map(|(id, member)| (member.sequence, *id))
+            .collect();
+        members.sort_unstable();
+        members.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// `(id, index)` of every tracked sprite, in ID order.
+    pub fn indices(&self) -> Vec<(u64, usize)> {
+        let layers: BTreeSet<i32> = self.members.values().map(|member| member.layer).collect();
+        let mut indices: Vec<_> = layers
+            .into_iter()
+            .flat_map(|layer| {
+                self.layer_ids(layer)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, id)| (id, index))
+            })
+            .collect();
+        indices.sort_unstable();
+        indices
+    }
+
+    /// Offset last reported for `id` by `take_changes`; 0 until then.
+    pub fn applied_offset(&self, id: u64) -> f32 {
+        self.applied.get(&id).copied().unwrap_or(0.0)
+    }
+
+    /// Offset of position `index` in a layer of `len` sprites.
+    pub fn offset_at(index: usize, len: usize) -> f32 {
+        let step = DRAW_ORDER_STEP.min(DRAW_ORDER_BAND / len.max(1) as f32);
+        index as f32 * step
+    }
+
+    /// Reassigns the offsets of every layer changed since the last call and
+    /// returns `(id, delta)` for each sprite whose offset moved, so callers
+    /// can shift the z they already wrote.
+    pub fn take_changes(&mut self) -> Vec<(u64, f32)> {
+        let mut changes = Vec::new();
+        for id in std::mem::take(&mut self.resets) {
+            if let Some(offset) = self.applied.remove(&id)
+                && offset != 0.0
+            {
+                changes.push((id, -offset));
+            }
+        }
+        for layer in std::mem::take(&mut self.dirty) {
+            let Some(list) = self.ordered.get(&layer) else {
+                continue;
+            };
+            for (index, id) in list.iter().enumerate() {
+                let offset = Self::offset_at(index, list.len());
+                let previous = self.applied.insert(*id, offset).unwrap_or(0.0);
+                if offset != previous {
+                    changes.push((*id, offset - previous));
+                }
+            }
+        }
+        changes
+    }
+
+    /// True while offsets are waiting for `take_changes`.
+    pub fn has_changes(&self) -> bool {
+        !self.dirty.is_empty() || !self.resets.is_empty()
+    }
+
+    /// Whether `layer` was ever reordered and still has sprites.
+    pub fn is_ordered(&self, layer: i32) -> bool {
+        self.ordered.contains_key(&layer)
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.members.clear();
+        self.ordered.clear();
+        self.applied.clear();
+        self.dirty.clear();
+        self.resets.clear();
+        self.revision += 1;
+    }
+
+    /// The list of `layer`, built from creation order the first time.
+    fn ordered_list(&mut self, layer: i32) -> &mut Vec<u64> {
+        if !self.ordered.contains_key(&layer) {
+            let list = self.layer_ids(layer);
+            self.ordered.insert(layer, list);
+            self.dirty.insert(layer);
+        }
+        self.ordered.get_mut(&layer).expect("ordered layer was just inserted")
+    }
+
+    /// Takes `id` off its layer's list, leaving its offset to be reset.
+    fn detach(&mut self, id: u64) {
+        let Some(member) = self.members.remove(&id) else {
+            return;
+        };
+        let Some(list) = self.ordered.get_mut(&member.layer) else {
+            return;
+        };
+        list.retain(|other| *other != id);
+        if list.is_empty() {
+            self.ordered.remove(&member.layer);
+            self.dirty.remove(&member.layer);
+        } else {
+            self.dirty.insert(member.layer);
+        }
+        if self.applied.contains_key(&id) {
+            self.resets.insert(id);
+        }
+    }
+}
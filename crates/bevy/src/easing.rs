@@ -0,0 +1,371 @@
+//! Easing module: the standard easing curves, shared by every bridge feature
+//! that takes an `ease:` name.
+//!
+//! Every curve is a pure `fn(f32) -> f32` that clamps `t` to `0.0..=1.0` and
+//! returns exactly 0.0 and 1.0 at the ends. Each family is written once as
+//! its `_in` curve; `_out` mirrors it (`1 - in(1 - t)`) and `_in_out` runs
+//! it over each half. Back and elastic curves leave `0.0..=1.0` in between.
+
+use std::f32::consts::{FRAC_PI_2, TAU};
+use thiserror::Error;
+
+/// How far `back_*` curves pull back before moving on (Penner's `s`),
+/// which overshoots by about 10%.
+pub const DEFAULT_BACK_OVERSHOOT: f32 = 1.70158;
+/// Length of one oscillation of the `elastic_*` curves, as a fraction of
+/// the curve.
+pub const DEFAULT_ELASTIC_PERIOD: f32 = 0.3;
+
+/// `back_in_out` scales the overshoot by this so each half overshoots by
+/// about as much as `back_in` does over the whole curve.
+const BACK_IN_OUT_OVERSHOOT_SCALE: f32 = 1.525;
+/// `elastic_in_out` stretches the period by this, as each half is shorter.
+const ELASTIC_IN_OUT_PERIOD_SCALE: f32 = 1.5;
+
+/// Runs `curve` on `t` clamped to `0.0..=1.0`, pinning the ends exactly.
+fn ends_pinned(t: f32, curve: impl FnOnce(f32) -> f32) -> f32 {
+    if t.is_nan() || t <= 0.0 {
+        0.0
+    } else if t >= 1.0 {
+        1.0
+    } else {
+        curve(t)
+    }
+}
+
+fn mirrored(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    ends_pinned(t, |t| 1.0 - ease_in(1.0 - t))
+}
+
+fn halved(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    ends_pinned(t, |t| {
+        if t < 0.5 {
+            ease_in(2.0 * t) / 2.0
+        } else {
+            1.0 - ease_in(2.0 - 2.0 * t) / 2.0
+        }
+    })
+}
+
+pub fn linear(t: f32) -> f32 {
+    ends_pinned(t, |t| t)
+}
+
+pub fn quad_in(t: f32) -> f32 {
+    ends_pinned(t, |t| t * t)
+}
+
+pub fn quad_out(t: f32) -> f32 {
+    mirrored(t, quad_in)
+}
+
+pub fn quad_in_out(t: f32) -> f32 {
+    halved(t, quad_in)
+}
+
+pub fn cubic_in(t: f32) -> f32 {
+    ends_pinned(t, |t| t * t * t)
+}
+
+pub fn cubic_out(t: f32) -> f32 {
+    mirrored(t, cubic_in)
+}
+
+pub fn cubic_in_out(t: f32) -> f32 {
+    halved(t, cubic_in)
+}
+
+pub fn quart_in(t: f32) -> f32 {
+    ends_pinned(t, |t| t.powi(4))
+}
+
+pub fn quart_out(t: f32) -> f32 {
+    mirrored(t, quart_in)
+}
+
+pub fn quart_in_out(t: f32) -> f32 {
+    halved(t, quart_in)
+}
+
+pub fn quint_in(t: f32) -> f32 {
+    ends_pinned(t, |t| t.powi(5))
+}
+
+pub fn quint_out(t: f32) -> f32 {
+    mirrored(t, quint_in)
+}
+
+pub fn quint_in_out(t: f32) -> f32 {
+    halved(t, quint_in)
+}
+
+pub fn sine_in(t: f32) -> f32 {
+    ends_pinned(t, |t| 1.0 - (t * FRAC_PI_2).cos())
+}
+
+pub fn sine_out(t: f32) -> f32 {
+    mirrored(t, sine_in)
+}
+
+pub fn sine_in_out(t: f32) -> f32 {
+    halved(t, sine_in)
+}
+
+pub fn expo_in(t: f32) -> f32 {
+    ends_pinned(t, |t| 2.0_f32.powf(10.0 * t - 10.0))
+}
+
+pub fn expo_out(t: f32) -> f32 {
+    mirrored(t, expo_in)
+}
+
+pub fn expo_in_out(t: f32) -> f32 {
+    halved(t, expo_in)
+}
+
+pub fn circ_in(t: f32) -> f32 {
+    ends_pinned(t, |t| 1.0 - (1.0 - t * t).max(0.0).sqrt())
+}
+
+pub fn circ_out(t: f32) -> f32 {
+    mirrored(t, circ_in)
+}
+
+pub fn circ_in_out(t: f32) -> f32 {
+    halved(t, circ_in)
+}
+
+/// Pulls back below 0.0 before accelerating toward 1.0; a larger
+/// `overshoot` pulls back further, and 0.0 is `cubic_in`.
+pub fn back_in_with(t: f32, overshoot: f32) -> f32 {
+    let overshoot = if overshoot.is_finite() { overshoot } else { DEFAULT_BACK_OVERSHOOT };
+    // t^3 + s * t^2 * (t - 1): exact at both ends for any `s`.
+    ends_pinned(t, |t| t * t * t + overshoot * t * t * (t - 1.0))
+}
+
+pub fn back_out_with(t: f32, overshoot: f32) -> f32 {
+    mirrored(t, |t| back_in_with(t, overshoot))
+}
+
+pub fn back_in_out_with(t: f32, overshoot: f32) -> f32 {
+    halved(t, |t| back_in_with(t, overshoot * BACK_IN_OUT_OVERSHOOT_SCALE))
+}
+
+pub fn back_in(t: f32) -> f32 {
+    back_in_with(t, DEFAULT_BACK_OVERSHOOT)
+}
+
+pub fn back_out(t: f32) -> f32 {
+    back_out_with(t, DEFAULT_BACK_OVERSHOOT)
+}
+
+pub fn back_in_out(t: f32) -> f32 {
+    back_in_out_with(t, DEFAULT_BACK_OVERSHOOT)
+}
+
+/// A sine wave growing exponentially into 1.0, one oscillation every
+/// `period` (a fraction of the curve; non-positive periods use the default).
+pub fn elastic_in_with(t: f32, period: f32) -> f32 {
+    let period = if period.is_finite() && period > 0.0 { period } else { DEFAULT_ELASTIC_PERIOD };
+    // The phase puts the peak of the last oscillation exactly at t = 1.
+    ends_pinned(t, |t| {
+        -(2.0_f32.powf(10.0 * t - 10.0)) * ((t - 1.0 - period / 4.0) * TAU / period).sin()
+    })
+}
+
+pub fn elastic_out_with(t: f32, period: f32) -> f32 {
+    mirrored(t, |t| elastic_in_with(t, period))
+}
+
+pub fn elastic_in_out_with(t: f32, period: f32) -> f32 {
+    halved(t, |t| elastic_in_with(t, period * ELASTIC_IN_OUT_PERIOD_SCALE))
+}
+
+pub fn elastic_in(t: f32) -> f32 {
+    elastic_in_with(t, DEFAULT_ELASTIC_PERIOD)
+}
+
+pub fn elastic_out(t: f32) -> f32 {
+    elastic_out_with(t, DEFAULT_ELASTIC_PERIOD)
+}
+
+pub fn elastic_in_out(t: f32) -> f32 {
+    elastic_in_out_with(t, DEFAULT_ELASTIC_PERIOD)
+}
+
+/// Four bounces of decreasing height, landing on 1.0.
+pub fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    ends_pinned(t, |t| {
+        if t < 1.0 / D {
+            N * t * t
+        } else if t < 2.0 / D {
+            let t = t - 1.5 / D;
+            N * t * t + 0.75
+        } else if t < 2.5 / D {
+            let t = t - 2.25 / D;
+            N * t * t + 0.9375
+        } else {
+            let t = t - 2.625 / D;
+            N * t * t + 0.984375
+        }
+    })
+}
+
+pub fn bounce_in(t: f32) -> f32 {
+    mirrored(t, bounce_out)
+}
+
+pub fn bounce_in_out(t: f32) -> f32 {
+    halved(t, bounce_in)
+}
+
+/// Defines `Ease` with its names and functions from one table, so the
+/// name-to-curve mapping lives in a single place.
+macro_rules! eases {
+    ($($variant:ident => $name:literal, $function:ident;)*) => {
+        /// A named easing curve.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Ease {
+            $($variant,)*
+        }
+
+        impl Ease {
+            /// Every curve, in declaration order.
+            pub const ALL: &[Ease] = &[$(Ease::$variant,)*];
+            /// Names of `ALL`, in the same order.
+            pub const NAMES: &[&str] = &[$($name,)*];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Ease::$variant => $name,)*
+                }
+            }
+
+            /// The curve as a plain function, with default parameters.
+            pub fn function(self) -> fn(f32) -> f32 {
+                match self {
+                    $(Ease::$variant => $function,)*
+                }
+            }
+        }
+    };
+}
+
+eases! {
+    Linear => "linear", linear;
+    QuadIn => "quad_in", quad_in;
+    QuadOut => "quad_out", quad_out;
+    QuadInOut => "quad_in_out", quad_in_out;
+    CubicIn => "cubic_in", cubic_in;
+    CubicOut => "cubic_out", cubic_out;
+    CubicInOut => "cubic_in_out", cubic_in_out;
+    QuartIn => "quart_in", quart_in;
+    QuartOut => "quart_out", quart_out;
+    QuartInOut => "quart_in_out", quart_in_out;
+    QuintIn => "quint_in", quint_in;
+    QuintOut => "quint_out", quint_out;
+    QuintInOut => "quint_in_out", quint_in_out;
+    SineIn => "sine_in", sine_in;
+    SineOut => "sine_out", sine_out;
+    SineInOut => "sine_in_out", sine_in_out;
+    ExpoIn => "expo_in", expo_in;
+    ExpoOut => "expo_out", expo_out;
+    ExpoInOut => "expo_in_out", expo_in_out;
+    CircIn => "circ_in", circ_in;
+    CircOut => "circ_out", circ_out;
+    CircInOut => "circ_in_out", circ_in_out;
+    BackIn => "back_in", back_in;
+    BackOut => "back_out", back_out;
+    BackInOut => "back_in_out", back_in_out;
+    ElasticIn => "elastic_in", elastic_in;
+    ElasticOut => "elastic_out", elastic_out;
+    ElasticInOut => "elastic_in_out", elastic_in_out;
+    BounceIn => "bounce_in", bounce_in;
+    BounceOut => "bounce_out", bounce_out;
+    BounceInOut => "bounce_in_out", bounce_in_out;
+}
+
+/// Parameters of the back and elastic curves; other curves ignore them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EaseParams {
+    pub overshoot: f32,
+    pub period: f32,
+}
+
+impl Default for EaseParams {
+    fn default() -> Self {
+        Self {
+            overshoot: DEFAULT_BACK_OVERSHOOT,
+            period: DEFAULT_ELASTIC_PERIOD,
+        }
+    }
+}
+
+/// An easing name that matches no curve.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("unknown easing: {name} (expected one of {})", Ease::NAMES.join(", "))]
+pub struct UnknownEase {
+    pub name: String,
+}
+
+impl Ease {
+    /// Looks up a curve by its name. The names used before this module,
+    /// `ease_in_quad` style and the cubic shorthands `ease_in`, `ease_out`
+    /// and `ease_in_out`, are accepted too.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = match name {
+            "ease_in" => "cubic_in",
+            "ease_out" => "cubic_out",
+            "ease_in_out" => "cubic_in_out",
+            _ => name,
+        };
+        if let Some(ease) = Self::lookup(name) {
+            return Some(ease);
+        }
+        let rest = name.strip_prefix("ease_")?;
+        ["in_out", "in", "out"].iter().find_map(|direction| {
+            let family = rest.strip_prefix(direction)?.strip_prefix('_')?;
+            Self::lookup(&format!("{}_{}", family, direction))
+        })
+    }
+
+    fn lookup(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .position(|candidate| *candidate == name)
+            .map(|index| Self::ALL[index])
+    }
+
+    pub fn parse(name: &str) -> Result<Self, UnknownEase> {
+        Self::from_name(name).ok_or_else(|| UnknownEase {
+            name: name.to_string(),
+        })
+    }
+
+    /// Maps linear progress `t` to eased progress with default parameters.
+    pub fn apply(self, t: f32) -> f32 {
+        (self.function())(t)
+    }
+
+    pub fn apply_with(self, t: f32, params: EaseParams) -> f32 {
+        match self {
+            Ease::BackIn => back_in_with(t, params.overshoot),
+            Ease::BackOut => back_out_with(t, params.overshoot),
+            Ease::BackInOut => back_in_out_with(t, params.overshoot),
+            Ease::ElasticIn => elastic_in_with(t, params.period),
+            Ease::ElasticOut => elastic_out_with(t, params.period),
+            Ease::ElasticInOut => elastic_in_out_with(t, params.period),
+            _ => self.apply(t),
+        }
+    }
+
+    pub fn is_back(self) -> bool {
+        matches!(self, Ease::BackIn | Ease::BackOut | Ease::BackInOut)
+    }
+
+    pub fn is_elastic(self) -> bool {
+        matches!(self, Ease::ElasticIn | Ease::ElasticOut | Ease::ElasticInOut)
+    }
+}
@@ -0,0 +1,285 @@
+//! Floating text module for fire-and-forget combat numbers.
+//!
+//! Each floating text owns a synced text under an ID with
+//! `FLOATING_TEXT_ID_FLAG` set, rises with an ease-out and fades out over
+//! its duration, and is removed at the end without Ruby tracking it. Texts
+//! spawned near the same spot in quick succession are stacked upward so
+//! they don't overlap, and the oldest are dropped once the cap is reached.
+
+use std::collections::VecDeque;
+
+use crate::easing::{quad_in, quad_out};
+use crate::text_renderer::{TextData, TextTransformData};
+
+/// Set on text IDs of floating texts so they never collide with Ruby entity
+/// IDs or bound-world sprite IDs.
+pub const FLOATING_TEXT_ID_FLAG: u64 = 1 << 61;
+
+/// Floating texts alive at once before the oldest are removed.
+pub const DEFAULT_FLOATING_TEXT_CAP: usize = 64;
+
+/// A text younger than this stacks the next one spawned near it.
+pub const FLOATING_TEXT_STACK_WINDOW_SECS: f32 = 0.3;
+
+/// Spawn points closer than this on both axes count as the same spot.
+pub const FLOATING_TEXT_STACK_RADIUS: f32 = 8.0;
+
+/// Floating texts are drawn above sprites at the default depth.
+const FLOATING_TEXT_Z: f32 = 100.0;
+
+/// One `floating_text` call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatingTextRequest {
+    pub x: f32,
+    pub y: f32,
+    pub content: String,
+    pub color: [f32; 4],
+    /// Distance risen over the whole duration.
+    pub rise: f32,
+    pub duration: f32,
+    pub font_size: f32,
+}
+
+impl Default for FloatingTextRequest {
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            content: String::new(),
+            color: [1.0, 1.0, 1.0, 1.0],
+            rise: 40.0,
+            duration: 0.8,
+            font_size: 18.0,
+        }
+    }
+}
+
+/// Pending floating text operation.
+#[derive(Debug, Clone)]
+pub enum FloatingTextOperation {
+    Spawn(FloatingTextRequest),
+    SetCap(usize),
+    Clear,
+}
+
+/// Where a live floating text is drawn this frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatingTextFrame {
+    pub ruby_entity_id: u64,
+    pub x: f32,
+    pub y: f32,
+    pub alpha: f32,
+}
+
+#[derive(Debug, Clone)]
+struct ActiveFloatingText {
+    ruby_entity_id: u64,
+    request: FloatingTextRequest,
+    /// Stacking slot; each slot lifts the text by one font size.
+    slot: usize,
+    age: f32,
+}
+
+impl ActiveFloatingText {
+    fn progress(&self) -> f32 {
+        if self.request.duration > 0.0 {
+            (self.age / self.request.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    fn frame(&self) -> FloatingTextFrame {
+        let t = self.progress();
+        let stack_offset = self.slot as f32 * self.request.font_size;
+        FloatingTextFrame {
+            ruby_entity_id: self.ruby_entity_id,
+            x: self.request.x,
+            y: self.request.y + stack_offset + self.request.rise * quad_out(t),
+            alpha: self.request.color[3] * (1.0 - quad_in(t)),
+        }
+    }
+
+    fn stacks_with(&self, request: &FloatingTextRequest) -> bool {
+        self.age < FLOATING_TEXT_STACK_WINDOW_SECS
+            && (self.request.x - request.x).abs() <= FLOATING_TEXT_STACK_RADIUS
+            && (self.request.y - request.y).abs() <= FLOATING_TEXT_STACK_RADIUS
+    }
+}
+
+/// Live floating texts in spawn order.
+pub struct FloatingTexts {
+    active: VecDeque<ActiveFloatingText>,
+    cap: usize,
+    next_id: u64,
+    /// Pending operations to apply on next update.
+    pub pending_operations: Vec<FloatingTextOperation>,
+    /// IDs of texts that expired or were evicted, until drained.
+    pub removed: Vec<u64>,
+}
+
+impl FloatingTexts {
+    pub fn new() -> Self {
+        Self {
+            active: VecDeque::new(),
+            cap: DEFAULT_FLOATING_TEXT_CAP,
+            next_id: 0,
+            pending_operations: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    /// Queues a floating text (standalone, no World needed).
+    pub fn spawn_standalone(&mut self, request: FloatingTextRequest) {
+        self.pending_operations
+            .push(FloatingTextOperation::Spawn(request));
+    }
+
+    /// Queues changing the cap (standalone, no World needed).
+    pub fn set_cap_standalone(&mut self, cap: usize) {
+        self.pending_operations.push(FloatingTextOperation::SetCap(cap));
+    }
+
+    /// Queues removing every floating text (standalone, no World needed).
+    pub fn clear_standalone(&mut self) {
+        self.pending_operations.push(FloatingTextOperation::Clear);
+    }
+
+    /// Applies queued spawn/cap/clear operations.
+    pub fn apply_operations(&mut self) {
+        let ops: Vec<_> = self.pending_operations.drain(..).collect();
+        for op in ops {
+            match op {
+                FloatingTextOperation::Spawn(request) => {
+                    self.spawn(request);
+                }
+                FloatingTextOperation::SetCap(cap) => self.set_cap(cap),
+                FloatingTextOperation::Clear => self.clear(),
+            }
+        }
+    }
+
+    /// Starts a floating text and returns its ID, evicting the oldest texts
+    /// past the cap. It takes the lowest stacking slot not held by a recent
+    /// text at the same spot.
+    pub fn spawn(&mut self, request: FloatingTextRequest) -> u64 {
+        let taken: Vec<usize> = self
+            .active
+            .iter()
+            .filter(|text| text.stacks_with(&request))
+            .map(|text| text.slot)
+            .collect();
+        let slot = (0..).find(|slot| !taken.contains(slot)).unwrap_or(0);
+
+        self.next_id += 1;
+        let ruby_entity_id = FLOATING_TEXT_ID_FLAG | self.next_id;
+        self.active.push_back(ActiveFloatingText {
+            ruby_entity_id,
+            request,
+            slot,
+            age: 0.0,
+        });
+        self.evict_over_cap();
+        ruby_entity_id
+    }
+
+    pub fn cap(&self) -> usize {
+        self.cap
+    }
+
+    /// Sets the cap, evicting the oldest texts if more are alive.
+    pub fn set_cap(&mut self, cap: usize) {
+        self.cap = cap;
+        self.evict_over_cap();
+    }
+
+    fn evict_over_cap(&mut self) {
+        while self.active.len() > self.cap {
+            if let Some(oldest) = self.active.pop_front() {
+                self.removed.push(oldest.ruby_entity_id);
+            }
+        }
+    }
+
+    /// Ages every text by `delta_secs`, moves the expired ones to `removed`
+    /// and returns where the rest are drawn.
+    pub fn advance(&mut self, delta_secs: f32) -> Vec<FloatingTextFrame> {
+        let delta_secs = delta_secs.max(0.0);
+        for text in self.active.iter_mut() {
+            text.age += delta_secs;
+        }
+
+        let removed = &mut self.removed;
+        self.active.retain(|text| {
+            let alive = text.age < text.request.duration;
+            if !alive {
+                removed.push(text.ruby_entity_id);
+            }
+            alive
+        });
+
+        self.active.iter().map(ActiveFloatingText::frame).collect()
+    }
+
+    /// Text and transform to sync for a live floating text.
+    pub fn text_sync_data(&self, frame: &FloatingTextFrame) -> Option<(TextData, TextTransformData)> {
+        let text = self.find(frame.ruby_entity_id)?;
+        let [color_r, color_g, color_b, _] = text.request.color;
+        let text_data = TextData {
+            content: text.request.content.clone(),
+            font_size: text.request.font_size,
+            color_r,
+            color_g,
+            color_b,
+            color_a: frame.alpha,
+            ..Default::default()
+        };
+        let transform_data = TextTransformData {
+            translation_x: frame.x,
+            translation_y: frame.y,
+            translation_z: FLOATING_TEXT_Z,
+            ..Default::default()
+        };
+        Some((text_data, transform_data))
+    }
+
+    fn find(&self, ruby_entity_id: u64) -> Option<&ActiveFloatingText> {
+        self.active
+            .iter()
+            .find(|text| text.ruby_entity_id == ruby_entity_id)
+    }
+
+    pub fn is_active(&self, ruby_entity_id: u64) -> bool {
+        self.find(ruby_entity_id).is_some()
+    }
+
+    /// Stacking slot of a live text; 0 is its own spawn height.
+    pub fn slot(&self, ruby_entity_id: u64) -> Option<usize> {
+        self.find(ruby_entity_id).map(|text| text.slot)
+    }
+
+    /// Takes the IDs removed since the last call.
+    pub fn drain_removed(&mut self) -> Vec<u64> {
+        std::mem::take(&mut self.removed)
+    }
+
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Removes every live text; their IDs go to `removed`.
+    pub fn clear(&mut self) {
+        self.removed
+            .extend(self.active.drain(..).map(|text| text.ruby_entity_id));
+    }
+}
+
+impl Default for FloatingTexts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
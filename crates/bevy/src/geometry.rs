@@ -0,0 +1,269 @@
+//! Geometry helpers for collision shapes derived from synced meshes.
+//!
+//! Polygons are simple rings of `[x, y]` points in either winding. Holes are
+//! not supported.
+
+use crate::mesh_renderer::{MeshData, ShapeType};
+use thiserror::Error;
+
+/// Segments used to outline circles and ellipses.
+pub const OUTLINE_SEGMENTS: usize = 32;
+
+/// Why a polygon could not be triangulated.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TriangulationError {
+    #[error("polygon needs at least 3 points, got {0}")]
+    TooFewPoints(usize),
+
+    #[error("polygon point {0} is not finite")]
+    NonFinite(usize),
+
+    #[error("polygon points {0} and {1} are the same point")]
+    DuplicatePoint(usize, usize),
+
+    #[error("polygon is degenerate: its points are collinear and enclose no area")]
+    Degenerate,
+
+    #[error("polygon is self-intersecting: edges {0} and {1} cross or overlap")]
+    SelfIntersecting(usize, usize),
+}
+
+/// Triangulates a simple polygon by ear clipping.
+///
+/// Returns triangles as indices into `points`, wound counter-clockwise.
+/// Points lying on a straight edge are skipped, so no zero-area triangles
+/// are produced.
+pub fn triangulate(points: &[[f32; 2]]) -> Result<Vec<[usize; 3]>, TriangulationError> {
+    let n = points.len();
+    if n < 3 {
+        return Err(TriangulationError::TooFewPoints(n));
+    }
+    if let Some(index) = points
+        .iter()
+        .position(|[x, y]| !x.is_finite() || !y.is_finite())
+    {
+        return Err(TriangulationError::NonFinite(index));
+    }
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if points[i] == points[j] {
+                return Err(TriangulationError::DuplicatePoint(i, j));
+            }
+        }
+    }
+
+    let pts: Vec<[f64; 2]> = points
+        .iter()
+        .map(|[x, y]| [*x as f64, *y as f64])
+        .collect();
+    let epsilon = tolerance(&pts);
+
+    if (2..n).all(|i| orientation(pts[0], pts[1], pts[i], epsilon) == 0) {
+        return Err(TriangulationError::Degenerate);
+    }
+    check_simple(&pts, epsilon)?;
+    let area = signed_area(&pts);
+    if area.abs() <= epsilon {
+        return Err(TriangulationError::Degenerate);
+    }
+
+    let mut ordered: Vec<usize> = (0..n).collect();
+    if area < 0.0 {
+        ordered.reverse();
+    }
+    // Points on a straight edge are the corner of no triangle.
+    let mut ring: Vec<usize> = (0..n)
+        .filter(|&i| {
+            let prev = ordered[(i + n - 1) % n];
+            let next = ordered[(i + 1) % n];
+            cross(pts[prev], pts[ordered[i]], pts[next]).abs() > epsilon
+        })
+        .map(|i| ordered[i])
+        .collect();
+
+    let mut triangles = Vec::with_capacity(ring.len().saturating_sub(2));
+    while ring.len() > 3 {
+        let count = ring.len();
+        let ear = (0..count).find(|&i| {
+            let prev = ring[(i + count - 1) % count];
+            let cur = ring[i];
+            let next = ring[(i + 1) % count];
+            is_ear(&pts, &ring, prev, cur, next, epsilon)
+        });
+        let Some(i) = ear else {
+            return Err(TriangulationError::Degenerate);
+        };
+        let prev = ring[(i + count - 1) % count];
+        let next = ring[(i + 1) % count];
+        triangles.push([prev, ring[i], next]);
+        ring.remove(i);
+    }
+    if cross(pts[ring[0]], pts[ring[1]], pts[ring[2]]) > epsilon {
+        triangles.push([ring[0], ring[1], ring[2]]);
+    }
+    Ok(triangles)
+}
+
+/// Outline of a synced mesh in local space, counter-clockwise.
+///
+/// Circles and ellipses are approximated with `OUTLINE_SEGMENTS` points.
+/// Lines and polygons with fewer than 3 sides have no outline.
+pub fn shape_outline(mesh_data: &MeshData) -> Option<Vec<[f32; 2]>> {
+    match mesh_data.shape_type {
+        ShapeType::Rectangle => {
+            let (hw, hh) = (mesh_data.width / 2.0, mesh_data.height / 2.0);
+            Some(vec![[-hw, -hh], [hw, -hh], [hw, hh], [-hw, hh]])
+        }
+        ShapeType::Circle => Some(ellipse_outline(mesh_data.radius, mesh_data.radius)),
+        ShapeType::Ellipse => Some(ellipse_outline(
+            mesh_data.width / 2.0,
+            mesh_data.height / 2.0,
+        )),
+        ShapeType::RegularPolygon => regular_polygon_outline(mesh_data.sides as usize, mesh_data.radius),
+        ShapeType::Triangle => regular_polygon_outline(3, mesh_data.radius),
+        ShapeType::Line => None,
+    }
+}
+
+/// Triangles covering a synced mesh, as vertex coordinates in local space.
+///
+/// Empty for lines and for shapes too small to enclose any area.
+pub fn shape_triangles(mesh_data: &MeshData) -> Vec<[[f32; 2]; 3]> {
+    let Some(outline) = shape_outline(mesh_data) else {
+        return Vec::new();
+    };
+    triangulate(&outline)
+        .map(|triangles| {
+            triangles
+                .into_iter()
+                .map(|[a, b, c]| [outline[a], outline[b], outline[c]])
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn ellipse_outline(radius_x: f32, radius_y: f32) -> Vec<[f32; 2]> {
+    let step = std::f32::consts::TAU / OUTLINE_SEGMENTS as f32;
+    (0..OUTLINE_SEGMENTS)
+        .map(|i| {
+            let angle = i as f32 * step;
+            [radius_x * angle.cos(), radius_y * angle.sin()]
+        })
+        .collect()
+}
+
+/// Vertices placed the way the renderer places them, with a flat bottom edge.
+fn regular_polygon_outline(sides: usize, radius: f32) -> Option<Vec<[f32; 2]>> {
+    if sides < 3 {
+        return None;
+    }
+    let n = sides as f32;
+    let internal = (n - 2.0) * std::f32::consts::PI / n;
+    let offset = -internal / 2.0;
+    let step = std::f32::consts::TAU / n;
+    Some(
+        (0..sides)
+            .map(|i| {
+                let angle = (i as f32).mul_add(step, offset);
+                [radius * angle.cos(), radius * angle.sin()]
+            })
+            .collect(),
+    )
+}
+
+/// Area tolerance scaled to the polygon's extent.
+fn tolerance(pts: &[[f64; 2]]) -> f64 {
+    let (mut min, mut max) = (pts[0], pts[0]);
+    for [x, y] in pts {
+        min = [min[0].min(*x), min[1].min(*y)];
+        max = [max[0].max(*x), max[1].max(*y)];
+    }
+    let extent = (max[0] - min[0]).max(max[1] - min[1]);
+    extent * extent * 1e-9
+}
+
+fn signed_area(pts: &[[f64; 2]]) -> f64 {
+    let n = pts.len();
+    (0..n)
+        .map(|i| {
+            let [x0, y0] = pts[i];
+            let [x1, y1] = pts[(i + 1) % n];
+            x0 * y1 - x1 * y0
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Twice the signed area of `abc`; positive when counter-clockwise.
+fn cross(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+fn orientation(a: [f64; 2], b: [f64; 2], c: [f64; 2], epsilon: f64) -> i8 {
+    let value = cross(a, b, c);
+    if value > epsilon {
+        1
+    } else if value < -epsilon {
+        -1
+    } else {
+        0
+    }
+}
+
+/// True if `p`, known to be collinear with `a` and `b`, lies within their bounds.
+fn within_bounds(a: [f64; 2], b: [f64; 2], p: [f64; 2]) -> bool {
+    p[0] >= a[0].min(b[0]) && p[0] <= a[0].max(b[0]) && p[1] >= a[1].min(b[1]) && p[1] <= a[1].max(b[1])
+}
+
+fn segments_touch(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2], epsilon: f64) -> bool {
+    let o1 = orientation(a, b, c, epsilon);
+    let o2 = orientation(a, b, d, epsilon);
+    let o3 = orientation(c, d, a, epsilon);
+    let o4 = orientation(c, d, b, epsilon);
+    if o1 != o2 && o3 != o4 && o1 != 0 && o2 != 0 && o3 != 0 && o4 != 0 {
+        return true;
+    }
+    (o1 == 0 && within_bounds(a, b, c))
+        || (o2 == 0 && within_bounds(a, b, d))
+        || (o3 == 0 && within_bounds(c, d, a))
+        || (o4 == 0 && within_bounds(c, d, b))
+}
+
+/// Rejects rings whose edges cross, touch or fold back onto each other.
+fn check_simple(pts: &[[f64; 2]], epsilon: f64) -> Result<(), TriangulationError> {
+    let n = pts.len();
+    for i in 0..n {
+        let (a, b) = (pts[i], pts[(i + 1) % n]);
+
+        // An edge that doubles back along its neighbour overlaps it.
+        let c = pts[(i + 2) % n];
+        let folds_back = (b[0] - a[0]) * (c[0] - b[0]) + (b[1] - a[1]) * (c[1] - b[1]) < 0.0;
+        if orientation(a, b, c, epsilon) == 0 && folds_back {
+            return Err(TriangulationError::SelfIntersecting(i, (i + 1) % n));
+        }
+
+        for j in (i + 2)..n {
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            if segments_touch(a, b, pts[j], pts[(j + 1) % n], epsilon) {
+                return Err(TriangulationError::SelfIntersecting(i, j));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// True if `cur` is a convex corner whose triangle holds no other ring vertex.
+fn is_ear(pts: &[[f64; 2]], ring: &[usize], prev: usize, cur: usize, next: usize, epsilon: f64) -> bool {
+    let (a, b, c) = (pts[prev], pts[cur], pts[next]);
+    if cross(a, b, c) <= epsilon {
+        return false;
+    }
+    ring.iter()
+        .filter(|&&index| index != prev && index != cur && index != next)
+        .all(|&index| {
+            let p = pts[index];
+            cross(a, b, p) < -epsilon || cross(b, c, p) < -epsilon || cross(c, a, p) < -epsilon
+        })
+}
@@ -0,0 +1,193 @@
+//! Group throttle module for updating sprite groups every Nth frame.
+//!
+//! A group with an update divisor of N is due on every Nth frame, counted
+//! from the frame the divisor was set. Sprite syncs for its entities queued
+//! on the frames in between are held back, and only the most recent one per
+//! entity is applied once the group is due again. Removals and clears go
+//! through right away and drop whatever was held for the entities they hit.
+
+use std::collections::BTreeMap;
+
+use crate::sprite_renderer::SpriteOperation;
+
+/// Pending group throttle operation.
+#[derive(Debug, Clone)]
+pub enum GroupThrottleOperation {
+    /// A divisor of 0 or 1 stops throttling the group.
+    SetDivisor { group: String, divisor: u32 },
+    Clear,
+}
+
+/// Divisor of a throttled group and the frame its schedule counts from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct GroupRate {
+    divisor: u32,
+    anchor: u32,
+}
+
+/// Which groups are due on which frame.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupSchedule {
+    rates: BTreeMap<String, GroupRate>,
+}
+
+impl GroupSchedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Throttles `group` to every `divisor`th frame starting at `frame`,
+    /// which is itself due. A divisor of 0 or 1 stops throttling it.
+    pub fn set_divisor(&mut self, group: &str, divisor: u32, frame: u32) {
+        if divisor <= 1 {
+            self.rates.remove(group);
+        } else {
+            self.rates.insert(
+                group.to_string(),
+                GroupRate {
+                    divisor,
+                    anchor: frame,
+                },
+            );
+        }
+    }
+
+    /// The group's divisor; 1 for groups that are not throttled.
+    pub fn divisor(&self, group: &str) -> u32 {
+        self.rates.get(group).map_or(1, |rate| rate.divisor)
+    }
+
+    /// True when updates for `group` go through on `frame`. Ungrouped
+    /// entities and groups that are not throttled are always due.
+    pub fn is_due(&self, group: Option<&str>, frame: u32) -> bool {
+        let Some(rate) = group.and_then(|group| self.rates.get(group)) else {
+            return true;
+        };
+        frame.wrapping_sub(rate.anchor).is_multiple_of(rate.divisor)
+    }
+
+    /// Throttled groups due on `frame`, in name order.
+    pub fn due_groups(&self, frame: u32) -> Vec<String> {
+        self.rates
+            .keys()
+            .filter(|group| self.is_due(Some(group), frame))
+            .cloned()
+            .collect()
+    }
+
+    /// Every throttled group, in name order.
+    pub fn throttled_groups(&self) -> Vec<String> {
+        self.rates.keys().cloned().collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.rates.clear();
+    }
+}
+
+/// Holds back sprite syncs for throttled groups between their due frames.
+#[derive(Debug, Default)]
+pub struct GroupThrottle {
+    schedule: GroupSchedule,
+    /// Latest held sync per Ruby entity ID.
+    held: BTreeMap<u64, SpriteOperation>,
+    /// Pending operations to apply on next update.
+    pub pending_operations: Vec<GroupThrottleOperation>,
+}
+
+impl GroupThrottle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a divisor change (standalone, no World needed).
+    pub fn set_divisor_standalone(&mut self, group: &str, divisor: u32) {
+        self.pending_operations.push(GroupThrottleOperation::SetDivisor {
+            group: group.to_string(),
+            divisor,
+        });
+    }
+
+    /// Queues removing every divisor (standalone, no World needed).
+    pub fn clear_standalone(&mut self) {
+        self.pending_operations.push(GroupThrottleOperation::Clear);
+    }
+
+    /// Applies queued divisor changes, counting new schedules from `frame`.
+    pub fn apply_operations(&mut self, frame: u32) {
+        let ops: Vec<_> = self.pending_operations.drain(..).collect();
+        for op in ops {
+            match op {
+                GroupThrottleOperation::SetDivisor { group, divisor } => {
+                    self.schedule.set_divisor(&group, divisor, frame);
+                }
+                GroupThrottleOperation::Clear => self.schedule.clear(),
+            }
+        }
+    }
+
+    pub fn schedule(&self) -> &GroupSchedule {
+        &self.schedule
+    }
+
+    pub fn set_divisor(&mut self, group: &str, divisor: u32, frame: u32) {
+        self.schedule.set_divisor(group, divisor, frame);
+    }
+
+    /// Returns the operations to apply on `frame`.
+    ///
+    /// Syncs for groups that are not due replace whatever was held for their
+    /// entity. Held syncs whose group is due again come first, in entity
+    /// order, followed by the rest of `ops` in the order they were queued.
+    pub fn filter(&mut self, ops: Vec<SpriteOperation>, frame: u32) -> Vec<SpriteOperation> {
+        let mut passed = Vec::with_capacity(ops.len());
+        for op in ops {
+            match &op {
+                SpriteOperation::Sync {
+                    ruby_entity_id,
+                    sprite_data,
+                    ..
+                } => {
+                    self.held.remove(ruby_entity_id);
+                    if !self.schedule.is_due(sprite_data.group.as_deref(), frame) {
+                        self.held.insert(*ruby_entity_id, op);
+                        continue;
+                    }
+                }
+                SpriteOperation::Remove { ruby_entity_id }
+                | SpriteOperation::RemoveDeferred { ruby_entity_id, .. } => {
+                    self.held.remove(ruby_entity_id);
+                }
+                SpriteOperation::Clear => self.held.clear(),
+                _ => {}
+            }
+            passed.push(op);
+        }
+
+        let schedule = &self.schedule;
+        let mut released = Vec::new();
+        self.held.retain(|_, op| {
+            let due = match op {
+                SpriteOperation::Sync { sprite_data, .. } => schedule.is_due(sprite_data.group.as_deref(), frame),
+                _ => true,
+            };
+            if due {
+                released.push(op.clone());
+            }
+            !due
+        });
+        released.extend(passed);
+        released
+    }
+
+    /// Number of entities with a held sync.
+    pub fn held_count(&self) -> usize {
+        self.held.len()
+    }
+
+    /// Drops every divisor and held sync.
+    pub fn clear(&mut self) {
+        self.schedule.clear();
+        self.held.clear();
+    }
+}
@@ -0,0 +1,88 @@
+//! Stable hashing module shared by the determinism audit and content hashes.
+//!
+//! Hashes are FNV-1a 64 over explicitly little-endian bytes, so they are the
+//! same across platforms and builds.
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Order-sensitive FNV-1a hasher over explicitly little-endian data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameChecksum(u64);
+
+impl FrameChecksum {
+    pub fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_u8(u8::from(value));
+    }
+
+    /// Writes a float with `-0.0` folded into `0.0` and every NaN into one pattern.
+    pub fn write_f32(&mut self, value: f32) {
+        let canonical = if value.is_nan() {
+            f32::NAN
+        } else if value == 0.0 {
+            0.0
+        } else {
+            value
+        };
+        self.write_u32(canonical.to_bits());
+    }
+
+    /// Writes the length first, so adjacent strings can't run together.
+    pub fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write_bytes(value.as_bytes());
+    }
+
+    pub fn write_option_str(&mut self, value: Option<&str>) {
+        self.write_bool(value.is_some());
+        if let Some(value) = value {
+            self.write_str(value);
+        }
+    }
+
+    /// Writes strings sorted, so the result doesn't depend on set iteration order.
+    pub fn write_sorted_strs<'a, I>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let mut values: Vec<&String> = values.into_iter().collect();
+        values.sort_unstable();
+        self.write_u64(values.len() as u64);
+        for value in values {
+            self.write_str(value);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for FrameChecksum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,241 @@
+//! Hit region module for pickable rectangles that aren't rendered entities.
+//!
+//! A region is an axis-aligned rectangle with its minimum corner at `(x, y)`:
+//! the bottom-left corner in world space (y up), or the top-left corner in
+//! window pixels (y down) for `screen_space` regions. Regions take part in
+//! picking like sprites at `z = layer`; where regions overlap only the
+//! topmost is hit, by layer and then by registration order.
+
+#[cfg(feature = "rendering")]
+use std::collections::HashMap;
+
+#[cfg(feature = "rendering")]
+use bevy_ecs::component::Component;
+#[cfg(feature = "rendering")]
+use bevy_ecs::entity::Entity;
+#[cfg(feature = "rendering")]
+use bevy_ecs::world::World;
+
+/// Pickable rectangle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitRegion {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Picks like a sprite at this z.
+    pub layer: i32,
+    /// Measured in window pixels instead of world units.
+    pub screen_space: bool,
+}
+
+impl HitRegion {
+    /// Returns true if `point` lies inside; the maximum edges are exclusive
+    /// so adjacent regions never share a point.
+    pub fn contains(&self, point: (f32, f32)) -> bool {
+        point.0 >= self.x && point.0 < self.x + self.width && point.1 >= self.y && point.1 < self.y + self.height
+    }
+}
+
+/// New geometry for a registered region; `None` keeps the current value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitRegionUpdate {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub layer: Option<i32>,
+    pub screen_space: Option<bool>,
+}
+
+/// Pending hit region operation.
+#[derive(Debug, Clone)]
+pub enum HitRegionOperation {
+    Register { id: u64, region: HitRegion },
+    Update { id: u64, update: HitRegionUpdate },
+    Remove { id: u64 },
+}
+
+/// Marks the bare entity that stands in for a hit region during picking.
+#[cfg(feature = "rendering")]
+#[derive(Component, Debug, Clone, Copy)]
+pub struct HitRegionTarget(pub u64);
+
+/// Registered hit regions, in registration order.
+pub struct HitRegions {
+    regions: Vec<(u64, HitRegion)>,
+    /// Pending operations to apply on next update.
+    pub pending_operations: Vec<HitRegionOperation>,
+    #[cfg(feature = "rendering")]
+    entities: HashMap<u64, Entity>,
+    #[cfg(feature = "rendering")]
+    region_ids: HashMap<Entity, u64>,
+    /// Stand-ins despawned by the last sync, kept so their out events still
+    /// resolve to the region.
+    #[cfg(feature = "rendering")]
+    retired: HashMap<Entity, u64>,
+}
+
+impl HitRegions {
+    pub fn new() -> Self {
+        Self {
+            regions: Vec::new(),
+            pending_operations: Vec::new(),
+            #[cfg(feature = "rendering")]
+            entities: HashMap::new(),
+            #[cfg(feature = "rendering")]
+            region_ids: HashMap::new(),
+            #[cfg(feature = "rendering")]
+            retired: HashMap::new(),
+        }
+    }
+
+    /// Queues registering a region (standalone, no World needed).
+    pub fn register_standalone(&mut self, id: u64, region: HitRegion) {
+        self.pending_operations
+            .push(HitRegionOperation::Register { id, region });
+    }
+
+    /// Queues updating a region (standalone, no World needed).
+    pub fn update_standalone(&mut self, id: u64, update: HitRegionUpdate) {
+        self.pending_operations
+            .push(HitRegionOperation::Update { id, update });
+    }
+
+    /// Queues removing a region (standalone, no World needed).
+    pub fn remove_standalone(&mut self, id: u64) {
+        self.pending_operations
+            .push(HitRegionOperation::Remove { id });
+    }
+
+    /// Registers `region` under `id`, replacing an existing region in place.
+    pub fn register(&mut self, id: u64, region: HitRegion) {
+        match self.regions.iter_mut().find(|(region_id, _)| *region_id == id) {
+            Some((_, existing)) => *existing = region,
+            None => self.regions.push((id, region)),
+        }
+    }
+
+    /// Updates a registered region; returns false if `id` isn't registered.
+    pub fn update(&mut self, id: u64, update: HitRegionUpdate) -> bool {
+        let Some((_, region)) = self.regions.iter_mut().find(|(region_id, _)| *region_id == id) else {
+            return false;
+        };
+        region.x = update.x;
+        region.y = update.y;
+        region.width = update.width;
+        region.height = update.height;
+        if let Some(layer) = update.layer {
+            region.layer = layer;
+        }
+        if let Some(screen_space) = update.screen_space {
+            region.screen_space = screen_space;
+        }
+        true
+    }
+
+    /// Removes a region; returns false if `id` isn't registered.
+    pub fn remove(&mut self, id: u64) -> bool {
+        let len = self.regions.len();
+        self.regions.retain(|(region_id, _)| *region_id != id);
+        self.regions.len() != len
+    }
+
+    pub fn get(&self, id: u64) -> Option<&HitRegion> {
+        self.regions
+            .iter()
+            .find(|(region_id, _)| *region_id == id)
+            .map(|(_, region)| region)
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Applies pending operations.
+    pub fn apply_operations(&mut self) {
+        let ops: Vec<_> = self.pending_operations.drain(..).collect();
+        for op in ops {
+            match op {
+                HitRegionOperation::Register { id, region } => self.register(id, region),
+                HitRegionOperation::Update { id, update } => {
+                    self.update(id, update);
+                }
+                HitRegionOperation::Remove { id } => {
+                    self.remove(id);
+                }
+            }
+        }
+    }
+
+    /// Topmost region under a pointer at `screen` window pixels, which is at
+    /// `world` in world space (`None` when the pointer can't be mapped).
+    ///
+    /// Higher layers win; within a layer the region registered last wins.
+    pub fn topmost(&self, screen: (f32, f32), world: Option<(f32, f32)>) -> Option<(u64, &HitRegion)> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, region))| match (region.screen_space, world) {
+                (true, _) => region.contains(screen),
+                (false, Some(world)) => region.contains(world),
+                (false, None) => false,
+            })
+            .max_by_key(|(index, (_, region))| (region.layer, *index))
+            .map(|(_, (id, region))| (*id, region))
+    }
+
+    /// Region a picking target stands in for, if any.
+    #[cfg(feature = "rendering")]
+    pub fn region_id(&self, entity: Entity) -> Option<u64> {
+        self.region_ids
+            .get(&entity)
+            .or_else(|| self.retired.get(&entity))
+            .copied()
+    }
+
+    /// Stand-in entity of a registered region.
+    #[cfg(feature = "rendering")]
+    pub fn entity(&self, id: u64) -> Option<Entity> {
+        self.entities.get(&id).copied()
+    }
+
+    /// Applies pending operations and keeps one bare stand-in entity per region.
+    #[cfg(feature = "rendering")]
+    pub fn sync_entities(&mut self, world: &mut World) {
+        self.apply_operations();
+        self.retired.clear();
+
+        let stale: Vec<u64> = self
+            .entities
+            .keys()
+            .copied()
+            .filter(|id| self.get(*id).is_none())
+            .collect();
+        for id in stale {
+            if let Some(entity) = self.entities.remove(&id) {
+                self.region_ids.remove(&entity);
+                self.retired.insert(entity, id);
+                world.despawn(entity);
+            }
+        }
+
+        for (id, _) in &self.regions {
+            if !self.entities.contains_key(id) {
+                let entity = world.spawn(HitRegionTarget(*id)).id();
+                self.entities.insert(*id, entity);
+                self.region_ids.insert(entity, *id);
+            }
+        }
+    }
+}
+
+impl Default for HitRegions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
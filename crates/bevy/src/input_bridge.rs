@@ -1,6 +1,8 @@
 //! Input bridge module for converting Bevy input to Ruby-compatible format.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::determinism::{AuditMap, AuditSet};
 
 #[cfg(feature = "rendering")]
 use bevy_ecs::world::World;
@@ -9,27 +11,260 @@ use bevy_input::{ButtonInput, keyboard::KeyCode, mouse::MouseButton};
 #[cfg(feature = "rendering")]
 use bevy_window::{PrimaryWindow, Window};
 
+/// Pixels counted as one line when folding pixel scrolling into `scroll_delta`.
+pub const SCROLL_PIXELS_PER_LINE: f32 = 16.0;
+
 /// Holds the current input state for Ruby.
 #[derive(Debug, Default, Clone)]
 pub struct InputState {
-    pub keys_pressed: HashSet<String>,
-    pub keys_just_pressed: HashSet<String>,
-    pub keys_just_released: HashSet<String>,
-    pub mouse_buttons_pressed: HashSet<String>,
-    pub mouse_buttons_just_pressed: HashSet<String>,
+    pub keys_pressed: AuditSet<String>,
+    pub keys_just_pressed: AuditSet<String>,
+    pub keys_just_released: AuditSet<String>,
+    pub mouse_buttons_pressed: AuditSet<String>,
+    pub mouse_buttons_just_pressed: AuditSet<String>,
+    pub mouse_buttons_just_released: AuditSet<String>,
     pub mouse_position: (f32, f32),
+    /// Mouse motion this frame, y up like `mouse_position`. Read from raw
+    /// motion events, so it keeps reporting while the cursor is grabbed.
     pub mouse_delta: (f32, f32),
-    pub gamepads: HashMap<u64, GamepadInputState>,
+    /// Cursor position as of the previous `set_cursor_position`, `None`
+    /// before the first frame or while the cursor is outside the window.
+    pub previous_cursor: Option<(f32, f32)>,
+    /// Whether raw motion arrived this frame; cursor movement only stands in
+    /// for `mouse_delta` when it did not.
+    pub mouse_motion_seen: bool,
+    /// Scrolling this frame in lines, with pixel scrolling converted at
+    /// `SCROLL_PIXELS_PER_LINE`. Positive y scrolls up.
+    pub scroll_delta: (f32, f32),
+    /// Scrolling this frame reported in lines (typically mouse wheels).
+    pub scroll_line_delta: (f32, f32),
+    /// Scrolling this frame reported in pixels (typically touchpads).
+    pub scroll_pixel_delta: (f32, f32),
+    pub gamepads: AuditMap<u64, GamepadInputState>,
+    /// Fingers on the screen, kept across frames unlike the rest of the state.
+    pub touches: TouchStates,
+    /// Characters typed this frame while text input is enabled.
+    pub typed_text: TypedText,
+    /// Elapsed seconds at which each held key went down. Kept across frames
+    /// like `touches`; `track_key_holds` starts and drops the timers.
+    pub key_press_started: AuditMap<String, f64>,
+    /// Elapsed seconds as of the last `track_key_holds`.
+    pub key_hold_clock: f64,
+}
+
+/// Phase of a touch as of the current frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPhase {
+    Started,
+    Moved,
+    /// Down without an event this frame.
+    Stationary,
+    Ended,
+    Canceled,
+}
+
+impl TouchPhase {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Started => "started",
+            Self::Moved => "moved",
+            Self::Stationary => "stationary",
+            Self::Ended => "ended",
+            Self::Canceled => "canceled",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "started" => Some(Self::Started),
+            "moved" => Some(Self::Moved),
+            "stationary" => Some(Self::Stationary),
+            "ended" => Some(Self::Ended),
+            "canceled" => Some(Self::Canceled),
+            _ => None,
+        }
+    }
+
+    /// True for the last frame a touch is reported.
+    pub fn is_released(self) -> bool {
+        matches!(self, Self::Ended | Self::Canceled)
+    }
+}
+
+/// One finger; positions are in window pixels with y down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TouchState {
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub position: (f32, f32),
+    /// Position at the end of the previous frame, or where it started.
+    pub previous_position: (f32, f32),
+    /// Pressure in `0.0..=1.0`, `None` when the device doesn't report it.
+    pub force: Option<f32>,
+    /// Set when the touch started this frame, even if it also ended.
+    pub just_pressed: bool,
+}
+
+/// Per-finger touch state built from touch events.
+///
+/// A released touch is reported once, with an `Ended` or `Canceled` phase,
+/// and dropped when the next frame begins.
+#[derive(Debug, Default, Clone)]
+pub struct TouchStates {
+    touches: BTreeMap<u64, TouchState>,
+}
+
+impl TouchStates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops touches released last frame and marks the rest stationary.
+    pub fn begin_frame(&mut self) {
+        self.touches.retain(|_, touch| !touch.phase.is_released());
+        for touch in self.touches.values_mut() {
+            touch.phase = TouchPhase::Stationary;
+            touch.previous_position = touch.position;
+            touch.just_pressed = false;
+        }
+    }
+
+    /// Folds one touch event into the state. A touch that starts and moves
+    /// in the same frame stays `Started`.
+    pub fn apply(&mut self, id: u64, phase: TouchPhase, position: (f32, f32), force: Option<f32>) {
+        let touch = self.touches.entry(id).or_insert_with(|| TouchState {
+            id,
+            phase,
+            position,
+            previous_position: position,
+            force,
+            just_pressed: false,
+        });
+        touch.position = position;
+        if force.is_some() {
+            touch.force = force;
+        }
+        match phase {
+            TouchPhase::Started => {
+                touch.phase = TouchPhase::Started;
+                touch.previous_position = position;
+                touch.just_pressed = true;
+            }
+            TouchPhase::Moved | TouchPhase::Stationary => {
+                if touch.phase != TouchPhase::Started {
+                    touch.phase = phase;
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Canceled => touch.phase = phase,
+        }
+    }
+
+    /// Touches down or released this frame, ordered by ID.
+    pub fn iter(&self) -> impl Iterator<Item = &TouchState> {
+        self.touches.values()
+    }
+
+    pub fn get(&self, id: u64) -> Option<&TouchState> {
+        self.touches.get(&id)
+    }
+
+    /// Current position of a touch, including one released this frame.
+    pub fn position(&self, id: u64) -> Option<(f32, f32)> {
+        self.get(id).map(|touch| touch.position)
+    }
+
+    /// Whether touch `id`, or any touch for `None`, started this frame.
+    pub fn just_pressed(&self, id: Option<u64>) -> bool {
+        match id {
+            Some(id) => self.get(id).is_some_and(|touch| touch.just_pressed),
+            None => self.iter().any(|touch| touch.just_pressed),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.touches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.touches.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.touches.clear();
+    }
+}
+
+/// Editing key reported inside typed text as a control character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEdit {
+    Backspace,
+    Delete,
+    Enter,
+    Tab,
+}
+
+impl TextEdit {
+    pub fn character(self) -> char {
+        match self {
+            Self::Backspace => '\u{8}',
+            Self::Delete => '\u{7f}',
+            Self::Enter => '\n',
+            Self::Tab => '\t',
+        }
+    }
+}
+
+/// Characters typed in one frame, in the order typed. Editing keys appear
+/// as the control character of their `TextEdit`; control characters in
+/// typed text itself are dropped so they can't be mistaken for edits.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TypedText {
+    text: String,
+}
+
+impl TypedText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        self.text.extend(text.chars().filter(|c| !c.is_control()));
+    }
+
+    pub fn push_edit(&mut self, edit: TextEdit) {
+        self.text.push(edit.character());
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// How many times `edit` was pressed (or repeated) this frame.
+    pub fn count(&self, edit: TextEdit) -> usize {
+        self.text.chars().filter(|&c| c == edit.character()).count()
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+    }
 }
 
+/// Input of one gamepad; buttons and axes are kept sorted by name so they
+/// can be handed to Ruby in order without sorting on every read.
 #[derive(Debug, Default, Clone)]
 pub struct GamepadInputState {
     pub id: u64,
     pub name: String,
-    pub buttons_pressed: HashSet<String>,
-    pub buttons_just_pressed: HashSet<String>,
-    pub buttons_just_released: HashSet<String>,
-    pub axes: HashMap<String, f32>,
+    pub buttons_pressed: BTreeSet<String>,
+    pub buttons_just_pressed: BTreeSet<String>,
+    pub buttons_just_released: BTreeSet<String>,
+    pub axes: BTreeMap<String, f32>,
+    /// Battery level in `0.0..=1.0`, or `None` when the backend can't report it.
+    pub battery: Option<f32>,
 }
 
 impl InputState {
@@ -52,15 +287,39 @@ impl InputState {
             self.keys_pressed.clear();
             self.keys_just_pressed.clear();
             self.keys_just_released.clear();
+            self.set_keyboard(keyboard);
+        }
+    }
 
-            for key in keyboard.get_pressed() {
-                self.keys_pressed.insert(keycode_to_string(*key));
+    /// Adds the keys of `keyboard` to the key sets under their
+    /// `key_code_name`. A modifier name counts as just pressed only when
+    /// neither side was already down, and as just released only once both
+    /// sides are up.
+    #[cfg(feature = "rendering")]
+    pub fn set_keyboard(&mut self, keyboard: &ButtonInput<KeyCode>) {
+        for key in keyboard.get_pressed() {
+            self.set_pressed(key_code_name(*key));
+        }
+        for key in keyboard.get_just_pressed() {
+            self.set_just_pressed(key_code_name(*key));
+        }
+        for key in keyboard.get_just_released() {
+            self.set_just_released(key_code_name(*key));
+        }
+
+        for (name, sides) in MODIFIER_KEYS {
+            let pressed = keyboard.any_pressed(sides);
+            if pressed {
+                self.set_pressed(name);
             }
-            for key in keyboard.get_just_pressed() {
-                self.keys_just_pressed.insert(keycode_to_string(*key));
+            let held_before = sides
+                .iter()
+                .any(|key| keyboard.pressed(*key) && !keyboard.just_pressed(*key));
+            if keyboard.any_just_pressed(sides) && !held_before {
+                self.set_just_pressed(name);
             }
-            for key in keyboard.get_just_released() {
-                self.keys_just_released.insert(keycode_to_string(*key));
+            if keyboard.any_just_released(sides) && !pressed {
+                self.set_just_released(name);
             }
         }
     }
@@ -70,6 +329,7 @@ impl InputState {
         if let Some(mouse) = world.get_resource::<ButtonInput<MouseButton>>() {
             self.mouse_buttons_pressed.clear();
             self.mouse_buttons_just_pressed.clear();
+            self.mouse_buttons_just_released.clear();
 
             for button in mouse.get_pressed() {
                 self.mouse_buttons_pressed
@@ -79,6 +339,10 @@ impl InputState {
                 self.mouse_buttons_just_pressed
                     .insert(mouse_button_to_string(*button));
             }
+            for button in mouse.get_just_released() {
+                self.mouse_buttons_just_released
+                    .insert(mouse_button_to_string(*button));
+            }
         }
     }
 
@@ -87,11 +351,7 @@ impl InputState {
         // Query for primary window to get cursor position
         let mut query = world.query_filtered::<&Window, bevy_ecs::query::With<PrimaryWindow>>();
         if let Some(window) = query.iter(world).next() {
-            if let Some(pos) = window.cursor_position() {
-                let old_pos = self.mouse_position;
-                self.mouse_position = (pos.x, pos.y);
-                self.mouse_delta = (pos.x - old_pos.0, pos.y - old_pos.1);
-            }
+            self.set_cursor_position(window.cursor_position().map(|pos| (pos.x, pos.y)));
         }
     }
 
@@ -120,6 +380,35 @@ impl InputState {
         self.mouse_buttons_just_pressed.contains(button)
     }
 
+    /// Checks if a mouse button was just released this frame.
+    pub fn mouse_button_just_released(&self, button: &str) -> bool {
+        self.mouse_buttons_just_released.contains(button)
+    }
+
+    /// Starts a hold timer for each key pressed this frame and drops the
+    /// timers of keys no longer held. Call once a frame after the key sets
+    /// are filled; a key pressed again in the frame it was released restarts.
+    pub fn track_key_holds(&mut self, elapsed_secs: f64) {
+        self.key_hold_clock = elapsed_secs;
+        let pressed = &self.keys_pressed;
+        self.key_press_started.retain(|key, _| pressed.contains(key));
+        for key in &self.keys_pressed {
+            if self.keys_just_pressed.contains(key) {
+                self.key_press_started.insert(key.clone(), elapsed_secs);
+            } else {
+                self.key_press_started.entry(key.clone()).or_insert(elapsed_secs);
+            }
+        }
+    }
+
+    /// Seconds `key` has been held as of the last `track_key_holds`, or 0.0
+    /// when it is not pressed.
+    pub fn key_held_duration(&self, key: &str) -> f64 {
+        self.key_press_started
+            .get(key)
+            .map_or(0.0, |started| (self.key_hold_clock - started).max(0.0))
+    }
+
     /// Returns all currently pressed keys.
     pub fn get_pressed_keys(&self) -> Vec<String> {
         self.keys_pressed.iter().cloned().collect()
@@ -137,7 +426,58 @@ impl InputState {
         self.keys_just_released.clear();
         self.mouse_buttons_pressed.clear();
         self.mouse_buttons_just_pressed.clear();
+        self.mouse_buttons_just_released.clear();
+        self.mouse_delta = (0.0, 0.0);
+        self.mouse_motion_seen = false;
+        self.scroll_delta = (0.0, 0.0);
+        self.scroll_line_delta = (0.0, 0.0);
+        self.scroll_pixel_delta = (0.0, 0.0);
         self.gamepads.clear();
+        self.typed_text.clear();
+    }
+
+    /// Accumulates raw mouse motion reported y down, as winit does.
+    pub fn add_mouse_motion(&mut self, x: f32, y: f32) {
+        self.mouse_delta.0 += x;
+        self.mouse_delta.1 -= y;
+        self.mouse_motion_seen = true;
+    }
+
+    /// Records where the cursor is this frame, `None` while it is outside the
+    /// window, which keeps the last `mouse_position`.
+    ///
+    /// Call after this frame's `add_mouse_motion`. Without raw motion the
+    /// delta falls back to the cursor's movement since the previous frame;
+    /// the first frame and a cursor re-entering the window report no
+    /// movement, so the delta never jumps.
+    pub fn set_cursor_position(&mut self, position: Option<(f32, f32)>) {
+        let Some(position) = position else {
+            self.previous_cursor = None;
+            return;
+        };
+        if !self.mouse_motion_seen
+            && let Some(previous) = self.previous_cursor
+        {
+            self.mouse_delta = (position.0 - previous.0, position.1 - previous.1);
+        }
+        self.mouse_position = position;
+        self.previous_cursor = Some(position);
+    }
+
+    /// Accumulates a scroll reported in lines.
+    pub fn add_scroll_lines(&mut self, x: f32, y: f32) {
+        self.scroll_line_delta.0 += x;
+        self.scroll_line_delta.1 += y;
+        self.scroll_delta.0 += x;
+        self.scroll_delta.1 += y;
+    }
+
+    /// Accumulates a scroll reported in pixels.
+    pub fn add_scroll_pixels(&mut self, x: f32, y: f32) {
+        self.scroll_pixel_delta.0 += x;
+        self.scroll_pixel_delta.1 += y;
+        self.scroll_delta.0 += x / SCROLL_PIXELS_PER_LINE;
+        self.scroll_delta.1 += y / SCROLL_PIXELS_PER_LINE;
     }
 
     /// Sets a key as pressed.
@@ -165,6 +505,11 @@ impl InputState {
         self.mouse_buttons_just_pressed.insert(button.to_string());
     }
 
+    /// Sets a mouse button as just released.
+    pub fn set_mouse_just_released(&mut self, button: &str) {
+        self.mouse_buttons_just_released.insert(button.to_string());
+    }
+
     /// Ensures a gamepad slot exists for this frame and updates its display name.
     pub fn set_gamepad_connected(&mut self, id: u64, name: &str) {
         let state = self
@@ -224,68 +569,366 @@ impl InputState {
             });
         state.axes.insert(axis.to_string(), value);
     }
+
+    /// Sets a gamepad battery level, clamped to `0.0..=1.0`.
+    pub fn set_gamepad_battery(&mut self, id: u64, level: Option<f32>) {
+        let state = self
+            .gamepads
+            .entry(id)
+            .or_insert_with(|| GamepadInputState {
+                id,
+                ..Default::default()
+            });
+        state.battery = level.map(|value| value.clamp(0.0, 1.0));
+    }
 }
 
-/// Converts a Bevy KeyCode to a Ruby-compatible string.
-#[cfg(feature = "rendering")]
-fn keycode_to_string(key: KeyCode) -> String {
-    match key {
-        KeyCode::KeyA => "A".to_string(),
-        KeyCode::KeyB => "B".to_string(),
-        KeyCode::KeyC => "C".to_string(),
-        KeyCode::KeyD => "D".to_string(),
-        KeyCode::KeyE => "E".to_string(),
-        KeyCode::KeyF => "F".to_string(),
-        KeyCode::KeyG => "G".to_string(),
-        KeyCode::KeyH => "H".to_string(),
-        KeyCode::KeyI => "I".to_string(),
-        KeyCode::KeyJ => "J".to_string(),
-        KeyCode::KeyK => "K".to_string(),
-        KeyCode::KeyL => "L".to_string(),
-        KeyCode::KeyM => "M".to_string(),
-        KeyCode::KeyN => "N".to_string(),
-        KeyCode::KeyO => "O".to_string(),
-        KeyCode::KeyP => "P".to_string(),
-        KeyCode::KeyQ => "Q".to_string(),
-        KeyCode::KeyR => "R".to_string(),
-        KeyCode::KeyS => "S".to_string(),
-        KeyCode::KeyT => "T".to_string(),
-        KeyCode::KeyU => "U".to_string(),
-        KeyCode::KeyV => "V".to_string(),
-        KeyCode::KeyW => "W".to_string(),
-        KeyCode::KeyX => "X".to_string(),
-        KeyCode::KeyY => "Y".to_string(),
-        KeyCode::KeyZ => "Z".to_string(),
-        KeyCode::Digit0 => "0".to_string(),
-        KeyCode::Digit1 => "1".to_string(),
-        KeyCode::Digit2 => "2".to_string(),
-        KeyCode::Digit3 => "3".to_string(),
-        KeyCode::Digit4 => "4".to_string(),
-        KeyCode::Digit5 => "5".to_string(),
-        KeyCode::Digit6 => "6".to_string(),
-        KeyCode::Digit7 => "7".to_string(),
-        KeyCode::Digit8 => "8".to_string(),
-        KeyCode::Digit9 => "9".to_string(),
-        KeyCode::ArrowUp => "UP".to_string(),
-        KeyCode::ArrowDown => "DOWN".to_string(),
-        KeyCode::ArrowLeft => "LEFT".to_string(),
-        KeyCode::ArrowRight => "RIGHT".to_string(),
-        KeyCode::Space => "SPACE".to_string(),
-        KeyCode::Enter => "ENTER".to_string(),
-        KeyCode::Escape => "ESCAPE".to_string(),
-        KeyCode::Tab => "TAB".to_string(),
-        KeyCode::Backspace => "BACKSPACE".to_string(),
-        KeyCode::Delete => "DELETE".to_string(),
-        KeyCode::ShiftLeft => "SHIFT_LEFT".to_string(),
-        KeyCode::ShiftRight => "SHIFT_RIGHT".to_string(),
-        KeyCode::ControlLeft => "CONTROL_LEFT".to_string(),
-        KeyCode::ControlRight => "CONTROL_RIGHT".to_string(),
-        KeyCode::AltLeft => "ALT_LEFT".to_string(),
-        KeyCode::AltRight => "ALT_RIGHT".to_string(),
-        _ => format!("{:?}", key),
+/// Keys, mouse buttons and gamepad buttons bound to one action, under the
+/// names the input state reports them with.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ActionBinding {
+    pub keys: BTreeSet<String>,
+    pub mouse_buttons: BTreeSet<String>,
+    /// Matched on every connected gamepad.
+    pub gamepad_buttons: BTreeSet<String>,
+}
+
+impl ActionBinding {
+    /// The first key that is neither in `KEY_CODE_NAMES` nor a modifier name.
+    pub fn unknown_key(&self) -> Option<&str> {
+        self.keys
+            .iter()
+            .map(String::as_str)
+            .find(|key| !KEY_CODE_NAMES.contains(key) && !MODIFIER_KEY_NAMES.contains(key))
+    }
+
+    fn any_source(
+        &self,
+        input: &InputState,
+        keys: &AuditSet<String>,
+        mouse_buttons: &AuditSet<String>,
+        gamepad_buttons: impl Fn(&GamepadInputState) -> &BTreeSet<String>,
+    ) -> bool {
+        self.keys.iter().any(|key| keys.contains(key))
+            || self.mouse_buttons.iter().any(|button| mouse_buttons.contains(button))
+            || input.gamepads.values().any(|gamepad| {
+                let buttons = gamepad_buttons(gamepad);
+                self.gamepad_buttons.iter().any(|button| buttons.contains(button))
+            })
+    }
+
+    /// True while any source is held.
+    pub fn pressed(&self, input: &InputState) -> bool {
+        self.any_source(input, &input.keys_pressed, &input.mouse_buttons_pressed, |gamepad| {
+            &gamepad.buttons_pressed
+        })
     }
+
+    /// True on the frame the action starts: a source went down and none
+    /// was already held, so pressing a second source adds nothing.
+    pub fn just_pressed(&self, input: &InputState) -> bool {
+        let went_down = self.any_source(input, &input.keys_just_pressed, &input.mouse_buttons_just_pressed, |gamepad| {
+            &gamepad.buttons_just_pressed
+        });
+        let held_before = self.keys.iter().any(|key| {
+            input.keys_pressed.contains(key) && !input.keys_just_pressed.contains(key)
+        }) || self.mouse_buttons.iter().any(|button| {
+            input.mouse_buttons_pressed.contains(button) && !input.mouse_buttons_just_pressed.contains(button)
+        }) || input.gamepads.values().any(|gamepad| {
+            self.gamepad_buttons.iter().any(|button| {
+                gamepad.buttons_pressed.contains(button) && !gamepad.buttons_just_pressed.contains(button)
+            })
+        });
+        went_down && !held_before
+    }
+
+    /// True on the frame the last held source goes up.
+    pub fn just_released(&self, input: &InputState) -> bool {
+        let went_up = self.any_source(input, &input.keys_just_released, &input.mouse_buttons_just_released, |gamepad| {
+            &gamepad.buttons_just_released
+        });
+        went_up && !self.pressed(input)
+    }
+}
+
+/// Named actions, each bound to any number of input sources. An action is
+/// pressed while any of its sources is; bindings can be replaced at any
+/// time, e.g. from a rebinding menu.
+#[derive(Debug, Default, Clone)]
+pub struct ActionMap {
+    actions: BTreeMap<String, ActionBinding>,
 }
 
+impl ActionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds `action` to `binding`, replacing its previous sources.
+    pub fn bind(&mut self, action: &str, binding: ActionBinding) {
+        self.actions.insert(action.to_string(), binding);
+    }
+
+    /// Removes `action`; returns whether it was bound.
+    pub fn unbind(&mut self, action: &str) -> bool {
+        self.actions.remove(action).is_some()
+    }
+
+    pub fn binding(&self, action: &str) -> Option<&ActionBinding> {
+        self.actions.get(action)
+    }
+
+    /// Bound action names in order.
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.actions.keys().map(String::as_str)
+    }
+
+    pub fn clear(&mut self) {
+        self.actions.clear();
+    }
+
+    /// Whether `action` is held; an unbound action never is.
+    pub fn pressed(&self, action: &str, input: &InputState) -> bool {
+        self.binding(action).is_some_and(|binding| binding.pressed(input))
+    }
+
+    pub fn just_pressed(&self, action: &str, input: &InputState) -> bool {
+        self.binding(action).is_some_and(|binding| binding.just_pressed(input))
+    }
+
+    pub fn just_released(&self, action: &str, input: &InputState) -> bool {
+        self.binding(action).is_some_and(|binding| binding.just_released(input))
+    }
+}
+
+/// Defines `KEY_CODE_NAMES` and `key_code_name` from one table so the two
+/// can't disagree. The match has no catch-all arm, so a `KeyCode` variant
+/// added upstream fails to compile until it is named here.
+macro_rules! key_code_names {
+    ($($code:ident => $name:literal,)*) => {
+        /// The name of every `KeyCode`, in declaration order.
+        pub const KEY_CODE_NAMES: &[&str] = &["UNIDENTIFIED", $($name,)*];
+
+        /// The uppercase name a key is reported under in the key sets.
+        #[cfg(feature = "rendering")]
+        pub fn key_code_name(key: KeyCode) -> &'static str {
+            match key {
+                KeyCode::Unidentified(_) => "UNIDENTIFIED",
+                $(KeyCode::$code => $name,)*
+            }
+        }
+    };
+}
+
+key_code_names! {
+    Backquote => "BACKQUOTE",
+    Backslash => "BACKSLASH",
+    BracketLeft => "BRACKET_LEFT",
+    BracketRight => "BRACKET_RIGHT",
+    Comma => "COMMA",
+    Digit0 => "0",
+    Digit1 => "1",
+    Digit2 => "2",
+    Digit3 => "3",
+    Digit4 => "4",
+    Digit5 => "5",
+    Digit6 => "6",
+    Digit7 => "7",
+    Digit8 => "8",
+    Digit9 => "9",
+    Equal => "EQUAL",
+    IntlBackslash => "INTL_BACKSLASH",
+    IntlRo => "INTL_RO",
+    IntlYen => "INTL_YEN",
+    KeyA => "A",
+    KeyB => "B",
+    KeyC => "C",
+    KeyD => "D",
+    KeyE => "E",
+    KeyF => "F",
+    KeyG => "G",
+    KeyH => "H",
+    KeyI => "I",
+    KeyJ => "J",
+    KeyK => "K",
+    KeyL => "L",
+    KeyM => "M",
+    KeyN => "N",
+    KeyO => "O",
+    KeyP => "P",
+    KeyQ => "Q",
+    KeyR => "R",
+    KeyS => "S",
+    KeyT => "T",
+    KeyU => "U",
+    KeyV => "V",
+    KeyW => "W",
+    KeyX => "X",
+    KeyY => "Y",
+    KeyZ => "Z",
+    Minus => "MINUS",
+    Period => "PERIOD",
+    Quote => "QUOTE",
+    Semicolon => "SEMICOLON",
+    Slash => "SLASH",
+    AltLeft => "ALT_LEFT",
+    AltRight => "ALT_RIGHT",
+    Backspace => "BACKSPACE",
+    CapsLock => "CAPS_LOCK",
+    ContextMenu => "CONTEXT_MENU",
+    ControlLeft => "CONTROL_LEFT",
+    ControlRight => "CONTROL_RIGHT",
+    Enter => "ENTER",
+    SuperLeft => "SUPER_LEFT",
+    SuperRight => "SUPER_RIGHT",
+    ShiftLeft => "SHIFT_LEFT",
+    ShiftRight => "SHIFT_RIGHT",
+    Space => "SPACE",
+    Tab => "TAB",
+    Convert => "CONVERT",
+    KanaMode => "KANA_MODE",
+    Lang1 => "LANG_1",
+    Lang2 => "LANG_2",
+    Lang3 => "LANG_3",
+    Lang4 => "LANG_4",
+    Lang5 => "LANG_5",
+    NonConvert => "NON_CONVERT",
+    Delete => "DELETE",
+    End => "END",
+    Help => "HELP",
+    Home => "HOME",
+    Insert => "INSERT",
+    PageDown => "PAGE_DOWN",
+    PageUp => "PAGE_UP",
+    ArrowDown => "DOWN",
+    ArrowLeft => "LEFT",
+    ArrowRight => "RIGHT",
+    ArrowUp => "UP",
+    NumLock => "NUM_LOCK",
+    Numpad0 => "NUMPAD_0",
+    Numpad1 => "NUMPAD_1",
+    Numpad2 => "NUMPAD_2",
+    Numpad3 => "NUMPAD_3",
+    Numpad4 => "NUMPAD_4",
+    Numpad5 => "NUMPAD_5",
+    Numpad6 => "NUMPAD_6",
+    Numpad7 => "NUMPAD_7",
+    Numpad8 => "NUMPAD_8",
+    Numpad9 => "NUMPAD_9",
+    NumpadAdd => "NUMPAD_ADD",
+    NumpadBackspace => "NUMPAD_BACKSPACE",
+    NumpadClear => "NUMPAD_CLEAR",
+    NumpadClearEntry => "NUMPAD_CLEAR_ENTRY",
+    NumpadComma => "NUMPAD_COMMA",
+    NumpadDecimal => "NUMPAD_DECIMAL",
+    NumpadDivide => "NUMPAD_DIVIDE",
+    NumpadEnter => "NUMPAD_ENTER",
+    NumpadEqual => "NUMPAD_EQUAL",
+    NumpadHash => "NUMPAD_HASH",
+    NumpadMemoryAdd => "NUMPAD_MEMORY_ADD",
+    NumpadMemoryClear => "NUMPAD_MEMORY_CLEAR",
+    NumpadMemoryRecall => "NUMPAD_MEMORY_RECALL",
+    NumpadMemoryStore => "NUMPAD_MEMORY_STORE",
+    NumpadMemorySubtract => "NUMPAD_MEMORY_SUBTRACT",
+    NumpadMultiply => "NUMPAD_MULTIPLY",
+    NumpadParenLeft => "NUMPAD_PAREN_LEFT",
+    NumpadParenRight => "NUMPAD_PAREN_RIGHT",
+    NumpadStar => "NUMPAD_STAR",
+    NumpadSubtract => "NUMPAD_SUBTRACT",
+    Escape => "ESCAPE",
+    Fn => "FN",
+    FnLock => "FN_LOCK",
+    PrintScreen => "PRINT_SCREEN",
+    ScrollLock => "SCROLL_LOCK",
+    Pause => "PAUSE",
+    BrowserBack => "BROWSER_BACK",
+    BrowserFavorites => "BROWSER_FAVORITES",
+    BrowserForward => "BROWSER_FORWARD",
+    BrowserHome => "BROWSER_HOME",
+    BrowserRefresh => "BROWSER_REFRESH",
+    BrowserSearch => "BROWSER_SEARCH",
+    BrowserStop => "BROWSER_STOP",
+    Eject => "EJECT",
+    LaunchApp1 => "LAUNCH_APP_1",
+    LaunchApp2 => "LAUNCH_APP_2",
+    LaunchMail => "LAUNCH_MAIL",
+    MediaPlayPause => "MEDIA_PLAY_PAUSE",
+    MediaSelect => "MEDIA_SELECT",
+    MediaStop => "MEDIA_STOP",
+    MediaTrackNext => "MEDIA_TRACK_NEXT",
+    MediaTrackPrevious => "MEDIA_TRACK_PREVIOUS",
+    Power => "POWER",
+    Sleep => "SLEEP",
+    AudioVolumeDown => "AUDIO_VOLUME_DOWN",
+    AudioVolumeMute => "AUDIO_VOLUME_MUTE",
+    AudioVolumeUp => "AUDIO_VOLUME_UP",
+    WakeUp => "WAKE_UP",
+    Meta => "META",
+    Hyper => "HYPER",
+    Turbo => "TURBO",
+    Abort => "ABORT",
+    Resume => "RESUME",
+    Suspend => "SUSPEND",
+    Again => "AGAIN",
+    Copy => "COPY",
+    Cut => "CUT",
+    Find => "FIND",
+    Open => "OPEN",
+    Paste => "PASTE",
+    Props => "PROPS",
+    Select => "SELECT",
+    Undo => "UNDO",
+    Hiragana => "HIRAGANA",
+    Katakana => "KATAKANA",
+    F1 => "F1",
+    F2 => "F2",
+    F3 => "F3",
+    F4 => "F4",
+    F5 => "F5",
+    F6 => "F6",
+    F7 => "F7",
+    F8 => "F8",
+    F9 => "F9",
+    F10 => "F10",
+    F11 => "F11",
+    F12 => "F12",
+    F13 => "F13",
+    F14 => "F14",
+    F15 => "F15",
+    F16 => "F16",
+    F17 => "F17",
+    F18 => "F18",
+    F19 => "F19",
+    F20 => "F20",
+    F21 => "F21",
+    F22 => "F22",
+    F23 => "F23",
+    F24 => "F24",
+    F25 => "F25",
+    F26 => "F26",
+    F27 => "F27",
+    F28 => "F28",
+    F29 => "F29",
+    F30 => "F30",
+    F31 => "F31",
+    F32 => "F32",
+    F33 => "F33",
+    F34 => "F34",
+    F35 => "F35",
+}
+
+/// Names reported for either key of a modifier pair, e.g. `"SHIFT"` while
+/// `"SHIFT_LEFT"` or `"SHIFT_RIGHT"` is down.
+pub const MODIFIER_KEY_NAMES: [&str; 4] = ["SHIFT", "CONTROL", "ALT", "SUPER"];
+
+#[cfg(feature = "rendering")]
+const MODIFIER_KEYS: [(&str, [KeyCode; 2]); 4] = [
+    (MODIFIER_KEY_NAMES[0], [KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+    (MODIFIER_KEY_NAMES[1], [KeyCode::ControlLeft, KeyCode::ControlRight]),
+    (MODIFIER_KEY_NAMES[2], [KeyCode::AltLeft, KeyCode::AltRight]),
+    (MODIFIER_KEY_NAMES[3], [KeyCode::SuperLeft, KeyCode::SuperRight]),
+];
+
 /// Converts a Bevy MouseButton to a Ruby-compatible string.
 #[cfg(feature = "rendering")]
 fn mouse_button_to_string(button: MouseButton) -> String {
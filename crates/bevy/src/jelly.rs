@@ -0,0 +1,220 @@
+//! Spring-damper simulation behind jelly (mesh-mode) sprites.
+//!
+//! A jelly sprite is a grid of vertices. Each vertex is tied to its rest
+//! position by a damped spring; impulses kick the vertices and the springs
+//! pull them back. The simulation is plain data so it can run and be
+//! inspected without a Bevy world.
+
+/// Most quads along one side of a jelly mesh.
+pub const MAX_JELLY_SUBDIVISIONS: u32 = 32;
+
+/// Spring energy below which a jelly mesh snaps back to rest.
+const RESTING_ENERGY: f32 = 1e-4;
+
+/// Tuning for the vertex springs of a jelly sprite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JellyConfig {
+    /// Quads along each side of the mesh, clamped to `1..=MAX_JELLY_SUBDIVISIONS`.
+    pub subdivisions: u32,
+    /// Spring pull toward the rest position, per second squared.
+    pub stiffness: f32,
+    /// Velocity damping, per second.
+    pub damping: f32,
+}
+
+impl Default for JellyConfig {
+    fn default() -> Self {
+        Self {
+            subdivisions: 4,
+            stiffness: 180.0,
+            damping: 6.0,
+        }
+    }
+}
+
+/// Vertex grid of a jelly sprite: rest positions plus spring displacement.
+///
+/// Vertices are stored row by row from the bottom-left corner, centered on
+/// the origin.
+#[derive(Debug, Clone)]
+pub struct JellyMesh {
+    config: JellyConfig,
+    size: [f32; 2],
+    rest: Vec<[f32; 2]>,
+    offsets: Vec<[f32; 2]>,
+    velocities: Vec<[f32; 2]>,
+}
+
+impl JellyMesh {
+    pub fn new(size: [f32; 2], config: JellyConfig) -> Self {
+        let config = JellyConfig {
+            subdivisions: config.subdivisions.clamp(1, MAX_JELLY_SUBDIVISIONS),
+            stiffness: config.stiffness.max(0.0),
+            damping: config.damping.max(0.0),
+        };
+        let columns = config.subdivisions as usize + 1;
+        let rest: Vec<[f32; 2]> = (0..columns * columns)
+            .map(|index| {
+                let (column, row) = (index % columns, index / columns);
+                let step = 1.0 / config.subdivisions as f32;
+                [
+                    (column as f32 * step - 0.5) * size[0],
+                    (row as f32 * step - 0.5) * size[1],
+                ]
+            })
+            .collect();
+        let count = rest.len();
+        Self {
+            config,
+            size,
+            rest,
+            offsets: vec![[0.0; 2]; count],
+            velocities: vec![[0.0; 2]; count],
+        }
+    }
+
+    pub fn config(&self) -> JellyConfig {
+        self.config
+    }
+
+    pub fn size(&self) -> [f32; 2] {
+        self.size
+    }
+
+    /// Vertices along one side of the grid.
+    pub fn columns(&self) -> usize {
+        self.config.subdivisions as usize + 1
+    }
+
+    pub fn rest_positions(&self) -> &[[f32; 2]] {
+        &self.rest
+    }
+
+    /// Current vertex positions: rest plus spring displacement.
+    pub fn positions(&self) -> Vec<[f32; 2]> {
+        self.rest
+            .iter()
+            .zip(&self.offsets)
+            .map(|(rest, offset)| [rest[0] + offset[0], rest[1] + offset[1]])
+            .collect()
+    }
+
+    /// Texture coordinates for each vertex, optionally mirrored.
+    pub fn uvs(&self, flip_x: bool, flip_y: bool) -> Vec<[f32; 2]> {
+        let columns = self.columns();
+        let last = (columns - 1) as f32;
+        (0..self.rest.len())
+            .map(|index| {
+                let u = (index % columns) as f32 / last;
+                let v = 1.0 - (index / columns) as f32 / last;
+                [
+                    if flip_x { 1.0 - u } else { u },
+                    if flip_y { 1.0 - v } else { v },
+                ]
+            })
+            .collect()
+    }
+
+    /// Two counter-clockwise triangles per grid cell.
+    pub fn indices(&self) -> Vec<u32> {
+        let columns = self.columns() as u32;
+        let cells = columns - 1;
+        let mut indices = Vec::with_capacity((cells * cells * 6) as usize);
+        for row in 0..cells {
+            for column in 0..cells {
+                let bottom_left = row * columns + column;
+                let bottom_right = bottom_left + 1;
+                let top_left = bottom_left + columns;
+                let top_right = top_left + 1;
+                indices.extend_from_slice(&[
+                    bottom_left,
+                    bottom_right,
+                    top_right,
+                    bottom_left,
+                    top_right,
+                    top_left,
+                ]);
+            }
+        }
+        indices
+    }
+
+    /// Kicks the vertices along `direction` at up to `strength` units per second.
+    ///
+    /// The kick grows with distance from the center, so the edges wobble while
+    /// the middle stays put. A zero direction does nothing.
+    pub fn apply_impulse(&mut self, direction: [f32; 2], strength: f32) {
+        let length = direction[0].hypot(direction[1]);
+        if length <= f32::EPSILON || !strength.is_finite() {
+            return;
+        }
+        let direction = [direction[0] / length, direction[1] / length];
+        let half_diagonal = (self.size[0] / 2.0).hypot(self.size[1] / 2.0);
+        if half_diagonal <= f32::EPSILON {
+            return;
+        }
+        for (rest, velocity) in self.rest.iter().zip(&mut self.velocities) {
+            let weight = rest[0].hypot(rest[1]) / half_diagonal;
+            velocity[0] += direction[0] * strength * weight;
+            velocity[1] += direction[1] * strength * weight;
+        }
+    }
+
+    /// Advances the springs by `delta` seconds.
+    ///
+    /// Uses implicit Euler, which stays stable for any step and never adds
+    /// energy. Once the energy drops below a small threshold the mesh snaps
+    /// back to rest.
+    pub fn step(&mut self, delta: f32) {
+        if !(delta.is_finite() && delta > 0.0) || self.is_resting() {
+            return;
+        }
+        let JellyConfig {
+            stiffness, damping, ..
+        } = self.config;
+        let denominator = 1.0 + delta * damping + delta * delta * stiffness;
+        for (offset, velocity) in self.offsets.iter_mut().zip(&mut self.velocities) {
+            for axis in 0..2 {
+                velocity[axis] = (velocity[axis] - delta * stiffness * offset[axis]) / denominator;
+                offset[axis] += delta * velocity[axis];
+            }
+        }
+        if self.energy() < RESTING_ENERGY {
+            self.reset();
+        }
+    }
+
+    /// Kinetic plus spring energy per unit mass, summed over the vertices.
+    pub fn energy(&self) -> f32 {
+        let stiffness = self.config.stiffness;
+        self.offsets
+            .iter()
+            .zip(&self.velocities)
+            .map(|(offset, velocity)| {
+                let kinetic = velocity[0] * velocity[0] + velocity[1] * velocity[1];
+                let potential = offset[0] * offset[0] + offset[1] * offset[1];
+                0.5 * kinetic + 0.5 * stiffness * potential
+            })
+            .sum()
+    }
+
+    /// Speed of the fastest vertex, in units per second.
+    pub fn max_speed(&self) -> f32 {
+        self.velocities
+            .iter()
+            .map(|velocity| velocity[0].hypot(velocity[1]))
+            .fold(0.0, f32::max)
+    }
+
+    /// True when every vertex is at rest and not moving.
+    pub fn is_resting(&self) -> bool {
+        self.offsets.iter().all(|offset| *offset == [0.0; 2])
+            && self.velocities.iter().all(|velocity| *velocity == [0.0; 2])
+    }
+
+    /// Puts every vertex back at rest.
+    pub fn reset(&mut self) {
+        self.offsets.fill([0.0; 2]);
+        self.velocities.fill([0.0; 2]);
+    }
+}
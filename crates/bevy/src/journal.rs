@@ -0,0 +1,1182 @@
+//! Operation journal module for reproducing rendering bugs.
+//!
+//! The render bridge can append every operation Ruby sends it (sprite, text
+//! and mesh sync operations, camera changes and shakes) to a file as JSON
+//! lines, one operation per line tagged with its frame. `JournalReplay` reads
+//! such a file back and hands its operations out frame by frame, so feeding
+//! them through the sync layers reproduces how the entity state evolved.
+//!
+//! Floats are written in their shortest round-trip form and IDs as integers,
+//! so a replayed operation is bit for bit the recorded one.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::accessibility::{AccessibilityData, AccessibleRole};
+use crate::determinism::Checksum;
+use crate::hash::FrameChecksum;
+use crate::draw_order::DrawOrderChange;
+use crate::error::BevyRubyError;
+use crate::jelly::JellyConfig;
+use crate::mesh_renderer::{
+    GradientDirection, LineCapStyle, LineJoinStyle, MeshData, MeshOperation, MeshTransformData, ShapeType,
+};
+use crate::sprite_renderer::{SpriteData, SpriteOperation, TransformData};
+use crate::text_renderer::{
+    TextBlend, TextData, TextGlow, TextJustify, TextLineBreak, TextOperation, TextTransformData,
+};
+
+/// Size cap of a journal file before it is rotated, in bytes.
+pub const DEFAULT_JOURNAL_MAX_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Rotated files kept next to a journal; `<path>.1` is the newest.
+pub const JOURNAL_ROTATIONS: usize = 2;
+
+/// Operation the render bridge received from Ruby.
+#[derive(Debug, Clone)]
+pub enum JournalOperation {
+    Sprite(SpriteOperation),
+    Text(TextOperation),
+    Mesh(MeshOperation),
+    /// Camera transform set by Ruby this frame.
+    Camera {
+        position: (f32, f32, f32),
+        scale: f32,
+        rotation: f32,
+    },
+    CameraShake {
+        intensity: f32,
+        duration_secs: f32,
+    },
+}
+
+impl Checksum for JournalOperation {
+    fn checksum(&self, hasher: &mut FrameChecksum) {
+        match self {
+            JournalOperation::Sprite(op) => op.checksum(hasher),
+            JournalOperation::Text(op) => op.checksum(hasher),
+            JournalOperation::Mesh(op) => op.checksum(hasher),
+            JournalOperation::Camera {
+                position,
+                scale,
+                rotation,
+            } => {
+                hasher.write_u8(80);
+                for value in [position.0, position.1, position.2, *scale, *rotation] {
+                    hasher.write_f32(value);
+                }
+            }
+            JournalOperation::CameraShake {
+                intensity,
+                duration_secs,
+            } => {
+                hasher.write_u8(81);
+                hasher.write_f32(*intensity);
+                hasher.write_f32(*duration_secs);
+            }
+        }
+    }
+}
+
+/// Checksum of one frame's operations, matching for a recording and its replay.
+pub fn journal_frame_checksum(operations: &[JournalOperation]) -> u64 {
+    let mut hasher = FrameChecksum::new();
+    for op in operations {
+        op.checksum(&mut hasher);
+    }
+    hasher.finish()
+}
+
+impl JournalOperation {
+    /// One compact JSON object tagged with `frame`, without a trailing newline.
+    pub fn to_json_line(&self, frame: u32) -> String {
+        let mut fields = vec![("frame", number(frame))];
+        match self {
+            JournalOperation::Sprite(op) => sprite_fields(op, &mut fields),
+            JournalOperation::Text(op) => text_fields(op, &mut fields),
+            JournalOperation::Mesh(op) => mesh_fields(op, &mut fields),
+            JournalOperation::Camera {
+                position,
+                scale,
+                rotation,
+            } => {
+                fields.push(("op", string("camera")));
+                fields.push(("position", floats(&[position.0, position.1, position.2])));
+                fields.push(("scale", float(*scale)));
+                fields.push(("rotation", float(*rotation)));
+            }
+            JournalOperation::CameraShake {
+                intensity,
+                duration_secs,
+            } => {
+                fields.push(("op", string("camera_shake")));
+                fields.push(("intensity", float(*intensity)));
+                fields.push(("duration", float(*duration_secs)));
+            }
+        }
+
+        let mut line = String::new();
+        object(fields).write(&mut line);
+        line
+    }
+
+    /// Parses a line written by `to_json_line` into its frame and operation.
+    pub fn from_json_line(line: &str) -> Result<(u32, Self), BevyRubyError> {
+        let json = JsonParser::parse(line).map_err(|message| invalid("JSON object", message))?;
+        let fields = json.as_object()?;
+        let frame = fields.u32("frame")?;
+        let id = || fields.u64("id");
+        let op = match fields.str("op")? {
+            "sprite_sync" => JournalOperation::Sprite(SpriteOperation::Sync {
+                ruby_entity_id: id()?,
+                sprite_data: parse_sprite_data(&fields.object("sprite")?)?,
+                transform_data: parse_transform_data(&fields.floats::<10>("transform")?),
+            }),
+            "sprite_remove" => JournalOperation::Sprite(SpriteOperation::Remove {
+                ruby_entity_id: id()?,
+            }),
+            "sprite_remove_deferred" => JournalOperation::Sprite(SpriteOperation::RemoveDeferred {
+                ruby_entity_id: id()?,
+                frames: fields.u32("frames")?,
+            }),
+            "sprite_remove_defer" => JournalOperation::Sprite(SpriteOperation::SetRemoveDefer {
+                frames: fields.u32("frames")?,
+            }),
+            "sprite_color" => JournalOperation::Sprite(SpriteOperation::SetColor {
+                ruby_entity_id: id()?,
+                color: fields.floats::<4>("color")?,
+            }),
+            "sprite_clear_color" => JournalOperation::Sprite(SpriteOperation::ClearColor {
+                ruby_entity_id: id()?,
+            }),
+            "sprite_lod" => JournalOperation::Sprite(SpriteOperation::SetLod {
+                ruby_entity_id: id()?,
+                sprite_data: fields
+                    .optional_object("sprite")?
+                    .map(|sprite| parse_sprite_data(&sprite))
+                    .transpose()?,
+                hidden: fields.bool("hidden")?,
+            }),
+            "sprite_clear_lod" => JournalOperation::Sprite(SpriteOperation::ClearLod {
+                ruby_entity_id: id()?,
+            }),
+            "sprite_impulse" => {
+                let [x, y] = fields.floats::<2>("direction")?;
+                JournalOperation::Sprite(SpriteOperation::ApplyImpulse {
+                    ruby_entity_id: id()?,
+                    direction: [x, y],
+                    strength: fields.f32("strength")?,
+                })
+            }
+            "sprite_draw_order" => JournalOperation::Sprite(SpriteOperation::SetDrawOrder {
+                ruby_entity_id: id()?,
+                change: match fields.str("order")? {
+                    "front" => DrawOrderChange::Front,
+                    "back" => DrawOrderChange::Back,
+                    "index" => DrawOrderChange::Index(fields.u64("index")? as usize),
+                    other => return Err(invalid("draw order", other)),
+                },
+            }),
+            "sprite_clear" => JournalOperation::Sprite(SpriteOperation::Clear),
+            "text_sync" => {
+                let [translation_x, translation_y, translation_z, scale_x, scale_y, scale_z] =
+                    fields.floats::<6>("transform")?;
+                JournalOperation::Text(TextOperation::Sync {
+                    ruby_entity_id: id()?,
+                    text_data: parse_text_data(&fields.object("text")?)?,
+                    transform_data: TextTransformData {
+                        translation_x,
+                        translation_y,
+                        translation_z,
+                        scale_x,
+                        scale_y,
+                        scale_z,
+                    },
+                })
+            }
+            "text_remove" => JournalOperation::Text(TextOperation::Remove {
+                ruby_entity_id: id()?,
+            }),
+            "text_clear" => JournalOperation::Text(TextOperation::Clear),
+            "mesh_sync" => {
+                let transform = parse_transform_data(&fields.floats::<10>("transform")?);
+                JournalOperation::Mesh(MeshOperation::Sync {
+                    ruby_entity_id: id()?,
+                    mesh_data: parse_mesh_data(&fields.object("mesh")?)?,
+                    transform_data: MeshTransformData {
+                        translation_x: transform.translation_x,
+                        translation_y: transform.translation_y,
+                        translation_z: transform.translation_z,
+                        rotation_x: transform.rotation_x,
+                        rotation_y: transform.rotation_y,
+                        rotation_z: transform.rotation_z,
+                        rotation_w: transform.rotation_w,
+                        scale_x: transform.scale_x,
+                        scale_y: transform.scale_y,
+                        scale_z: transform.scale_z,
+                    },
+                })
+            }
+            "mesh_remove" => JournalOperation::Mesh(MeshOperation::Remove {
+                ruby_entity_id: id()?,
+            }),
+            "mesh_clear" => JournalOperation::Mesh(MeshOperation::Clear),
+            "camera" => {
+                let [x, y, z] = fields.floats::<3>("position")?;
+                JournalOperation::Camera {
+                    position: (x, y, z),
+                    scale: fields.f32("scale")?,
+                    rotation: fields.f32("rotation")?,
+                }
+            }
+            "camera_shake" => JournalOperation::CameraShake {
+                intensity: fields.f32("intensity")?,
+                duration_secs: fields.f32("duration")?,
+            },
+            other => return Err(invalid("journal operation", other)),
+        };
+        Ok((frame, op))
+    }
+}
+
+/// Appends operations to a size-capped JSON-lines file.
+///
+/// Once the file would grow past its cap it is renamed to `<path>.1`, an
+/// older `<path>.1` to `<path>.2`, and so on up to `JOURNAL_ROTATIONS`.
+pub struct OperationJournal {
+    path: PathBuf,
+    max_bytes: u64,
+    file: BufWriter<File>,
+    len: u64,
+}
+
+impl OperationJournal {
+    /// Starts a journal at `path`, replacing it and the rotated files of an earlier one.
+    pub fn create(path: impl Into<PathBuf>, max_bytes: u64) -> io::Result<Self> {
+        let path = path.into();
+        for index in 1..=JOURNAL_ROTATIONS {
+            match fs::remove_file(rotated_journal_path(&path, index)) {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+                _ => {}
+            }
+        }
+        let file = BufWriter::new(File::create(&path)?);
+        Ok(Self {
+            path,
+            max_bytes: max_bytes.max(1),
+            file,
+            len: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends one frame's operations and flushes, so the journal survives a crash.
+    ///
+    /// A frame is never split across files: the file is rotated first when the
+    /// frame wouldn't fit. A single frame larger than the cap is written whole.
+    pub fn record_frame(&mut self, frame: u32, operations: &[JournalOperation]) -> io::Result<()> {
+        if operations.is_empty() {
+            return Ok(());
+        }
+        let mut lines = String::new();
+        for op in operations {
+            lines.push_str(&op.to_json_line(frame));
+            lines.push('\n');
+        }
+        if self.len > 0 && self.len + lines.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(lines.as_bytes())?;
+        self.file.flush()?;
+        self.len += lines.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        for index in (1..JOURNAL_ROTATIONS).rev() {
+            let from = rotated_journal_path(&self.path, index);
+            if from.exists() {
+                fs::rename(&from, rotated_journal_path(&self.path, index + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_journal_path(&self.path, 1))?;
+        self.file = BufWriter::new(File::create(&self.path)?);
+        self.len = 0;
+        Ok(())
+    }
+}
+
+/// `<path>.<index>`, e.g. `journal.jsonl.1`.
+pub fn rotated_journal_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Operations of a recorded journal, handed out one frame at a time.
+#[derive(Debug, Clone, Default)]
+pub struct JournalReplay {
+    frames: VecDeque<(u32, Vec<JournalOperation>)>,
+    /// Recorded frame number the next `next_frame` call replays.
+    cursor: u32,
+}
+
+impl JournalReplay {
+    /// Parses a journal written by `OperationJournal`; blank lines are ignored.
+    pub fn parse(text: &str) -> Result<Self, BevyRubyError> {
+        let mut frames: VecDeque<(u32, Vec<JournalOperation>)> = VecDeque::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (frame, op) = JournalOperation::from_json_line(line).map_err(|error| {
+                invalid("journal line", format!("line {}: {}", index + 1, error))
+            })?;
+            match frames.back_mut() {
+                Some((last, operations)) if *last == frame => operations.push(op),
+                _ => frames.push_back((frame, vec![op])),
+            }
+        }
+        let cursor = frames.front().map_or(0, |(frame, _)| *frame);
+        Ok(Self { frames, cursor })
+    }
+
+    /// Remaining recorded frames with their operations, oldest first.
+    pub fn frames(&self) -> impl Iterator<Item = (u32, &[JournalOperation])> {
+        self.frames
+            .iter()
+            .map(|(frame, operations)| (*frame, operations.as_slice()))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Operations of the next frame, or `None` once every frame was replayed.
+    ///
+    /// Frames that recorded nothing replay as empty, so the gaps between
+    /// recorded frames are kept.
+    pub fn next_frame(&mut self) -> Option<Vec<JournalOperation>> {
+        let (frame, _) = self.frames.front()?;
+        let operations = if *frame <= self.cursor {
+            self.frames.pop_front().map(|(_, operations)| operations)
+        } else {
+            None
+        };
+        self.cursor = self.cursor.wrapping_add(1);
+        Some(operations.unwrap_or_default())
+    }
+}
+
+fn sprite_fields(op: &SpriteOperation, fields: &mut Vec<(&'static str, Json)>) {
+    match op {
+        SpriteOperation::Sync {
+            ruby_entity_id,
+            sprite_data,
+            transform_data,
+        } => {
+            fields.push(("op", string("sprite_sync")));
+            fields.push(("id", number(ruby_entity_id)));
+            fields.push(("sprite", sprite_data_json(sprite_data)));
+            fields.push(("transform", transform_json(transform_data)));
+        }
+        SpriteOperation::Remove { ruby_entity_id } => {
+            fields.push(("op", string("sprite_remove")));
+            fields.push(("id", number(ruby_entity_id)));
+        }
+        SpriteOperation::RemoveDeferred {
+            ruby_entity_id,
+            frames,
+        } => {
+            fields.push(("op", string("sprite_remove_deferred")));
+            fields.push(("id", number(ruby_entity_id)));
+            fields.push(("frames", number(frames)));
+        }
+        SpriteOperation::SetRemoveDefer { frames } => {
+            fields.push(("op", string("sprite_remove_defer")));
+            fields.push(("frames", number(frames)));
+        }
+        SpriteOperation::SetColor {
+            ruby_entity_id,
+            color,
+        } => {
+            fields.push(("op", string("sprite_color")));
+            fields.push(("id", number(ruby_entity_id)));
+            fields.push(("color", floats(color)));
+        }
+        SpriteOperation::ClearColor { ruby_entity_id } => {
+            fields.push(("op", string("sprite_clear_color")));
+            fields.push(("id", number(ruby_entity_id)));
+        }
+        SpriteOperation::SetLod {
+            ruby_entity_id,
+            sprite_data,
+            hidden,
+        } => {
+            fields.push(("op", string("sprite_lod")));
+            fields.push(("id", number(ruby_entity_id)));
+            fields.push((
+                "sprite",
+                sprite_data.as_ref().map_or(Json::Null, sprite_data_json),
+            ));
+            fields.push(("hidden", Json::Bool(*hidden)));
+        }
+        SpriteOperation::ClearLod { ruby_entity_id } => {
+            fields.push(("op", string("sprite_clear_lod")));
+            fields.push(("id", number(ruby_entity_id)));
+        }
+        SpriteOperation::ApplyImpulse {
+            ruby_entity_id,
+            direction,
+            strength,
+        } => {
+            fields.push(("op", string("sprite_impulse")));
+            fields.push(("id", number(ruby_entity_id)));
+            fields.push(("direction", floats(direction)));
+            fields.push(("strength", float(*strength)));
+        }
+        SpriteOperation::SetDrawOrder {
+            ruby_entity_id,
+            change,
+        } => {
+            fields.push(("op", string("sprite_draw_order")));
+            fields.push(("id", number(ruby_entity_id)));
+            match change {
+                DrawOrderChange::Front => fields.push(("order", string("front"))),
+                DrawOrderChange::Back => fields.push(("order", string("back"))),
+                DrawOrderChange::Index(index) => {
+                    fields.push(("order", string("index")));
+                    fields.push(("index", number(index)));
+                }
+            }
+        }
+        SpriteOperation::Clear => fields.push(("op", string("sprite_clear"))),
+    }
+}
+
+fn text_fields(op: &TextOperation, fields: &mut Vec<(&'static str, Json)>) {
+    match op {
+        TextOperation::Sync {
+            ruby_entity_id,
+            text_data,
+            transform_data,
+        } => {
+            fields.push(("op", string("text_sync")));
+            fields.push(("id", number(ruby_entity_id)));
+            fields.push(("text", text_data_json(text_data)));
+            fields.push((
+                "transform",
+                floats(&[
+                    transform_data.translation_x,
+                    transform_data.translation_y,
+                    transform_data.translation_z,
+                    transform_data.scale_x,
+                    transform_data.scale_y,
+                    transform_data.scale_z,
+                ]),
+            ));
+        }
+        TextOperation::Remove { ruby_entity_id } => {
+            fields.push(("op", string("text_remove")));
+            fields.push(("id", number(ruby_entity_id)));
+        }
+        TextOperation::Clear => fields.push(("op", string("text_clear"))),
+    }
+}
+
+fn mesh_fields(op: &MeshOperation, fields: &mut Vec<(&'static str, Json)>) {
+    match op {
+        MeshOperation::Sync {
+            ruby_entity_id,
+            mesh_data,
+            transform_data,
+        } => {
+            fields.push(("op", string("mesh_sync")));
+            fields.push(("id", number(ruby_entity_id)));
+            fields.push(("mesh", mesh_data_json(mesh_data)));
+            fields.push((
+                "transform",
+                floats(&[
+                    transform_data.translation_x,
+                    transform_data.translation_y,
+                    transform_data.translation_z,
+                    transform_data.rotation_x,
+                    transform_data.rotation_y,
+                    transform_data.rotation_z,
+                    transform_data.rotation_w,
+                    transform_data.scale_x,
+                    transform_data.scale_y,
+                    transform_data.scale_z,
+                ]),
+            ));
+        }
+        MeshOperation::Remove { ruby_entity_id } => {
+            fields.push(("op", string("mesh_remove")));
+            fields.push(("id", number(ruby_entity_id)));
+        }
+        MeshOperation::Clear => fields.push(("op", string("mesh_clear"))),
+    }
+}
+
+fn sprite_data_json(data: &SpriteData) -> Json {
+    object(vec![
+        (
+            "color",
+            floats(&[data.color_r, data.color_g, data.color_b, data.color_a]),
+        ),
+        ("flip_x", Json::Bool(data.flip_x)),
+        ("flip_y", Json::Bool(data.flip_y)),
+        ("anchor", floats(&[data.anchor_x, data.anchor_y])),
+        ("has_custom_size", Json::Bool(data.has_custom_size)),
+        ("custom_size", floats(&[data.custom_size_x, data.custom_size_y])),
+        ("group", optional_string(data.group.as_deref())),
+        ("accessibility", accessibility_json(data.accessibility.as_ref())),
+        ("texture", optional_string(data.texture_path.as_deref())),
+        ("mesh_mode", Json::Bool(data.mesh_mode)),
+        (
+            "jelly",
+            object(vec![
+                ("subdivisions", number(data.jelly.subdivisions)),
+                ("stiffness", float(data.jelly.stiffness)),
+                ("damping", float(data.jelly.damping)),
+            ]),
+        ),
+        ("z_index", data.z_index.map_or(Json::Null, number)),
+        (
+            "atlas_rect",
+            data.atlas_rect
+                .map_or(Json::Null, |(x, y, width, height)| floats(&[x, y, width, height])),
+        ),
+    ])
+}
+
+fn parse_sprite_data(fields: &JsonFields) -> Result<SpriteData, BevyRubyError> {
+    let [color_r, color_g, color_b, color_a] = fields.floats::<4>("color")?;
+    let [anchor_x, anchor_y] = fields.floats::<2>("anchor")?;
+    let [custom_size_x, custom_size_y] = fields.floats::<2>("custom_size")?;
+    let jelly = fields.object("jelly")?;
+    Ok(SpriteData {
+        color_r,
+        color_g,
+        color_b,
+        color_a,
+        flip_x: fields.bool("flip_x")?,
+        flip_y: fields.bool("flip_y")?,
+        anchor_x,
+        anchor_y,
+        has_custom_size: fields.bool("has_custom_size")?,
+        custom_size_x,
+        custom_size_y,
+        group: fields.optional_string("group")?,
+        accessibility: parse_accessibility(fields)?,
+        texture_path: fields.optional_string("texture")?,
+        mesh_mode: fields.bool("mesh_mode")?,
+        jelly: JellyConfig {
+            subdivisions: jelly.u32("subdivisions")?,
+            stiffness: jelly.f32("stiffness")?,
+            damping: jelly.f32("damping")?,
+        },
+        z_index: match fields.get("z_index")? {
+            Json::Null => None,
+            value => Some(value.as_number("z_index")?),
+        },
+        atlas_rect: match fields.get("atlas_rect")? {
+            Json::Null => None,
+            _ => {
+                let [x, y, width, height] = fields.floats::<4>("atlas_rect")?;
+                Some((x, y, width, height))
+            }
+        },
+    })
+}
+
+fn transform_json(data: &TransformData) -> Json {
+    floats(&[
+        data.translation_x,
+        data.translation_y,
+        data.translation_z,
+        data.rotation_x,
+        data.rotation_y,
+        data.rotation_z,
+        data.rotation_w,
+        data.scale_x,
+        data.scale_y,
+        data.scale_z,
+    ])
+}
+
+fn parse_transform_data(values: &[f32; 10]) -> TransformData {
+    let [
+        translation_x,
+        translation_y,
+        translation_z,
+        rotation_x,
+        rotation_y,
+        rotation_z,
+        rotation_w,
+        scale_x,
+        scale_y,
+        scale_z,
+    ] = *values;
+    TransformData {
+        translation_x,
+        translation_y,
+        translation_z,
+        rotation_x,
+        rotation_y,
+        rotation_z,
+        rotation_w,
+        scale_x,
+        scale_y,
+        scale_z,
+    }
+}
+
+fn text_data_json(data: &TextData) -> Json {
+    object(vec![
+        ("content", string(&data.content)),
+        ("font_size", float(data.font_size)),
+        (
+            "color",
+            floats(&[data.color_r, data.color_g, data.color_b, data.color_a]),
+        ),
+        ("accessibility", accessibility_json(data.accessibility.as_ref())),
+        (
+            "glow",
+            data.glow.map_or(Json::Null, |glow| {
+                object(vec![
+                    (
+                        "color",
+                        floats(&[glow.color_r, glow.color_g, glow.color_b, glow.color_a]),
+                    ),
+                    ("radius", float(glow.radius)),
+                    ("intensity", float(glow.intensity)),
+                ])
+            }),
+        ),
+        ("blend", string(data.blend.name())),
+        ("justify", string(data.justify.name())),
+        ("linebreak", string(data.linebreak.name())),
+    ])
+}
+
+fn parse_text_data(fields: &JsonFields) -> Result<TextData, BevyRubyError> {
+    let [color_r, color_g, color_b, color_a] = fields.floats::<4>("color")?;
+    let glow = match fields.optional_object("glow")? {
+        Some(glow) => {
+            let [color_r, color_g, color_b, color_a] = glow.floats::<4>("color")?;
+            Some(TextGlow {
+                color_r,
+                color_g,
+                color_b,
+                color_a,
+                radius: glow.f32("radius")?,
+                intensity: glow.f32("intensity")?,
+            })
+        }
+        None => None,
+    };
+    let blend = fields.str("blend")?;
+    let justify = fields.str("justify")?;
+    let linebreak = fields.str("linebreak")?;
+    Ok(TextData {
+        content: fields.str("content")?.to_string(),
+        font_size: fields.f32("font_size")?,
+        color_r,
+        color_g,
+        color_b,
+        color_a,
+        accessibility: parse_accessibility(fields)?,
+        glow,
+        blend: TextBlend::from_name(blend).ok_or_else(|| invalid("text blend", blend))?,
+        justify: TextJustify::from_name(justify).ok_or_else(|| invalid("text justify", justify))?,
+        linebreak: TextLineBreak::from_name(linebreak).ok_or_else(|| invalid("text linebreak", linebreak))?,
+    })
+}
+
+fn mesh_data_json(data: &MeshData) -> Json {
+    object(vec![
+        ("shape", string(shape_type_name(data.shape_type))),
+        (
+            "color",
+            floats(&[data.color_r, data.color_g, data.color_b, data.color_a]),
+        ),
+        ("width", float(data.width)),
+        ("height", float(data.height)),
+        ("radius", float(data.radius)),
+        ("sides", number(data.sides)),
+        (
+            "line",
+            floats(&[
+                data.line_start_x,
+                data.line_start_y,
+                data.line_end_x,
+                data.line_end_y,
+            ]),
+        ),
+        ("thickness", float(data.thickness)),
+        ("fill", Json::Bool(data.fill)),
+        ("line_cap", string(data.line_cap.name())),
+        ("line_join", string(data.line_join.name())),
+        (
+            "gradient_color",
+            data.gradient_color.map_or(Json::Null, |color| floats(&color)),
+        ),
+        ("gradient_direction", string(data.gradient_direction.name())),
+    ])
+}
+
+fn parse_mesh_data(fields: &JsonFields) -> Result<MeshData, BevyRubyError> {
+    let [color_r, color_g, color_b, color_a] = fields.floats::<4>("color")?;
+    let [line_start_x, line_start_y, line_end_x, line_end_y] = fields.floats::<4>("line")?;
+    let shape = fields.str("shape")?;
+    let line_cap = fields.str("line_cap")?;
+    let line_join = fields.str("line_join")?;
+    let gradient_direction = fields.str("gradient_direction")?;
+    Ok(MeshData {
+        shape_type: shape_type_from_name(shape).ok_or_else(|| invalid("mesh shape", shape))?,
+        color_r,
+        color_g,
+        color_b,
+        color_a,
+        width: fields.f32("width")?,
+        height: fields.f32("height")?,
+        radius: fields.f32("radius")?,
+        sides: fields.u32("sides")?,
+        line_start_x,
+        line_start_y,
+        line_end_x,
+        line_end_y,
+        thickness: fields.f32("thickness")?,
+        fill: fields.bool("fill")?,
+        line_cap: LineCapStyle::from_name(line_cap).ok_or_else(|| invalid("line cap", line_cap))?,
+        line_join: LineJoinStyle::from_name(line_join)
+            .ok_or_else(|| invalid("line join", line_join))?,
+        gradient_color: fields.optional_floats::<4>("gradient_color")?,
+        gradient_direction: GradientDirection::from_name(gradient_direction)
+            .ok_or_else(|| invalid("gradient direction", gradient_direction))?,
+    })
+}
+
+fn shape_type_name(shape_type: ShapeType) -> &'static str {
+    match shape_type {
+        ShapeType::Rectangle => "rectangle",
+        ShapeType::Circle => "circle",
+        ShapeType::RegularPolygon => "regular_polygon",
+        ShapeType::Triangle => "triangle",
+        ShapeType::Line => "line",
+        ShapeType::Ellipse => "ellipse",
+    }
+}
+
+fn shape_type_from_name(name: &str) -> Option<ShapeType> {
+    match name {
+        "rectangle" => Some(ShapeType::Rectangle),
+        "circle" => Some(ShapeType::Circle),
+        "regular_polygon" => Some(ShapeType::RegularPolygon),
+        "triangle" => Some(ShapeType::Triangle),
+        "line" => Some(ShapeType::Line),
+        "ellipse" => Some(ShapeType::Ellipse),
+        _ => None,
+    }
+}
+
+fn accessibility_json(accessibility: Option<&AccessibilityData>) -> Json {
+    accessibility.map_or(Json::Null, |accessibility| {
+        object(vec![
+            ("label", string(&accessibility.label)),
+            ("role", string(accessibility.role.name())),
+        ])
+    })
+}
+
+fn parse_accessibility(fields: &JsonFields) -> Result<Option<AccessibilityData>, BevyRubyError> {
+    let Some(accessibility) = fields.optional_object("accessibility")? else {
+        return Ok(None);
+    };
+    let role = accessibility.str("role")?;
+    Ok(Some(AccessibilityData::new(
+        accessibility.str("label")?,
+        AccessibleRole::from_name(role).ok_or_else(|| invalid("accessible role", role))?,
+    )))
+}
+
+fn invalid(expected: &str, actual: impl std::fmt::Display) -> BevyRubyError {
+    BevyRubyError::InvalidType {
+        expected: expected.to_string(),
+        actual: actual.to_string(),
+    }
+}
+
+/// JSON value; numbers keep their source text so integers and floats both round-trip exactly.
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+fn number(value: impl ToString) -> Json {
+    Json::Number(value.to_string())
+}
+
+/// Non-finite floats, which JSON numbers can't hold, are written as `"NaN"`, `"inf"` or `"-inf"`.
+fn float(value: f32) -> Json {
+    if value.is_finite() {
+        Json::Number(value.to_string())
+    } else {
+        Json::String(value.to_string())
+    }
+}
+
+fn floats(values: &[f32]) -> Json {
+    Json::Array(values.iter().map(|value| float(*value)).collect())
+}
+
+fn string(value: &str) -> Json {
+    Json::String(value.to_string())
+}
+
+fn optional_string(value: Option<&str>) -> Json {
+    value.map_or(Json::Null, string)
+}
+
+fn object(fields: Vec<(&str, Json)>) -> Json {
+    Json::Object(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
+
+impl Json {
+    fn write(&self, out: &mut String) {
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            Json::Number(text) => out.push_str(text),
+            Json::String(value) => write_json_string(out, value),
+            Json::Array(values) => {
+                out.push('[');
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        out.push(',');
+                    }
+                    value.write(out);
+                }
+                out.push(']');
+            }
+            Json::Object(fields) => {
+                out.push('{');
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        out.push(',');
+                    }
+                    write_json_string(out, key);
+                    out.push(':');
+                    value.write(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+
+    fn as_object(&self) -> Result<JsonFields<'_>, BevyRubyError> {
+        match self {
+            Json::Object(fields) => Ok(JsonFields(fields)),
+            other => Err(invalid("JSON object", format!("{:?}", other))),
+        }
+    }
+
+    fn as_number<T: std::str::FromStr>(&self, key: &str) -> Result<T, BevyRubyError> {
+        let text = match self {
+            Json::Number(text) | Json::String(text) => text,
+            other => return Err(invalid(&format!("number for {}", key), format!("{:?}", other))),
+        };
+        text.parse()
+            .map_err(|_| invalid(&format!("number for {}", key), text))
+    }
+}
+
+pub(crate) fn write_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", ch as u32);
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+/// Fields of a JSON object, looked up by key.
+struct JsonFields<'a>(&'a [(String, Json)]);
+
+impl JsonFields<'_> {
+    fn get(&self, key: &str) -> Result<&Json, BevyRubyError> {
+        self.0
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value)
+            .ok_or_else(|| invalid(&format!("field {}", key), "nothing"))
+    }
+
+    fn f32(&self, key: &str) -> Result<f32, BevyRubyError> {
+        self.get(key)?.as_number(key)
+    }
+
+    fn u32(&self, key: &str) -> Result<u32, BevyRubyError> {
+        self.get(key)?.as_number(key)
+    }
+
+    fn u64(&self, key: &str) -> Result<u64, BevyRubyError> {
+        self.get(key)?.as_number(key)
+    }
+
+    fn bool(&self, key: &str) -> Result<bool, BevyRubyError> {
+        match self.get(key)? {
+            Json::Bool(value) => Ok(*value),
+            other => Err(invalid(&format!("boolean for {}", key), format!("{:?}", other))),
+        }
+    }
+
+    fn str(&self, key: &str) -> Result<&str, BevyRubyError> {
+        match self.get(key)? {
+            Json::String(value) => Ok(value),
+            other => Err(invalid(&format!("string for {}", key), format!("{:?}", other))),
+        }
+    }
+
+    fn optional_string(&self, key: &str) -> Result<Option<String>, BevyRubyError> {
+        match self.get(key)? {
+            Json::Null => Ok(None),
+            _ => self.str(key).map(|value| Some(value.to_string())),
+        }
+    }
+
+    fn floats<const N: usize>(&self, key: &str) -> Result<[f32; N], BevyRubyError> {
+        let values = match self.get(key)? {
+            Json::Array(values) if values.len() == N => values,
+            other => {
+                return Err(invalid(
+                    &format!("{} numbers for {}", N, key),
+                    format!("{:?}", other),
+                ));
+            }
+        };
+        let mut result = [0.0; N];
+        for (slot, value) in result.iter_mut().zip(values) {
+            *slot = value.as_number(key)?;
+        }
+        Ok(result)
+    }
+
+    fn optional_floats<const N: usize>(&self, key: &str) -> Result<Option<[f32; N]>, BevyRubyError> {
+        match self.get(key)? {
+            Json::Null => Ok(None),
+            _ => self.floats(key).map(Some),
+        }
+    }
+
+    fn object(&self, key: &str) -> Result<JsonFields<'_>, BevyRubyError> {
+        self.get(key)?.as_object()
+    }
+
+    fn optional_object(&self, key: &str) -> Result<Option<JsonFields<'_>>, BevyRubyError> {
+        match self.get(key)? {
+            Json::Null => Ok(None),
+            value => value.as_object().map(Some),
+        }
+    }
+}
+
+/// Minimal JSON reader for journal lines.
+struct JsonParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn parse(text: &'a str) -> Result<Json, String> {
+        let mut parser = Self { text, pos: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos < text.len() {
+            return Err(format!("trailing characters at {}", parser.pos));
+        }
+        Ok(value)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("expected '{}' at {}", byte as char, self.pos))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(b'-' | b'0'..=b'9') => Ok(self.number()),
+            _ => Err(format!("unexpected input at {}", self.pos)),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        if self.text[self.pos..].starts_with(word) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(format!("unexpected input at {}", self.pos))
+        }
+    }
+
+    fn number(&mut self) -> Json {
+        let start = self.pos;
+        while matches!(
+            self.peek(),
+            Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        ) {
+            self.pos += 1;
+        }
+        Json::Number(self.text[start..self.pos].to_string())
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.expect(b'[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(values));
+                }
+                _ => return Err(format!("expected ',' or ']' at {}", self.pos)),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(b':')?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(format!("expected ',' or '}}' at {}", self.pos)),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+        let mut value = String::new();
+        let mut chars = self.text[self.pos..].char_indices();
+        while let Some((offset, ch)) = chars.next() {
+            match ch {
+                '"' => {
+                    self.pos += offset + 1;
+                    return Ok(value);
+                }
+                '\\' => {
+                    let escaped = match chars.next().map(|(_, ch)| ch) {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            let mut code = Self::hex4(&mut chars)?;
+                            if (0xd800..0xdc00).contains(&code) {
+                                let low = match (chars.next(), chars.next()) {
+                                    (Some((_, '\\')), Some((_, 'u'))) => Self::hex4(&mut chars)?,
+                                    _ => return Err("unpaired surrogate".to_string()),
+                                };
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff);
+                            }
+                            char::from_u32(code).ok_or("invalid \\u escape")?
+                        }
+                        _ => return Err(format!("invalid escape at {}", self.pos + offset)),
+                    };
+                    value.push(escaped);
+                }
+                ch => value.push(ch),
+            }
+        }
+        Err("unterminated string".to_string())
+    }
+
+    fn hex4(chars: &mut std::str::CharIndices) -> Result<u32, String> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = chars
+                .next()
+                .and_then(|(_, ch)| ch.to_digit(16))
+                .ok_or("invalid \\u escape")?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+}
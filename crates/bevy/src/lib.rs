@@ -1,42 +1,161 @@
+pub mod accessibility;
 pub mod app;
+pub mod audio;
+pub mod behavior;
+pub mod clip;
 pub mod component;
+pub mod debug_bundle;
+pub mod determinism;
+pub mod drag;
+pub mod draw_order;
+pub mod easing;
 pub mod entity;
 pub mod error;
 pub mod event;
+pub mod floating_text;
+pub mod geometry;
+pub mod group_throttle;
+pub mod hash;
+pub mod hit_region;
 pub mod input_bridge;
+pub mod jelly;
+pub mod journal;
+pub mod line_of_sight;
+pub mod lod;
 pub mod mesh_renderer;
+pub mod parallax;
+pub mod pixel_snap;
 pub mod query;
 pub mod render_app;
 pub mod resource;
+pub mod rewind;
+pub mod rumble;
 pub mod schedule;
+pub mod sleep;
+pub mod sound_set;
 pub mod sprite_renderer;
 pub mod system;
+pub mod system_timing;
 pub mod text_renderer;
+pub mod texture_loading;
 pub mod types;
+pub mod virtual_cursor;
+pub mod warnings;
 pub mod world;
+pub mod world_snapshot;
+pub mod world_sync;
 
+pub use accessibility::{AccessibilityData, AccessibilityUpdate, AccessibleRole};
 pub use app::AppBuilder;
+pub use audio::AudioCommand;
+pub use behavior::{Behavior, BehaviorAxis, BehaviorOperation, EntityBehaviors};
+pub use clip::{
+    ClipEasing, ClipOperation, ClipPlayer, ClipProperty, ClipSample, ClipTrack, KeyframeClip,
+};
 pub use component::{ComponentData, ComponentRegistry};
+pub use debug_bundle::{
+    BUNDLE_FORMAT_VERSION, BUNDLE_MANIFEST_FILE, BundleContent, DEFAULT_BUNDLE_LOG_BYTES, DebugBundle, SectionStatus,
+    encode_png, log_tail,
+};
+pub use determinism::{
+    AuditMap, AuditSet, Checksum, ChecksumDivergence, DeterminismAudit,
+    compare_checksum_streams, format_checksum_stream, parse_checksum_stream,
+};
+pub use drag::{
+    ActiveDrag, DEFAULT_DRAG_LAYER, DRAG_SNAP_BACK_CLIP_FLAG, DRAG_SNAP_BACK_SECS, DragBounds, DragConfig, DragEvent,
+    DragGestures, DragOperation, DropTarget, pointer_world_position,
+};
+pub use draw_order::{DRAW_ORDER_BAND, DRAW_ORDER_STEP, DrawOrder, DrawOrderChange};
+pub use easing::{
+    DEFAULT_BACK_OVERSHOOT, DEFAULT_ELASTIC_PERIOD, Ease, EaseParams, UnknownEase,
+};
 pub use entity::EntityWrapper;
 pub use error::BevyRubyError;
 pub use event::{Event, EventQueue, EventReader, EventWriter, Events};
-pub use input_bridge::InputState;
-pub use mesh_renderer::{MeshData, MeshSync, MeshTransformData, ShapeType};
+pub use floating_text::{
+    DEFAULT_FLOATING_TEXT_CAP, FLOATING_TEXT_ID_FLAG, FLOATING_TEXT_STACK_RADIUS, FLOATING_TEXT_STACK_WINDOW_SECS,
+    FloatingTextFrame, FloatingTextOperation, FloatingTextRequest, FloatingTexts,
+};
+pub use geometry::{
+    OUTLINE_SEGMENTS, TriangulationError, shape_outline, shape_triangles, triangulate,
+};
+pub use group_throttle::{GroupSchedule, GroupThrottle, GroupThrottleOperation};
+pub use hash::FrameChecksum;
+pub use hit_region::{HitRegion, HitRegionOperation, HitRegionUpdate, HitRegions};
+#[cfg(feature = "rendering")]
+pub use hit_region::HitRegionTarget;
+pub use input_bridge::{
+    ActionBinding, ActionMap, GamepadInputState, InputState, KEY_CODE_NAMES, MODIFIER_KEY_NAMES, SCROLL_PIXELS_PER_LINE, TextEdit, TouchPhase,
+    TouchState, TouchStates, TypedText,
+};
+#[cfg(feature = "rendering")]
+pub use input_bridge::key_code_name;
+pub use jelly::{JellyConfig, JellyMesh, MAX_JELLY_SUBDIVISIONS};
+pub use journal::{
+    DEFAULT_JOURNAL_MAX_BYTES, JOURNAL_ROTATIONS, JournalOperation, JournalReplay, OperationJournal,
+    journal_frame_checksum, rotated_journal_path,
+};
+pub use line_of_sight::{Bounds, DEFAULT_SIGHT_CELL_SIZE, RayHit, SightGrid, segment_bounds_hit};
+pub use lod::{DEFAULT_LOD_MARGIN, EntityLods, LodLevel, LodOperation, select_lod_level};
+pub use mesh_renderer::{
+    GradientDirection, GradientGeometry, LineCapStyle, LineJoinStyle, MeshData, MeshOperation, MeshSync,
+    MeshTransformData, ShapeType, gradient_geometry,
+};
+pub use parallax::{Parallax, ParallaxOperation, ParallaxTarget, parallax_offset};
+pub use pixel_snap::{PixelGrid, PixelSnap, PixelSnapOperation};
 pub use query::QueryBuilder;
 #[cfg(feature = "rendering")]
 pub use render_app::{
-    GamepadRumbleCommand, PickingEventData, RenderApp, RubyBridge, RubyBridgeState, WindowConfig,
+    CameraFollow, CursorGrab, ExitReason, FIRST_FRAME_DELTA_SECS, FileDropData, FramePixels, GamepadConnectionData, GamepadRumbleCommand, PickingEventData, RenderApp, RubyBridge, RubyBridgeState, TouchData, WindowConfig,
+    WindowDisplayMode, WindowEventData, WindowEventFilter, WindowMetrics, WindowMsaa, WindowPresentMode,
 };
 #[cfg(not(feature = "rendering"))]
-pub use render_app::{RenderApp, WindowConfig};
+pub use render_app::{
+    CursorGrab, ExitReason, FIRST_FRAME_DELTA_SECS, FramePixels, RenderApp, WindowConfig, WindowDisplayMode, WindowEventFilter, WindowMetrics, WindowMsaa, WindowPresentMode,
+};
 pub use resource::ResourceWrapper;
+pub use rewind::{
+    Rewind, RewindBuffer, RewindFrame, RewindOperation, RewindSample, RewindSnapshot, RewindStats,
+};
+pub use rumble::{
+    HEAVY_RUMBLE_INTENSITY, LIGHT_RUMBLE_INTENSITY, RUMBLE_PATTERN_NAMES, RumbleScheduler, RumbleSegment,
+    RumbleStep, RumbleTracker,
+};
 pub use schedule::{Schedule, ScheduleConfig, Schedules, SystemOrdering, SystemSet};
+pub use sleep::{DEFAULT_SLEEP_EPSILON, DEFAULT_SLEEP_FRAMES, MotionSleep, SleepConfig, SleepOperation, SleepTracker};
+pub use sound_set::{DEFAULT_SOUND_SET_SEED, SoundPick, SoundRng, SoundSet, SoundSets, sound_variation};
 #[cfg(feature = "rendering")]
 pub use sprite_renderer::DefaultSpriteTexture;
-pub use sprite_renderer::{SpriteData, SpriteSync, TransformData};
-pub use text_renderer::{TextData, TextSync, TextTransformData};
+pub use sprite_renderer::{
+    SPRITE_LAYER_STEP, SpriteData, SpriteOperation, SpriteSync, SpriteTransformSample, TransformData, layered_z,
+};
+pub use system_timing::{SYSTEM_TIMING_WINDOW, SystemTimings};
+pub use text_renderer::{
+    GlowCopy, TEXT_BLEND_SUPPORTED, TextBlend, TextChanges, TextData, TextGlow, TextJustify,
+    TextLineBreak, TextOperation, TextSync, TextTransformData,
+};
+pub use texture_loading::{
+    PendingTextures, TEXTURE_PLACEHOLDER_NAMES, TextureLoadSource, TextureLoadState, TexturePlaceholder,
+    TextureResolution,
+};
 pub use types::{
-    DynamicComponent, DynamicComponents, DynamicValue, RubyColor, RubyQuat, RubyTransform,
-    RubyVec2, RubyVec3,
+    DynamicComponent, DynamicComponents, DynamicValue, RubyColor, RubyMat4, RubyPalette,
+    RubyQuat, RubyTransform, RubyVec2, RubyVec3,
+};
+pub use virtual_cursor::{
+    StickCurve, VirtualCursor, VirtualCursorConfig, VirtualCursorOperation,
+};
+pub use warnings::{
+    DEFAULT_WARNING_CAPACITY, DEFAULT_WARNING_INTERVAL_SECS, WarningLog, WarningPolicy, WarningRecord,
 };
 pub use world::WorldWrapper;
+pub use world_snapshot::WorldSnapshot;
+pub use world_sync::{
+    WorldSpriteSync, WorldSyncBinding, WorldSyncFields, WorldSyncOperation, bound_entity_id,
+};
+
+/// Version of this crate.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Bevy release the crate is built against, as required in the workspace manifest.
+pub const BEVY_VERSION: &str = "0.15";
@@ -0,0 +1,277 @@
+//! Line of sight module for segment queries against sprite bounds.
+//!
+//! Muffled audio and stealth AI ask whether anything in a group of blockers
+//! (walls, crates) lies on the straight line between two points. The
+//! blockers' axis-aligned bounds are bucketed into a uniform grid, and a
+//! query walks only the cells the segment crosses, in order, stopping at
+//! the first cell that cannot hold a closer hit. A rotated sprite is
+//! treated as the box enclosing it.
+
+use std::collections::{HashMap, HashSet};
+
+/// Grid cell edge in world units used for synced sprite queries.
+pub const DEFAULT_SIGHT_CELL_SIZE: f32 = 64.0;
+
+/// Bounds covering more cells than this are tested by every query instead
+/// of being copied into each cell.
+const MAX_CELLS_PER_ENTRY: i64 = 256;
+
+/// Closed axis-aligned box in world units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min: (f32, f32),
+    pub max: (f32, f32),
+}
+
+impl Bounds {
+    /// Box between two corners, in either order.
+    pub fn new(a: (f32, f32), b: (f32, f32)) -> Self {
+        Self {
+            min: (a.0.min(b.0), a.1.min(b.1)),
+            max: (a.0.max(b.0), a.1.max(b.1)),
+        }
+    }
+
+    /// Box enclosing a `size` rectangle centered on `center` and rotated by
+    /// `rotation` radians.
+    pub fn from_rotated(center: (f32, f32), size: (f32, f32), rotation: f32) -> Self {
+        let (sin, cos) = rotation.sin_cos();
+        let half_width = size.0.abs() / 2.0;
+        let half_height = size.1.abs() / 2.0;
+        let extent_x = half_width * cos.abs() + half_height * sin.abs();
+        let extent_y = half_width * sin.abs() + half_height * cos.abs();
+        Self {
+            min: (center.0 - extent_x, center.1 - extent_y),
+            max: (center.0 + extent_x, center.1 + extent_y),
+        }
+    }
+
+    pub fn is_finite(&self) -> bool {
+        [self.min.0, self.min.1, self.max.0, self.max.1]
+            .iter()
+            .all(|value| value.is_finite())
+    }
+}
+
+/// Fraction along `start..end` (`0.0..=1.0`) where the segment first touches
+/// `bounds`; `0.0` when it starts inside, `None` when it misses.
+pub fn segment_bounds_hit(start: (f32, f32), end: (f32, f32), bounds: &Bounds) -> Option<f32> {
+    let mut enter = 0.0f64;
+    let mut exit = 1.0f64;
+    let axes = [
+        (start.0 as f64, end.0 as f64, bounds.min.0 as f64, bounds.max.0 as f64),
+        (start.1 as f64, end.1 as f64, bounds.min.1 as f64, bounds.max.1 as f64),
+    ];
+    for (from, to, low, high) in axes {
+        let delta = to - from;
+        if delta == 0.0 {
+            if from < low || from > high {
+                return None;
+            }
+            continue;
+        }
+        let (mut near, mut far) = ((low - from) / delta, (high - from) / delta);
+        if near > far {
+            std::mem::swap(&mut near, &mut far);
+        }
+        enter = enter.max(near);
+        exit = exit.min(far);
+        if enter > exit {
+            return None;
+        }
+    }
+    Some(enter as f32)
+}
+
+/// First blocker on a segment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    pub ruby_entity_id: u64,
+    /// Fraction along the segment, `0.0..=1.0`.
+    pub fraction: f32,
+    pub point: (f32, f32),
+}
+
+impl RayHit {
+    fn at(ruby_entity_id: u64, fraction: f32, start: (f32, f32), end: (f32, f32)) -> Self {
+        Self {
+            ruby_entity_id,
+            fraction,
+            point: (
+                start.0 + (end.0 - start.0) * fraction,
+                start.1 + (end.1 - start.1) * fraction,
+            ),
+        }
+    }
+
+    /// True if this hit comes before `other`; equal fractions go to the
+    /// lower entity ID so results don't depend on iteration order.
+    fn is_before(&self, other: &Self) -> bool {
+        (self.fraction, self.ruby_entity_id) < (other.fraction, other.ruby_entity_id)
+    }
+}
+
+/// Blocker bounds bucketed into square cells.
+#[derive(Debug, Clone)]
+pub struct SightGrid {
+    cell_size: f32,
+    entries: Vec<(u64, Bounds)>,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+    /// Entries too large to copy into each of their cells.
+    oversized: Vec<usize>,
+}
+
+impl SightGrid {
+    /// `cell_size` falls back to `DEFAULT_SIGHT_CELL_SIZE` unless positive and finite.
+    pub fn new(cell_size: f32) -> Self {
+        let cell_size = if cell_size.is_finite() && cell_size > 0.0 {
+            cell_size
+        } else {
+            DEFAULT_SIGHT_CELL_SIZE
+        };
+        Self {
+            cell_size,
+            entries: Vec::new(),
+            cells: HashMap::new(),
+            oversized: Vec::new(),
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn cell_of(&self, value: f64) -> i64 {
+        (value / self.cell_size as f64).floor() as i64
+    }
+
+    /// Adds a blocker; bounds with a non-finite coordinate are skipped.
+    pub fn insert(&mut self, ruby_entity_id: u64, bounds: Bounds) {
+        if !bounds.is_finite() {
+            return;
+        }
+        let index = self.entries.len();
+        self.entries.push((ruby_entity_id, bounds));
+
+        // Padded so a hit on a cell edge is found from either side of it.
+        let pad = self.cell_size as f64 * 1e-4;
+        let (x0, x1) = (self.cell_of(bounds.min.0 as f64 - pad), self.cell_of(bounds.max.0 as f64 + pad));
+        let (y0, y1) = (self.cell_of(bounds.min.1 as f64 - pad), self.cell_of(bounds.max.1 as f64 + pad));
+        let cells = (x1 - x0 + 1).saturating_mul(y1 - y0 + 1);
+        if cells > MAX_CELLS_PER_ENTRY {
+            self.oversized.push(index);
+            return;
+        }
+        for x in x0..=x1 {
+            for y in y0..=y1 {
+                self.cells.entry((x, y)).or_default().push(index);
+            }
+        }
+    }
+
+    /// First blocker between `start` and `end`, skipping `ignore`.
+    ///
+    /// Walks the cells the segment crosses from `start`, so the cost grows
+    /// with the segment's length in cells and the blockers near it, not
+    /// with the number of blockers.
+    pub fn raycast(&self, start: (f32, f32), end: (f32, f32), ignore: &[u64]) -> Option<RayHit> {
+        let mut best: Option<RayHit> = None;
+        let consider = |index: usize, best: &mut Option<RayHit>| {
+            let (ruby_entity_id, bounds) = self.entries[index];
+            if ignore.contains(&ruby_entity_id) {
+                return;
+            }
+            let Some(fraction) = segment_bounds_hit(start, end, &bounds) else {
+                return;
+            };
+            let hit = RayHit::at(ruby_entity_id, fraction, start, end);
+            if best.as_ref().is_none_or(|best| hit.is_before(best)) {
+                *best = Some(hit);
+            }
+        };
+
+        for &index in &self.oversized {
+            consider(index, &mut best);
+        }
+        if self.cells.is_empty() {
+            return best;
+        }
+
+        let (from_x, from_y) = (start.0 as f64, start.1 as f64);
+        let (delta_x, delta_y) = (end.0 as f64 - from_x, end.1 as f64 - from_y);
+        let size = self.cell_size as f64;
+        let (mut cell_x, mut cell_y) = (self.cell_of(from_x), self.cell_of(from_y));
+        let (end_x, end_y) = (self.cell_of(end.0 as f64), self.cell_of(end.1 as f64));
+
+        // Fraction at which the segment crosses the next cell edge on each
+        // axis, and the fraction it takes to cross a whole cell.
+        let axis = |from: f64, delta: f64, cell: i64| -> (i64, f64, f64) {
+            if delta > 0.0 {
+                (1, ((cell + 1) as f64 * size - from) / delta, size / delta)
+            } else if delta < 0.0 {
+                (-1, (cell as f64 * size - from) / delta, -size / delta)
+            } else {
+                (0, f64::INFINITY, f64::INFINITY)
+            }
+        };
+        let (step_x, mut next_x, cross_x) = axis(from_x, delta_x, cell_x);
+        let (step_y, mut next_y, cross_y) = axis(from_y, delta_y, cell_y);
+
+        let steps = (end_x - cell_x).abs().saturating_add((end_y - cell_y).abs());
+        // A segment crossing more cells than are occupied is cheaper to test directly.
+        if steps > self.cells.len() as i64 {
+            return self.raycast_brute_force(start, end, ignore);
+        }
+
+        let mut visited = HashSet::new();
+        for step in 0..=steps {
+            if let Some(indices) = self.cells.get(&(cell_x, cell_y)) {
+                for &index in indices {
+                    if visited.insert(index) {
+                        consider(index, &mut best);
+                    }
+                }
+            }
+            // Later cells are entered after this one is left, so they
+            // cannot hold a closer hit.
+            let leave = next_x.min(next_y);
+            if step == steps || best.is_some_and(|best| best.fraction as f64 <= leave) || leave > 1.0 {
+                break;
+            }
+            if next_x < next_y {
+                cell_x += step_x;
+                next_x += cross_x;
+            } else {
+                cell_y += step_y;
+                next_y += cross_y;
+            }
+        }
+        best
+    }
+
+    /// `raycast` by testing every blocker; the reference the grid walk must match.
+    pub fn raycast_brute_force(&self, start: (f32, f32), end: (f32, f32), ignore: &[u64]) -> Option<RayHit> {
+        self.entries
+            .iter()
+            .filter(|(ruby_entity_id, _)| !ignore.contains(ruby_entity_id))
+            .filter_map(|&(ruby_entity_id, bounds)| {
+                segment_bounds_hit(start, end, &bounds)
+                    .map(|fraction| RayHit::at(ruby_entity_id, fraction, start, end))
+            })
+            .reduce(|best, hit| if hit.is_before(&best) { hit } else { best })
+    }
+}
+
+impl Default for SightGrid {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIGHT_CELL_SIZE)
+    }
+}
@@ -0,0 +1,206 @@
+//! Level-of-detail module for switching synced sprites by camera zoom.
+//!
+//! Each entity carries an ordered list of levels keyed by the camera scale they
+//! cover. When the active level changes, the matching sprite data or
+//! visibility is queued as a `SpriteOperation`, so it goes through the same
+//! sync path as sprites authored from Ruby.
+
+use crate::determinism::AuditMap;
+use crate::sprite_renderer::{SpriteData, SpriteOperation};
+
+/// Default hysteresis margin, as a fraction of the boundary scale.
+pub const DEFAULT_LOD_MARGIN: f32 = 0.1;
+
+/// One level of detail.
+///
+/// A level covers camera scales up to `max_scale`; `None` covers everything
+/// above the previous level. `sprite: None` keeps the synced look.
+#[derive(Debug, Clone)]
+pub struct LodLevel {
+    pub max_scale: Option<f32>,
+    pub sprite: Option<SpriteData>,
+    pub hide: bool,
+}
+
+/// Pending LOD operation.
+#[derive(Debug, Clone)]
+pub enum LodOperation {
+    Set {
+        ruby_entity_id: u64,
+        levels: Vec<LodLevel>,
+    },
+    Clear {
+        ruby_entity_id: u64,
+    },
+    SetMargin {
+        margin: f32,
+    },
+}
+
+/// Picks the level for `scale`, staying on `current` while the scale is
+/// within `margin` of its bounds.
+///
+/// Levels are expected in ascending `max_scale` order. Scales past the last
+/// bounded level select the last level. `margin` is relative, so a margin of
+/// `0.1` lets a level with `max_scale: 2.0` hold until the scale exceeds 2.2.
+pub fn select_lod_level(
+    levels: &[LodLevel],
+    scale: f32,
+    current: Option<usize>,
+    margin: f32,
+) -> usize {
+    let Some(last) = levels.len().checked_sub(1) else {
+        return 0;
+    };
+
+    if let Some(current) = current.filter(|current| *current <= last) {
+        let margin = margin.max(0.0);
+        let below_upper = levels[current]
+            .max_scale
+            .is_none_or(|upper| scale <= upper * (1.0 + margin));
+        let above_lower = current == 0
+            || levels[current - 1]
+                .max_scale
+                .is_none_or(|lower| scale > lower * (1.0 - margin));
+        if below_upper && above_lower {
+            return current;
+        }
+    }
+
+    levels
+        .iter()
+        .position(|level| level.max_scale.is_none_or(|upper| scale <= upper))
+        .unwrap_or(last)
+}
+
+#[derive(Debug, Clone)]
+struct EntityLod {
+    levels: Vec<LodLevel>,
+    current: Option<usize>,
+}
+
+/// Tracks LOD levels per Ruby entity and turns level changes into sprite operations.
+pub struct EntityLods {
+    entities: AuditMap<u64, EntityLod>,
+    margin: f32,
+    /// Pending operations to apply on next update.
+    pub pending_operations: Vec<LodOperation>,
+}
+
+impl EntityLods {
+    pub fn new() -> Self {
+        Self {
+            entities: AuditMap::new(),
+            margin: DEFAULT_LOD_MARGIN,
+            pending_operations: Vec::new(),
+        }
+    }
+
+    /// Queues setting an entity's levels (standalone, no World needed).
+    pub fn set_standalone(&mut self, ruby_entity_id: u64, levels: Vec<LodLevel>) {
+        self.pending_operations.push(LodOperation::Set {
+            ruby_entity_id,
+            levels,
+        });
+    }
+
+    /// Queues removing an entity's levels (standalone, no World needed).
+    pub fn clear_standalone(&mut self, ruby_entity_id: u64) {
+        self.pending_operations
+            .push(LodOperation::Clear { ruby_entity_id });
+    }
+
+    /// Queues changing the hysteresis margin (standalone, no World needed).
+    pub fn set_margin_standalone(&mut self, margin: f32) {
+        self.pending_operations
+            .push(LodOperation::SetMargin { margin });
+    }
+
+    /// Hysteresis margin, as a fraction of the boundary scale.
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+
+    /// Active level of an entity, or `None` before its first update.
+    pub fn current(&self, ruby_entity_id: u64) -> Option<usize> {
+        self.entities.get(&ruby_entity_id)?.current
+    }
+
+    /// Active level of every entity that has been updated, ordered by Ruby entity ID.
+    pub fn current_levels(&self) -> Vec<(u64, usize)> {
+        let mut levels: Vec<(u64, usize)> = self
+            .entities
+            .iter()
+            .filter_map(|(ruby_entity_id, lod)| Some((*ruby_entity_id, lod.current?)))
+            .collect();
+        levels.sort_unstable_by_key(|(ruby_entity_id, _)| *ruby_entity_id);
+        levels
+    }
+
+    /// Returns the number of entities with levels.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns true if no entity has levels.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Applies pending operations and selects levels for `camera_scale`.
+    ///
+    /// Returns the sprite operations for entities whose level changed or
+    /// whose levels were cleared.
+    pub fn update(&mut self, camera_scale: f32) -> Vec<SpriteOperation> {
+        let mut operations = Vec::new();
+
+        for op in std::mem::take(&mut self.pending_operations) {
+            match op {
+                LodOperation::Set {
+                    ruby_entity_id,
+                    levels,
+                } => {
+                    self.entities.insert(
+                        ruby_entity_id,
+                        EntityLod {
+                            levels,
+                            current: None,
+                        },
+                    );
+                }
+                LodOperation::Clear { ruby_entity_id } => {
+                    if self.entities.remove(&ruby_entity_id).is_some() {
+                        operations.push(SpriteOperation::ClearLod { ruby_entity_id });
+                    }
+                }
+                LodOperation::SetMargin { margin } => {
+                    self.margin = margin.max(0.0);
+                }
+            }
+        }
+
+        for (ruby_entity_id, lod) in self.entities.iter_mut() {
+            if lod.levels.is_empty() {
+                continue;
+            }
+            let level = select_lod_level(&lod.levels, camera_scale, lod.current, self.margin);
+            if lod.current == Some(level) {
+                continue;
+            }
+            lod.current = Some(level);
+            operations.push(SpriteOperation::SetLod {
+                ruby_entity_id: *ruby_entity_id,
+                sprite_data: lod.levels[level].sprite.clone(),
+                hidden: lod.levels[level].hide,
+            });
+        }
+
+        operations
+    }
+}
+
+impl Default for EntityLods {
+    fn default() -> Self {
+        Self::new()
+    }
+}
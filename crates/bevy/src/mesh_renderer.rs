@@ -1,14 +1,99 @@
-use std::collections::HashMap;
+use crate::determinism::AuditMap;
+use crate::geometry::{shape_outline, triangulate};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ShapeType {
     Rectangle,
     Circle,
     RegularPolygon,
+    Triangle,
     Line,
     Ellipse,
 }
 
+/// How the ends of a stroked line are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCapStyle {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+impl LineCapStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "butt" => Some(LineCapStyle::Butt),
+            "round" => Some(LineCapStyle::Round),
+            "square" => Some(LineCapStyle::Square),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LineCapStyle::Butt => "butt",
+            LineCapStyle::Round => "round",
+            LineCapStyle::Square => "square",
+        }
+    }
+}
+
+/// How corners between stroked segments are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineJoinStyle {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl LineJoinStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "miter" => Some(LineJoinStyle::Miter),
+            "round" => Some(LineJoinStyle::Round),
+            "bevel" => Some(LineJoinStyle::Bevel),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LineJoinStyle::Miter => "miter",
+            LineJoinStyle::Round => "round",
+            LineJoinStyle::Bevel => "bevel",
+        }
+    }
+}
+
+/// Axis a two-color gradient fill runs along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GradientDirection {
+    /// From `color` at the top to `gradient_color` at the bottom.
+    #[default]
+    Vertical,
+    /// From `color` at the left to `gradient_color` at the right.
+    Horizontal,
+}
+
+impl GradientDirection {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "vertical" => Some(GradientDirection::Vertical),
+            "horizontal" => Some(GradientDirection::Horizontal),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GradientDirection::Vertical => "vertical",
+            GradientDirection::Horizontal => "horizontal",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MeshData {
     pub shape_type: ShapeType,
@@ -26,6 +111,14 @@ pub struct MeshData {
     pub line_end_y: f32,
     pub thickness: f32,
     pub fill: bool,
+    /// Line shapes only.
+    pub line_cap: LineCapStyle,
+    /// Line shapes only.
+    pub line_join: LineJoinStyle,
+    /// Second RGBA of a gradient fill; filled shapes only. `None` fills
+    /// with `color` alone.
+    pub gradient_color: Option<[f32; 4]>,
+    pub gradient_direction: GradientDirection,
 }
 
 impl Default for MeshData {
@@ -46,10 +139,77 @@ impl Default for MeshData {
             line_end_y: 0.0,
             thickness: 2.0,
             fill: true,
+            line_cap: LineCapStyle::default(),
+            line_join: LineJoinStyle::default(),
+            gradient_color: None,
+            gradient_direction: GradientDirection::default(),
         }
     }
 }
 
+/// Triangles of a gradient-filled shape with a color per vertex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradientGeometry {
+    /// Outline points in local space.
+    pub positions: Vec<[f32; 2]>,
+    /// sRGBA of each point, blended from `color` to `gradient_color` across
+    /// the shape's extent.
+    pub colors: Vec<[f32; 4]>,
+    /// Counter-clockwise triangles as indices into `positions`.
+    pub indices: Vec<u32>,
+}
+
+/// Geometry of `mesh_data`'s gradient fill.
+///
+/// `None` without a gradient color, for outlines and lines, and for shapes
+/// too small to enclose any area; those draw as solid shapes.
+pub fn gradient_geometry(mesh_data: &MeshData) -> Option<GradientGeometry> {
+    let end = mesh_data.gradient_color?;
+    if !mesh_data.fill {
+        return None;
+    }
+    let positions = shape_outline(mesh_data)?;
+    let triangles = triangulate(&positions).ok()?;
+
+    let axis = match mesh_data.gradient_direction {
+        GradientDirection::Vertical => 1,
+        GradientDirection::Horizontal => 0,
+    };
+    let (min, max) = positions
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), point| {
+            (min.min(point[axis]), max.max(point[axis]))
+        });
+    let start = [
+        mesh_data.color_r,
+        mesh_data.color_g,
+        mesh_data.color_b,
+        mesh_data.color_a,
+    ];
+    let colors = positions
+        .iter()
+        .map(|point| {
+            let along = if max > min { (point[axis] - min) / (max - min) } else { 0.0 };
+            // Vertical gradients start at the top, where y is largest.
+            let t = match mesh_data.gradient_direction {
+                GradientDirection::Vertical => 1.0 - along,
+                GradientDirection::Horizontal => along,
+            };
+            std::array::from_fn(|channel| start[channel] + (end[channel] - start[channel]) * t)
+        })
+        .collect();
+
+    Some(GradientGeometry {
+        positions,
+        colors,
+        indices: triangles
+            .into_iter()
+            .flatten()
+            .map(|index| index as u32)
+            .collect(),
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct MeshTransformData {
     pub translation_x: f32,
@@ -97,20 +257,30 @@ pub enum MeshOperation {
 struct EntityData {
     #[cfg(feature = "rendering")]
     bevy_entity: bevy_ecs::entity::Entity,
+    /// Vertex-colored mesh of a gradient fill, rewritten on re-sync. Gaining
+    /// or losing the gradient despawns the entity and spawns a new one.
+    #[cfg(feature = "rendering")]
+    gradient_mesh: Option<bevy_asset::Handle<bevy_render::mesh::Mesh>>,
     #[cfg(not(feature = "rendering"))]
     _phantom: (),
 }
 
 pub struct MeshSync {
-    entity_map: HashMap<u64, EntityData>,
+    entity_map: AuditMap<u64, EntityData>,
     pub pending_operations: Vec<MeshOperation>,
+    /// White material shared by gradient fills, which take their colors
+    /// from the vertices.
+    #[cfg(feature = "rendering")]
+    gradient_material: Option<bevy_asset::Handle<bevy_sprite::ColorMaterial>>,
 }
 
 impl MeshSync {
     pub fn new() -> Self {
         Self {
-            entity_map: HashMap::new(),
+            entity_map: AuditMap::new(),
             pending_operations: Vec::new(),
+            #[cfg(feature = "rendering")]
+            gradient_material: None,
         }
     }
 
@@ -137,10 +307,13 @@ impl MeshSync {
 
     #[cfg(feature = "rendering")]
     pub fn apply_pending(&mut self, world: &mut bevy_ecs::world::World) {
+        use bevy_asset::Assets;
         use bevy_color::Color;
         use bevy_math::Vec3;
         use bevy_prototype_lyon::prelude::*;
+        use bevy_render::mesh::{Mesh, Mesh2d};
         use bevy_render::view::Visibility;
+        use bevy_sprite::{ColorMaterial, MeshMaterial2d};
         use bevy_transform::components::Transform;
 
         let ops: Vec<_> = self.pending_operations.drain(..).collect();
@@ -177,17 +350,57 @@ impl MeshSync {
                         ),
                     };
 
+                    let gradient = gradient_geometry(&mesh_data);
+                    if let Some(entity_data) = self.entity_map.get(&ruby_entity_id)
+                        && entity_data.gradient_mesh.is_some() != gradient.is_some()
+                    {
+                        world.despawn(entity_data.bevy_entity);
+                        self.entity_map.remove(&ruby_entity_id);
+                    }
+
                     if let Some(entity_data) = self.entity_map.get(&ruby_entity_id) {
                         let bevy_entity = entity_data.bevy_entity;
                         if let Some(mut t) = world.get_mut::<Transform>(bevy_entity) {
                             *t = transform;
                         }
+                        if let (Some(mesh), Some(geometry)) = (&entity_data.gradient_mesh, &gradient) {
+                            world
+                                .resource_mut::<Assets<Mesh>>()
+                                .insert(mesh, gradient_mesh(geometry));
+                        }
                         if let Some(mut fill) = world.get_mut::<Fill>(bevy_entity) {
                             fill.color = color;
                         }
                         if let Some(mut stroke) = world.get_mut::<Stroke>(bevy_entity) {
                             stroke.color = color;
                         }
+                    } else if let Some(geometry) = gradient {
+                        let mesh = world
+                            .resource_mut::<Assets<Mesh>>()
+                            .add(gradient_mesh(&geometry));
+                        let material = self
+                            .gradient_material
+                            .get_or_insert_with(|| {
+                                world
+                                    .resource_mut::<Assets<ColorMaterial>>()
+                                    .add(ColorMaterial::default())
+                            })
+                            .clone();
+                        let bevy_entity = world
+                            .spawn((
+                                Mesh2d(mesh.clone()),
+                                MeshMaterial2d(material),
+                                transform,
+                                Visibility::Visible,
+                            ))
+                            .id();
+                        self.entity_map.insert(
+                            ruby_entity_id,
+                            EntityData {
+                                bevy_entity,
+                                gradient_mesh: Some(mesh),
+                            },
+                        );
                     } else {
                         let transparent = Color::srgba(0.0, 0.0, 0.0, 0.0);
                         let draw_mode = if mesh_data.fill {
@@ -253,11 +466,40 @@ impl MeshSync {
                                     draw_mode.1,
                                 )).id()
                             }
+                            ShapeType::Triangle => {
+                                let shape = shapes::RegularPolygon {
+                                    sides: 3,
+                                    feature: RegularPolygonFeature::Radius(mesh_data.radius),
+                                    ..Default::default()
+                                };
+                                world.spawn((
+                                    ShapeBundle {
+                                        path: GeometryBuilder::build_as(&shape),
+                                        transform,
+                                        visibility: Visibility::Visible,
+                                        ..Default::default()
+                                    },
+                                    draw_mode.0,
+                                    draw_mode.1,
+                                )).id()
+                            }
                             ShapeType::Line => {
                                 let shape = shapes::Line(
                                     bevy_math::Vec2::new(mesh_data.line_start_x, mesh_data.line_start_y),
                                     bevy_math::Vec2::new(mesh_data.line_end_x, mesh_data.line_end_y),
                                 );
+                                let cap = match mesh_data.line_cap {
+                                    LineCapStyle::Butt => LineCap::Butt,
+                                    LineCapStyle::Round => LineCap::Round,
+                                    LineCapStyle::Square => LineCap::Square,
+                                };
+                                let join = match mesh_data.line_join {
+                                    LineJoinStyle::Miter => LineJoin::Miter,
+                                    LineJoinStyle::Round => LineJoin::Round,
+                                    LineJoinStyle::Bevel => LineJoin::Bevel,
+                                };
+                                let mut stroke = Stroke::new(color, mesh_data.thickness);
+                                stroke.options = stroke.options.with_line_cap(cap).with_line_join(join);
                                 world.spawn((
                                     ShapeBundle {
                                         path: GeometryBuilder::build_as(&shape),
@@ -265,7 +507,7 @@ impl MeshSync {
                                         visibility: Visibility::Visible,
                                         ..Default::default()
                                     },
-                                    Stroke::new(color, mesh_data.thickness),
+                                    stroke,
                                 )).id()
                             }
                             ShapeType::Ellipse => {
@@ -286,7 +528,13 @@ impl MeshSync {
                             }
                         };
 
-                        self.entity_map.insert(ruby_entity_id, EntityData { bevy_entity });
+                        self.entity_map.insert(
+                            ruby_entity_id,
+                            EntityData {
+                                bevy_entity,
+                                gradient_mesh: None,
+                            },
+                        );
                     }
                 }
                 MeshOperation::Remove { ruby_entity_id } => {
@@ -295,7 +543,7 @@ impl MeshSync {
                     }
                 }
                 MeshOperation::Clear => {
-                    for (_, entity_data) in self.entity_map.drain() {
+                    for (_, entity_data) in std::mem::take(&mut self.entity_map) {
                         world.despawn(entity_data.bevy_entity);
                     }
                 }
@@ -319,6 +567,13 @@ impl MeshSync {
     pub fn synced_entities(&self) -> Vec<u64> {
         self.entity_map.keys().copied().collect()
     }
+
+    #[cfg(feature = "rendering")]
+    pub fn bevy_entity(&self, ruby_entity_id: u64) -> Option<bevy_ecs::entity::Entity> {
+        self.entity_map
+            .get(&ruby_entity_id)
+            .map(|entity_data| entity_data.bevy_entity)
+    }
 }
 
 impl Default for MeshSync {
@@ -326,3 +581,24 @@ impl Default for MeshSync {
         Self::new()
     }
 }
+
+/// Triangle-list mesh of a gradient fill, with linear vertex colors for
+/// `ColorMaterial` to multiply.
+#[cfg(feature = "rendering")]
+fn gradient_mesh(geometry: &GradientGeometry) -> bevy_render::mesh::Mesh {
+    use bevy_color::{Color, ColorToComponents};
+    use bevy_render::mesh::{Indices, Mesh, PrimitiveTopology};
+    use bevy_render::render_asset::RenderAssetUsages;
+
+    let positions: Vec<[f32; 3]> = geometry.positions.iter().map(|[x, y]| [*x, *y, 0.0]).collect();
+    let colors: Vec<[f32; 4]> = geometry
+        .colors
+        .iter()
+        .map(|[r, g, b, a]| Color::srgba(*r, *g, *b, *a).to_linear().to_f32_array())
+        .collect();
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_indices(Indices::U32(geometry.indices.clone()));
+    mesh
+}
@@ -0,0 +1,230 @@
+//! Parallax module for moving sprite groups and layers at a fraction of the camera's speed.
+//!
+//! A factor of 1.0 keeps the authored position, 0.0 pins the sprite to the
+//! camera like a skybox and factors above 1.0 move it faster than the world.
+//! The offset `camera * (1 - factor)` is added after every other bridge-driven
+//! motion and taken off again at the start of the next frame, so syncs, clips
+//! and behaviors keep working on the authored position.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "rendering")]
+use bevy_ecs::entity::Entity;
+#[cfg(feature = "rendering")]
+use bevy_ecs::world::World;
+#[cfg(feature = "rendering")]
+use bevy_transform::components::Transform;
+
+/// Sprites a parallax factor applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParallaxTarget {
+    /// Sprites synced with this `group`.
+    Group(String),
+    /// Sprites whose authored z rounds to this layer.
+    Layer(i32),
+}
+
+impl ParallaxTarget {
+    /// Layer of a sprite at depth `z`.
+    pub fn layer_of(z: f32) -> i32 {
+        z.round() as i32
+    }
+}
+
+/// Pending parallax operation.
+#[derive(Debug, Clone)]
+pub enum ParallaxOperation {
+    Set {
+        target: ParallaxTarget,
+        factor: (f32, f32),
+    },
+    Clear {
+        target: ParallaxTarget,
+    },
+}
+
+/// Offset added to a sprite with `factor` while the camera is at `camera`.
+pub fn parallax_offset(factor: (f32, f32), camera: (f32, f32)) -> (f32, f32) {
+    (camera.0 * (1.0 - factor.0), camera.1 * (1.0 - factor.1))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AppliedParallax {
+    offset: (f32, f32),
+    written: (f32, f32, f32),
+}
+
+/// Parallax factors per group and layer, and the offsets written this frame.
+pub struct Parallax {
+    groups: HashMap<String, (f32, f32)>,
+    layers: HashMap<i32, (f32, f32)>,
+    /// Pending operations to apply on next update.
+    pub pending_operations: Vec<ParallaxOperation>,
+    applied: HashMap<u64, AppliedParallax>,
+}
+
+impl Parallax {
+    pub fn new() -> Self {
+        Self {
+            groups: HashMap::new(),
+            layers: HashMap::new(),
+            pending_operations: Vec::new(),
+            applied: HashMap::new(),
+        }
+    }
+
+    /// Queues setting a factor (standalone, no World needed).
+    pub fn set_factor_standalone(&mut self, target: ParallaxTarget, factor: (f32, f32)) {
+        self.pending_operations
+            .push(ParallaxOperation::Set { target, factor });
+    }
+
+    /// Queues removing a factor (standalone, no World needed).
+    pub fn clear_standalone(&mut self, target: ParallaxTarget) {
+        self.pending_operations
+            .push(ParallaxOperation::Clear { target });
+    }
+
+    pub fn set_factor(&mut self, target: ParallaxTarget, factor: (f32, f32)) {
+        match target {
+            ParallaxTarget::Group(group) => {
+                self.groups.insert(group, factor);
+            }
+            ParallaxTarget::Layer(layer) => {
+                self.layers.insert(layer, factor);
+            }
+        }
+    }
+
+    pub fn clear(&mut self, target: &ParallaxTarget) {
+        match target {
+            ParallaxTarget::Group(group) => {
+                self.groups.remove(group);
+            }
+            ParallaxTarget::Layer(layer) => {
+                self.layers.remove(layer);
+            }
+        }
+    }
+
+    pub fn factor(&self, target: &ParallaxTarget) -> Option<(f32, f32)> {
+        match target {
+            ParallaxTarget::Group(group) => self.groups.get(group).copied(),
+            ParallaxTarget::Layer(layer) => self.layers.get(layer).copied(),
+        }
+    }
+
+    /// Factor for a sprite in `group` at depth `z`; a group factor wins over a layer factor.
+    pub fn factor_for(&self, group: Option<&str>, z: f32) -> Option<(f32, f32)> {
+        group
+            .and_then(|group| self.groups.get(group))
+            .or_else(|| self.layers.get(&ParallaxTarget::layer_of(z)))
+            .copied()
+    }
+
+    /// Returns true if no group or layer has a factor and no offset is applied.
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty() && self.layers.is_empty() && self.applied.is_empty()
+    }
+
+    /// Applies pending operations.
+    pub fn apply_operations(&mut self) {
+        let ops: Vec<_> = self.pending_operations.drain(..).collect();
+        for op in ops {
+            match op {
+                ParallaxOperation::Set { target, factor } => self.set_factor(target, factor),
+                ParallaxOperation::Clear { target } => self.clear(&target),
+            }
+        }
+    }
+
+    /// Takes the offset written last frame off `position`.
+    ///
+    /// A position that no longer matches what was written was re-authored by
+    /// a sync layer and is returned unchanged.
+    pub fn remove_offset(&mut self, ruby_entity_id: u64, position: (f32, f32, f32)) -> (f32, f32, f32) {
+        match self.applied.remove(&ruby_entity_id) {
+            Some(applied) if applied.written == position => (
+                position.0 - applied.offset.0,
+                position.1 - applied.offset.1,
+                position.2,
+            ),
+            _ => position,
+        }
+    }
+
+    /// Adds the offset for the sprite's group or layer to its authored `position`.
+    pub fn add_offset(
+        &mut self,
+        ruby_entity_id: u64,
+        group: Option<&str>,
+        position: (f32, f32, f32),
+        camera: (f32, f32),
+    ) -> (f32, f32, f32) {
+        let Some(factor) = self.factor_for(group, position.2) else {
+            self.applied.remove(&ruby_entity_id);
+            return position;
+        };
+        let offset = parallax_offset(factor, camera);
+        let written = (position.0 + offset.0, position.1 + offset.1, position.2);
+        self.applied
+            .insert(ruby_entity_id, AppliedParallax { offset, written });
+        written
+    }
+
+    /// Restores the authored translation of every sprite offset last frame.
+    ///
+    /// Runs before the sync layers so they, clips and behaviors see the
+    /// transform they wrote.
+    #[cfg(feature = "rendering")]
+    pub fn restore<F>(&mut self, world: &mut World, resolve: F)
+    where
+        F: Fn(u64) -> Option<Entity>,
+    {
+        let ids: Vec<u64> = self.applied.keys().copied().collect();
+        for ruby_entity_id in ids {
+            let Some(mut transform) =
+                resolve(ruby_entity_id).and_then(|entity| world.get_mut::<Transform>(entity))
+            else {
+                self.applied.remove(&ruby_entity_id);
+                continue;
+            };
+            let position = transform.translation;
+            let (x, y, z) = self.remove_offset(ruby_entity_id, (position.x, position.y, position.z));
+            if (x, y, z) != (position.x, position.y, position.z) {
+                transform.translation = bevy_math::Vec3::new(x, y, z);
+            }
+        }
+    }
+
+    /// Applies pending operations and offsets every synced sprite with a factor.
+    ///
+    /// `sprites` yields each synced sprite's Ruby ID, Bevy entity and group.
+    #[cfg(feature = "rendering")]
+    pub fn apply<'a, I>(&mut self, world: &mut World, camera: (f32, f32), sprites: I)
+    where
+        I: IntoIterator<Item = (u64, Entity, Option<&'a str>)>,
+    {
+        self.apply_operations();
+        if self.groups.is_empty() && self.layers.is_empty() {
+            return;
+        }
+
+        for (ruby_entity_id, entity, group) in sprites {
+            let Some(mut transform) = world.get_mut::<Transform>(entity) else {
+                continue;
+            };
+            let position = transform.translation;
+            let (x, y, z) = self.add_offset(ruby_entity_id, group, (position.x, position.y, position.z), camera);
+            if (x, y, z) != (position.x, position.y, position.z) {
+                transform.translation = bevy_math::Vec3::new(x, y, z);
+            }
+        }
+    }
+}
+
+impl Default for Parallax {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,269 @@
+//! Pixel snap module for drawing sprites and text on whole physical pixels.
+//!
+//! Pixel-art glyphs and sprites shimmer when the camera leaves them on
+//! half-pixels. With pixel-perfect mode on, the rendered translation of a
+//! flagged entity is moved by the sub-pixel remainder so its origin lands on
+//! a physical pixel corner, taking the camera position, zoom and rotation
+//! and the window scale factor into account. The snapped translation is
+//! written at the end of the frame and the authored one put back at the
+//! start of the next, like parallax offsets, so the rounding never feeds
+//! into the positions syncs, clips and behaviors work on and never drifts.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "rendering")]
+use bevy_ecs::entity::Entity;
+#[cfg(feature = "rendering")]
+use bevy_ecs::world::World;
+#[cfg(feature = "rendering")]
+use bevy_transform::components::Transform;
+
+/// How world units map to the physical pixels of the render target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PixelGrid {
+    /// Camera translation in world units.
+    pub camera: (f32, f32),
+    /// World units per logical pixel (the camera zoom).
+    pub scale: f32,
+    /// Camera rotation around the Z axis in radians.
+    pub rotation: f32,
+    /// Physical pixels per logical pixel.
+    pub scale_factor: f32,
+    /// Render target size in physical pixels.
+    pub viewport: (f32, f32),
+}
+
+impl PixelGrid {
+    /// Returns false for a zero or non-finite zoom or scale factor, where
+    /// there is no pixel grid to snap to.
+    pub fn is_valid(&self) -> bool {
+        let finite = [
+            self.camera.0,
+            self.camera.1,
+            self.scale,
+            self.rotation,
+            self.scale_factor,
+            self.viewport.0,
+            self.viewport.1,
+        ]
+        .iter()
+        .all(|value| value.is_finite());
+        finite && self.scale > 0.0 && self.scale_factor > 0.0
+    }
+
+    /// Physical pixel position of a world point, from the top-left corner
+    /// of the render target with y down.
+    pub fn to_physical(&self, position: (f32, f32)) -> (f64, f64) {
+        let (sin, cos) = (self.rotation as f64).sin_cos();
+        let dx = position.0 as f64 - self.camera.0 as f64;
+        let dy = position.1 as f64 - self.camera.1 as f64;
+        let pixels_per_unit = self.scale_factor as f64 / self.scale as f64;
+        let view_x = (cos * dx + sin * dy) * pixels_per_unit;
+        let view_y = (cos * dy - sin * dx) * pixels_per_unit;
+        (
+            view_x + self.viewport.0 as f64 / 2.0,
+            self.viewport.1 as f64 / 2.0 - view_y,
+        )
+    }
+
+    /// World offset that moves a point by `delta` physical pixels.
+    fn world_delta(&self, delta: (f64, f64)) -> (f64, f64) {
+        let (sin, cos) = (self.rotation as f64).sin_cos();
+        let units_per_pixel = self.scale as f64 / self.scale_factor as f64;
+        let view_x = delta.0 * units_per_pixel;
+        let view_y = -delta.1 * units_per_pixel;
+        (cos * view_x - sin * view_y, sin * view_x + cos * view_y)
+    }
+
+    /// Moves a world point onto the nearest physical pixel corner; an
+    /// invalid grid returns it unchanged.
+    pub fn snap(&self, position: (f32, f32)) -> (f32, f32) {
+        if !self.is_valid() {
+            return position;
+        }
+        let physical = self.to_physical(position);
+        let (dx, dy) = self.world_delta((
+            physical.0.round() - physical.0,
+            physical.1.round() - physical.1,
+        ));
+        (
+            (position.0 as f64 + dx) as f32,
+            (position.1 as f64 + dy) as f32,
+        )
+    }
+}
+
+/// Pending pixel snap operation.
+#[derive(Debug, Clone)]
+pub enum PixelSnapOperation {
+    SetGlobal(bool),
+    SetEntity { ruby_entity_id: u64, enabled: bool },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AppliedSnap {
+    authored: (f32, f32, f32),
+    written: (f32, f32, f32),
+}
+
+/// Which entities are pixel-perfect, and the translations snapped this frame.
+pub struct PixelSnap {
+    global: bool,
+    /// Per-entity settings, which win over the global one.
+    entities: HashMap<u64, bool>,
+    /// Pending operations to apply on next update.
+    pub pending_operations: Vec<PixelSnapOperation>,
+    /// Keyed by Bevy entity bits, since a sprite and a text can share a Ruby ID.
+    applied: HashMap<u64, AppliedSnap>,
+}
+
+impl PixelSnap {
+    pub fn new() -> Self {
+        Self {
+            global: false,
+            entities: HashMap::new(),
+            pending_operations: Vec::new(),
+            applied: HashMap::new(),
+        }
+    }
+
+    /// Queues switching every sprite and text (standalone, no World needed).
+    pub fn set_global_standalone(&mut self, enabled: bool) {
+        self.pending_operations
+            .push(PixelSnapOperation::SetGlobal(enabled));
+    }
+
+    /// Queues switching one entity (standalone, no World needed).
+    pub fn set_entity_standalone(&mut self, ruby_entity_id: u64, enabled: bool) {
+        self.pending_operations.push(PixelSnapOperation::SetEntity {
+            ruby_entity_id,
+            enabled,
+        });
+    }
+
+    pub fn set_global(&mut self, enabled: bool) {
+        self.global = enabled;
+    }
+
+    pub fn global(&self) -> bool {
+        self.global
+    }
+
+    /// Overrides the global setting for one entity.
+    pub fn set_entity(&mut self, ruby_entity_id: u64, enabled: bool) {
+        self.entities.insert(ruby_entity_id, enabled);
+    }
+
+    /// Drops an entity's override so it follows the global setting again.
+    pub fn clear_entity(&mut self, ruby_entity_id: u64) {
+        self.entities.remove(&ruby_entity_id);
+    }
+
+    pub fn is_enabled(&self, ruby_entity_id: u64) -> bool {
+        self.entities
+            .get(&ruby_entity_id)
+            .copied()
+            .unwrap_or(self.global)
+    }
+
+    /// Returns true if nothing can be snapped and no translation is snapped.
+    pub fn is_empty(&self) -> bool {
+        !self.global && !self.entities.values().any(|enabled| *enabled) && self.applied.is_empty()
+    }
+
+    /// Applies pending operations.
+    pub fn apply_operations(&mut self) {
+        let ops: Vec<_> = self.pending_operations.drain(..).collect();
+        for op in ops {
+            match op {
+                PixelSnapOperation::SetGlobal(enabled) => self.set_global(enabled),
+                PixelSnapOperation::SetEntity {
+                    ruby_entity_id,
+                    enabled,
+                } => self.set_entity(ruby_entity_id, enabled),
+            }
+        }
+    }
+
+    /// Snaps an authored `position` to `grid` and remembers it under `key`.
+    pub fn snap_position(&mut self, key: u64, position: (f32, f32, f32), grid: &PixelGrid) -> (f32, f32, f32) {
+        let (x, y) = grid.snap((position.0, position.1));
+        let written = (x, y, position.2);
+        if written == position {
+            self.applied.remove(&key);
+        } else {
+            self.applied.insert(
+                key,
+                AppliedSnap {
+                    authored: position,
+                    written,
+                },
+            );
+        }
+        written
+    }
+
+    /// Returns the authored position snapped under `key` last frame.
+    ///
+    /// A position that no longer matches what was written was re-authored by
+    /// a sync layer and is returned unchanged.
+    pub fn restore_position(&mut self, key: u64, position: (f32, f32, f32)) -> (f32, f32, f32) {
+        match self.applied.remove(&key) {
+            Some(applied) if applied.written == position => applied.authored,
+            _ => position,
+        }
+    }
+
+    /// Puts back the authored translation of every entity snapped last frame.
+    #[cfg(feature = "rendering")]
+    pub fn restore(&mut self, world: &mut World) {
+        let keys: Vec<u64> = self.applied.keys().copied().collect();
+        for key in keys {
+            let Some(mut transform) = world.get_mut::<Transform>(Entity::from_bits(key)) else {
+                self.applied.remove(&key);
+                continue;
+            };
+            let position = transform.translation;
+            let (x, y, z) = self.restore_position(key, (position.x, position.y, position.z));
+            if (x, y, z) != (position.x, position.y, position.z) {
+                transform.translation = bevy_math::Vec3::new(x, y, z);
+            }
+        }
+    }
+
+    /// Applies pending operations and snaps every enabled entity.
+    ///
+    /// `entities` yields the Ruby entity ID and Bevy entity of each synced
+    /// sprite and text; synced entities have no parent, so their translation
+    /// is their world position.
+    #[cfg(feature = "rendering")]
+    pub fn apply<I>(&mut self, world: &mut World, grid: &PixelGrid, entities: I)
+    where
+        I: IntoIterator<Item = (u64, Entity)>,
+    {
+        self.apply_operations();
+        if !grid.is_valid() || (!self.global && !self.entities.values().any(|enabled| *enabled)) {
+            return;
+        }
+
+        for (ruby_entity_id, entity) in entities {
+            if !self.is_enabled(ruby_entity_id) {
+                continue;
+            }
+            let Some(mut transform) = world.get_mut::<Transform>(entity) else {
+                continue;
+            };
+            let position = transform.translation;
+            let (x, y, z) = self.snap_position(entity.to_bits(), (position.x, position.y, position.z), grid);
+            if (x, y, z) != (position.x, position.y, position.z) {
+                transform.translation = bevy_math::Vec3::new(x, y, z);
+            }
+        }
+    }
+}
+
+impl Default for PixelSnap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -11,6 +11,10 @@ pub enum QueryFilter {
 #[derive(Clone)]
 pub struct QueryBuilder {
     fetch: Vec<String>,
+    /// Component type names an entity must have without fetching them.
+    with: Vec<String>,
+    /// Component type names an entity must not have.
+    without: Vec<String>,
     filters: Vec<QueryFilter>,
 }
 
@@ -18,6 +22,8 @@ impl QueryBuilder {
     pub fn new() -> Self {
         Self {
             fetch: Vec::new(),
+            with: Vec::new(),
+            without: Vec::new(),
             filters: Vec::new(),
         }
     }
@@ -27,6 +33,16 @@ impl QueryBuilder {
         self
     }
 
+    pub fn with(mut self, component_name: &str) -> Self {
+        self.with.push(component_name.to_string());
+        self
+    }
+
+    pub fn without(mut self, component_name: &str) -> Self {
+        self.without.push(component_name.to_string());
+        self
+    }
+
     pub fn filter_with(mut self, component_id: ComponentId) -> Self {
         self.filters.push(QueryFilter::With(component_id));
         self
@@ -41,6 +57,14 @@ impl QueryBuilder {
         &self.fetch
     }
 
+    pub fn with_components(&self) -> &[String] {
+        &self.with
+    }
+
+    pub fn without_components(&self) -> &[String] {
+        &self.without
+    }
+
     pub fn filters(&self) -> &[QueryFilter] {
         &self.filters
     }
@@ -1,13 +1,17 @@
 //! RenderApp module for managing the Bevy rendering application.
 
 #[cfg(feature = "rendering")]
-use bevy_a11y::AccessibilityPlugin;
+use crate::accessibility::capture_accessibility_nodes;
 #[cfg(feature = "rendering")]
-use bevy_app::{App, AppExit, Startup, Update};
+use bevy_a11y::{AccessibilityPlugin, AccessibilityRequested, ManageAccessibilityUpdates};
+#[cfg(feature = "rendering")]
+use bevy_app::{App, AppExit, Last, PluginsState, PostUpdate, PreUpdate, Startup, Update};
 #[cfg(feature = "rendering")]
 use bevy_asset::AssetPlugin;
 #[cfg(feature = "rendering")]
-use bevy_core::{FrameCountPlugin, Name, TaskPoolPlugin, TypeRegistrationPlugin};
+use bevy_color::Color;
+#[cfg(feature = "rendering")]
+use bevy_core::{FrameCount, FrameCountPlugin, Name, TaskPoolPlugin, TypeRegistrationPlugin};
 #[cfg(feature = "rendering")]
 use bevy_core_pipeline::CorePipelinePlugin;
 #[cfg(feature = "rendering")]
@@ -15,53 +19,71 @@ use bevy_core_pipeline::core_2d::Camera2d;
 #[cfg(feature = "rendering")]
 use bevy_ecs::event::{EventReader, EventWriter};
 #[cfg(feature = "rendering")]
-use bevy_ecs::system::{Commands, Res};
+use bevy_ecs::schedule::IntoSystemConfigs;
+#[cfg(feature = "rendering")]
+use bevy_ecs::system::{Commands, Local, NonSend, Res, ResMut, SystemParam};
 #[cfg(feature = "rendering")]
 use bevy_ecs::world::World;
 #[cfg(feature = "rendering")]
 use bevy_hierarchy::HierarchyPlugin;
 #[cfg(feature = "rendering")]
 use bevy_input::gamepad::{
-    Gamepad, GamepadAxis, GamepadButton, GamepadRumbleIntensity, GamepadRumbleRequest,
+    Gamepad, GamepadAxis, GamepadButton, GamepadConnection, GamepadConnectionEvent, GamepadRumbleIntensity,
+    GamepadRumbleRequest,
 };
 #[cfg(feature = "rendering")]
-use bevy_input::keyboard::KeyCode;
+use bevy_input::keyboard::{Key, KeyCode, KeyboardInput};
+#[cfg(feature = "rendering")]
+use bevy_input::mouse::{MouseButton, MouseMotion, MouseScrollUnit, MouseWheel};
 #[cfg(feature = "rendering")]
-use bevy_input::mouse::MouseButton;
+use bevy_input::touch::{ForceTouch, TouchInput, TouchPhase};
 #[cfg(feature = "rendering")]
-use bevy_input::{ButtonInput, InputPlugin};
+use bevy_input::{ButtonInput, ButtonState, InputPlugin};
 #[cfg(feature = "rendering")]
 use bevy_log::LogPlugin;
 #[cfg(feature = "rendering")]
 use bevy_picking::{
-    DefaultPickingPlugins,
-    events::{Click, Down, Out, Over, Pointer, Up},
-    pointer::{PointerButton, PointerId},
+    DefaultPickingPlugins, PickSet, PickingBehavior,
+    backend::{HitData, PointerHits},
+    focus::HoverMap,
+    events::{Click, Down, Drag, DragEnd, DragStart, Move, Out, Over, Pointer, Up},
+    pointer::{Location, PointerAction, PointerButton, PointerId, PointerInput, PointerLocation, PressDirection},
 };
 #[cfg(feature = "rendering")]
 use bevy_render::RenderPlugin;
 #[cfg(feature = "rendering")]
-use bevy_render::camera::Camera;
+use bevy_render::camera::{Camera, ClearColor, NormalizedRenderTarget, OrthographicProjection};
 #[cfg(feature = "rendering")]
 use bevy_render::prelude::ImagePlugin;
 #[cfg(feature = "rendering")]
+use bevy_render::view::Msaa;
+#[cfg(feature = "rendering")]
+use bevy_ruby_render::CameraShake;
+#[cfg(feature = "rendering")]
 use bevy_sprite::SpritePlugin;
 #[cfg(feature = "rendering")]
 use bevy_text::TextPlugin;
 #[cfg(feature = "rendering")]
-use bevy_time::TimePlugin;
+use bevy_time::{Time, TimePlugin};
 #[cfg(feature = "rendering")]
-use bevy_transform::TransformPlugin;
+use bevy_transform::{TransformPlugin, TransformSystem};
 #[cfg(feature = "rendering")]
-use bevy_transform::components::Transform;
+use bevy_transform::components::{GlobalTransform, Transform};
 #[cfg(feature = "rendering")]
-use bevy_window::{Window, WindowPlugin};
+use bevy_window::{
+    CompositeAlphaMode, CursorGrabMode, ExitCondition, FileDragAndDrop, MonitorSelection, PresentMode, PrimaryWindow, Window, WindowCloseRequested,
+    WindowMode, WindowPlugin, WindowRef, WindowResizeConstraints,
+};
 #[cfg(feature = "rendering")]
-use bevy_winit::{WakeUp, WinitPlugin};
+use bevy_winit::{WakeUp, WinitPlugin, WinitWindows};
 #[cfg(feature = "rendering")]
 use std::sync::Arc;
 #[cfg(feature = "rendering")]
 use std::sync::Mutex;
+#[cfg(feature = "rendering")]
+use std::time::{Duration, Instant};
+#[cfg(feature = "rendering")]
+use winit::window::Icon;
 
 /// Window configuration for the render application.
 #[derive(Debug, Clone)]
@@ -70,6 +92,263 @@ pub struct WindowConfig {
     pub width: f32,
     pub height: f32,
     pub resizable: bool,
+    /// Title bar and borders; `false` leaves only the client area.
+    pub decorations: bool,
+    /// Lets the desktop show through wherever the clear color and sprites
+    /// are not opaque.
+    pub transparent: bool,
+    pub min_width: Option<f32>,
+    pub min_height: Option<f32>,
+    /// Registers `text_sync_system`; disable for games without `Text2d`.
+    pub text_sync: bool,
+    /// Registers `mesh_sync_system`; disable for games without mesh shapes.
+    pub mesh_sync: bool,
+    /// Bevy window events forwarded to Ruby through `window_events`.
+    pub window_events: WindowEventFilter,
+    pub mode: WindowDisplayMode,
+    /// Swap-chain presentation; `Fifo` is vsync.
+    pub present_mode: WindowPresentMode,
+    /// Multisample anti-aliasing of the 2D camera.
+    pub msaa: WindowMsaa,
+    /// Image file for the window icon; decoded with the formats `bevy_image` supports.
+    pub icon_path: Option<String>,
+    /// Background RGBA, or `None` for Bevy's default.
+    pub clear_color: Option<[f32; 4]>,
+    /// Renders into an offscreen `width` × `height` image instead of a
+    /// window, driven by a manual update loop; frames are read back for
+    /// `FramePixels`.
+    pub headless: bool,
+    /// Frames a headless `run` updates before returning; `None` runs until
+    /// the app exits.
+    pub frames: Option<u32>,
+    /// Caps the update rate by sleeping at the end of each frame; `None` or
+    /// `0.0` runs uncapped.
+    pub max_fps: Option<f32>,
+    /// Draws every sprite and text on whole physical pixels; see `PixelSnap`.
+    pub pixel_perfect: bool,
+    /// Shown by sprites whose texture is still loading.
+    pub texture_placeholder: TexturePlaceholder,
+}
+
+/// How the primary window is presented.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WindowDisplayMode {
+    #[default]
+    Windowed,
+    /// Exclusive fullscreen on the current monitor.
+    Fullscreen,
+    /// Borderless window covering the current monitor.
+    Borderless,
+}
+
+impl WindowDisplayMode {
+    /// Parses `windowed`, `fullscreen` or `borderless` (also `borderless_fullscreen`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "windowed" => Some(Self::Windowed),
+            "fullscreen" => Some(Self::Fullscreen),
+            "borderless" | "borderless_fullscreen" => Some(Self::Borderless),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Windowed => "windowed",
+            Self::Fullscreen => "fullscreen",
+            Self::Borderless => "borderless",
+        }
+    }
+
+    #[cfg(feature = "rendering")]
+    fn to_bevy(self) -> WindowMode {
+        match self {
+            Self::Windowed => WindowMode::Windowed,
+            Self::Fullscreen => WindowMode::Fullscreen(MonitorSelection::Current),
+            Self::Borderless => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+        }
+    }
+}
+
+/// Why the render loop is ending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitReason {
+    /// The window was closed, or something other than `stop!` sent `AppExit`.
+    UserClose,
+    /// Ruby called `stop!`, or a headless app ran all of its `frames`.
+    StopCalled,
+    /// The Ruby frame block raised.
+    Error,
+}
+
+impl ExitReason {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::UserClose => "user_close",
+            Self::StopCalled => "stop_called",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Samples per pixel the camera renders with; smooths the edges of mesh shapes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WindowMsaa {
+    /// One sample: no anti-aliasing.
+    Off,
+    Sample2,
+    /// Bevy's default.
+    #[default]
+    Sample4,
+    Sample8,
+}
+
+impl WindowMsaa {
+    /// Sample counts accepted by `from_samples`.
+    pub const SAMPLE_COUNTS: [u32; 4] = [1, 2, 4, 8];
+
+    pub fn from_samples(samples: u32) -> Option<Self> {
+        match samples {
+            1 => Some(Self::Off),
+            2 => Some(Self::Sample2),
+            4 => Some(Self::Sample4),
+            8 => Some(Self::Sample8),
+            _ => None,
+        }
+    }
+
+    pub fn samples(self) -> u32 {
+        match self {
+            Self::Off => 1,
+            Self::Sample2 => 2,
+            Self::Sample4 => 4,
+            Self::Sample8 => 8,
+        }
+    }
+
+    #[cfg(feature = "rendering")]
+    fn to_bevy(self) -> Msaa {
+        match self {
+            Self::Off => Msaa::Off,
+            Self::Sample2 => Msaa::Sample2,
+            Self::Sample4 => Msaa::Sample4,
+            Self::Sample8 => Msaa::Sample8,
+        }
+    }
+}
+
+/// How rendered frames are handed to the display.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WindowPresentMode {
+    /// Vsync; waits for the display and never tears.
+    #[default]
+    Fifo,
+    /// No vsync; presents immediately and may tear.
+    Immediate,
+    /// Low-latency vsync that drops stale frames; falls back to `Fifo` where unsupported.
+    Mailbox,
+    /// Uncapped, picking `Immediate` or `Mailbox` as the platform allows.
+    AutoNoVsync,
+}
+
+impl WindowPresentMode {
+    /// Names accepted by `from_name`, in declaration order.
+    pub const NAMES: [&'static str; 4] = ["fifo", "immediate", "mailbox", "auto_no_vsync"];
+
+    /// Parses `fifo`, `immediate`, `mailbox` or `auto_no_vsync`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fifo" => Some(Self::Fifo),
+            "immediate" => Some(Self::Immediate),
+            "mailbox" => Some(Self::Mailbox),
+            "auto_no_vsync" => Some(Self::AutoNoVsync),
+            _ => None,
+        }
+    }
+
+    /// `true` selects `Fifo`, `false` selects `AutoNoVsync`.
+    pub fn from_vsync(vsync: bool) -> Self {
+        if vsync { Self::Fifo } else { Self::AutoNoVsync }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Fifo => "fifo",
+            Self::Immediate => "immediate",
+            Self::Mailbox => "mailbox",
+            Self::AutoNoVsync => "auto_no_vsync",
+        }
+    }
+
+    #[cfg(feature = "rendering")]
+    fn to_bevy(self) -> PresentMode {
+        match self {
+            Self::Fifo => PresentMode::Fifo,
+            Self::Immediate => PresentMode::Immediate,
+            Self::Mailbox => PresentMode::Mailbox,
+            Self::AutoNoVsync => PresentMode::AutoNoVsync,
+        }
+    }
+}
+
+/// How the cursor is held by the primary window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CursorGrab {
+    /// The cursor moves freely in and out of the window.
+    #[default]
+    None,
+    /// The cursor can't leave the window.
+    Confined,
+    /// The cursor is pinned in place; only raw motion is reported.
+    Locked,
+}
+
+impl CursorGrab {
+    /// Names accepted by `from_name`, in declaration order.
+    pub const NAMES: [&'static str; 3] = ["none", "confined", "locked"];
+
+    /// Parses `none`, `confined` or `locked`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(Self::None),
+            "confined" => Some(Self::Confined),
+            "locked" => Some(Self::Locked),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Confined => "confined",
+            Self::Locked => "locked",
+        }
+    }
+
+    #[cfg(feature = "rendering")]
+    fn to_bevy(self) -> CursorGrabMode {
+        match self {
+            Self::None => CursorGrabMode::None,
+            Self::Confined => CursorGrabMode::Confined,
+            Self::Locked => CursorGrabMode::Locked,
+        }
+    }
+}
+
+/// Selects which Bevy window events are captured for Ruby.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowEventFilter {
+    pub close_requested: bool,
+    pub file_drag_and_drop: bool,
+}
+
+impl Default for WindowEventFilter {
+    fn default() -> Self {
+        Self {
+            close_requested: true,
+            file_drag_and_drop: true,
+        }
+    }
 }
 
 impl Default for WindowConfig {
@@ -79,11 +358,76 @@ impl Default for WindowConfig {
             width: 800.0,
             height: 600.0,
             resizable: true,
+            decorations: true,
+            transparent: false,
+            min_width: None,
+            min_height: None,
+            text_sync: true,
+            mesh_sync: true,
+            window_events: WindowEventFilter::default(),
+            mode: WindowDisplayMode::Windowed,
+            present_mode: WindowPresentMode::Fifo,
+            msaa: WindowMsaa::default(),
+            icon_path: None,
+            clear_color: None,
+            headless: false,
+            frames: None,
+            max_fps: None,
+            pixel_perfect: false,
+            texture_placeholder: TexturePlaceholder::White,
+        }
+    }
+}
+
+/// RGBA8 pixels of a headless frame, top row first, in sRGB.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FramePixels {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl FramePixels {
+    /// Drops the per-row padding of a GPU readback whose rows are
+    /// `padded_row_bytes` long; a short buffer yields only its full rows.
+    pub fn from_padded(data: &[u8], width: u32, height: u32, padded_row_bytes: usize) -> Self {
+        let row_bytes = width as usize * 4;
+        let mut rgba = Vec::with_capacity(row_bytes * height as usize);
+        if row_bytes > 0 && padded_row_bytes >= row_bytes {
+            for row in data.chunks(padded_row_bytes).take(height as usize) {
+                if row.len() < row_bytes {
+                    break;
+                }
+                rgba.extend_from_slice(&row[..row_bytes]);
+            }
         }
+        let height = rgba.len().checked_div(row_bytes).unwrap_or(0) as u32;
+        Self { width, height, rgba }
     }
 }
 
-use crate::{DefaultSpriteTexture, InputState, MeshSync, SpriteSync, TextSync};
+/// Window size and OS safe-area insets, in logical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WindowMetrics {
+    pub width: f32,
+    pub height: f32,
+    pub inset_top: f32,
+    pub inset_right: f32,
+    pub inset_bottom: f32,
+    pub inset_left: f32,
+}
+
+#[cfg(feature = "rendering")]
+use crate::{
+    AccessibilityData, AudioCommand, ClipPlayer, DefaultSpriteTexture, DragGestures, DropTarget, EntityBehaviors, EntityLods, FloatingTexts, GroupThrottle, HitRegions, InputState, JournalOperation, JournalReplay, MeshOperation, MeshSync, MotionSleep,
+    OperationJournal, Parallax, PixelGrid, PixelSnap, Rewind, RewindOperation, RewindStats, RumbleScheduler, RumbleStep, RumbleTracker, SpriteOperation, SpriteSync, SpriteTransformSample, SystemTimings, TextEdit, TextOperation, TextSync, TypedText, VirtualCursor, VirtualCursorOperation, WarningLog,
+};
+use crate::TexturePlaceholder;
+#[cfg(all(feature = "rendering", feature = "determinism_audit"))]
+use crate::DeterminismAudit;
+
+/// Delta reported on the first frame, before Bevy has measured one.
+pub const FIRST_FRAME_DELTA_SECS: f32 = 1.0 / 60.0;
 
 #[cfg(feature = "rendering")]
 type UpdateCallback = Arc<Mutex<Option<Box<dyn FnMut(&mut RubyBridgeState) + Send>>>>;
@@ -101,13 +445,180 @@ pub struct RubyBridgeState {
     pub sprite_sync: SpriteSync,
     pub text_sync: TextSync,
     pub mesh_sync: MeshSync,
+    pub behaviors: EntityBehaviors,
+    pub lods: EntityLods,
+    /// Parallax factors, applied after every other transform change.
+    pub parallax: Parallax,
+    pub pixel_snap: PixelSnap,
+    /// Pickable rectangles reported through the picking events.
+    pub hit_regions: HitRegions,
+    pub clips: ClipPlayer,
+    /// Draggable sprites and the drag in progress, moved by `drag_system`.
+    pub drag: DragGestures,
+    /// Rising, fading texts from `floating_text`, synced through `text_sync`.
+    pub floating_texts: FloatingTexts,
+    /// Update divisors per sprite group and the syncs held back between due frames.
+    pub group_throttle: GroupThrottle,
+    /// Thresholds for putting settled behaviors and jelly springs to sleep.
+    pub sleep: MotionSleep,
+    /// Sprite transforms as applied at the end of the previous frame.
+    pub sprite_transforms: Vec<SpriteTransformSample>,
+    pub rewind_operations: Vec<RewindOperation>,
+    pub rewind: Option<Rewind>,
+    /// Rewind buffer statistics, or `None` while rewind is disabled.
+    pub rewind_stats: Option<RewindStats>,
     pub pending_gamepad_rumble: Vec<GamepadRumbleCommand>,
+    /// Rumble patterns playing per gamepad, advanced by `ruby_bridge_system`.
+    pub rumble_patterns: RumbleScheduler,
+    /// End times of the rumble requests sent so far, by `elapsed_secs`.
+    pub rumble_end_times: RumbleTracker,
+    /// Playback commands from `Bevy::Audio`, drained by `audio_system`.
+    pub pending_audio: Vec<AudioCommand>,
     pub picking_events: Vec<PickingEventData>,
+    pub window_metrics: WindowMetrics,
+    pub window_event_filter: WindowEventFilter,
+    pub window_events: Vec<WindowEventData>,
+    /// Mode requested by Ruby, applied by `window_mode_system`.
+    pub pending_window_mode: Option<WindowDisplayMode>,
+    /// Background RGBA requested by Ruby, applied by `clear_color_system`.
+    pub pending_clear_color: Option<[f32; 4]>,
+    /// Title requested by Ruby, applied by `window_sync_system`.
+    pub pending_window_title: Option<String>,
+    /// Logical size requested by Ruby, applied by `window_sync_system`.
+    pub pending_window_size: Option<(f32, f32)>,
+    /// Cursor visibility requested by Ruby, applied by `cursor_system`.
+    pub pending_cursor_visible: Option<bool>,
+    /// Cursor grab requested by Ruby, applied by `cursor_system`.
+    pub pending_cursor_grab: Option<CursorGrab>,
+    /// Frame rate cap enforced by `frame_limiter_system`; `None` or `0.0` is uncapped.
+    pub max_fps: Option<f32>,
+    pub window_mode: WindowDisplayMode,
+    /// Logical size to restore when returning to `Windowed`, kept while fullscreen.
+    pub windowed_resolution: Option<(f32, f32)>,
+    /// File drag-and-drop entries accumulated until Ruby drains them.
+    pub file_drops: Vec<FileDropData>,
+    /// Gamepad connects and disconnects accumulated until Ruby drains them.
+    pub gamepad_connections: Vec<GamepadConnectionData>,
+    /// Touch events read this frame, oldest first.
+    pub touches: Vec<TouchData>,
+    pub virtual_cursor_operations: Vec<VirtualCursorOperation>,
+    pub virtual_cursor: Option<VirtualCursor>,
+    /// Virtual cursor position relative to the window center, y up (like `mouse_position`).
+    pub virtual_cursor_position: Option<(f32, f32)>,
+    /// Whether typed characters are collected into `input_state.typed_text`.
+    pub text_input_enabled: bool,
+    /// Whether synced accessibility nodes are sent to assistive technologies.
+    pub accessibility_enabled: bool,
+    /// True while an assistive technology has requested the accessibility tree.
+    pub accessibility_active: bool,
+    /// Label and role of each synced sprite and text's accessibility node, by Ruby entity ID.
+    pub accessibility_nodes: Vec<(u64, AccessibilityData)>,
     pub should_exit: bool,
+    /// Set in the frame the loop starts ending, before the app exits.
+    pub closing: bool,
+    /// Why the loop is ending; the first reason reported wins.
+    pub exit_reason: Option<ExitReason>,
     pub world_access: Option<*mut World>,
     pub camera_position: (f32, f32, f32),
     pub camera_scale: f32,
+    /// Camera rotation around the Z axis in radians.
+    pub camera_rotation: f32,
     pub camera_dirty: bool,
+    /// Shake `(intensity, duration_secs)` requested by Ruby, applied by `camera_sync_system`.
+    pub pending_camera_shake: Option<(f32, f32)>,
+    /// Entity the camera is kept on; replaces `camera_position` x and y each frame.
+    pub camera_follow: Option<CameraFollow>,
+    /// Frame in which `sprite_sync_system` last applied queued operations.
+    pub sprites_applied_frame: Option<u32>,
+    /// Frame in which `camera_sync_system` last wrote the camera transform.
+    pub camera_applied_frame: Option<u32>,
+    /// Seconds since the previous frame; `FIRST_FRAME_DELTA_SECS` on the first frame.
+    pub delta_secs: f32,
+    /// Seconds since the app started.
+    pub elapsed_secs: f64,
+    /// Bevy frame number of the current update, starting at 0.
+    pub frame_count: u32,
+    /// Latest headless frame read back from the GPU, until Ruby takes it.
+    pub frame_pixels: Option<FramePixels>,
+    /// Non-fatal warnings from the bridge and sync layers, drained by Ruby.
+    pub warnings: WarningLog,
+    /// Run times of the bridge systems while detailed stats are on.
+    pub system_timings: SystemTimings,
+    /// Receives every operation queued by the Ruby callback while journaling.
+    pub journal: Option<OperationJournal>,
+    /// Journal whose operations are queued one frame per update.
+    pub journal_replay: Option<JournalReplay>,
+    /// Per-frame checksums of delivered input and queued sync operations.
+    #[cfg(feature = "determinism_audit")]
+    pub audit: DeterminismAudit,
+}
+
+#[cfg(feature = "rendering")]
+impl RubyBridgeState {
+    /// Reports a non-fatal warning; see `WarningLog::push` for rate limiting.
+    pub fn push_warning(&mut self, category: &str, message: impl Into<String>, dedupe_key: &str) {
+        self.warnings.push(category, message, dedupe_key);
+    }
+
+    /// Starts ending the loop: the app exits at the end of this frame.
+    pub fn request_exit(&mut self, reason: ExitReason) {
+        if !self.closing {
+            self.closing = true;
+            self.exit_reason = Some(reason);
+        }
+        self.should_exit = true;
+    }
+
+    /// Entities asleep across the motion subsystems.
+    pub fn sleeping_count(&self) -> usize {
+        self.behaviors.sleeping_count() + self.sprite_sync.sleeping_jelly_count()
+    }
+
+    /// Wakes whatever the queued sprite, text and mesh operations target, so
+    /// behaviors re-compose transforms the syncs are about to overwrite.
+    fn wake_sync_targets(&mut self) {
+        let mut targets = Vec::new();
+        let mut clear = false;
+        for op in &self.sprite_sync.pending_operations {
+            match op.ruby_entity_id() {
+                Some(ruby_entity_id) => targets.push(ruby_entity_id),
+                None => clear |= matches!(op, SpriteOperation::Clear),
+            }
+        }
+        for op in &self.text_sync.pending_operations {
+            match op {
+                TextOperation::Sync { ruby_entity_id, .. } | TextOperation::Remove { ruby_entity_id } => {
+                    targets.push(*ruby_entity_id)
+                }
+                TextOperation::Clear => clear = true,
+            }
+        }
+        for op in &self.mesh_sync.pending_operations {
+            match op {
+                MeshOperation::Sync { ruby_entity_id, .. } | MeshOperation::Remove { ruby_entity_id } => {
+                    targets.push(*ruby_entity_id)
+                }
+                MeshOperation::Clear => clear = true,
+            }
+        }
+        if clear {
+            self.behaviors.wake_all();
+        }
+        for ruby_entity_id in targets {
+            self.behaviors.wake(ruby_entity_id);
+        }
+    }
+}
+
+/// A synced entity the camera stays on, `offset` away from its translation.
+///
+/// `camera_sync_system` reads the target after every system that moves
+/// entities, so the camera never lags the target by a frame.
+#[cfg(feature = "rendering")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraFollow {
+    pub ruby_entity_id: u64,
+    pub offset: (f32, f32),
 }
 
 #[cfg(feature = "rendering")]
@@ -120,11 +631,59 @@ pub struct GamepadRumbleCommand {
     pub stop: bool,
 }
 
+/// A captured Bevy window event.
+///
+/// `kind` is one of `close_requested`, `file_dropped`, `file_hovered` or
+/// `file_hover_cancelled`; `path` is set for dropped and hovered files.
+#[cfg(feature = "rendering")]
+#[derive(Debug, Clone)]
+pub struct WindowEventData {
+    pub kind: String,
+    pub window_id: u64,
+    pub path: Option<String>,
+}
+
+/// A file dragged onto the window.
+///
+/// `kind` is `dropped`, `hovered` or `cancelled`; `path` is unset for `cancelled`.
+#[cfg(feature = "rendering")]
+#[derive(Debug, Clone)]
+pub struct FileDropData {
+    pub kind: String,
+    pub path: Option<String>,
+}
+
+/// A gamepad connecting or disconnecting.
+///
+/// `kind` is `connected` or `disconnected`; `id` matches the gamepad's id in
+/// the input state, and `name` is the one it connected with.
+#[cfg(feature = "rendering")]
+#[derive(Debug, Clone)]
+pub struct GamepadConnectionData {
+    pub id: u64,
+    pub name: String,
+    pub kind: String,
+}
+
+/// A raw touch event, kept apart from the mouse state.
+///
+/// `phase` is `started`, `moved`, `ended` or `canceled`; `position` is in
+/// window pixels with y down, as Bevy reports it.
+#[cfg(feature = "rendering")]
+#[derive(Debug, Clone)]
+pub struct TouchData {
+    pub id: u64,
+    pub position: (f32, f32),
+    pub phase: String,
+}
+
 #[cfg(feature = "rendering")]
 #[derive(Debug, Clone)]
 pub struct PickingEventData {
     pub kind: String,
+    /// Bevy entity bits, or the region ID when `hit_region` is set.
     pub target_id: u64,
+    pub hit_region: bool,
     pub pointer_id: String,
     pub pointer_position: (f32, f32),
     pub button: Option<String>,
@@ -132,6 +691,11 @@ pub struct PickingEventData {
     pub depth: Option<f32>,
     pub hit_position: Option<(f32, f32, f32)>,
     pub hit_normal: Option<(f32, f32, f32)>,
+    /// Pointer movement since the previous `move` or `drag` event, in window
+    /// pixels with y down.
+    pub delta: Option<(f32, f32)>,
+    /// Pointer movement since `drag_start`, for `drag` and `drag_end`.
+    pub distance: Option<(f32, f32)>,
 }
 
 #[cfg(feature = "rendering")]
@@ -147,20 +711,160 @@ impl Default for RubyBridgeState {
             sprite_sync: SpriteSync::new(),
             text_sync: TextSync::new(),
             mesh_sync: MeshSync::new(),
+            behaviors: EntityBehaviors::new(),
+            lods: EntityLods::new(),
+            parallax: Parallax::new(),
+            pixel_snap: PixelSnap::new(),
+            hit_regions: HitRegions::new(),
+            clips: ClipPlayer::new(),
+            drag: DragGestures::new(),
+            floating_texts: FloatingTexts::new(),
+            group_throttle: GroupThrottle::new(),
+            sleep: MotionSleep::new(),
+            sprite_transforms: Vec::new(),
+            rewind_operations: Vec::new(),
+            rewind: None,
+            rewind_stats: None,
             pending_gamepad_rumble: Vec::new(),
+            rumble_patterns: RumbleScheduler::new(),
+            rumble_end_times: RumbleTracker::new(),
+            pending_audio: Vec::new(),
             picking_events: Vec::new(),
+            window_metrics: WindowMetrics::default(),
+            window_event_filter: WindowEventFilter::default(),
+            window_events: Vec::new(),
+            pending_window_mode: None,
+            pending_clear_color: None,
+            pending_window_title: None,
+            pending_window_size: None,
+            pending_cursor_visible: None,
+            pending_cursor_grab: None,
+            max_fps: None,
+            window_mode: WindowDisplayMode::Windowed,
+            windowed_resolution: None,
+            file_drops: Vec::new(),
+            gamepad_connections: Vec::new(),
+            touches: Vec::new(),
+            virtual_cursor_operations: Vec::new(),
+            virtual_cursor: None,
+            virtual_cursor_position: None,
+            text_input_enabled: false,
+            accessibility_enabled: true,
+            accessibility_active: false,
+            accessibility_nodes: Vec::new(),
             should_exit: false,
+            closing: false,
+            exit_reason: None,
             world_access: None,
             camera_position: (0.0, 0.0, 0.0),
             camera_scale: 1.0,
+            camera_rotation: 0.0,
             camera_dirty: false,
+            pending_camera_shake: None,
+            camera_follow: None,
+            sprites_applied_frame: None,
+            camera_applied_frame: None,
+            delta_secs: FIRST_FRAME_DELTA_SECS,
+            elapsed_secs: 0.0,
+            frame_count: 0,
+            frame_pixels: None,
+            warnings: WarningLog::default(),
+            system_timings: SystemTimings::default(),
+            journal: None,
+            journal_replay: None,
+            #[cfg(feature = "determinism_audit")]
+            audit: DeterminismAudit::new(),
         }
     }
 }
 
 #[cfg(feature = "rendering")]
-fn spawn_camera_2d_system(mut commands: Commands) {
-    commands.spawn((Camera::default(), Camera2d::default(), Transform::default()));
+fn spawn_camera_2d_system(mut commands: Commands, msaa: Res<CameraMsaa>) {
+    commands.spawn((
+        Camera::default(),
+        Camera2d::default(),
+        Transform::default(),
+        CameraShake::new(),
+        msaa.0.to_bevy(),
+    ));
+}
+
+/// Anti-aliasing the 2D camera is spawned with.
+#[cfg(feature = "rendering")]
+#[derive(bevy_ecs::system::Resource, Debug, Clone, Copy)]
+struct CameraMsaa(WindowMsaa);
+
+/// Size of the offscreen image a headless app renders into.
+#[cfg(feature = "rendering")]
+#[derive(bevy_ecs::system::Resource, Debug, Clone, Copy)]
+struct HeadlessTarget {
+    width: u32,
+    height: u32,
+}
+
+/// Points the 2D camera at an offscreen image and reads every rendered frame
+/// back into `RubyBridgeState::frame_pixels`.
+#[cfg(feature = "rendering")]
+fn headless_target_system(world: &mut World) {
+    use bevy_image::Image;
+    use bevy_render::camera::RenderTarget;
+    use bevy_render::gpu_readback::{Readback, ReadbackComplete};
+    use bevy_render::render_asset::RenderAssetUsages;
+    use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+    use bevy_render::renderer::RenderDevice;
+
+    let HeadlessTarget { width, height } = *world.resource::<HeadlessTarget>();
+    let mut image = Image::new_fill(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.texture_descriptor.usage |=
+        TextureUsages::COPY_SRC | TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING;
+    let handle = world.resource_mut::<bevy_asset::Assets<Image>>().add(image);
+
+    let mut cameras = world.query_filtered::<&mut Camera, bevy_ecs::query::With<Camera2d>>();
+    for mut camera in cameras.iter_mut(world) {
+        camera.target = RenderTarget::Image(handle.clone());
+    }
+
+    let padded_row_bytes = RenderDevice::align_copy_bytes_per_row(width as usize * 4);
+    world.spawn(Readback::texture(handle)).observe(
+        move |trigger: bevy_ecs::observer::Trigger<ReadbackComplete>, bridge: Res<RubyBridge>| {
+            let pixels = FramePixels::from_padded(&trigger.event().0, width, height, padded_row_bytes);
+            bridge.state.lock().unwrap().frame_pixels = Some(pixels);
+        },
+    );
+}
+
+/// Runner used instead of winit's event loop when headless: updates `frames`
+/// times, or until an `AppExit` such as the one `stop!` requests.
+#[cfg(feature = "rendering")]
+fn headless_runner(mut app: App, frames: Option<u32>) -> AppExit {
+    while app.plugins_state() == PluginsState::Adding {
+        bevy_tasks::tick_global_task_pools_on_main_thread();
+    }
+    app.finish();
+    app.cleanup();
+
+    let mut updated = 0;
+    while frames.is_none_or(|frames| updated < frames) {
+        app.update();
+        updated += 1;
+        if let Some(exit) = app.should_exit() {
+            return exit;
+        }
+    }
+    if let Some(bridge) = app.world().get_resource::<RubyBridge>() {
+        bridge.state.lock().unwrap().request_exit(ExitReason::StopCalled);
+    }
+    AppExit::Success
 }
 
 #[cfg(feature = "rendering")]
@@ -168,41 +872,165 @@ fn setup_default_sprite_texture_system(world: &mut World) {
     DefaultSpriteTexture::insert_into_world(world);
 }
 
+/// Icon decoded in `RenderApp::new`, waiting for winit to create the primary window.
+#[cfg(feature = "rendering")]
+#[derive(bevy_ecs::system::Resource)]
+struct PendingWindowIcon(Option<Icon>);
+
+/// Reads and decodes an icon file into RGBA pixels for winit.
+#[cfg(feature = "rendering")]
+fn load_window_icon(path: &str) -> Result<Icon, String> {
+    use bevy_image::{CompressedImageFormats, Image, ImageSampler, ImageType};
+    use bevy_render::render_asset::RenderAssetUsages;
+    use bevy_render::render_resource::TextureFormat;
+
+    let bytes = std::fs::read(path).map_err(|error| error.to_string())?;
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("png");
+    let image = Image::from_buffer(
+        &bytes,
+        ImageType::Extension(extension),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::Default,
+        RenderAssetUsages::MAIN_WORLD,
+    )
+    .map_err(|error| error.to_string())?;
+    let rgba = image
+        .convert(TextureFormat::Rgba8UnormSrgb)
+        .ok_or_else(|| format!("cannot convert {:?} pixels to RGBA", image.texture_descriptor.format))?;
+    let (width, height) = (rgba.width(), rgba.height());
+    Icon::from_rgba(rgba.data, width, height).map_err(|error| error.to_string())
+}
+
+/// Sets the pending icon once the primary winit window exists.
+#[cfg(feature = "rendering")]
+fn window_icon_system(
+    mut pending: ResMut<PendingWindowIcon>,
+    winit_windows: NonSend<WinitWindows>,
+    primary: bevy_ecs::system::Query<bevy_ecs::entity::Entity, bevy_ecs::query::With<PrimaryWindow>>,
+) {
+    if pending.0.is_none() {
+        return;
+    }
+    let Some(window) = primary
+        .get_single()
+        .ok()
+        .and_then(|entity| winit_windows.get_window(entity))
+    else {
+        return;
+    };
+    window.set_window_icon(pending.0.take());
+}
+
+/// Keyboard, mouse, touch and window input read by `ruby_bridge_system`.
+#[cfg(feature = "rendering")]
+#[derive(SystemParam)]
+struct BridgeInput<'w, 's> {
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    keyboard_events: EventReader<'w, 's, KeyboardInput>,
+    mouse_buttons: Res<'w, ButtonInput<MouseButton>>,
+    mouse_motion_events: EventReader<'w, 's, MouseMotion>,
+    mouse_wheel_events: EventReader<'w, 's, MouseWheel>,
+    touch_events: EventReader<'w, 's, TouchInput>,
+    windows: bevy_ecs::system::Query<'w, 's, &'static Window>,
+    file_drop_events: EventReader<'w, 's, FileDragAndDrop>,
+}
+
+/// Readers for every picking event forwarded to Ruby.
+#[cfg(feature = "rendering")]
+#[derive(SystemParam)]
+struct PickingEventReaders<'w, 's> {
+    over: EventReader<'w, 's, Pointer<Over>>,
+    out: EventReader<'w, 's, Pointer<Out>>,
+    down: EventReader<'w, 's, Pointer<Down>>,
+    up: EventReader<'w, 's, Pointer<Up>>,
+    click: EventReader<'w, 's, Pointer<Click>>,
+    moves: EventReader<'w, 's, Pointer<Move>>,
+    drag_start: EventReader<'w, 's, Pointer<DragStart>>,
+    drag: EventReader<'w, 's, Pointer<Drag>>,
+    drag_end: EventReader<'w, 's, Pointer<DragEnd>>,
+}
+
+/// Connected gamepads, their connection events and the names gamepad entities
+/// keep after disconnecting, grouped to stay within the system parameter limit.
+#[cfg(feature = "rendering")]
+type GamepadAccess<'w, 's> = (
+    bevy_ecs::system::Query<'w, 's, (bevy_ecs::entity::Entity, Option<&'static Name>, &'static Gamepad)>,
+    EventReader<'w, 's, GamepadConnectionEvent>,
+    bevy_ecs::system::Query<'w, 's, &'static Name>,
+);
+
+/// Appends the text a key press types, following the keyboard layout and
+/// shift state; key repeats type again. Keys that type nothing are ignored.
+#[cfg(feature = "rendering")]
+fn push_typed_key(typed: &mut TypedText, key: &Key) {
+    match key {
+        Key::Character(text) => typed.push_str(text),
+        Key::Space => typed.push_str(" "),
+        Key::Backspace => typed.push_edit(TextEdit::Backspace),
+        Key::Delete => typed.push_edit(TextEdit::Delete),
+        Key::Enter => typed.push_edit(TextEdit::Enter),
+        Key::Tab => typed.push_edit(TextEdit::Tab),
+        _ => {}
+    }
+}
+
 #[cfg(feature = "rendering")]
 fn ruby_bridge_system(
     bridge: Res<RubyBridge>,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mouse_buttons: Res<ButtonInput<MouseButton>>,
-    windows: bevy_ecs::system::Query<&Window>,
-    gamepad_query: bevy_ecs::system::Query<(bevy_ecs::entity::Entity, Option<&Name>, &Gamepad)>,
-    mut over_events: EventReader<Pointer<Over>>,
-    mut out_events: EventReader<Pointer<Out>>,
-    mut down_events: EventReader<Pointer<Down>>,
-    mut up_events: EventReader<Pointer<Up>>,
-    mut click_events: EventReader<Pointer<Click>>,
+    input: BridgeInput,
+    (gamepad_query, mut gamepad_connection_events, gamepad_names): GamepadAccess,
+    mut picking: PickingEventReaders,
     mut gamepad_rumble_requests: EventWriter<GamepadRumbleRequest>,
     mut exit_writer: EventWriter<AppExit>,
+    (time, frame_count): (Res<Time>, Res<FrameCount>),
 ) {
+    let BridgeInput {
+        keyboard,
+        mut keyboard_events,
+        mouse_buttons,
+        mut mouse_motion_events,
+        mut mouse_wheel_events,
+        mut touch_events,
+        windows,
+        mut file_drop_events,
+    } = input;
     let mut state = bridge.state.lock().unwrap();
 
-    state.input_state.clear();
-
-    for key in keyboard.get_pressed() {
-        if let Some(key_name) = keycode_to_string(*key) {
-            state.input_state.set_pressed(&key_name);
-        }
+    let delta_secs = time.delta_secs();
+    state.delta_secs = if delta_secs.is_finite() && delta_secs > 0.0 {
+        delta_secs
+    } else {
+        FIRST_FRAME_DELTA_SECS
+    };
+    state.elapsed_secs = time.elapsed_secs_f64();
+    state.frame_count = frame_count.0;
+    let frame = state.frame_count;
+    state.group_throttle.apply_operations(frame);
+    if state.sleep.apply_operations() {
+        state.behaviors.wake_all();
+        state.sprite_sync.wake_all_jelly();
     }
+    state.system_timings.end_frame();
+    let started = state.system_timings.start();
 
-    for key in keyboard.get_just_pressed() {
-        if let Some(key_name) = keycode_to_string(*key) {
-            state.input_state.set_just_pressed(&key_name);
-        }
-    }
+    state.input_state.clear();
 
-    for key in keyboard.get_just_released() {
-        if let Some(key_name) = keycode_to_string(*key) {
-            state.input_state.set_just_released(&key_name);
+    state.input_state.set_keyboard(&keyboard);
+    let elapsed_secs = state.elapsed_secs;
+    state.input_state.track_key_holds(elapsed_secs);
+
+    if state.text_input_enabled {
+        for event in keyboard_events.read() {
+            if event.state == ButtonState::Pressed {
+                push_typed_key(&mut state.input_state.typed_text, &event.logical_key);
+            }
         }
+    } else {
+        keyboard_events.clear();
     }
 
     if mouse_buttons.pressed(MouseButton::Left) {
@@ -225,6 +1053,62 @@ fn ruby_bridge_system(
         state.input_state.set_mouse_just_pressed("MIDDLE");
     }
 
+    if mouse_buttons.just_released(MouseButton::Left) {
+        state.input_state.set_mouse_just_released("LEFT");
+    }
+    if mouse_buttons.just_released(MouseButton::Right) {
+        state.input_state.set_mouse_just_released("RIGHT");
+    }
+    if mouse_buttons.just_released(MouseButton::Middle) {
+        state.input_state.set_mouse_just_released("MIDDLE");
+    }
+
+    for event in mouse_motion_events.read() {
+        state.input_state.add_mouse_motion(event.delta.x, event.delta.y);
+    }
+
+    for event in mouse_wheel_events.read() {
+        match event.unit {
+            MouseScrollUnit::Line => state.input_state.add_scroll_lines(event.x, event.y),
+            MouseScrollUnit::Pixel => state.input_state.add_scroll_pixels(event.x, event.y),
+        }
+    }
+
+    state.touches.clear();
+    state.input_state.touches.begin_frame();
+    for event in touch_events.read() {
+        state.input_state.touches.apply(
+            event.id,
+            touch_phase(event.phase),
+            (event.position.x, event.position.y),
+            event.force.map(normalized_touch_force),
+        );
+        state.touches.push(TouchData {
+            id: event.id,
+            position: (event.position.x, event.position.y),
+            phase: touch_phase(event.phase).name().to_string(),
+        });
+    }
+
+    for event in gamepad_connection_events.read() {
+        let id = event.gamepad.to_bits();
+        let (kind, name) = match &event.connection {
+            GamepadConnection::Connected { name, .. } => ("connected", Some(name.clone())),
+            GamepadConnection::Disconnected => (
+                "disconnected",
+                gamepad_names
+                    .get(event.gamepad)
+                    .ok()
+                    .map(|name| name.as_str().to_string()),
+            ),
+        };
+        state.gamepad_connections.push(GamepadConnectionData {
+            id,
+            name: name.unwrap_or_else(|| format!("Gamepad {}", id)),
+            kind: kind.to_string(),
+        });
+    }
+
     for (entity, maybe_name, gamepad) in gamepad_query.iter() {
         let id = entity.to_bits();
         let gamepad_name = maybe_name
@@ -261,118 +1145,169 @@ fn ruby_bridge_system(
                 .input_state
                 .set_gamepad_axis(id, &axis_name, axis_value);
         }
+
+        state
+            .input_state
+            .set_gamepad_battery(id, gamepad_battery_level(gamepad));
     }
 
     if let Ok(window) = windows.get_single() {
-        if let Some(pos) = window.cursor_position() {
-            let center_x = window.width() / 2.0;
-            let center_y = window.height() / 2.0;
-            state.input_state.mouse_position = (pos.x - center_x, center_y - pos.y);
-        }
+        state.window_metrics = window_metrics(window);
+
+        let center_x = window.width() / 2.0;
+        let center_y = window.height() / 2.0;
+        let cursor = window
+            .cursor_position()
+            .map(|pos| (pos.x - center_x, center_y - pos.y));
+        state.input_state.set_cursor_position(cursor);
     }
 
     state.picking_events.clear();
 
-    for event in over_events.read() {
-        let hit = &event.event.hit;
-        state.picking_events.push(PickingEventData {
-            kind: "over".to_string(),
-            target_id: event.target.to_bits(),
-            pointer_id: pointer_id_to_string(event.pointer_id),
-            pointer_position: (
-                event.pointer_location.position.x,
-                event.pointer_location.position.y,
-            ),
-            button: None,
-            camera_id: Some(hit.camera.to_bits()),
-            depth: Some(hit.depth),
-            hit_position: hit
-                .position
-                .map(|position| (position.x, position.y, position.z)),
-            hit_normal: hit.normal.map(|normal| (normal.x, normal.y, normal.z)),
-        });
+    for event in picking.over.read() {
+        let event_data = picking_event(
+            "over",
+            &state.hit_regions,
+            event.target,
+            event.pointer_id,
+            &event.pointer_location,
+            Some(&event.event.hit),
+            None,
+        );
+        state.picking_events.push(event_data);
     }
 
-    for event in out_events.read() {
-        let hit = &event.event.hit;
-        state.picking_events.push(PickingEventData {
-            kind: "out".to_string(),
-            target_id: event.target.to_bits(),
-            pointer_id: pointer_id_to_string(event.pointer_id),
-            pointer_position: (
-                event.pointer_location.position.x,
-                event.pointer_location.position.y,
-            ),
-            button: None,
-            camera_id: Some(hit.camera.to_bits()),
-            depth: Some(hit.depth),
-            hit_position: hit
-                .position
-                .map(|position| (position.x, position.y, position.z)),
-            hit_normal: hit.normal.map(|normal| (normal.x, normal.y, normal.z)),
-        });
+    for event in picking.out.read() {
+        let event_data = picking_event(
+            "out",
+            &state.hit_regions,
+            event.target,
+            event.pointer_id,
+            &event.pointer_location,
+            Some(&event.event.hit),
+            None,
+        );
+        state.picking_events.push(event_data);
     }
 
-    for event in down_events.read() {
-        let hit = &event.event.hit;
-        state.picking_events.push(PickingEventData {
-            kind: "down".to_string(),
-            target_id: event.target.to_bits(),
-            pointer_id: pointer_id_to_string(event.pointer_id),
-            pointer_position: (
-                event.pointer_location.position.x,
-                event.pointer_location.position.y,
-            ),
-            button: Some(pointer_button_to_string(event.event.button).to_string()),
-            camera_id: Some(hit.camera.to_bits()),
-            depth: Some(hit.depth),
-            hit_position: hit
-                .position
-                .map(|position| (position.x, position.y, position.z)),
-            hit_normal: hit.normal.map(|normal| (normal.x, normal.y, normal.z)),
-        });
+    for event in picking.down.read() {
+        let event_data = picking_event(
+            "down",
+            &state.hit_regions,
+            event.target,
+            event.pointer_id,
+            &event.pointer_location,
+            Some(&event.event.hit),
+            Some(event.event.button),
+        );
+        state.picking_events.push(event_data);
     }
 
-    for event in up_events.read() {
-        let hit = &event.event.hit;
-        state.picking_events.push(PickingEventData {
-            kind: "up".to_string(),
-            target_id: event.target.to_bits(),
-            pointer_id: pointer_id_to_string(event.pointer_id),
-            pointer_position: (
-                event.pointer_location.position.x,
-                event.pointer_location.position.y,
-            ),
-            button: Some(pointer_button_to_string(event.event.button).to_string()),
-            camera_id: Some(hit.camera.to_bits()),
-            depth: Some(hit.depth),
-            hit_position: hit
-                .position
-                .map(|position| (position.x, position.y, position.z)),
-            hit_normal: hit.normal.map(|normal| (normal.x, normal.y, normal.z)),
-        });
+    for event in picking.up.read() {
+        let event_data = picking_event(
+            "up",
+            &state.hit_regions,
+            event.target,
+            event.pointer_id,
+            &event.pointer_location,
+            Some(&event.event.hit),
+            Some(event.event.button),
+        );
+        state.picking_events.push(event_data);
     }
 
-    for event in click_events.read() {
-        let hit = &event.event.hit;
-        state.picking_events.push(PickingEventData {
-            kind: "click".to_string(),
-            target_id: event.target.to_bits(),
-            pointer_id: pointer_id_to_string(event.pointer_id),
-            pointer_position: (
-                event.pointer_location.position.x,
-                event.pointer_location.position.y,
-            ),
-            button: Some(pointer_button_to_string(event.event.button).to_string()),
-            camera_id: Some(hit.camera.to_bits()),
-            depth: Some(hit.depth),
-            hit_position: hit
-                .position
-                .map(|position| (position.x, position.y, position.z)),
-            hit_normal: hit.normal.map(|normal| (normal.x, normal.y, normal.z)),
+    for event in picking.click.read() {
+        let event_data = picking_event(
+            "click",
+            &state.hit_regions,
+            event.target,
+            event.pointer_id,
+            &event.pointer_location,
+            Some(&event.event.hit),
+            Some(event.event.button),
+        );
+        state.picking_events.push(event_data);
+    }
+
+    for event in picking.moves.read() {
+        let event_data = PickingEventData {
+            delta: Some((event.event.delta.x, event.event.delta.y)),
+            ..picking_event(
+                "move",
+                &state.hit_regions,
+                event.target,
+                event.pointer_id,
+                &event.pointer_location,
+                Some(&event.event.hit),
+                None,
+            )
+        };
+        state.picking_events.push(event_data);
+    }
+
+    for event in picking.drag_start.read() {
+        let event_data = picking_event(
+            "drag_start",
+            &state.hit_regions,
+            event.target,
+            event.pointer_id,
+            &event.pointer_location,
+            Some(&event.event.hit),
+            Some(event.event.button),
+        );
+        state.picking_events.push(event_data);
+    }
+
+    // Drags carry no hit data: the pointer may have left the target.
+    for event in picking.drag.read() {
+        let event_data = PickingEventData {
+            delta: Some((event.event.delta.x, event.event.delta.y)),
+            distance: Some((event.event.distance.x, event.event.distance.y)),
+            ..picking_event(
+                "drag",
+                &state.hit_regions,
+                event.target,
+                event.pointer_id,
+                &event.pointer_location,
+                None,
+                Some(event.event.button),
+            )
+        };
+        state.picking_events.push(event_data);
+    }
+
+    for event in picking.drag_end.read() {
+        let event_data = PickingEventData {
+            distance: Some((event.event.distance.x, event.event.distance.y)),
+            ..picking_event(
+                "drag_end",
+                &state.hit_regions,
+                event.target,
+                event.pointer_id,
+                &event.pointer_location,
+                None,
+                Some(event.event.button),
+            )
+        };
+        state.picking_events.push(event_data);
+    }
+
+    for event in file_drop_events.read() {
+        let (kind, path) = match event {
+            FileDragAndDrop::DroppedFile { path_buf, .. } => ("dropped", Some(path_buf)),
+            FileDragAndDrop::HoveredFile { path_buf, .. } => ("hovered", Some(path_buf)),
+            FileDragAndDrop::HoveredFileCanceled { .. } => ("cancelled", None),
+        };
+        state.file_drops.push(FileDropData {
+            kind: kind.to_string(),
+            path: path.map(|path| path.to_string_lossy().into_owned()),
         });
     }
 
+    #[cfg(feature = "determinism_audit")]
+    audit_frame_input(&mut state);
+    let sync_marks = pending_sync_marks(&state);
+
     drop(state);
 
     if let Ok(mut callback) = bridge.callback.lock() {
@@ -383,13 +1318,21 @@ fn ruby_bridge_system(
     }
 
     let mut state = bridge.state.lock().unwrap();
-    for command in state.pending_gamepad_rumble.drain(..) {
+    #[cfg(feature = "determinism_audit")]
+    audit_frame_operations(&mut state, sync_marks);
+    journal_frame_operations(&mut state, sync_marks);
+    let now = state.elapsed_secs;
+    for command in std::mem::take(&mut state.pending_gamepad_rumble) {
         let gamepad = bevy_ecs::entity::Entity::from_bits(command.gamepad_id);
         if command.stop || (command.strong_motor <= 0.0 && command.weak_motor <= 0.0) {
             gamepad_rumble_requests.send(GamepadRumbleRequest::Stop { gamepad });
+            state.rumble_end_times.stop(command.gamepad_id);
             continue;
         }
 
+        state
+            .rumble_end_times
+            .add(command.gamepad_id, now, command.duration_secs);
         gamepad_rumble_requests.send(GamepadRumbleRequest::Add {
             gamepad,
             intensity: GamepadRumbleIntensity {
@@ -399,119 +1342,1056 @@ fn ruby_bridge_system(
             duration: std::time::Duration::from_secs_f32(command.duration_secs.max(0.0)),
         });
     }
+    let delta_secs = state.delta_secs;
+    for step in state.rumble_patterns.advance(delta_secs) {
+        match step {
+            RumbleStep::Start { gamepad_id, segment } => {
+                let gamepad = bevy_ecs::entity::Entity::from_bits(gamepad_id);
+                // Rumble requests add up, so the previous segment is stopped first.
+                gamepad_rumble_requests.send(GamepadRumbleRequest::Stop { gamepad });
+                gamepad_rumble_requests.send(GamepadRumbleRequest::Add {
+                    gamepad,
+                    intensity: GamepadRumbleIntensity {
+                        strong_motor: segment.strong_motor,
+                        weak_motor: segment.weak_motor,
+                    },
+                    duration: std::time::Duration::from_secs_f32(segment.duration_secs),
+                });
+                state
+                    .rumble_end_times
+                    .restart(gamepad_id, now, segment.duration_secs);
+            }
+            RumbleStep::Stop { gamepad_id } => {
+                let gamepad = bevy_ecs::entity::Entity::from_bits(gamepad_id);
+                gamepad_rumble_requests.send(GamepadRumbleRequest::Stop { gamepad });
+                state.rumble_end_times.stop(gamepad_id);
+            }
+        }
+    }
+    state.rumble_end_times.prune(now);
+
+    state.system_timings.finish("ruby_bridge", started);
 
     if state.should_exit {
         exit_writer.send(AppExit::Success);
     }
 }
 
-#[cfg(feature = "rendering")]
-fn sprite_sync_system(world: &mut World) {
-    let state_arc = {
-        let bridge = world.resource::<RubyBridge>();
-        bridge.state.clone()
-    };
+/// Folds this frame's input into the audit.
+#[cfg(all(feature = "rendering", feature = "determinism_audit"))]
+fn audit_frame_input(state: &mut RubyBridgeState) {
+    state.audit.record(&state.input_state);
+    state.audit.record_all(&state.picking_events);
+    state.audit.record_all(&state.touches);
+}
 
-    let mut state = state_arc.lock().unwrap();
-    state.sprite_sync.apply_pending(world);
+/// Marks where the sprite, text and mesh operations queued by Ruby will start.
+#[cfg(feature = "rendering")]
+fn pending_sync_marks(state: &RubyBridgeState) -> (usize, usize, usize) {
+    (
+        state.sprite_sync.pending_operations.len(),
+        state.text_sync.pending_operations.len(),
+        state.mesh_sync.pending_operations.len(),
+    )
 }
 
+/// Appends the operations queued by the Ruby callback to the journal, then
+/// queues the next frame of a journal being replayed.
 #[cfg(feature = "rendering")]
-fn text_sync_system(world: &mut World) {
-    let state_arc = {
-        let bridge = world.resource::<RubyBridge>();
-        bridge.state.clone()
+fn journal_frame_operations(state: &mut RubyBridgeState, marks: (usize, usize, usize)) {
+    if state.journal.is_some() {
+        let (sprites, texts, meshes) = marks;
+        let mut operations: Vec<JournalOperation> = state
+            .sprite_sync
+            .pending_operations
+            .iter()
+            .skip(sprites)
+            .cloned()
+            .map(JournalOperation::Sprite)
+            .collect();
+        operations.extend(
+            state
+                .text_sync
+                .pending_operations
+                .iter()
+                .skip(texts)
+                .cloned()
+                .map(JournalOperation::Text),
+        );
+        operations.extend(
+            state
+                .mesh_sync
+                .pending_operations
+                .iter()
+                .skip(meshes)
+                .cloned()
+                .map(JournalOperation::Mesh),
+        );
+        if state.camera_dirty {
+            operations.push(JournalOperation::Camera {
+                position: state.camera_position,
+                scale: state.camera_scale,
+                rotation: state.camera_rotation,
+            });
+        }
+        if let Some((intensity, duration_secs)) = state.pending_camera_shake {
+            operations.push(JournalOperation::CameraShake {
+                intensity,
+                duration_secs,
+            });
+        }
+
+        let frame = state.frame_count;
+        if let Some(Err(error)) = state
+            .journal
+            .as_mut()
+            .map(|journal| journal.record_frame(frame, &operations))
+        {
+            state.journal = None;
+            state.push_warning("journal", format!("journaling stopped: {}", error), "journal_write");
+        }
+    }
+
+    let Some(replay) = state.journal_replay.as_mut() else {
+        return;
     };
+    let Some(operations) = replay.next_frame() else {
+        state.journal_replay = None;
+        return;
+    };
+    for op in operations {
+        match op {
+            JournalOperation::Sprite(op) => state.sprite_sync.pending_operations.push(op),
+            JournalOperation::Text(op) => state.text_sync.pending_operations.push(op),
+            JournalOperation::Mesh(op) => state.mesh_sync.pending_operations.push(op),
+            JournalOperation::Camera {
+                position,
+                scale,
+                rotation,
+            } => {
+                state.camera_position = position;
+                state.camera_scale = scale;
+                state.camera_rotation = rotation;
+                state.camera_dirty = true;
+            }
+            JournalOperation::CameraShake {
+                intensity,
+                duration_secs,
+            } => state.pending_camera_shake = Some((intensity, duration_secs)),
+        }
+    }
+}
 
-    let mut state = state_arc.lock().unwrap();
-    state.text_sync.apply_pending(world);
+/// Folds the operations queued by the Ruby callback into the audit and closes the frame.
+#[cfg(all(feature = "rendering", feature = "determinism_audit"))]
+fn audit_frame_operations(state: &mut RubyBridgeState, marks: (usize, usize, usize)) {
+    let (sprites, texts, meshes) = marks;
+    state
+        .audit
+        .record_all(state.sprite_sync.pending_operations.iter().skip(sprites));
+    state
+        .audit
+        .record_all(state.text_sync.pending_operations.iter().skip(texts));
+    state
+        .audit
+        .record_all(state.mesh_sync.pending_operations.iter().skip(meshes));
+    let diverged = state.audit.divergence().is_some();
+    state.audit.finish_frame();
+    if let Some(divergence) = state.audit.divergence().filter(|_| !diverged) {
+        state.push_warning(
+            "determinism",
+            format!(
+                "frame {} diverged (expected {:x?}, got {:x?})",
+                divergence.frame, divergence.expected, divergence.actual
+            ),
+            "determinism_divergence",
+        );
+    }
 }
 
 #[cfg(feature = "rendering")]
-fn mesh_sync_system(world: &mut World) {
-    let state_arc = {
-        let bridge = world.resource::<RubyBridge>();
+fn accessibility_system(
+    bridge: Res<RubyBridge>,
+    requested: Res<AccessibilityRequested>,
+    mut manage_updates: ResMut<ManageAccessibilityUpdates>,
+) {
+    let mut state = bridge.state.lock().unwrap();
+    if manage_updates.get() != state.accessibility_enabled {
+        manage_updates.set(state.accessibility_enabled);
+    }
+    state.accessibility_active = requested.get();
+}
+
+#[cfg(feature = "rendering")]
+fn clear_color(rgba: [f32; 4]) -> ClearColor {
+    ClearColor(Color::srgba(rgba[0], rgba[1], rgba[2], rgba[3]))
+}
+
+#[cfg(feature = "rendering")]
+fn clear_color_system(bridge: Res<RubyBridge>, mut commands: Commands) {
+    let mut state = bridge.state.lock().unwrap();
+    if let Some(rgba) = state.pending_clear_color.take() {
+        commands.insert_resource(clear_color(rgba));
+    }
+}
+
+/// Time one frame may take under a `max_fps` cap, or `None` when uncapped.
+#[cfg(feature = "rendering")]
+fn max_fps_frame_period(max_fps: Option<f32>) -> Option<Duration> {
+    max_fps
+        .filter(|fps| fps.is_finite() && *fps > 0.0)
+        .map(|fps| Duration::from_secs_f64(1.0 / fps as f64))
+}
+
+/// Sleeps out the rest of the frame when `max_fps` is set. A frame starts
+/// when the previous one's limiter returns, so the time spent rendering
+/// counts against the cap.
+#[cfg(feature = "rendering")]
+fn frame_limiter_system(bridge: Res<RubyBridge>, mut frame_start: Local<Option<Instant>>) {
+    let max_fps = bridge.state.lock().unwrap().max_fps;
+    if let (Some(period), Some(start)) = (max_fps_frame_period(max_fps), *frame_start) {
+        let deadline = start + period;
+        // `sleep` can overshoot by a scheduler tick, so yield through the last millisecond.
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            if remaining > Duration::from_millis(1) {
+                std::thread::sleep(remaining - Duration::from_millis(1));
+            } else {
+                std::thread::yield_now();
+            }
+        }
+    }
+    *frame_start = Some(Instant::now());
+}
+
+/// Drains audio commands. No audio backend is linked into the render app
+/// yet, so they are dropped with a rate-limited warning.
+#[cfg(feature = "rendering")]
+fn audio_system(bridge: Res<RubyBridge>) {
+    let mut state = bridge.state.lock().unwrap();
+    if state.pending_audio.is_empty() {
+        return;
+    }
+    let dropped = state.pending_audio.len();
+    state.pending_audio.clear();
+    state.push_warning(
+        "audio",
+        format!("dropped {} Bevy::Audio commands: this build has no audio backend", dropped),
+        "audio_backend",
+    );
+}
+
+/// Stamps warnings with this frame and flushes rate-limited repeats.
+#[cfg(feature = "rendering")]
+fn warnings_clock_system(bridge: Res<RubyBridge>, time: Res<Time>) {
+    let mut state = bridge.state.lock().unwrap();
+    state.warnings.begin_frame(time.elapsed_secs_f64());
+}
+
+#[cfg(feature = "rendering")]
+fn window_mode_system(
+    bridge: Res<RubyBridge>,
+    mut windows: bevy_ecs::system::Query<&mut Window, bevy_ecs::query::With<PrimaryWindow>>,
+) {
+    let mut state = bridge.state.lock().unwrap();
+    let Some(mode) = state.pending_window_mode.take() else {
+        return;
+    };
+    if let Ok(mut window) = windows.get_single_mut() {
+        // Fullscreen resizes the window to the monitor, so the windowed size
+        // is remembered on the way out and put back on the way in.
+        if state.window_mode == WindowDisplayMode::Windowed && mode != WindowDisplayMode::Windowed {
+            state.windowed_resolution = Some((window.resolution.width(), window.resolution.height()));
+        } else if mode == WindowDisplayMode::Windowed {
+            if let Some((width, height)) = state.windowed_resolution.take() {
+                window.resolution.set(width, height);
+            }
+        }
+        window.mode = mode.to_bevy();
+        state.window_mode = mode;
+    }
+}
+
+#[cfg(feature = "rendering")]
+fn window_sync_system(
+    bridge: Res<RubyBridge>,
+    mut windows: bevy_ecs::system::Query<&mut Window, bevy_ecs::query::With<PrimaryWindow>>,
+) {
+    let mut state = bridge.state.lock().unwrap();
+    if state.pending_window_title.is_none() && state.pending_window_size.is_none() {
+        return;
+    }
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    if let Some(title) = state.pending_window_title.take() {
+        window.title = title;
+    }
+    if let Some((width, height)) = state.pending_window_size.take() {
+        window.resolution.set(width, height);
+    }
+}
+
+#[cfg(feature = "rendering")]
+fn cursor_system(
+    bridge: Res<RubyBridge>,
+    mut windows: bevy_ecs::system::Query<&mut Window, bevy_ecs::query::With<PrimaryWindow>>,
+) {
+    let mut state = bridge.state.lock().unwrap();
+    if state.pending_cursor_visible.is_none() && state.pending_cursor_grab.is_none() {
+        return;
+    }
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    if let Some(visible) = state.pending_cursor_visible.take() {
+        window.cursor_options.visible = visible;
+    }
+    if let Some(grab) = state.pending_cursor_grab.take() {
+        window.cursor_options.grab_mode = grab.to_bevy();
+    }
+}
+
+#[cfg(feature = "rendering")]
+fn window_event_system(
+    bridge: Res<RubyBridge>,
+    mut close_requested_events: EventReader<WindowCloseRequested>,
+    mut file_drag_and_drop_events: EventReader<FileDragAndDrop>,
+    mut exit_events: EventReader<AppExit>,
+) {
+    let mut state = bridge.state.lock().unwrap();
+    let filter = state.window_event_filter;
+
+    // Read before the bridge so Ruby sees `closing` in the frame the close was requested.
+    if exit_events.read().last().is_some() {
+        state.request_exit(ExitReason::UserClose);
+    }
+
+    for event in close_requested_events.read() {
+        state.request_exit(ExitReason::UserClose);
+        if filter.close_requested {
+            state.window_events.push(WindowEventData {
+                kind: "close_requested".to_string(),
+                window_id: event.window.to_bits(),
+                path: None,
+            });
+        }
+    }
+
+    for event in file_drag_and_drop_events.read() {
+        if !filter.file_drag_and_drop {
+            continue;
+        }
+        let (kind, window, path) = match event {
+            FileDragAndDrop::DroppedFile { window, path_buf } => {
+                ("file_dropped", window, Some(path_buf))
+            }
+            FileDragAndDrop::HoveredFile { window, path_buf } => {
+                ("file_hovered", window, Some(path_buf))
+            }
+            FileDragAndDrop::HoveredFileCanceled { window } => {
+                ("file_hover_cancelled", window, None)
+            }
+        };
+        state.window_events.push(WindowEventData {
+            kind: kind.to_string(),
+            window_id: window.to_bits(),
+            path: path.map(|path| path.to_string_lossy().into_owned()),
+        });
+    }
+}
+
+/// Queues sprite LOD changes for the camera scale requested by Ruby.
+#[cfg(feature = "rendering")]
+fn lod_system(bridge: Res<RubyBridge>) {
+    let mut guard = bridge.state.lock().unwrap();
+    let state = &mut *guard;
+    let started = state.system_timings.start();
+    let operations = state.lods.update(state.camera_scale);
+    state.sprite_sync.pending_operations.extend(operations);
+    state.system_timings.finish("lod", started);
+}
+
+/// Picking backend for hit regions: reports the topmost region under each
+/// pointer as a hit on its stand-in entity, at the depth of a sprite at
+/// `z = layer`, so Bevy's hover map orders it against rendered entities.
+#[cfg(feature = "rendering")]
+fn hit_region_picking_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+
+    let mut guard = state_arc.lock().unwrap();
+    let state = &mut *guard;
+    if state.hit_regions.is_empty() && state.hit_regions.pending_operations.is_empty() {
+        return;
+    }
+    let started = state.system_timings.start();
+    state.hit_regions.sync_entities(world);
+
+    let camera = world
+        .query_filtered::<(
+            bevy_ecs::entity::Entity,
+            &Camera,
+            &GlobalTransform,
+            &OrthographicProjection,
+        ), bevy_ecs::query::With<Camera2d>>()
+        .iter(world)
+        .find(|(_, camera, _, _)| camera.is_active)
+        .map(|(entity, camera, transform, projection)| (entity, camera.clone(), *transform, projection.near));
+    let pointers: Vec<(PointerId, Location)> = world
+        .query::<(&PointerId, &PointerLocation)>()
+        .iter(world)
+        .filter_map(|(pointer_id, location)| location.location().map(|location| (*pointer_id, location.clone())))
+        .collect();
+
+    let mut hits = Vec::new();
+    if let Some((camera_entity, camera, camera_transform, near)) = camera {
+        for (pointer_id, location) in pointers {
+            let viewport_min = camera
+                .logical_viewport_rect()
+                .map(|rect| rect.min)
+                .unwrap_or_default();
+            let world_position = camera
+                .viewport_to_world_2d(&camera_transform, location.position - viewport_min)
+                .ok();
+            let screen = (location.position.x, location.position.y);
+            let picks = state
+                .hit_regions
+                .topmost(screen, world_position.map(|position| (position.x, position.y)))
+                .and_then(|(id, region)| {
+                    let entity = state.hit_regions.entity(id)?;
+                    let layer = region.layer as f32;
+                    let depth = -near - (layer - camera_transform.translation().z);
+                    let position = world_position.map(|position| position.extend(layer));
+                    Some((
+                        entity,
+                        HitData::new(camera_entity, depth, position, Some(bevy_math::Vec3::Z)),
+                    ))
+                })
+                .into_iter()
+                .collect();
+            hits.push(PointerHits::new(pointer_id, picks, camera.order as f32));
+        }
+    }
+
+    world.send_event_batch(hits);
+    state.system_timings.finish("hit_regions", started);
+}
+
+/// Takes last frame's parallax offsets off before anything re-authors the sprites.
+#[cfg(feature = "rendering")]
+fn parallax_restore_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+
+    let mut guard = state_arc.lock().unwrap();
+    let state = &mut *guard;
+    let started = state.system_timings.start();
+    let sprite_sync = &state.sprite_sync;
+    state
+        .parallax
+        .restore(world, |ruby_entity_id| sprite_sync.bevy_entity(ruby_entity_id));
+    state.system_timings.finish("parallax", started);
+}
+
+#[cfg(feature = "rendering")]
+fn sprite_sync_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+
+    let mut state = state_arc.lock().unwrap();
+    let started = state.system_timings.start();
+    let ops: Vec<_> = state.sprite_sync.pending_operations.drain(..).collect();
+    let frame = state.frame_count;
+    state.sprite_sync.pending_operations = state.group_throttle.filter(ops, frame);
+    state.wake_sync_targets();
+    if !state.sprite_sync.pending_operations.is_empty() {
+        state.sprites_applied_frame = Some(frame);
+    }
+    state.sprite_sync.apply_pending(world);
+    for path in state.sprite_sync.resolve_textures(world) {
+        let message = format!("could not load texture {}; drawing the error texture", path);
+        state.push_warning("texture", message, &path);
+    }
+    state.system_timings.finish("sprite_sync", started);
+}
+
+/// Puts back the translations `pixel_snap_system` rounded last frame, before
+/// anything re-authors them.
+#[cfg(feature = "rendering")]
+fn pixel_snap_restore_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
         bridge.state.clone()
     };
 
     let mut state = state_arc.lock().unwrap();
+    if state.pixel_snap.is_empty() {
+        return;
+    }
+    let started = state.system_timings.start();
+    state.pixel_snap.restore(world);
+    state.system_timings.finish("pixel_snap", started);
+}
+
+/// Rounds pixel-perfect sprites and texts onto physical pixels after every
+/// bridge-driven motion and camera shake, before transforms propagate.
+#[cfg(feature = "rendering")]
+fn pixel_snap_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+
+    let mut guard = state_arc.lock().unwrap();
+    let state = &mut *guard;
+    if state.pixel_snap.is_empty() && state.pixel_snap.pending_operations.is_empty() {
+        return;
+    }
+    let started = state.system_timings.start();
+
+    let grid = world
+        .query_filtered::<(&Camera, &Transform, &OrthographicProjection), bevy_ecs::query::With<Camera2d>>()
+        .iter(world)
+        .find(|(camera, _, _)| camera.is_active)
+        .and_then(|(camera, transform, projection)| {
+            let viewport = camera.physical_viewport_size()?;
+            Some(PixelGrid {
+                camera: (transform.translation.x, transform.translation.y),
+                scale: transform.scale.x * projection.scale,
+                rotation: transform.rotation.to_euler(bevy_math::EulerRot::ZYX).0,
+                scale_factor: camera.target_scaling_factor().unwrap_or(1.0),
+                viewport: (viewport.x as f32, viewport.y as f32),
+            })
+        });
+
+    match grid {
+        Some(grid) => {
+            let sprites = state
+                .sprite_sync
+                .synced_sprites()
+                .map(|(ruby_entity_id, entity, _)| (ruby_entity_id, entity));
+            state
+                .pixel_snap
+                .apply(world, &grid, sprites.chain(state.text_sync.synced_texts()));
+        }
+        None => state.pixel_snap.apply_operations(),
+    }
+    state.system_timings.finish("pixel_snap", started);
+}
+
+/// Steps the springs of mesh-mode sprites after this frame's syncs and impulses.
+#[cfg(feature = "rendering")]
+fn jelly_system(world: &mut World) {
+    let delta_secs = world.resource::<Time>().delta_secs();
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+
+    let mut state = state_arc.lock().unwrap();
+    let started = state.system_timings.start();
+    let sleep = state.sleep.config();
+    state.sprite_sync.step_jelly(world, delta_secs, &sleep);
+    state.system_timings.finish("jelly", started);
+}
+
+#[cfg(feature = "rendering")]
+fn text_sync_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+
+    let mut state = state_arc.lock().unwrap();
+    let started = state.system_timings.start();
+    state.text_sync.apply_pending(world);
+    state.system_timings.finish("text_sync", started);
+}
+
+#[cfg(feature = "rendering")]
+fn mesh_sync_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+
+    let mut state = state_arc.lock().unwrap();
+    let started = state.system_timings.start();
     state.mesh_sync.apply_pending(world);
+    state.system_timings.finish("mesh_sync", started);
+}
+
+#[cfg(feature = "rendering")]
+fn clip_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+    let delta_secs = world.resource::<Time>().delta_secs();
+
+    let mut guard = state_arc.lock().unwrap();
+    let state = &mut *guard;
+    let started = state.system_timings.start();
+    let (sprite_sync, text_sync, mesh_sync) = (&state.sprite_sync, &state.text_sync, &state.mesh_sync);
+    let moved = state.clips.apply_pending(world, delta_secs, |ruby_entity_id| {
+        sprite_sync
+            .bevy_entity(ruby_entity_id)
+            .or_else(|| text_sync.bevy_entity(ruby_entity_id))
+            .or_else(|| mesh_sync.bevy_entity(ruby_entity_id))
+    });
+    for ruby_entity_id in moved {
+        state.behaviors.wake(ruby_entity_id);
+    }
+    state.system_timings.finish("clip", started);
+}
+
+/// Picks up, moves and drops draggable sprites with the mouse. Runs after
+/// the syncs and clips so a held sprite follows the pointer over whatever
+/// was synced this frame.
+///
+/// Pickup and drop targets come from the picking hover map; while held, the
+/// sprite neither hovers nor blocks what lies below it.
+#[cfg(feature = "rendering")]
+fn drag_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+
+    let mut guard = state_arc.lock().unwrap();
+    let state = &mut *guard;
+    if state.drag.is_idle() && state.drag.pending_operations.is_empty() {
+        return;
+    }
+    let started = state.system_timings.start();
+    if let Some(dropped) = state.drag.apply_operations() {
+        put_down_sprite(world, state, dropped.id, None, dropped.origin.2);
+    }
+    if let Some(held) = state.drag.active().map(|active| active.id)
+        && state.sprite_sync.bevy_entity(held).is_none()
+    {
+        state.drag.cancel();
+    }
+
+    let pointer = state.input_state.previous_cursor.map(|cursor| {
+        crate::pointer_world_position(
+            cursor,
+            (state.camera_position.0, state.camera_position.1),
+            state.camera_scale,
+            state.camera_rotation,
+        )
+    });
+    let hovered = hovered_entities(world);
+    let buttons = &state.input_state;
+    let (pressed, released) = (
+        buttons.mouse_buttons_just_pressed.contains("LEFT"),
+        buttons.mouse_buttons_just_released.contains("LEFT"),
+    );
+
+    if pressed
+        && !state.drag.is_dragging()
+        && let Some(pointer) = pointer
+        && let Some(&(entity, _)) = hovered.first()
+        && let Some(id) = synced_sprite_id(state, entity)
+        && state.drag.is_enabled(id)
+        && let Some(transform) = world.get::<Transform>(entity)
+    {
+        let origin = transform.translation;
+        if state.drag.press(id, (origin.x, origin.y, origin.z), pointer) {
+            state.clips.stop_standalone(crate::DRAG_SNAP_BACK_CLIP_FLAG | id);
+            world.entity_mut(entity).insert(PickingBehavior {
+                should_block_lower: false,
+                is_hoverable: false,
+            });
+        }
+    }
+
+    if let Some(pointer) = pointer
+        && let Some((id, position)) = state.drag.drag_to(pointer)
+        && let Some(entity) = state.sprite_sync.bevy_entity(id)
+        && let Some(mut transform) = world.get_mut::<Transform>(entity)
+    {
+        let layer = state.drag.active().map_or(0, |active| active.config.drag_layer);
+        transform.translation.x = position.0;
+        transform.translation.y = position.1;
+        transform.translation.z = crate::layered_z(0.0, Some(layer));
+    }
+
+    if released && let Some(origin_z) = state.drag.active().map(|active| active.origin.2) {
+        let drop_target = hovered.iter().find_map(|(entity, _)| {
+            if let Some(id) = state.hit_regions.region_id(*entity) {
+                return Some(DropTarget { id, hit_region: true });
+            }
+            synced_sprite_id(state, *entity).map(|id| DropTarget { id, hit_region: false })
+        });
+        if let Some(event) = state.drag.release(pointer, drop_target) {
+            put_down_sprite(world, state, event.id, Some(event.dropped_at), origin_z);
+            if let Some(clip) = event.snap_back_clip() {
+                state.clips.play_standalone(event.snap_back_handle(), clip);
+            }
+        }
+    }
+    state.system_timings.finish("drag", started);
+}
+
+/// Entities the mouse hovers this frame, topmost first.
+#[cfg(feature = "rendering")]
+fn hovered_entities(world: &World) -> Vec<(bevy_ecs::entity::Entity, f32)> {
+    let mut hovered: Vec<_> = world
+        .get_resource::<HoverMap>()
+        .and_then(|hover_map| hover_map.get(&PointerId::Mouse))
+        .map(|hits| hits.iter().map(|(entity, hit)| (*entity, hit.depth)).collect())
+        .unwrap_or_default();
+    hovered.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+    hovered
+}
+
+/// Ruby entity ID of the synced sprite drawn by `entity`.
+#[cfg(feature = "rendering")]
+fn synced_sprite_id(state: &RubyBridgeState, entity: bevy_ecs::entity::Entity) -> Option<u64> {
+    state
+        .sprite_sync
+        .synced_sprites()
+        .find(|(_, bevy_entity, _)| *bevy_entity == entity)
+        .map(|(id, _, _)| id)
+}
+
+/// Lets a released sprite take part in picking again and moves it back to
+/// its own layer, at `position` when given.
+#[cfg(feature = "rendering")]
+fn put_down_sprite(world: &mut World, state: &RubyBridgeState, id: u64, position: Option<(f32, f32)>, z: f32) {
+    let Some(entity) = state.sprite_sync.bevy_entity(id) else {
+        return;
+    };
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    entity_mut.remove::<PickingBehavior>();
+    if let Some(mut transform) = entity_mut.get_mut::<Transform>() {
+        if let Some((x, y)) = position {
+            transform.translation.x = x;
+            transform.translation.y = y;
+        }
+        transform.translation.z = z;
+    }
+}
+
+/// Spawns, moves, fades and removes floating texts through the text sync.
+#[cfg(feature = "rendering")]
+fn floating_text_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+    let delta_secs = world.resource::<Time>().delta_secs();
+
+    let mut guard = state_arc.lock().unwrap();
+    let state = &mut *guard;
+    let started = state.system_timings.start();
+    state.floating_texts.apply_operations();
+    let frames = state.floating_texts.advance(delta_secs);
+    for ruby_entity_id in state.floating_texts.drain_removed() {
+        state.text_sync.remove_text(world, ruby_entity_id);
+    }
+    for frame in &frames {
+        if let Some((text_data, transform_data)) = state.floating_texts.text_sync_data(frame) {
+            state
+                .text_sync
+                .sync_text(world, frame.ruby_entity_id, &text_data, &transform_data);
+        }
+    }
+    state.system_timings.finish("floating_text", started);
+}
+
+#[cfg(feature = "rendering")]
+fn behavior_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+    let delta_secs = world.resource::<Time>().delta_secs();
+
+    let mut guard = state_arc.lock().unwrap();
+    let state = &mut *guard;
+    let started = state.system_timings.start();
+    let sleep = state.sleep.config();
+    let (sprite_sync, text_sync, mesh_sync) = (&state.sprite_sync, &state.text_sync, &state.mesh_sync);
+    state.behaviors.apply_pending(world, delta_secs, &sleep, |ruby_entity_id| {
+        sprite_sync
+            .bevy_entity(ruby_entity_id)
+            .or_else(|| text_sync.bevy_entity(ruby_entity_id))
+            .or_else(|| mesh_sync.bevy_entity(ruby_entity_id))
+    });
+    state.system_timings.finish("behavior", started);
+}
+
+#[cfg(feature = "rendering")]
+fn sprite_snapshot_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+
+    let mut state = state_arc.lock().unwrap();
+    state.sprite_transforms = state.sprite_sync.capture_transforms(world);
+    let sprites = state
+        .sprite_sync
+        .synced_entities()
+        .into_iter()
+        .filter_map(|ruby_entity_id| Some((ruby_entity_id, state.sprite_sync.bevy_entity(ruby_entity_id)?)));
+    let nodes = capture_accessibility_nodes(world, sprites.chain(state.text_sync.synced_texts()));
+    state.accessibility_nodes = nodes;
+}
+
+#[cfg(feature = "rendering")]
+fn rewind_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+    let delta_secs = world.resource::<Time>().delta_secs();
+
+    let mut guard = state_arc.lock().unwrap();
+    let state = &mut *guard;
+    let started = state.system_timings.start();
+    let sprite_sync = &state.sprite_sync;
+    let resolve = |ruby_entity_id| sprite_sync.bevy_entity(ruby_entity_id);
+
+    let ops: Vec<_> = state.rewind_operations.drain(..).collect();
+    // Rewound and restored transforms replace what behaviors wrote.
+    if !ops.is_empty() || state.rewind.as_ref().is_some_and(Rewind::is_rewinding) {
+        state.behaviors.wake_all();
+    }
+    for op in ops {
+        match op {
+            RewindOperation::Enable {
+                window_secs,
+                snapshot_hz,
+            } => {
+                if let Some(mut rewind) = state.rewind.take() {
+                    let live: Vec<_> = state.sprite_transforms.iter().map(Into::into).collect();
+                    crate::rewind::apply_frame(world, &rewind.finish(&live), resolve);
+                }
+                state.rewind = Some(Rewind::new(window_secs, snapshot_hz));
+            }
+            RewindOperation::Disable => {
+                if let Some(mut rewind) = state.rewind.take() {
+                    let live: Vec<_> = state.sprite_transforms.iter().map(Into::into).collect();
+                    crate::rewind::apply_frame(world, &rewind.finish(&live), resolve);
+                }
+            }
+            RewindOperation::RewindTo { seconds_ago } => {
+                if let Some(rewind) = state.rewind.as_mut() {
+                    rewind.rewind_to(seconds_ago);
+                }
+            }
+            RewindOperation::Resume => {
+                if let Some(rewind) = state.rewind.as_mut() {
+                    rewind.resume();
+                }
+            }
+        }
+    }
+
+    if let Some(rewind) = state.rewind.as_mut() {
+        rewind.apply_pending(world, delta_secs, &state.sprite_transforms, resolve);
+    }
+    state.rewind_stats = state.rewind.as_ref().map(Rewind::stats);
+    state.system_timings.finish("rewind", started);
+}
+
+/// Offsets parallax sprites last, once every other system has moved them.
+#[cfg(feature = "rendering")]
+fn parallax_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+
+    let mut guard = state_arc.lock().unwrap();
+    let state = &mut *guard;
+    let started = state.system_timings.start();
+    let camera = (state.camera_position.0, state.camera_position.1);
+    state
+        .parallax
+        .apply(world, camera, state.sprite_sync.synced_sprites());
+    state.system_timings.finish("parallax", started);
+}
+
+#[cfg(feature = "rendering")]
+fn virtual_cursor_system(world: &mut World) {
+    let state_arc = {
+        let bridge = world.resource::<RubyBridge>();
+        bridge.state.clone()
+    };
+    let delta_secs = world.resource::<Time>().delta_secs();
+
+    let mut guard = state_arc.lock().unwrap();
+    let state = &mut *guard;
+
+    let ops: Vec<_> = state.virtual_cursor_operations.drain(..).collect();
+    for op in ops {
+        if let Some(mut cursor) = state.virtual_cursor.take() {
+            cursor.despawn(world);
+        }
+        if let VirtualCursorOperation::Enable(config) = op {
+            state.virtual_cursor = Some(VirtualCursor::new(*config));
+        }
+    }
+
+    let Some(cursor) = state.virtual_cursor.as_mut() else {
+        state.virtual_cursor_position = None;
+        return;
+    };
+
+    let Ok((window_entity, bounds)) = world
+        .query_filtered::<(bevy_ecs::entity::Entity, &Window), bevy_ecs::query::With<PrimaryWindow>>()
+        .get_single(world)
+        .map(|(entity, window)| (entity, (window.width(), window.height())))
+    else {
+        return;
+    };
+    let Some(window_ref) = WindowRef::Primary.normalize(Some(window_entity)) else {
+        return;
+    };
+
+    let (stick, button_down, button_up) = {
+        let mut gamepads: Vec<_> = world
+            .query::<(bevy_ecs::entity::Entity, &Gamepad)>()
+            .iter(world)
+            .collect();
+        gamepads.sort_unstable_by_key(|(entity, _)| *entity);
+        let button = cursor.config.button.as_str();
+        gamepads
+            .get(cursor.config.gamepad_slot)
+            .map(|(_, gamepad)| {
+                (
+                    (
+                        gamepad.get(GamepadAxis::LeftStickX).unwrap_or(0.0),
+                        gamepad.get(GamepadAxis::LeftStickY).unwrap_or(0.0),
+                    ),
+                    gamepad
+                        .get_just_pressed()
+                        .any(|pressed| gamepad_button_to_string(*pressed) == button),
+                    gamepad
+                        .get_just_released()
+                        .any(|released| gamepad_button_to_string(*released) == button),
+                )
+            })
+            .unwrap_or_default()
+    };
+
+    let first_frame = !cursor.is_spawned();
+    cursor.spawn(world);
+    let (dx, dy) = cursor.integrate(stick, delta_secs, bounds);
+    let Some((x, y)) = cursor.position() else {
+        return;
+    };
+
+    let pointer_id = cursor.picking_pointer_id();
+    let location = Location {
+        target: NormalizedRenderTarget::Window(window_ref),
+        position: bevy_math::Vec2::new(x, y),
+    };
+    if first_frame || dx != 0.0 || dy != 0.0 {
+        world.send_event(PointerInput::new(
+            pointer_id,
+            location.clone(),
+            PointerAction::Moved {
+                delta: bevy_math::Vec2::new(dx, dy),
+            },
+        ));
+    }
+    for (pressed, direction) in [(button_down, PressDirection::Down), (button_up, PressDirection::Up)] {
+        if pressed {
+            world.send_event(PointerInput::new(
+                pointer_id,
+                location.clone(),
+                PointerAction::Pressed {
+                    direction,
+                    button: PointerButton::Primary,
+                },
+            ));
+        }
+    }
+
+    let camera_position = (state.camera_position.0, state.camera_position.1);
+    if let Some(transform_data) = cursor.sprite_transform(bounds, camera_position, state.camera_scale) {
+        cursor.sync_sprite(world, &transform_data);
+    }
+    state.virtual_cursor_position = Some((x - bounds.0 / 2.0, bounds.1 / 2.0 - y));
 }
 
+/// Writes the Ruby-set camera transform, plus the shake offset while a shake runs.
+///
+/// Runs after every system that moves synced entities, so a camera change
+/// queued in the callback lands in the same frame as the sprite changes
+/// queued with it. The shake never touches `camera_position`; the frame after
+/// it ends the camera is put back at the set position.
 #[cfg(feature = "rendering")]
 fn camera_sync_system(
     bridge: Res<RubyBridge>,
-    mut query: bevy_ecs::system::Query<&mut Transform, bevy_ecs::query::With<Camera2d>>,
+    time: Res<Time>,
+    mut was_shaking: Local<bool>,
+    mut query: bevy_ecs::system::Query<(&mut Transform, Option<&mut CameraShake>), bevy_ecs::query::With<Camera2d>>,
+    targets: bevy_ecs::system::Query<&Transform, bevy_ecs::query::Without<Camera2d>>,
 ) {
     let mut state = bridge.state.lock().unwrap();
-    if !state.camera_dirty {
-        return;
+    let started = state.system_timings.start();
+    let shake_request = state.pending_camera_shake.take();
+    let delta_secs = time.delta_secs();
+
+    if let Some(follow) = state.camera_follow {
+        let target = state
+            .sprite_sync
+            .bevy_entity(follow.ruby_entity_id)
+            .or_else(|| state.mesh_sync.bevy_entity(follow.ruby_entity_id))
+            .and_then(|entity| targets.get(entity).ok());
+        if let Some(target) = target {
+            state.camera_position.0 = target.translation.x + follow.offset.0;
+            state.camera_position.1 = target.translation.y + follow.offset.1;
+            state.camera_dirty = true;
+        }
     }
 
-    for mut transform in query.iter_mut() {
-        transform.translation.x = state.camera_position.0;
-        transform.translation.y = state.camera_position.1;
+    let mut shaking = false;
+    for (mut transform, shake) in query.iter_mut() {
+        let mut offset = bevy_math::Vec2::ZERO;
+        if let Some(mut shake) = shake {
+            if let Some((intensity, duration_secs)) = shake_request {
+                shake.trigger(intensity, duration_secs);
+            }
+            if shake.is_active() {
+                let sample = shake.update(delta_secs);
+                // The last step can overshoot the duration; it ends at rest.
+                if shake.is_active() && sample.is_finite() {
+                    offset = sample;
+                }
+                shaking = true;
+            }
+        }
+
+        if !state.camera_dirty && !shaking && !*was_shaking {
+            continue;
+        }
+        transform.translation.x = state.camera_position.0 + offset.x;
+        transform.translation.y = state.camera_position.1 + offset.y;
         transform.translation.z = state.camera_position.2;
         transform.scale.x = state.camera_scale;
         transform.scale.y = state.camera_scale;
+        transform.rotation = bevy_math::Quat::from_rotation_z(state.camera_rotation);
+        state.camera_applied_frame = Some(state.frame_count);
     }
 
+    *was_shaking = shaking;
     state.camera_dirty = false;
-}
-
-#[cfg(feature = "rendering")]
-fn keycode_to_string(key: KeyCode) -> Option<String> {
-    match key {
-        KeyCode::KeyA => Some("A".to_string()),
-        KeyCode::KeyB => Some("B".to_string()),
-        KeyCode::KeyC => Some("C".to_string()),
-        KeyCode::KeyD => Some("D".to_string()),
-        KeyCode::KeyE => Some("E".to_string()),
-        KeyCode::KeyF => Some("F".to_string()),
-        KeyCode::KeyG => Some("G".to_string()),
-        KeyCode::KeyH => Some("H".to_string()),
-        KeyCode::KeyI => Some("I".to_string()),
-        KeyCode::KeyJ => Some("J".to_string()),
-        KeyCode::KeyK => Some("K".to_string()),
-        KeyCode::KeyL => Some("L".to_string()),
-        KeyCode::KeyM => Some("M".to_string()),
-        KeyCode::KeyN => Some("N".to_string()),
-        KeyCode::KeyO => Some("O".to_string()),
-        KeyCode::KeyP => Some("P".to_string()),
-        KeyCode::KeyQ => Some("Q".to_string()),
-        KeyCode::KeyR => Some("R".to_string()),
-        KeyCode::KeyS => Some("S".to_string()),
-        KeyCode::KeyT => Some("T".to_string()),
-        KeyCode::KeyU => Some("U".to_string()),
-        KeyCode::KeyV => Some("V".to_string()),
-        KeyCode::KeyW => Some("W".to_string()),
-        KeyCode::KeyX => Some("X".to_string()),
-        KeyCode::KeyY => Some("Y".to_string()),
-        KeyCode::KeyZ => Some("Z".to_string()),
-        KeyCode::Digit0 => Some("0".to_string()),
-        KeyCode::Digit1 => Some("1".to_string()),
-        KeyCode::Digit2 => Some("2".to_string()),
-        KeyCode::Digit3 => Some("3".to_string()),
-        KeyCode::Digit4 => Some("4".to_string()),
-        KeyCode::Digit5 => Some("5".to_string()),
-        KeyCode::Digit6 => Some("6".to_string()),
-        KeyCode::Digit7 => Some("7".to_string()),
-        KeyCode::Digit8 => Some("8".to_string()),
-        KeyCode::Digit9 => Some("9".to_string()),
-        KeyCode::Space => Some("SPACE".to_string()),
-        KeyCode::Enter => Some("ENTER".to_string()),
-        KeyCode::Escape => Some("ESCAPE".to_string()),
-        KeyCode::ArrowUp => Some("UP".to_string()),
-        KeyCode::ArrowDown => Some("DOWN".to_string()),
-        KeyCode::ArrowLeft => Some("LEFT".to_string()),
-        KeyCode::ArrowRight => Some("RIGHT".to_string()),
-        KeyCode::ShiftLeft | KeyCode::ShiftRight => Some("SHIFT".to_string()),
-        KeyCode::ControlLeft | KeyCode::ControlRight => Some("CONTROL".to_string()),
-        KeyCode::AltLeft | KeyCode::AltRight => Some("ALT".to_string()),
-        KeyCode::Tab => Some("TAB".to_string()),
-        KeyCode::Backspace => Some("BACKSPACE".to_string()),
-        _ => None,
-    }
+    state.system_timings.finish("camera_sync", started);
 }
 
 #[cfg(feature = "rendering")]
@@ -553,6 +2433,70 @@ fn gamepad_axis_to_string(axis: GamepadAxis) -> String {
     }
 }
 
+/// Reads the battery level of a gamepad, if the input backend reports one.
+///
+/// Bevy 0.15's `Gamepad` carries no power information, so this is `None` for
+/// every device until a backend exposes it.
+#[cfg(feature = "rendering")]
+fn gamepad_battery_level(_gamepad: &Gamepad) -> Option<f32> {
+    None
+}
+
+/// Reads the window size and the insets the OS reserves for title bars or notches.
+///
+/// winit in Bevy 0.15 exposes no safe-area query, so the insets are zero and
+/// the safe area falls back to the full window.
+#[cfg(feature = "rendering")]
+fn window_metrics(window: &Window) -> WindowMetrics {
+    WindowMetrics {
+        width: window.width(),
+        height: window.height(),
+        ..Default::default()
+    }
+}
+
+/// Picking event with the fields every kind shares; `hit` is `None` for
+/// drags, and callers fill in `delta` and `distance`.
+#[cfg(feature = "rendering")]
+fn picking_event(
+    kind: &str,
+    hit_regions: &HitRegions,
+    target: bevy_ecs::entity::Entity,
+    pointer_id: PointerId,
+    pointer_location: &Location,
+    hit: Option<&HitData>,
+    button: Option<PointerButton>,
+) -> PickingEventData {
+    let (target_id, hit_region) = picking_target(hit_regions, target);
+    PickingEventData {
+        kind: kind.to_string(),
+        target_id,
+        hit_region,
+        pointer_id: pointer_id_to_string(pointer_id),
+        pointer_position: (pointer_location.position.x, pointer_location.position.y),
+        button: button.map(|button| pointer_button_to_string(button).to_string()),
+        camera_id: hit.map(|hit| hit.camera.to_bits()),
+        depth: hit.map(|hit| hit.depth),
+        hit_position: hit
+            .and_then(|hit| hit.position)
+            .map(|position| (position.x, position.y, position.z)),
+        hit_normal: hit
+            .and_then(|hit| hit.normal)
+            .map(|normal| (normal.x, normal.y, normal.z)),
+        delta: None,
+        distance: None,
+    }
+}
+
+/// Picking event target as `(target_id, hit_region)`.
+#[cfg(feature = "rendering")]
+fn picking_target(hit_regions: &HitRegions, target: bevy_ecs::entity::Entity) -> (u64, bool) {
+    match hit_regions.region_id(target) {
+        Some(id) => (id, true),
+        None => (target.to_bits(), false),
+    }
+}
+
 #[cfg(feature = "rendering")]
 fn pointer_id_to_string(pointer_id: PointerId) -> String {
     match pointer_id {
@@ -562,6 +2506,31 @@ fn pointer_id_to_string(pointer_id: PointerId) -> String {
     }
 }
 
+#[cfg(feature = "rendering")]
+fn touch_phase(phase: TouchPhase) -> crate::TouchPhase {
+    match phase {
+        TouchPhase::Started => crate::TouchPhase::Started,
+        TouchPhase::Moved => crate::TouchPhase::Moved,
+        TouchPhase::Ended => crate::TouchPhase::Ended,
+        TouchPhase::Canceled => crate::TouchPhase::Canceled,
+    }
+}
+
+/// Touch pressure in `0.0..=1.0`, whichever way the device reports it.
+#[cfg(feature = "rendering")]
+fn normalized_touch_force(force: ForceTouch) -> f32 {
+    let force = match force {
+        ForceTouch::Calibrated {
+            force,
+            max_possible_force,
+            ..
+        } if max_possible_force > 0.0 => force / max_possible_force,
+        ForceTouch::Calibrated { .. } => 0.0,
+        ForceTouch::Normalized(force) => force,
+    };
+    force.clamp(0.0, 1.0) as f32
+}
+
 #[cfg(feature = "rendering")]
 fn pointer_button_to_string(button: PointerButton) -> &'static str {
     match button {
@@ -571,6 +2540,16 @@ fn pointer_button_to_string(button: PointerButton) -> &'static str {
     }
 }
 
+#[cfg(feature = "rendering")]
+fn window_resize_constraints(config: &WindowConfig) -> WindowResizeConstraints {
+    let defaults = WindowResizeConstraints::default();
+    WindowResizeConstraints {
+        min_width: config.min_width.unwrap_or(defaults.min_width),
+        min_height: config.min_height.unwrap_or(defaults.min_height),
+        ..defaults
+    }
+}
+
 #[cfg(feature = "rendering")]
 pub struct RenderApp {
     app: App,
@@ -582,6 +2561,10 @@ pub struct RenderApp {
 impl RenderApp {
     pub fn new(config: WindowConfig) -> Self {
         let mut app = App::new();
+        let resize_constraints = window_resize_constraints(&config);
+        let (text_sync, mesh_sync) = (config.text_sync, config.mesh_sync);
+        let window_event_filter = config.window_events;
+        let window_mode = config.mode;
 
         app.add_plugins((
             LogPlugin::default(),
@@ -594,20 +2577,50 @@ impl RenderApp {
             InputPlugin::default(),
         ));
 
-        app.add_plugins((
-            WindowPlugin {
-                primary_window: Some(Window {
-                    title: config.title,
-                    resolution: (config.width, config.height).into(),
-                    resizable: config.resizable,
+        if config.headless {
+            // No window: keep the window types but don't exit for lack of one.
+            app.add_plugins((
+                WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
                     ..Default::default()
-                }),
-                ..Default::default()
-            },
-            AccessibilityPlugin,
-            AssetPlugin::default(),
-            WinitPlugin::<WakeUp>::default(),
-        ));
+                },
+                AccessibilityPlugin,
+                AssetPlugin::default(),
+            ));
+            let frames = config.frames;
+            app.insert_resource(HeadlessTarget {
+                width: config.width.max(1.0) as u32,
+                height: config.height.max(1.0) as u32,
+            });
+            app.set_runner(move |app| headless_runner(app, frames));
+        } else {
+            app.add_plugins((
+                WindowPlugin {
+                    primary_window: Some(Window {
+                        title: config.title,
+                        resolution: (config.width, config.height).into(),
+                        resizable: config.resizable,
+                        decorations: config.decorations,
+                        transparent: config.transparent,
+                        // macOS only blends a transparent surface when told to.
+                        composite_alpha_mode: if config.transparent && cfg!(target_os = "macos") {
+                            CompositeAlphaMode::PostMultiplied
+                        } else {
+                            CompositeAlphaMode::Auto
+                        },
+                        resize_constraints,
+                        mode: config.mode.to_bevy(),
+                        present_mode: config.present_mode.to_bevy(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                AccessibilityPlugin,
+                AssetPlugin::default(),
+                WinitPlugin::<WakeUp>::default(),
+            ));
+        }
 
         app.add_plugins((
             RenderPlugin::default(),
@@ -619,7 +2632,49 @@ impl RenderApp {
             bevy_prototype_lyon::prelude::ShapePlugin,
         ));
 
-        let bridge_state = Arc::new(Mutex::new(RubyBridgeState::default()));
+        // A transparent window with Bevy's opaque grey default would hide the desktop.
+        match config.clear_color {
+            Some(rgba) => {
+                app.insert_resource(clear_color(rgba));
+            }
+            None if config.transparent && !config.headless => {
+                app.insert_resource(ClearColor(Color::NONE));
+            }
+            None => {}
+        }
+
+        let mut warnings = WarningLog::default();
+
+        // A broken icon should not stop the game from starting.
+        if let Some(path) = config.icon_path.as_deref().filter(|_| !config.headless) {
+            match load_window_icon(path) {
+                Ok(icon) => {
+                    app.insert_resource(PendingWindowIcon(Some(icon)));
+                    app.add_systems(Update, window_icon_system);
+                }
+                Err(error) => warnings.push(
+                    "window",
+                    format!("could not load window icon {}: {}", path, error),
+                    "window_icon",
+                ),
+            }
+        }
+
+        let mut pixel_snap = PixelSnap::new();
+        pixel_snap.set_global(config.pixel_perfect);
+
+        let mut sprite_sync = SpriteSync::new();
+        sprite_sync.set_texture_placeholder(config.texture_placeholder);
+
+        let bridge_state = Arc::new(Mutex::new(RubyBridgeState {
+            window_event_filter,
+            window_mode,
+            max_fps: config.max_fps,
+            pixel_snap,
+            sprite_sync,
+            warnings,
+            ..Default::default()
+        }));
         let callback: UpdateCallback = Arc::new(Mutex::new(None));
 
         let bridge = RubyBridge {
@@ -628,13 +2683,79 @@ impl RenderApp {
         };
 
         app.insert_resource(bridge);
+        app.insert_resource(CameraMsaa(config.msaa));
         app.add_systems(Startup, spawn_camera_2d_system);
         app.add_systems(Startup, setup_default_sprite_texture_system);
+        if config.headless {
+            app.add_systems(Startup, headless_target_system.after(spawn_camera_2d_system));
+        }
+        app.add_systems(PreUpdate, hit_region_picking_system.in_set(PickSet::Backend));
+        app.add_systems(Update, window_event_system.before(ruby_bridge_system));
+        app.add_systems(Update, warnings_clock_system.before(ruby_bridge_system));
         app.add_systems(Update, ruby_bridge_system);
+        app.add_systems(Update, accessibility_system.after(ruby_bridge_system));
+        app.add_systems(Update, window_mode_system.after(ruby_bridge_system));
+        app.add_systems(Update, window_sync_system.after(ruby_bridge_system));
+        app.add_systems(Update, cursor_system.after(ruby_bridge_system));
+        app.add_systems(Update, clear_color_system.after(ruby_bridge_system));
+        app.add_systems(Update, audio_system.after(ruby_bridge_system));
+        app.add_systems(
+            Update,
+            lod_system
+                .after(ruby_bridge_system)
+                .before(sprite_sync_system),
+        );
+        app.add_systems(
+            Update,
+            parallax_restore_system
+                .after(ruby_bridge_system)
+                .before(sprite_sync_system),
+        );
         app.add_systems(Update, sprite_sync_system);
-        app.add_systems(Update, text_sync_system);
-        app.add_systems(Update, mesh_sync_system);
-        app.add_systems(Update, camera_sync_system);
+        app.add_systems(Update, jelly_system.after(sprite_sync_system));
+        if text_sync {
+            app.add_systems(Update, text_sync_system.before(clip_system));
+            app.add_systems(
+                Update,
+                floating_text_system
+                    .after(text_sync_system)
+                    .before(clip_system),
+            );
+        }
+        if mesh_sync {
+            app.add_systems(Update, mesh_sync_system.before(clip_system));
+        }
+        app.add_systems(Update, clip_system.after(sprite_sync_system));
+        app.add_systems(Update, drag_system.after(clip_system).before(behavior_system));
+        app.add_systems(Update, behavior_system.after(clip_system));
+        app.add_systems(Update, sprite_snapshot_system.after(behavior_system));
+        app.add_systems(Update, rewind_system.after(sprite_snapshot_system));
+        app.add_systems(
+            Update,
+            camera_sync_system
+                .after(ruby_bridge_system)
+                .after(rewind_system)
+                .after(jelly_system),
+        );
+        app.add_systems(
+            Update,
+            parallax_system
+                .after(rewind_system)
+                .after(jelly_system)
+                .after(camera_sync_system),
+        );
+        app.add_systems(
+            Update,
+            virtual_cursor_system
+                .after(ruby_bridge_system)
+                .after(camera_sync_system),
+        );
+        app.add_systems(PreUpdate, pixel_snap_restore_system);
+        app.add_systems(
+            PostUpdate,
+            pixel_snap_system.before(TransformSystem::TransformPropagate),
+        );
+        app.add_systems(Last, frame_limiter_system);
 
         Self {
             app,
@@ -655,6 +2776,39 @@ impl RenderApp {
         self.app.run();
     }
 
+    /// Sets the background color directly; used before `run` starts the loop.
+    pub fn set_clear_color(&mut self, rgba: [f32; 4]) {
+        self.app.insert_resource(clear_color(rgba));
+    }
+
+    /// Sets the frame rate cap directly; used before `run` starts the loop.
+    pub fn set_max_fps(&mut self, max_fps: Option<f32>) {
+        self.bridge.lock().unwrap().max_fps = max_fps;
+    }
+
+    /// Sets the primary window title directly; used before `run` starts the loop.
+    pub fn set_window_title(&mut self, title: String) {
+        if let Some(mut window) = self.primary_window_mut() {
+            window.title = title;
+        }
+    }
+
+    /// Sets the primary window's logical size directly; used before `run` starts the loop.
+    pub fn set_window_size(&mut self, width: f32, height: f32) {
+        if let Some(mut window) = self.primary_window_mut() {
+            window.resolution.set(width, height);
+        }
+    }
+
+    fn primary_window_mut(&mut self) -> Option<bevy_ecs::world::Mut<'_, Window>> {
+        let world = self.app.world_mut();
+        let entity = world
+            .query_filtered::<bevy_ecs::entity::Entity, bevy_ecs::query::With<PrimaryWindow>>()
+            .get_single(world)
+            .ok()?;
+        world.get_mut::<Window>(entity)
+    }
+
     pub fn bridge_state(&self) -> Arc<Mutex<RubyBridgeState>> {
         self.bridge.clone()
     }
@@ -663,6 +2817,11 @@ impl RenderApp {
         self.bridge.lock().map(|s| s.should_exit).unwrap_or(false)
     }
 
+    /// Why the loop ended or is ending; `None` while it runs normally.
+    pub fn exit_reason(&self) -> Option<ExitReason> {
+        self.bridge.lock().ok().and_then(|s| s.exit_reason)
+    }
+
     pub fn is_initialized(&self) -> bool {
         true
     }
@@ -683,6 +2842,10 @@ impl RenderApp {
         false
     }
 
+    pub fn exit_reason(&self) -> Option<ExitReason> {
+        None
+    }
+
     pub fn is_initialized(&self) -> bool {
         false
     }
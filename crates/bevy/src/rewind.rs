@@ -0,0 +1,469 @@
+//! Rewind module for replaying recent sprite transforms.
+//!
+//! While enabled, the applied transforms of synced sprites are captured into a
+//! ring buffer at a fixed rate. Rewinding interpolates between the two nearest
+//! snapshots and writes the historical transforms over the live ones until
+//! playback resumes, which hands control back to the sync layer.
+//!
+//! Only entities that still exist are rewound: sprites spawned after the
+//! rewound moment are hidden, and sprites removed since then are not
+//! resurrected.
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::f32::consts::{PI, TAU};
+
+use crate::sprite_renderer::SpriteTransformSample;
+
+#[cfg(feature = "rendering")]
+use bevy_ecs::entity::Entity;
+#[cfg(feature = "rendering")]
+use bevy_ecs::world::World;
+#[cfg(feature = "rendering")]
+use bevy_render::view::Visibility;
+#[cfg(feature = "rendering")]
+use bevy_transform::components::Transform;
+
+/// Upper bound on the sprites kept per snapshot; sprites with higher IDs are dropped.
+pub const REWIND_MAX_SAMPLES_PER_SNAPSHOT: usize = 16_384;
+
+/// Snapshot rates outside this range are clamped.
+const SNAPSHOT_HZ_RANGE: (f32, f32) = (1.0, 120.0);
+
+/// Longest rewind window, in seconds.
+const MAX_WINDOW_SECS: f32 = 600.0;
+
+/// Tolerance used to tell whether a sync layer overwrote a rewound transform.
+const SAMPLE_EPSILON: f32 = 1e-4;
+
+/// 2D transform of one sprite, packed like `transforms_snapshot`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RewindSample {
+    pub ruby_entity_id: u64,
+    pub x: f32,
+    pub y: f32,
+    pub rotation: f32,
+}
+
+impl RewindSample {
+    fn matches(&self, other: &RewindSample) -> bool {
+        (self.x - other.x).abs() <= SAMPLE_EPSILON
+            && (self.y - other.y).abs() <= SAMPLE_EPSILON
+            && shortest_angle(self.rotation, other.rotation).abs() <= SAMPLE_EPSILON
+    }
+
+    /// Interpolates position linearly and rotation along the shortest arc.
+    pub fn lerp(&self, other: &RewindSample, t: f32) -> RewindSample {
+        RewindSample {
+            ruby_entity_id: self.ruby_entity_id,
+            x: self.x + (other.x - self.x) * t,
+            y: self.y + (other.y - self.y) * t,
+            rotation: self.rotation + shortest_angle(self.rotation, other.rotation) * t,
+        }
+    }
+}
+
+impl From<&SpriteTransformSample> for RewindSample {
+    fn from(sample: &SpriteTransformSample) -> Self {
+        Self {
+            ruby_entity_id: sample.ruby_entity_id,
+            x: sample.x,
+            y: sample.y,
+            rotation: sample.rotation,
+        }
+    }
+}
+
+/// Signed difference `to - from`, wrapped to `-PI..PI`.
+fn shortest_angle(from: f32, to: f32) -> f32 {
+    (to - from + PI).rem_euclid(TAU) - PI
+}
+
+/// Sprite transforms captured at one moment, ordered by Ruby entity ID.
+#[derive(Debug, Clone)]
+pub struct RewindSnapshot {
+    /// Recording clock at capture time, in seconds.
+    pub time: f64,
+    pub samples: Vec<RewindSample>,
+}
+
+impl RewindSnapshot {
+    fn get(&self, ruby_entity_id: u64) -> Option<&RewindSample> {
+        self.samples
+            .binary_search_by_key(&ruby_entity_id, |sample| sample.ruby_entity_id)
+            .ok()
+            .map(|index| &self.samples[index])
+    }
+
+    fn memory_bytes(&self) -> usize {
+        std::mem::size_of::<Self>() + self.samples.capacity() * std::mem::size_of::<RewindSample>()
+    }
+}
+
+/// Fixed-capacity ring buffer of snapshots covering the rewind window.
+#[derive(Debug, Clone)]
+pub struct RewindBuffer {
+    window_secs: f32,
+    snapshot_hz: f32,
+    capacity: usize,
+    snapshots: VecDeque<RewindSnapshot>,
+    clock: f64,
+    until_next: f32,
+    /// Snapshots that hit `REWIND_MAX_SAMPLES_PER_SNAPSHOT` and dropped sprites.
+    pub truncated_snapshots: u64,
+}
+
+impl RewindBuffer {
+    /// Creates a buffer holding `window_secs` of history at `snapshot_hz`.
+    pub fn new(window_secs: f32, snapshot_hz: f32) -> Self {
+        let window_secs = window_secs.clamp(0.0, MAX_WINDOW_SECS);
+        let snapshot_hz = snapshot_hz.clamp(SNAPSHOT_HZ_RANGE.0, SNAPSHOT_HZ_RANGE.1);
+        let capacity = (window_secs * snapshot_hz).ceil() as usize + 1;
+        Self {
+            window_secs,
+            snapshot_hz,
+            capacity,
+            snapshots: VecDeque::with_capacity(capacity),
+            clock: 0.0,
+            until_next: 0.0,
+            truncated_snapshots: 0,
+        }
+    }
+
+    pub fn window_secs(&self) -> f32 {
+        self.window_secs
+    }
+
+    pub fn snapshot_hz(&self) -> f32 {
+        self.snapshot_hz
+    }
+
+    /// Maximum number of snapshots kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Recording clock, in seconds since the buffer was created.
+    pub fn clock(&self) -> f64 {
+        self.clock
+    }
+
+    pub fn snapshots(&self) -> impl Iterator<Item = &RewindSnapshot> {
+        self.snapshots.iter()
+    }
+
+    /// Seconds of history between the oldest snapshot and now.
+    pub fn covered_secs(&self) -> f32 {
+        self.snapshots
+            .front()
+            .map_or(0.0, |oldest| (self.clock - oldest.time) as f32)
+    }
+
+    /// Bytes currently held by snapshots.
+    pub fn memory_bytes(&self) -> usize {
+        self.snapshots.iter().map(RewindSnapshot::memory_bytes).sum()
+    }
+
+    /// Upper bound on `memory_bytes` for this buffer's capacity.
+    pub fn max_memory_bytes(&self) -> usize {
+        self.capacity
+            * (std::mem::size_of::<RewindSnapshot>()
+                + REWIND_MAX_SAMPLES_PER_SNAPSHOT * std::mem::size_of::<RewindSample>())
+    }
+
+    /// Advances the clock and captures `samples` when a snapshot is due.
+    ///
+    /// The first call always captures. Returns true if a snapshot was taken.
+    pub fn record(&mut self, delta_secs: f32, samples: &[RewindSample]) -> bool {
+        self.clock += delta_secs.max(0.0) as f64;
+        self.until_next -= delta_secs.max(0.0);
+        if self.until_next > 0.0 {
+            return false;
+        }
+        let interval = 1.0 / self.snapshot_hz;
+        self.until_next = (self.until_next + interval).max(0.0);
+        if self.until_next == 0.0 {
+            self.until_next = interval;
+        }
+
+        let mut samples = samples.to_vec();
+        samples.sort_unstable_by_key(|sample| sample.ruby_entity_id);
+        if samples.len() > REWIND_MAX_SAMPLES_PER_SNAPSHOT {
+            samples.truncate(REWIND_MAX_SAMPLES_PER_SNAPSHOT);
+            self.truncated_snapshots += 1;
+        }
+        samples.shrink_to_fit();
+
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(RewindSnapshot {
+            time: self.clock,
+            samples,
+        });
+        true
+    }
+
+    /// Returns sprite transforms as they were `seconds_ago`, ordered by ID.
+    ///
+    /// The moment is clamped to the recorded range. Sprites present in both
+    /// surrounding snapshots are interpolated; otherwise the nearer snapshot
+    /// decides whether a sprite existed and where it was.
+    pub fn sample(&self, seconds_ago: f32) -> Option<Vec<RewindSample>> {
+        let (oldest, newest) = (self.snapshots.front()?, self.snapshots.back()?);
+        let target = (self.clock - seconds_ago.max(0.0) as f64).clamp(oldest.time, newest.time);
+
+        let next_index = self.snapshots.partition_point(|snapshot| snapshot.time < target);
+        let after = &self.snapshots[next_index];
+        if next_index == 0 || after.time <= target {
+            return Some(after.samples.clone());
+        }
+        let before = &self.snapshots[next_index - 1];
+
+        let t = ((target - before.time) / (after.time - before.time)) as f32;
+        let (nearer, other) = if t < 0.5 { (before, after) } else { (after, before) };
+        Some(
+            nearer
+                .samples
+                .iter()
+                .map(|sample| match other.get(sample.ruby_entity_id) {
+                    Some(other_sample) if t < 0.5 => sample.lerp(other_sample, t),
+                    Some(other_sample) => other_sample.lerp(sample, t),
+                    None => *sample,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Changes a rewind frame makes to the synced sprites.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RewindFrame {
+    /// Transforms to write.
+    pub writes: Vec<RewindSample>,
+    /// Sprites that did not exist at the rewound moment.
+    pub hide: Vec<u64>,
+    /// Previously hidden sprites to show again.
+    pub show: Vec<u64>,
+}
+
+impl RewindFrame {
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty() && self.hide.is_empty() && self.show.is_empty()
+    }
+}
+
+/// Buffer statistics reported to Ruby.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RewindStats {
+    pub snapshots: usize,
+    pub capacity: usize,
+    pub memory_bytes: usize,
+    pub max_memory_bytes: usize,
+    pub covered_secs: f32,
+    pub truncated_snapshots: u64,
+    pub rewinding: bool,
+}
+
+/// Pending rewind operation.
+#[derive(Debug, Clone, Copy)]
+pub enum RewindOperation {
+    Enable { window_secs: f32, snapshot_hz: f32 },
+    Disable,
+    RewindTo { seconds_ago: f32 },
+    Resume,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AppliedSample {
+    authored: RewindSample,
+    written: RewindSample,
+}
+
+/// Records sprite transforms and plays them back on request.
+#[derive(Debug, Clone)]
+pub struct Rewind {
+    buffer: RewindBuffer,
+    seconds_ago: Option<f32>,
+    resuming: bool,
+    applied: HashMap<u64, AppliedSample>,
+    hidden: BTreeSet<u64>,
+}
+
+impl Rewind {
+    pub fn new(window_secs: f32, snapshot_hz: f32) -> Self {
+        Self {
+            buffer: RewindBuffer::new(window_secs, snapshot_hz),
+            seconds_ago: None,
+            resuming: false,
+            applied: HashMap::new(),
+            hidden: BTreeSet::new(),
+        }
+    }
+
+    pub fn buffer(&self) -> &RewindBuffer {
+        &self.buffer
+    }
+
+    /// Starts or moves playback; recording pauses until `resume`.
+    pub fn rewind_to(&mut self, seconds_ago: f32) {
+        self.seconds_ago = Some(seconds_ago.max(0.0));
+        self.resuming = false;
+    }
+
+    /// Ends playback; the next update restores live transforms.
+    pub fn resume(&mut self) {
+        if self.seconds_ago.take().is_some() {
+            self.resuming = true;
+        }
+    }
+
+    pub fn is_rewinding(&self) -> bool {
+        self.seconds_ago.is_some()
+    }
+
+    /// Seconds into the past being played back.
+    pub fn seconds_ago(&self) -> Option<f32> {
+        self.seconds_ago
+    }
+
+    /// Sprites currently hidden by playback.
+    pub fn hidden(&self) -> impl Iterator<Item = &u64> {
+        self.hidden.iter()
+    }
+
+    pub fn stats(&self) -> RewindStats {
+        RewindStats {
+            snapshots: self.buffer.len(),
+            capacity: self.buffer.capacity(),
+            memory_bytes: self.buffer.memory_bytes(),
+            max_memory_bytes: self.buffer.max_memory_bytes(),
+            covered_secs: self.buffer.covered_secs(),
+            truncated_snapshots: self.buffer.truncated_snapshots,
+            rewinding: self.is_rewinding(),
+        }
+    }
+
+    /// Runs one frame against the live sprite transforms.
+    ///
+    /// Records while live, writes historical transforms while rewinding, and
+    /// restores the live transforms on the first frame after `resume`.
+    pub fn update(&mut self, delta_secs: f32, live: &[RewindSample]) -> RewindFrame {
+        if let Some(seconds_ago) = self.seconds_ago {
+            return self.play(seconds_ago, live);
+        }
+        if self.resuming {
+            self.resuming = false;
+            return self.finish(live);
+        }
+        self.buffer.record(delta_secs, live);
+        RewindFrame::default()
+    }
+
+    /// Ends playback immediately, returning the writes that restore live control.
+    ///
+    /// A transform a sync layer replaced during playback is left as synced.
+    pub fn finish(&mut self, live: &[RewindSample]) -> RewindFrame {
+        self.seconds_ago = None;
+        self.resuming = false;
+        let writes = live
+            .iter()
+            .filter_map(|sample| {
+                let applied = self.applied.get(&sample.ruby_entity_id)?;
+                applied.written.matches(sample).then_some(applied.authored)
+            })
+            .collect();
+        self.applied.clear();
+        RewindFrame {
+            writes,
+            hide: Vec::new(),
+            show: std::mem::take(&mut self.hidden).into_iter().collect(),
+        }
+    }
+
+    fn play(&mut self, seconds_ago: f32, live: &[RewindSample]) -> RewindFrame {
+        let mut frame = RewindFrame::default();
+        let Some(historical) = self.buffer.sample(seconds_ago) else {
+            return frame;
+        };
+
+        let live_ids: BTreeSet<u64> = live.iter().map(|sample| sample.ruby_entity_id).collect();
+        self.applied.retain(|id, _| live_ids.contains(id));
+        self.hidden.retain(|id| live_ids.contains(id));
+
+        for sample in live {
+            let id = sample.ruby_entity_id;
+            let Ok(index) = historical.binary_search_by_key(&id, |past| past.ruby_entity_id) else {
+                if self.hidden.insert(id) {
+                    frame.hide.push(id);
+                }
+                continue;
+            };
+            if self.hidden.remove(&id) {
+                frame.show.push(id);
+            }
+
+            // A sync layer that wrote a new transform during playback becomes the live value.
+            let authored = match self.applied.get(&id) {
+                Some(applied) if applied.written.matches(sample) => applied.authored,
+                _ => *sample,
+            };
+            let written = historical[index];
+            self.applied.insert(id, AppliedSample { authored, written });
+            frame.writes.push(written);
+        }
+        frame
+    }
+
+    /// Runs `update` with the captured sprite transforms and applies the result.
+    #[cfg(feature = "rendering")]
+    pub fn apply_pending<F>(
+        &mut self,
+        world: &mut World,
+        delta_secs: f32,
+        live: &[SpriteTransformSample],
+        resolve: F,
+    ) where
+        F: Fn(u64) -> Option<Entity>,
+    {
+        let live: Vec<RewindSample> = live.iter().map(RewindSample::from).collect();
+        let frame = self.update(delta_secs, &live);
+        apply_frame(world, &frame, resolve);
+    }
+}
+
+/// Writes a rewind frame to the synced sprites.
+#[cfg(feature = "rendering")]
+pub fn apply_frame<F>(world: &mut World, frame: &RewindFrame, resolve: F)
+where
+    F: Fn(u64) -> Option<Entity>,
+{
+    for sample in &frame.writes {
+        if let Some(mut transform) =
+            resolve(sample.ruby_entity_id).and_then(|entity| world.get_mut::<Transform>(entity))
+        {
+            transform.translation.x = sample.x;
+            transform.translation.y = sample.y;
+            transform.rotation = bevy_math::Quat::from_rotation_z(sample.rotation);
+        }
+    }
+    for (ids, visibility) in [
+        (&frame.hide, Visibility::Hidden),
+        (&frame.show, Visibility::Inherited),
+    ] {
+        for id in ids {
+            if let Some(mut current) =
+                resolve(*id).and_then(|entity| world.get_mut::<Visibility>(entity))
+            {
+                *current = visibility;
+            }
+        }
+    }
+}
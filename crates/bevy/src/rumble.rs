@@ -0,0 +1,252 @@
+//! Rumble pattern module for playing gamepad haptics as timed segments.
+//!
+//! A single rumble request is one intensity for one duration. Patterns such
+//! as a double pulse or a ramp are a sequence of those, and sending them all
+//! at once would make Bevy add their intensities together. The scheduler
+//! keeps a queue of segments per gamepad and starts each one when the
+//! previous one has played out, using the frame time it is advanced by.
+
+use std::collections::{BTreeMap, VecDeque};
+
+/// Weak-motor intensity of `rumble_light`.
+pub const LIGHT_RUMBLE_INTENSITY: f32 = 0.4;
+/// Intensity of both motors for `rumble_heavy`.
+pub const HEAVY_RUMBLE_INTENSITY: f32 = 1.0;
+
+/// Names accepted by `RumbleSegment::named_pattern`.
+pub const RUMBLE_PATTERN_NAMES: [&str; 4] = ["double_pulse", "heartbeat", "ramp_up", "ramp_down"];
+
+/// One step of a pattern; a segment with both motors off is a pause.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RumbleSegment {
+    pub strong_motor: f32,
+    pub weak_motor: f32,
+    pub duration_secs: f32,
+}
+
+impl RumbleSegment {
+    /// Clamps the motors to `0.0..=1.0` and the duration to non-negative.
+    pub fn new(strong_motor: f32, weak_motor: f32, duration_secs: f32) -> Self {
+        Self {
+            strong_motor: strong_motor.clamp(0.0, 1.0),
+            weak_motor: weak_motor.clamp(0.0, 1.0),
+            duration_secs: duration_secs.max(0.0),
+        }
+    }
+
+    pub fn light(duration_secs: f32) -> Self {
+        Self::new(0.0, LIGHT_RUMBLE_INTENSITY, duration_secs)
+    }
+
+    pub fn heavy(duration_secs: f32) -> Self {
+        Self::new(HEAVY_RUMBLE_INTENSITY, HEAVY_RUMBLE_INTENSITY, duration_secs)
+    }
+
+    pub fn pause(duration_secs: f32) -> Self {
+        Self::new(0.0, 0.0, duration_secs)
+    }
+
+    pub fn is_pause(&self) -> bool {
+        self.strong_motor <= 0.0 && self.weak_motor <= 0.0
+    }
+
+    /// Built-in patterns, listed in `RUMBLE_PATTERN_NAMES`.
+    pub fn named_pattern(name: &str) -> Option<Vec<Self>> {
+        let pattern = match name {
+            "double_pulse" => vec![Self::heavy(0.1), Self::pause(0.08), Self::heavy(0.1)],
+            "heartbeat" => vec![
+                Self::new(0.8, 0.3, 0.08),
+                Self::pause(0.1),
+                Self::new(0.5, 0.2, 0.08),
+                Self::pause(0.4),
+            ],
+            "ramp_up" => (1..=5).map(|step| Self::both(step as f32 / 5.0, 0.08)).collect(),
+            "ramp_down" => (1..=5).rev().map(|step| Self::both(step as f32 / 5.0, 0.08)).collect(),
+            _ => return None,
+        };
+        Some(pattern)
+    }
+
+    fn both(intensity: f32, duration_secs: f32) -> Self {
+        Self::new(intensity, intensity, duration_secs)
+    }
+}
+
+/// What the scheduler asks of a gamepad this frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RumbleStep {
+    /// Replace whatever plays with `segment`, for its remaining duration.
+    Start { gamepad_id: u64, segment: RumbleSegment },
+    /// The pattern ended, was cancelled or reached a pause.
+    Stop { gamepad_id: u64 },
+}
+
+#[derive(Debug, Clone, Default)]
+struct RumbleQueue {
+    segments: VecDeque<RumbleSegment>,
+    /// Time left of the playing segment; `None` until the first one starts.
+    remaining: Option<f32>,
+}
+
+/// Per-gamepad queues of rumble segments, played back to back.
+#[derive(Debug, Clone, Default)]
+pub struct RumbleScheduler {
+    queues: BTreeMap<u64, RumbleQueue>,
+    cancelled: Vec<u64>,
+}
+
+impl RumbleScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `segments` after anything already queued for the gamepad.
+    pub fn enqueue<I>(&mut self, gamepad_id: u64, segments: I)
+    where
+        I: IntoIterator<Item = RumbleSegment>,
+    {
+        self.queues.entry(gamepad_id).or_default().segments.extend(segments);
+    }
+
+    /// Drops the gamepad's queue; the next `advance` stops its motors.
+    pub fn cancel(&mut self, gamepad_id: u64) -> bool {
+        let cancelled = self.queues.remove(&gamepad_id).is_some();
+        if cancelled {
+            self.cancelled.push(gamepad_id);
+        }
+        cancelled
+    }
+
+    pub fn is_playing(&self, gamepad_id: u64) -> bool {
+        self.queues.contains_key(&gamepad_id)
+    }
+
+    /// Gamepads with a queued or playing pattern, in order.
+    pub fn gamepad_ids(&self) -> Vec<u64> {
+        self.queues.keys().copied().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queues.is_empty() && self.cancelled.is_empty()
+    }
+
+    /// Moves every queue on by `delta_secs` and returns the changes to send,
+    /// at most one per gamepad, in gamepad order.
+    ///
+    /// A queue that has not started yet starts its first segment without
+    /// consuming `delta_secs`, so segments queued this frame play in full.
+    /// Segments shorter than the frame are skipped over, and the one that
+    /// ends up playing is sent with the time it has left.
+    pub fn advance(&mut self, delta_secs: f32) -> Vec<RumbleStep> {
+        let delta_secs = if delta_secs.is_finite() { delta_secs.max(0.0) } else { 0.0 };
+        let mut steps: Vec<RumbleStep> = self
+            .cancelled
+            .drain(..)
+            .map(|gamepad_id| RumbleStep::Stop { gamepad_id })
+            .collect();
+
+        let mut finished = Vec::new();
+        for (&gamepad_id, queue) in &mut self.queues {
+            let mut time = if queue.remaining.is_some() { delta_secs } else { 0.0 };
+            let mut started = None;
+            loop {
+                if let Some(remaining) = queue.remaining {
+                    if remaining > time {
+                        queue.remaining = Some(remaining - time);
+                        break;
+                    }
+                    time -= remaining;
+                }
+                match queue.segments.pop_front() {
+                    Some(segment) => {
+                        queue.remaining = Some(segment.duration_secs);
+                        started = Some(segment);
+                    }
+                    None => {
+                        finished.push(gamepad_id);
+                        break;
+                    }
+                }
+            }
+
+            if finished.last() == Some(&gamepad_id) {
+                steps.push(RumbleStep::Stop { gamepad_id });
+            } else if let Some(segment) = started {
+                let segment = RumbleSegment {
+                    duration_secs: queue.remaining.unwrap_or(segment.duration_secs),
+                    ..segment
+                };
+                steps.push(if segment.is_pause() {
+                    RumbleStep::Stop { gamepad_id }
+                } else {
+                    RumbleStep::Start { gamepad_id, segment }
+                });
+            }
+        }
+        for gamepad_id in finished {
+            self.queues.remove(&gamepad_id);
+        }
+        steps
+    }
+}
+
+/// When each gamepad's requested rumble runs out, by frame elapsed time.
+///
+/// Bevy plays overlapping rumble requests side by side, so a gamepad keeps
+/// rumbling until the latest one ends; a stop request ends all of them.
+#[derive(Debug, Clone, Default)]
+pub struct RumbleTracker {
+    end_times: BTreeMap<u64, f64>,
+}
+
+impl RumbleTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a request added on top of whatever plays, keeping the later
+    /// of the two end times.
+    pub fn add(&mut self, gamepad_id: u64, now: f64, duration_secs: f32) {
+        let end = now + f64::from(duration_secs.max(0.0));
+        let entry = self.end_times.entry(gamepad_id).or_insert(end);
+        *entry = entry.max(end);
+    }
+
+    /// Records a request that replaced whatever played.
+    pub fn restart(&mut self, gamepad_id: u64, now: f64, duration_secs: f32) {
+        self.end_times
+            .insert(gamepad_id, now + f64::from(duration_secs.max(0.0)));
+    }
+
+    pub fn stop(&mut self, gamepad_id: u64) {
+        self.end_times.remove(&gamepad_id);
+    }
+
+    /// Latest end time recorded for the gamepad, even if it has passed.
+    pub fn end_time(&self, gamepad_id: u64) -> Option<f64> {
+        self.end_times.get(&gamepad_id).copied()
+    }
+
+    pub fn is_active(&self, gamepad_id: u64, now: f64) -> bool {
+        self.end_time(gamepad_id).is_some_and(|end| end > now)
+    }
+
+    /// Gamepads still rumbling at `now`, in order.
+    pub fn active_ids(&self, now: f64) -> Vec<u64> {
+        self.end_times
+            .iter()
+            .filter(|(_, end)| **end > now)
+            .map(|(gamepad_id, _)| *gamepad_id)
+            .collect()
+    }
+
+    /// Forgets requests that ended by `now`.
+    pub fn prune(&mut self, now: f64) {
+        self.end_times.retain(|_, end| *end > now);
+    }
+
+    /// Every gamepad with a recorded request, ended or not, in order.
+    pub fn gamepad_ids(&self) -> Vec<u64> {
+        self.end_times.keys().copied().collect()
+    }
+}
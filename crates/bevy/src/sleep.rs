@@ -0,0 +1,196 @@
+//! Sleep module for skipping entities whose bridge-driven motion has settled.
+//!
+//! Motion subsystems (behaviors, jelly springs) report how far each of their
+//! entities moved this frame. An entity that moved less than the sleep
+//! threshold for enough frames in a row is put to sleep: it leaves the
+//! subsystem's awake list and is no longer stepped, so it keeps rendering
+//! exactly where it stopped. Any operation that targets it wakes it again
+//! before the subsystem next runs.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Default motion per frame, in world units, below which a frame is quiet.
+pub const DEFAULT_SLEEP_EPSILON: f32 = 1.0e-4;
+/// Default number of quiet frames in a row before an entity sleeps.
+pub const DEFAULT_SLEEP_FRAMES: u32 = 30;
+
+/// Thresholds shared by every motion subsystem.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SleepConfig {
+    /// Motion per frame below which the frame counts toward sleeping.
+    pub sleep_epsilon: f32,
+    /// Motion per frame above which the count starts over. Motion between
+    /// the two thresholds neither counts nor resets it.
+    pub wake_epsilon: f32,
+    /// Quiet frames in a row before an entity sleeps; 0 never sleeps.
+    pub frames: u32,
+}
+
+impl SleepConfig {
+    /// Negative or non-finite thresholds become 0, and the wake threshold is
+    /// raised to the sleep threshold if it is below it.
+    pub fn new(sleep_epsilon: f32, wake_epsilon: f32, frames: u32) -> Self {
+        let sanitize = |value: f32| if value.is_finite() { value.max(0.0) } else { 0.0 };
+        let sleep_epsilon = sanitize(sleep_epsilon);
+        Self {
+            sleep_epsilon,
+            wake_epsilon: sanitize(wake_epsilon).max(sleep_epsilon),
+            frames,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.frames > 0
+    }
+}
+
+impl Default for SleepConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_SLEEP_EPSILON, DEFAULT_SLEEP_EPSILON, DEFAULT_SLEEP_FRAMES)
+    }
+}
+
+/// Pending sleep operation.
+#[derive(Debug, Clone)]
+pub enum SleepOperation {
+    SetConfig(SleepConfig),
+    WakeAll,
+}
+
+/// Awake list and sleeping side set of one motion subsystem.
+#[derive(Debug, Clone, Default)]
+pub struct SleepTracker {
+    /// Awake entities and how many quiet frames in a row each has had.
+    awake: BTreeMap<u64, u32>,
+    sleeping: BTreeSet<u64>,
+}
+
+impl SleepTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts tracking `id` as awake, waking it if it was asleep.
+    pub fn insert(&mut self, id: u64) {
+        self.sleeping.remove(&id);
+        self.awake.insert(id, 0);
+    }
+
+    /// Stops tracking `id`, awake or asleep.
+    pub fn remove(&mut self, id: u64) {
+        self.awake.remove(&id);
+        self.sleeping.remove(&id);
+    }
+
+    pub fn contains(&self, id: u64) -> bool {
+        self.awake.contains_key(&id) || self.sleeping.contains(&id)
+    }
+
+    /// Entities to step this frame, in ID order.
+    pub fn awake_ids(&self) -> Vec<u64> {
+        self.awake.keys().copied().collect()
+    }
+
+    /// Records how far `id` moved this frame. Returns true when that put it
+    /// to sleep; entities that are asleep or not tracked are left alone.
+    pub fn observe(&mut self, id: u64, motion: f32, config: &SleepConfig) -> bool {
+        let Some(quiet_frames) = self.awake.get_mut(&id) else {
+            return false;
+        };
+        if motion < config.sleep_epsilon {
+            *quiet_frames = quiet_frames.saturating_add(1);
+        } else if motion > config.wake_epsilon || motion.is_nan() {
+            *quiet_frames = 0;
+        }
+        if !config.is_enabled() || *quiet_frames < config.frames {
+            return false;
+        }
+        self.awake.remove(&id);
+        self.sleeping.insert(id);
+        true
+    }
+
+    /// Moves `id` back to the awake list with its quiet count reset. Returns
+    /// true if it was asleep.
+    pub fn wake(&mut self, id: u64) -> bool {
+        if self.sleeping.remove(&id) {
+            self.awake.insert(id, 0);
+            return true;
+        }
+        if let Some(quiet_frames) = self.awake.get_mut(&id) {
+            *quiet_frames = 0;
+        }
+        false
+    }
+
+    /// Wakes every sleeping entity; returns how many there were.
+    pub fn wake_all(&mut self) -> usize {
+        let sleeping = std::mem::take(&mut self.sleeping);
+        let count = sleeping.len();
+        for id in sleeping {
+            self.awake.insert(id, 0);
+        }
+        count
+    }
+
+    pub fn is_asleep(&self, id: u64) -> bool {
+        self.sleeping.contains(&id)
+    }
+
+    pub fn sleeping_count(&self) -> usize {
+        self.sleeping.len()
+    }
+
+    pub fn awake_count(&self) -> usize {
+        self.awake.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.awake.clear();
+        self.sleeping.clear();
+    }
+}
+
+/// Sleep thresholds in effect and the operations queued for them.
+#[derive(Debug, Clone, Default)]
+pub struct MotionSleep {
+    config: SleepConfig,
+    /// Pending operations to apply on next update.
+    pub pending_operations: Vec<SleepOperation>,
+}
+
+impl MotionSleep {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues new thresholds (standalone, no World needed).
+    pub fn set_config_standalone(&mut self, config: SleepConfig) {
+        self.pending_operations.push(SleepOperation::SetConfig(config));
+    }
+
+    /// Queues waking every sleeping entity (standalone, no World needed).
+    pub fn wake_all_standalone(&mut self) {
+        self.pending_operations.push(SleepOperation::WakeAll);
+    }
+
+    /// Applies queued operations. Returns true when every subsystem should
+    /// wake its sleeping entities, which turning sleep off also asks for.
+    pub fn apply_operations(&mut self) -> bool {
+        let mut wake_all = false;
+        for op in self.pending_operations.drain(..) {
+            match op {
+                SleepOperation::SetConfig(config) => {
+                    self.config = config;
+                    wake_all |= !config.is_enabled();
+                }
+                SleepOperation::WakeAll => wake_all = true,
+            }
+        }
+        wake_all
+    }
+
+    pub fn config(&self) -> SleepConfig {
+        self.config
+    }
+}
@@ -0,0 +1,169 @@
+//! Sound set module for playing one of several takes of a sound.
+//!
+//! Footsteps, impacts and clicks sound mechanical when the same sample
+//! repeats. A set picks one of its members at random, skipping the last
+//! `avoid_repeat` picks, and the volume and speed of each play are offset
+//! by a random amount within the requested variation. Picks come from a
+//! seedable generator, so a given seed always plays the same sequence.
+
+use std::collections::{HashMap, VecDeque};
+
+/// Seed of the generator until `SoundSets::seed` is called.
+pub const DEFAULT_SOUND_SET_SEED: u64 = 0x5eed;
+
+/// SplitMix64 generator: tiny, and identical on every platform.
+#[derive(Debug, Clone)]
+pub struct SoundRng {
+    state: u64,
+}
+
+impl SoundRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0.0..1.0`.
+    pub fn next_unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Offsets `base` by up to `variation` of itself either way, with `roll`
+/// in `0.0..1.0` mapping linearly onto `base * (1 - variation)..base * (1 + variation)`.
+///
+/// `variation` is clamped to `0.0..=1.0`; a non-finite one means none.
+pub fn sound_variation(base: f32, variation: f32, roll: f64) -> f32 {
+    let variation = if variation.is_finite() { variation.clamp(0.0, 1.0) } else { 0.0 };
+    let offset = variation as f64 * (2.0 * roll.clamp(0.0, 1.0) - 1.0);
+    (base as f64 * (1.0 + offset)) as f32
+}
+
+/// Takes of one sound and the picks to avoid repeating.
+#[derive(Debug, Clone)]
+pub struct SoundSet {
+    paths: Vec<String>,
+    avoid_repeat: usize,
+    /// Most recent picks, oldest first.
+    history: VecDeque<usize>,
+}
+
+impl SoundSet {
+    pub fn new(paths: Vec<String>, avoid_repeat: usize) -> Self {
+        Self {
+            paths,
+            avoid_repeat,
+            history: VecDeque::new(),
+        }
+    }
+
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    pub fn avoid_repeat(&self) -> usize {
+        self.avoid_repeat
+    }
+
+    /// Picks actually avoided: one fewer than the members at most, so
+    /// there is always something left to pick.
+    fn effective_avoid_repeat(&self) -> usize {
+        self.avoid_repeat.min(self.paths.len().saturating_sub(1))
+    }
+
+    /// Members that may be picked next, in registration order.
+    pub fn candidates(&self) -> Vec<usize> {
+        (0..self.paths.len())
+            .filter(|index| !self.history.contains(index))
+            .collect()
+    }
+
+    /// Picks the candidate at `roll` (`0.0..1.0`) and remembers it; `None`
+    /// for an empty set.
+    pub fn pick(&mut self, roll: f64) -> Option<usize> {
+        let candidates = self.candidates();
+        if candidates.is_empty() {
+            return None;
+        }
+        let slot = (roll.clamp(0.0, 1.0) * candidates.len() as f64) as usize;
+        let picked = candidates[slot.min(candidates.len() - 1)];
+
+        let avoid = self.effective_avoid_repeat();
+        self.history.push_back(picked);
+        while self.history.len() > avoid {
+            self.history.pop_front();
+        }
+        Some(picked)
+    }
+}
+
+/// One play of a set: the member and the factors to scale volume and speed by.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoundPick {
+    pub path: String,
+    pub volume_scale: f32,
+    pub speed_scale: f32,
+}
+
+/// Named sound sets sharing one generator.
+pub struct SoundSets {
+    sets: HashMap<String, SoundSet>,
+    rng: SoundRng,
+}
+
+impl SoundSets {
+    pub fn new() -> Self {
+        Self {
+            sets: HashMap::new(),
+            rng: SoundRng::new(DEFAULT_SOUND_SET_SEED),
+        }
+    }
+
+    /// Restarts the generator; the same seed replays the same picks and offsets.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = SoundRng::new(seed);
+    }
+
+    /// Registers `set` under `name`, replacing an existing set and its history.
+    pub fn register(&mut self, name: &str, set: SoundSet) {
+        self.sets.insert(name.to_string(), set);
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.sets.remove(name).is_some()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SoundSet> {
+        self.sets.get(name)
+    }
+
+    /// Picks a member of `name` with random volume and speed factors, or
+    /// `None` for an unknown or empty set.
+    ///
+    /// Three values are drawn per pick, member first, so the sequence for a
+    /// seed doesn't depend on the variations asked for.
+    pub fn pick(&mut self, name: &str, volume_variation: f32, pitch_variation: f32) -> Option<SoundPick> {
+        let set = self.sets.get_mut(name)?;
+        let member = set.pick(self.rng.next_unit())?;
+        let volume_roll = self.rng.next_unit();
+        let speed_roll = self.rng.next_unit();
+        Some(SoundPick {
+            path: set.paths[member].clone(),
+            volume_scale: sound_variation(1.0, volume_variation, volume_roll),
+            speed_scale: sound_variation(1.0, pitch_variation, speed_roll),
+        })
+    }
+}
+
+impl Default for SoundSets {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,9 +1,22 @@
 //! Sprite renderer module for synchronizing Ruby sprites with Bevy.
 
-use std::collections::HashMap;
+use crate::accessibility::AccessibilityData;
+use crate::determinism::AuditMap;
+use crate::draw_order::{DrawOrder, DrawOrderChange};
+use crate::line_of_sight::Bounds;
+#[cfg(feature = "rendering")]
+use crate::texture_loading::{PendingTextures, TextureLoadSource, TextureLoadState, TexturePlaceholder};
+use crate::jelly::JellyConfig;
+#[cfg(feature = "rendering")]
+use crate::jelly::JellyMesh;
+#[cfg(feature = "rendering")]
+use crate::accessibility::sync_accessibility_node;
+#[cfg(feature = "rendering")]
+use crate::sleep::SleepConfig;
+use crate::sleep::SleepTracker;
 
 #[cfg(feature = "rendering")]
-use bevy_asset::{Assets, Handle};
+use bevy_asset::{AssetServer, Assets, Handle, LoadState};
 #[cfg(feature = "rendering")]
 use bevy_color::Color;
 #[cfg(feature = "rendering")]
@@ -15,12 +28,32 @@ use bevy_ecs::world::World;
 #[cfg(feature = "rendering")]
 use bevy_image::Image;
 #[cfg(feature = "rendering")]
-use bevy_math::Vec2;
+use bevy_math::{Rect, Vec2};
+#[cfg(feature = "rendering")]
+use bevy_render::mesh::{Indices, Mesh, Mesh2d, PrimitiveTopology};
 #[cfg(feature = "rendering")]
-use bevy_sprite::Sprite;
+use bevy_render::render_asset::RenderAssetUsages;
+#[cfg(feature = "rendering")]
+use bevy_render::view::Visibility;
+#[cfg(feature = "rendering")]
+use bevy_sprite::{Anchor, ColorMaterial, MeshMaterial2d, Sprite};
 #[cfg(feature = "rendering")]
 use bevy_transform::components::Transform;
 
+/// Z distance between sprite layers.
+///
+/// A sprite's transform z moves it within its layer; keeping it inside
+/// ±`SPRITE_LAYER_STEP / 2` keeps every higher layer on top.
+pub const SPRITE_LAYER_STEP: f32 = 10.0;
+
+/// Rendered z of a sprite at transform depth `z` on layer `z_index`.
+pub fn layered_z(z: f32, z_index: Option<i32>) -> f32 {
+    match z_index {
+        Some(z_index) => z + z_index as f32 * SPRITE_LAYER_STEP,
+        None => z,
+    }
+}
+
 /// Sprite data received from Ruby.
 #[derive(Debug, Clone)]
 pub struct SpriteData {
@@ -35,6 +68,22 @@ pub struct SpriteData {
     pub has_custom_size: bool,
     pub custom_size_x: f32,
     pub custom_size_y: f32,
+    pub group: Option<String>,
+    pub accessibility: Option<AccessibilityData>,
+    /// Image file loaded through the asset server; `None` uses the default white texture.
+    pub texture_path: Option<String>,
+    /// Draws a subdivided mesh instead of a `Sprite` so impulses can wobble it.
+    ///
+    /// The mesh is sized by the custom size (1×1 without one). LOD overrides
+    /// change only its color and texture.
+    pub mesh_mode: bool,
+    /// Vertex springs used in mesh mode.
+    pub jelly: JellyConfig,
+    /// Draw layer, applied on top of the transform z; `None` draws at the transform z.
+    pub z_index: Option<i32>,
+    /// Region of the texture drawn, as `(x, y, width, height)` in pixels from
+    /// its top-left corner; `None` draws the whole image. Plain sprites only.
+    pub atlas_rect: Option<(f32, f32, f32, f32)>,
 }
 
 impl Default for SpriteData {
@@ -51,6 +100,13 @@ impl Default for SpriteData {
             has_custom_size: false,
             custom_size_x: 0.0,
             custom_size_y: 0.0,
+            group: None,
+            accessibility: None,
+            texture_path: None,
+            mesh_mode: false,
+            jelly: JellyConfig::default(),
+            z_index: None,
+            atlas_rect: None,
         }
     }
 }
@@ -87,6 +143,48 @@ impl Default for TransformData {
     }
 }
 
+/// Applied 2D transform of a synced sprite, captured once per frame.
+#[derive(Debug, Clone)]
+pub struct SpriteTransformSample {
+    pub ruby_entity_id: u64,
+    pub x: f32,
+    pub y: f32,
+    pub rotation: f32,
+    /// Drawn size in world units, scale included and rotation not.
+    pub width: f32,
+    pub height: f32,
+    /// Point of the sprite placed at `x`, `y`: (0, 0) is its top-left
+    /// corner and (0.5, 0.5) its center.
+    pub anchor_x: f32,
+    pub anchor_y: f32,
+    pub group: Option<String>,
+}
+
+impl SpriteTransformSample {
+    /// Axis-aligned box enclosing the drawn, rotated sprite.
+    pub fn bounds(&self) -> Bounds {
+        let offset_x = (0.5 - self.anchor_x) * self.width;
+        let offset_y = (self.anchor_y - 0.5) * self.height;
+        let (sin, cos) = self.rotation.sin_cos();
+        let center = (
+            self.x + offset_x * cos - offset_y * sin,
+            self.y + offset_x * sin + offset_y * cos,
+        );
+        Bounds::from_rotated(center, (self.width, self.height), self.rotation)
+    }
+}
+
+/// Bevy anchor for a normalized sprite anchor where (0, 0) is the top-left
+/// corner and (0.5, 0.5) the center.
+#[cfg(feature = "rendering")]
+fn sprite_anchor(anchor_x: f32, anchor_y: f32) -> Anchor {
+    if anchor_x == 0.5 && anchor_y == 0.5 {
+        Anchor::Center
+    } else {
+        Anchor::Custom(Vec2::new(anchor_x - 0.5, 0.5 - anchor_y))
+    }
+}
+
 /// Pending sprite operation.
 #[derive(Debug, Clone)]
 pub enum SpriteOperation {
@@ -95,60 +193,333 @@ pub enum SpriteOperation {
         sprite_data: SpriteData,
         transform_data: TransformData,
     },
+    /// Removes using the sync's default removal deferral.
     Remove {
         ruby_entity_id: u64,
     },
+    /// Keeps the sprite visible for `frames` more frames before despawning it.
+    RemoveDeferred {
+        ruby_entity_id: u64,
+        frames: u32,
+    },
+    /// Sets the deferral used by `Remove`.
+    SetRemoveDefer {
+        frames: u32,
+    },
+    /// Overrides the synced color until cleared.
+    SetColor {
+        ruby_entity_id: u64,
+        color: [f32; 4],
+    },
+    ClearColor {
+        ruby_entity_id: u64,
+    },
+    /// Replaces the synced look with a level-of-detail sprite until cleared.
+    ///
+    /// `sprite_data: None` keeps the synced look; `hidden` hides the sprite.
+    SetLod {
+        ruby_entity_id: u64,
+        sprite_data: Option<SpriteData>,
+        hidden: bool,
+    },
+    ClearLod {
+        ruby_entity_id: u64,
+    },
+    /// Kicks the vertex springs of a mesh-mode sprite; ignored for plain sprites.
+    ApplyImpulse {
+        ruby_entity_id: u64,
+        direction: [f32; 2],
+        strength: f32,
+    },
+    /// Moves a synced sprite within its layer; ignored for unsynced ones.
+    SetDrawOrder {
+        ruby_entity_id: u64,
+        change: DrawOrderChange,
+    },
     Clear,
 }
 
-/// Resource to hold the default white texture for sprites.
+impl SpriteOperation {
+    /// The sprite this operation targets; `None` for ones that target every sprite or none.
+    pub fn ruby_entity_id(&self) -> Option<u64> {
+        match self {
+            SpriteOperation::Sync { ruby_entity_id, .. }
+            | SpriteOperation::Remove { ruby_entity_id }
+            | SpriteOperation::RemoveDeferred { ruby_entity_id, .. }
+            | SpriteOperation::SetColor { ruby_entity_id, .. }
+            | SpriteOperation::ClearColor { ruby_entity_id }
+            | SpriteOperation::SetLod { ruby_entity_id, .. }
+            | SpriteOperation::ClearLod { ruby_entity_id }
+            | SpriteOperation::ApplyImpulse { ruby_entity_id, .. }
+            | SpriteOperation::SetDrawOrder { ruby_entity_id, .. } => Some(*ruby_entity_id),
+            SpriteOperation::SetRemoveDefer { .. } | SpriteOperation::Clear => None,
+        }
+    }
+}
+
+/// Resource to hold the default white texture for sprites, and the
+/// textures shown while a sprite's own texture loads or after it failed.
 #[cfg(feature = "rendering")]
 #[derive(Resource)]
 pub struct DefaultSpriteTexture {
     pub handle: Handle<Image>,
+    pub checkerboard: Handle<Image>,
+    pub transparent: Handle<Image>,
+    /// Magenta, drawn for textures that failed to load.
+    pub error: Handle<Image>,
 }
 
+/// Edge in pixels of the checkerboard placeholder; squares are a quarter of it.
+#[cfg(feature = "rendering")]
+const CHECKERBOARD_SIZE: u32 = 8;
+
 #[cfg(feature = "rendering")]
 impl DefaultSpriteTexture {
     pub fn create_1x1_white_image() -> Image {
+        Self::create_image(1, vec![255, 255, 255, 255])
+    }
+
+    fn create_image(size: u32, pixels: Vec<u8>) -> Image {
         use bevy_image::Image;
         use bevy_render::render_asset::RenderAssetUsages;
         use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat};
 
         Image::new(
             Extent3d {
-                width: 1,
-                height: 1,
+                width: size,
+                height: size,
                 depth_or_array_layers: 1,
             },
             TextureDimension::D2,
-            vec![255, 255, 255, 255],
+            pixels,
             TextureFormat::Rgba8UnormSrgb,
             RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD,
         )
     }
 
+    fn create_checkerboard_image() -> Image {
+        let square = CHECKERBOARD_SIZE / 4;
+        let pixels = (0..CHECKERBOARD_SIZE * CHECKERBOARD_SIZE)
+            .flat_map(|index| {
+                let (x, y) = (index % CHECKERBOARD_SIZE, index / CHECKERBOARD_SIZE);
+                let shade = if (x / square + y / square).is_multiple_of(2) { 255 } else { 160 };
+                [shade, shade, shade, 255]
+            })
+            .collect();
+        Self::create_image(CHECKERBOARD_SIZE, pixels)
+    }
+
     pub fn insert_into_world(world: &mut World) {
-        let image = Self::create_1x1_white_image();
-        let handle = {
-            let mut images = world.resource_mut::<Assets<Image>>();
-            images.add(image)
+        let mut images = world.resource_mut::<Assets<Image>>();
+        let handle = images.add(Self::create_1x1_white_image());
+        let checkerboard = images.add(Self::create_checkerboard_image());
+        let transparent = images.add(Self::create_image(1, vec![0, 0, 0, 0]));
+        let error = images.add(Self::create_image(1, vec![255, 0, 255, 255]));
+        world.insert_resource(DefaultSpriteTexture {
+            handle,
+            checkerboard,
+            transparent,
+            error,
+        });
+    }
+
+    pub fn placeholder(&self, placeholder: TexturePlaceholder) -> Handle<Image> {
+        match placeholder {
+            TexturePlaceholder::White => self.handle.clone(),
+            TexturePlaceholder::Checkerboard => self.checkerboard.clone(),
+            TexturePlaceholder::Transparent => self.transparent.clone(),
+        }
+    }
+}
+
+/// Load states of the texture paths a `SpriteSync` has requested.
+#[cfg(feature = "rendering")]
+struct AssetLoadStates<'a> {
+    asset_server: &'a AssetServer,
+    textures: &'a AuditMap<String, Handle<Image>>,
+}
+
+#[cfg(feature = "rendering")]
+impl TextureLoadSource for AssetLoadStates<'_> {
+    fn load_state(&self, path: &str) -> TextureLoadState {
+        let Some(handle) = self.textures.get(path) else {
+            return TextureLoadState::Failed;
         };
-        world.insert_resource(DefaultSpriteTexture { handle });
+        match self.asset_server.get_load_state(handle) {
+            Some(LoadState::Loaded) => TextureLoadState::Loaded,
+            Some(LoadState::Failed(_)) => TextureLoadState::Failed,
+            _ => TextureLoadState::Loading,
+        }
     }
 }
 
 /// Manages the synchronization of Ruby sprites to Bevy entities.
 pub struct SpriteSync {
     /// Maps Ruby entity IDs to Bevy render entities.
-    entity_map: HashMap<u64, EntityData>,
+    entity_map: AuditMap<u64, EntityData>,
     /// Pending operations to apply on next update.
     pub pending_operations: Vec<SpriteOperation>,
+    /// Color overrides that take precedence over the synced sprite color.
+    color_overrides: AuditMap<u64, [f32; 4]>,
+    /// Frames `Remove` keeps a sprite visible before despawning it.
+    remove_defer: u32,
+    /// Frames left before each deferred sprite is despawned.
+    deferred_removals: AuditMap<u64, u32>,
+    /// Level-of-detail overrides; they outlive removal so re-synced sprites keep their level.
+    lod_overrides: AuditMap<u64, LodOverride>,
+    /// Image handles loaded from texture paths, shared by every sprite using the path.
+    #[cfg(feature = "rendering")]
+    textures: AuditMap<String, Handle<Image>>,
+    /// Sprites drawn with the placeholder until their texture finishes loading.
+    #[cfg(feature = "rendering")]
+    pending_textures: PendingTextures,
+    #[cfg(feature = "rendering")]
+    texture_placeholder: TexturePlaceholder,
+    /// Mesh-mode sprites whose springs are stepped, and the ones asleep.
+    jelly_sleep: SleepTracker,
+    /// Layer and fine order of every synced sprite.
+    draw_order: DrawOrder,
+}
+
+#[derive(Debug, Clone)]
+struct LodOverride {
+    #[cfg(feature = "rendering")]
+    sprite_data: Option<SpriteData>,
+    hidden: bool,
+}
+
+/// Sprite fields written to Bevy once color and LOD overrides are resolved.
+#[cfg(feature = "rendering")]
+struct SpriteLook {
+    color: Color,
+    custom_size: Option<Vec2>,
+    flip_x: bool,
+    flip_y: bool,
+    /// Applied to plain sprites; mesh-mode sprites stay centered.
+    anchor: Anchor,
+    texture_path: Option<String>,
+    rect: Option<Rect>,
+    visibility: Visibility,
+}
+
+/// How a synced sprite is drawn.
+#[cfg(feature = "rendering")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpriteRenderMode {
+    Sprite,
+    Mesh,
+}
+
+#[cfg(feature = "rendering")]
+impl SpriteRenderMode {
+    fn of(sprite_data: &SpriteData) -> Self {
+        if sprite_data.mesh_mode {
+            Self::Mesh
+        } else {
+            Self::Sprite
+        }
+    }
+}
+
+/// Mesh, material and vertex springs of a mesh-mode sprite.
+#[cfg(feature = "rendering")]
+struct JellyBody {
+    sim: JellyMesh,
+    /// Tuning as synced, before `JellyMesh` clamps it.
+    config: JellyConfig,
+    flip_x: bool,
+    flip_y: bool,
+    mesh: Handle<Mesh>,
+    material: Handle<ColorMaterial>,
+}
+
+#[cfg(feature = "rendering")]
+impl JellyBody {
+    fn new(world: &mut World, look: &SpriteLook, image: Handle<Image>, config: JellyConfig) -> Self {
+        let size = look.custom_size.unwrap_or(Vec2::ONE);
+        let sim = JellyMesh::new([size.x, size.y], config);
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(jelly_mesh(&sim, look.flip_x, look.flip_y));
+        let material = world.resource_mut::<Assets<ColorMaterial>>().add(ColorMaterial {
+            color: look.color,
+            texture: Some(image),
+            ..Default::default()
+        });
+        Self {
+            sim,
+            config,
+            flip_x: look.flip_x,
+            flip_y: look.flip_y,
+            mesh,
+            material,
+        }
+    }
+
+    /// Rebuilds the grid when the size, tuning or flip changed, which settles
+    /// the springs; returns whether it did.
+    fn resync(&mut self, world: &mut World, look: &SpriteLook, config: JellyConfig) -> bool {
+        let size = look.custom_size.unwrap_or(Vec2::ONE);
+        if self.sim.size() == [size.x, size.y]
+            && self.config == config
+            && (self.flip_x, self.flip_y) == (look.flip_x, look.flip_y)
+        {
+            return false;
+        }
+        self.sim = JellyMesh::new([size.x, size.y], config);
+        self.config = config;
+        self.flip_x = look.flip_x;
+        self.flip_y = look.flip_y;
+        if let Some(mut meshes) = world.get_resource_mut::<Assets<Mesh>>() {
+            meshes.insert(&self.mesh, jelly_mesh(&self.sim, self.flip_x, self.flip_y));
+        }
+        true
+    }
+
+    /// Copies the simulated vertex positions into the mesh asset.
+    fn write_positions(&self, world: &mut World) {
+        if let Some(mut meshes) = world.get_resource_mut::<Assets<Mesh>>()
+            && let Some(mesh) = meshes.get_mut(&self.mesh)
+        {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertex_positions(&self.sim));
+        }
+    }
+}
+
+#[cfg(feature = "rendering")]
+fn vertex_positions(sim: &JellyMesh) -> Vec<[f32; 3]> {
+    sim.positions().into_iter().map(|[x, y]| [x, y, 0.0]).collect()
+}
+
+/// Triangle-list mesh for a jelly grid, kept in the main world so it can be rewritten.
+#[cfg(feature = "rendering")]
+fn jelly_mesh(sim: &JellyMesh, flip_x: bool, flip_y: bool) -> Mesh {
+    let positions = vertex_positions(sim);
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD,
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, sim.uvs(flip_x, flip_y));
+    mesh.insert_indices(Indices::U32(sim.indices()));
+    mesh
 }
 
 struct EntityData {
     #[cfg(feature = "rendering")]
     bevy_entity: Entity,
+    /// Changing the mode on re-sync despawns the entity and spawns a new one.
+    #[cfg(feature = "rendering")]
+    mode: SpriteRenderMode,
+    #[cfg(feature = "rendering")]
+    jelly: Option<JellyBody>,
+    #[cfg(feature = "rendering")]
+    group: Option<String>,
+    /// Sprite data from the last sync, restored when a LOD override goes away.
+    #[cfg(feature = "rendering")]
+    synced: SpriteData,
     #[cfg(not(feature = "rendering"))]
     _phantom: (),
 }
@@ -157,8 +528,20 @@ impl SpriteSync {
     /// Creates a new SpriteSync instance.
     pub fn new() -> Self {
         Self {
-            entity_map: HashMap::new(),
+            entity_map: AuditMap::new(),
             pending_operations: Vec::new(),
+            color_overrides: AuditMap::new(),
+            remove_defer: 0,
+            deferred_removals: AuditMap::new(),
+            lod_overrides: AuditMap::new(),
+            #[cfg(feature = "rendering")]
+            textures: AuditMap::new(),
+            #[cfg(feature = "rendering")]
+            pending_textures: PendingTextures::new(),
+            #[cfg(feature = "rendering")]
+            texture_placeholder: TexturePlaceholder::default(),
+            jelly_sleep: SleepTracker::new(),
+            draw_order: DrawOrder::new(),
         }
     }
 
@@ -181,14 +564,87 @@ impl SpriteSync {
         self.pending_operations.push(SpriteOperation::Remove { ruby_entity_id });
     }
 
+    /// Queues a removal that keeps the sprite for `frames` more frames (standalone, no World needed).
+    pub fn remove_sprite_deferred_standalone(&mut self, ruby_entity_id: u64, frames: u32) {
+        self.pending_operations.push(SpriteOperation::RemoveDeferred {
+            ruby_entity_id,
+            frames,
+        });
+    }
+
+    /// Queues changing the default removal deferral (standalone, no World needed).
+    pub fn set_remove_defer_standalone(&mut self, frames: u32) {
+        self.pending_operations
+            .push(SpriteOperation::SetRemoveDefer { frames });
+    }
+
+    /// Queues a color override (standalone, no World needed).
+    pub fn set_color_standalone(&mut self, ruby_entity_id: u64, color: [f32; 4]) {
+        self.pending_operations.push(SpriteOperation::SetColor {
+            ruby_entity_id,
+            color,
+        });
+    }
+
+    /// Queues removing a color override (standalone, no World needed).
+    pub fn clear_color_standalone(&mut self, ruby_entity_id: u64) {
+        self.pending_operations
+            .push(SpriteOperation::ClearColor { ruby_entity_id });
+    }
+
+    /// Queues a level-of-detail override (standalone, no World needed).
+    pub fn set_lod_standalone(
+        &mut self,
+        ruby_entity_id: u64,
+        sprite_data: Option<SpriteData>,
+        hidden: bool,
+    ) {
+        self.pending_operations.push(SpriteOperation::SetLod {
+            ruby_entity_id,
+            sprite_data,
+            hidden,
+        });
+    }
+
+    /// Queues removing a level-of-detail override (standalone, no World needed).
+    pub fn clear_lod_standalone(&mut self, ruby_entity_id: u64) {
+        self.pending_operations
+            .push(SpriteOperation::ClearLod { ruby_entity_id });
+    }
+
+    /// Queues an impulse on a mesh-mode sprite (standalone, no World needed).
+    pub fn apply_impulse_standalone(&mut self, ruby_entity_id: u64, direction: [f32; 2], strength: f32) {
+        self.pending_operations.push(SpriteOperation::ApplyImpulse {
+            ruby_entity_id,
+            direction,
+            strength,
+        });
+    }
+
+    /// Queues moving a sprite within its layer (standalone, no World needed).
+    pub fn set_draw_order_standalone(&mut self, ruby_entity_id: u64, change: DrawOrderChange) {
+        self.pending_operations.push(SpriteOperation::SetDrawOrder {
+            ruby_entity_id,
+            change,
+        });
+    }
+
     /// Queues clearing all sprites (standalone, no World needed).
     pub fn clear_standalone(&mut self) {
         self.pending_operations.push(SpriteOperation::Clear);
     }
 
     /// Applies all pending operations to the World.
+    ///
+    /// Deferred removals issued in earlier frames count down by one first, and
+    /// the ones that reach zero are despawned. Draw order offsets of the
+    /// layers changed by the operations are reassigned last, in one pass.
     #[cfg(feature = "rendering")]
     pub fn apply_pending(&mut self, world: &mut World) {
+        for ruby_entity_id in self.tick_deferred_removals() {
+            self.remove_sprite(world, ruby_entity_id);
+        }
+
         let ops: Vec<_> = self.pending_operations.drain(..).collect();
         for op in ops {
             match op {
@@ -200,13 +656,88 @@ impl SpriteSync {
                     self.sync_sprite(world, ruby_entity_id, &sprite_data, &transform_data);
                 }
                 SpriteOperation::Remove { ruby_entity_id } => {
-                    self.remove_sprite(world, ruby_entity_id);
+                    self.defer_removal(world, ruby_entity_id, self.remove_defer);
+                }
+                SpriteOperation::RemoveDeferred {
+                    ruby_entity_id,
+                    frames,
+                } => {
+                    self.defer_removal(world, ruby_entity_id, frames);
+                }
+                SpriteOperation::SetRemoveDefer { frames } => {
+                    self.remove_defer = frames;
+                }
+                SpriteOperation::SetColor {
+                    ruby_entity_id,
+                    color,
+                } => {
+                    self.set_color(world, ruby_entity_id, color);
+                }
+                SpriteOperation::ClearColor { ruby_entity_id } => {
+                    self.color_overrides.remove(&ruby_entity_id);
+                }
+                SpriteOperation::SetLod {
+                    ruby_entity_id,
+                    sprite_data,
+                    hidden,
+                } => {
+                    self.lod_overrides.insert(
+                        ruby_entity_id,
+                        LodOverride {
+                            sprite_data,
+                            hidden,
+                        },
+                    );
+                    self.refresh_look(world, ruby_entity_id);
+                }
+                SpriteOperation::ClearLod { ruby_entity_id } => {
+                    if self.lod_overrides.remove(&ruby_entity_id).is_some() {
+                        self.refresh_look(world, ruby_entity_id);
+                    }
+                }
+                SpriteOperation::ApplyImpulse {
+                    ruby_entity_id,
+                    direction,
+                    strength,
+                } => {
+                    if let Some(body) = self
+                        .entity_map
+                        .get_mut(&ruby_entity_id)
+                        .and_then(|entity_data| entity_data.jelly.as_mut())
+                    {
+                        body.sim.apply_impulse(direction, strength);
+                        self.jelly_sleep.wake(ruby_entity_id);
+                    }
+                }
+                SpriteOperation::SetDrawOrder {
+                    ruby_entity_id,
+                    change,
+                } => {
+                    self.draw_order.apply(ruby_entity_id, change);
                 }
                 SpriteOperation::Clear => {
                     self.clear(world);
                 }
             }
         }
+
+        self.apply_draw_order(world);
+    }
+
+    /// Shifts the z of every sprite whose draw order offset moved.
+    #[cfg(feature = "rendering")]
+    fn apply_draw_order(&mut self, world: &mut World) {
+        if !self.draw_order.has_changes() {
+            return;
+        }
+        for (ruby_entity_id, delta) in self.draw_order.take_changes() {
+            let Some(entity_data) = self.entity_map.get(&ruby_entity_id) else {
+                continue;
+            };
+            if let Some(mut transform) = world.get_mut::<Transform>(entity_data.bevy_entity) {
+                transform.translation.z += delta;
+            }
+        }
     }
 
     #[cfg(not(feature = "rendering"))]
@@ -214,21 +745,73 @@ impl SpriteSync {
         self.pending_operations.clear();
     }
 
-    /// Synchronizes a Ruby sprite to Bevy.
+    /// Default deferral, in frames, applied by `Remove`.
+    pub fn remove_defer(&self) -> u32 {
+        self.remove_defer
+    }
+
+    /// Frames left before a deferred sprite is despawned, if its removal is pending.
+    pub fn deferred_frames(&self, ruby_entity_id: u64) -> Option<u32> {
+        self.deferred_removals.get(&ruby_entity_id).copied()
+    }
+
+    /// Counts every deferred removal down by one frame and returns the IDs now due.
+    pub fn tick_deferred_removals(&mut self) -> Vec<u64> {
+        let mut due = Vec::new();
+        self.deferred_removals.retain(|ruby_entity_id, frames| {
+            *frames = frames.saturating_sub(1);
+            if *frames == 0 {
+                due.push(*ruby_entity_id);
+            }
+            *frames > 0
+        });
+        due.sort_unstable();
+        due
+    }
+
+    /// Removes a sprite now when `frames` is zero, otherwise schedules it.
+    ///
+    /// A deferred sprite stays mapped, so it keeps rendering and can be
+    /// re-synced; only the actual removal releases its entity.
     #[cfg(feature = "rendering")]
-    pub fn sync_sprite(
-        &mut self,
-        world: &mut World,
-        ruby_entity_id: u64,
-        sprite_data: &SpriteData,
-        transform_data: &TransformData,
-    ) {
-        let color = Color::srgba(
-            sprite_data.color_r,
-            sprite_data.color_g,
-            sprite_data.color_b,
-            sprite_data.color_a,
-        );
+    fn defer_removal(&mut self, world: &mut World, ruby_entity_id: u64, frames: u32) {
+        if frames == 0 || !self.entity_map.contains_key(&ruby_entity_id) {
+            self.remove_sprite(world, ruby_entity_id);
+        } else {
+            self.deferred_removals.insert(ruby_entity_id, frames);
+        }
+    }
+
+    /// Returns the color override for a Ruby entity, if any.
+    pub fn color_override(&self, ruby_entity_id: u64) -> Option<[f32; 4]> {
+        self.color_overrides.get(&ruby_entity_id).copied()
+    }
+
+    /// Returns true if the sprite is hidden by its level-of-detail override.
+    pub fn lod_hidden(&self, ruby_entity_id: u64) -> bool {
+        self.lod_overrides
+            .get(&ruby_entity_id)
+            .is_some_and(|lod| lod.hidden)
+    }
+
+    /// Resolves the look of a sprite: LOD sprite data replaces the synced
+    /// data, and a color override wins over both.
+    #[cfg(feature = "rendering")]
+    fn look(&self, ruby_entity_id: u64, synced: &SpriteData) -> SpriteLook {
+        let lod = self.lod_overrides.get(&ruby_entity_id);
+        let sprite_data = lod
+            .and_then(|lod| lod.sprite_data.as_ref())
+            .unwrap_or(synced);
+
+        let color = match self.color_overrides.get(&ruby_entity_id) {
+            Some(&[r, g, b, a]) => Color::srgba(r, g, b, a),
+            None => Color::srgba(
+                sprite_data.color_r,
+                sprite_data.color_g,
+                sprite_data.color_b,
+                sprite_data.color_a,
+            ),
+        };
 
         let custom_size = if sprite_data.has_custom_size {
             Some(Vec2::new(
@@ -239,11 +822,183 @@ impl SpriteSync {
             None
         };
 
+        SpriteLook {
+            color,
+            custom_size,
+            flip_x: sprite_data.flip_x,
+            flip_y: sprite_data.flip_y,
+            anchor: sprite_anchor(sprite_data.anchor_x, sprite_data.anchor_y),
+            texture_path: sprite_data.texture_path.clone(),
+            rect: sprite_data
+                .atlas_rect
+                .map(|(x, y, width, height)| Rect::new(x, y, x + width, y + height)),
+            visibility: if lod.is_some_and(|lod| lod.hidden) {
+                Visibility::Hidden
+            } else {
+                Visibility::Inherited
+            },
+        }
+    }
+
+    /// Returns the image a sprite shows for a texture path, starting the
+    /// load on first use.
+    ///
+    /// Until the path has loaded the sprite shows the placeholder and waits
+    /// for `resolve_textures`; a failed path shows the error texture. `None`,
+    /// or a world without an asset server, falls back to the default white
+    /// texture.
+    #[cfg(feature = "rendering")]
+    fn texture(&mut self, world: &World, ruby_entity_id: u64, texture_path: Option<&str>) -> Handle<Image> {
+        let defaults = world.get_resource::<DefaultSpriteTexture>();
+        let default = || defaults.map(|t| t.handle.clone()).unwrap_or_default();
+        let (Some(texture_path), Some(asset_server)) = (texture_path, world.get_resource::<AssetServer>()) else {
+            self.pending_textures.forget(ruby_entity_id);
+            return default();
+        };
+        let handle = match self.textures.get(texture_path) {
+            Some(handle) => handle.clone(),
+            None => {
+                let handle: Handle<Image> = asset_server.load(texture_path.to_owned());
+                self.textures.insert(texture_path.to_owned(), handle.clone());
+                handle
+            }
+        };
+
+        let failed = self.pending_textures.is_failed(texture_path)
+            || matches!(asset_server.get_load_state(&handle), Some(LoadState::Failed(_)));
+        if asset_server.is_loaded(&handle) {
+            self.pending_textures.forget(ruby_entity_id);
+            handle
+        } else if failed {
+            self.pending_textures.forget(ruby_entity_id);
+            defaults.map(|t| t.error.clone()).unwrap_or_default()
+        } else {
+            self.pending_textures.wait(ruby_entity_id, texture_path);
+            defaults
+                .map(|t| t.placeholder(self.texture_placeholder))
+                .unwrap_or_default()
+        }
+    }
+
+    /// Swaps the textures that finished loading into the sprites waiting on
+    /// them, or the error texture for failed loads, and returns the paths
+    /// that failed.
+    #[cfg(feature = "rendering")]
+    pub fn resolve_textures(&mut self, world: &mut World) -> Vec<String> {
+        if self.pending_textures.is_empty() {
+            return Vec::new();
+        }
+        let Some(asset_server) = world.get_resource::<AssetServer>() else {
+            return Vec::new();
+        };
+        let resolutions = self.pending_textures.poll(&AssetLoadStates {
+            asset_server,
+            textures: &self.textures,
+        });
+
+        let mut failed = Vec::new();
+        for resolution in resolutions {
+            for ruby_entity_id in resolution.ruby_entity_ids {
+                self.refresh_look(world, ruby_entity_id);
+            }
+            if resolution.state == TextureLoadState::Failed {
+                failed.push(resolution.path);
+            }
+        }
+        failed
+    }
+
+    /// Sets what sprites show while their texture loads; sprites already
+    /// waiting keep the placeholder they were drawn with.
+    #[cfg(feature = "rendering")]
+    pub fn set_texture_placeholder(&mut self, placeholder: TexturePlaceholder) {
+        self.texture_placeholder = placeholder;
+    }
+
+    #[cfg(feature = "rendering")]
+    pub fn texture_placeholder(&self) -> TexturePlaceholder {
+        self.texture_placeholder
+    }
+
+    /// Updates the material of a mesh-mode sprite, if the entity has one.
+    #[cfg(feature = "rendering")]
+    fn with_material(world: &mut World, bevy_entity: Entity, update: impl FnOnce(&mut ColorMaterial)) {
+        let Some(handle) = world
+            .get::<MeshMaterial2d<ColorMaterial>>(bevy_entity)
+            .map(|material| material.0.clone())
+        else {
+            return;
+        };
+        if let Some(mut materials) = world.get_resource_mut::<Assets<ColorMaterial>>()
+            && let Some(material) = materials.get_mut(&handle)
+        {
+            update(material);
+        }
+    }
+
+    /// Writes a resolved look onto a synced Bevy entity.
+    ///
+    /// Mesh-mode sprites take the color and texture; their size and flip are
+    /// applied by `JellyBody::resync`.
+    #[cfg(feature = "rendering")]
+    fn write_look(world: &mut World, bevy_entity: Entity, look: SpriteLook, image: Handle<Image>) {
+        if let Some(mut sprite) = world.get_mut::<Sprite>(bevy_entity) {
+            sprite.color = look.color;
+            sprite.custom_size = look.custom_size;
+            sprite.flip_x = look.flip_x;
+            sprite.flip_y = look.flip_y;
+            sprite.anchor = look.anchor;
+            sprite.rect = look.rect;
+            if sprite.image != image {
+                sprite.image = image;
+            }
+        } else {
+            Self::with_material(world, bevy_entity, |material| {
+                material.color = look.color;
+                if material.texture.as_ref() != Some(&image) {
+                    material.texture = Some(image);
+                }
+            });
+        }
+        if let Some(mut visibility) = world.get_mut::<Visibility>(bevy_entity) {
+            *visibility = look.visibility;
+        }
+    }
+
+    /// Re-applies the look of a synced sprite after its overrides changed.
+    #[cfg(feature = "rendering")]
+    fn refresh_look(&mut self, world: &mut World, ruby_entity_id: u64) {
+        let Some(entity_data) = self.entity_map.get(&ruby_entity_id) else {
+            return;
+        };
+        let bevy_entity = entity_data.bevy_entity;
+        let look = self.look(ruby_entity_id, &entity_data.synced);
+        let image = self.texture(world, ruby_entity_id, look.texture_path.as_deref());
+        Self::write_look(world, bevy_entity, look, image);
+    }
+
+    /// Synchronizes a Ruby sprite to Bevy.
+    #[cfg(feature = "rendering")]
+    pub fn sync_sprite(
+        &mut self,
+        world: &mut World,
+        ruby_entity_id: u64,
+        sprite_data: &SpriteData,
+        transform_data: &TransformData,
+    ) {
+        // Re-syncing a sprite inside its removal window keeps it alive.
+        self.deferred_removals.remove(&ruby_entity_id);
+        self.draw_order.insert(ruby_entity_id, sprite_data.z_index.unwrap_or(0));
+
+        let look = self.look(ruby_entity_id, sprite_data);
+        let image = self.texture(world, ruby_entity_id, look.texture_path.as_deref());
+
         let transform = Transform {
             translation: bevy_math::Vec3::new(
                 transform_data.translation_x,
                 transform_data.translation_y,
-                transform_data.translation_z,
+                layered_z(transform_data.translation_z, sprite_data.z_index)
+                    + self.draw_order.applied_offset(ruby_entity_id),
             ),
             rotation: bevy_math::Quat::from_xyzw(
                 transform_data.rotation_x,
@@ -258,59 +1013,162 @@ impl SpriteSync {
             ),
         };
 
-        if let Some(entity_data) = self.entity_map.get(&ruby_entity_id) {
+        let mode = SpriteRenderMode::of(sprite_data);
+        if self
+            .entity_map
+            .get(&ruby_entity_id)
+            .is_some_and(|entity_data| entity_data.mode != mode)
+            && let Some(entity_data) = self.entity_map.remove(&ruby_entity_id)
+        {
+            self.jelly_sleep.remove(ruby_entity_id);
+            world.despawn(entity_data.bevy_entity);
+        }
+
+        if let Some(entity_data) = self.entity_map.get_mut(&ruby_entity_id) {
             // Update existing Bevy entity
             let bevy_entity = entity_data.bevy_entity;
-
-            if let Some(mut sprite) = world.get_mut::<Sprite>(bevy_entity) {
-                sprite.color = color;
-                sprite.custom_size = custom_size;
-                sprite.flip_x = sprite_data.flip_x;
-                sprite.flip_y = sprite_data.flip_y;
+            entity_data.group.clone_from(&sprite_data.group);
+            entity_data.synced = sprite_data.clone();
+            if let Some(body) = entity_data.jelly.as_mut()
+                && body.resync(world, &look, sprite_data.jelly)
+            {
+                self.jelly_sleep.wake(ruby_entity_id);
             }
 
+            Self::write_look(world, bevy_entity, look, image);
+
             if let Some(mut t) = world.get_mut::<Transform>(bevy_entity) {
                 *t = transform;
             }
+
+            sync_accessibility_node(world, bevy_entity, sprite_data.accessibility.as_ref());
         } else {
-            // Spawn new Bevy render entity with default white texture
-            let texture_handle = world
-                .get_resource::<DefaultSpriteTexture>()
-                .map(|t| t.handle.clone());
-
-            let bevy_entity = world
-                .spawn((
-                    Sprite {
-                        color,
-                        custom_size,
-                        flip_x: sprite_data.flip_x,
-                        flip_y: sprite_data.flip_y,
-                        image: texture_handle.clone().unwrap_or_default(),
-                        ..Default::default()
-                    },
-                    transform,
-                ))
-                .id();
+            // Spawn new Bevy render entity with its texture or the default white one
+            let (bevy_entity, jelly) = match mode {
+                SpriteRenderMode::Sprite => {
+                    let bevy_entity = world
+                        .spawn((
+                            Sprite {
+                                color: look.color,
+                                custom_size: look.custom_size,
+                                flip_x: look.flip_x,
+                                flip_y: look.flip_y,
+                                anchor: look.anchor,
+                                image,
+                                rect: look.rect,
+                                ..Default::default()
+                            },
+                            transform,
+                            look.visibility,
+                        ))
+                        .id();
+                    (bevy_entity, None)
+                }
+                SpriteRenderMode::Mesh => {
+                    let body = JellyBody::new(world, &look, image, sprite_data.jelly);
+                    let bevy_entity = world
+                        .spawn((
+                            Mesh2d(body.mesh.clone()),
+                            MeshMaterial2d(body.material.clone()),
+                            transform,
+                            look.visibility,
+                        ))
+                        .id();
+                    (bevy_entity, Some(body))
+                }
+            };
+            sync_accessibility_node(world, bevy_entity, sprite_data.accessibility.as_ref());
 
+            if jelly.is_some() {
+                self.jelly_sleep.insert(ruby_entity_id);
+            }
             self.entity_map.insert(
                 ruby_entity_id,
-                EntityData { bevy_entity },
+                EntityData {
+                    bevy_entity,
+                    mode,
+                    jelly,
+                    group: sprite_data.group.clone(),
+                    synced: sprite_data.clone(),
+                },
             );
         }
     }
 
+    /// Sets a color override and applies it to the synced sprite, if any.
+    #[cfg(feature = "rendering")]
+    pub fn set_color(&mut self, world: &mut World, ruby_entity_id: u64, color: [f32; 4]) {
+        self.color_overrides.insert(ruby_entity_id, color);
+        let Some(entity_data) = self.entity_map.get(&ruby_entity_id) else {
+            return;
+        };
+        let [r, g, b, a] = color;
+        let color = Color::srgba(r, g, b, a);
+        if let Some(mut sprite) = world.get_mut::<Sprite>(entity_data.bevy_entity) {
+            sprite.color = color;
+        } else {
+            Self::with_material(world, entity_data.bevy_entity, |material| material.color = color);
+        }
+    }
+
+    /// Advances the vertex springs of every awake mesh-mode sprite and
+    /// writes the new positions into their meshes. Springs that barely move
+    /// for `sleep.frames` frames fall asleep where they are until an impulse
+    /// or a resize wakes them.
+    #[cfg(feature = "rendering")]
+    pub fn step_jelly(&mut self, world: &mut World, delta: f32, sleep: &SleepConfig) {
+        for ruby_entity_id in self.jelly_sleep.awake_ids() {
+            let Some(body) = self
+                .entity_map
+                .get_mut(&ruby_entity_id)
+                .and_then(|entity_data| entity_data.jelly.as_mut())
+            else {
+                self.jelly_sleep.remove(ruby_entity_id);
+                continue;
+            };
+            let motion = if body.sim.is_resting() {
+                0.0
+            } else {
+                body.sim.step(delta);
+                body.write_positions(world);
+                body.sim.max_speed() * delta
+            };
+            self.jelly_sleep.observe(ruby_entity_id, motion, sleep);
+        }
+    }
+
+    /// Wakes every sleeping mesh-mode sprite.
+    pub fn wake_all_jelly(&mut self) -> usize {
+        self.jelly_sleep.wake_all()
+    }
+
+    /// Returns the number of mesh-mode sprites whose springs are asleep.
+    pub fn sleeping_jelly_count(&self) -> usize {
+        self.jelly_sleep.sleeping_count()
+    }
+
     /// Removes a sprite from Bevy.
     #[cfg(feature = "rendering")]
     pub fn remove_sprite(&mut self, world: &mut World, ruby_entity_id: u64) {
+        self.color_overrides.remove(&ruby_entity_id);
+        self.deferred_removals.remove(&ruby_entity_id);
+        self.pending_textures.forget(ruby_entity_id);
+        self.jelly_sleep.remove(ruby_entity_id);
+        self.draw_order.remove(ruby_entity_id);
         if let Some(entity_data) = self.entity_map.remove(&ruby_entity_id) {
             world.despawn(entity_data.bevy_entity);
         }
     }
 
-    /// Clears all sprites and removes them from Bevy.
+    /// Clears all sprites and removes them from Bevy, including deferred ones.
     #[cfg(feature = "rendering")]
     pub fn clear(&mut self, world: &mut World) {
-        for (_, entity_data) in self.entity_map.drain() {
+        self.color_overrides.clear();
+        self.deferred_removals.clear();
+        self.pending_textures.clear();
+        self.jelly_sleep.clear();
+        self.draw_order.clear();
+        for (_, entity_data) in std::mem::take(&mut self.entity_map) {
             world.despawn(entity_data.bevy_entity);
         }
     }
@@ -325,11 +1183,82 @@ impl SpriteSync {
         self.entity_map.is_empty()
     }
 
+    /// Layer and fine order of the synced sprites.
+    pub fn draw_order(&self) -> &DrawOrder {
+        &self.draw_order
+    }
+
     /// Returns all Ruby entity IDs that are currently synced.
     pub fn synced_entities(&self) -> Vec<u64> {
         self.entity_map.keys().copied().collect()
     }
 
+    /// Returns the Bevy render entity for a synced Ruby entity ID.
+    #[cfg(feature = "rendering")]
+    pub fn bevy_entity(&self, ruby_entity_id: u64) -> Option<Entity> {
+        self.entity_map
+            .get(&ruby_entity_id)
+            .map(|entity_data| entity_data.bevy_entity)
+    }
+
+    /// Returns the Ruby entity ID, Bevy entity and group of every synced sprite.
+    #[cfg(feature = "rendering")]
+    pub fn synced_sprites(&self) -> impl Iterator<Item = (u64, Entity, Option<&str>)> + '_ {
+        self.entity_map.iter().map(|(ruby_entity_id, entity_data)| {
+            (*ruby_entity_id, entity_data.bevy_entity, entity_data.group.as_deref())
+        })
+    }
+
+    /// Captures the applied transform of every synced sprite, ordered by Ruby entity ID.
+    #[cfg(feature = "rendering")]
+    pub fn capture_transforms(&self, world: &World) -> Vec<SpriteTransformSample> {
+        let mut samples: Vec<SpriteTransformSample> = self
+            .entity_map
+            .iter()
+            .filter_map(|(ruby_entity_id, entity_data)| {
+                let transform = world.get::<Transform>(entity_data.bevy_entity)?;
+                let (width, height) = Self::drawn_size(world, entity_data);
+                let (anchor_x, anchor_y) = match entity_data.mode {
+                    SpriteRenderMode::Sprite => (entity_data.synced.anchor_x, entity_data.synced.anchor_y),
+                    SpriteRenderMode::Mesh => (0.5, 0.5),
+                };
+                Some(SpriteTransformSample {
+                    ruby_entity_id: *ruby_entity_id,
+                    x: transform.translation.x,
+                    y: transform.translation.y,
+                    rotation: transform.rotation.to_euler(bevy_math::EulerRot::ZYX).0,
+                    width: width * transform.scale.x.abs(),
+                    height: height * transform.scale.y.abs(),
+                    anchor_x,
+                    anchor_y,
+                    group: entity_data.group.clone(),
+                })
+            })
+            .collect();
+        samples.sort_unstable_by_key(|sample| sample.ruby_entity_id);
+        samples
+    }
+
+    /// Unscaled size a sprite is drawn at: its custom size, else its loaded
+    /// texture's size, else 1×1 (the default texture, or a mesh without a size).
+    #[cfg(feature = "rendering")]
+    fn drawn_size(world: &World, entity_data: &EntityData) -> (f32, f32) {
+        if entity_data.synced.has_custom_size {
+            return (entity_data.synced.custom_size_x, entity_data.synced.custom_size_y);
+        }
+        if let Some((_, _, width, height)) = entity_data.synced.atlas_rect {
+            return (width, height);
+        }
+        world
+            .get::<Sprite>(entity_data.bevy_entity)
+            .and_then(|sprite| world.get_resource::<Assets<Image>>()?.get(&sprite.image))
+            .map(|image| {
+                let size = image.size_f32();
+                (size.x, size.y)
+            })
+            .unwrap_or((1.0, 1.0))
+    }
+
     // No-op implementations for non-rendering builds
     #[cfg(not(feature = "rendering"))]
     pub fn sync_sprite(
@@ -341,6 +1270,9 @@ impl SpriteSync {
     ) {
     }
 
+    #[cfg(not(feature = "rendering"))]
+    pub fn set_color(&mut self, _world: &mut (), _ruby_entity_id: u64, _color: [f32; 4]) {}
+
     #[cfg(not(feature = "rendering"))]
     pub fn remove_sprite(&mut self, _world: &mut (), _ruby_entity_id: u64) {}
 
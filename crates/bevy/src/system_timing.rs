@@ -0,0 +1,122 @@
+//! Opt-in timing of the systems the render bridge owns.
+//!
+//! While enabled, each bridge system records how long it ran into the current
+//! frame. `end_frame` closes the frame: its timings become the reported ones
+//! and join a rolling window used for per-system maxima. While disabled no
+//! `Instant` is taken and nothing is stored.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Instant;
+
+/// Frames covered by the rolling per-system maximum.
+pub const SYSTEM_TIMING_WINDOW: usize = 120;
+
+/// Per-system run times in microseconds.
+#[derive(Debug, Clone)]
+pub struct SystemTimings {
+    enabled: bool,
+    window: usize,
+    current: BTreeMap<String, u64>,
+    last_frame: BTreeMap<String, u64>,
+    /// One entry per closed frame; `None` when the system didn't run.
+    history: BTreeMap<String, VecDeque<Option<u64>>>,
+}
+
+impl Default for SystemTimings {
+    fn default() -> Self {
+        Self::new(SYSTEM_TIMING_WINDOW)
+    }
+}
+
+impl SystemTimings {
+    /// Disabled timings keeping maxima over `window` frames.
+    pub fn new(window: usize) -> Self {
+        Self {
+            enabled: false,
+            window: window.max(1),
+            current: BTreeMap::new(),
+            last_frame: BTreeMap::new(),
+            history: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Turns timing on or off; turning it off drops everything recorded.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.current.clear();
+            self.last_frame.clear();
+            self.history.clear();
+        }
+    }
+
+    /// Start of a timed run, or `None` while disabled.
+    pub fn start(&self) -> Option<Instant> {
+        self.enabled.then(Instant::now)
+    }
+
+    /// Records the run begun by `start` under `name`.
+    pub fn finish(&mut self, name: &str, started: Option<Instant>) {
+        if let Some(started) = started {
+            self.record(name, started.elapsed().as_micros() as u64);
+        }
+    }
+
+    /// Adds `micros` to `name` in the current frame; ignored while disabled.
+    pub fn record(&mut self, name: &str, micros: u64) {
+        if !self.enabled {
+            return;
+        }
+        match self.current.get_mut(name) {
+            Some(total) => *total += micros,
+            None => {
+                self.current.insert(name.to_string(), micros);
+            }
+        }
+    }
+
+    /// Closes the current frame. Systems that ran in none of the last
+    /// `window` frames are forgotten.
+    pub fn end_frame(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        for name in self.current.keys() {
+            if !self.history.contains_key(name) {
+                self.history.insert(name.clone(), VecDeque::new());
+            }
+        }
+        for (name, samples) in self.history.iter_mut() {
+            samples.push_back(self.current.get(name).copied());
+            while samples.len() > self.window {
+                samples.pop_front();
+            }
+        }
+        self.history
+            .retain(|_, samples| samples.iter().any(Option::is_some));
+        self.last_frame = std::mem::take(&mut self.current);
+    }
+
+    /// Timings of the last closed frame.
+    pub fn frame(&self) -> &BTreeMap<String, u64> {
+        &self.last_frame
+    }
+
+    /// Longest run of each system over the window.
+    pub fn max(&self) -> BTreeMap<String, u64> {
+        self.history
+            .iter()
+            .filter_map(|(name, samples)| {
+                samples
+                    .iter()
+                    .flatten()
+                    .max()
+                    .map(|max| (name.clone(), *max))
+            })
+            .collect()
+    }
+}
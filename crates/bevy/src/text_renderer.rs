@@ -1,6 +1,9 @@
 //! Text renderer module for synchronizing Ruby text entities with Bevy.
 
-use std::collections::HashMap;
+use crate::accessibility::AccessibilityData;
+use crate::determinism::AuditMap;
+#[cfg(feature = "rendering")]
+use crate::accessibility::sync_accessibility_node;
 
 #[cfg(feature = "rendering")]
 use bevy_color::Color;
@@ -9,13 +12,176 @@ use bevy_ecs::entity::Entity;
 #[cfg(feature = "rendering")]
 use bevy_ecs::world::World;
 #[cfg(feature = "rendering")]
+use bevy_hierarchy::BuildChildren;
+#[cfg(feature = "rendering")]
 use bevy_render::view::{InheritedVisibility, ViewVisibility, Visibility};
 #[cfg(feature = "rendering")]
-use bevy_text::{Text2d, TextColor, TextFont};
+use bevy_text::{JustifyText, LineBreak, Text2d, TextColor, TextFont, TextLayout};
 #[cfg(feature = "rendering")]
 use bevy_transform::components::{GlobalTransform, Transform};
 
-#[derive(Debug, Clone)]
+/// Whether the pinned `bevy_text` lets synced text change its blend state.
+///
+/// Text glyphs go through the shared sprite pipeline, which always uses alpha
+/// blending, so `TextBlend::Add` is accepted but rendered as `Alpha`.
+pub const TEXT_BLEND_SUPPORTED: bool = false;
+
+/// Number of glow copies per ring, spread evenly around the text.
+pub const GLOW_DIRECTIONS: usize = 8;
+
+/// Upper bound on glow rings, so a large radius never spawns unbounded copies.
+pub const MAX_GLOW_RINGS: usize = 4;
+
+/// Distance in pixels between glow rings.
+const GLOW_RING_SPACING: f32 = 2.0;
+
+/// Z offset of glow copies, behind the main text.
+#[cfg(feature = "rendering")]
+const GLOW_Z_OFFSET: f32 = -0.01;
+
+/// Blend state requested for a synced text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextBlend {
+    #[default]
+    Alpha,
+    Add,
+}
+
+impl TextBlend {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "alpha" | "normal" => Some(TextBlend::Alpha),
+            "add" | "additive" => Some(TextBlend::Add),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TextBlend::Alpha => "alpha",
+            TextBlend::Add => "add",
+        }
+    }
+}
+
+/// Horizontal alignment of the lines of a synced text.
+///
+/// The text block stays centered on its translation; this only aligns its
+/// lines against each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextJustify {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl TextJustify {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "left" => Some(TextJustify::Left),
+            "center" => Some(TextJustify::Center),
+            "right" => Some(TextJustify::Right),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TextJustify::Left => "left",
+            TextJustify::Center => "center",
+            TextJustify::Right => "right",
+        }
+    }
+}
+
+/// Where the lines of a synced text may break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextLineBreak {
+    /// At word boundaries, and at newlines.
+    #[default]
+    Word,
+    /// Between any two characters, and at newlines.
+    Char,
+    /// Only at newlines.
+    None,
+}
+
+impl TextLineBreak {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "word" => Some(TextLineBreak::Word),
+            "char" => Some(TextLineBreak::Char),
+            "none" => Some(TextLineBreak::None),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TextLineBreak::Word => "word",
+            TextLineBreak::Char => "char",
+            TextLineBreak::None => "none",
+        }
+    }
+}
+
+/// Radial glow drawn as offset copies of the text behind it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextGlow {
+    pub color_r: f32,
+    pub color_g: f32,
+    pub color_b: f32,
+    pub color_a: f32,
+    /// Outer radius in pixels.
+    pub radius: f32,
+    /// Overall opacity of the glow; 1.0 makes the innermost ring roughly opaque.
+    pub intensity: f32,
+}
+
+/// One glow copy: its offset from the text and its alpha.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlowCopy {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub alpha: f32,
+}
+
+impl TextGlow {
+    /// Number of rings for the radius: one per `GLOW_RING_SPACING` pixels, up to `MAX_GLOW_RINGS`.
+    pub fn rings(&self) -> usize {
+        if !(self.radius.is_finite() && self.intensity.is_finite())
+            || self.radius <= 0.0
+            || self.intensity <= 0.0
+        {
+            return 0;
+        }
+        ((self.radius / GLOW_RING_SPACING).ceil() as usize).clamp(1, MAX_GLOW_RINGS)
+    }
+
+    /// Copies ordered from the innermost ring outwards, with alpha falling off linearly.
+    pub fn copies(&self) -> Vec<GlowCopy> {
+        let rings = self.rings();
+        let mut copies = Vec::with_capacity(rings * GLOW_DIRECTIONS);
+        for ring in 1..=rings {
+            let distance = self.radius * ring as f32 / rings as f32;
+            let falloff = 1.0 - (ring - 1) as f32 / rings as f32;
+            let alpha = (self.color_a * self.intensity * falloff / GLOW_DIRECTIONS as f32 * 2.0)
+                .clamp(0.0, 1.0);
+            for direction in 0..GLOW_DIRECTIONS {
+                let angle = std::f32::consts::TAU * direction as f32 / GLOW_DIRECTIONS as f32;
+                copies.push(GlowCopy {
+                    offset_x: distance * angle.cos(),
+                    offset_y: distance * angle.sin(),
+                    alpha,
+                });
+            }
+        }
+        copies
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct TextData {
     pub content: String,
     pub font_size: f32,
@@ -23,6 +189,11 @@ pub struct TextData {
     pub color_g: f32,
     pub color_b: f32,
     pub color_a: f32,
+    pub accessibility: Option<AccessibilityData>,
+    pub glow: Option<TextGlow>,
+    pub blend: TextBlend,
+    pub justify: TextJustify,
+    pub linebreak: TextLineBreak,
 }
 
 impl Default for TextData {
@@ -34,10 +205,47 @@ impl Default for TextData {
             color_g: 1.0,
             color_b: 1.0,
             color_a: 1.0,
+            accessibility: None,
+            glow: None,
+            blend: TextBlend::Alpha,
+            justify: TextJustify::Left,
+            linebreak: TextLineBreak::Word,
         }
     }
 }
 
+/// Which parts of a synced text differ from what was last applied.
+///
+/// Only changed parts are written, so unchanged text and its glow copies are
+/// not laid out again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextChanges {
+    pub content: bool,
+    pub font_size: bool,
+    pub color: bool,
+    pub glow: bool,
+    /// Justification or line breaking.
+    pub alignment: bool,
+}
+
+impl TextChanges {
+    pub fn between(previous: &TextData, next: &TextData) -> Self {
+        Self {
+            content: previous.content != next.content,
+            font_size: previous.font_size != next.font_size,
+            color: (previous.color_r, previous.color_g, previous.color_b, previous.color_a)
+                != (next.color_r, next.color_g, next.color_b, next.color_a),
+            glow: previous.glow != next.glow,
+            alignment: (previous.justify, previous.linebreak) != (next.justify, next.linebreak),
+        }
+    }
+
+    /// True if the text needs a new layout.
+    pub fn layout(&self) -> bool {
+        self.content || self.font_size || self.alignment
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TextTransformData {
     pub translation_x: f32,
@@ -75,13 +283,18 @@ pub enum TextOperation {
 }
 
 pub struct TextSync {
-    entity_map: HashMap<u64, TextEntityData>,
+    entity_map: AuditMap<u64, TextEntityData>,
     pub pending_operations: Vec<TextOperation>,
 }
 
 struct TextEntityData {
     #[cfg(feature = "rendering")]
     bevy_entity: Entity,
+    /// Glow copies, children of the text, in `TextGlow::copies` order.
+    #[cfg(feature = "rendering")]
+    glow_entities: Vec<Entity>,
+    #[cfg(feature = "rendering")]
+    applied: TextData,
     #[cfg(not(feature = "rendering"))]
     _phantom: (),
 }
@@ -89,7 +302,7 @@ struct TextEntityData {
 impl TextSync {
     pub fn new() -> Self {
         Self {
-            entity_map: HashMap::new(),
+            entity_map: AuditMap::new(),
             pending_operations: Vec::new(),
         }
     }
@@ -172,24 +385,32 @@ impl TextSync {
             ),
         };
 
-        if let Some(entity_data) = self.entity_map.get(&ruby_entity_id) {
+        if let Some(entity_data) = self.entity_map.get_mut(&ruby_entity_id) {
             let bevy_entity = entity_data.bevy_entity;
+            let changes = TextChanges::between(&entity_data.applied, text_data);
 
-            if let Some(mut text) = world.get_mut::<Text2d>(bevy_entity) {
-                **text = text_data.content.clone();
+            if changes.layout() {
+                for entity in std::iter::once(bevy_entity).chain(entity_data.glow_entities.iter().copied()) {
+                    write_layout(world, entity, text_data);
+                }
             }
 
-            if let Some(mut text_color) = world.get_mut::<TextColor>(bevy_entity) {
+            if changes.color
+                && let Some(mut text_color) = world.get_mut::<TextColor>(bevy_entity)
+            {
                 text_color.0 = color;
             }
 
-            if let Some(mut font) = world.get_mut::<TextFont>(bevy_entity) {
-                font.font_size = text_data.font_size;
-            }
-
             if let Some(mut t) = world.get_mut::<Transform>(bevy_entity) {
                 *t = transform;
             }
+
+            if changes.glow {
+                sync_glow(world, bevy_entity, &mut entity_data.glow_entities, text_data);
+            }
+
+            sync_accessibility_node(world, bevy_entity, text_data.accessibility.as_ref());
+            entity_data.applied = text_data.clone();
         } else {
             let bevy_entity = world
                 .spawn((
@@ -198,6 +419,7 @@ impl TextSync {
                         font_size: text_data.font_size,
                         ..Default::default()
                     },
+                    text_layout(text_data),
                     TextColor(color),
                     transform,
                     GlobalTransform::default(),
@@ -206,26 +428,44 @@ impl TextSync {
                     ViewVisibility::default(),
                 ))
                 .id();
+            sync_accessibility_node(world, bevy_entity, text_data.accessibility.as_ref());
+
+            let mut glow_entities = Vec::new();
+            sync_glow(world, bevy_entity, &mut glow_entities, text_data);
 
-            self.entity_map
-                .insert(ruby_entity_id, TextEntityData { bevy_entity });
+            self.entity_map.insert(
+                ruby_entity_id,
+                TextEntityData {
+                    bevy_entity,
+                    glow_entities,
+                    applied: text_data.clone(),
+                },
+            );
         }
     }
 
     #[cfg(feature = "rendering")]
     pub fn remove_text(&mut self, world: &mut World, ruby_entity_id: u64) {
         if let Some(entity_data) = self.entity_map.remove(&ruby_entity_id) {
-            world.despawn(entity_data.bevy_entity);
+            despawn_text_entity(world, entity_data);
         }
     }
 
     #[cfg(feature = "rendering")]
     pub fn clear(&mut self, world: &mut World) {
-        for (_, entity_data) in self.entity_map.drain() {
-            world.despawn(entity_data.bevy_entity);
+        for (_, entity_data) in std::mem::take(&mut self.entity_map) {
+            despawn_text_entity(world, entity_data);
         }
     }
 
+    /// Number of glow copies currently spawned for a text.
+    #[cfg(feature = "rendering")]
+    pub fn glow_copy_count(&self, ruby_entity_id: u64) -> usize {
+        self.entity_map
+            .get(&ruby_entity_id)
+            .map_or(0, |entity_data| entity_data.glow_entities.len())
+    }
+
     pub fn len(&self) -> usize {
         self.entity_map.len()
     }
@@ -234,6 +474,21 @@ impl TextSync {
         self.entity_map.is_empty()
     }
 
+    #[cfg(feature = "rendering")]
+    pub fn bevy_entity(&self, ruby_entity_id: u64) -> Option<Entity> {
+        self.entity_map
+            .get(&ruby_entity_id)
+            .map(|entity_data| entity_data.bevy_entity)
+    }
+
+    /// Returns the Ruby entity ID and Bevy entity of every synced text.
+    #[cfg(feature = "rendering")]
+    pub fn synced_texts(&self) -> impl Iterator<Item = (u64, Entity)> + '_ {
+        self.entity_map
+            .iter()
+            .map(|(ruby_entity_id, entity_data)| (*ruby_entity_id, entity_data.bevy_entity))
+    }
+
     #[cfg(not(feature = "rendering"))]
     pub fn sync_text(
         &mut self,
@@ -251,6 +506,87 @@ impl TextSync {
     pub fn clear(&mut self, _world: &mut ()) {}
 }
 
+#[cfg(feature = "rendering")]
+fn write_layout(world: &mut World, entity: Entity, text_data: &TextData) {
+    if let Some(mut text) = world.get_mut::<Text2d>(entity)
+        && **text != text_data.content
+    {
+        **text = text_data.content.clone();
+    }
+    if let Some(mut font) = world.get_mut::<TextFont>(entity)
+        && font.font_size != text_data.font_size
+    {
+        font.font_size = text_data.font_size;
+    }
+    let layout = text_layout(text_data);
+    if let Some(mut current) = world.get_mut::<TextLayout>(entity)
+        && (current.justify, current.linebreak) != (layout.justify, layout.linebreak)
+    {
+        *current = layout;
+    }
+}
+
+#[cfg(feature = "rendering")]
+fn text_layout(text_data: &TextData) -> TextLayout {
+    let justify = match text_data.justify {
+        TextJustify::Left => JustifyText::Left,
+        TextJustify::Center => JustifyText::Center,
+        TextJustify::Right => JustifyText::Right,
+    };
+    let linebreak = match text_data.linebreak {
+        TextLineBreak::Word => LineBreak::WordBoundary,
+        TextLineBreak::Char => LineBreak::AnyCharacter,
+        TextLineBreak::None => LineBreak::NoWrap,
+    };
+    TextLayout::new(justify, linebreak)
+}
+
+/// Grows or shrinks the glow copies to match the glow, reusing existing copies.
+#[cfg(feature = "rendering")]
+fn sync_glow(world: &mut World, parent: Entity, glow_entities: &mut Vec<Entity>, text_data: &TextData) {
+    let copies = text_data.glow.map(|glow| glow.copies()).unwrap_or_default();
+
+    for entity in glow_entities.drain(copies.len().min(glow_entities.len())..) {
+        world.despawn(entity);
+    }
+    while glow_entities.len() < copies.len() {
+        let entity = world
+            .spawn((
+                Text2d::new(text_data.content.clone()),
+                TextFont {
+                    font_size: text_data.font_size,
+                    ..Default::default()
+                },
+                text_layout(text_data),
+                TextColor(Color::NONE),
+                Transform::default(),
+            ))
+            .id();
+        world.entity_mut(parent).add_child(entity);
+        glow_entities.push(entity);
+    }
+
+    let Some(glow) = text_data.glow else {
+        return;
+    };
+    for (entity, copy) in glow_entities.iter().zip(&copies) {
+        if let Some(mut text_color) = world.get_mut::<TextColor>(*entity) {
+            text_color.0 = Color::srgba(glow.color_r, glow.color_g, glow.color_b, copy.alpha);
+        }
+        if let Some(mut transform) = world.get_mut::<Transform>(*entity) {
+            *transform = Transform::from_xyz(copy.offset_x, copy.offset_y, GLOW_Z_OFFSET);
+        }
+    }
+}
+
+#[cfg(feature = "rendering")]
+fn despawn_text_entity(world: &mut World, entity_data: TextEntityData) {
+    for entity in entity_data.glow_entities {
+        world.despawn(entity);
+    }
+    world.despawn(entity_data.bevy_entity);
+}
+
 impl Default for TextSync {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,166 @@
+//! Texture loading module for sprites whose image is still decoding.
+//!
+//! The asset server decodes images on its task pool, so a sprite synced with
+//! a texture that has not finished loading is drawn with a placeholder
+//! instead of waiting for it. The sprites waiting on each path are tracked
+//! here; once the path's load settles they are handed back so the real
+//! texture, or the error texture for a failed load, can be swapped in
+//! without Ruby syncing them again.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Names accepted by `TexturePlaceholder::from_name`.
+pub const TEXTURE_PLACEHOLDER_NAMES: [&str; 3] = ["white", "checkerboard", "transparent"];
+
+/// What a sprite shows while its texture loads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TexturePlaceholder {
+    /// The default white texture, tinted by the sprite color.
+    #[default]
+    White,
+    /// Grey and white squares, for spotting missing art during development.
+    Checkerboard,
+    /// Nothing is drawn until the texture arrives.
+    Transparent,
+}
+
+impl TexturePlaceholder {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "white" => Some(Self::White),
+            "checkerboard" => Some(Self::Checkerboard),
+            "transparent" => Some(Self::Transparent),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::White => "white",
+            Self::Checkerboard => "checkerboard",
+            Self::Transparent => "transparent",
+        }
+    }
+}
+
+/// Load progress of one texture path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureLoadState {
+    Loading,
+    Loaded,
+    Failed,
+}
+
+/// Reports how far the load of a texture path has got; the asset server in
+/// a running app.
+pub trait TextureLoadSource {
+    fn load_state(&self, path: &str) -> TextureLoadState;
+}
+
+/// A path whose load settled, with the sprites that were waiting on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextureResolution {
+    pub path: String,
+    /// `Loaded` or `Failed`.
+    pub state: TextureLoadState,
+    /// Waiting sprites, in ID order.
+    pub ruby_entity_ids: Vec<u64>,
+}
+
+/// Sprites drawn with a placeholder, grouped by the texture path they wait on.
+#[derive(Debug, Clone, Default)]
+pub struct PendingTextures {
+    waiting: BTreeMap<String, BTreeSet<u64>>,
+    /// Path each waiting sprite is on, so a re-sync or removal finds it.
+    paths: BTreeMap<u64, String>,
+    /// Paths whose load failed; sprites using them get the error texture.
+    failed: BTreeSet<String>,
+}
+
+impl PendingTextures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that a sprite is waiting on `path`, replacing any path it
+    /// waited on before.
+    pub fn wait(&mut self, ruby_entity_id: u64, path: &str) {
+        if self.paths.get(&ruby_entity_id).is_some_and(|waiting_on| waiting_on == path) {
+            return;
+        }
+        self.forget(ruby_entity_id);
+        self.waiting
+            .entry(path.to_owned())
+            .or_default()
+            .insert(ruby_entity_id);
+        self.paths.insert(ruby_entity_id, path.to_owned());
+    }
+
+    /// Stops waiting for a sprite that was removed or no longer needs its
+    /// texture; false if it was not waiting.
+    pub fn forget(&mut self, ruby_entity_id: u64) -> bool {
+        let Some(path) = self.paths.remove(&ruby_entity_id) else {
+            return false;
+        };
+        if let Some(ids) = self.waiting.get_mut(&path) {
+            ids.remove(&ruby_entity_id);
+            if ids.is_empty() {
+                self.waiting.remove(&path);
+            }
+        }
+        true
+    }
+
+    /// Path the sprite is waiting on, if any.
+    pub fn waiting_on(&self, ruby_entity_id: u64) -> Option<&str> {
+        self.paths.get(&ruby_entity_id).map(String::as_str)
+    }
+
+    pub fn is_failed(&self, path: &str) -> bool {
+        self.failed.contains(path)
+    }
+
+    /// Number of paths still loading.
+    pub fn len(&self) -> usize {
+        self.waiting.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.waiting.is_empty()
+    }
+
+    /// Asks `source` about every waiting path and returns the ones that
+    /// settled, in path order; their sprites stop waiting.
+    pub fn poll(&mut self, source: &impl TextureLoadSource) -> Vec<TextureResolution> {
+        let settled: Vec<(String, TextureLoadState)> = self
+            .waiting
+            .keys()
+            .map(|path| (path.clone(), source.load_state(path)))
+            .filter(|(_, state)| *state != TextureLoadState::Loading)
+            .collect();
+
+        settled
+            .into_iter()
+            .map(|(path, state)| {
+                let ids = self.waiting.remove(&path).unwrap_or_default();
+                for ruby_entity_id in &ids {
+                    self.paths.remove(ruby_entity_id);
+                }
+                if state == TextureLoadState::Failed {
+                    self.failed.insert(path.clone());
+                }
+                TextureResolution {
+                    path,
+                    state,
+                    ruby_entity_ids: ids.into_iter().collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// Forgets every waiting sprite; failed paths are kept.
+    pub fn clear(&mut self) {
+        self.waiting.clear();
+        self.paths.clear();
+    }
+}
@@ -1,4 +1,4 @@
-use bevy_color::{Alpha, Color, Srgba};
+use bevy_color::{Alpha, Color, Hsla, LinearRgba, Mix, Srgba};
 
 #[derive(Debug, Clone, Copy)]
 pub struct RubyColor {
@@ -24,6 +24,13 @@ impl RubyColor {
         Srgba::hex(hex).ok().map(|c| Self { inner: c })
     }
 
+    /// Opaque color from hue in degrees (wrapped to 0..360) and saturation
+    /// and lightness in 0..1 (clamped).
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        let hsla = Hsla::new(h.rem_euclid(360.0), s.clamp(0.0, 1.0), l.clamp(0.0, 1.0), 1.0);
+        Self { inner: hsla.into() }
+    }
+
     pub fn white() -> Self {
         Self::new(1.0, 1.0, 1.0, 1.0)
     }
@@ -86,6 +93,14 @@ impl RubyColor {
         }
     }
 
+    /// Blends toward `other` in linear space; `t` is clamped to 0..1 and
+    /// alpha is interpolated along with the color channels.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let mixed = LinearRgba::from(self.inner).mix(&LinearRgba::from(other.inner), t);
+        Self { inner: mixed.into() }
+    }
+
     pub fn to_bevy(&self) -> Color {
         Color::Srgba(self.inner)
     }
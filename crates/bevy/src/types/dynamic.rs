@@ -1,5 +1,20 @@
+//! Dynamic component data stored for Ruby components.
+//!
+//! `content_hash` values are stable across runs, platforms and builds: data
+//! is fed to FNV-1a 64 (`FrameChecksum`) as explicitly little-endian bytes in
+//! a canonical order. Each value starts with a tag byte: nil 0, boolean 1
+//! (then one byte), integer 2 (then 8 bytes), float 3 (then the 8 bytes of
+//! its bit pattern, so `0.0` and `-0.0` differ), string 4 and symbol 5 (then
+//! the byte length as 8 bytes and the UTF-8 bytes), array 6 (then the length
+//! and each item) and hash 7 (then the length and each key and value, sorted
+//! by key). A component hashes its type name followed by its fields as a
+//! hash without the tag.
+
 use bevy_ecs::component::Component;
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::hash::FrameChecksum;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DynamicValue {
@@ -43,12 +58,64 @@ impl DynamicValue {
             _ => None,
         }
     }
+
+    /// Feeds the value to `hasher` in the canonical encoding described above.
+    pub fn write_content(&self, hasher: &mut FrameChecksum) {
+        match self {
+            DynamicValue::Nil => hasher.write_u8(0),
+            DynamicValue::Boolean(v) => {
+                hasher.write_u8(1);
+                hasher.write_bool(*v);
+            }
+            DynamicValue::Integer(v) => {
+                hasher.write_u8(2);
+                hasher.write_bytes(&v.to_le_bytes());
+            }
+            DynamicValue::Float(v) => {
+                hasher.write_u8(3);
+                hasher.write_u64(v.to_bits());
+            }
+            DynamicValue::String(v) => {
+                hasher.write_u8(4);
+                hasher.write_str(v);
+            }
+            DynamicValue::Symbol(v) => {
+                hasher.write_u8(5);
+                hasher.write_str(v);
+            }
+            DynamicValue::Array(items) => {
+                hasher.write_u8(6);
+                hasher.write_u64(items.len() as u64);
+                for item in items {
+                    item.write_content(hasher);
+                }
+            }
+            DynamicValue::Hash(entries) => {
+                hasher.write_u8(7);
+                write_fields(hasher, entries);
+            }
+        }
+    }
+}
+
+/// Writes the entry count, then each key and value sorted by key.
+fn write_fields(hasher: &mut FrameChecksum, fields: &HashMap<String, DynamicValue>) {
+    let mut entries: Vec<_> = fields.iter().collect();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+    hasher.write_u64(entries.len() as u64);
+    for (key, value) in entries {
+        hasher.write_str(key);
+        value.write_content(hasher);
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct DynamicComponent {
     pub type_name: String,
     pub data: HashMap<String, DynamicValue>,
+    /// Bumped by every `set`, and moved past the world's clock whenever a
+    /// `WorldWrapper` stores the component; not part of `content_hash`.
+    pub generation: u64,
 }
 
 impl DynamicComponent {
@@ -56,6 +123,7 @@ impl DynamicComponent {
         Self {
             type_name: type_name.to_string(),
             data: HashMap::new(),
+            generation: 0,
         }
     }
 
@@ -66,6 +134,7 @@ impl DynamicComponent {
 
     pub fn set(&mut self, name: &str, value: DynamicValue) {
         self.data.insert(name.to_string(), value);
+        self.generation += 1;
     }
 
     pub fn get(&self, name: &str) -> Option<&DynamicValue> {
@@ -75,11 +144,27 @@ impl DynamicComponent {
     pub fn type_name(&self) -> &str {
         &self.type_name
     }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Stable hash of the type name and field values; equal for equal data
+    /// whatever order the fields were set in.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = FrameChecksum::new();
+        hasher.write_str(&self.type_name);
+        write_fields(&mut hasher, &self.data);
+        hasher.finish()
+    }
 }
 
+/// Components of one entity. Each component sits behind an `Arc`, so cloning
+/// the set (as `WorldSnapshot` does) shares the component data and a later
+/// write copies only the component being changed.
 #[derive(Debug, Clone, Component, Default)]
 pub struct DynamicComponents {
-    components: Vec<DynamicComponent>,
+    components: Vec<Arc<DynamicComponent>>,
 }
 
 impl DynamicComponents {
@@ -92,17 +177,28 @@ impl DynamicComponents {
     pub fn add(&mut self, component: DynamicComponent) {
         let type_name = component.type_name.clone();
         self.components.retain(|c| c.type_name != type_name);
-        self.components.push(component);
+        self.components.push(Arc::new(component));
     }
 
     pub fn get(&self, type_name: &str) -> Option<&DynamicComponent> {
+        self.components
+            .iter()
+            .find(|c| c.type_name == type_name)
+            .map(Arc::as_ref)
+    }
+
+    /// Shared handle to a component, for checking whether two sets still
+    /// point at the same data.
+    pub fn get_shared(&self, type_name: &str) -> Option<&Arc<DynamicComponent>> {
         self.components.iter().find(|c| c.type_name == type_name)
     }
 
+    /// Copies the component first if a clone of this set still shares it.
     pub fn get_mut(&mut self, type_name: &str) -> Option<&mut DynamicComponent> {
         self.components
             .iter_mut()
             .find(|c| c.type_name == type_name)
+            .map(Arc::make_mut)
     }
 
     pub fn has(&self, type_name: &str) -> bool {
@@ -118,14 +214,27 @@ impl DynamicComponents {
             .components
             .iter()
             .position(|c| c.type_name == type_name)?;
-        Some(self.components.remove(pos))
+        Some(Arc::unwrap_or_clone(self.components.remove(pos)))
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &DynamicComponent> {
-        self.components.iter()
+        self.components.iter().map(Arc::as_ref)
     }
 
     pub fn type_names(&self) -> Vec<&str> {
         self.components.iter().map(|c| c.type_name.as_str()).collect()
     }
+
+    /// Stable hash of every component, independent of the order they were
+    /// added in: the count, then each `content_hash` sorted by type name.
+    pub fn content_hash(&self) -> u64 {
+        let mut components: Vec<_> = self.iter().collect();
+        components.sort_unstable_by(|a, b| a.type_name.cmp(&b.type_name));
+        let mut hasher = FrameChecksum::new();
+        hasher.write_u64(components.len() as u64);
+        for component in components {
+            hasher.write_u64(component.content_hash());
+        }
+        hasher.finish()
+    }
 }
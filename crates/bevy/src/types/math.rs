@@ -1,4 +1,4 @@
-use bevy_math::{Quat, Vec2, Vec3};
+use bevy_math::{Mat4, Quat, Vec2, Vec3};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct RubyVec2(pub Vec2);
@@ -68,6 +68,41 @@ impl RubyVec2 {
         self.0.distance(other.0)
     }
 
+    /// Linear interpolation; `t` is not clamped, so values outside `0..=1` extrapolate.
+    pub fn lerp(&self, other: &RubyVec2, t: f32) -> Self {
+        Self(self.0.lerp(other.0, t))
+    }
+
+    /// Reflects off a surface with the given normal, which need not be normalized.
+    pub fn reflect(&self, normal: &RubyVec2) -> Self {
+        Self(self.0.reflect(normal.0.normalize_or_zero()))
+    }
+
+    /// Scales down to at most `max` length; shorter vectors are unchanged.
+    pub fn clamp_length(&self, max: f32) -> Self {
+        Self(self.0.clamp_length_max(max.max(0.0)))
+    }
+
+    /// Heading in radians, counterclockwise from +x: `atan2(y, x)`.
+    pub fn angle(&self) -> f32 {
+        self.0.y.atan2(self.0.x)
+    }
+
+    /// Unit vector pointing `radians` counterclockwise from +x.
+    pub fn from_angle(radians: f32) -> Self {
+        Self(Vec2::from_angle(radians))
+    }
+
+    /// Rotates counterclockwise by `radians`, keeping the length.
+    pub fn rotate(&self, radians: f32) -> Self {
+        Self(Vec2::from_angle(radians).rotate(self.0))
+    }
+
+    /// Rotated 90 degrees counterclockwise: `(-y, x)`.
+    pub fn perp(&self) -> Self {
+        Self(self.0.perp())
+    }
+
     pub fn inner(&self) -> Vec2 {
         self.0
     }
@@ -165,6 +200,21 @@ impl RubyVec3 {
         self.0.distance(other.0)
     }
 
+    /// Linear interpolation; `t` is not clamped, so values outside `0..=1` extrapolate.
+    pub fn lerp(&self, other: &RubyVec3, t: f32) -> Self {
+        Self(self.0.lerp(other.0, t))
+    }
+
+    /// Reflects off a surface with the given normal, which need not be normalized.
+    pub fn reflect(&self, normal: &RubyVec3) -> Self {
+        Self(self.0.reflect(normal.0.normalize_or_zero()))
+    }
+
+    /// Scales down to at most `max` length; shorter vectors are unchanged.
+    pub fn clamp_length(&self, max: f32) -> Self {
+        Self(self.0.clamp_length_max(max.max(0.0)))
+    }
+
     pub fn inner(&self) -> Vec3 {
         self.0
     }
@@ -258,3 +308,69 @@ impl From<RubyQuat> for Quat {
         q.0
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RubyMat4(pub Mat4);
+
+impl RubyMat4 {
+    pub fn identity() -> Self {
+        Self(Mat4::IDENTITY)
+    }
+
+    pub fn from_translation(translation: &RubyVec3) -> Self {
+        Self(Mat4::from_translation(translation.0))
+    }
+
+    pub fn from_scale_rotation_translation(
+        scale: &RubyVec3,
+        rotation: &RubyQuat,
+        translation: &RubyVec3,
+    ) -> Self {
+        Self(Mat4::from_scale_rotation_translation(
+            scale.0,
+            rotation.0,
+            translation.0,
+        ))
+    }
+
+    pub fn determinant(&self) -> f32 {
+        self.0.determinant()
+    }
+
+    pub fn inverse(&self) -> Self {
+        Self(self.0.inverse())
+    }
+
+    pub fn mul(&self, other: &RubyMat4) -> Self {
+        Self(self.0 * other.0)
+    }
+
+    pub fn transform_point3(&self, point: &RubyVec3) -> RubyVec3 {
+        RubyVec3(self.0.transform_point3(point.0))
+    }
+
+    pub fn transform_vector3(&self, vector: &RubyVec3) -> RubyVec3 {
+        RubyVec3(self.0.transform_vector3(vector.0))
+    }
+
+    /// Returns the matrix elements in column-major order.
+    pub fn to_cols_array(&self) -> [f32; 16] {
+        self.0.to_cols_array()
+    }
+
+    pub fn inner(&self) -> Mat4 {
+        self.0
+    }
+}
+
+impl From<Mat4> for RubyMat4 {
+    fn from(m: Mat4) -> Self {
+        Self(m)
+    }
+}
+
+impl From<RubyMat4> for Mat4 {
+    fn from(m: RubyMat4) -> Self {
+        m.0
+    }
+}
@@ -1,9 +1,11 @@
 pub mod color;
 pub mod dynamic;
 pub mod math;
+pub mod palette;
 pub mod transform;
 
 pub use color::RubyColor;
 pub use dynamic::{DynamicComponent, DynamicComponents, DynamicValue};
-pub use math::{RubyQuat, RubyVec2, RubyVec3};
+pub use math::{RubyMat4, RubyQuat, RubyVec2, RubyVec3};
+pub use palette::RubyPalette;
 pub use transform::RubyTransform;
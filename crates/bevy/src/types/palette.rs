@@ -0,0 +1,104 @@
+use crate::types::color::RubyColor;
+
+/// An ordered list of colors that can be rotated over time (palette cycling).
+#[derive(Debug, Clone)]
+pub struct RubyPalette {
+    colors: Vec<RubyColor>,
+    offset: f32,
+    speed: f32,
+}
+
+impl RubyPalette {
+    pub fn new(colors: Vec<RubyColor>) -> Self {
+        Self {
+            colors,
+            offset: 0.0,
+            speed: 1.0,
+        }
+    }
+
+    /// Builds `steps` colors linearly interpolated from `from` to `to`, inclusive.
+    pub fn gradient(from: &RubyColor, to: &RubyColor, steps: usize) -> Self {
+        let colors = match steps {
+            0 => Vec::new(),
+            1 => vec![*from],
+            _ => (0..steps)
+                .map(|step| {
+                    let t = step as f32 / (steps - 1) as f32;
+                    let [fr, fg, fb, fa] = from.to_array();
+                    let [tr, tg, tb, ta] = to.to_array();
+                    RubyColor::new(
+                        fr + (tr - fr) * t,
+                        fg + (tg - fg) * t,
+                        fb + (tb - fb) * t,
+                        fa + (ta - fa) * t,
+                    )
+                })
+                .collect(),
+        };
+        Self::new(colors)
+    }
+
+    /// Parses every entry as a hex color; returns `None` if any entry is invalid.
+    pub fn from_hex_list<S: AsRef<str>>(hexes: &[S]) -> Option<Self> {
+        hexes
+            .iter()
+            .map(|hex| RubyColor::from_hex(hex.as_ref()))
+            .collect::<Option<Vec<_>>>()
+            .map(Self::new)
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    pub fn colors(&self) -> &[RubyColor] {
+        &self.colors
+    }
+
+    /// Cycling speed in palette entries per second.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    /// Current rotation, in entries, within `0.0..len`.
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    /// Advances the rotation by `speed * delta_secs` entries, wrapping at the palette length.
+    pub fn cycle(&mut self, delta_secs: f32) {
+        if self.colors.is_empty() {
+            return;
+        }
+        self.offset = (self.offset + self.speed * delta_secs).rem_euclid(self.colors.len() as f32);
+    }
+
+    /// Returns the color at `index` after applying the current rotation.
+    ///
+    /// Indices wrap in both directions, so any integer is valid for a non-empty palette.
+    pub fn color_at(&self, index: i64) -> Option<RubyColor> {
+        if self.colors.is_empty() {
+            return None;
+        }
+        let len = self.colors.len() as i64;
+        let rotated = (index + self.offset.floor() as i64).rem_euclid(len);
+        self.colors.get(rotated as usize).copied()
+    }
+
+    /// Pairs each id with consecutive rotated palette entries.
+    pub fn assignments(&self, ids: &[u64]) -> Vec<(u64, RubyColor)> {
+        ids.iter()
+            .enumerate()
+            .filter_map(|(index, id)| self.color_at(index as i64).map(|color| (*id, color)))
+            .collect()
+    }
+}
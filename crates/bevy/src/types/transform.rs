@@ -1,6 +1,6 @@
-use bevy_math::{Quat, Vec3};
+use bevy_math::{EulerRot, Quat, Vec3};
 use bevy_transform::components::Transform;
-use crate::types::math::{RubyQuat, RubyVec3};
+use crate::types::math::{RubyMat4, RubyQuat, RubyVec3};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct RubyTransform(pub Transform);
@@ -142,6 +142,35 @@ impl RubyTransform {
         RubyVec3::from(self.0.transform_point(point.inner()))
     }
 
+    /// Maps a world-space point into this transform's local space.
+    ///
+    /// Undoes translation, then rotation, then scale. An axis with zero scale
+    /// has no inverse and maps to zero.
+    pub fn inverse_transform_point(&self, point: RubyVec3) -> RubyVec3 {
+        let local = self.0.rotation.inverse() * (point.inner() - self.0.translation);
+        let scale = self.0.scale;
+        let unscale = |value: f32, factor: f32| if factor == 0.0 { 0.0 } else { value / factor };
+        RubyVec3::new(
+            unscale(local.x, scale.x),
+            unscale(local.y, scale.y),
+            unscale(local.z, scale.z),
+        )
+    }
+
+    /// Rotation around the Z axis in radians, as used by 2D transforms.
+    pub fn rotation_z(&self) -> f32 {
+        self.0.rotation.to_euler(EulerRot::ZYX).0
+    }
+
+    /// Single scale factor for 2D use: the mean of the X and Y scales.
+    pub fn uniform_scale(&self) -> f32 {
+        (self.0.scale.x + self.0.scale.y) / 2.0
+    }
+
+    pub fn compute_matrix(&self) -> RubyMat4 {
+        RubyMat4::from(self.0.compute_matrix())
+    }
+
     pub fn inner(&self) -> Transform {
         self.0
     }
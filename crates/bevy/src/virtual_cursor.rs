@@ -0,0 +1,240 @@
+//! Virtual cursor module for steering a picking pointer with a gamepad stick.
+//!
+//! The cursor integrates stick input into a window position every frame, draws
+//! an internal sprite above the scene and feeds its position and button presses
+//! to `bevy_picking` as a custom pointer, so hover and click events fire exactly
+//! as they do for the mouse.
+
+use crate::sprite_renderer::{SpriteData, TransformData};
+
+#[cfg(feature = "rendering")]
+use crate::sprite_renderer::SpriteSync;
+#[cfg(feature = "rendering")]
+use bevy_ecs::entity::Entity;
+#[cfg(feature = "rendering")]
+use bevy_ecs::world::World;
+#[cfg(feature = "rendering")]
+use bevy_picking::PickingBehavior;
+#[cfg(feature = "rendering")]
+use bevy_picking::pointer::PointerId;
+
+/// Z translation of the cursor sprite, above every synced layer.
+pub const VIRTUAL_CURSOR_Z: f32 = 999.0;
+
+/// Base UUID (version 4 layout) for virtual cursor pointers; the gamepad slot fills the low bits.
+const POINTER_UUID_BASE: u128 = 0x6276_7275_6279_4000_8000_0000_0000_0000;
+
+/// The cursor sprite is kept in a private `SpriteSync`, so this ID never clashes with Ruby entities.
+#[cfg(feature = "rendering")]
+const CURSOR_SPRITE_ID: u64 = 0;
+
+/// Response curve applied to stick deflection before it moves the cursor.
+///
+/// Deflection inside `dead_zone` is ignored; the rest is rescaled to `0.0..=1.0`
+/// and raised to `exponent`, so small tilts move slowly for precise aiming.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StickCurve {
+    pub dead_zone: f32,
+    pub exponent: f32,
+}
+
+impl Default for StickCurve {
+    fn default() -> Self {
+        Self {
+            dead_zone: 0.15,
+            exponent: 2.0,
+        }
+    }
+}
+
+impl StickCurve {
+    pub fn new(dead_zone: f32, exponent: f32) -> Self {
+        Self {
+            dead_zone: dead_zone.clamp(0.0, 0.99),
+            exponent: exponent.max(0.01),
+        }
+    }
+
+    /// Applies the curve radially, keeping the stick direction.
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        let length = (x * x + y * y).sqrt();
+        if length <= self.dead_zone {
+            return (0.0, 0.0);
+        }
+        let normalized = (length.min(1.0) - self.dead_zone) / (1.0 - self.dead_zone);
+        let scale = normalized.powf(self.exponent) / length;
+        (x * scale, y * scale)
+    }
+}
+
+/// Settings for the gamepad-driven cursor.
+#[derive(Debug, Clone)]
+pub struct VirtualCursorConfig {
+    /// Index into the connected gamepads, ordered by gamepad entity.
+    pub gamepad_slot: usize,
+    /// Cursor speed in logical pixels per second at full deflection.
+    pub speed: f32,
+    pub curve: StickCurve,
+    /// Gamepad button name (as in `gamepads_state`) mapped to the primary pointer button.
+    pub button: String,
+    pub sprite: SpriteData,
+}
+
+impl Default for VirtualCursorConfig {
+    fn default() -> Self {
+        Self {
+            gamepad_slot: 0,
+            speed: 600.0,
+            curve: StickCurve::default(),
+            button: "South".to_string(),
+            sprite: SpriteData {
+                has_custom_size: true,
+                custom_size_x: 12.0,
+                custom_size_y: 12.0,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl VirtualCursorConfig {
+    /// Stable UUID of this cursor's pointer.
+    pub fn pointer_uuid(&self) -> u128 {
+        POINTER_UUID_BASE | (self.gamepad_slot as u128 & 0xFFFF_FFFF)
+    }
+
+    /// The pointer ID as reported on picking events, e.g. `Custom(62767275-6279-4000-8000-000000000000)`.
+    pub fn pointer_id(&self) -> String {
+        let uuid = self.pointer_uuid();
+        format!(
+            "Custom({:08x}-{:04x}-{:04x}-{:04x}-{:012x})",
+            uuid >> 96,
+            (uuid >> 80) & 0xFFFF,
+            (uuid >> 64) & 0xFFFF,
+            (uuid >> 48) & 0xFFFF,
+            uuid & 0xFFFF_FFFF_FFFF
+        )
+    }
+}
+
+/// Pending virtual cursor operation.
+#[derive(Debug, Clone)]
+pub enum VirtualCursorOperation {
+    Enable(Box<VirtualCursorConfig>),
+    Disable,
+}
+
+/// A gamepad-driven cursor in window coordinates (origin top-left, y down, like picking).
+pub struct VirtualCursor {
+    pub config: VirtualCursorConfig,
+    position: Option<(f32, f32)>,
+    #[cfg(feature = "rendering")]
+    sprite_sync: SpriteSync,
+    #[cfg(feature = "rendering")]
+    pointer_entity: Option<Entity>,
+}
+
+impl VirtualCursor {
+    pub fn new(config: VirtualCursorConfig) -> Self {
+        Self {
+            config,
+            position: None,
+            #[cfg(feature = "rendering")]
+            sprite_sync: SpriteSync::new(),
+            #[cfg(feature = "rendering")]
+            pointer_entity: None,
+        }
+    }
+
+    /// Current position, or `None` before the first frame placed the cursor.
+    pub fn position(&self) -> Option<(f32, f32)> {
+        self.position
+    }
+
+    /// Moves the cursor by the curved stick vector and clamps it to `bounds`.
+    ///
+    /// The cursor starts at the window center. Stick y points up while window
+    /// y points down. Returns the applied movement.
+    pub fn integrate(&mut self, stick: (f32, f32), delta_secs: f32, bounds: (f32, f32)) -> (f32, f32) {
+        let (x, y) = self.position.unwrap_or((bounds.0 / 2.0, bounds.1 / 2.0));
+        let (dx, dy) = self.config.curve.apply(stick.0, stick.1);
+        let step = self.config.speed * delta_secs;
+        let next = (
+            (x + dx * step).clamp(0.0, bounds.0.max(0.0)),
+            (y - dy * step).clamp(0.0, bounds.1.max(0.0)),
+        );
+        self.position = Some(next);
+        (next.0 - x, next.1 - y)
+    }
+
+    /// Converts the cursor position to world coordinates for the 2D camera.
+    pub fn world_position(
+        &self,
+        bounds: (f32, f32),
+        camera_position: (f32, f32),
+        camera_scale: f32,
+    ) -> Option<(f32, f32)> {
+        let (x, y) = self.position?;
+        Some((
+            camera_position.0 + (x - bounds.0 / 2.0) * camera_scale,
+            camera_position.1 + (bounds.1 / 2.0 - y) * camera_scale,
+        ))
+    }
+
+    /// Transform of the cursor sprite: on top of the scene and a constant on-screen size.
+    pub fn sprite_transform(
+        &self,
+        bounds: (f32, f32),
+        camera_position: (f32, f32),
+        camera_scale: f32,
+    ) -> Option<TransformData> {
+        let (x, y) = self.world_position(bounds, camera_position, camera_scale)?;
+        Some(TransformData {
+            translation_x: x,
+            translation_y: y,
+            translation_z: VIRTUAL_CURSOR_Z,
+            scale_x: camera_scale,
+            scale_y: camera_scale,
+            ..Default::default()
+        })
+    }
+
+    /// The `bevy_picking` pointer this cursor drives.
+    #[cfg(feature = "rendering")]
+    pub fn picking_pointer_id(&self) -> PointerId {
+        PointerId::Custom(uuid::Uuid::from_u128(self.config.pointer_uuid()))
+    }
+
+    /// Returns true once the pointer entity has been spawned.
+    #[cfg(feature = "rendering")]
+    pub fn is_spawned(&self) -> bool {
+        self.pointer_entity.is_some()
+    }
+
+    /// Spawns the custom pointer entity; picking adds its location and press state.
+    #[cfg(feature = "rendering")]
+    pub fn spawn(&mut self, world: &mut World) {
+        if self.pointer_entity.is_none() {
+            self.pointer_entity = Some(world.spawn(self.picking_pointer_id()).id());
+        }
+    }
+
+    /// Draws the cursor sprite; it ignores picking so it never hides what it points at.
+    #[cfg(feature = "rendering")]
+    pub fn sync_sprite(&mut self, world: &mut World, transform_data: &TransformData) {
+        self.sprite_sync
+            .sync_sprite(world, CURSOR_SPRITE_ID, &self.config.sprite, transform_data);
+        if let Some(entity) = self.sprite_sync.bevy_entity(CURSOR_SPRITE_ID) {
+            world.entity_mut(entity).insert(PickingBehavior::IGNORE);
+        }
+    }
+
+    /// Removes the pointer entity and the cursor sprite.
+    #[cfg(feature = "rendering")]
+    pub fn despawn(&mut self, world: &mut World) {
+        if let Some(entity) = self.pointer_entity.take() {
+            world.despawn(entity);
+        }
+        self.sprite_sync.clear(world);
+    }
+}
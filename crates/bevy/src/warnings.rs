@@ -0,0 +1,245 @@
+//! Non-fatal warnings raised by the bridge and sync layers.
+//!
+//! Warnings are keyed for deduplication: the first occurrence of a key is
+//! kept immediately, later ones are counted and folded into one record at
+//! most every `interval_secs`, carrying the latest message. Ruby drains the kept records each frame, and
+//! a per-category policy can silence them or turn them into exceptions.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Records kept until Ruby drains them; older ones are dropped first.
+pub const DEFAULT_WARNING_CAPACITY: usize = 256;
+
+/// Seconds between two records for the same dedupe key.
+pub const DEFAULT_WARNING_INTERVAL_SECS: f64 = 5.0;
+
+/// Category of the record reporting warnings dropped from a full buffer.
+pub const DROPPED_WARNINGS_CATEGORY: &str = "warnings";
+
+/// What happens to warnings of a category.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WarningPolicy {
+    /// Discarded without being counted.
+    Silent,
+    /// Logged and kept for Ruby to drain.
+    #[default]
+    Collect,
+    /// Kept like `Collect`; Ruby raises `Bevy::WarningError` when draining it.
+    Raise,
+}
+
+impl WarningPolicy {
+    /// Names accepted by `from_name`, in declaration order.
+    pub const NAMES: [&'static str; 3] = ["silent", "collect", "raise"];
+
+    /// Parses `silent`, `collect` or `raise`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "silent" => Some(Self::Silent),
+            "collect" => Some(Self::Collect),
+            "raise" => Some(Self::Raise),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Silent => "silent",
+            Self::Collect => "collect",
+            Self::Raise => "raise",
+        }
+    }
+}
+
+/// One kept warning, standing for `count` occurrences of its dedupe key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarningRecord {
+    pub category: String,
+    pub message: String,
+    /// Occurrences folded into this record, including suppressed ones.
+    pub count: u32,
+    /// Frame of the first occurrence folded into this record.
+    pub first_frame: u64,
+}
+
+#[derive(Debug, Clone)]
+struct DedupeState {
+    last_kept_secs: f64,
+    suppressed: u32,
+    first_suppressed_frame: u64,
+    /// Category and message of the latest suppressed occurrence.
+    latest: Option<(String, String)>,
+}
+
+/// Rate-limited, bounded buffer of warnings.
+#[derive(Debug, Clone)]
+pub struct WarningLog {
+    records: VecDeque<WarningRecord>,
+    /// Ordered so suppressed counts flush in a stable order.
+    dedupe: BTreeMap<String, DedupeState>,
+    policies: HashMap<String, WarningPolicy>,
+    capacity: usize,
+    interval_secs: f64,
+    dropped: u32,
+    frame: u64,
+    now_secs: f64,
+}
+
+impl Default for WarningLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_WARNING_CAPACITY, DEFAULT_WARNING_INTERVAL_SECS)
+    }
+}
+
+impl WarningLog {
+    pub fn new(capacity: usize, interval_secs: f64) -> Self {
+        Self {
+            records: VecDeque::new(),
+            dedupe: BTreeMap::new(),
+            policies: HashMap::new(),
+            capacity: capacity.max(1),
+            interval_secs: interval_secs.max(0.0),
+            dropped: 0,
+            frame: 0,
+            now_secs: 0.0,
+        }
+    }
+
+    /// Starts a frame at `elapsed_secs` since launch; occurrences are stamped
+    /// with it. Keys whose interval has passed with suppressed occurrences
+    /// are kept as one record.
+    pub fn begin_frame(&mut self, elapsed_secs: f64) {
+        self.frame += 1;
+        self.now_secs = elapsed_secs;
+
+        let due: Vec<(String, String, u32, u64)> = self
+            .dedupe
+            .values_mut()
+            .filter(|state| state.suppressed > 0 && elapsed_secs - state.last_kept_secs >= self.interval_secs)
+            .filter_map(|state| {
+                let (category, message) = state.latest.take()?;
+                let record = (category, message, state.suppressed, state.first_suppressed_frame);
+                state.last_kept_secs = elapsed_secs;
+                state.suppressed = 0;
+                Some(record)
+            })
+            .collect();
+        for (category, message, count, first_frame) in due {
+            if self.policy(&category) != WarningPolicy::Silent {
+                self.keep(category, message, count, first_frame);
+            }
+        }
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Most records kept between drains.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_policy(&mut self, category: &str, policy: WarningPolicy) {
+        self.policies.insert(category.to_string(), policy);
+    }
+
+    pub fn policy(&self, category: &str) -> WarningPolicy {
+        self.policies.get(category).copied().unwrap_or_default()
+    }
+
+    /// Reports a warning; repeats of `dedupe_key` within the interval are only counted.
+    pub fn push(&mut self, category: &str, message: impl Into<String>, dedupe_key: &str) {
+        if self.policy(category) == WarningPolicy::Silent {
+            return;
+        }
+
+        let (now, frame) = (self.now_secs, self.frame);
+        let (count, first_frame) = match self.dedupe.get_mut(dedupe_key) {
+            Some(state) if now - state.last_kept_secs < self.interval_secs => {
+                if state.suppressed == 0 {
+                    state.first_suppressed_frame = frame;
+                }
+                state.suppressed += 1;
+                state.latest = Some((category.to_string(), message.into()));
+                return;
+            }
+            Some(state) => {
+                let first_frame = if state.suppressed > 0 {
+                    state.first_suppressed_frame
+                } else {
+                    frame
+                };
+                let count = state.suppressed + 1;
+                *state = DedupeState {
+                    last_kept_secs: now,
+                    suppressed: 0,
+                    first_suppressed_frame: frame,
+                    latest: None,
+                };
+                (count, first_frame)
+            }
+            None => {
+                self.dedupe.insert(
+                    dedupe_key.to_string(),
+                    DedupeState {
+                        last_kept_secs: now,
+                        suppressed: 0,
+                        first_suppressed_frame: frame,
+                        latest: None,
+                    },
+                );
+                (1, frame)
+            }
+        };
+        self.keep(category.to_string(), message.into(), count, first_frame);
+    }
+
+    fn keep(&mut self, category: String, message: String, count: u32, first_frame: u64) {
+        #[cfg(feature = "rendering")]
+        if count > 1 {
+            bevy_log::warn!("{}: {} ({} times)", category, message, count);
+        } else {
+            bevy_log::warn!("{}: {}", category, message);
+        }
+
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+            self.dropped += 1;
+        }
+        self.records.push_back(WarningRecord {
+            category,
+            message,
+            count,
+            first_frame,
+        });
+    }
+
+    /// Records kept since the last drain, oldest first. Warnings dropped
+    /// from a full buffer are reported by a final `warnings` record.
+    pub fn drain(&mut self) -> Vec<WarningRecord> {
+        let mut drained: Vec<WarningRecord> = self.records.drain(..).collect();
+        if self.dropped > 0 {
+            drained.push(WarningRecord {
+                category: DROPPED_WARNINGS_CATEGORY.to_string(),
+                message: format!(
+                    "{} warnings were dropped because more than {} were waiting",
+                    self.dropped, self.capacity
+                ),
+                count: self.dropped,
+                first_frame: self.frame,
+            });
+            self.dropped = 0;
+        }
+        drained
+    }
+
+    /// Records waiting to be drained.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
@@ -1,14 +1,24 @@
 use crate::component::ComponentRegistry;
+use crate::determinism::{AuditMap, AuditSet};
+use crate::hash::FrameChecksum;
 use crate::entity::EntityWrapper;
 use crate::error::BevyRubyError;
 use crate::types::{DynamicComponent, DynamicComponents};
+use crate::world_snapshot::WorldSnapshot;
+use bevy_ecs::entity::Entity;
 use bevy_ecs::world::World;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 pub struct WorldWrapper {
     world: RefCell<World>,
     registry: Arc<ComponentRegistry>,
+    /// Entities touched since the last `query_changed` drain, kept only for
+    /// the component types `query_changed` was called for.
+    change_feed: RefCell<AuditMap<String, AuditSet<Entity>>>,
+    /// Highest generation stamped on a stored component so far.
+    generation: Cell<u64>,
 }
 
 impl WorldWrapper {
@@ -16,6 +26,8 @@ impl WorldWrapper {
         Self {
             world: RefCell::new(World::new()),
             registry: ComponentRegistry::new(),
+            change_feed: RefCell::new(AuditMap::new()),
+            generation: Cell::new(0),
         }
     }
 
@@ -23,6 +35,8 @@ impl WorldWrapper {
         Self {
             world: RefCell::new(World::new()),
             registry,
+            change_feed: RefCell::new(AuditMap::new()),
+            generation: Cell::new(0),
         }
     }
 
@@ -31,19 +45,30 @@ impl WorldWrapper {
         EntityWrapper::new(entity)
     }
 
-    pub fn spawn_with_component(&self, component: DynamicComponent) -> EntityWrapper {
+    pub fn spawn_with_component(&self, mut component: DynamicComponent) -> EntityWrapper {
+        let type_name = component.type_name.clone();
+        self.stamp(&mut component);
         let mut components = DynamicComponents::new();
         components.add(component);
         let entity = self.world.borrow_mut().spawn(components).id();
+        self.record_change(entity, &type_name);
         EntityWrapper::new(entity)
     }
 
     pub fn spawn_with_components(&self, component_list: Vec<DynamicComponent>) -> EntityWrapper {
+        let type_names: Vec<String> = component_list
+            .iter()
+            .map(|c| c.type_name.clone())
+            .collect();
         let mut components = DynamicComponents::new();
-        for component in component_list {
+        for mut component in component_list {
+            self.stamp(&mut component);
             components.add(component);
         }
         let entity = self.world.borrow_mut().spawn(components).id();
+        for type_name in &type_names {
+            self.record_change(entity, type_name);
+        }
         EntityWrapper::new(entity)
     }
 
@@ -51,6 +76,9 @@ impl WorldWrapper {
         let mut world = self.world.borrow_mut();
         if world.get_entity(entity.inner()).is_ok() {
             world.despawn(entity.inner());
+            for touched in self.change_feed.borrow_mut().values_mut() {
+                touched.remove(&entity.inner());
+            }
             Ok(())
         } else {
             Err(BevyRubyError::EntityNotFound(entity.inner()))
@@ -64,11 +92,13 @@ impl WorldWrapper {
     pub fn insert_component(
         &self,
         entity: EntityWrapper,
-        component: DynamicComponent,
+        mut component: DynamicComponent,
     ) -> Result<(), BevyRubyError> {
+        let type_name = component.type_name.clone();
         let mut world = self.world.borrow_mut();
         match world.get_entity_mut(entity.inner()) {
             Ok(mut entity_mut) => {
+                self.stamp(&mut component);
                 if let Some(mut components) = entity_mut.get_mut::<DynamicComponents>() {
                     components.add(component);
                 } else {
@@ -76,6 +106,7 @@ impl WorldWrapper {
                     components.add(component);
                     entity_mut.insert(components);
                 }
+                self.record_change(entity.inner(), &type_name);
                 Ok(())
             }
             Err(_) => Err(BevyRubyError::EntityNotFound(entity.inner())),
@@ -108,6 +139,58 @@ impl WorldWrapper {
         }
     }
 
+    /// Removes one component, leaving the entity and its other components in place.
+    pub fn remove_component(&self, entity: EntityWrapper, type_name: &str) -> Result<(), BevyRubyError> {
+        let mut world = self.world.borrow_mut();
+        let mut entity_mut = world
+            .get_entity_mut(entity.inner())
+            .map_err(|_| BevyRubyError::EntityNotFound(entity.inner()))?;
+
+        entity_mut
+            .get_mut::<DynamicComponents>()
+            .and_then(|mut components| components.remove(type_name))
+            .map(|_| ())
+            .ok_or_else(|| BevyRubyError::ComponentNotFound {
+                entity: entity.inner(),
+                component: type_name.to_string(),
+            })
+    }
+
+    /// Returns the `type_name` component, inserting `default` first if it is missing.
+    ///
+    /// The lookup and insert happen under one borrow. `default` is stored under
+    /// `type_name` whatever its own type name is.
+    pub fn get_or_insert_component(
+        &self,
+        entity: EntityWrapper,
+        type_name: &str,
+        mut default: DynamicComponent,
+    ) -> Result<DynamicComponent, BevyRubyError> {
+        let mut world = self.world.borrow_mut();
+        let mut entity_mut = world
+            .get_entity_mut(entity.inner())
+            .map_err(|_| BevyRubyError::EntityNotFound(entity.inner()))?;
+
+        if let Some(existing) = entity_mut
+            .get::<DynamicComponents>()
+            .and_then(|components| components.get(type_name))
+        {
+            return Ok(existing.clone());
+        }
+
+        default.type_name = type_name.to_string();
+        self.stamp(&mut default);
+        if let Some(mut components) = entity_mut.get_mut::<DynamicComponents>() {
+            components.add(default.clone());
+        } else {
+            let mut components = DynamicComponents::new();
+            components.add(default.clone());
+            entity_mut.insert(components);
+        }
+        self.record_change(entity.inner(), type_name);
+        Ok(default)
+    }
+
     pub fn has_component(&self, entity: EntityWrapper, type_name: &str) -> bool {
         let world = self.world.borrow();
         match world.get_entity(entity.inner()) {
@@ -122,13 +205,92 @@ impl WorldWrapper {
         }
     }
 
+    /// Number of entities, including those without components.
+    pub fn count(&self) -> usize {
+        self.world.borrow().entities().len() as usize
+    }
+
+    /// Every live entity, sorted by entity. A plain world spawns no internal
+    /// entities of its own, so these are exactly the ones spawned through it.
+    pub fn all_entities(&self) -> Vec<EntityWrapper> {
+        let world = self.world.borrow();
+        let mut result: Vec<_> = world
+            .iter_entities()
+            .map(|entity| EntityWrapper::new(entity.id()))
+            .collect();
+        result.sort_unstable_by_key(|entity| entity.inner());
+        result
+    }
+
+    /// Number of live entities, matching `all_entities().len()`.
+    pub fn entity_count(&self) -> usize {
+        self.world.borrow().iter_entities().count()
+    }
+
+    /// Takes a read-only copy of every entity's components that later
+    /// mutations of this world do not affect. Component data is shared
+    /// until the world next writes to it.
+    pub fn snapshot(&self) -> WorldSnapshot {
+        let world = self.world.borrow();
+        let entities: BTreeMap<Entity, DynamicComponents> = world
+            .iter_entities()
+            .map(|entity| {
+                let components = entity.get::<DynamicComponents>().cloned().unwrap_or_default();
+                (entity.id(), components)
+            })
+            .collect();
+        WorldSnapshot::new(entities)
+    }
+
+    /// Stable hash of every entity and its components, for skipping work
+    /// when nothing changed.
+    ///
+    /// Each entity hashes its `Entity::to_bits` followed by its components'
+    /// `content_hash`; the sorted entity hashes are then hashed with their
+    /// count, so the result does not depend on iteration order. Re-inserting
+    /// identical data leaves it unchanged.
+    pub fn world_hash(&self) -> u64 {
+        let world = self.world.borrow();
+        let empty = DynamicComponents::new().content_hash();
+        let mut entity_hashes: Vec<u64> = world
+            .iter_entities()
+            .map(|entity| {
+                let mut hasher = FrameChecksum::new();
+                hasher.write_u64(entity.id().to_bits());
+                hasher.write_u64(
+                    entity
+                        .get::<DynamicComponents>()
+                        .map_or(empty, DynamicComponents::content_hash),
+                );
+                hasher.finish()
+            })
+            .collect();
+        entity_hashes.sort_unstable();
+
+        let mut hasher = FrameChecksum::new();
+        hasher.write_u64(entity_hashes.len() as u64);
+        for entity_hash in entity_hashes {
+            hasher.write_u64(entity_hash);
+        }
+        hasher.finish()
+    }
+
     pub fn query_entities_with(&self, type_names: &[&str]) -> Vec<EntityWrapper> {
+        self.query_entities_filtered(type_names, &[], &[])
+    }
+
+    /// Returns entities having every `fetch` and `with` component and none
+    /// of the `without` components, matched by component type name.
+    pub fn query_entities_filtered(&self, fetch: &[&str], with: &[&str], without: &[&str]) -> Vec<EntityWrapper> {
         let world = self.world.borrow();
         let mut result = Vec::new();
 
         for entity in world.iter_entities() {
             if let Some(components) = entity.get::<DynamicComponents>() {
-                if components.has_all(type_names) {
+                if components.has_all(fetch)
+                    && components.has_all(with)
+                    && !without.iter().any(|name| components.has(name))
+                {
                     result.push(EntityWrapper::new(entity.id()));
                 }
             }
@@ -137,6 +299,101 @@ impl WorldWrapper {
         result
     }
 
+    /// Returns every entity having all `type_names`, with clones of those
+    /// components in the requested order, sorted by entity.
+    pub fn query_components(&self, type_names: &[&str]) -> Vec<(EntityWrapper, Vec<DynamicComponent>)> {
+        let world = self.world.borrow();
+        let mut result: Vec<_> = world
+            .iter_entities()
+            .filter_map(|entity| {
+                let components = entity.get::<DynamicComponents>()?;
+                let matched = type_names
+                    .iter()
+                    .map(|name| components.get(name).cloned())
+                    .collect::<Option<Vec<_>>>()?;
+                Some((EntityWrapper::new(entity.id()), matched))
+            })
+            .collect();
+        result.sort_unstable_by_key(|(entity, _)| entity.inner());
+        result
+    }
+
+    /// Returns entities whose `type_name` component was inserted or replaced
+    /// since the previous call for that type, then clears the feed for it,
+    /// sorted by entity.
+    ///
+    /// Changes are only recorded for types queried before. The first call for
+    /// a type is the priming call: it starts recording and returns every
+    /// entity that has the component, since earlier changes were not kept.
+    /// Entities stripped of the component in the meantime are skipped.
+    pub fn query_changed(&self, type_name: &str) -> Vec<EntityWrapper> {
+        let touched = self
+            .change_feed
+            .borrow_mut()
+            .insert(type_name.to_string(), AuditSet::new());
+        let Some(touched) = touched else {
+            let mut result = self.query_entities_with(&[type_name]);
+            result.sort_unstable_by_key(|entity| entity.inner());
+            return result;
+        };
+
+        let world = self.world.borrow();
+        let mut result: Vec<_> = touched
+            .into_iter()
+            .filter(|entity| {
+                world
+                    .get_entity(*entity)
+                    .ok()
+                    .and_then(|entity_ref| entity_ref.get::<DynamicComponents>())
+                    .is_some_and(|components| components.has(type_name))
+            })
+            .map(EntityWrapper::new)
+            .collect();
+        result.sort_unstable_by_key(|entity| entity.inner());
+        result
+    }
+
+    /// Entities whose `type_name` component was stored with a generation
+    /// above `since_generation`, sorted by entity.
+    ///
+    /// Unlike `query_changed` nothing is drained, so each reader keeps its own
+    /// `since_generation`, usually the `generation` read after its last pass.
+    pub fn changed_entities(&self, type_name: &str, since_generation: u64) -> Vec<EntityWrapper> {
+        let world = self.world.borrow();
+        let mut result: Vec<_> = world
+            .iter_entities()
+            .filter(|entity| {
+                entity
+                    .get::<DynamicComponents>()
+                    .and_then(|components| components.get(type_name))
+                    .is_some_and(|component| component.generation > since_generation)
+            })
+            .map(|entity| EntityWrapper::new(entity.id()))
+            .collect();
+        result.sort_unstable_by_key(|entity| entity.inner());
+        result
+    }
+
+    /// Highest generation stamped on a component stored in this world; 0
+    /// before the first one.
+    pub fn generation(&self) -> u64 {
+        self.generation.get()
+    }
+
+    /// Moves `component` past both its own generation and the world's, so
+    /// every store reads as newer than anything stored before it.
+    fn stamp(&self, component: &mut DynamicComponent) {
+        let generation = self.generation.get().max(component.generation) + 1;
+        self.generation.set(generation);
+        component.generation = generation;
+    }
+
+    fn record_change(&self, entity: Entity, type_name: &str) {
+        if let Some(touched) = self.change_feed.borrow_mut().get_mut(type_name) {
+            touched.insert(entity);
+        }
+    }
+
     pub fn registry(&self) -> &Arc<ComponentRegistry> {
         &self.registry
     }
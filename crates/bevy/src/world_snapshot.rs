@@ -0,0 +1,102 @@
+//! Read-only copies of a `WorldWrapper` for reader threads.
+//!
+//! A snapshot clones each entity's `DynamicComponents`, whose components sit
+//! behind `Arc`s, so taking one costs a pointer copy per component rather
+//! than a copy of the component data. The world copies a component only when
+//! it next writes to it, which leaves the snapshot seeing the values it was
+//! taken with. Snapshots hold no world reference and no Ruby objects, so they
+//! can be handed to other threads.
+
+use crate::entity::EntityWrapper;
+use crate::error::BevyRubyError;
+use crate::types::{DynamicComponent, DynamicComponents};
+use crate::world::WorldWrapper;
+use bevy_ecs::entity::Entity;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Immutable view of every entity and its components at the moment
+/// `WorldWrapper::snapshot` was called. Cloning it shares the same data.
+#[derive(Debug, Clone, Default)]
+pub struct WorldSnapshot {
+    entities: Arc<BTreeMap<Entity, DynamicComponents>>,
+}
+
+impl WorldSnapshot {
+    pub(crate) fn new(entities: BTreeMap<Entity, DynamicComponents>) -> Self {
+        Self {
+            entities: Arc::new(entities),
+        }
+    }
+
+    pub fn entity_exists(&self, entity: EntityWrapper) -> bool {
+        self.entities.contains_key(&entity.inner())
+    }
+
+    /// Same errors as `WorldWrapper::get_component`.
+    pub fn get_component(
+        &self,
+        entity: EntityWrapper,
+        type_name: &str,
+    ) -> Result<DynamicComponent, BevyRubyError> {
+        let components = self
+            .entities
+            .get(&entity.inner())
+            .ok_or(BevyRubyError::EntityNotFound(entity.inner()))?;
+        components
+            .get(type_name)
+            .cloned()
+            .ok_or_else(|| BevyRubyError::ComponentNotFound {
+                entity: entity.inner(),
+                component: type_name.to_string(),
+            })
+    }
+
+    pub fn has_component(&self, entity: EntityWrapper, type_name: &str) -> bool {
+        self.entities
+            .get(&entity.inner())
+            .is_some_and(|components| components.has(type_name))
+    }
+
+    pub fn query_entities_with(&self, type_names: &[&str]) -> Vec<EntityWrapper> {
+        self.query_entities_filtered(type_names, &[], &[])
+    }
+
+    /// Matches like `WorldWrapper::query_entities_filtered`, in entity order.
+    pub fn query_entities_filtered(&self, fetch: &[&str], with: &[&str], without: &[&str]) -> Vec<EntityWrapper> {
+        self.entities
+            .iter()
+            .filter(|(_, components)| {
+                components.has_all(fetch)
+                    && components.has_all(with)
+                    && !without.iter().any(|name| components.has(name))
+            })
+            .map(|(entity, _)| EntityWrapper::new(*entity))
+            .collect()
+    }
+
+    /// Number of entities, including those without components.
+    pub fn count(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// True while the world still holds the very component data this
+    /// snapshot does, i.e. it has not written to that component since.
+    pub fn shares_component_with(&self, world: &WorldWrapper, entity: EntityWrapper, type_name: &str) -> bool {
+        let Some(ours) = self
+            .entities
+            .get(&entity.inner())
+            .and_then(|components| components.get_shared(type_name))
+        else {
+            return false;
+        };
+        world.with_world(|world| {
+            world
+                .get_entity(entity.inner())
+                .ok()
+                .and_then(|entity_ref| entity_ref.get::<DynamicComponents>())
+                .and_then(|components| components.get_shared(type_name))
+                .is_some_and(|theirs| Arc::ptr_eq(ours, theirs))
+        })
+    }
+}
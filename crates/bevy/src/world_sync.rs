@@ -0,0 +1,255 @@
+//! World sync module for mirroring dynamic `WorldWrapper` entities as sprites.
+//!
+//! A binding names a component holding sprite attributes and one holding the
+//! position. Each plan compares the matching entities with the ones synced
+//! last time and yields sync operations for every match and removals for
+//! entities that lost a component or were despawned.
+
+use std::collections::HashSet;
+
+use crate::entity::EntityWrapper;
+use crate::sprite_renderer::{SpriteData, SpriteOperation, TransformData};
+use crate::types::{DynamicComponent, DynamicValue, RubyColor};
+use crate::world::WorldWrapper;
+
+/// Set on sprite IDs derived from bound-world entities so they never collide
+/// with entities of the app's own world.
+pub const BOUND_ENTITY_ID_FLAG: u64 = 1 << 62;
+
+/// Field names read from the bound components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldSyncFields {
+    /// `[r, g, b]`, `[r, g, b, a]` or a hex string.
+    pub color: String,
+    /// `[width, height]` or a single number for squares.
+    pub size: String,
+    pub flip_x: String,
+    pub flip_y: String,
+    pub x: String,
+    pub y: String,
+    pub z: String,
+    /// Radians around the Z axis.
+    pub rotation: String,
+}
+
+impl Default for WorldSyncFields {
+    fn default() -> Self {
+        Self {
+            color: "color".to_string(),
+            size: "size".to_string(),
+            flip_x: "flip_x".to_string(),
+            flip_y: "flip_y".to_string(),
+            x: "x".to_string(),
+            y: "y".to_string(),
+            z: "z".to_string(),
+            rotation: "rotation".to_string(),
+        }
+    }
+}
+
+/// Which components of a bound world become sprites.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldSyncBinding {
+    pub component_type: String,
+    pub transform_from: String,
+    pub fields: WorldSyncFields,
+}
+
+impl Default for WorldSyncBinding {
+    fn default() -> Self {
+        Self {
+            component_type: "Renderable".to_string(),
+            transform_from: "Position".to_string(),
+            fields: WorldSyncFields::default(),
+        }
+    }
+}
+
+/// A sprite change derived from the bound world.
+#[derive(Debug, Clone)]
+pub enum WorldSyncOperation {
+    Sync {
+        ruby_entity_id: u64,
+        sprite_data: SpriteData,
+        x: f32,
+        y: f32,
+        z: f32,
+        rotation: f32,
+    },
+    Remove {
+        ruby_entity_id: u64,
+    },
+}
+
+impl WorldSyncOperation {
+    /// Converts to the operation queued on a `SpriteSync`.
+    pub fn into_sprite_operation(self) -> SpriteOperation {
+        match self {
+            WorldSyncOperation::Sync {
+                ruby_entity_id,
+                sprite_data,
+                x,
+                y,
+                z,
+                rotation,
+            } => {
+                let (sin_half, cos_half) = (rotation / 2.0).sin_cos();
+                SpriteOperation::Sync {
+                    ruby_entity_id,
+                    sprite_data,
+                    transform_data: TransformData {
+                        translation_x: x,
+                        translation_y: y,
+                        translation_z: z,
+                        rotation_z: sin_half,
+                        rotation_w: cos_half,
+                        ..Default::default()
+                    },
+                }
+            }
+            WorldSyncOperation::Remove { ruby_entity_id } => {
+                SpriteOperation::Remove { ruby_entity_id }
+            }
+        }
+    }
+}
+
+/// Returns the sprite ID used for an entity of a bound world.
+pub fn bound_entity_id(entity: EntityWrapper) -> u64 {
+    entity.id() | BOUND_ENTITY_ID_FLAG
+}
+
+/// Tracks which bound-world entities are currently mirrored as sprites.
+pub struct WorldSpriteSync {
+    pub binding: WorldSyncBinding,
+    synced: HashSet<u64>,
+}
+
+impl WorldSpriteSync {
+    pub fn new(binding: WorldSyncBinding) -> Self {
+        Self {
+            binding,
+            synced: HashSet::new(),
+        }
+    }
+
+    /// Returns the sprite IDs synced by the last plan.
+    pub fn synced_ids(&self) -> Vec<u64> {
+        let mut ids: Vec<u64> = self.synced.iter().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Derives this frame's operations: syncs in entity order, then removals in ID order.
+    pub fn plan(&mut self, world: &WorldWrapper) -> Vec<WorldSyncOperation> {
+        let type_names = [
+            self.binding.component_type.as_str(),
+            self.binding.transform_from.as_str(),
+        ];
+        let matches = world.query_components(&type_names);
+
+        let mut operations = Vec::with_capacity(matches.len());
+        let mut current = HashSet::with_capacity(matches.len());
+        for (entity, components) in matches {
+            let ruby_entity_id = bound_entity_id(entity);
+            current.insert(ruby_entity_id);
+            operations.push(self.sync_operation(ruby_entity_id, &components[0], &components[1]));
+        }
+
+        let mut removed: Vec<u64> = self.synced.difference(&current).copied().collect();
+        removed.sort_unstable();
+        operations.extend(
+            removed
+                .into_iter()
+                .map(|ruby_entity_id| WorldSyncOperation::Remove { ruby_entity_id }),
+        );
+
+        self.synced = current;
+        operations
+    }
+
+    /// Forgets all synced entities, returning removals for them.
+    pub fn release(&mut self) -> Vec<WorldSyncOperation> {
+        let ids = self.synced_ids();
+        self.synced.clear();
+        ids.into_iter()
+            .map(|ruby_entity_id| WorldSyncOperation::Remove { ruby_entity_id })
+            .collect()
+    }
+
+    fn sync_operation(
+        &self,
+        ruby_entity_id: u64,
+        renderable: &DynamicComponent,
+        position: &DynamicComponent,
+    ) -> WorldSyncOperation {
+        let fields = &self.binding.fields;
+        let mut sprite_data = SpriteData::default();
+
+        if let Some([r, g, b, a]) = renderable.get(&fields.color).and_then(color_value) {
+            sprite_data.color_r = r;
+            sprite_data.color_g = g;
+            sprite_data.color_b = b;
+            sprite_data.color_a = a;
+        }
+        if let Some((width, height)) = renderable.get(&fields.size).and_then(size_value) {
+            sprite_data.has_custom_size = true;
+            sprite_data.custom_size_x = width;
+            sprite_data.custom_size_y = height;
+        }
+        sprite_data.flip_x = renderable
+            .get(&fields.flip_x)
+            .and_then(DynamicValue::as_bool)
+            .unwrap_or(false);
+        sprite_data.flip_y = renderable
+            .get(&fields.flip_y)
+            .and_then(DynamicValue::as_bool)
+            .unwrap_or(false);
+
+        let number = |name: &str| {
+            position
+                .get(name)
+                .and_then(DynamicValue::as_f64)
+                .unwrap_or(0.0) as f32
+        };
+
+        WorldSyncOperation::Sync {
+            ruby_entity_id,
+            sprite_data,
+            x: number(&fields.x),
+            y: number(&fields.y),
+            z: number(&fields.z),
+            rotation: number(&fields.rotation),
+        }
+    }
+}
+
+fn color_value(value: &DynamicValue) -> Option<[f32; 4]> {
+    match value {
+        DynamicValue::String(hex) | DynamicValue::Symbol(hex) => {
+            RubyColor::from_hex(hex).map(|color| color.to_array())
+        }
+        DynamicValue::Array(channels) => {
+            let channels: Vec<f32> = channels
+                .iter()
+                .map(|channel| channel.as_f64().map(|v| v as f32))
+                .collect::<Option<_>>()?;
+            match channels.as_slice() {
+                [r, g, b] => Some([*r, *g, *b, 1.0]),
+                [r, g, b, a] => Some([*r, *g, *b, *a]),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn size_value(value: &DynamicValue) -> Option<(f32, f32)> {
+    match value {
+        DynamicValue::Array(dimensions) => match dimensions.as_slice() {
+            [width, height] => Some((width.as_f64()? as f32, height.as_f64()? as f32)),
+            _ => None,
+        },
+        other => other.as_f64().map(|side| (side as f32, side as f32)),
+    }
+}
@@ -60,6 +60,8 @@ pub enum AssetChangeType {
 
 pub struct FileWatcher {
     watched_paths: HashMap<PathBuf, SystemTime>,
+    /// Watched paths with no file yet, reported as created once one appears.
+    missing_paths: HashSet<PathBuf>,
     base_paths: Vec<PathBuf>,
     extensions: HashSet<String>,
 }
@@ -68,6 +70,7 @@ impl FileWatcher {
     pub fn new() -> Self {
         Self {
             watched_paths: HashMap::new(),
+            missing_paths: HashSet::new(),
             base_paths: Vec::new(),
             extensions: HashSet::new(),
         }
@@ -81,29 +84,45 @@ impl FileWatcher {
         self.extensions.insert(ext.to_lowercase());
     }
 
+    /// Starts watching `path`. Returns false when there is no file there yet;
+    /// the path is still watched and reported as created when one appears.
     pub fn watch(&mut self, path: &Path) -> bool {
-        if let Ok(metadata) = std::fs::metadata(path) {
-            if let Ok(modified) = metadata.modified() {
-                self.watched_paths.insert(path.to_path_buf(), modified);
-                return true;
-            }
+        if let Some(modified) = modified_time(path) {
+            self.missing_paths.remove(path);
+            self.watched_paths.insert(path.to_path_buf(), modified);
+            return true;
         }
+        self.missing_paths.insert(path.to_path_buf());
         false
     }
 
     pub fn unwatch(&mut self, path: &Path) {
         self.watched_paths.remove(path);
+        self.missing_paths.remove(path);
     }
 
+    /// Changes since the last check, ordered by path. A deleted file keeps
+    /// being watched, so recreating it is reported as created.
     pub fn check_changes(&mut self) -> Vec<(PathBuf, AssetChangeType)> {
         let mut changes = Vec::new();
         let mut to_remove = Vec::new();
 
+        let created: Vec<(PathBuf, SystemTime)> = self
+            .missing_paths
+            .iter()
+            .filter_map(|path| modified_time(path).map(|modified| (path.clone(), modified)))
+            .collect();
+        for (path, modified) in created {
+            self.missing_paths.remove(&path);
+            self.watched_paths.insert(path.clone(), modified);
+            changes.push((path, AssetChangeType::Created));
+        }
+
         for (path, last_modified) in &self.watched_paths {
             match std::fs::metadata(path) {
                 Ok(metadata) => {
                     if let Ok(modified) = metadata.modified() {
-                        if modified > *last_modified {
+                        if modified != *last_modified {
                             changes.push((path.clone(), AssetChangeType::Modified));
                         }
                     }
@@ -117,6 +136,7 @@ impl FileWatcher {
 
         for path in to_remove {
             self.watched_paths.remove(&path);
+            self.missing_paths.insert(path);
         }
 
         for (path, change_type) in &changes {
@@ -129,6 +149,7 @@ impl FileWatcher {
             }
         }
 
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
         changes
     }
 
@@ -137,6 +158,10 @@ impl FileWatcher {
     }
 }
 
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
 impl Default for FileWatcher {
     fn default() -> Self {
         Self::new()
@@ -162,12 +187,26 @@ impl AssetRegistry {
         }
     }
 
+    /// Starts watching every registered asset, including ones registered
+    /// before hot reload was enabled.
     pub fn enable_hot_reload(&mut self) {
+        if self.hot_reload_enabled {
+            return;
+        }
         self.hot_reload_enabled = true;
+        for path in self.path_to_id.keys() {
+            self.file_watcher.watch(path);
+        }
     }
 
     pub fn disable_hot_reload(&mut self) {
+        if !self.hot_reload_enabled {
+            return;
+        }
         self.hot_reload_enabled = false;
+        for path in self.path_to_id.keys() {
+            self.file_watcher.unwatch(path);
+        }
     }
 
     pub fn is_hot_reload_enabled(&self) -> bool {
@@ -240,13 +279,14 @@ impl AssetRegistry {
         }
     }
 
+    /// Changes to registered assets since the last check, ordered by ID.
     pub fn check_for_changes(&mut self) -> Vec<AssetChangeEvent> {
         if !self.hot_reload_enabled {
             return Vec::new();
         }
 
         let changes = self.file_watcher.check_changes();
-        changes
+        let mut events: Vec<AssetChangeEvent> = changes
             .into_iter()
             .filter_map(|(path, change_type)| {
                 self.path_to_id.get(&path).map(|&id| AssetChangeEvent {
@@ -255,7 +295,9 @@ impl AssetRegistry {
                     change_type,
                 })
             })
-            .collect()
+            .collect();
+        events.sort_by_key(|event| event.id.0);
+        events
     }
 
     pub fn get_dependents(&self, id: AssetId) -> Vec<AssetId> {
@@ -264,21 +306,18 @@ impl AssetRegistry {
             .map_or_else(Vec::new, |meta| meta.dependents.clone())
     }
 
+    /// `changed_id` followed by everything depending on it, directly or
+    /// not, nearest first. Each asset is listed once.
     pub fn get_reload_list(&self, changed_id: AssetId) -> Vec<AssetId> {
         let mut to_reload = vec![changed_id];
-        let mut visited = HashSet::new();
-        let mut queue = vec![changed_id];
-
-        while let Some(id) = queue.pop() {
-            if visited.contains(&id) {
-                continue;
-            }
-            visited.insert(id);
+        let mut visited = HashSet::from([changed_id]);
+        let mut next = 0;
 
+        while let Some(&id) = to_reload.get(next) {
+            next += 1;
             for dep_id in self.get_dependents(id) {
-                if !visited.contains(&dep_id) {
+                if visited.insert(dep_id) {
                     to_reload.push(dep_id);
-                    queue.push(dep_id);
                 }
             }
         }
@@ -151,19 +151,26 @@ impl AudioTrack {
         ));
     }
 
+    /// Advances the current fade. A finished fade-in is cleared; a finished
+    /// fade-out is kept so the track stays silent until the mixer stops it.
     pub fn update(&mut self, delta_secs: f32) {
-        if let Some(fade) = &mut self.current_fade {
-            match fade {
-                Fade::In(settings) | Fade::Out(settings) => {
-                    settings.update(delta_secs);
-                    if settings.is_complete() {
-                        self.current_fade = None;
-                    }
+        match &mut self.current_fade {
+            Some(Fade::In(settings)) => {
+                settings.update(delta_secs);
+                if settings.is_complete() {
+                    self.current_fade = None;
                 }
             }
+            Some(Fade::Out(settings)) => settings.update(delta_secs),
+            None => {}
         }
     }
 
+    /// True once a fade-out has reached silence.
+    pub fn faded_out(&self) -> bool {
+        matches!(&self.current_fade, Some(Fade::Out(settings)) if settings.is_complete())
+    }
+
     pub fn effective_volume(&self) -> f32 {
         let base_volume = self.settings.volume;
         match &self.current_fade {
@@ -287,7 +294,10 @@ impl AudioMixer {
         let track_id = self.next_track_id;
         self.next_track_id += 1;
 
-        let track = AudioTrack::new(path).with_settings(settings);
+        let mut track = AudioTrack::new(path).with_settings(settings);
+        if let Some(fade_in) = track.settings.fade_in {
+            track.start_fade_in(fade_in.duration_secs);
+        }
         self.tracks.insert(track_id, track);
 
         if let Some(ch) = self.channels.get_mut(channel) {
@@ -334,10 +344,8 @@ impl AudioMixer {
         let mut completed = Vec::new();
         for (&id, track) in self.tracks.iter_mut() {
             track.update(delta_secs);
-            if let Some(Fade::Out(settings)) = &track.current_fade {
-                if settings.is_complete() {
-                    completed.push(id);
-                }
+            if track.faded_out() {
+                completed.push(id);
             }
         }
         for id in completed {
@@ -363,12 +371,28 @@ impl AudioMixer {
     }
 }
 
+/// Seed of the shuffle order until `AudioQueue::seed` is called.
+pub const DEFAULT_AUDIO_QUEUE_SEED: u64 = 0x5eed_a0d1;
+
+/// Playlist of track paths, played in order or shuffled.
+///
+/// While shuffled, every track plays once before any repeats: the queue
+/// walks a random permutation of the tracks, and a looping queue draws a
+/// new one each time it wraps, never starting with the track that just
+/// ended. The previous cycle is kept so `previous` can step back into it.
 #[derive(Debug, Clone)]
 pub struct AudioQueue {
-    pub tracks: Vec<String>,
-    pub current_index: usize,
+    tracks: Vec<String>,
+    current_index: usize,
     pub loop_queue: bool,
-    pub shuffle: bool,
+    shuffle: bool,
+    /// Track indices in shuffled play order: the previous cycle, if any,
+    /// then the current one from `cycle_start`.
+    order: Vec<usize>,
+    cycle_start: usize,
+    /// Place of the current track in `order`; `order.len()` once it ran out.
+    position: usize,
+    rng_state: u64,
 }
 
 impl Default for AudioQueue {
@@ -378,6 +402,10 @@ impl Default for AudioQueue {
             current_index: 0,
             loop_queue: false,
             shuffle: false,
+            order: Vec::new(),
+            cycle_start: 0,
+            position: 0,
+            rng_state: DEFAULT_AUDIO_QUEUE_SEED,
         }
     }
 }
@@ -387,23 +415,125 @@ impl AudioQueue {
         Self::default()
     }
 
+    /// Restarts the shuffle generator; the same seed gives the same orders.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng_state = seed;
+    }
+
+    /// SplitMix64, so a seed shuffles the same way on every platform.
+    fn next_random(&mut self) -> u64 {
+        self.rng_state = self.rng_state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..bound`; `bound` must be positive.
+    fn random_below(&mut self, bound: usize) -> usize {
+        (self.next_random() % bound as u64) as usize
+    }
+
+    fn shuffle_in_place(&mut self, indices: &mut [usize]) {
+        for i in (1..indices.len()).rev() {
+            let j = self.random_below(i + 1);
+            indices.swap(i, j);
+        }
+    }
+
     pub fn add(&mut self, path: String) {
+        let index = self.tracks.len();
         self.tracks.push(path);
+        if self.shuffle {
+            // Somewhere among the tracks still to come this cycle.
+            let first = (self.position + 1).min(self.order.len());
+            let slot = first + self.random_below(self.order.len() - first + 1);
+            self.order.insert(slot, index);
+        }
     }
 
     pub fn add_all(&mut self, paths: Vec<String>) {
-        self.tracks.extend(paths);
+        for path in paths {
+            self.add(path);
+        }
+    }
+
+    pub fn tracks(&self) -> &[String] {
+        &self.tracks
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current_index
     }
 
     pub fn current(&self) -> Option<&String> {
         self.tracks.get(self.current_index)
     }
 
+    pub fn is_shuffled(&self) -> bool {
+        self.shuffle
+    }
+
+    /// Turning shuffle on keeps the current track and shuffles the rest
+    /// after it; turning it off continues in order from the current track.
+    pub fn set_shuffle(&mut self, shuffle: bool) {
+        if shuffle == self.shuffle {
+            return;
+        }
+        self.shuffle = shuffle;
+        self.order.clear();
+        self.cycle_start = 0;
+        self.position = 0;
+        if !shuffle {
+            return;
+        }
+
+        let mut rest: Vec<usize> = (0..self.tracks.len())
+            .filter(|&index| index != self.current_index)
+            .collect();
+        self.shuffle_in_place(&mut rest);
+        if self.current_index < self.tracks.len() {
+            self.order.push(self.current_index);
+        } else {
+            self.position = rest.len();
+        }
+        self.order.extend(rest);
+    }
+
+    /// Appends the next cycle of a looping shuffled queue, dropping the
+    /// cycle before the one that just ended.
+    fn reshuffle(&mut self) {
+        let mut cycle: Vec<usize> = (0..self.tracks.len()).collect();
+        self.shuffle_in_place(&mut cycle);
+        if cycle.len() > 1 && cycle[0] == self.current_index {
+            let other = 1 + self.random_below(cycle.len() - 1);
+            cycle.swap(0, other);
+        }
+        self.order.drain(..self.cycle_start);
+        self.cycle_start = self.order.len();
+        self.position = self.order.len();
+        self.order.extend(cycle);
+    }
+
     pub fn next(&mut self) -> Option<&String> {
         if self.tracks.is_empty() {
             return None;
         }
 
+        if self.shuffle {
+            if self.position + 1 < self.order.len() {
+                self.position += 1;
+            } else if self.loop_queue {
+                self.reshuffle();
+            } else {
+                self.position = self.order.len();
+                self.current_index = self.tracks.len();
+                return None;
+            }
+            self.current_index = self.order[self.position];
+            return self.current();
+        }
+
         self.current_index += 1;
         if self.current_index >= self.tracks.len() {
             if self.loop_queue {
@@ -420,6 +550,19 @@ impl AudioQueue {
             return None;
         }
 
+        if self.shuffle {
+            if self.position == 0 {
+                if !self.loop_queue {
+                    return None;
+                }
+                self.position = self.order.len() - 1;
+            } else {
+                self.position -= 1;
+            }
+            self.current_index = self.order[self.position];
+            return self.current();
+        }
+
         if self.current_index == 0 {
             if self.loop_queue {
                 self.current_index = self.tracks.len() - 1;
@@ -435,6 +578,9 @@ impl AudioQueue {
     pub fn clear(&mut self) {
         self.tracks.clear();
         self.current_index = 0;
+        self.order.clear();
+        self.cycle_start = 0;
+        self.position = 0;
     }
 
     pub fn len(&self) -> usize {
@@ -11,7 +11,7 @@ pub use asset::{
     AssetMeta, AssetRegistry, FileWatcher,
 };
 pub use audio::{
-    AudioChannel, AudioMixer, AudioQueue, AudioSettings, AudioTrack, FadeSettings, PlaybackMode,
+    AudioChannel, AudioMixer, AudioQueue, AudioSettings, AudioTrack, DEFAULT_AUDIO_QUEUE_SEED, FadeSettings, PlaybackMode,
     SpatialAudio,
 };
 pub use camera::{
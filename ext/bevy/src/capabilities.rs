@@ -0,0 +1,75 @@
+use magnus::{Error, RHash, RModule, Ruby, Symbol, TryConvert, Value, function, prelude::*};
+use std::cell::RefCell;
+
+thread_local! {
+    /// Optional subsystems in registration order, with whether this build provides them.
+    static CAPABILITIES: RefCell<Vec<(&'static str, bool)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Records whether an optional subsystem is available in this build.
+///
+/// Called from the `define` of the module that owns the subsystem, so the
+/// list follows the modules that are actually registered.
+pub fn register(name: &'static str, available: bool) {
+    CAPABILITIES.with(|capabilities| {
+        let mut capabilities = capabilities.borrow_mut();
+        match capabilities.iter_mut().find(|(registered, _)| *registered == name) {
+            Some(entry) => entry.1 = available,
+            None => capabilities.push((name, available)),
+        }
+    });
+}
+
+/// Registered subsystems and whether each is available, in registration order.
+pub(crate) fn registered() -> Vec<(String, bool)> {
+    CAPABILITIES.with(|capabilities| {
+        capabilities
+            .borrow()
+            .iter()
+            .map(|(name, available)| (name.to_string(), *available))
+            .collect()
+    })
+}
+
+fn version() -> &'static str {
+    bevy_ruby::VERSION
+}
+
+fn bevy_version() -> &'static str {
+    bevy_ruby::BEVY_VERSION
+}
+
+/// Frozen `{name => available}` hash keyed by symbols.
+fn capabilities() -> Result<RHash, Error> {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    let capabilities = CAPABILITIES.with(|capabilities| capabilities.borrow().clone());
+    let hash = ruby.hash_new_capa(capabilities.len());
+    for (name, available) in capabilities {
+        hash.aset(ruby.to_symbol(name), available)?;
+    }
+    hash.freeze();
+    Ok(hash)
+}
+
+/// True if `name` (a Symbol or String) is registered and available.
+fn has_capability(name: Value) -> Result<bool, Error> {
+    let name = match Symbol::try_convert(name) {
+        Ok(symbol) => symbol.name()?.to_string(),
+        Err(_) => String::try_convert(name)?,
+    };
+    Ok(CAPABILITIES.with(|capabilities| {
+        capabilities
+            .borrow()
+            .iter()
+            .any(|(registered, available)| *registered == name && *available)
+    }))
+}
+
+pub fn define(_ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    module.define_module_function("version", function!(version, 0))?;
+    module.define_module_function("bevy_version", function!(bevy_version, 0))?;
+    module.define_module_function("capabilities", function!(capabilities, 0))?;
+    module.define_module_function("capability?", function!(has_capability, 1))?;
+
+    Ok(())
+}
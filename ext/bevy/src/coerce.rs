@@ -0,0 +1,158 @@
+//! Lenient conversion of values read from option and data hashes.
+//!
+//! Anything numeric is accepted where a float is expected, and the value
+//! must fit in an `f32` because that is what Bevy stores. Errors name the
+//! key and the class of the offending value.
+
+use magnus::{Error, RArray, RHash, RModule, Ruby, TryConvert, Value, function, prelude::*, value::Id};
+use std::cell::Cell;
+
+thread_local! {
+    /// `Bevy.coerce_strings`: parse numeric Strings where floats are expected.
+    static COERCE_STRINGS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A type that can be read from a hash value stored under a named key.
+pub(crate) trait HashValue: Sized {
+    fn from_hash_value(ruby: &Ruby, key: Id, value: Value) -> Result<Self, Error>;
+}
+
+/// Reads `hash[key]`; missing keys and `nil` are `None`.
+pub(crate) fn hash_value<T: HashValue>(ruby: &Ruby, hash: &RHash, key: Id) -> Result<Option<T>, Error> {
+    match hash.get(key) {
+        Some(value) if !value.is_nil() => Ok(Some(T::from_hash_value(ruby, key, value)?)),
+        _ => Ok(None),
+    }
+}
+
+fn key_name(key: Id) -> String {
+    key.name().map_or_else(|_| "?".to_string(), str::to_string)
+}
+
+fn class_name(value: Value) -> String {
+    value.class().inspect()
+}
+
+/// Converts an Integer, Float, Rational, BigDecimal or (with `Bevy.coerce_strings`)
+/// numeric String to a float that fits in an `f32`.
+pub(crate) fn coerce_float(ruby: &Ruby, key: Id, value: Value) -> Result<f64, Error> {
+    let is_float = value.is_kind_of(ruby.class_float());
+    let number = if is_float || value.is_kind_of(ruby.class_integer()) {
+        f64::try_convert(value)?
+    } else if value.is_kind_of(ruby.class_numeric()) {
+        value.funcall::<_, _, f64>("to_f", ()).map_err(|error| {
+            Error::new(
+                ruby.exception_type_error(),
+                format!(
+                    "invalid value for :{}: {} cannot be converted to a float ({})",
+                    key_name(key),
+                    class_name(value),
+                    error
+                ),
+            )
+        })?
+    } else if value.is_kind_of(ruby.class_string()) {
+        if !COERCE_STRINGS.with(Cell::get) {
+            return Err(Error::new(
+                ruby.exception_type_error(),
+                format!(
+                    "invalid value for :{}: expected a number, got String (set Bevy.coerce_strings = true to parse numeric strings)",
+                    key_name(key)
+                ),
+            ));
+        }
+        let text = String::try_convert(value)?;
+        text.trim().parse::<f64>().map_err(|_| {
+            Error::new(
+                ruby.exception_arg_error(),
+                format!("invalid value for :{}: {:?} (String) is not a number", key_name(key), text),
+            )
+        })?
+    } else {
+        return Err(Error::new(
+            ruby.exception_type_error(),
+            format!("invalid value for :{}: expected a number, got {}", key_name(key), class_name(value)),
+        ));
+    };
+
+    // Floats may be explicitly infinite or NaN; anything else overflowed converting.
+    let overflowed = if is_float {
+        number.is_finite() && number.abs() > f32::MAX as f64
+    } else {
+        !number.is_finite() || number.abs() > f32::MAX as f64
+    };
+    if overflowed {
+        return Err(Error::new(
+            ruby.exception_range_error(),
+            format!(
+                "invalid value for :{}: {} ({}) is out of range for a 32-bit float",
+                key_name(key),
+                value.inspect(),
+                class_name(value)
+            ),
+        ));
+    }
+    Ok(number)
+}
+
+impl HashValue for f64 {
+    fn from_hash_value(ruby: &Ruby, key: Id, value: Value) -> Result<Self, Error> {
+        coerce_float(ruby, key, value)
+    }
+}
+
+impl HashValue for Vec<f64> {
+    fn from_hash_value(ruby: &Ruby, key: Id, value: Value) -> Result<Self, Error> {
+        let array = RArray::from_hash_value(ruby, key, value)?;
+        array
+            .into_iter()
+            .map(|element| coerce_float(ruby, key, element))
+            .collect()
+    }
+}
+
+/// Strict conversions; failures are re-raised naming the key and value class.
+macro_rules! strict_hash_value {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl HashValue for $ty {
+                fn from_hash_value(ruby: &Ruby, key: Id, value: Value) -> Result<Self, Error> {
+                    TryConvert::try_convert(value).map_err(|error: Error| {
+                        let class = if error.is_kind_of(ruby.exception_range_error()) {
+                            ruby.exception_range_error()
+                        } else {
+                            ruby.exception_type_error()
+                        };
+                        Error::new(
+                            class,
+                            format!(
+                                "invalid value for :{}: {} ({})",
+                                key_name(key),
+                                class_name(value),
+                                error
+                            ),
+                        )
+                    })
+                }
+            }
+        )*
+    };
+}
+
+strict_hash_value!(bool, i32, i64, u32, u64, usize, String, Value, RArray, RHash, Vec<(f64, f64)>);
+
+fn set_coerce_strings(enabled: bool) -> bool {
+    COERCE_STRINGS.with(|flag| flag.set(enabled));
+    enabled
+}
+
+fn coerce_strings() -> bool {
+    COERCE_STRINGS.with(Cell::get)
+}
+
+pub fn define(_ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    module.define_module_function("coerce_strings=", function!(set_coerce_strings, 1))?;
+    module.define_module_function("coerce_strings", function!(coerce_strings, 0))?;
+
+    Ok(())
+}
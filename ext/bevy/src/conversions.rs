@@ -1,6 +1,8 @@
 use bevy_ruby::types::{DynamicValue, RubyColor, RubyQuat, RubyTransform, RubyVec2, RubyVec3};
 use magnus::{prelude::*, Error, RHash, Ruby, TryConvert, Value};
 
+use crate::coerce::{HashValue, hash_value};
+
 pub fn ruby_hash_to_dynamic_value(ruby: &Ruby, hash: &RHash) -> Result<std::collections::HashMap<String, DynamicValue>, Error> {
     let mut result = std::collections::HashMap::new();
 
@@ -118,20 +120,10 @@ pub fn transform_from_hash(ruby: &Ruby, hash: &RHash) -> Result<RubyTransform, E
     Ok(RubyTransform::from_translation_rotation_scale(translation, quat, scale))
 }
 
-fn get_hash_value<T: TryConvert>(ruby: &Ruby, hash: &RHash, key: &str) -> Result<Option<T>, Error> {
-    let sym = ruby.to_symbol(key);
-    match hash.get(sym) {
-        Some(val) => {
-            if val.is_nil() {
-                Ok(None)
-            } else {
-                Ok(Some(TryConvert::try_convert(val)?))
-            }
-        }
-        None => Ok(None),
-    }
+fn get_hash_value<T: HashValue>(ruby: &Ruby, hash: &RHash, key: &str) -> Result<Option<T>, Error> {
+    hash_value(ruby, hash, ruby.intern(key))
 }
 
-fn get_hash_value_or_default<T: TryConvert>(ruby: &Ruby, hash: &RHash, key: &str, default: T) -> Result<T, Error> {
+fn get_hash_value_or_default<T: HashValue>(ruby: &Ruby, hash: &RHash, key: &str, default: T) -> Result<T, Error> {
     get_hash_value(ruby, hash, key).map(|opt| opt.unwrap_or(default))
 }
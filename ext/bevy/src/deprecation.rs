@@ -0,0 +1,265 @@
+//! Forwarding shims for renamed Ruby methods.
+//!
+//! `deprecated!` keeps an old method name working by defining it as a
+//! forwarder to its replacement that reports the call first. Each call site
+//! (the caller string Ruby reports for the call) is recorded once, and
+//! `Bevy.deprecation_report` lists what was recorded. `Bevy.deprecations =
+//! :raise` turns every deprecated call into `Bevy::DeprecationError` instead,
+//! and `:silent` ignores them.
+
+use magnus::{Error, ExceptionClass, RArray, RModule, Ruby, Value, function, prelude::*};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use crate::ruby_render_app::symbol_or_string;
+
+/// Call sites recorded before further ones are only counted.
+pub const MAX_DEPRECATION_NOTICES: usize = 256;
+
+/// Defines `$old` on `$class` as a forwarder to `$new` that reports the call,
+/// naming `$removal` as the version the old name goes away in.
+macro_rules! deprecated {
+    ($class:expr, $old:literal => $new:literal, $removal:literal) => {
+        $crate::deprecation::define_deprecated($class, $old, $new, $removal)
+    };
+}
+pub(crate) use deprecated;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeprecationMode {
+    Silent,
+    Collect,
+    Raise,
+}
+
+impl DeprecationMode {
+    const NAMES: [&'static str; 3] = ["silent", "collect", "raise"];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "silent" => Some(Self::Silent),
+            "collect" => Some(Self::Collect),
+            "raise" => Some(Self::Raise),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Silent => "silent",
+            Self::Collect => "collect",
+            Self::Raise => "raise",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct DeprecationNotice {
+    method: String,
+    replacement: String,
+    removal_version: String,
+    call_site: String,
+}
+
+impl DeprecationNotice {
+    fn message(&self) -> String {
+        format!(
+            "{} is deprecated and will be removed in {}; use {} instead",
+            self.method, self.removal_version, self.replacement
+        )
+    }
+}
+
+struct DeprecationLog {
+    mode: DeprecationMode,
+    /// Notices in the order their call site was first seen.
+    notices: Vec<DeprecationNotice>,
+    /// `(method, call_site)` of every recorded notice.
+    seen: BTreeSet<(String, String)>,
+    /// Call sites not recorded because the log was full.
+    dropped: usize,
+}
+
+impl DeprecationLog {
+    const fn new() -> Self {
+        Self {
+            mode: DeprecationMode::Collect,
+            notices: Vec::new(),
+            seen: BTreeSet::new(),
+            dropped: 0,
+        }
+    }
+
+    fn record(&mut self, notice: DeprecationNotice) {
+        if self.seen.contains(&(notice.method.clone(), notice.call_site.clone())) {
+            return;
+        }
+        if self.notices.len() >= MAX_DEPRECATION_NOTICES {
+            self.dropped += 1;
+            return;
+        }
+        self.seen
+            .insert((notice.method.clone(), notice.call_site.clone()));
+        self.notices.push(notice);
+    }
+
+    fn clear(&mut self) {
+        self.notices.clear();
+        self.seen.clear();
+        self.dropped = 0;
+    }
+}
+
+/// Shared by every Ruby thread, so shims called off the main thread are recorded too.
+static DEPRECATIONS: Mutex<DeprecationLog> = Mutex::new(DeprecationLog::new());
+
+fn with_log<T>(f: impl FnOnce(&mut DeprecationLog) -> T) -> T {
+    let mut log = DEPRECATIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut log)
+}
+
+fn is_method_name(name: &str) -> bool {
+    let body = name.strip_suffix(['?', '!', '=']).unwrap_or(name);
+    body.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && body.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Defines `old` on `class` (a class or module) as a forwarder to `new`.
+pub(crate) fn define_deprecated(class: Value, old: &str, new: &str, removal_version: &str) -> Result<(), Error> {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    if !class.is_kind_of(ruby.class_module()) {
+        return Err(Error::new(
+            ruby.exception_type_error(),
+            format!("expected a class or module, got {}", class.class().inspect()),
+        ));
+    }
+    for name in [old, new] {
+        if !is_method_name(name) {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("invalid method name: {:?}", name),
+            ));
+        }
+    }
+
+    let owner = class.funcall::<_, _, String>("to_s", ())?;
+    let source = format!(
+        "def {old}(*args, **kwargs, &block)\n\
+         \x20 ::Bevy.record_deprecation({method:?}, {replacement:?}, {removal_version:?}, caller(1, 1).first.to_s)\n\
+         \x20 __send__(:{new}, *args, **kwargs, &block)\n\
+         end",
+        method = format!("{}#{}", owner, old),
+        replacement = format!("{}#{}", owner, new),
+    );
+    class.funcall::<_, _, Value>("class_eval", (source, "(bevy deprecation)", 1))?;
+    Ok(())
+}
+
+/// `Bevy.deprecate_method(klass, old, new, removal_version)`: `deprecated!`
+/// for methods defined in Ruby.
+fn deprecate_method(class: Value, old: Value, new: Value, removal_version: String) -> Result<(), Error> {
+    define_deprecated(class, &symbol_or_string(old)?, &symbol_or_string(new)?, &removal_version)
+}
+
+fn deprecation_error(ruby: &Ruby, notice: &DeprecationNotice) -> Error {
+    let class = ruby
+        .define_module("Bevy")
+        .and_then(|module| module.const_get::<_, ExceptionClass>("DeprecationError"))
+        .unwrap_or_else(|_| ruby.exception_runtime_error());
+    Error::new(class, format!("{} (called from {})", notice.message(), notice.call_site))
+}
+
+/// Called by the shims before forwarding.
+fn record_deprecation(method: String, replacement: String, removal_version: String, call_site: String) -> Result<(), Error> {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    let notice = DeprecationNotice {
+        method,
+        replacement,
+        removal_version,
+        call_site,
+    };
+    match with_log(|log| log.mode) {
+        DeprecationMode::Silent => Ok(()),
+        DeprecationMode::Collect => {
+            with_log(|log| log.record(notice));
+            Ok(())
+        }
+        DeprecationMode::Raise => Err(deprecation_error(&ruby, &notice)),
+    }
+}
+
+fn set_deprecations(mode: Value) -> Result<Value, Error> {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    let name = symbol_or_string(mode)?;
+    let parsed = DeprecationMode::from_name(&name).ok_or_else(|| {
+        Error::new(
+            ruby.exception_arg_error(),
+            format!(
+                "unknown deprecation mode: {} (expected {})",
+                name,
+                DeprecationMode::NAMES.join(", ")
+            ),
+        )
+    })?;
+    with_log(|log| log.mode = parsed);
+    Ok(mode)
+}
+
+fn deprecations() -> Value {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    ruby.to_symbol(with_log(|log| log.mode).name()).as_value()
+}
+
+/// `[{ method:, replacement:, removal_version:, call_site: }, ...]` in the
+/// order the call sites were first seen.
+fn deprecation_notices() -> Result<RArray, Error> {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    let notices = with_log(|log| log.notices.clone());
+    let array = ruby.ary_new_capa(notices.len());
+    for notice in notices {
+        let hash = ruby.hash_new_capa(4);
+        hash.aset(ruby.to_symbol("method"), notice.method)?;
+        hash.aset(ruby.to_symbol("replacement"), notice.replacement)?;
+        hash.aset(ruby.to_symbol("removal_version"), notice.removal_version)?;
+        hash.aset(ruby.to_symbol("call_site"), notice.call_site)?;
+        array.push(hash)?;
+    }
+    Ok(array)
+}
+
+/// One line per recorded call site, grouped by method, plus a line counting
+/// the call sites dropped once the log was full; empty when nothing was recorded.
+fn deprecation_report() -> String {
+    let (mut notices, dropped) = with_log(|log| (log.notices.clone(), log.dropped));
+    notices.sort_by(|a, b| a.method.cmp(&b.method));
+
+    let mut report = String::new();
+    for notice in &notices {
+        report.push_str(&format!("{} (called from {})\n", notice.message(), notice.call_site));
+    }
+    if dropped > 0 {
+        report.push_str(&format!(
+            "{} more call site{} not recorded (limit {})\n",
+            dropped,
+            if dropped == 1 { "" } else { "s" },
+            MAX_DEPRECATION_NOTICES
+        ));
+    }
+    report
+}
+
+fn clear_deprecations() {
+    with_log(DeprecationLog::clear);
+}
+
+pub fn define(_ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    module.define_module_function("deprecate_method", function!(deprecate_method, 4))?;
+    module.define_module_function("record_deprecation", function!(record_deprecation, 4))?;
+    module.define_module_function("deprecations=", function!(set_deprecations, 1))?;
+    module.define_module_function("deprecations", function!(deprecations, 0))?;
+    module.define_module_function("deprecation_notices", function!(deprecation_notices, 0))?;
+    module.define_module_function("deprecation_report", function!(deprecation_report, 0))?;
+    module.define_module_function("clear_deprecations", function!(clear_deprecations, 0))?;
+
+    Ok(())
+}
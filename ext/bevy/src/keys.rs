@@ -0,0 +1,193 @@
+//! Hash keys interned once at init.
+//!
+//! Looking a key up through `ruby.to_symbol` hashes the name on every call;
+//! these are resolved to Ruby IDs when the extension loads instead.
+
+use magnus::{Ruby, value::LazyId};
+
+macro_rules! keys {
+    ($($name:ident => $key:literal,)*) => {
+        $(pub static $name: LazyId = LazyId::new($key);)*
+
+        /// Interns every key so the first frame doesn't pay for it.
+        pub fn init(ruby: &Ruby) {
+            $(LazyId::force(&$name, ruby);)*
+        }
+    };
+}
+
+keys! {
+    ACCESSIBLE_LABEL => "accessible_label",
+    ACCESSIBLE_ROLE => "accessible_role",
+    ACTUAL => "actual",
+    AMPLITUDE => "amplitude",
+    ANCHOR_X => "anchor_x",
+    ANCHOR_Y => "anchor_y",
+    ATLAS_RECT => "atlas_rect",
+    AVOID_REPEAT => "avoid_repeat",
+    AXES => "axes",
+    AXIS => "axis",
+    BATTERY => "battery",
+    BLEND => "blend",
+    BLOCKING_GROUP => "blocking_group",
+    BUTTON => "button",
+    BUTTONS_JUST_PRESSED => "buttons_just_pressed",
+    BUTTONS_JUST_RELEASED => "buttons_just_released",
+    BUTTONS_PRESSED => "buttons_pressed",
+    CAMERA => "camera",
+    CAMERA_ID => "camera_id",
+    CAPACITY => "capacity",
+    CATEGORY => "category",
+    CELL_SIZE => "cell_size",
+    CHANGE_TYPE => "change_type",
+    CHANNEL => "channel",
+    CLEAR_COLOR => "clear_color",
+    COLOR_A => "color_a",
+    COLOR_B => "color_b",
+    COLOR_G => "color_g",
+    COLOR_R => "color_r",
+    CONSTRAIN_TO => "constrain_to",
+    CONTENT => "content",
+    COUNT => "count",
+    COVERED_SECONDS => "covered_seconds",
+    CUSTOM_SIZE_X => "custom_size_x",
+    CUSTOM_SIZE_Y => "custom_size_y",
+    DAMPING => "damping",
+    DEAD_ZONE => "dead_zone",
+    DECORATIONS => "decorations",
+    DEFER => "defer",
+    DELTA => "delta",
+    DEPTH => "depth",
+    DISTANCE => "distance",
+    DRAG_LAYER => "drag_layer",
+    DROPPED_AT => "dropped_at",
+    DROP_TARGET => "drop_target",
+    DURATION => "duration",
+    EASING => "easing",
+    ENTITY_ID => "entity_id",
+    EXPECTED => "expected",
+    EXPONENT => "exponent",
+    FADE_IN => "fade_in",
+    FILL => "fill",
+    FIRST_FRAME => "first_frame",
+    FLIP_X => "flip_x",
+    FLIP_Y => "flip_y",
+    FONT_SIZE => "font_size",
+    FORCE => "force",
+    FRAME => "frame",
+    FRAMES => "frames",
+    GAMEPAD_BUTTONS => "gamepad_buttons",
+    GAMEPAD_SLOT => "gamepad_slot",
+    GLOW_A => "glow_a",
+    GLOW_B => "glow_b",
+    GLOW_G => "glow_g",
+    GLOW_INTENSITY => "glow_intensity",
+    GLOW_R => "glow_r",
+    GLOW_RADIUS => "glow_radius",
+    GRADIENT_COLOR => "gradient_color",
+    GRADIENT_DIRECTION => "gradient_direction",
+    GROUP => "group",
+    HEADLESS => "headless",
+    HEIGHT => "height",
+    HIDE => "hide",
+    HIT_NORMAL => "hit_normal",
+    HIT_POSITION => "hit_position",
+    HIT_REGION => "hit_region",
+    ICON => "icon",
+    ID => "id",
+    IGNORE => "ignore",
+    INSETS => "insets",
+    INTERVAL => "interval",
+    JUSTIFY => "justify",
+    KEYFRAMES => "keyframes",
+    KEYS => "keys",
+    KIND => "kind",
+    LAYER => "layer",
+    LINEBREAK => "linebreak",
+    LINE_CAP => "line_cap",
+    LINE_END_X => "line_end_x",
+    LINE_END_Y => "line_end_y",
+    LINE_JOIN => "line_join",
+    LINE_START_X => "line_start_x",
+    LINE_START_Y => "line_start_y",
+    LOOP => "loop",
+    MAX_FPS => "max_fps",
+    MAX_LOG_BYTES => "max_log_bytes",
+    MAX_MB => "max_mb",
+    MAX_MEMORY_BYTES => "max_memory_bytes",
+    MAX_SCALE => "max_scale",
+    MEMORY_BYTES => "memory_bytes",
+    MESH_MODE => "mesh_mode",
+    MESH_SYNC => "mesh_sync",
+    MESSAGE => "message",
+    MIN_HEIGHT => "min_height",
+    MIN_WIDTH => "min_width",
+    MODE => "mode",
+    MOUSE_BUTTONS => "mouse_buttons",
+    MSAA => "msaa",
+    NAME => "name",
+    OFFSET => "offset",
+    ORIGIN => "origin",
+    OVERSHOOT => "overshoot",
+    PATH => "path",
+    PAUSED => "paused",
+    PERIOD => "period",
+    PHASE => "phase",
+    PITCH_VARIATION => "pitch_variation",
+    PIXEL_PERFECT => "pixel_perfect",
+    POINTER_ID => "pointer_id",
+    POSITION => "position",
+    PRESENT_MODE => "present_mode",
+    PREVIOUS_POSITION => "previous_position",
+    PROPERTY => "property",
+    RADIUS => "radius",
+    RESIZABLE => "resizable",
+    REWINDING => "rewinding",
+    RISE => "rise",
+    ROTATION => "rotation",
+    ROTATION_Z => "rotation_z",
+    SCALE => "scale",
+    SCALE_FACTOR => "scale_factor",
+    SCALE_MAX => "scale_max",
+    SCALE_MIN => "scale_min",
+    SCALE_X => "scale_x",
+    SCALE_Y => "scale_y",
+    SCALE_Z => "scale_z",
+    SCREEN_SPACE => "screen_space",
+    SHAPE_TYPE => "shape_type",
+    SHOW => "show",
+    SIDES => "sides",
+    SLEEPING_COUNT => "sleeping_count",
+    SNAPPED_BACK => "snapped_back",
+    SNAPSHOTS => "snapshots",
+    SNAP_BACK => "snap_back",
+    SPEED => "speed",
+    SPRITE => "sprite",
+    SPRITES => "sprites",
+    STIFFNESS => "stiffness",
+    SUBDIVISIONS => "subdivisions",
+    SYSTEMS => "systems",
+    SYSTEMS_MAX => "systems_max",
+    TARGET_ID => "target_id",
+    TEXTURE_PATH => "texture_path",
+    TEXTURE_PLACEHOLDER => "texture_placeholder",
+    TEXT_SYNC => "text_sync",
+    THICKNESS => "thickness",
+    TITLE => "title",
+    TRACKS => "tracks",
+    TRANSLATION => "translation",
+    TRANSPARENT => "transparent",
+    TRUNCATED_SNAPSHOTS => "truncated_snapshots",
+    VIEWPORT => "viewport",
+    VOLUME => "volume",
+    VOLUME_VARIATION => "volume_variation",
+    VSYNC => "vsync",
+    WIDTH => "width",
+    WINDOW_EVENTS => "window_events",
+    WINDOW_ID => "window_id",
+    WRITE => "write",
+    X => "x",
+    Y => "y",
+    Z => "z",
+    Z_INDEX => "z_index",
+}
@@ -1,12 +1,47 @@
+mod capabilities;
+mod coerce;
 mod conversions;
+mod deprecation;
+mod keys;
 mod ruby_app;
+mod ruby_asset_registry;
+mod ruby_audio;
+mod ruby_audio_queue;
+mod ruby_behavior;
 mod ruby_color;
 mod ruby_component;
+mod ruby_debug_bundle;
+mod ruby_determinism;
+mod ruby_drag;
+mod ruby_draw_order;
+mod ruby_ease;
 mod ruby_entity;
+mod ruby_event;
+mod ruby_floating_text;
+mod ruby_gamepad_state;
+mod ruby_geometry;
+mod ruby_group_throttle;
+mod ruby_hit_region;
+mod ruby_jelly;
+mod ruby_key_code;
+mod ruby_key_hold;
+mod ruby_journal;
+mod ruby_line_of_sight;
+mod ruby_lod;
 mod ruby_math;
+mod ruby_palette;
+mod ruby_parallax;
+mod ruby_pixel_snap;
 mod ruby_query;
+mod ruby_rewind;
 mod ruby_render_app;
+mod ruby_sleep;
 mod ruby_system;
+mod ruby_system_timing;
+mod ruby_texture_loading;
+mod ruby_touch;
+mod ruby_transform;
+mod ruby_warnings;
 mod ruby_world;
 
 use magnus::{Error, Ruby};
@@ -14,16 +49,54 @@ use magnus::{Error, Ruby};
 #[magnus::init]
 fn init(ruby: &Ruby) -> Result<(), Error> {
     let module = ruby.define_module("Bevy")?;
+    keys::init(ruby);
 
+    capabilities::define(ruby, &module)?;
+    coerce::define(ruby, &module)?;
+    deprecation::define(ruby, &module)?;
     ruby_app::define(ruby, &module)?;
+    ruby_asset_registry::define(ruby, &module)?;
+    ruby_audio::define(ruby, &module)?;
+    ruby_audio_queue::define(ruby, &module)?;
     ruby_color::define(ruby, &module)?;
     ruby_component::define(ruby, &module)?;
+    ruby_debug_bundle::define(ruby, &module)?;
+    ruby_determinism::define(ruby, &module)?;
+    ruby_drag::define(ruby, &module)?;
+    ruby_draw_order::define(ruby, &module)?;
+    ruby_ease::define(ruby, &module)?;
+    ruby_floating_text::define(ruby, &module)?;
+    ruby_geometry::define(ruby, &module)?;
+    ruby_group_throttle::define(ruby, &module)?;
+    ruby_hit_region::define(ruby, &module)?;
+    ruby_jelly::define(ruby, &module)?;
+    ruby_key_code::define(ruby, &module)?;
+    ruby_journal::define(ruby, &module)?;
+    ruby_line_of_sight::define(ruby, &module)?;
+    ruby_lod::define(ruby, &module)?;
     ruby_math::define(ruby, &module)?;
+    ruby_palette::define(ruby, &module)?;
+    ruby_parallax::define(ruby, &module)?;
+    ruby_pixel_snap::define(ruby, &module)?;
     ruby_query::define(ruby, &module)?;
+    ruby_rewind::define(ruby, &module)?;
+    ruby_sleep::define(ruby, &module)?;
     ruby_system::define(ruby, &module)?;
+    ruby_system_timing::define(ruby, &module)?;
+    ruby_texture_loading::define(ruby, &module)?;
+    ruby_touch::define(ruby, &module)?;
+    ruby_transform::define(ruby, &module)?;
+    ruby_warnings::define(ruby, &module)?;
     ruby_world::define(ruby, &module)?;
     ruby_entity::define(ruby, &module)?;
+    ruby_event::define(ruby, &module)?;
     ruby_render_app::define(ruby, &module)?;
 
+    // Probes that let specs drive native logic directly; not public API.
+    let internal = module.define_module("Internal")?;
+    ruby_behavior::define(ruby, &internal)?;
+    ruby_gamepad_state::define(ruby, &internal)?;
+    ruby_key_hold::define(ruby, &internal)?;
+
     Ok(())
 }
@@ -4,6 +4,7 @@ use magnus::{function, method, prelude::*, Error, RModule, Ruby, Symbol};
 use std::cell::RefCell;
 use std::sync::Arc;
 
+use crate::capabilities;
 use crate::ruby_world::RubyWorld;
 
 #[magnus::wrap(class = "Bevy::AppBuilder", free_immediately, size)]
@@ -61,5 +62,7 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     class.define_method("systems_for_schedule", method!(RubyAppBuilder::systems_for_schedule, 1))?;
     class.define_method("create_world", method!(RubyAppBuilder::create_world, 0))?;
 
+    capabilities::register("headless_mode", true);
+
     Ok(())
 }
@@ -0,0 +1,129 @@
+use bevy_ruby_render::{AssetChangeType, AssetId, AssetRegistry};
+use magnus::{Error, RArray, RModule, Ruby, function, method, prelude::*};
+use std::cell::RefCell;
+use std::path::PathBuf;
+
+use crate::keys;
+
+fn change_type_name(change_type: AssetChangeType) -> &'static str {
+    match change_type {
+        AssetChangeType::Created => "created",
+        AssetChangeType::Modified => "modified",
+        AssetChangeType::Deleted => "deleted",
+    }
+}
+
+/// Asset files to watch for hot reload, and which assets depend on which.
+#[magnus::wrap(class = "Bevy::AssetRegistry", free_immediately, size)]
+pub struct MagnusAssetRegistry {
+    inner: RefCell<AssetRegistry>,
+}
+
+impl MagnusAssetRegistry {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(AssetRegistry::new()),
+        }
+    }
+
+    /// `register(path, type)`: the asset's ID; registering a path twice
+    /// returns the same ID.
+    fn register(&self, path: String, asset_type: String) -> u64 {
+        self.inner
+            .borrow_mut()
+            .register(PathBuf::from(path), asset_type)
+            .0
+    }
+
+    fn unregister(&self, id: u64) {
+        self.inner.borrow_mut().unregister(AssetId::new(id));
+    }
+
+    /// `add_dependency(id, dependency_id)`: `id` is reloaded whenever
+    /// `dependency_id` changes.
+    fn add_dependency(&self, id: u64, dependency_id: u64) {
+        self.inner
+            .borrow_mut()
+            .add_dependency(AssetId::new(id), AssetId::new(dependency_id));
+    }
+
+    fn path(&self, id: u64) -> Option<String> {
+        self.inner
+            .borrow()
+            .get(AssetId::new(id))
+            .map(|meta| meta.path.to_string_lossy().into_owned())
+    }
+
+    fn asset_type(&self, id: u64) -> Option<String> {
+        self.inner
+            .borrow()
+            .get(AssetId::new(id))
+            .map(|meta| meta.asset_type.clone())
+    }
+
+    fn enable_hot_reload(&self) {
+        self.inner.borrow_mut().enable_hot_reload();
+    }
+
+    fn disable_hot_reload(&self) {
+        self.inner.borrow_mut().disable_hot_reload();
+    }
+
+    fn is_hot_reload_enabled(&self) -> bool {
+        self.inner.borrow().is_hot_reload_enabled()
+    }
+
+    /// Changes since the last call as `{id:, path:, change_type:}`, with
+    /// `change_type` one of `"created"`, `"modified"` or `"deleted"`.
+    /// Always empty while hot reload is disabled.
+    fn check_for_changes(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let events = self.inner.borrow_mut().check_for_changes();
+        let result = ruby.ary_new_capa(events.len());
+        for event in events {
+            let hash = ruby.hash_new_capa(3);
+            hash.aset(*keys::ID, event.id.0)?;
+            hash.aset(*keys::PATH, event.path.to_string_lossy().into_owned())?;
+            hash.aset(*keys::CHANGE_TYPE, change_type_name(event.change_type))?;
+            result.push(hash)?;
+        }
+        Ok(result)
+    }
+
+    /// `id` followed by every asset to reload after it, nearest dependents first.
+    fn reload_list(&self, id: u64) -> Vec<u64> {
+        self.inner
+            .borrow()
+            .get_reload_list(AssetId::new(id))
+            .into_iter()
+            .map(|id| id.0)
+            .collect()
+    }
+
+    fn count(&self) -> usize {
+        self.inner.borrow().count()
+    }
+}
+
+unsafe impl Send for MagnusAssetRegistry {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let registry_class = module.define_class("AssetRegistry", ruby.class_object())?;
+    registry_class.define_singleton_method("new", function!(MagnusAssetRegistry::new, 0))?;
+    registry_class.define_method("register", method!(MagnusAssetRegistry::register, 2))?;
+    registry_class.define_method("unregister", method!(MagnusAssetRegistry::unregister, 1))?;
+    registry_class.define_method("add_dependency", method!(MagnusAssetRegistry::add_dependency, 2))?;
+    registry_class.define_method("path", method!(MagnusAssetRegistry::path, 1))?;
+    registry_class.define_method("asset_type", method!(MagnusAssetRegistry::asset_type, 1))?;
+    registry_class.define_method("enable_hot_reload", method!(MagnusAssetRegistry::enable_hot_reload, 0))?;
+    registry_class.define_method("disable_hot_reload", method!(MagnusAssetRegistry::disable_hot_reload, 0))?;
+    registry_class.define_method(
+        "hot_reload_enabled?",
+        method!(MagnusAssetRegistry::is_hot_reload_enabled, 0),
+    )?;
+    registry_class.define_method("check_for_changes", method!(MagnusAssetRegistry::check_for_changes, 0))?;
+    registry_class.define_method("reload_list", method!(MagnusAssetRegistry::reload_list, 1))?;
+    registry_class.define_method("count", method!(MagnusAssetRegistry::count, 0))?;
+
+    Ok(())
+}
@@ -0,0 +1,325 @@
+use bevy_ruby::{AudioCommand, SoundSet, SoundSets};
+use bevy_ruby_render::{AudioMixer, AudioSettings, PlaybackMode};
+use magnus::{Error, RHash, RModule, Ruby, TryConvert, Value, function, prelude::*};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::capabilities;
+use crate::keys;
+use crate::ruby_render_app::{get_hash_value, queue_audio};
+
+/// A playing track, with the volume last sent to the render app.
+struct TrackState {
+    channel: String,
+    volume: f32,
+}
+
+thread_local! {
+    static MIXER: RefCell<AudioMixer> = RefCell::new(AudioMixer::new());
+    static TRACKS: RefCell<BTreeMap<u32, TrackState>> = const { RefCell::new(BTreeMap::new()) };
+    static SOUND_SETS: RefCell<SoundSets> = RefCell::new(SoundSets::new());
+}
+
+fn play_track(path: String, channel: String, settings: AudioSettings) -> u32 {
+    let looping = settings.mode == PlaybackMode::Loop;
+    let paused = settings.paused;
+    let speed = settings.speed;
+
+    let (track_id, volume) = MIXER.with(|mixer| {
+        let mut mixer = mixer.borrow_mut();
+        mixer.add_channel(channel.clone());
+        let track_id = mixer.play_with_settings(path.clone(), &channel, settings);
+        (track_id, mixer.effective_volume(track_id, &channel))
+    });
+
+    TRACKS.with(|tracks| {
+        tracks
+            .borrow_mut()
+            .insert(track_id, TrackState { channel, volume });
+    });
+    queue_audio(AudioCommand::Play {
+        track_id,
+        path,
+        volume,
+        speed,
+        looping,
+        paused,
+    });
+    track_id
+}
+
+/// Queues a volume change for every track whose mixed volume moved.
+fn sync_volumes() {
+    MIXER.with(|mixer| {
+        let mixer = mixer.borrow();
+        TRACKS.with(|tracks| {
+            for (&track_id, track) in tracks.borrow_mut().iter_mut() {
+                let volume = mixer.effective_volume(track_id, &track.channel);
+                if volume != track.volume {
+                    track.volume = volume;
+                    queue_audio(AudioCommand::SetVolume { track_id, volume });
+                }
+            }
+        });
+    });
+}
+
+fn has_track(track_id: u32) -> bool {
+    TRACKS.with(|tracks| tracks.borrow().contains_key(&track_id))
+}
+
+/// `play(path, channel)`; unknown channels are created on first use.
+fn play(path: String, channel: String) -> u32 {
+    play_track(path, channel, AudioSettings::default())
+}
+
+/// `play_with_settings(path, channel, {volume:, speed:, loop:, paused:, fade_in:})`.
+fn play_with_settings(path: String, channel: String, settings: RHash) -> Result<u32, Error> {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    let mut parsed = AudioSettings::new();
+    if let Some(volume) = get_hash_value::<f64>(&ruby, &settings, &keys::VOLUME)? {
+        parsed = parsed.with_volume(volume as f32);
+    }
+    if let Some(speed) = get_hash_value::<f64>(&ruby, &settings, &keys::SPEED)? {
+        parsed = parsed.with_speed(speed as f32);
+    }
+    if get_hash_value::<bool>(&ruby, &settings, &keys::LOOP)?.unwrap_or(false) {
+        parsed = parsed.looping();
+    }
+    if let Some(fade_in) = get_hash_value::<f64>(&ruby, &settings, &keys::FADE_IN)? {
+        parsed = parsed.with_fade_in(fade_in as f32);
+    }
+    parsed.paused = get_hash_value::<bool>(&ruby, &settings, &keys::PAUSED)?.unwrap_or(false);
+
+    Ok(play_track(path, channel, parsed))
+}
+
+fn stop(track_id: u32) {
+    MIXER.with(|mixer| mixer.borrow_mut().stop(track_id));
+    if TRACKS.with(|tracks| tracks.borrow_mut().remove(&track_id)).is_some() {
+        queue_audio(AudioCommand::Stop { track_id });
+    }
+}
+
+/// Fades the track to silence over `seconds`, then stops it on a later `update`.
+fn stop_with_fade(track_id: u32, seconds: f64) {
+    if seconds <= 0.0 {
+        stop(track_id);
+        return;
+    }
+    MIXER.with(|mixer| mixer.borrow_mut().stop_with_fade(track_id, seconds as f32));
+}
+
+fn pause(track_id: u32) {
+    if has_track(track_id) {
+        MIXER.with(|mixer| mixer.borrow_mut().pause(track_id));
+        queue_audio(AudioCommand::Pause { track_id });
+    }
+}
+
+fn resume(track_id: u32) {
+    if has_track(track_id) {
+        MIXER.with(|mixer| mixer.borrow_mut().resume(track_id));
+        queue_audio(AudioCommand::Resume { track_id });
+    }
+}
+
+/// Sets a channel volume, clamped to `0.0..=2.0`; unknown channels are created.
+fn set_channel_volume(channel: String, volume: f64) {
+    MIXER.with(|mixer| {
+        let mut mixer = mixer.borrow_mut();
+        mixer.add_channel(channel.clone());
+        mixer.set_channel_volume(&channel, volume as f32);
+    });
+    sync_volumes();
+}
+
+/// Sets the master volume, clamped to `0.0..=2.0`.
+fn set_master_volume(volume: f64) {
+    MIXER.with(|mixer| mixer.borrow_mut().master_volume = (volume as f32).clamp(0.0, 2.0));
+    sync_volumes();
+}
+
+fn master_volume() -> f64 {
+    MIXER.with(|mixer| mixer.borrow().master_volume as f64)
+}
+
+/// Advances fades by `delta` seconds and stops tracks that have faded out.
+fn update(delta: f64) {
+    let finished: Vec<u32> = MIXER.with(|mixer| {
+        let mut mixer = mixer.borrow_mut();
+        mixer.update(delta.max(0.0) as f32);
+        TRACKS.with(|tracks| {
+            tracks
+                .borrow()
+                .keys()
+                .copied()
+                .filter(|track_id| mixer.get_track(*track_id).is_none())
+                .collect()
+        })
+    });
+    for track_id in finished {
+        stop(track_id);
+    }
+    sync_volumes();
+}
+
+/// Mixed volume of a track (master × channel × track, including fades), or nil.
+fn volume(track_id: u32) -> Option<f64> {
+    TRACKS.with(|tracks| tracks.borrow().get(&track_id).map(|track| track.volume as f64))
+}
+
+/// Path a track was started with, or nil once it stops.
+fn path(track_id: u32) -> Option<String> {
+    MIXER.with(|mixer| mixer.borrow().get_track(track_id).map(|track| track.path.clone()))
+}
+
+/// Playback speed of a track, or nil once it stops.
+fn speed(track_id: u32) -> Option<f64> {
+    MIXER.with(|mixer| {
+        mixer
+            .borrow()
+            .get_track(track_id)
+            .map(|track| track.settings.speed as f64)
+    })
+}
+
+fn playing(track_id: u32) -> bool {
+    MIXER.with(|mixer| {
+        mixer
+            .borrow()
+            .get_track(track_id)
+            .is_some_and(|track| !track.settings.paused)
+    })
+}
+
+/// Splits `(name, arg, options = {})` arguments.
+fn parse_named_args(ruby: &Ruby, args: &[Value], expected: &str) -> Result<(String, Vec<Value>, Option<RHash>), Error> {
+    let Some((&name, rest)) = args.split_first() else {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("wrong number of arguments (given 0, expected {})", expected),
+        ));
+    };
+    let (positional, options) = match rest.split_last() {
+        Some((&last, init)) if RHash::from_value(last).is_some() => (init.to_vec(), RHash::from_value(last)),
+        _ => (rest.to_vec(), None),
+    };
+    Ok((String::try_convert(name)?, positional, options))
+}
+
+/// Audio paths resolve like Bevy assets: as given, or under `assets/`.
+fn sound_file_exists(path: &str) -> bool {
+    Path::new(path).is_file() || Path::new("assets").join(path).is_file()
+}
+
+/// `register_sound_set(name, paths, avoid_repeat: 1)`; replaces a set of the same name.
+fn register_sound_set(args: &[Value]) -> Result<(), Error> {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    let (name, positional, options) = parse_named_args(&ruby, args, "2")?;
+    let [paths] = positional[..] else {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("wrong number of arguments (given {}, expected 2)", positional.len() + 1),
+        ));
+    };
+    let paths = Vec::<String>::try_convert(paths)?;
+    if paths.is_empty() {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("sound set {} has no sounds", name),
+        ));
+    }
+    let missing: Vec<&str> = paths
+        .iter()
+        .map(String::as_str)
+        .filter(|path| !sound_file_exists(path))
+        .collect();
+    if !missing.is_empty() {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("sound set {}: no such file {}", name, missing.join(", ")),
+        ));
+    }
+    let avoid_repeat = match options {
+        Some(options) => get_hash_value::<usize>(&ruby, &options, &keys::AVOID_REPEAT)?,
+        None => None,
+    };
+
+    SOUND_SETS.with(|sets| {
+        sets.borrow_mut()
+            .register(&name, SoundSet::new(paths, avoid_repeat.unwrap_or(1)))
+    });
+    Ok(())
+}
+
+/// `play_sound_set(name, channel: "sfx", volume: 1.0, speed: 1.0,
+/// volume_variation: 0.0, pitch_variation: 0.0)`; returns the track ID.
+fn play_sound_set(args: &[Value]) -> Result<u32, Error> {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    let (name, positional, options) = parse_named_args(&ruby, args, "1")?;
+    if !positional.is_empty() {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("wrong number of arguments (given {}, expected 1)", positional.len() + 1),
+        ));
+    }
+    let options = options.unwrap_or_else(|| ruby.hash_new());
+    let channel = get_hash_value::<String>(&ruby, &options, &keys::CHANNEL)?.unwrap_or_else(|| "sfx".to_string());
+    let volume = get_hash_value::<f64>(&ruby, &options, &keys::VOLUME)?.unwrap_or(1.0);
+    let speed = get_hash_value::<f64>(&ruby, &options, &keys::SPEED)?.unwrap_or(1.0);
+    let volume_variation = get_hash_value::<f64>(&ruby, &options, &keys::VOLUME_VARIATION)?.unwrap_or(0.0);
+    let pitch_variation = get_hash_value::<f64>(&ruby, &options, &keys::PITCH_VARIATION)?.unwrap_or(0.0);
+
+    let picked = SOUND_SETS.with(|sets| {
+        sets.borrow_mut()
+            .pick(&name, volume_variation as f32, pitch_variation as f32)
+    });
+    let Some(picked) = picked else {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("unknown sound set {}", name),
+        ));
+    };
+    let settings = AudioSettings::new()
+        .with_volume(volume as f32 * picked.volume_scale)
+        .with_speed(speed as f32 * picked.speed_scale);
+    Ok(play_track(picked.path, channel, settings))
+}
+
+fn remove_sound_set(name: String) -> bool {
+    SOUND_SETS.with(|sets| sets.borrow_mut().remove(&name))
+}
+
+/// Restarts the generator behind every set; a seed replays the same picks.
+fn seed_sound_sets(seed: u64) {
+    SOUND_SETS.with(|sets| sets.borrow_mut().seed(seed));
+}
+
+pub fn define(_ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let audio_module = module.define_module("Audio")?;
+    audio_module.define_module_function("play", function!(play, 2))?;
+    audio_module.define_module_function("play_with_settings", function!(play_with_settings, 3))?;
+    audio_module.define_module_function("stop", function!(stop, 1))?;
+    audio_module.define_module_function("stop_with_fade", function!(stop_with_fade, 2))?;
+    audio_module.define_module_function("pause", function!(pause, 1))?;
+    audio_module.define_module_function("resume", function!(resume, 1))?;
+    audio_module.define_module_function("set_channel_volume", function!(set_channel_volume, 2))?;
+    audio_module.define_module_function("set_master_volume", function!(set_master_volume, 1))?;
+    audio_module.define_module_function("master_volume", function!(master_volume, 0))?;
+    audio_module.define_module_function("update", function!(update, 1))?;
+    audio_module.define_module_function("volume", function!(volume, 1))?;
+    audio_module.define_module_function("path", function!(path, 1))?;
+    audio_module.define_module_function("speed", function!(speed, 1))?;
+    audio_module.define_module_function("playing?", function!(playing, 1))?;
+    audio_module.define_module_function("register_sound_set", function!(register_sound_set, -1))?;
+    audio_module.define_module_function("play_sound_set", function!(play_sound_set, -1))?;
+    audio_module.define_module_function("remove_sound_set", function!(remove_sound_set, 1))?;
+    audio_module.define_module_function("seed_sound_sets", function!(seed_sound_sets, 1))?;
+
+    // Commands reach the render app, but no audio backend is linked to play them yet.
+    capabilities::register("audio", false);
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use bevy_ruby_render::AudioQueue;
+use magnus::{Error, RModule, Ruby, function, method, prelude::*};
+use std::cell::RefCell;
+
+/// Playlist of track paths; play `next` / `current` through `Bevy::Audio`.
+#[magnus::wrap(class = "Bevy::AudioQueue", free_immediately, size)]
+pub struct MagnusAudioQueue {
+    inner: RefCell<AudioQueue>,
+}
+
+impl MagnusAudioQueue {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(AudioQueue::new()),
+        }
+    }
+
+    fn add(&self, path: String) {
+        self.inner.borrow_mut().add(path);
+    }
+
+    fn add_all(&self, paths: Vec<String>) {
+        self.inner.borrow_mut().add_all(paths);
+    }
+
+    fn next(&self) -> Option<String> {
+        self.inner.borrow_mut().next().cloned()
+    }
+
+    fn previous(&self) -> Option<String> {
+        self.inner.borrow_mut().previous().cloned()
+    }
+
+    fn current(&self) -> Option<String> {
+        self.inner.borrow().current().cloned()
+    }
+
+    fn current_index(&self) -> usize {
+        self.inner.borrow().current_index()
+    }
+
+    fn clear(&self) {
+        self.inner.borrow_mut().clear();
+    }
+
+    fn loop_queue(&self) -> bool {
+        self.inner.borrow().loop_queue
+    }
+
+    fn set_loop_queue(&self, loop_queue: bool) {
+        self.inner.borrow_mut().loop_queue = loop_queue;
+    }
+
+    fn shuffle(&self) -> bool {
+        self.inner.borrow().is_shuffled()
+    }
+
+    fn set_shuffle(&self, shuffle: bool) {
+        self.inner.borrow_mut().set_shuffle(shuffle);
+    }
+
+    /// Restarts the shuffle generator; the same seed gives the same orders.
+    fn seed(&self, seed: u64) {
+        self.inner.borrow_mut().seed(seed);
+    }
+
+    fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    fn to_a(&self) -> Vec<String> {
+        self.inner.borrow().tracks().to_vec()
+    }
+}
+
+unsafe impl Send for MagnusAudioQueue {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let queue_class = module.define_class("AudioQueue", ruby.class_object())?;
+    queue_class.define_singleton_method("new", function!(MagnusAudioQueue::new, 0))?;
+    queue_class.define_method("add", method!(MagnusAudioQueue::add, 1))?;
+    queue_class.define_method("add_all", method!(MagnusAudioQueue::add_all, 1))?;
+    queue_class.define_method("next", method!(MagnusAudioQueue::next, 0))?;
+    queue_class.define_method("previous", method!(MagnusAudioQueue::previous, 0))?;
+    queue_class.define_method("current", method!(MagnusAudioQueue::current, 0))?;
+    queue_class.define_method("current_index", method!(MagnusAudioQueue::current_index, 0))?;
+    queue_class.define_method("clear", method!(MagnusAudioQueue::clear, 0))?;
+    queue_class.define_method("loop_queue", method!(MagnusAudioQueue::loop_queue, 0))?;
+    queue_class.define_method("loop_queue=", method!(MagnusAudioQueue::set_loop_queue, 1))?;
+    queue_class.define_method("shuffle", method!(MagnusAudioQueue::shuffle, 0))?;
+    queue_class.define_method("shuffle=", method!(MagnusAudioQueue::set_shuffle, 1))?;
+    queue_class.define_method("seed", method!(MagnusAudioQueue::seed, 1))?;
+    queue_class.define_method("size", method!(MagnusAudioQueue::len, 0))?;
+    queue_class.define_method("to_a", method!(MagnusAudioQueue::to_a, 0))?;
+
+    Ok(())
+}
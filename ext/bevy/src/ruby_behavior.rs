@@ -0,0 +1,103 @@
+use bevy_ruby::{Behavior, EntityBehaviors};
+use magnus::{Error, RHash, RModule, Ruby, function, method, prelude::*};
+use std::cell::RefCell;
+
+use crate::keys;
+use crate::ruby_render_app::{parse_bob, parse_pulse};
+use crate::ruby_transform::MagnusTransform;
+
+/// The behavior composition behind `RenderApp#spin`, `#pulse` and `#bob`,
+/// stepped by hand instead of by the frame clock. Used by specs; not part of
+/// the public API.
+#[magnus::wrap(class = "Bevy::Internal::EntityBehaviors", free_immediately, size)]
+pub struct MagnusEntityBehaviors {
+    inner: RefCell<EntityBehaviors>,
+}
+
+impl MagnusEntityBehaviors {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(EntityBehaviors::new()),
+        }
+    }
+
+    fn spin(&self, id: u64, radians_per_second: f64) {
+        self.inner.borrow_mut().add(
+            id,
+            Behavior::Spin {
+                radians_per_second: radians_per_second as f32,
+            },
+        );
+    }
+
+    /// `pulse(id, {scale_min:, scale_max:, period:})`.
+    fn pulse(&self, id: u64, options: RHash) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        self.inner.borrow_mut().add(id, parse_pulse(&ruby, &options)?);
+        Ok(())
+    }
+
+    /// `bob(id, {amplitude:, period:, axis: :y})`.
+    fn bob(&self, id: u64, options: RHash) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        self.inner.borrow_mut().add(id, parse_bob(&ruby, &options)?);
+        Ok(())
+    }
+
+    fn clear(&self, id: u64) -> bool {
+        self.inner.borrow_mut().clear(id)
+    }
+
+    fn advance(&self, delta_secs: f64) {
+        self.inner.borrow_mut().advance(delta_secs as f32);
+    }
+
+    /// `{translation: [x, y, z], rotation_z:, scale:}`, or nil without behaviors.
+    fn offset(&self, id: u64) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let Some(offset) = self.inner.borrow().offset(id) else {
+            return Ok(None);
+        };
+        let (x, y, z) = offset.translation;
+        let hash = ruby.hash_new_capa(3);
+        hash.aset(*keys::TRANSLATION, (x as f64, y as f64, z as f64))?;
+        hash.aset(*keys::ROTATION_Z, offset.rotation_z as f64)?;
+        hash.aset(*keys::SCALE, offset.scale as f64)?;
+        Ok(Some(hash))
+    }
+
+    /// `compose(id, transform)`: the Transform written over `transform`, the
+    /// one the entity has now, or nil without behaviors.
+    fn compose(&self, id: u64, current: &MagnusTransform) -> Option<MagnusTransform> {
+        self.inner
+            .borrow_mut()
+            .compose(id, &current.inner().into())
+            .map(|transform| MagnusTransform::from(transform.into()))
+    }
+
+    fn has_behaviors(&self, id: u64) -> bool {
+        self.inner.borrow().has_behaviors(id)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+}
+
+unsafe impl Send for MagnusEntityBehaviors {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let behaviors_class = module.define_class("EntityBehaviors", ruby.class_object())?;
+    behaviors_class.define_singleton_method("new", function!(MagnusEntityBehaviors::new, 0))?;
+    behaviors_class.define_method("spin", method!(MagnusEntityBehaviors::spin, 2))?;
+    behaviors_class.define_method("pulse", method!(MagnusEntityBehaviors::pulse, 2))?;
+    behaviors_class.define_method("bob", method!(MagnusEntityBehaviors::bob, 2))?;
+    behaviors_class.define_method("clear", method!(MagnusEntityBehaviors::clear, 1))?;
+    behaviors_class.define_method("advance", method!(MagnusEntityBehaviors::advance, 1))?;
+    behaviors_class.define_method("offset", method!(MagnusEntityBehaviors::offset, 1))?;
+    behaviors_class.define_method("compose", method!(MagnusEntityBehaviors::compose, 2))?;
+    behaviors_class.define_method("behaviors?", method!(MagnusEntityBehaviors::has_behaviors, 1))?;
+    behaviors_class.define_method("size", method!(MagnusEntityBehaviors::len, 0))?;
+
+    Ok(())
+}
@@ -34,6 +34,13 @@ impl MagnusColor {
             .ok_or_else(|| Error::new(magnus::exception::arg_error(), "Invalid hex color"))
     }
 
+    /// `from_hsl(hue_degrees, saturation, lightness)`.
+    fn from_hsl(h: f64, s: f64, l: f64) -> Self {
+        Self {
+            inner: RefCell::new(RubyColor::from_hsl(h as f32, s as f32, l as f32)),
+        }
+    }
+
     fn white() -> Self {
         Self {
             inner: RefCell::new(RubyColor::white()),
@@ -108,6 +115,13 @@ impl MagnusColor {
         }
     }
 
+    fn lerp(&self, other: &MagnusColor, t: f64) -> Self {
+        let other = other.inner();
+        Self {
+            inner: RefCell::new(self.inner.borrow().lerp(&other, t as f32)),
+        }
+    }
+
     fn to_a(&self) -> Result<RArray, Error> {
         let ruby = Ruby::get().unwrap();
         let arr = ruby.ary_new();
@@ -118,6 +132,18 @@ impl MagnusColor {
         arr.push(c.a() as f64)?;
         Ok(arr)
     }
+
+    pub fn inner(&self) -> RubyColor {
+        *self.inner.borrow()
+    }
+}
+
+impl From<RubyColor> for MagnusColor {
+    fn from(color: RubyColor) -> Self {
+        Self {
+            inner: RefCell::new(color),
+        }
+    }
 }
 
 unsafe impl Send for MagnusColor {}
@@ -128,6 +154,7 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     color_class.define_singleton_method("rgb", function!(MagnusColor::rgb, 3))?;
     color_class.define_singleton_method("rgba", function!(MagnusColor::rgba, 4))?;
     color_class.define_singleton_method("from_hex", function!(MagnusColor::from_hex, 1))?;
+    color_class.define_singleton_method("from_hsl", function!(MagnusColor::from_hsl, 3))?;
     color_class.define_singleton_method("white", function!(MagnusColor::white, 0))?;
     color_class.define_singleton_method("black", function!(MagnusColor::black, 0))?;
     color_class.define_singleton_method("red", function!(MagnusColor::red, 0))?;
@@ -143,6 +170,7 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     color_class.define_method("b=", method!(MagnusColor::set_b, 1))?;
     color_class.define_method("a=", method!(MagnusColor::set_a, 1))?;
     color_class.define_method("with_alpha", method!(MagnusColor::with_alpha, 1))?;
+    color_class.define_method("lerp", method!(MagnusColor::lerp, 2))?;
     color_class.define_method("to_a", method!(MagnusColor::to_a, 0))?;
 
     Ok(())
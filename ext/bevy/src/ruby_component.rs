@@ -57,6 +57,14 @@ impl RubyComponent {
         Ok(hash)
     }
 
+    fn content_hash(&self) -> u64 {
+        self.inner.borrow().content_hash()
+    }
+
+    fn generation(&self) -> u64 {
+        self.inner.borrow().generation()
+    }
+
     fn from_hash(type_name: String, hash: RHash) -> Result<Self, Error> {
         let mut component = DynamicComponent::new(&type_name);
 
@@ -81,7 +89,7 @@ impl RubyComponent {
 
 unsafe impl Send for RubyComponent {}
 
-fn dynamic_value_to_ruby(ruby: &Ruby, value: &DynamicValue) -> Result<Value, Error> {
+pub(crate) fn dynamic_value_to_ruby(ruby: &Ruby, value: &DynamicValue) -> Result<Value, Error> {
     match value {
         DynamicValue::Nil => Ok(ruby.qnil().as_value()),
         DynamicValue::Boolean(b) => Ok(if *b {
@@ -110,7 +118,7 @@ fn dynamic_value_to_ruby(ruby: &Ruby, value: &DynamicValue) -> Result<Value, Err
     }
 }
 
-fn ruby_to_dynamic_value(value: Value) -> Result<DynamicValue, Error> {
+pub(crate) fn ruby_to_dynamic_value(value: Value) -> Result<DynamicValue, Error> {
     let ruby = Ruby::get().unwrap();
 
     if value.is_nil() {
@@ -185,5 +193,7 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     class.define_method("[]", method!(RubyComponent::get, 1))?;
     class.define_method("[]=", method!(RubyComponent::set, 2))?;
     class.define_method("to_h", method!(RubyComponent::to_h, 0))?;
+    class.define_method("content_hash", method!(RubyComponent::content_hash, 0))?;
+    class.define_method("generation", method!(RubyComponent::generation, 0))?;
     Ok(())
 }
@@ -0,0 +1,221 @@
+use bevy_ruby::{BundleContent, DEFAULT_BUNDLE_LOG_BYTES, DebugBundle, SectionStatus};
+use magnus::{
+    Error, IntoValue, RHash, RModule, RString, Ruby, TryConvert, Value, block::Proc, function, method, prelude::*,
+};
+use std::cell::RefCell;
+use std::path::Path;
+
+use crate::capabilities;
+use crate::keys;
+use crate::ruby_render_app::{get_hash_value, symbol_or_string};
+
+/// Sections for one bundle, collected by blocks; a block that raises fails
+/// only its own section. `RenderApp#dump_debug_bundle` yields one of these
+/// with the render-side sections already collected.
+#[magnus::wrap(class = "Bevy::DebugBundle", free_immediately, size)]
+pub struct MagnusDebugBundle {
+    inner: RefCell<DebugBundle>,
+}
+
+impl MagnusDebugBundle {
+    pub(crate) fn with_max_log_bytes(max_log_bytes: usize) -> Self {
+        let mut bundle = DebugBundle::new(max_log_bytes);
+        bundle.set_capabilities(capabilities::registered());
+        Self {
+            inner: RefCell::new(bundle),
+        }
+    }
+
+    /// `new` or `new(max_log_bytes:)`.
+    fn new(args: &[Value]) -> Result<Self, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        Ok(Self::with_max_log_bytes(parse_max_log_bytes(&ruby, args)?))
+    }
+
+    /// Collects a section natively; see `DebugBundle::collect`.
+    pub(crate) fn collect_native(&self, name: &str, collector: impl FnOnce() -> Result<Option<BundleContent>, String>) {
+        self.inner.borrow_mut().collect(name, collector);
+    }
+
+    /// `collect(name) { hash_or_array }`: written as `<name>.json`; nil skips it.
+    fn collect(&self, name: Value) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let name = symbol_or_string(name)?;
+        let content = collector_block(&ruby)?.call(()).and_then(|value| {
+            if value.is_nil() {
+                return Ok(None);
+            }
+            to_json(&ruby, value).map(|json| Some(BundleContent::Json(json)))
+        });
+        self.inner.borrow_mut().add(&name, content.map_err(|error| error.to_string()));
+        Ok(())
+    }
+
+    /// `collect_log(name) { lines }`: the newest lines within `max_log_bytes`,
+    /// written as `<name>.log`.
+    fn collect_log(&self, name: Value) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let name = symbol_or_string(name)?;
+        let content = collector_block(&ruby)?.call(()).and_then(|value| {
+            if value.is_nil() {
+                return Ok(None);
+            }
+            Vec::<String>::try_convert(value).map(|lines| Some(BundleContent::Lines(lines)))
+        });
+        self.inner.borrow_mut().add(&name, content.map_err(|error| error.to_string()));
+        Ok(())
+    }
+
+    /// `collect_image(name) { [width, height, rgba] }`, the shape
+    /// `RenderApp#read_pixels` returns: written as `<name>.png`.
+    fn collect_image(&self, name: Value) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let name = symbol_or_string(name)?;
+        let content = collector_block(&ruby)?.call(()).and_then(|value| {
+            if value.is_nil() {
+                return Ok(None);
+            }
+            let (width, height, rgba) = <(u32, u32, RString)>::try_convert(value)?;
+            // SAFETY: the bytes are copied before any other Ruby code runs.
+            let rgba = unsafe { rgba.as_slice() }.to_vec();
+            Ok(Some(BundleContent::Image { width, height, rgba }))
+        });
+        self.inner.borrow_mut().add(&name, content.map_err(|error| error.to_string()));
+        Ok(())
+    }
+
+    pub(crate) fn set_info_native(&self, key: &str, value: String) {
+        self.inner.borrow_mut().set_info(key, value);
+    }
+
+    /// Adds `key => value.to_s` to the manifest's `info`.
+    fn set_info(&self, key: Value, value: Value) -> Result<(), Error> {
+        let key = symbol_or_string(key)?;
+        self.set_info_native(&key, value.funcall("to_s", ())?);
+        Ok(())
+    }
+
+    fn section_names(&self) -> Vec<String> {
+        self.inner
+            .borrow()
+            .section_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn max_log_bytes(&self) -> usize {
+        self.inner.borrow().max_log_bytes()
+    }
+
+    /// Writes the bundle into the directory `path` and returns
+    /// `{path:, sections: [{name:, status:, ...}]}` as in the manifest.
+    pub(crate) fn write(&self, path: String) -> Result<RHash, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let statuses = self.inner.borrow().write(Path::new(&path)).map_err(|error| {
+            Error::new(
+                ruby.exception_io_error(),
+                format!("cannot write debug bundle {}: {}", path, error),
+            )
+        })?;
+
+        let sections = ruby.ary_new_capa(statuses.len());
+        for (name, status) in &statuses {
+            sections.push(status_to_hash(&ruby, name, status)?)?;
+        }
+        let result = ruby.hash_new_capa(2);
+        result.aset(ruby.to_symbol("path"), path)?;
+        result.aset(ruby.to_symbol("sections"), sections)?;
+        Ok(result)
+    }
+}
+
+unsafe impl Send for MagnusDebugBundle {}
+
+pub(crate) fn parse_max_log_bytes(ruby: &Ruby, args: &[Value]) -> Result<usize, Error> {
+    match args {
+        [] => Ok(DEFAULT_BUNDLE_LOG_BYTES),
+        [options] => {
+            let options = RHash::try_convert(*options)?;
+            Ok(get_hash_value::<usize>(ruby, &options, &keys::MAX_LOG_BYTES)?.unwrap_or(DEFAULT_BUNDLE_LOG_BYTES))
+        }
+        _ => Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("wrong number of arguments (given {}, expected 0..1)", args.len()),
+        )),
+    }
+}
+
+/// The collector block; raises when none was given, unlike the block's own errors.
+fn collector_block(ruby: &Ruby) -> Result<Proc, Error> {
+    if !ruby.block_given() {
+        return Err(Error::new(ruby.exception_arg_error(), "a collector block is required"));
+    }
+    ruby.block_proc()
+}
+
+/// `JSON.pretty_generate(value)`.
+pub(crate) fn to_json(ruby: &Ruby, value: impl IntoValue) -> Result<String, Error> {
+    ruby.require("json")?;
+    let json: RModule = ruby.class_object().const_get("JSON")?;
+    json.funcall("pretty_generate", (value,))
+}
+
+fn status_to_hash(ruby: &Ruby, name: &str, status: &SectionStatus) -> Result<RHash, Error> {
+    let hash = ruby.hash_new_capa(5);
+    hash.aset(ruby.to_symbol("name"), name)?;
+    match status {
+        SectionStatus::Written {
+            file,
+            bytes,
+            dropped_lines,
+        } => {
+            hash.aset(ruby.to_symbol("status"), ruby.to_symbol("written"))?;
+            hash.aset(ruby.to_symbol("file"), file.as_str())?;
+            hash.aset(ruby.to_symbol("bytes"), *bytes)?;
+            hash.aset(ruby.to_symbol("dropped_lines"), *dropped_lines)?;
+        }
+        SectionStatus::Skipped(reason) => {
+            hash.aset(ruby.to_symbol("status"), ruby.to_symbol("skipped"))?;
+            hash.aset(ruby.to_symbol("reason"), reason.as_str())?;
+        }
+        SectionStatus::Failed(error) => {
+            hash.aset(ruby.to_symbol("status"), ruby.to_symbol("failed"))?;
+            hash.aset(ruby.to_symbol("error"), error.as_str())?;
+        }
+    }
+    Ok(hash)
+}
+
+/// `[[name, value], ...]` pairs as a Hash with symbol keys, for collectors.
+pub(crate) fn fields_hash(ruby: &Ruby, fields: Vec<(&str, Value)>) -> Result<RHash, Error> {
+    let hash = ruby.hash_new_capa(fields.len());
+    for (name, value) in fields {
+        hash.aset(ruby.to_symbol(name), value)?;
+    }
+    Ok(hash)
+}
+
+/// Collects `build` as a JSON section of `bundle`, failing the section on error.
+pub(crate) fn collect_json(ruby: &Ruby, bundle: &MagnusDebugBundle, name: &str, build: impl FnOnce() -> Result<Value, Error>) {
+    bundle.collect_native(name, || {
+        build()
+            .and_then(|value| to_json(ruby, value))
+            .map(|json| Some(BundleContent::Json(json)))
+            .map_err(|error| error.to_string())
+    });
+}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let bundle_class = module.define_class("DebugBundle", ruby.class_object())?;
+    bundle_class.define_singleton_method("new", function!(MagnusDebugBundle::new, -1))?;
+    bundle_class.define_method("collect", method!(MagnusDebugBundle::collect, 1))?;
+    bundle_class.define_method("collect_log", method!(MagnusDebugBundle::collect_log, 1))?;
+    bundle_class.define_method("collect_image", method!(MagnusDebugBundle::collect_image, 1))?;
+    bundle_class.define_method("set_info", method!(MagnusDebugBundle::set_info, 2))?;
+    bundle_class.define_method("section_names", method!(MagnusDebugBundle::section_names, 0))?;
+    bundle_class.define_method("max_log_bytes", method!(MagnusDebugBundle::max_log_bytes, 0))?;
+    bundle_class.define_method("write", method!(MagnusDebugBundle::write, 1))?;
+
+    Ok(())
+}
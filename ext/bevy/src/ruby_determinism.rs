@@ -0,0 +1,155 @@
+use bevy_ruby::{
+    ChecksumDivergence, DeterminismAudit, InputState, SpriteOperation, compare_checksum_streams,
+    format_checksum_stream, parse_checksum_stream,
+};
+use magnus::{Error, RHash, RModule, Ruby, function, method, prelude::*};
+use std::cell::RefCell;
+
+use crate::capabilities;
+use crate::keys;
+use crate::ruby_render_app::{parse_sprite_data, parse_transform_data};
+
+/// Standalone checksum recorder, fed the same way the render bridge feeds its audit.
+#[magnus::wrap(class = "Bevy::DeterminismAudit", free_immediately, size)]
+pub struct MagnusDeterminismAudit {
+    inner: RefCell<DeterminismAudit>,
+    history: RefCell<Vec<u64>>,
+}
+
+impl MagnusDeterminismAudit {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(DeterminismAudit::new()),
+            history: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn record_sprite(
+        &self,
+        ruby_entity_id: u64,
+        sprite_hash: RHash,
+        transform_hash: RHash,
+    ) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let op = SpriteOperation::Sync {
+            ruby_entity_id,
+            sprite_data: parse_sprite_data(&ruby, &sprite_hash)?,
+            transform_data: parse_transform_data(&ruby, &transform_hash)?,
+        };
+        self.inner.borrow_mut().record(&op);
+        Ok(())
+    }
+
+    fn record_remove_sprite(&self, ruby_entity_id: u64) {
+        self.inner
+            .borrow_mut()
+            .record(&SpriteOperation::Remove { ruby_entity_id });
+    }
+
+    /// Records delivered input as a set of pressed keys and a mouse position.
+    fn record_input(&self, keys: Vec<String>, mouse_x: f64, mouse_y: f64) {
+        let mut input = InputState::new();
+        for key in &keys {
+            input.set_pressed(key);
+        }
+        input.mouse_position = (mouse_x as f32, mouse_y as f32);
+        self.inner.borrow_mut().record(&input);
+    }
+
+    fn finish_frame(&self) -> u64 {
+        let mut inner = self.inner.borrow_mut();
+        let checksum = inner.finish_frame();
+        self.history.borrow_mut().extend(inner.drain_finished());
+        checksum
+    }
+
+    fn frame_checksum(&self) -> Option<u64> {
+        self.inner.borrow().last_checksum()
+    }
+
+    fn history(&self) -> Vec<u64> {
+        self.history.borrow().clone()
+    }
+
+    fn compare_to(&self, recorded: Vec<u64>) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let mut inner = self.inner.borrow_mut();
+        inner.set_recorded(recorded, &self.history.borrow());
+        inner
+            .divergence()
+            .map(|divergence| divergence_to_hash(&ruby, &divergence))
+            .transpose()
+    }
+
+    fn divergence(&self) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        self.inner
+            .borrow()
+            .divergence()
+            .map(|divergence| divergence_to_hash(&ruby, &divergence))
+            .transpose()
+    }
+
+    fn compare(expected: Vec<u64>, actual: Vec<u64>) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        compare_checksum_streams(&expected, &actual, true)
+            .map(|divergence| divergence_to_hash(&ruby, &divergence))
+            .transpose()
+    }
+
+    /// Formats checksums one 16-digit hex value per line.
+    fn dump(checksums: Vec<u64>) -> String {
+        format_checksum_stream(&checksums)
+    }
+
+    fn parse(text: String) -> Result<Vec<u64>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        parse_checksum_stream(&text)
+            .map_err(|error| Error::new(ruby.exception_arg_error(), error.to_string()))
+    }
+}
+
+unsafe impl Send for MagnusDeterminismAudit {}
+
+pub fn divergence_to_hash(ruby: &Ruby, divergence: &ChecksumDivergence) -> Result<RHash, Error> {
+    let hash = ruby.hash_new_capa(3);
+    hash.aset(*keys::FRAME, divergence.frame)?;
+    hash.aset(*keys::EXPECTED, divergence.expected)?;
+    hash.aset(*keys::ACTUAL, divergence.actual)?;
+    Ok(hash)
+}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let audit_class = module.define_class("DeterminismAudit", ruby.class_object())?;
+    audit_class.define_singleton_method("new", function!(MagnusDeterminismAudit::new, 0))?;
+    audit_class.define_singleton_method("compare", function!(MagnusDeterminismAudit::compare, 2))?;
+    audit_class.define_singleton_method("dump", function!(MagnusDeterminismAudit::dump, 1))?;
+    audit_class.define_singleton_method("parse", function!(MagnusDeterminismAudit::parse, 1))?;
+    audit_class.define_method(
+        "record_sprite",
+        method!(MagnusDeterminismAudit::record_sprite, 3),
+    )?;
+    audit_class.define_method(
+        "record_remove_sprite",
+        method!(MagnusDeterminismAudit::record_remove_sprite, 1),
+    )?;
+    audit_class.define_method(
+        "record_input",
+        method!(MagnusDeterminismAudit::record_input, 3),
+    )?;
+    audit_class.define_method(
+        "finish_frame",
+        method!(MagnusDeterminismAudit::finish_frame, 0),
+    )?;
+    audit_class.define_method(
+        "frame_checksum",
+        method!(MagnusDeterminismAudit::frame_checksum, 0),
+    )?;
+    audit_class.define_method("history", method!(MagnusDeterminismAudit::history, 0))?;
+    audit_class.define_method("compare_to", method!(MagnusDeterminismAudit::compare_to, 1))?;
+    audit_class.define_method("divergence", method!(MagnusDeterminismAudit::divergence, 0))?;
+
+    capabilities::register("determinism_audit", cfg!(feature = "determinism_audit"));
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use bevy_ruby::{DragEvent, DragGestures, DropTarget};
+use magnus::{Error, RHash, RModule, Ruby, TryConvert, Value, function, method, prelude::*};
+use std::cell::RefCell;
+
+use crate::keys;
+use crate::ruby_render_app::{get_hash_value, parse_drag_args};
+
+/// The gesture tracking behind `RenderApp#enable_drag`, driven by hand with
+/// world positions instead of by the mouse.
+#[magnus::wrap(class = "Bevy::DragGestures", free_immediately, size)]
+pub struct MagnusDragGestures {
+    inner: RefCell<DragGestures>,
+}
+
+impl MagnusDragGestures {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(DragGestures::new()),
+        }
+    }
+
+    /// `enable(id, constrain_to: nil, snap_back: false, drag_layer: 100)`.
+    fn enable(&self, args: &[Value]) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (id, config) = parse_drag_args(&ruby, args)?;
+        self.inner.borrow_mut().enable(id, config);
+        Ok(())
+    }
+
+    /// Returns false if `id` wasn't draggable; a drag of it ends without an event.
+    fn disable(&self, id: u64) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        let enabled = inner.is_enabled(id);
+        inner.disable(id);
+        enabled
+    }
+
+    fn is_enabled(&self, id: u64) -> bool {
+        self.inner.borrow().is_enabled(id)
+    }
+
+    fn is_dragging(&self) -> bool {
+        self.inner.borrow().is_dragging()
+    }
+
+    fn held_id(&self) -> Option<u64> {
+        self.inner.borrow().active().map(|active| active.id)
+    }
+
+    /// `[x, y]` of the held sprite, or nil.
+    fn position(&self) -> Option<(f64, f64)> {
+        self.inner
+            .borrow()
+            .active()
+            .map(|active| (active.position.0 as f64, active.position.1 as f64))
+    }
+
+    /// `press(id, [x, y], [pointer_x, pointer_y])`: picks up `id` at `[x, y]`;
+    /// false while another drag is held or `id` isn't draggable.
+    fn press(&self, id: u64, origin: Vec<f64>, pointer: Vec<f64>) -> Result<bool, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let origin = point(&ruby, "origin", &origin)?;
+        let pointer = point(&ruby, "pointer", &pointer)?;
+        Ok(self.inner.borrow_mut().press(id, (origin.0, origin.1, 0.0), pointer))
+    }
+
+    /// `[x, y]` the held sprite moved to, or nil when nothing is held.
+    fn drag_to(&self, pointer: Vec<f64>) -> Result<Option<(f64, f64)>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let pointer = point(&ruby, "pointer", &pointer)?;
+        Ok(self
+            .inner
+            .borrow_mut()
+            .drag_to(pointer)
+            .map(|(_, position)| (position.0 as f64, position.1 as f64)))
+    }
+
+    /// `release([x, y])` or `release(nil, drop_target: id, hit_region: false)`:
+    /// the event hash, also kept for `drain_events`, or nil when nothing is held.
+    fn release(&self, args: &[Value]) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (pointer, options) = match args {
+            [pointer] => (*pointer, None),
+            [pointer, options] => (*pointer, Some(RHash::try_convert(*options)?)),
+            _ => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("wrong number of arguments (given {}, expected 1..2)", args.len()),
+                ));
+            }
+        };
+        let pointer = match Option::<Vec<f64>>::try_convert(pointer)? {
+            Some(pointer) => Some(point(&ruby, "pointer", &pointer)?),
+            None => None,
+        };
+        let drop_target = match options {
+            Some(options) => get_hash_value::<u64>(&ruby, &options, &keys::DROP_TARGET)?
+                .map(|id| -> Result<DropTarget, Error> {
+                    let hit_region = get_hash_value(&ruby, &options, &keys::HIT_REGION)?.unwrap_or(false);
+                    Ok(DropTarget { id, hit_region })
+                })
+                .transpose()?,
+            None => None,
+        };
+
+        let event = self.inner.borrow_mut().release(pointer, drop_target);
+        event.map(|event| drag_event_to_hash(&ruby, &event)).transpose()
+    }
+
+    fn drain_events(&self) -> Result<Vec<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        self.inner
+            .borrow_mut()
+            .drain_events()
+            .iter()
+            .map(|event| drag_event_to_hash(&ruby, event))
+            .collect()
+    }
+}
+
+unsafe impl Send for MagnusDragGestures {}
+
+fn point(ruby: &Ruby, name: &str, values: &[f64]) -> Result<(f32, f32), Error> {
+    match values {
+        &[x, y] if x.is_finite() && y.is_finite() => Ok((x as f32, y as f32)),
+        _ => Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("{} must be two finite numbers, got {:?}", name, values),
+        )),
+    }
+}
+
+/// `{id:, position:, dropped_at:, origin:, drop_target:, hit_region:, snapped_back:}`;
+/// `drop_target` is nil when nothing was under the pointer.
+pub(crate) fn drag_event_to_hash(ruby: &Ruby, event: &DragEvent) -> Result<RHash, Error> {
+    let pair = |(x, y): (f32, f32)| (x as f64, y as f64);
+    let hash = ruby.hash_new_capa(7);
+    hash.aset(*keys::ID, event.id)?;
+    hash.aset(*keys::POSITION, pair(event.position))?;
+    hash.aset(*keys::DROPPED_AT, pair(event.dropped_at))?;
+    hash.aset(*keys::ORIGIN, pair(event.origin))?;
+    hash.aset(*keys::DROP_TARGET, event.drop_target.map(|target| target.id))?;
+    hash.aset(
+        *keys::HIT_REGION,
+        event.drop_target.is_some_and(|target| target.hit_region),
+    )?;
+    hash.aset(*keys::SNAPPED_BACK, event.snapped_back)?;
+    Ok(hash)
+}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let gestures_class = module.define_class("DragGestures", ruby.class_object())?;
+    gestures_class.define_singleton_method("new", function!(MagnusDragGestures::new, 0))?;
+    gestures_class.define_method("enable", method!(MagnusDragGestures::enable, -1))?;
+    gestures_class.define_method("disable", method!(MagnusDragGestures::disable, 1))?;
+    gestures_class.define_method("enabled?", method!(MagnusDragGestures::is_enabled, 1))?;
+    gestures_class.define_method("dragging?", method!(MagnusDragGestures::is_dragging, 0))?;
+    gestures_class.define_method("held_id", method!(MagnusDragGestures::held_id, 0))?;
+    gestures_class.define_method("position", method!(MagnusDragGestures::position, 0))?;
+    gestures_class.define_method("press", method!(MagnusDragGestures::press, 3))?;
+    gestures_class.define_method("drag_to", method!(MagnusDragGestures::drag_to, 1))?;
+    gestures_class.define_method("release", method!(MagnusDragGestures::release, -1))?;
+    gestures_class.define_method("drain_events", method!(MagnusDragGestures::drain_events, 0))?;
+
+    Ok(())
+}
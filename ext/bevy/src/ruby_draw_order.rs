@@ -0,0 +1,99 @@
+use bevy_ruby::{DrawOrder, DrawOrderChange};
+use magnus::{Error, RModule, Ruby, function, method, prelude::*};
+use std::cell::RefCell;
+
+/// The per-layer order lists behind `RenderApp#bring_to_front`, driven by
+/// hand instead of by sprite syncs.
+#[magnus::wrap(class = "Bevy::DrawOrder", free_immediately, size)]
+pub struct MagnusDrawOrder {
+    inner: RefCell<DrawOrder>,
+}
+
+impl MagnusDrawOrder {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(DrawOrder::new()),
+        }
+    }
+
+    /// `insert(id, layer)`: false if `id` is already on `layer`.
+    fn insert(&self, id: u64, layer: i32) -> bool {
+        self.inner.borrow_mut().insert(id, layer)
+    }
+
+    fn remove(&self, id: u64) -> bool {
+        self.inner.borrow_mut().remove(id)
+    }
+
+    fn bring_to_front(&self, id: u64) -> bool {
+        self.inner.borrow_mut().apply(id, DrawOrderChange::Front)
+    }
+
+    fn send_to_back(&self, id: u64) -> bool {
+        self.inner.borrow_mut().apply(id, DrawOrderChange::Back)
+    }
+
+    fn set_index(&self, id: u64, index: usize) -> bool {
+        self.inner.borrow_mut().apply(id, DrawOrderChange::Index(index))
+    }
+
+    fn index(&self, id: u64) -> Option<usize> {
+        self.inner.borrow().index(id)
+    }
+
+    fn layer(&self, id: u64) -> Option<i32> {
+        self.inner.borrow().layer(id)
+    }
+
+    fn layer_ids(&self, layer: i32) -> Vec<u64> {
+        self.inner.borrow().layer_ids(layer)
+    }
+
+    fn is_ordered(&self, layer: i32) -> bool {
+        self.inner.borrow().is_ordered(layer)
+    }
+
+    fn offset(&self, id: u64) -> f64 {
+        self.inner.borrow().applied_offset(id) as f64
+    }
+
+    /// `[[id, delta], ...]` for the sprites whose offset moved since the last call.
+    fn take_changes(&self) -> Vec<(u64, f64)> {
+        self.inner
+            .borrow_mut()
+            .take_changes()
+            .into_iter()
+            .map(|(id, delta)| (id, delta as f64))
+            .collect()
+    }
+
+    fn has_changes(&self) -> bool {
+        self.inner.borrow().has_changes()
+    }
+
+    fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+}
+
+unsafe impl Send for MagnusDrawOrder {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let order_class = module.define_class("DrawOrder", ruby.class_object())?;
+    order_class.define_singleton_method("new", function!(MagnusDrawOrder::new, 0))?;
+    order_class.define_method("insert", method!(MagnusDrawOrder::insert, 2))?;
+    order_class.define_method("remove", method!(MagnusDrawOrder::remove, 1))?;
+    order_class.define_method("bring_to_front", method!(MagnusDrawOrder::bring_to_front, 1))?;
+    order_class.define_method("send_to_back", method!(MagnusDrawOrder::send_to_back, 1))?;
+    order_class.define_method("set_index", method!(MagnusDrawOrder::set_index, 2))?;
+    order_class.define_method("index", method!(MagnusDrawOrder::index, 1))?;
+    order_class.define_method("layer", method!(MagnusDrawOrder::layer, 1))?;
+    order_class.define_method("layer_ids", method!(MagnusDrawOrder::layer_ids, 1))?;
+    order_class.define_method("ordered?", method!(MagnusDrawOrder::is_ordered, 1))?;
+    order_class.define_method("offset", method!(MagnusDrawOrder::offset, 1))?;
+    order_class.define_method("take_changes", method!(MagnusDrawOrder::take_changes, 0))?;
+    order_class.define_method("changes?", method!(MagnusDrawOrder::has_changes, 0))?;
+    order_class.define_method("size", method!(MagnusDrawOrder::len, 0))?;
+
+    Ok(())
+}
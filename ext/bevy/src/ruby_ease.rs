@@ -0,0 +1,71 @@
+use bevy_ruby::{Ease, EaseParams};
+use magnus::{Error, RArray, RHash, RModule, Ruby, TryConvert, Value, function, prelude::*};
+
+use crate::keys;
+use crate::ruby_render_app::{get_hash_value, symbol_or_string};
+
+fn parse_ease(ruby: &Ruby, name: Value) -> Result<Ease, Error> {
+    let name = symbol_or_string(name)?;
+    Ease::parse(&name).map_err(|error| Error::new(ruby.exception_arg_error(), error.to_string()))
+}
+
+/// `apply(name, t, overshoot: 1.70158, period: 0.3)`. `overshoot:` is only
+/// accepted by the back curves and `period:` only by the elastic ones.
+fn apply(args: &[Value]) -> Result<f64, Error> {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    let (positional, options) = match args.split_last() {
+        Some((&last, init)) if RHash::from_value(last).is_some() => (init, RHash::from_value(last)),
+        _ => (args, None),
+    };
+    let [name, t] = positional[..] else {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("wrong number of arguments (given {}, expected 2)", positional.len()),
+        ));
+    };
+    let ease = parse_ease(&ruby, name)?;
+    let t = f64::try_convert(t)? as f32;
+
+    let mut params = EaseParams::default();
+    if let Some(options) = options {
+        if let Some(overshoot) = get_hash_value::<f64>(&ruby, &options, &keys::OVERSHOOT)? {
+            if !ease.is_back() {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("{} does not take overshoot:", ease.name()),
+                ));
+            }
+            params.overshoot = overshoot as f32;
+        }
+        if let Some(period) = get_hash_value::<f64>(&ruby, &options, &keys::PERIOD)? {
+            if !ease.is_elastic() {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("{} does not take period:", ease.name()),
+                ));
+            }
+            if !period.is_finite() || period <= 0.0 {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("period must be positive, got {}", period),
+                ));
+            }
+            params.period = period as f32;
+        }
+    }
+    Ok(ease.apply_with(t, params) as f64)
+}
+
+/// Canonical curve names, as symbols, in `Ease::NAMES` order.
+fn names() -> RArray {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    ruby.ary_from_iter(Ease::NAMES.iter().map(|name| ruby.to_symbol(name)))
+}
+
+pub fn define(_ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let ease_module = module.define_module("Ease")?;
+    ease_module.define_module_function("apply", function!(apply, -1))?;
+    ease_module.define_module_function("names", function!(names, 0))?;
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use bevy_ruby::{Event, Events};
+use magnus::{Error, RArray, RHash, RModule, Ruby, Symbol, TryConvert, Value, function, method, prelude::*};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::ruby_component::{dynamic_value_to_ruby, ruby_to_dynamic_value};
+
+/// Named queues of hash-shaped events, backed by the native `Events`.
+#[magnus::wrap(class = "Bevy::EventBus", free_immediately, size)]
+pub struct MagnusEventBus {
+    inner: RefCell<Events>,
+}
+
+impl MagnusEventBus {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(Events::new()),
+        }
+    }
+
+    fn unknown_type(ruby: &Ruby, type_name: &str) -> Error {
+        Error::new(
+            ruby.exception_arg_error(),
+            format!("unknown event type: {} (register it first)", type_name),
+        )
+    }
+
+    fn register(&self, type_name: String) {
+        self.inner.borrow_mut().register(&type_name);
+    }
+
+    fn is_registered(&self, type_name: String) -> bool {
+        self.inner.borrow().is_registered(&type_name)
+    }
+
+    /// Queues an event whose data is `hash`, with its keys turned into strings.
+    fn send(&self, type_name: String, hash: RHash) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !self.inner.borrow().is_registered(&type_name) {
+            return Err(Self::unknown_type(&ruby, &type_name));
+        }
+
+        let mut data = HashMap::new();
+        hash.foreach(|key: Value, value: Value| {
+            let key = match Symbol::try_convert(key) {
+                Ok(sym) => sym.name()?.to_string(),
+                Err(_) => key.to_string(),
+            };
+            data.insert(key, ruby_to_dynamic_value(value)?);
+            Ok(magnus::r_hash::ForEach::Continue)
+        })?;
+
+        self.inner
+            .borrow_mut()
+            .send(Event::with_data(&type_name, data));
+        Ok(())
+    }
+
+    /// Events sent since the last read, oldest first, as hashes with symbol keys.
+    fn read(&self, type_name: String) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let mut events = self.inner.borrow_mut();
+        let Some(unread) = events.read(&type_name) else {
+            return Err(Self::unknown_type(&ruby, &type_name));
+        };
+
+        let array = ruby.ary_new();
+        for event in unread {
+            let hash = ruby.hash_new_capa(event.data.len());
+            for (key, value) in &event.data {
+                hash.aset(ruby.to_symbol(key), dynamic_value_to_ruby(&ruby, value)?)?;
+            }
+            array.push(hash)?;
+        }
+        Ok(array)
+    }
+
+    fn unread_count(&self, type_name: String) -> usize {
+        self.inner
+            .borrow()
+            .get_queue(&type_name)
+            .map_or(0, |queue| queue.unread_count())
+    }
+
+    /// Drops every queued event of `type_name`, read or not.
+    fn clear(&self, type_name: String) {
+        self.inner.borrow_mut().clear(&type_name);
+    }
+
+    fn clear_all(&self) {
+        self.inner.borrow_mut().clear_all();
+    }
+}
+
+unsafe impl Send for MagnusEventBus {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let bus_class = module.define_class("EventBus", ruby.class_object())?;
+    bus_class.define_singleton_method("new", function!(MagnusEventBus::new, 0))?;
+    bus_class.define_method("register", method!(MagnusEventBus::register, 1))?;
+    bus_class.define_method("registered?", method!(MagnusEventBus::is_registered, 1))?;
+    bus_class.define_method("send", method!(MagnusEventBus::send, 2))?;
+    bus_class.define_method("read", method!(MagnusEventBus::read, 1))?;
+    bus_class.define_method("unread_count", method!(MagnusEventBus::unread_count, 1))?;
+    bus_class.define_method("clear", method!(MagnusEventBus::clear, 1))?;
+    bus_class.define_method("clear_all", method!(MagnusEventBus::clear_all, 0))?;
+
+    Ok(())
+}
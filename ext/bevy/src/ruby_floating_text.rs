@@ -0,0 +1,136 @@
+use bevy_ruby::{FloatingTextFrame, FloatingTextRequest, FloatingTexts};
+use magnus::value::LazyId;
+use magnus::{Error, RArray, RHash, RModule, Ruby, function, method, prelude::*};
+use std::cell::RefCell;
+
+use crate::keys;
+use crate::ruby_render_app::get_hash_value;
+
+/// Builds a request from `x`, `y`, the text and
+/// `{ color_r:, color_g:, color_b:, color_a:, rise:, duration:, font_size: }`,
+/// defaulting what is left out.
+pub(crate) fn parse_floating_text_request(
+    ruby: &Ruby,
+    x: f64,
+    y: f64,
+    content: String,
+    options: &RHash,
+) -> Result<FloatingTextRequest, Error> {
+    let defaults = FloatingTextRequest::default();
+    let float = |key: &LazyId, default: f32| -> Result<f32, Error> {
+        Ok(get_hash_value::<f64>(ruby, options, key)?.map_or(default, |value| value as f32))
+    };
+
+    let request = FloatingTextRequest {
+        x: x as f32,
+        y: y as f32,
+        content,
+        color: [
+            float(&keys::COLOR_R, defaults.color[0])?,
+            float(&keys::COLOR_G, defaults.color[1])?,
+            float(&keys::COLOR_B, defaults.color[2])?,
+            float(&keys::COLOR_A, defaults.color[3])?,
+        ],
+        rise: float(&keys::RISE, defaults.rise)?,
+        duration: float(&keys::DURATION, defaults.duration)?,
+        font_size: float(&keys::FONT_SIZE, defaults.font_size)?,
+    };
+
+    if !(request.duration.is_finite() && request.duration > 0.0) {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("floating text duration must be positive, got {}", request.duration),
+        ));
+    }
+    if !(request.font_size.is_finite() && request.font_size > 0.0) {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("floating text font_size must be positive, got {}", request.font_size),
+        ));
+    }
+    Ok(request)
+}
+
+fn frame_to_hash(ruby: &Ruby, frame: &FloatingTextFrame) -> Result<RHash, Error> {
+    let hash = ruby.hash_new();
+    hash.aset(ruby.to_symbol("id"), frame.ruby_entity_id)?;
+    hash.aset(ruby.to_symbol("x"), frame.x)?;
+    hash.aset(ruby.to_symbol("y"), frame.y)?;
+    hash.aset(ruby.to_symbol("alpha"), frame.alpha)?;
+    Ok(hash)
+}
+
+/// The lifetime, stacking and cap bookkeeping behind `App#floating_text`,
+/// stepped by hand instead of by the render loop.
+#[magnus::wrap(class = "Bevy::FloatingTexts", free_immediately, size)]
+pub struct MagnusFloatingTexts {
+    inner: RefCell<FloatingTexts>,
+}
+
+impl MagnusFloatingTexts {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(FloatingTexts::new()),
+        }
+    }
+
+    /// `spawn(x, y, text, options)`: the text's ID.
+    fn spawn(&self, x: f64, y: f64, content: String, options: RHash) -> Result<u64, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let request = parse_floating_text_request(&ruby, x, y, content, &options)?;
+        Ok(self.inner.borrow_mut().spawn(request))
+    }
+
+    /// `[{ id:, x:, y:, alpha: }, ...]` for the texts still alive, oldest first.
+    fn advance(&self, delta_secs: f64) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let frames = self.inner.borrow_mut().advance(delta_secs as f32);
+
+        let result = ruby.ary_new_capa(frames.len());
+        for frame in &frames {
+            result.push(frame_to_hash(&ruby, frame)?)?;
+        }
+        Ok(result)
+    }
+
+    fn drain_removed(&self) -> Vec<u64> {
+        self.inner.borrow_mut().drain_removed()
+    }
+
+    fn slot(&self, ruby_entity_id: u64) -> Option<usize> {
+        self.inner.borrow().slot(ruby_entity_id)
+    }
+
+    fn cap(&self) -> usize {
+        self.inner.borrow().cap()
+    }
+
+    fn set_cap(&self, cap: usize) {
+        self.inner.borrow_mut().set_cap(cap);
+    }
+
+    fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    fn clear(&self) {
+        self.inner.borrow_mut().clear();
+    }
+}
+
+unsafe impl Send for MagnusFloatingTexts {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let floating_class = module.define_class("FloatingTexts", ruby.class_object())?;
+    floating_class.define_singleton_method("new", function!(MagnusFloatingTexts::new, 0))?;
+    floating_class.define_method("spawn", method!(MagnusFloatingTexts::spawn, 4))?;
+    floating_class.define_method("advance", method!(MagnusFloatingTexts::advance, 1))?;
+    floating_class.define_method("drain_removed", method!(MagnusFloatingTexts::drain_removed, 0))?;
+    floating_class.define_method("slot", method!(MagnusFloatingTexts::slot, 1))?;
+    floating_class.define_method("cap", method!(MagnusFloatingTexts::cap, 0))?;
+    floating_class.define_method("cap=", method!(MagnusFloatingTexts::set_cap, 1))?;
+    floating_class.define_method("size", method!(MagnusFloatingTexts::len, 0))?;
+    floating_class.define_method("clear", method!(MagnusFloatingTexts::clear, 0))?;
+
+    Ok(())
+}
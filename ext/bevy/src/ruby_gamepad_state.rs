@@ -0,0 +1,120 @@
+use bevy_ruby::{AuditMap, GamepadInputState, InputState};
+use magnus::{Error, RArray, RHash, RModule, Ruby, function, method, prelude::*};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+
+use crate::keys;
+
+thread_local! {
+    /// Reused by `gamepads_to_array` to order gamepads without allocating per call.
+    static GAMEPAD_ID_SCRATCH: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The `RenderApp#gamepads_state` array: one hash per gamepad, ordered by id.
+pub(crate) fn gamepads_to_array(
+    ruby: &Ruby,
+    gamepads: &AuditMap<u64, GamepadInputState>,
+) -> Result<RArray, Error> {
+    GAMEPAD_ID_SCRATCH.with(|ids| {
+        let mut ids = ids.borrow_mut();
+        ids.clear();
+        ids.extend(gamepads.keys().copied());
+        ids.sort_unstable();
+
+        let result = ruby.ary_new_capa(ids.len());
+        for id in ids.iter() {
+            result.push(gamepad_state_to_hash(ruby, &gamepads[id])?)?;
+        }
+        Ok(result)
+    })
+}
+
+fn gamepad_state_to_hash(ruby: &Ruby, state: &GamepadInputState) -> Result<RHash, Error> {
+    let hash = ruby.hash_new_capa(7);
+    hash.aset(*keys::ID, state.id)?;
+    hash.aset(*keys::NAME, state.name.as_str())?;
+    hash.aset(*keys::BUTTONS_PRESSED, sorted_names_array(ruby, &state.buttons_pressed)?)?;
+    hash.aset(
+        *keys::BUTTONS_JUST_PRESSED,
+        sorted_names_array(ruby, &state.buttons_just_pressed)?,
+    )?;
+    hash.aset(
+        *keys::BUTTONS_JUST_RELEASED,
+        sorted_names_array(ruby, &state.buttons_just_released)?,
+    )?;
+
+    let axes = ruby.hash_new_capa(state.axes.len());
+    for (axis, value) in &state.axes {
+        axes.aset(axis.as_str(), *value as f64)?;
+    }
+    hash.aset(*keys::AXES, axes)?;
+    hash.aset(*keys::BATTERY, state.battery.map(|level| level as f64))?;
+    Ok(hash)
+}
+
+fn sorted_names_array(ruby: &Ruby, names: &BTreeSet<String>) -> Result<RArray, Error> {
+    let array = ruby.ary_new_capa(names.len());
+    for name in names {
+        array.push(name.as_str())?;
+    }
+    Ok(array)
+}
+
+/// Gamepad input set by hand and marshaled the way `RenderApp#gamepads_state`
+/// marshals connected gamepads. Used by specs; not part of the public API.
+#[magnus::wrap(class = "Bevy::Internal::GamepadStates", free_immediately, size)]
+pub struct MagnusGamepadStates {
+    inner: RefCell<InputState>,
+}
+
+impl MagnusGamepadStates {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(InputState::new()),
+        }
+    }
+
+    fn connect(&self, id: u64, name: String) {
+        self.inner.borrow_mut().set_gamepad_connected(id, &name);
+    }
+
+    fn press(&self, id: u64, button: String) {
+        let mut input = self.inner.borrow_mut();
+        input.set_gamepad_button_pressed(id, &button);
+        input.set_gamepad_button_just_pressed(id, &button);
+    }
+
+    fn release(&self, id: u64, button: String) {
+        self.inner.borrow_mut().set_gamepad_button_just_released(id, &button);
+    }
+
+    fn set_axis(&self, id: u64, axis: String, value: f64) {
+        self.inner.borrow_mut().set_gamepad_axis(id, &axis, value as f32);
+    }
+
+    fn set_battery(&self, id: u64, level: Option<f64>) {
+        self.inner
+            .borrow_mut()
+            .set_gamepad_battery(id, level.map(|level| level as f32));
+    }
+
+    fn states(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        gamepads_to_array(&ruby, &self.inner.borrow().gamepads)
+    }
+}
+
+unsafe impl Send for MagnusGamepadStates {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let states_class = module.define_class("GamepadStates", ruby.class_object())?;
+    states_class.define_singleton_method("new", function!(MagnusGamepadStates::new, 0))?;
+    states_class.define_method("connect", method!(MagnusGamepadStates::connect, 2))?;
+    states_class.define_method("press", method!(MagnusGamepadStates::press, 2))?;
+    states_class.define_method("release", method!(MagnusGamepadStates::release, 2))?;
+    states_class.define_method("set_axis", method!(MagnusGamepadStates::set_axis, 3))?;
+    states_class.define_method("set_battery", method!(MagnusGamepadStates::set_battery, 2))?;
+    states_class.define_method("states", method!(MagnusGamepadStates::states, 0))?;
+
+    Ok(())
+}
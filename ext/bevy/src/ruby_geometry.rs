@@ -0,0 +1,47 @@
+use bevy_ruby::triangulate;
+use magnus::{Error, RArray, RModule, Ruby, TryConvert, Value, function, prelude::*};
+
+/// Flattens triangles to `[x0, y0, x1, y1, x2, y2, ...]`.
+pub(crate) fn triangles_to_array(ruby: &Ruby, triangles: &[[[f32; 2]; 3]]) -> Result<RArray, Error> {
+    let array = ruby.ary_new_capa(triangles.len() * 6);
+    for [x, y] in triangles.iter().flatten() {
+        array.push(*x as f64)?;
+        array.push(*y as f64)?;
+    }
+    Ok(array)
+}
+
+/// `triangulate([[x, y], ...])`: ear-clips a simple polygon in either winding.
+///
+/// Raises `ArgumentError` naming the problem for degenerate or
+/// self-intersecting input, and for rings of rings (holes are unsupported).
+fn triangulate_points(points: RArray) -> Result<RArray, Error> {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    let mut parsed: Vec<[f32; 2]> = Vec::with_capacity(points.len());
+    for point in points.into_iter() {
+        let pair = RArray::try_convert(point)?;
+        if pair.entry::<Value>(0).is_ok_and(|first| RArray::try_convert(first).is_ok()) {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                "polygons with holes are not supported; pass a single outer ring",
+            ));
+        }
+        let (x, y): (f64, f64) = TryConvert::try_convert(pair.as_value())?;
+        parsed.push([x as f32, y as f32]);
+    }
+
+    let triangles = triangulate(&parsed)
+        .map_err(|error| Error::new(ruby.exception_arg_error(), error.to_string()))?;
+    let triangles: Vec<[[f32; 2]; 3]> = triangles
+        .into_iter()
+        .map(|[a, b, c]| [parsed[a], parsed[b], parsed[c]])
+        .collect();
+    triangles_to_array(&ruby, &triangles)
+}
+
+pub fn define(_ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let geometry_module = module.define_module("Geometry")?;
+    geometry_module.define_module_function("triangulate", function!(triangulate_points, 1))?;
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use bevy_ruby::{GroupThrottle, SpriteData, SpriteOperation, TransformData};
+use magnus::{Error, RArray, RModule, Ruby, function, method, prelude::*};
+use std::cell::RefCell;
+
+/// The due-frame scheduling and sync coalescing behind
+/// `App#set_group_update_divisor`, fed frame by frame instead of by the
+/// render loop.
+#[magnus::wrap(class = "Bevy::GroupThrottle", free_immediately, size)]
+pub struct MagnusGroupThrottle {
+    inner: RefCell<GroupThrottle>,
+    queued: RefCell<Vec<SpriteOperation>>,
+}
+
+impl MagnusGroupThrottle {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(GroupThrottle::new()),
+            queued: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// `set_divisor(group, divisor, frame)`: the group is due on `frame` and
+    /// every `divisor`th frame after it.
+    fn set_divisor(&self, group: String, divisor: u32, frame: u32) {
+        self.inner.borrow_mut().set_divisor(&group, divisor, frame);
+    }
+
+    fn divisor(&self, group: String) -> u32 {
+        self.inner.borrow().schedule().divisor(&group)
+    }
+
+    fn is_due(&self, group: Option<String>, frame: u32) -> bool {
+        self.inner.borrow().schedule().is_due(group.as_deref(), frame)
+    }
+
+    fn due_groups(&self, frame: u32) -> Vec<String> {
+        self.inner.borrow().schedule().due_groups(frame)
+    }
+
+    /// Queues a sync of `id` in `group` (nil for none) to `x`, `y`.
+    fn sync(&self, ruby_entity_id: u64, group: Option<String>, x: f64, y: f64) {
+        self.queued.borrow_mut().push(SpriteOperation::Sync {
+            ruby_entity_id,
+            sprite_data: SpriteData {
+                group,
+                ..Default::default()
+            },
+            transform_data: TransformData {
+                translation_x: x as f32,
+                translation_y: y as f32,
+                ..Default::default()
+            },
+        });
+    }
+
+    fn remove(&self, ruby_entity_id: u64) {
+        self.queued
+            .borrow_mut()
+            .push(SpriteOperation::Remove { ruby_entity_id });
+    }
+
+    /// Runs the queued operations through the throttle for `frame`:
+    /// `[[id, x, y], ...]` for the syncs applied, in the order applied.
+    fn flush(&self, frame: u32) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let ops = std::mem::take(&mut *self.queued.borrow_mut());
+        let applied = self.inner.borrow_mut().filter(ops, frame);
+
+        let result = ruby.ary_new();
+        for op in applied {
+            if let SpriteOperation::Sync {
+                ruby_entity_id,
+                transform_data,
+                ..
+            } = op
+            {
+                result.push((
+                    ruby_entity_id,
+                    transform_data.translation_x as f64,
+                    transform_data.translation_y as f64,
+                ))?;
+            }
+        }
+        Ok(result)
+    }
+
+    fn held_count(&self) -> usize {
+        self.inner.borrow().held_count()
+    }
+
+    fn clear(&self) {
+        self.inner.borrow_mut().clear();
+        self.queued.borrow_mut().clear();
+    }
+}
+
+unsafe impl Send for MagnusGroupThrottle {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let throttle_class = module.define_class("GroupThrottle", ruby.class_object())?;
+    throttle_class.define_singleton_method("new", function!(MagnusGroupThrottle::new, 0))?;
+    throttle_class.define_method("set_divisor", method!(MagnusGroupThrottle::set_divisor, 3))?;
+    throttle_class.define_method("divisor", method!(MagnusGroupThrottle::divisor, 1))?;
+    throttle_class.define_method("due?", method!(MagnusGroupThrottle::is_due, 2))?;
+    throttle_class.define_method("due_groups", method!(MagnusGroupThrottle::due_groups, 1))?;
+    throttle_class.define_method("sync", method!(MagnusGroupThrottle::sync, 4))?;
+    throttle_class.define_method("remove", method!(MagnusGroupThrottle::remove, 1))?;
+    throttle_class.define_method("flush", method!(MagnusGroupThrottle::flush, 1))?;
+    throttle_class.define_method("held_count", method!(MagnusGroupThrottle::held_count, 0))?;
+    throttle_class.define_method("clear", method!(MagnusGroupThrottle::clear, 0))?;
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use bevy_ruby::{HitRegion, HitRegionUpdate, HitRegions};
+use magnus::{Error, RHash, RModule, Ruby, Value, function, method, prelude::*};
+use std::cell::RefCell;
+
+use crate::keys;
+use crate::ruby_render_app::parse_hit_region_args;
+
+/// Hit regions resolved the same way the render bridge's picking backend resolves them.
+#[magnus::wrap(class = "Bevy::HitRegions", free_immediately, size)]
+pub struct MagnusHitRegions {
+    inner: RefCell<HitRegions>,
+}
+
+impl MagnusHitRegions {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(HitRegions::new()),
+        }
+    }
+
+    fn register(&self, args: &[Value]) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (id, [x, y, width, height], layer, screen_space) = parse_hit_region_args(&ruby, args)?;
+        self.inner.borrow_mut().register(
+            id,
+            HitRegion {
+                x,
+                y,
+                width,
+                height,
+                layer: layer.unwrap_or(0),
+                screen_space: screen_space.unwrap_or(false),
+            },
+        );
+        Ok(())
+    }
+
+    /// Returns false when `id` isn't registered.
+    fn update(&self, args: &[Value]) -> Result<bool, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (id, [x, y, width, height], layer, screen_space) = parse_hit_region_args(&ruby, args)?;
+        Ok(self.inner.borrow_mut().update(
+            id,
+            HitRegionUpdate {
+                x,
+                y,
+                width,
+                height,
+                layer,
+                screen_space,
+            },
+        ))
+    }
+
+    fn remove(&self, id: u64) -> bool {
+        self.inner.borrow_mut().remove(id)
+    }
+
+    /// `{x:, y:, width:, height:, layer:, screen_space:}`, or nil when unregistered.
+    fn region(&self, id: u64) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let inner = self.inner.borrow();
+        let Some(region) = inner.get(id) else {
+            return Ok(None);
+        };
+        let hash = ruby.hash_new_capa(6);
+        hash.aset(*keys::X, region.x as f64)?;
+        hash.aset(*keys::Y, region.y as f64)?;
+        hash.aset(*keys::WIDTH, region.width as f64)?;
+        hash.aset(*keys::HEIGHT, region.height as f64)?;
+        hash.aset(*keys::LAYER, region.layer)?;
+        hash.aset(*keys::SCREEN_SPACE, region.screen_space)?;
+        Ok(Some(hash))
+    }
+
+    fn size(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    /// ID of the topmost region under a pointer at `screen_x, screen_y`
+    /// window pixels and `world_x, world_y` world units, or nil.
+    fn hit_test(&self, screen_x: f64, screen_y: f64, world_x: Option<f64>, world_y: Option<f64>) -> Option<u64> {
+        let world = world_x.zip(world_y).map(|(x, y)| (x as f32, y as f32));
+        self.inner
+            .borrow()
+            .topmost((screen_x as f32, screen_y as f32), world)
+            .map(|(id, _)| id)
+    }
+}
+
+unsafe impl Send for MagnusHitRegions {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let regions_class = module.define_class("HitRegions", ruby.class_object())?;
+    regions_class.define_singleton_method("new", function!(MagnusHitRegions::new, 0))?;
+    regions_class.define_method("register", method!(MagnusHitRegions::register, -1))?;
+    regions_class.define_method("update", method!(MagnusHitRegions::update, -1))?;
+    regions_class.define_method("remove", method!(MagnusHitRegions::remove, 1))?;
+    regions_class.define_method("region", method!(MagnusHitRegions::region, 1))?;
+    regions_class.define_method("size", method!(MagnusHitRegions::size, 0))?;
+    regions_class.define_method("hit_test", method!(MagnusHitRegions::hit_test, 4))?;
+
+    Ok(())
+}
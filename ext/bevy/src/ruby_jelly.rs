@@ -0,0 +1,98 @@
+use bevy_ruby::JellyMesh;
+use magnus::{Error, RArray, RHash, RModule, Ruby, TryConvert, Value, function, method, prelude::*};
+use std::cell::RefCell;
+
+use crate::ruby_render_app::parse_jelly_config;
+
+/// The vertex springs of a jelly sprite, stepped the same way the render bridge steps them.
+#[magnus::wrap(class = "Bevy::JellyMesh", free_immediately, size)]
+pub struct MagnusJellyMesh {
+    inner: RefCell<JellyMesh>,
+}
+
+impl MagnusJellyMesh {
+    /// `new(width, height)` or `new(width, height, subdivisions:, stiffness:, damping:)`.
+    fn new(args: &[Value]) -> Result<Self, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (width, height, options) = match args {
+            [width, height] => (f64::try_convert(*width)?, f64::try_convert(*height)?, None),
+            [width, height, options] => (
+                f64::try_convert(*width)?,
+                f64::try_convert(*height)?,
+                Some(RHash::try_convert(*options)?),
+            ),
+            _ => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("wrong number of arguments (given {}, expected 2..3)", args.len()),
+                ));
+            }
+        };
+        let config = match options {
+            Some(options) => parse_jelly_config(&ruby, &options)?,
+            None => Default::default(),
+        };
+        Ok(Self {
+            inner: RefCell::new(JellyMesh::new([width as f32, height as f32], config)),
+        })
+    }
+
+    fn apply_impulse(&self, x: f64, y: f64, strength: f64) {
+        self.inner
+            .borrow_mut()
+            .apply_impulse([x as f32, y as f32], strength as f32);
+    }
+
+    fn step(&self, delta: f64) {
+        self.inner.borrow_mut().step(delta as f32);
+    }
+
+    /// Current vertex positions as flat `[x, y, x, y, ...]`, row by row from the bottom-left.
+    fn positions(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let positions = self.inner.borrow().positions();
+        let array = ruby.ary_new_capa(positions.len() * 2);
+        for [x, y] in positions {
+            array.push(x as f64)?;
+            array.push(y as f64)?;
+        }
+        Ok(array)
+    }
+
+    fn columns(&self) -> usize {
+        self.inner.borrow().columns()
+    }
+
+    fn energy(&self) -> f64 {
+        self.inner.borrow().energy() as f64
+    }
+
+    fn max_speed(&self) -> f64 {
+        self.inner.borrow().max_speed() as f64
+    }
+
+    fn resting(&self) -> bool {
+        self.inner.borrow().is_resting()
+    }
+
+    fn reset(&self) {
+        self.inner.borrow_mut().reset();
+    }
+}
+
+unsafe impl Send for MagnusJellyMesh {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let jelly_class = module.define_class("JellyMesh", ruby.class_object())?;
+    jelly_class.define_singleton_method("new", function!(MagnusJellyMesh::new, -1))?;
+    jelly_class.define_method("apply_impulse", method!(MagnusJellyMesh::apply_impulse, 3))?;
+    jelly_class.define_method("step", method!(MagnusJellyMesh::step, 1))?;
+    jelly_class.define_method("positions", method!(MagnusJellyMesh::positions, 0))?;
+    jelly_class.define_method("columns", method!(MagnusJellyMesh::columns, 0))?;
+    jelly_class.define_method("energy", method!(MagnusJellyMesh::energy, 0))?;
+    jelly_class.define_method("max_speed", method!(MagnusJellyMesh::max_speed, 0))?;
+    jelly_class.define_method("resting?", method!(MagnusJellyMesh::resting, 0))?;
+    jelly_class.define_method("reset", method!(MagnusJellyMesh::reset, 0))?;
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use bevy_ruby::{
+    DEFAULT_JOURNAL_MAX_BYTES, JournalOperation, JournalReplay, MeshOperation, OperationJournal,
+    SpriteOperation, TextOperation, journal_frame_checksum,
+};
+use magnus::{Error, RArray, RHash, RModule, Ruby, TryConvert, Value, function, method, prelude::*};
+use std::cell::RefCell;
+
+use crate::keys;
+use crate::ruby_render_app::{
+    get_hash_value, parse_mesh_data, parse_mesh_transform_data, parse_sprite_data,
+    parse_text_data, parse_text_transform_data, parse_transform_data,
+};
+
+/// Opens the journal for `(path, max_mb: 50)` arguments.
+pub(crate) fn create_journal(ruby: &Ruby, args: &[Value]) -> Result<OperationJournal, Error> {
+    let Some(&path_value) = args.first() else {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            "wrong number of arguments (given 0, expected 1..2)",
+        ));
+    };
+    let path = String::try_convert(path_value)?;
+    let max_mb = match args.get(1) {
+        Some(value) => {
+            let hash: RHash = TryConvert::try_convert(*value)?;
+            get_hash_value::<f64>(ruby, &hash, &keys::MAX_MB)?
+        }
+        None => None,
+    };
+    let max_bytes = match max_mb {
+        Some(max_mb) if !(max_mb.is_finite() && max_mb > 0.0) => {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("journal max_mb must be positive, got {}", max_mb),
+            ));
+        }
+        Some(max_mb) => (max_mb * 1024.0 * 1024.0) as u64,
+        None => DEFAULT_JOURNAL_MAX_BYTES,
+    };
+    OperationJournal::create(&path, max_bytes).map_err(|error| {
+        Error::new(
+            ruby.exception_io_error(),
+            format!("cannot write journal {}: {}", path, error),
+        )
+    })
+}
+
+/// Reads and parses the journal at `path`.
+pub(crate) fn load_journal(ruby: &Ruby, path: &str) -> Result<JournalReplay, Error> {
+    let text = std::fs::read_to_string(path).map_err(|error| {
+        Error::new(
+            ruby.exception_io_error(),
+            format!("cannot read journal {}: {}", path, error),
+        )
+    })?;
+    JournalReplay::parse(&text).map_err(|error| Error::new(ruby.exception_arg_error(), error.to_string()))
+}
+
+/// Standalone journal writer, fed the same operations the render bridge journals.
+#[magnus::wrap(class = "Bevy::OperationJournal", free_immediately, size)]
+pub struct MagnusOperationJournal {
+    journal: RefCell<Option<OperationJournal>>,
+    frame: RefCell<Vec<JournalOperation>>,
+}
+
+impl MagnusOperationJournal {
+    fn new(args: &[Value]) -> Result<Self, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        Ok(Self {
+            journal: RefCell::new(Some(create_journal(&ruby, args)?)),
+            frame: RefCell::new(Vec::new()),
+        })
+    }
+
+    fn push(&self, op: JournalOperation) {
+        self.frame.borrow_mut().push(op);
+    }
+
+    fn record_sprite(
+        &self,
+        ruby_entity_id: u64,
+        sprite_hash: RHash,
+        transform_hash: RHash,
+    ) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        self.push(JournalOperation::Sprite(SpriteOperation::Sync {
+            ruby_entity_id,
+            sprite_data: parse_sprite_data(&ruby, &sprite_hash)?,
+            transform_data: parse_transform_data(&ruby, &transform_hash)?,
+        }));
+        Ok(())
+    }
+
+    fn record_remove_sprite(&self, ruby_entity_id: u64) {
+        self.push(JournalOperation::Sprite(SpriteOperation::Remove { ruby_entity_id }));
+    }
+
+    fn record_clear_sprites(&self) {
+        self.push(JournalOperation::Sprite(SpriteOperation::Clear));
+    }
+
+    fn record_text(
+        &self,
+        ruby_entity_id: u64,
+        text_hash: RHash,
+        transform_hash: RHash,
+    ) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        self.push(JournalOperation::Text(TextOperation::Sync {
+            ruby_entity_id,
+            text_data: parse_text_data(&ruby, &text_hash)?,
+            transform_data: parse_text_transform_data(&ruby, &transform_hash)?,
+        }));
+        Ok(())
+    }
+
+    fn record_remove_text(&self, ruby_entity_id: u64) {
+        self.push(JournalOperation::Text(TextOperation::Remove { ruby_entity_id }));
+    }
+
+    fn record_mesh(
+        &self,
+        ruby_entity_id: u64,
+        mesh_hash: RHash,
+        transform_hash: RHash,
+    ) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        self.push(JournalOperation::Mesh(MeshOperation::Sync {
+            ruby_entity_id,
+            mesh_data: parse_mesh_data(&ruby, &mesh_hash)?,
+            transform_data: parse_mesh_transform_data(&ruby, &transform_hash)?,
+        }));
+        Ok(())
+    }
+
+    fn record_remove_mesh(&self, ruby_entity_id: u64) {
+        self.push(JournalOperation::Mesh(MeshOperation::Remove { ruby_entity_id }));
+    }
+
+    fn record_camera(&self, x: f64, y: f64, z: f64, scale: f64, rotation: f64) {
+        self.push(JournalOperation::Camera {
+            position: (x as f32, y as f32, z as f32),
+            scale: scale as f32,
+            rotation: rotation as f32,
+        });
+    }
+
+    fn record_camera_shake(&self, intensity: f64, duration: f64) {
+        self.push(JournalOperation::CameraShake {
+            intensity: intensity as f32,
+            duration_secs: duration as f32,
+        });
+    }
+
+    /// Writes the recorded operations as `frame` and returns their checksum.
+    fn finish_frame(&self, frame: u32) -> Result<u64, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let operations = std::mem::take(&mut *self.frame.borrow_mut());
+        let mut journal = self.journal.borrow_mut();
+        let Some(journal) = journal.as_mut() else {
+            return Err(Error::new(ruby.exception_runtime_error(), "journal is closed"));
+        };
+        journal
+            .record_frame(frame, &operations)
+            .map_err(|error| Error::new(ruby.exception_io_error(), error.to_string()))?;
+        Ok(journal_frame_checksum(&operations))
+    }
+
+    fn close(&self) {
+        self.journal.borrow_mut().take();
+    }
+
+    /// `[[frame, checksum], ...]` for every frame of the journal at `path`,
+    /// comparable with the checksums `finish_frame` returned while recording.
+    fn frame_checksums(path: String) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let replay = load_journal(&ruby, &path)?;
+        let result = ruby.ary_new();
+        for (frame, operations) in replay.frames() {
+            result.push((frame, journal_frame_checksum(operations)))?;
+        }
+        Ok(result)
+    }
+}
+
+unsafe impl Send for MagnusOperationJournal {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let journal_class = module.define_class("OperationJournal", ruby.class_object())?;
+    journal_class.define_singleton_method("new", function!(MagnusOperationJournal::new, -1))?;
+    journal_class.define_singleton_method(
+        "frame_checksums",
+        function!(MagnusOperationJournal::frame_checksums, 1),
+    )?;
+    journal_class.define_method("record_sprite", method!(MagnusOperationJournal::record_sprite, 3))?;
+    journal_class.define_method(
+        "record_remove_sprite",
+        method!(MagnusOperationJournal::record_remove_sprite, 1),
+    )?;
+    journal_class.define_method(
+        "record_clear_sprites",
+        method!(MagnusOperationJournal::record_clear_sprites, 0),
+    )?;
+    journal_class.define_method("record_text", method!(MagnusOperationJournal::record_text, 3))?;
+    journal_class.define_method(
+        "record_remove_text",
+        method!(MagnusOperationJournal::record_remove_text, 1),
+    )?;
+    journal_class.define_method("record_mesh", method!(MagnusOperationJournal::record_mesh, 3))?;
+    journal_class.define_method(
+        "record_remove_mesh",
+        method!(MagnusOperationJournal::record_remove_mesh, 1),
+    )?;
+    journal_class.define_method("record_camera", method!(MagnusOperationJournal::record_camera, 5))?;
+    journal_class.define_method(
+        "record_camera_shake",
+        method!(MagnusOperationJournal::record_camera_shake, 2),
+    )?;
+    journal_class.define_method("finish_frame", method!(MagnusOperationJournal::finish_frame, 1))?;
+    journal_class.define_method("close", method!(MagnusOperationJournal::close, 0))?;
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use bevy_ruby::{KEY_CODE_NAMES, MODIFIER_KEY_NAMES};
+use magnus::{Error, RModule, Ruby, function, prelude::*};
+
+/// Every key name the render loop reports: one per Bevy `KeyCode`, followed
+/// by the side-agnostic modifier names.
+fn names() -> Vec<&'static str> {
+    KEY_CODE_NAMES
+        .iter()
+        .chain(MODIFIER_KEY_NAMES.iter())
+        .copied()
+        .collect()
+}
+
+pub fn define(_ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let key_code_module = module.define_module("KeyCode")?;
+    key_code_module.define_module_function("names", function!(names, 0))?;
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use bevy_ruby::InputState;
+use magnus::{Error, RModule, Ruby, function, method, prelude::*};
+use std::cell::RefCell;
+
+/// The hold timing behind `RenderApp#key_held_duration`, fed pressed keys
+/// frame by frame instead of by the keyboard. Used by specs; not part of the
+/// public API.
+#[magnus::wrap(class = "Bevy::Internal::KeyHolds", free_immediately, size)]
+pub struct MagnusKeyHolds {
+    inner: RefCell<InputState>,
+}
+
+impl MagnusKeyHolds {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(InputState::new()),
+        }
+    }
+
+    /// `frame(elapsed_secs, pressed_keys)`: starts a frame with these keys
+    /// down; keys that were up last frame count as just pressed.
+    fn frame(&self, elapsed_secs: f64, pressed_keys: Vec<String>) {
+        let mut input = self.inner.borrow_mut();
+        let previous = input.get_pressed_keys();
+        input.clear();
+        for key in &pressed_keys {
+            input.set_pressed(key);
+            if !previous.contains(key) {
+                input.set_just_pressed(key);
+            }
+        }
+        input.track_key_holds(elapsed_secs);
+    }
+
+    fn held_duration(&self, key: String) -> f64 {
+        self.inner.borrow().key_held_duration(&key)
+    }
+}
+
+unsafe impl Send for MagnusKeyHolds {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let holds_class = module.define_class("KeyHolds", ruby.class_object())?;
+    holds_class.define_singleton_method("new", function!(MagnusKeyHolds::new, 0))?;
+    holds_class.define_method("frame", method!(MagnusKeyHolds::frame, 2))?;
+    holds_class.define_method("held_duration", method!(MagnusKeyHolds::held_duration, 1))?;
+
+    Ok(())
+}
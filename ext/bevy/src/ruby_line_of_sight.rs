@@ -0,0 +1,112 @@
+use bevy_ruby::{Bounds, DEFAULT_SIGHT_CELL_SIZE, RayHit, SightGrid};
+use magnus::{Error, RArray, RHash, RModule, Ruby, TryConvert, Value, function, method, prelude::*};
+use std::cell::RefCell;
+
+use crate::keys;
+use crate::ruby_render_app::get_hash_value;
+
+/// Reads `x1, y1, x2, y2` as two points, which must be finite.
+pub(crate) fn parse_points(ruby: &Ruby, coordinates: [f64; 4]) -> Result<((f32, f32), (f32, f32)), Error> {
+    if let Some(value) = coordinates.iter().find(|value| !value.is_finite()) {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("coordinates must be finite, got {}", value),
+        ));
+    }
+    let [x1, y1, x2, y2] = coordinates.map(|value| value as f32);
+    Ok(((x1, y1), (x2, y2)))
+}
+
+/// `[id, x, y]` of the blocker and the point the segment first touches it.
+pub(crate) fn hit_to_tuple(hit: RayHit) -> (u64, f64, f64) {
+    (hit.ruby_entity_id, hit.point.0 as f64, hit.point.1 as f64)
+}
+
+/// Standalone blocker grid, queried the same way the render app queries synced sprites.
+#[magnus::wrap(class = "Bevy::SightGrid", free_immediately, size)]
+pub struct MagnusSightGrid {
+    inner: RefCell<SightGrid>,
+}
+
+impl MagnusSightGrid {
+    /// `new(cell_size: 64.0)`.
+    fn new(args: &[Value]) -> Result<Self, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let cell_size = match args.first() {
+            Some(value) => {
+                let hash: RHash = TryConvert::try_convert(*value)?;
+                get_hash_value::<f64>(&ruby, &hash, &keys::CELL_SIZE)?
+            }
+            None => None,
+        };
+        let cell_size = match cell_size {
+            Some(size) if !(size.is_finite() && size > 0.0) => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("cell_size must be positive, got {}", size),
+                ));
+            }
+            Some(size) => size as f32,
+            None => DEFAULT_SIGHT_CELL_SIZE,
+        };
+        Ok(Self {
+            inner: RefCell::new(SightGrid::new(cell_size)),
+        })
+    }
+
+    fn insert(&self, id: u64, min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (min, max) = parse_points(&ruby, [min_x, min_y, max_x, max_y])?;
+        self.inner.borrow_mut().insert(id, Bounds::new(min, max));
+        Ok(())
+    }
+
+    /// `raycast(x1, y1, x2, y2, ignore: [ids])`: `[id, x, y]` of the first
+    /// blocker from `(x1, y1)`, or nil.
+    fn raycast(&self, args: &[Value]) -> Result<Option<(u64, f64, f64)>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !(4..=5).contains(&args.len()) {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("wrong number of arguments (given {}, expected 4..5)", args.len()),
+            ));
+        }
+        let mut coordinates = [0.0; 4];
+        for (coordinate, value) in coordinates.iter_mut().zip(args) {
+            *coordinate = f64::try_convert(*value)?;
+        }
+        let (start, end) = parse_points(&ruby, coordinates)?;
+        let ignore = match args.get(4) {
+            Some(value) => {
+                let hash: RHash = TryConvert::try_convert(*value)?;
+                match get_hash_value::<RArray>(&ruby, &hash, &keys::IGNORE)? {
+                    Some(ids) => ids.to_vec::<u64>()?,
+                    None => Vec::new(),
+                }
+            }
+            None => Vec::new(),
+        };
+        Ok(self.inner.borrow().raycast(start, end, &ignore).map(hit_to_tuple))
+    }
+
+    fn cell_size(&self) -> f64 {
+        self.inner.borrow().cell_size() as f64
+    }
+
+    fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+}
+
+unsafe impl Send for MagnusSightGrid {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let grid_class = module.define_class("SightGrid", ruby.class_object())?;
+    grid_class.define_singleton_method("new", function!(MagnusSightGrid::new, -1))?;
+    grid_class.define_method("insert", method!(MagnusSightGrid::insert, 5))?;
+    grid_class.define_method("raycast", method!(MagnusSightGrid::raycast, -1))?;
+    grid_class.define_method("cell_size", method!(MagnusSightGrid::cell_size, 0))?;
+    grid_class.define_method("size", method!(MagnusSightGrid::len, 0))?;
+
+    Ok(())
+}
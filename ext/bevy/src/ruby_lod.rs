@@ -0,0 +1,80 @@
+use bevy_ruby::{DEFAULT_LOD_MARGIN, LodLevel, select_lod_level};
+use magnus::{Error, RArray, RModule, Ruby, TryConvert, Value, function, method, prelude::*};
+use std::cell::Cell;
+
+use crate::ruby_render_app::parse_lod_levels;
+
+/// Level selection for one entity, run the same way the render bridge runs it.
+#[magnus::wrap(class = "Bevy::LodSelector", free_immediately, size)]
+pub struct MagnusLodSelector {
+    levels: Vec<LodLevel>,
+    margin: Cell<f32>,
+    current: Cell<Option<usize>>,
+}
+
+impl MagnusLodSelector {
+    /// `new(levels)` or `new(levels, margin)`.
+    fn new(args: &[Value]) -> Result<Self, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (levels, margin) = match args {
+            [levels] => (RArray::try_convert(*levels)?, DEFAULT_LOD_MARGIN),
+            [levels, margin] => (RArray::try_convert(*levels)?, f64::try_convert(*margin)? as f32),
+            _ => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("wrong number of arguments (given {}, expected 1..2)", args.len()),
+                ));
+            }
+        };
+        Ok(Self {
+            levels: parse_lod_levels(&ruby, levels)?,
+            margin: Cell::new(margin.max(0.0)),
+            current: Cell::new(None),
+        })
+    }
+
+    /// Selects the level for `scale` and returns its index.
+    fn update(&self, scale: f64) -> usize {
+        let level = select_lod_level(&self.levels, scale as f32, self.current.get(), self.margin.get());
+        self.current.set(Some(level));
+        level
+    }
+
+    fn current(&self) -> Option<usize> {
+        self.current.get()
+    }
+
+    fn margin(&self) -> f64 {
+        self.margin.get() as f64
+    }
+
+    fn set_margin(&self, margin: f64) {
+        self.margin.set((margin as f32).max(0.0));
+    }
+
+    fn hidden(&self) -> bool {
+        self.current
+            .get()
+            .and_then(|level| self.levels.get(level))
+            .is_some_and(|level| level.hide)
+    }
+
+    fn size(&self) -> usize {
+        self.levels.len()
+    }
+}
+
+unsafe impl Send for MagnusLodSelector {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let selector_class = module.define_class("LodSelector", ruby.class_object())?;
+    selector_class.define_singleton_method("new", function!(MagnusLodSelector::new, -1))?;
+    selector_class.define_method("update", method!(MagnusLodSelector::update, 1))?;
+    selector_class.define_method("current", method!(MagnusLodSelector::current, 0))?;
+    selector_class.define_method("margin", method!(MagnusLodSelector::margin, 0))?;
+    selector_class.define_method("margin=", method!(MagnusLodSelector::set_margin, 1))?;
+    selector_class.define_method("hidden?", method!(MagnusLodSelector::hidden, 0))?;
+    selector_class.define_method("size", method!(MagnusLodSelector::size, 0))?;
+
+    Ok(())
+}
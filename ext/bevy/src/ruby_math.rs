@@ -1,4 +1,4 @@
-use bevy_ruby::{RubyQuat, RubyVec2, RubyVec3};
+use bevy_ruby::{RubyMat4, RubyQuat, RubyVec2, RubyVec3};
 use magnus::{function, method, prelude::*, Error, RArray, RModule, Ruby};
 use std::cell::RefCell;
 
@@ -88,6 +88,46 @@ impl MagnusVec2 {
         self.inner.borrow().distance(&other.inner.borrow()) as f64
     }
 
+    fn lerp(&self, other: &MagnusVec2, t: f64) -> Self {
+        Self {
+            inner: RefCell::new(self.inner.borrow().lerp(&other.inner.borrow(), t as f32)),
+        }
+    }
+
+    fn reflect(&self, normal: &MagnusVec2) -> Self {
+        Self {
+            inner: RefCell::new(self.inner.borrow().reflect(&normal.inner.borrow())),
+        }
+    }
+
+    fn clamp_length(&self, max: f64) -> Self {
+        Self {
+            inner: RefCell::new(self.inner.borrow().clamp_length(max as f32)),
+        }
+    }
+
+    fn angle(&self) -> f64 {
+        self.inner.borrow().angle() as f64
+    }
+
+    fn from_angle(radians: f64) -> Self {
+        Self {
+            inner: RefCell::new(RubyVec2::from_angle(radians as f32)),
+        }
+    }
+
+    fn rotate(&self, radians: f64) -> Self {
+        Self {
+            inner: RefCell::new(self.inner.borrow().rotate(radians as f32)),
+        }
+    }
+
+    fn perp(&self) -> Self {
+        Self {
+            inner: RefCell::new(self.inner.borrow().perp()),
+        }
+    }
+
     fn to_a(&self) -> Result<RArray, Error> {
         let ruby = Ruby::get().unwrap();
         let arr = ruby.ary_new();
@@ -204,6 +244,24 @@ impl MagnusVec3 {
         self.inner.borrow().distance(&other.inner.borrow()) as f64
     }
 
+    fn lerp(&self, other: &MagnusVec3, t: f64) -> Self {
+        Self {
+            inner: RefCell::new(self.inner.borrow().lerp(&other.inner.borrow(), t as f32)),
+        }
+    }
+
+    fn reflect(&self, normal: &MagnusVec3) -> Self {
+        Self {
+            inner: RefCell::new(self.inner.borrow().reflect(&normal.inner.borrow())),
+        }
+    }
+
+    fn clamp_length(&self, max: f64) -> Self {
+        Self {
+            inner: RefCell::new(self.inner.borrow().clamp_length(max as f32)),
+        }
+    }
+
     fn to_a(&self) -> Result<RArray, Error> {
         let ruby = Ruby::get().unwrap();
         let arr = ruby.ary_new();
@@ -221,6 +279,14 @@ impl MagnusVec3 {
 
 unsafe impl Send for MagnusVec3 {}
 
+impl From<RubyVec3> for MagnusVec3 {
+    fn from(inner: RubyVec3) -> Self {
+        Self {
+            inner: RefCell::new(inner),
+        }
+    }
+}
+
 #[magnus::wrap(class = "Bevy::Quat", free_immediately, size)]
 pub struct MagnusQuat {
     inner: RefCell<RubyQuat>,
@@ -321,11 +387,104 @@ impl MagnusQuat {
 
 unsafe impl Send for MagnusQuat {}
 
+impl From<RubyQuat> for MagnusQuat {
+    fn from(inner: RubyQuat) -> Self {
+        Self {
+            inner: RefCell::new(inner),
+        }
+    }
+}
+
+#[magnus::wrap(class = "Bevy::Mat4", free_immediately, size)]
+pub struct MagnusMat4 {
+    inner: RefCell<RubyMat4>,
+}
+
+impl MagnusMat4 {
+    fn identity() -> Self {
+        Self {
+            inner: RefCell::new(RubyMat4::identity()),
+        }
+    }
+
+    fn from_translation(translation: &MagnusVec3) -> Self {
+        Self {
+            inner: RefCell::new(RubyMat4::from_translation(&translation.inner())),
+        }
+    }
+
+    fn from_scale_rotation_translation(
+        scale: &MagnusVec3,
+        rotation: &MagnusQuat,
+        translation: &MagnusVec3,
+    ) -> Self {
+        Self {
+            inner: RefCell::new(RubyMat4::from_scale_rotation_translation(
+                &scale.inner(),
+                &rotation.inner(),
+                &translation.inner(),
+            )),
+        }
+    }
+
+    fn determinant(&self) -> f64 {
+        self.inner.borrow().determinant() as f64
+    }
+
+    fn inverse(&self) -> Self {
+        Self {
+            inner: RefCell::new(self.inner.borrow().inverse()),
+        }
+    }
+
+    fn mul_mat4(&self, other: &MagnusMat4) -> Self {
+        Self {
+            inner: RefCell::new(self.inner.borrow().mul(&other.inner.borrow())),
+        }
+    }
+
+    fn transform_point3(&self, point: &MagnusVec3) -> MagnusVec3 {
+        MagnusVec3 {
+            inner: RefCell::new(self.inner.borrow().transform_point3(&point.inner())),
+        }
+    }
+
+    fn transform_vector3(&self, vector: &MagnusVec3) -> MagnusVec3 {
+        MagnusVec3 {
+            inner: RefCell::new(self.inner.borrow().transform_vector3(&vector.inner())),
+        }
+    }
+
+    fn to_a(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().unwrap();
+        let arr = ruby.ary_new_capa(16);
+        for value in self.inner.borrow().to_cols_array() {
+            arr.push(value as f64)?;
+        }
+        Ok(arr)
+    }
+
+    pub fn inner(&self) -> RubyMat4 {
+        *self.inner.borrow()
+    }
+}
+
+unsafe impl Send for MagnusMat4 {}
+
+impl From<RubyMat4> for MagnusMat4 {
+    fn from(inner: RubyMat4) -> Self {
+        Self {
+            inner: RefCell::new(inner),
+        }
+    }
+}
+
 pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     let vec2_class = module.define_class("Vec2", ruby.class_object())?;
     vec2_class.define_singleton_method("new", function!(MagnusVec2::new, 2))?;
     vec2_class.define_singleton_method("zero", function!(MagnusVec2::zero, 0))?;
     vec2_class.define_singleton_method("one", function!(MagnusVec2::one, 0))?;
+    vec2_class.define_singleton_method("from_angle", function!(MagnusVec2::from_angle, 1))?;
     vec2_class.define_method("x", method!(MagnusVec2::x, 0))?;
     vec2_class.define_method("y", method!(MagnusVec2::y, 0))?;
     vec2_class.define_method("x=", method!(MagnusVec2::set_x, 1))?;
@@ -339,6 +498,12 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     vec2_class.define_method("*", method!(MagnusVec2::mul, 1))?;
     vec2_class.define_method("/", method!(MagnusVec2::div, 1))?;
     vec2_class.define_method("distance", method!(MagnusVec2::distance, 1))?;
+    vec2_class.define_method("lerp", method!(MagnusVec2::lerp, 2))?;
+    vec2_class.define_method("reflect", method!(MagnusVec2::reflect, 1))?;
+    vec2_class.define_method("clamp_length", method!(MagnusVec2::clamp_length, 1))?;
+    vec2_class.define_method("angle", method!(MagnusVec2::angle, 0))?;
+    vec2_class.define_method("rotate", method!(MagnusVec2::rotate, 1))?;
+    vec2_class.define_method("perp", method!(MagnusVec2::perp, 0))?;
     vec2_class.define_method("to_a", method!(MagnusVec2::to_a, 0))?;
 
     let vec3_class = module.define_class("Vec3", ruby.class_object())?;
@@ -361,6 +526,9 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     vec3_class.define_method("*", method!(MagnusVec3::mul, 1))?;
     vec3_class.define_method("/", method!(MagnusVec3::div, 1))?;
     vec3_class.define_method("distance", method!(MagnusVec3::distance, 1))?;
+    vec3_class.define_method("lerp", method!(MagnusVec3::lerp, 2))?;
+    vec3_class.define_method("reflect", method!(MagnusVec3::reflect, 1))?;
+    vec3_class.define_method("clamp_length", method!(MagnusVec3::clamp_length, 1))?;
     vec3_class.define_method("to_a", method!(MagnusVec3::to_a, 0))?;
 
     let quat_class = module.define_class("Quat", ruby.class_object())?;
@@ -380,5 +548,19 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     quat_class.define_method("mul_vec3", method!(MagnusQuat::mul_vec3, 1))?;
     quat_class.define_method("to_a", method!(MagnusQuat::to_a, 0))?;
 
+    let mat4_class = module.define_class("Mat4", ruby.class_object())?;
+    mat4_class.define_singleton_method("identity", function!(MagnusMat4::identity, 0))?;
+    mat4_class.define_singleton_method("from_translation", function!(MagnusMat4::from_translation, 1))?;
+    mat4_class.define_singleton_method(
+        "from_scale_rotation_translation",
+        function!(MagnusMat4::from_scale_rotation_translation, 3),
+    )?;
+    mat4_class.define_method("determinant", method!(MagnusMat4::determinant, 0))?;
+    mat4_class.define_method("inverse", method!(MagnusMat4::inverse, 0))?;
+    mat4_class.define_method("*", method!(MagnusMat4::mul_mat4, 1))?;
+    mat4_class.define_method("transform_point3", method!(MagnusMat4::transform_point3, 1))?;
+    mat4_class.define_method("transform_vector3", method!(MagnusMat4::transform_vector3, 1))?;
+    mat4_class.define_method("to_a", method!(MagnusMat4::to_a, 0))?;
+
     Ok(())
 }
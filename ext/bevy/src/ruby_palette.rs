@@ -0,0 +1,150 @@
+use bevy_ruby::{RubyColor, RubyPalette};
+use magnus::{
+    function, method, prelude::*, typed_data::Obj, Error, RArray, RHash, RModule, Ruby, TryConvert,
+    Value,
+};
+use std::cell::RefCell;
+
+use crate::ruby_color::MagnusColor;
+
+#[magnus::wrap(class = "Bevy::Palette", free_immediately, size)]
+pub struct MagnusPalette {
+    inner: RefCell<RubyPalette>,
+}
+
+impl MagnusPalette {
+    fn new(args: &[Value]) -> Result<Self, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let Some(colors) = args.first() else {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                "Palette.new requires an array of colors",
+            ));
+        };
+
+        let colors: RArray = TryConvert::try_convert(*colors)?;
+        let mut palette = RubyPalette::new(parse_colors(&ruby, colors)?);
+
+        if let Some(options) = args.get(1) {
+            let options: RHash = TryConvert::try_convert(*options)?;
+            if let Some(speed) = options.get(ruby.to_symbol("speed"))
+                && !speed.is_nil()
+            {
+                palette.set_speed(f64::try_convert(speed)? as f32);
+            }
+        }
+
+        Ok(Self {
+            inner: RefCell::new(palette),
+        })
+    }
+
+    fn gradient(from: Value, to: Value, steps: usize) -> Result<Self, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let from = parse_color(&ruby, from)?;
+        let to = parse_color(&ruby, to)?;
+        Ok(Self {
+            inner: RefCell::new(RubyPalette::gradient(&from, &to, steps)),
+        })
+    }
+
+    fn from_hex_list(hexes: Vec<String>) -> Result<Self, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        RubyPalette::from_hex_list(&hexes)
+            .map(|palette| Self {
+                inner: RefCell::new(palette),
+            })
+            .ok_or_else(|| Error::new(ruby.exception_arg_error(), "Invalid hex color"))
+    }
+
+    fn size(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    fn speed(&self) -> f64 {
+        self.inner.borrow().speed() as f64
+    }
+
+    fn set_speed(&self, speed: f64) {
+        self.inner.borrow_mut().set_speed(speed as f32);
+    }
+
+    fn offset(&self) -> f64 {
+        self.inner.borrow().offset() as f64
+    }
+
+    fn cycle(&self, delta_secs: f64) {
+        self.inner.borrow_mut().cycle(delta_secs as f32);
+    }
+
+    fn color_at(&self, index: i64) -> Option<MagnusColor> {
+        self.inner.borrow().color_at(index).map(MagnusColor::from)
+    }
+
+    fn colors(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let palette = self.inner.borrow();
+        let result = ruby.ary_new_capa(palette.len());
+        for color in palette.colors() {
+            result.push(MagnusColor::from(*color))?;
+        }
+        Ok(result)
+    }
+
+    pub fn assignments(&self, ids: &[u64]) -> Vec<(u64, RubyColor)> {
+        self.inner.borrow().assignments(ids)
+    }
+}
+
+unsafe impl Send for MagnusPalette {}
+
+/// Accepts a `Bevy::Color`, an `[r, g, b]` / `[r, g, b, a]` array, or a hex string.
+fn parse_color(ruby: &Ruby, value: Value) -> Result<RubyColor, Error> {
+    if let Ok(color) = <Obj<MagnusColor>>::try_convert(value) {
+        return Ok(color.inner());
+    }
+
+    if let Ok(hex) = String::try_convert(value) {
+        return RubyColor::from_hex(&hex)
+            .ok_or_else(|| Error::new(ruby.exception_arg_error(), "Invalid hex color"));
+    }
+
+    if let Ok(components) = Vec::<f64>::try_convert(value) {
+        return match components.as_slice() {
+            [r, g, b] => Ok(RubyColor::rgb(*r as f32, *g as f32, *b as f32)),
+            [r, g, b, a] => Ok(RubyColor::rgba(*r as f32, *g as f32, *b as f32, *a as f32)),
+            _ => Err(Error::new(
+                ruby.exception_arg_error(),
+                "color arrays must have 3 or 4 components",
+            )),
+        };
+    }
+
+    Err(Error::new(
+        ruby.exception_type_error(),
+        "expected a Bevy::Color, an [r, g, b(, a)] array, or a hex string",
+    ))
+}
+
+fn parse_colors(ruby: &Ruby, colors: RArray) -> Result<Vec<RubyColor>, Error> {
+    colors
+        .into_iter()
+        .map(|value| parse_color(ruby, value))
+        .collect()
+}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let palette_class = module.define_class("Palette", ruby.class_object())?;
+    palette_class.define_singleton_method("new", function!(MagnusPalette::new, -1))?;
+    palette_class.define_singleton_method("gradient", function!(MagnusPalette::gradient, 3))?;
+    palette_class.define_singleton_method("from_hex_list", function!(MagnusPalette::from_hex_list, 1))?;
+    palette_class.define_method("size", method!(MagnusPalette::size, 0))?;
+    palette_class.define_method("speed", method!(MagnusPalette::speed, 0))?;
+    palette_class.define_method("speed=", method!(MagnusPalette::set_speed, 1))?;
+    palette_class.define_method("offset", method!(MagnusPalette::offset, 0))?;
+    palette_class.define_method("cycle", method!(MagnusPalette::cycle, 1))?;
+    palette_class.define_method("color_at", method!(MagnusPalette::color_at, 1))?;
+    palette_class.define_method("colors", method!(MagnusPalette::colors, 0))?;
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use bevy_ruby::Parallax;
+use magnus::{Error, RModule, Ruby, Value, function, method, prelude::*};
+use std::cell::RefCell;
+
+use crate::ruby_render_app::{parse_parallax_target, symbol_or_string};
+
+/// Parallax factors and offsets, applied to positions the same way the render bridge applies them.
+#[magnus::wrap(class = "Bevy::ParallaxLayers", free_immediately, size)]
+pub struct MagnusParallaxLayers {
+    inner: RefCell<Parallax>,
+}
+
+impl MagnusParallaxLayers {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(Parallax::new()),
+        }
+    }
+
+    fn set_factor(&self, target: Value, factor_x: f64, factor_y: f64) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let target = parse_parallax_target(&ruby, target)?;
+        self.inner
+            .borrow_mut()
+            .set_factor(target, (factor_x as f32, factor_y as f32));
+        Ok(())
+    }
+
+    fn clear(&self, target: Value) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let target = parse_parallax_target(&ruby, target)?;
+        self.inner.borrow_mut().clear(&target);
+        Ok(())
+    }
+
+    /// `[factor_x, factor_y]`, or nil when the group or layer has none.
+    fn factor(&self, target: Value) -> Result<Option<(f64, f64)>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let target = parse_parallax_target(&ruby, target)?;
+        Ok(self
+            .inner
+            .borrow()
+            .factor(&target)
+            .map(|(x, y)| (x as f64, y as f64)))
+    }
+
+    /// Offsets an authored position for the end of a frame; returns `[x, y, z]`.
+    fn apply(
+        &self,
+        id: u64,
+        group: Option<Value>,
+        x: f64,
+        y: f64,
+        z: f64,
+        camera_x: f64,
+        camera_y: f64,
+    ) -> Result<(f64, f64, f64), Error> {
+        let group = group.map(symbol_or_string).transpose()?;
+        let (x, y, z) = self.inner.borrow_mut().add_offset(
+            id,
+            group.as_deref(),
+            (x as f32, y as f32, z as f32),
+            (camera_x as f32, camera_y as f32),
+        );
+        Ok((x as f64, y as f64, z as f64))
+    }
+
+    /// Takes the last offset off a position at the start of a frame; returns `[x, y, z]`.
+    fn restore(&self, id: u64, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        let (x, y, z) = self
+            .inner
+            .borrow_mut()
+            .remove_offset(id, (x as f32, y as f32, z as f32));
+        (x as f64, y as f64, z as f64)
+    }
+}
+
+unsafe impl Send for MagnusParallaxLayers {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let parallax_class = module.define_class("ParallaxLayers", ruby.class_object())?;
+    parallax_class.define_singleton_method("new", function!(MagnusParallaxLayers::new, 0))?;
+    parallax_class.define_method("set_factor", method!(MagnusParallaxLayers::set_factor, 3))?;
+    parallax_class.define_method("clear", method!(MagnusParallaxLayers::clear, 1))?;
+    parallax_class.define_method("factor", method!(MagnusParallaxLayers::factor, 1))?;
+    parallax_class.define_method("apply", method!(MagnusParallaxLayers::apply, 7))?;
+    parallax_class.define_method("restore", method!(MagnusParallaxLayers::restore, 4))?;
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use bevy_ruby::{PixelGrid, PixelSnap};
+use magnus::{Error, RHash, RModule, Ruby, function, method, prelude::*, value::LazyId};
+use std::cell::RefCell;
+
+use crate::keys;
+use crate::ruby_render_app::get_hash_value;
+
+/// Reads an `[x, y]` pair under `key`.
+fn get_pair(ruby: &Ruby, hash: &RHash, key: &LazyId, name: &str) -> Result<Option<(f32, f32)>, Error> {
+    match get_hash_value::<Vec<f64>>(ruby, hash, key)? {
+        None => Ok(None),
+        Some(pair) => match pair[..] {
+            [x, y] => Ok(Some((x as f32, y as f32))),
+            _ => Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("pixel grid {} needs 2 components (given {})", name, pair.len()),
+            )),
+        },
+    }
+}
+
+/// Reads `{camera: [x, y], scale:, rotation:, scale_factor:, viewport: [width, height]}`;
+/// `scale` and `scale_factor` default to 1 and `camera` and `rotation` to 0.
+fn parse_pixel_grid(ruby: &Ruby, hash: &RHash) -> Result<PixelGrid, Error> {
+    let Some(viewport) = get_pair(ruby, hash, &keys::VIEWPORT, "viewport")? else {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            "pixel grid needs viewport: [width, height] in physical pixels",
+        ));
+    };
+    let scale: Option<f64> = get_hash_value(ruby, hash, &keys::SCALE)?;
+    let rotation: Option<f64> = get_hash_value(ruby, hash, &keys::ROTATION)?;
+    let scale_factor: Option<f64> = get_hash_value(ruby, hash, &keys::SCALE_FACTOR)?;
+    Ok(PixelGrid {
+        camera: get_pair(ruby, hash, &keys::CAMERA, "camera")?.unwrap_or((0.0, 0.0)),
+        scale: scale.unwrap_or(1.0) as f32,
+        rotation: rotation.unwrap_or(0.0) as f32,
+        scale_factor: scale_factor.unwrap_or(1.0) as f32,
+        viewport,
+    })
+}
+
+/// Pixel-perfect settings and snapping, applied to positions the same way the render bridge applies them.
+#[magnus::wrap(class = "Bevy::PixelSnap", free_immediately, size)]
+pub struct MagnusPixelSnap {
+    inner: RefCell<PixelSnap>,
+}
+
+impl MagnusPixelSnap {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(PixelSnap::new()),
+        }
+    }
+
+    fn set_global(&self, enabled: bool) {
+        self.inner.borrow_mut().set_global(enabled);
+    }
+
+    fn global(&self) -> bool {
+        self.inner.borrow().global()
+    }
+
+    fn set_entity(&self, id: u64, enabled: bool) {
+        self.inner.borrow_mut().set_entity(id, enabled);
+    }
+
+    fn clear_entity(&self, id: u64) {
+        self.inner.borrow_mut().clear_entity(id);
+    }
+
+    fn is_enabled(&self, id: u64) -> bool {
+        self.inner.borrow().is_enabled(id)
+    }
+
+    /// Nearest world position on a physical pixel corner; returns `[x, y]`.
+    fn snap(x: f64, y: f64, grid: RHash) -> Result<(f64, f64), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let grid = parse_pixel_grid(&ruby, &grid)?;
+        let (x, y) = grid.snap((x as f32, y as f32));
+        Ok((x as f64, y as f64))
+    }
+
+    /// Physical pixel position of a world point, y down; returns `[x, y]`.
+    fn to_physical(x: f64, y: f64, grid: RHash) -> Result<(f64, f64), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let grid = parse_pixel_grid(&ruby, &grid)?;
+        Ok(grid.to_physical((x as f32, y as f32)))
+    }
+
+    /// Snaps an authored position for the end of a frame; returns `[x, y, z]`.
+    fn apply(&self, key: u64, x: f64, y: f64, z: f64, grid: RHash) -> Result<(f64, f64, f64), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let grid = parse_pixel_grid(&ruby, &grid)?;
+        let (x, y, z) = self
+            .inner
+            .borrow_mut()
+            .snap_position(key, (x as f32, y as f32, z as f32), &grid);
+        Ok((x as f64, y as f64, z as f64))
+    }
+
+    /// Puts back the authored position at the start of a frame; returns `[x, y, z]`.
+    fn restore(&self, key: u64, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        let (x, y, z) = self
+            .inner
+            .borrow_mut()
+            .restore_position(key, (x as f32, y as f32, z as f32));
+        (x as f64, y as f64, z as f64)
+    }
+}
+
+unsafe impl Send for MagnusPixelSnap {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let snap_class = module.define_class("PixelSnap", ruby.class_object())?;
+    snap_class.define_singleton_method("new", function!(MagnusPixelSnap::new, 0))?;
+    snap_class.define_singleton_method("snap", function!(MagnusPixelSnap::snap, 3))?;
+    snap_class.define_singleton_method("to_physical", function!(MagnusPixelSnap::to_physical, 3))?;
+    snap_class.define_method("set_global", method!(MagnusPixelSnap::set_global, 1))?;
+    snap_class.define_method("global?", method!(MagnusPixelSnap::global, 0))?;
+    snap_class.define_method("set_entity", method!(MagnusPixelSnap::set_entity, 2))?;
+    snap_class.define_method("clear_entity", method!(MagnusPixelSnap::clear_entity, 1))?;
+    snap_class.define_method("enabled?", method!(MagnusPixelSnap::is_enabled, 1))?;
+    snap_class.define_method("apply", method!(MagnusPixelSnap::apply, 5))?;
+    snap_class.define_method("restore", method!(MagnusPixelSnap::restore, 4))?;
+
+    Ok(())
+}
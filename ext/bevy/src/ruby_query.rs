@@ -34,17 +34,24 @@ impl RubyQueryBuilder {
 
     fn with_filter(&self, component_name: String) -> Self {
         Self {
-            inner: RefCell::new(self.inner.borrow().clone().fetch(&component_name)),
+            inner: RefCell::new(self.inner.borrow().clone().with(&component_name)),
         }
     }
 
-    fn without_filter(&self, _component_name: String) -> Self {
+    fn without_filter(&self, component_name: String) -> Self {
         Self {
-            inner: RefCell::new(self.inner.borrow().clone()),
+            inner: RefCell::new(self.inner.borrow().clone().without(&component_name)),
         }
     }
 
-    #[allow(dead_code)]
+    fn with_components(&self) -> Vec<String> {
+        self.inner.borrow().with_components().to_vec()
+    }
+
+    fn without_components(&self) -> Vec<String> {
+        self.inner.borrow().without_components().to_vec()
+    }
+
     pub fn inner(&self) -> QueryBuilder {
         self.inner.borrow().clone()
     }
@@ -59,6 +66,8 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     class.define_method("fetch_components", method!(RubyQueryBuilder::fetch_components, 0))?;
     class.define_method("with", method!(RubyQueryBuilder::with_filter, 1))?;
     class.define_method("without", method!(RubyQueryBuilder::without_filter, 1))?;
+    class.define_method("with_components", method!(RubyQueryBuilder::with_components, 0))?;
+    class.define_method("without_components", method!(RubyQueryBuilder::without_components, 0))?;
 
     Ok(())
 }
@@ -1,14 +1,41 @@
 //! Ruby bindings for the RenderApp and input handling.
 
 use bevy_ruby::{
-    GamepadRumbleCommand, InputState, MeshData, MeshSync, MeshTransformData, PickingEventData,
-    RenderApp, ShapeType, SpriteData, SpriteSync, TextData, TextSync, TextTransformData,
-    TransformData, WindowConfig,
+    AccessibilityData, AccessibleRole, ActionBinding, ActionMap, AuditSet, AudioCommand, BundleContent, CameraFollow, CursorGrab, ExitReason, FIRST_FRAME_DELTA_SECS, Behavior, BehaviorAxis, ChecksumDivergence, ClipEasing, ClipPlayer, ClipProperty, ClipTrack, DRAG_SNAP_BACK_CLIP_FLAG, DragBounds, DragConfig, DragEvent, DragGestures, DrawOrderChange, EntityBehaviors, EntityLods,
+    FileDropData, FloatingTexts, GamepadConnectionData, FramePixels, GradientDirection, GroupSchedule, GroupThrottle, MotionSleep, HitRegion, HitRegionUpdate, HitRegions, JournalReplay, KeyframeClip, OperationJournal, JellyConfig, GamepadRumbleCommand, InputState, LineCapStyle, LodLevel, LineJoinStyle, MeshData, MeshSync, MeshTransformData, Parallax, ParallaxTarget, PickingEventData, PixelSnap, RUMBLE_PATTERN_NAMES, RumbleSegment,
+    DEFAULT_SIGHT_CELL_SIZE, RenderApp, RewindOperation, SightGrid, RewindStats, ShapeType, shape_triangles, SleepConfig, SpriteData, SpriteSync, SpriteTransformSample, TEXT_BLEND_SUPPORTED,
+    TextBlend, TextData, TextGlow, TextJustify, TextLineBreak, TextSync, TextTransformData,
+    StickCurve, TransformData, VirtualCursorConfig, VirtualCursorOperation, WindowConfig, WindowDisplayMode,
+    WarningPolicy, WarningRecord, WindowEventData, WindowEventFilter, WindowMetrics, WindowMsaa, WindowPresentMode,
 };
+use bevy_ruby_render::CameraZoom;
 use magnus::{
-    Error, RArray, RHash, Ruby, TryConvert, Value, block::Proc, function, method, prelude::*,
+    Error, Integer, RArray, RHash, Ruby, Symbol, TryConvert, Value, block::Proc, function, method,
+    prelude::*,
 };
+use magnus::value::LazyId;
 use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use crate::capabilities;
+use crate::coerce::{HashValue, hash_value};
+use crate::deprecation::deprecated;
+use crate::conversions::transform_from_hash;
+use crate::keys;
+use crate::ruby_debug_bundle::{MagnusDebugBundle, collect_json, fields_hash, parse_max_log_bytes};
+use crate::ruby_determinism::divergence_to_hash;
+use crate::ruby_drag::drag_event_to_hash;
+use crate::ruby_floating_text::parse_floating_text_request;
+use crate::ruby_gamepad_state::gamepads_to_array;
+use crate::ruby_geometry::triangles_to_array;
+use crate::ruby_journal::{create_journal, load_journal};
+use crate::ruby_line_of_sight::{hit_to_tuple, parse_points};
+use crate::ruby_palette::MagnusPalette;
+use crate::ruby_rewind::stats_to_hash;
+use crate::ruby_system_timing::timings_to_hash;
+use crate::ruby_texture_loading::parse_texture_placeholder;
+use crate::ruby_touch::touches_to_array;
+use crate::ruby_warnings::{drain_warning_records, parse_warning_policy, queue_warning_records, warning_to_hash};
 
 struct RenderState {
     render_app: RenderApp,
@@ -18,16 +45,111 @@ struct RenderState {
 thread_local! {
     static RENDER_STATE: RefCell<Option<RenderState>> = const { RefCell::new(None) };
     static RUBY_CALLBACK: RefCell<Option<Proc>> = const { RefCell::new(None) };
+    /// winit creates one event loop per process, so only the first windowed app can open.
+    static WINDOW_OPENED: RefCell<bool> = const { RefCell::new(false) };
     static SHARED_INPUT: RefCell<InputState> = RefCell::new(InputState::new());
+    /// `(delta_secs, elapsed_secs)` of the current frame.
+    static SHARED_FRAME_TIME: RefCell<(f32, f64)> = const { RefCell::new((FIRST_FRAME_DELTA_SECS, 0.0)) };
+    static SHARED_FRAME_COUNT: RefCell<u32> = const { RefCell::new(0) };
+    /// Latest headless frame; kept after `run` returns so tests can inspect the last one.
+    static SHARED_FRAME_PIXELS: RefCell<Option<FramePixels>> = const { RefCell::new(None) };
+    static DETAILED_STATS: RefCell<bool> = const { RefCell::new(false) };
+    static PENDING_DETAILED_STATS: RefCell<Option<bool>> = const { RefCell::new(None) };
+    /// `Some(None)` stops journaling.
+    static PENDING_JOURNAL: RefCell<Option<Option<OperationJournal>>> = const { RefCell::new(None) };
+    static PENDING_JOURNAL_REPLAY: RefCell<Option<JournalReplay>> = const { RefCell::new(None) };
+    static SHARED_JOURNAL_REPLAYING: RefCell<bool> = const { RefCell::new(false) };
+    /// Last frame's system timings and their rolling maxima, copied only while detailed stats are on.
+    static SHARED_SYSTEM_TIMINGS: RefCell<(BTreeMap<String, u64>, BTreeMap<String, u64>)> =
+        const { RefCell::new((BTreeMap::new(), BTreeMap::new())) };
     static SHOULD_STOP: RefCell<bool> = const { RefCell::new(false) };
+    /// Called once with the exit reason; taken when called.
+    static ON_EXIT_CALLBACK: RefCell<Option<Proc>> = const { RefCell::new(None) };
+    /// True from the frame the loop starts ending.
+    static SHARED_CLOSING: RefCell<bool> = const { RefCell::new(false) };
+    /// First error raised by the frame block or `on_exit`, re-raised when `run` returns.
+    static RUN_ERROR: RefCell<Option<Error>> = const { RefCell::new(None) };
+    static TEXT_SYNC_ENABLED: RefCell<bool> = const { RefCell::new(true) };
+    static MESH_SYNC_ENABLED: RefCell<bool> = const { RefCell::new(true) };
     static PENDING_SPRITES: RefCell<SpriteSync> = RefCell::new(SpriteSync::new());
     static PENDING_TEXTS: RefCell<TextSync> = RefCell::new(TextSync::new());
     static PENDING_MESHES: RefCell<MeshSync> = RefCell::new(MeshSync::new());
+    /// Last synced data per mesh, kept for `mesh_triangles`.
+    static MESH_SHAPES: RefCell<BTreeMap<u64, MeshData>> = const { RefCell::new(BTreeMap::new()) };
+    static PENDING_BEHAVIORS: RefCell<EntityBehaviors> = RefCell::new(EntityBehaviors::new());
+    static PENDING_LODS: RefCell<EntityLods> = RefCell::new(EntityLods::new());
+    static PENDING_PARALLAX: RefCell<Parallax> = RefCell::new(Parallax::new());
+    static PENDING_PIXEL_SNAP: RefCell<PixelSnap> = RefCell::new(PixelSnap::new());
+    static PENDING_HIT_REGIONS: RefCell<HitRegions> = RefCell::new(HitRegions::new());
+    static SHARED_LOD_LEVELS: RefCell<Vec<(u64, usize)>> = const { RefCell::new(Vec::new()) };
+    static PENDING_CLIPS: RefCell<ClipPlayer> = RefCell::new(ClipPlayer::new());
+    static PENDING_DRAG: RefCell<DragGestures> = RefCell::new(DragGestures::new());
+    static SHARED_DRAG_EVENTS: RefCell<Vec<DragEvent>> = const { RefCell::new(Vec::new()) };
+    static PENDING_FLOATING_TEXTS: RefCell<FloatingTexts> = RefCell::new(FloatingTexts::new());
+    static PENDING_GROUP_THROTTLE: RefCell<GroupThrottle> = RefCell::new(GroupThrottle::new());
+    /// Group divisors in effect this frame and the frame number, for `group_due?`.
+    static SHARED_GROUP_SCHEDULE: RefCell<(GroupSchedule, u32)> = RefCell::new((GroupSchedule::new(), 0));
+    static PENDING_SLEEP: RefCell<MotionSleep> = RefCell::new(MotionSleep::new());
+    /// Entities asleep across the motion subsystems as of the last frame.
+    static SHARED_SLEEPING_COUNT: RefCell<usize> = const { RefCell::new(0) };
+    static SHARED_FINISHED_CLIPS: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+    static SHARED_CLIP_SKIPPED_TRACKS: RefCell<u64> = const { RefCell::new(0) };
+    static PENDING_REWIND_OPERATIONS: RefCell<Vec<RewindOperation>> = const { RefCell::new(Vec::new()) };
+    static SHARED_REWIND_STATS: RefCell<Option<RewindStats>> = const { RefCell::new(None) };
+    static SHARED_CHECKSUMS: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+    static SHARED_CHECKSUM_DIVERGENCE: RefCell<Option<ChecksumDivergence>> = const { RefCell::new(None) };
+    static PENDING_RECORDED_CHECKSUMS: RefCell<Option<Vec<u64>>> = const { RefCell::new(None) };
     static CAMERA_POSITION: RefCell<(f32, f32, f32)> = RefCell::new((0.0, 0.0, 0.0));
     static CAMERA_SCALE: RefCell<f32> = RefCell::new(1.0);
+    /// `(min, max)` the camera scale is clamped to, as in `CameraZoom`.
+    static CAMERA_ZOOM_LIMITS: RefCell<(f32, f32)> = const { RefCell::new((0.1, 10.0)) };
+    static CAMERA_ROTATION: RefCell<f32> = const { RefCell::new(0.0) };
     static CAMERA_DIRTY: RefCell<bool> = const { RefCell::new(false) };
-    static PENDING_GAMEPAD_RUMBLE: RefCell<Vec<GamepadRumbleCommand>> = const { RefCell::new(Vec::new()) };
+    static PENDING_CAMERA_SHAKE: RefCell<Option<(f32, f32)>> = const { RefCell::new(None) };
+    static CAMERA_FOLLOW: RefCell<Option<CameraFollow>> = const { RefCell::new(None) };
+    static PENDING_CAMERA_FOLLOW: RefCell<Option<Option<CameraFollow>>> = const { RefCell::new(None) };
+    /// `(sprites, camera)` frames the last sprite and camera writes landed in.
+    static SHARED_APPLIED_FRAMES: RefCell<(Option<u32>, Option<u32>)> = const { RefCell::new((None, None)) };
+    static PENDING_GAMEPAD_RUMBLE: RefCell<Vec<PendingRumble>> = const { RefCell::new(Vec::new()) };
+    /// Gamepads rumbling or with a pattern queued at the start of the frame, sorted.
+    static SHARED_ACTIVE_RUMBLE: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+    static PENDING_AUDIO: RefCell<Vec<AudioCommand>> = const { RefCell::new(Vec::new()) };
     static SHARED_PICKING_EVENTS: RefCell<Vec<PickingEventData>> = const { RefCell::new(Vec::new()) };
+    static SHARED_WINDOW_EVENTS: RefCell<Vec<WindowEventData>> = const { RefCell::new(Vec::new()) };
+    static SHARED_FILE_DROPS: RefCell<Vec<FileDropData>> = const { RefCell::new(Vec::new()) };
+    static SHARED_GAMEPAD_CONNECTIONS: RefCell<Vec<GamepadConnectionData>> = const { RefCell::new(Vec::new()) };
+    static SHARED_WINDOW_METRICS: RefCell<WindowMetrics> = RefCell::new(WindowMetrics::default());
+    static SHARED_SPRITE_TRANSFORMS: RefCell<Vec<SpriteTransformSample>> = const { RefCell::new(Vec::new()) };
+    /// Index of every synced sprite in its layer, and the draw order revision it was read at.
+    static SHARED_DRAW_ORDER: RefCell<(Option<u64>, HashMap<u64, usize>)> = RefCell::new((None, HashMap::new()));
+    /// Blocker grids over `SHARED_SPRITE_TRANSFORMS` by group (`None` for all
+    /// sprites), built on the first query after each snapshot.
+    static SHARED_SIGHT_GRIDS: RefCell<HashMap<Option<String>, SightGrid>> = RefCell::new(HashMap::new());
+    static PENDING_VIRTUAL_CURSOR: RefCell<Vec<VirtualCursorOperation>> = const { RefCell::new(Vec::new()) };
+    static SHARED_VIRTUAL_CURSOR_POSITION: RefCell<Option<(f32, f32)>> = const { RefCell::new(None) };
+    static PENDING_ACCESSIBILITY_ENABLED: RefCell<Option<bool>> = const { RefCell::new(None) };
+    static SHARED_ACCESSIBILITY_ACTIVE: RefCell<bool> = const { RefCell::new(false) };
+    static SHARED_ACCESSIBILITY_NODES: RefCell<Vec<(u64, AccessibilityData)>> = const { RefCell::new(Vec::new()) };
+    static INPUT_CAPTURED: RefCell<bool> = const { RefCell::new(false) };
+    static ACTION_MAP: RefCell<ActionMap> = RefCell::new(ActionMap::new());
+    static WINDOW_MODE: RefCell<WindowDisplayMode> = const { RefCell::new(WindowDisplayMode::Windowed) };
+    static PENDING_WINDOW_MODE: RefCell<Option<WindowDisplayMode>> = const { RefCell::new(None) };
+    static CLEAR_COLOR: RefCell<Option<[f32; 4]>> = const { RefCell::new(None) };
+    static PENDING_CLEAR_COLOR: RefCell<Option<[f32; 4]>> = const { RefCell::new(None) };
+    static MAX_FPS: RefCell<Option<f32>> = const { RefCell::new(None) };
+    static PENDING_MAX_FPS: RefCell<Option<Option<f32>>> = const { RefCell::new(None) };
+    static WINDOW_SIZE: RefCell<(f32, f32)> = const { RefCell::new((0.0, 0.0)) };
+    static PENDING_WINDOW_TITLE: RefCell<Option<String>> = const { RefCell::new(None) };
+    static PENDING_WINDOW_SIZE: RefCell<Option<(f32, f32)>> = const { RefCell::new(None) };
+    static CURSOR_VISIBLE: RefCell<bool> = const { RefCell::new(true) };
+    static CURSOR_GRAB: RefCell<CursorGrab> = const { RefCell::new(CursorGrab::None) };
+    static PENDING_CURSOR_VISIBLE: RefCell<Option<bool>> = const { RefCell::new(None) };
+    static PENDING_CURSOR_GRAB: RefCell<Option<CursorGrab>> = const { RefCell::new(None) };
+    static TEXT_INPUT_ENABLED: RefCell<bool> = const { RefCell::new(false) };
+    static PENDING_TEXT_INPUT_ENABLED: RefCell<Option<bool>> = const { RefCell::new(None) };
+    static SHARED_WARNINGS: RefCell<VecDeque<WarningRecord>> = const { RefCell::new(VecDeque::new()) };
+    static WARNING_POLICIES: RefCell<HashMap<String, WarningPolicy>> = RefCell::new(HashMap::new());
+    static PENDING_WARNING_POLICIES: RefCell<Vec<(String, WarningPolicy)>> = const { RefCell::new(Vec::new()) };
 }
 
 #[magnus::wrap(class = "Bevy::RenderApp", free_immediately, size)]
@@ -43,27 +165,102 @@ impl RubyRenderApp {
             WindowConfig::default()
         } else {
             let hash: RHash = TryConvert::try_convert(args[0])?;
-            let title: Option<String> = get_hash_value(&ruby, &hash, "title")?;
-            let width: Option<f64> = get_hash_value(&ruby, &hash, "width")?;
-            let height: Option<f64> = get_hash_value(&ruby, &hash, "height")?;
-            let resizable: Option<bool> = get_hash_value(&ruby, &hash, "resizable")?;
+            let title: Option<String> = get_hash_value(&ruby, &hash, &keys::TITLE)?;
+            let width: Option<f64> = get_hash_value(&ruby, &hash, &keys::WIDTH)?;
+            let height: Option<f64> = get_hash_value(&ruby, &hash, &keys::HEIGHT)?;
+            let resizable: Option<bool> = get_hash_value(&ruby, &hash, &keys::RESIZABLE)?;
+            let decorations: Option<bool> = get_hash_value(&ruby, &hash, &keys::DECORATIONS)?;
+            let transparent: Option<bool> = get_hash_value(&ruby, &hash, &keys::TRANSPARENT)?;
+            let min_width: Option<f64> = get_hash_value(&ruby, &hash, &keys::MIN_WIDTH)?;
+            let min_height: Option<f64> = get_hash_value(&ruby, &hash, &keys::MIN_HEIGHT)?;
+            let text_sync: Option<bool> = get_hash_value(&ruby, &hash, &keys::TEXT_SYNC)?;
+            let mesh_sync: Option<bool> = get_hash_value(&ruby, &hash, &keys::MESH_SYNC)?;
+            let window_events: Option<RArray> = get_hash_value(&ruby, &hash, &keys::WINDOW_EVENTS)?;
+            let mode: Option<Value> = get_hash_value(&ruby, &hash, &keys::MODE)?;
+            let present_mode: Option<Value> = get_hash_value(&ruby, &hash, &keys::PRESENT_MODE)?;
+            let vsync: Option<bool> = get_hash_value(&ruby, &hash, &keys::VSYNC)?;
+            let msaa: Option<i64> = get_hash_value(&ruby, &hash, &keys::MSAA)?;
+            let icon: Option<String> = get_hash_value(&ruby, &hash, &keys::ICON)?;
+            let clear_color: Option<Vec<f64>> = get_hash_value(&ruby, &hash, &keys::CLEAR_COLOR)?;
+            let headless: Option<bool> = get_hash_value(&ruby, &hash, &keys::HEADLESS)?;
+            let frames: Option<u32> = get_hash_value(&ruby, &hash, &keys::FRAMES)?;
+            let max_fps: Option<f64> = get_hash_value(&ruby, &hash, &keys::MAX_FPS)?;
+            let pixel_perfect: Option<bool> = get_hash_value(&ruby, &hash, &keys::PIXEL_PERFECT)?;
+            let texture_placeholder: Option<Value> =
+                get_hash_value(&ruby, &hash, &keys::TEXTURE_PLACEHOLDER)?;
 
             WindowConfig {
                 title: title.unwrap_or_else(|| "Bevy Ruby".to_string()),
                 width: width.unwrap_or(800.0) as f32,
                 height: height.unwrap_or(600.0) as f32,
                 resizable: resizable.unwrap_or(true),
+                decorations: decorations.unwrap_or(true),
+                transparent: transparent.unwrap_or(false),
+                min_width: min_width.map(|value| value as f32),
+                min_height: min_height.map(|value| value as f32),
+                text_sync: text_sync.unwrap_or(true),
+                mesh_sync: mesh_sync.unwrap_or(true),
+                window_events: window_events
+                    .map(|kinds| parse_window_event_filter(&ruby, kinds))
+                    .transpose()?
+                    .unwrap_or_default(),
+                mode: mode
+                    .map(|mode| parse_window_mode(&ruby, mode))
+                    .transpose()?
+                    .unwrap_or_default(),
+                // An explicit present mode wins over the `vsync` shorthand.
+                present_mode: match present_mode {
+                    Some(value) => parse_present_mode(&ruby, value)?,
+                    None => vsync.map(WindowPresentMode::from_vsync).unwrap_or_default(),
+                },
+                msaa: msaa
+                    .map(|samples| parse_msaa(&ruby, samples))
+                    .transpose()?
+                    .unwrap_or_default(),
+                icon_path: icon,
+                clear_color: clear_color
+                    .map(|rgba| parse_clear_color(&ruby, &rgba))
+                    .transpose()?,
+                headless: headless.unwrap_or(false),
+                frames,
+                max_fps: parse_max_fps(&ruby, max_fps)?,
+                pixel_perfect: pixel_perfect.unwrap_or(false),
+                texture_placeholder: texture_placeholder
+                    .map(|placeholder| parse_texture_placeholder(&ruby, placeholder))
+                    .transpose()?
+                    .unwrap_or_default(),
             }
         };
 
         RENDER_STATE.with(|state| {
-            let mut state = state.borrow_mut();
+            let Ok(mut state) = state.try_borrow_mut() else {
+                return Err(Error::new(
+                    ruby.exception_runtime_error(),
+                    "cannot create a RenderApp while another one is running",
+                ));
+            };
             if state.is_some() {
                 return Err(Error::new(
                     ruby.exception_runtime_error(),
-                    "RenderApp already exists. Only one instance is allowed.",
+                    "RenderApp already exists. Only one instance is allowed; run or close it first.",
+                ));
+            }
+            if !config.headless && WINDOW_OPENED.with(|opened| *opened.borrow()) {
+                return Err(Error::new(
+                    ruby.exception_runtime_error(),
+                    "a window can only be opened once per process; use headless: true for repeated apps",
                 ));
             }
+            reset_shared_state();
+            TEXT_SYNC_ENABLED.with(|enabled| *enabled.borrow_mut() = config.text_sync);
+            WINDOW_MODE.with(|mode| *mode.borrow_mut() = config.mode);
+            CLEAR_COLOR.with(|color| *color.borrow_mut() = config.clear_color);
+            MAX_FPS.with(|max_fps| *max_fps.borrow_mut() = config.max_fps);
+            WINDOW_SIZE.with(|size| *size.borrow_mut() = (config.width, config.height));
+            MESH_SYNC_ENABLED.with(|enabled| *enabled.borrow_mut() = config.mesh_sync);
+            if !config.headless {
+                WINDOW_OPENED.with(|opened| *opened.borrow_mut() = true);
+            }
             *state = Some(RenderState {
                 render_app: RenderApp::new(config),
                 sprite_sync: SpriteSync::new(),
@@ -88,7 +285,22 @@ impl RubyRenderApp {
             ));
         }
 
+        match RENDER_STATE.with(|state| state.try_borrow().map(|state| state.is_some())) {
+            Ok(true) => {}
+            Ok(false) => {
+                return Err(Error::new(
+                    ruby.exception_runtime_error(),
+                    "RenderApp has already run or was closed; create a new one",
+                ));
+            }
+            Err(_) => {
+                return Err(Error::new(ruby.exception_runtime_error(), "RenderApp is already running"));
+            }
+        }
+
         let proc = ruby.block_proc()?;
+        // Tears the app down however `run` ends, including a panic in Bevy.
+        let _teardown = AppTeardown;
         RUBY_CALLBACK.with(|cb| {
             *cb.borrow_mut() = Some(proc);
         });
@@ -102,13 +314,148 @@ impl RubyRenderApp {
                         SHARED_INPUT.with(|input| {
                             *input.borrow_mut() = bridge_state.input_state.clone();
                         });
+                        SHARED_FRAME_TIME.with(|time| {
+                            *time.borrow_mut() = (bridge_state.delta_secs, bridge_state.elapsed_secs);
+                        });
+                        SHARED_FRAME_COUNT.with(|count| {
+                            *count.borrow_mut() = bridge_state.frame_count;
+                        });
+                        if let Some(pixels) = bridge_state.frame_pixels.take() {
+                            SHARED_FRAME_PIXELS.with(|shared| *shared.borrow_mut() = Some(pixels));
+                        }
+                        SHARED_JOURNAL_REPLAYING.with(|replaying| {
+                            *replaying.borrow_mut() = bridge_state.journal_replay.is_some();
+                        });
+                        if let Some(enabled) = PENDING_DETAILED_STATS.with(|pending| pending.borrow_mut().take()) {
+                            bridge_state.system_timings.set_enabled(enabled);
+                        }
+                        if bridge_state.system_timings.is_enabled() {
+                            SHARED_SYSTEM_TIMINGS.with(|timings| {
+                                *timings.borrow_mut() = (
+                                    bridge_state.system_timings.frame().clone(),
+                                    bridge_state.system_timings.max(),
+                                );
+                            });
+                        }
                         SHARED_PICKING_EVENTS.with(|events| {
                             *events.borrow_mut() = bridge_state.picking_events.clone();
                         });
+                        PENDING_WARNING_POLICIES.with(|policies| {
+                            for (category, policy) in policies.borrow_mut().drain(..) {
+                                bridge_state.warnings.set_policy(&category, policy);
+                            }
+                        });
+                        SHARED_WARNINGS.with(|warnings| {
+                            let capacity = bridge_state.warnings.capacity();
+                            queue_warning_records(&mut warnings.borrow_mut(), bridge_state.warnings.drain(), capacity);
+                        });
+                        SHARED_WINDOW_EVENTS.with(|events| {
+                            events
+                                .borrow_mut()
+                                .extend(bridge_state.window_events.drain(..));
+                        });
+                        SHARED_FINISHED_CLIPS.with(|finished| {
+                            finished.borrow_mut().extend(
+                                bridge_state
+                                    .clips
+                                    .finished
+                                    .drain(..)
+                                    .filter(|handle| handle & DRAG_SNAP_BACK_CLIP_FLAG == 0),
+                            );
+                        });
+                        SHARED_DRAG_EVENTS.with(|events| {
+                            events.borrow_mut().extend(bridge_state.drag.drain_events());
+                        });
+                        SHARED_CLIP_SKIPPED_TRACKS.with(|skipped| {
+                            *skipped.borrow_mut() = bridge_state.clips.skipped_tracks;
+                        });
+                        SHARED_REWIND_STATS.with(|stats| {
+                            *stats.borrow_mut() = bridge_state.rewind_stats;
+                        });
+                        SHARED_LOD_LEVELS.with(|levels| {
+                            *levels.borrow_mut() = bridge_state.lods.current_levels();
+                        });
+                        SHARED_FILE_DROPS.with(|drops| {
+                            drops.borrow_mut().extend(bridge_state.file_drops.drain(..));
+                        });
+                        SHARED_GAMEPAD_CONNECTIONS.with(|connections| {
+                            connections
+                                .borrow_mut()
+                                .extend(bridge_state.gamepad_connections.drain(..));
+                        });
+                        SHARED_ACTIVE_RUMBLE.with(|active| {
+                            let mut active = active.borrow_mut();
+                            *active = bridge_state.rumble_end_times.active_ids(bridge_state.elapsed_secs);
+                            active.extend(bridge_state.rumble_patterns.gamepad_ids());
+                            active.sort_unstable();
+                            active.dedup();
+                        });
+                        #[cfg(feature = "determinism_audit")]
+                        {
+                            SHARED_CHECKSUMS.with(|checksums| {
+                                let mut checksums = checksums.borrow_mut();
+                                checksums.extend(bridge_state.audit.drain_finished());
+                                if let Some(recorded) =
+                                    PENDING_RECORDED_CHECKSUMS.with(|recorded| recorded.borrow_mut().take())
+                                {
+                                    bridge_state.audit.set_recorded(recorded, &checksums);
+                                }
+                            });
+                            SHARED_CHECKSUM_DIVERGENCE.with(|divergence| {
+                                *divergence.borrow_mut() = bridge_state.audit.divergence();
+                            });
+                        }
+                        SHARED_WINDOW_METRICS.with(|metrics| {
+                            *metrics.borrow_mut() = bridge_state.window_metrics;
+                        });
+                        SHARED_SPRITE_TRANSFORMS.with(|transforms| {
+                            *transforms.borrow_mut() =
+                                std::mem::take(&mut bridge_state.sprite_transforms);
+                        });
+                        SHARED_SIGHT_GRIDS.with(|grids| grids.borrow_mut().clear());
+                        SHARED_DRAW_ORDER.with(|shared| {
+                            let mut shared = shared.borrow_mut();
+                            let draw_order = bridge_state.sprite_sync.draw_order();
+                            if shared.0 != Some(draw_order.revision()) {
+                                *shared = (Some(draw_order.revision()), draw_order.indices().into_iter().collect());
+                            }
+                        });
+                        SHARED_VIRTUAL_CURSOR_POSITION.with(|position| {
+                            *position.borrow_mut() = bridge_state.virtual_cursor_position;
+                        });
+                        SHARED_ACCESSIBILITY_ACTIVE.with(|active| {
+                            *active.borrow_mut() = bridge_state.accessibility_active;
+                        });
+                        SHARED_ACCESSIBILITY_NODES.with(|nodes| {
+                            *nodes.borrow_mut() = std::mem::take(&mut bridge_state.accessibility_nodes);
+                        });
+
+                        SHARED_GROUP_SCHEDULE.with(|schedule| {
+                            *schedule.borrow_mut() =
+                                (bridge_state.group_throttle.schedule().clone(), bridge_state.frame_count);
+                        });
+                        SHARED_CLOSING.with(|closing| *closing.borrow_mut() = bridge_state.closing);
+                        SHARED_SLEEPING_COUNT.with(|count| *count.borrow_mut() = bridge_state.sleeping_count());
+                        SHARED_APPLIED_FRAMES.with(|frames| {
+                            *frames.borrow_mut() =
+                                (bridge_state.sprites_applied_frame, bridge_state.camera_applied_frame);
+                        });
+                        // A followed camera moves without Ruby; `camera_position` reports where it went.
+                        if bridge_state.camera_follow.is_some() && !CAMERA_DIRTY.with(|d| *d.borrow()) {
+                            let (x, y, _) = bridge_state.camera_position;
+                            CAMERA_POSITION.with(|p| {
+                                let mut position = p.borrow_mut();
+                                position.0 = x;
+                                position.1 = y;
+                            });
+                        }
 
                         RUBY_CALLBACK.with(|cb| {
                             if let Some(ref proc) = *cb.borrow() {
-                                let _ = proc.call::<_, Value>(());
+                                if let Err(error) = proc.call::<_, Value>(()) {
+                                    record_run_error(error);
+                                    bridge_state.request_exit(ExitReason::Error);
+                                }
                             }
                         });
 
@@ -133,13 +480,166 @@ impl RubyRenderApp {
                             }
                         });
 
+                        PENDING_BEHAVIORS.with(|behaviors| {
+                            let mut pending = behaviors.borrow_mut();
+                            for op in pending.pending_operations.drain(..) {
+                                bridge_state.behaviors.pending_operations.push(op);
+                            }
+                        });
+
+                        PENDING_LODS.with(|lods| {
+                            let mut pending = lods.borrow_mut();
+                            for op in pending.pending_operations.drain(..) {
+                                bridge_state.lods.pending_operations.push(op);
+                            }
+                        });
+
+                        PENDING_PARALLAX.with(|parallax| {
+                            let mut pending = parallax.borrow_mut();
+                            for op in pending.pending_operations.drain(..) {
+                                bridge_state.parallax.pending_operations.push(op);
+                            }
+                        });
+
+                        PENDING_PIXEL_SNAP.with(|pixel_snap| {
+                            let mut pending = pixel_snap.borrow_mut();
+                            for op in pending.pending_operations.drain(..) {
+                                bridge_state.pixel_snap.pending_operations.push(op);
+                            }
+                        });
+
+                        PENDING_HIT_REGIONS.with(|hit_regions| {
+                            let mut pending = hit_regions.borrow_mut();
+                            for op in pending.pending_operations.drain(..) {
+                                bridge_state.hit_regions.pending_operations.push(op);
+                            }
+                        });
+
+                        PENDING_CLIPS.with(|clips| {
+                            let mut pending = clips.borrow_mut();
+                            for op in pending.pending_operations.drain(..) {
+                                bridge_state.clips.pending_operations.push(op);
+                            }
+                        });
+
+                        PENDING_DRAG.with(|drag| {
+                            let mut pending = drag.borrow_mut();
+                            for op in pending.pending_operations.drain(..) {
+                                bridge_state.drag.pending_operations.push(op);
+                            }
+                        });
+
+                        PENDING_FLOATING_TEXTS.with(|floating_texts| {
+                            let mut pending = floating_texts.borrow_mut();
+                            for op in pending.pending_operations.drain(..) {
+                                bridge_state.floating_texts.pending_operations.push(op);
+                            }
+                        });
+
+                        PENDING_GROUP_THROTTLE.with(|throttle| {
+                            let mut pending = throttle.borrow_mut();
+                            for op in pending.pending_operations.drain(..) {
+                                bridge_state.group_throttle.pending_operations.push(op);
+                            }
+                        });
+
+                        PENDING_SLEEP.with(|sleep| {
+                            let mut pending = sleep.borrow_mut();
+                            for op in pending.pending_operations.drain(..) {
+                                bridge_state.sleep.pending_operations.push(op);
+                            }
+                        });
+
+                        if let Some(journal) = PENDING_JOURNAL.with(|pending| pending.borrow_mut().take()) {
+                            bridge_state.journal = journal;
+                        }
+                        if let Some(replay) = PENDING_JOURNAL_REPLAY.with(|pending| pending.borrow_mut().take()) {
+                            bridge_state.journal_replay = Some(replay);
+                        }
+
+                        PENDING_REWIND_OPERATIONS.with(|ops| {
+                            bridge_state.rewind_operations.extend(ops.borrow_mut().drain(..));
+                        });
+
                         PENDING_GAMEPAD_RUMBLE.with(|rumbles| {
                             let mut pending = rumbles.borrow_mut();
-                            for command in pending.drain(..) {
-                                bridge_state.pending_gamepad_rumble.push(command);
+                            for rumble in pending.drain(..) {
+                                match rumble {
+                                    PendingRumble::Command(command) => {
+                                        // Stopping a gamepad also drops the rest of its pattern.
+                                        if command.stop {
+                                            bridge_state.rumble_patterns.cancel(command.gamepad_id);
+                                        }
+                                        bridge_state.pending_gamepad_rumble.push(command);
+                                    }
+                                    PendingRumble::Pattern(gamepad_id, segments) => {
+                                        bridge_state.rumble_patterns.enqueue(gamepad_id, segments);
+                                    }
+                                    PendingRumble::StopAll => {
+                                        let mut gamepad_ids: Vec<u64> =
+                                            bridge_state.input_state.gamepads.keys().copied().collect();
+                                        gamepad_ids.extend(bridge_state.rumble_end_times.gamepad_ids());
+                                        gamepad_ids.extend(bridge_state.rumble_patterns.gamepad_ids());
+                                        gamepad_ids.sort_unstable();
+                                        gamepad_ids.dedup();
+                                        for gamepad_id in gamepad_ids {
+                                            bridge_state.rumble_patterns.cancel(gamepad_id);
+                                            bridge_state.pending_gamepad_rumble.push(GamepadRumbleCommand {
+                                                gamepad_id,
+                                                strong_motor: 0.0,
+                                                weak_motor: 0.0,
+                                                duration_secs: 0.0,
+                                                stop: true,
+                                            });
+                                        }
+                                    }
+                                }
                             }
                         });
 
+                        PENDING_AUDIO.with(|commands| {
+                            bridge_state.pending_audio.extend(commands.borrow_mut().drain(..));
+                        });
+
+                        PENDING_VIRTUAL_CURSOR.with(|operations| {
+                            bridge_state
+                                .virtual_cursor_operations
+                                .extend(operations.borrow_mut().drain(..));
+                        });
+
+                        if let Some(mode) = PENDING_WINDOW_MODE.with(|mode| mode.borrow_mut().take()) {
+                            bridge_state.pending_window_mode = Some(mode);
+                        }
+                        if let Some(rgba) = PENDING_CLEAR_COLOR.with(|color| color.borrow_mut().take()) {
+                            bridge_state.pending_clear_color = Some(rgba);
+                        }
+                        if let Some(max_fps) = PENDING_MAX_FPS.with(|max_fps| max_fps.borrow_mut().take()) {
+                            bridge_state.max_fps = max_fps;
+                        }
+                        if let Some(title) = PENDING_WINDOW_TITLE.with(|title| title.borrow_mut().take()) {
+                            bridge_state.pending_window_title = Some(title);
+                        }
+                        if let Some(size) = PENDING_WINDOW_SIZE.with(|size| size.borrow_mut().take()) {
+                            bridge_state.pending_window_size = Some(size);
+                        }
+                        if let Some(visible) = PENDING_CURSOR_VISIBLE.with(|visible| visible.borrow_mut().take()) {
+                            bridge_state.pending_cursor_visible = Some(visible);
+                        }
+                        if let Some(grab) = PENDING_CURSOR_GRAB.with(|grab| grab.borrow_mut().take()) {
+                            bridge_state.pending_cursor_grab = Some(grab);
+                        }
+                        if let Some(enabled) =
+                            PENDING_TEXT_INPUT_ENABLED.with(|enabled| enabled.borrow_mut().take())
+                        {
+                            bridge_state.text_input_enabled = enabled;
+                        }
+
+                        if let Some(enabled) =
+                            PENDING_ACCESSIBILITY_ENABLED.with(|enabled| enabled.borrow_mut().take())
+                        {
+                            bridge_state.accessibility_enabled = enabled;
+                        }
+
                         let camera_dirty = CAMERA_DIRTY.with(|d| {
                             let dirty = *d.borrow();
                             *d.borrow_mut() = false;
@@ -148,29 +648,233 @@ impl RubyRenderApp {
                         if camera_dirty {
                             bridge_state.camera_position = CAMERA_POSITION.with(|p| *p.borrow());
                             bridge_state.camera_scale = CAMERA_SCALE.with(|s| *s.borrow());
+                            bridge_state.camera_rotation = CAMERA_ROTATION.with(|r| *r.borrow());
                             bridge_state.camera_dirty = true;
                         }
+                        if let Some(shake) = PENDING_CAMERA_SHAKE.with(|shake| shake.borrow_mut().take()) {
+                            bridge_state.pending_camera_shake = Some(shake);
+                        }
+                        if let Some(follow) = PENDING_CAMERA_FOLLOW.with(|follow| follow.borrow_mut().take()) {
+                            bridge_state.camera_follow = follow;
+                        }
 
                         let should_stop = SHOULD_STOP.with(|s| *s.borrow());
                         if should_stop {
-                            bridge_state.should_exit = true;
+                            bridge_state.request_exit(ExitReason::StopCalled);
+                        }
+                        // The app exits at the end of this frame; let Ruby save state first.
+                        if let Some(reason) = bridge_state.exit_reason {
+                            call_on_exit(reason);
                         }
                     });
 
                     s.render_app.run();
+                    // Exits the bridge never saw, such as a headless app running out of frames.
+                    call_on_exit(s.render_app.exit_reason().unwrap_or(ExitReason::UserClose));
+
+                    // The readback of the final frames lands after the last callback.
+                    let bridge = s.render_app.bridge_state();
+                    if let Some(pixels) = bridge.lock().unwrap().frame_pixels.take() {
+                        SHARED_FRAME_PIXELS.with(|shared| *shared.borrow_mut() = Some(pixels));
+                    }
                 }
             }
         });
 
-        RUBY_CALLBACK.with(|cb| {
-            *cb.borrow_mut() = None;
+        match RUN_ERROR.with(|error| error.borrow_mut().take()) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Registers a block called once with `:user_close`, `:stop_called` or
+    /// `:error` in the last frame, before the app exits.
+    fn on_exit(&self) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !ruby.block_given() {
+            return Err(Error::new(ruby.exception_arg_error(), "on_exit requires a block"));
+        }
+        let proc = ruby.block_proc()?;
+        ON_EXIT_CALLBACK.with(|callback| *callback.borrow_mut() = Some(proc));
+        Ok(())
+    }
+
+    /// Drops an app that was created but not run, so a new one can be created.
+    fn close(&self) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if RENDER_STATE.with(|state| state.try_borrow_mut().is_err()) {
+            return Err(Error::new(
+                ruby.exception_runtime_error(),
+                "cannot close a RenderApp while it is running; call stop instead",
+            ));
+        }
+        reset_app_state();
+        Ok(())
+    }
+
+    /// `[width, height, rgba]` of the latest headless frame, with `rgba` a
+    /// binary String of top-to-bottom RGBA8 rows; nil before the first readback.
+    fn read_pixels(&self) -> Result<Option<RArray>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        SHARED_FRAME_PIXELS.with(|shared| {
+            let shared = shared.borrow();
+            let Some(pixels) = shared.as_ref() else {
+                return Ok(None);
+            };
+            let result = ruby.ary_new_capa(3);
+            result.push(pixels.width)?;
+            result.push(pixels.height)?;
+            result.push(ruby.str_from_slice(&pixels.rgba))?;
+            Ok(Some(result))
+        })
+    }
+
+    /// Writes a debug bundle into the directory `path` from the latest frame:
+    /// render state, input, stats, warnings, camera, window and a screenshot
+    /// when headless readback ran. A given block receives the
+    /// `Bevy::DebugBundle` to add its own sections before it is written.
+    /// Returns `{path:, sections:}`; a failing section is listed, not raised.
+    fn dump_debug_bundle(&self, args: &[Value]) -> Result<RHash, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let Some((path, options)) = args.split_first() else {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                "wrong number of arguments (given 0, expected 1..2)",
+            ));
+        };
+        let path = String::try_convert(*path)?;
+        let bundle = MagnusDebugBundle::with_max_log_bytes(parse_max_log_bytes(&ruby, options)?);
+        bundle.set_info_native("frame", self.frame_count().to_string());
+
+        collect_json(&ruby, &bundle, "render_state", || {
+            let sprites = ruby.ary_new();
+            SHARED_SPRITE_TRANSFORMS.with(|samples| -> Result<(), Error> {
+                for sample in samples.borrow().iter() {
+                    sprites.push(fields_hash(
+                        &ruby,
+                        vec![
+                            ("id", sample.ruby_entity_id.into_value_with(&ruby)),
+                            ("position", (sample.x, sample.y).into_value_with(&ruby)),
+                            ("rotation", sample.rotation.into_value_with(&ruby)),
+                            ("size", (sample.width, sample.height).into_value_with(&ruby)),
+                            ("anchor", (sample.anchor_x, sample.anchor_y).into_value_with(&ruby)),
+                            ("group", sample.group.as_deref().into_value_with(&ruby)),
+                        ],
+                    )?)?;
+                }
+                Ok(())
+            })?;
+            let meshes: Vec<u64> = MESH_SHAPES.with(|shapes| shapes.borrow().keys().copied().collect());
+            Ok(fields_hash(
+                &ruby,
+                vec![
+                    ("sprites", sprites.as_value()),
+                    ("mesh_ids", meshes.into_value_with(&ruby)),
+                    ("sleeping", self.sleeping_count().into_value_with(&ruby)),
+                ],
+            )?
+            .as_value())
         });
 
-        RENDER_STATE.with(|state| {
-            *state.borrow_mut() = None;
+        collect_json(&ruby, &bundle, "input", || {
+            SHARED_INPUT.with(|input| {
+                let input = input.borrow();
+                let sorted = |set: &AuditSet<String>| {
+                    let mut names: Vec<String> = set.iter().cloned().collect();
+                    names.sort_unstable();
+                    names
+                };
+                let held = ruby.hash_new();
+                for key in sorted(&input.keys_pressed) {
+                    let seconds = input.key_held_duration(&key);
+                    held.aset(key, seconds)?;
+                }
+                Ok(fields_hash(
+                    &ruby,
+                    vec![
+                        ("captured", self.input_captured().into_value_with(&ruby)),
+                        ("keys_pressed", sorted(&input.keys_pressed).into_value_with(&ruby)),
+                        ("keys_just_pressed", sorted(&input.keys_just_pressed).into_value_with(&ruby)),
+                        ("keys_just_released", sorted(&input.keys_just_released).into_value_with(&ruby)),
+                        ("keys_held", held.as_value()),
+                        ("mouse_buttons", sorted(&input.mouse_buttons_pressed).into_value_with(&ruby)),
+                        ("mouse_position", input.mouse_position.into_value_with(&ruby)),
+                        ("mouse_delta", input.mouse_delta.into_value_with(&ruby)),
+                        ("scroll_delta", input.scroll_delta.into_value_with(&ruby)),
+                        ("typed_text", input.typed_text.as_str().into_value_with(&ruby)),
+                        ("touches", touches_to_array(&ruby, &input.touches)?.as_value()),
+                    ],
+                )?
+                .as_value())
+            })
         });
 
-        Ok(())
+        collect_json(&ruby, &bundle, "gamepads", || Ok(self.gamepads_state()?.as_value()));
+
+        collect_json(&ruby, &bundle, "stats", || {
+            let stats = self.stats()?;
+            stats.aset(ruby.to_symbol("frame"), self.frame_count())?;
+            stats.aset(ruby.to_symbol("delta_time"), self.delta_time())?;
+            stats.aset(ruby.to_symbol("elapsed_time"), self.elapsed_time())?;
+            Ok(stats.as_value())
+        });
+
+        collect_json(&ruby, &bundle, "render_warnings", || {
+            let warnings = ruby.ary_new();
+            SHARED_WARNINGS.with(|records| -> Result<(), Error> {
+                for record in records.borrow().iter() {
+                    warnings.push(warning_to_hash(&ruby, record)?)?;
+                }
+                Ok(())
+            })?;
+            Ok(warnings.as_value())
+        });
+
+        collect_json(&ruby, &bundle, "camera", || {
+            Ok(fields_hash(
+                &ruby,
+                vec![
+                    ("position", self.get_camera_position().as_value()),
+                    ("scale", self.get_camera_scale().into_value_with(&ruby)),
+                    ("rotation", self.get_camera_rotation().into_value_with(&ruby)),
+                    ("zoom_limits", self.zoom_limits().into_value_with(&ruby)),
+                ],
+            )?
+            .as_value())
+        });
+
+        collect_json(&ruby, &bundle, "window", || {
+            Ok(fields_hash(
+                &ruby,
+                vec![
+                    ("size", self.window_size().into_value_with(&ruby)),
+                    ("mode", self.window_mode().as_value()),
+                    ("metrics", self.window_metrics()?.as_value()),
+                    ("clear_color", self.clear_color().into_value_with(&ruby)),
+                    ("max_fps", self.max_fps().into_value_with(&ruby)),
+                    ("cursor_visible", self.cursor_visible().into_value_with(&ruby)),
+                    ("cursor_grab", self.cursor_grab().as_value()),
+                    ("text_input_enabled", self.text_input_enabled().into_value_with(&ruby)),
+                ],
+            )?
+            .as_value())
+        });
+
+        bundle.collect_native("screenshot", || {
+            Ok(SHARED_FRAME_PIXELS.with(|shared| {
+                shared.borrow().as_ref().map(|pixels| BundleContent::Image {
+                    width: pixels.width,
+                    height: pixels.height,
+                    rgba: pixels.rgba.clone(),
+                })
+            }))
+        });
+
+        let bundle = ruby.obj_wrap(bundle);
+        if ruby.block_given() {
+            ruby.yield_value::<_, Value>(bundle)?;
+        }
+        bundle.write(path)
     }
 
     fn stop(&self) -> Result<(), Error> {
@@ -180,34 +884,173 @@ impl RubyRenderApp {
         Ok(())
     }
 
+    /// True once the loop starts ending, including the frame the window's
+    /// close button was pressed.
     fn should_close(&self) -> bool {
-        RENDER_STATE.with(|state| {
-            let state = state.borrow();
-            if let Some(ref s) = *state {
-                s.render_app.should_exit()
-            } else {
-                true
+        if SHARED_CLOSING.with(|closing| *closing.borrow()) {
+            return true;
+        }
+        // The state is borrowed while running, where the snapshot above is current.
+        match RENDER_STATE.with(|state| state.try_borrow().map(|state| state.as_ref().map(|s| s.render_app.should_exit()))) {
+            Ok(Some(should_exit)) => should_exit,
+            Ok(None) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// While captured, the gameplay-facing key and mouse button accessors
+    /// report nothing pressed; the `raw_` accessors keep working so a focused
+    /// widget can consume the input.
+    fn set_input_captured(&self, captured: bool) -> Result<(), Error> {
+        INPUT_CAPTURED.with(|c| {
+            *c.borrow_mut() = captured;
+        });
+        Ok(())
+    }
+
+    fn input_captured(&self) -> bool {
+        INPUT_CAPTURED.with(|c| *c.borrow())
+    }
+
+    fn key_pressed(&self, key: String) -> bool {
+        !self.input_captured() && self.raw_key_pressed(key)
+    }
+
+    fn key_just_pressed(&self, key: String) -> bool {
+        !self.input_captured() && self.raw_key_just_pressed(key)
+    }
+
+    fn key_just_released(&self, key: String) -> bool {
+        !self.input_captured() && self.raw_key_just_released(key)
+    }
+
+    fn mouse_button_pressed(&self, button: String) -> bool {
+        !self.input_captured() && self.raw_mouse_button_pressed(button)
+    }
+
+    /// Seconds `key` has been held, 0.0 when it is not pressed or input is captured.
+    fn key_held_duration(&self, key: String) -> f64 {
+        if self.input_captured() {
+            return 0.0;
+        }
+        SHARED_INPUT.with(|input| input.borrow().key_held_duration(&key))
+    }
+
+    /// Binds `action` to `keys:`, `mouse_buttons:` and `gamepad_buttons:`
+    /// (names as the input accessors take them), replacing its old sources.
+    /// Takes effect immediately, so a rebinding menu can call it any time.
+    fn bind_action(&self, args: &[Value]) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (action, options) = match args {
+            [action] => (*action, None),
+            [action, options] => (*action, Some(RHash::try_convert(*options)?)),
+            _ => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("wrong number of arguments (given {}, expected 1..2)", args.len()),
+                ));
+            }
+        };
+        let action = symbol_or_string(action)?;
+        let names = |key: &LazyId| -> Result<BTreeSet<String>, Error> {
+            let Some(options) = options.as_ref() else {
+                return Ok(BTreeSet::new());
+            };
+            match get_hash_value::<RArray>(&ruby, options, key)? {
+                Some(names) => names.into_iter().map(symbol_or_string).collect(),
+                None => Ok(BTreeSet::new()),
             }
+        };
+        let binding = ActionBinding {
+            keys: names(&keys::KEYS)?,
+            mouse_buttons: names(&keys::MOUSE_BUTTONS)?,
+            gamepad_buttons: names(&keys::GAMEPAD_BUTTONS)?,
+        };
+        if let Some(key) = binding.unknown_key() {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("unknown key {:?} for action {:?} (see Bevy::KeyCode.names)", key, action),
+            ));
+        }
+        ACTION_MAP.with(|map| map.borrow_mut().bind(&action, binding));
+        Ok(())
+    }
+
+    /// Removes an action; returns whether it was bound.
+    fn unbind_action(&self, action: Value) -> Result<bool, Error> {
+        let action = symbol_or_string(action)?;
+        Ok(ACTION_MAP.with(|map| map.borrow_mut().unbind(&action)))
+    }
+
+    /// `{keys:, mouse_buttons:, gamepad_buttons:}` of an action, or nil when unbound.
+    fn action_binding(&self, action: Value) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let action = symbol_or_string(action)?;
+        ACTION_MAP.with(|map| {
+            let map = map.borrow();
+            let Some(binding) = map.binding(&action) else {
+                return Ok(None);
+            };
+            let hash = ruby.hash_new_capa(3);
+            hash.aset(ruby.to_symbol("keys"), binding.keys.iter().cloned().collect::<Vec<_>>())?;
+            hash.aset(
+                ruby.to_symbol("mouse_buttons"),
+                binding.mouse_buttons.iter().cloned().collect::<Vec<_>>(),
+            )?;
+            hash.aset(
+                ruby.to_symbol("gamepad_buttons"),
+                binding.gamepad_buttons.iter().cloned().collect::<Vec<_>>(),
+            )?;
+            Ok(Some(hash))
         })
     }
 
-    fn key_pressed(&self, key: String) -> bool {
+    fn action_names(&self) -> Vec<String> {
+        ACTION_MAP.with(|map| map.borrow().actions().map(str::to_string).collect())
+    }
+
+    /// True while any source of `action` is held; false while input is captured.
+    fn action_pressed(&self, action: Value) -> Result<bool, Error> {
+        let action = symbol_or_string(action)?;
+        Ok(!self.input_captured()
+            && ACTION_MAP.with(|map| SHARED_INPUT.with(|input| map.borrow().pressed(&action, &input.borrow()))))
+    }
+
+    /// True on the frame the first source of `action` goes down.
+    fn action_just_pressed(&self, action: Value) -> Result<bool, Error> {
+        let action = symbol_or_string(action)?;
+        Ok(!self.input_captured()
+            && ACTION_MAP.with(|map| SHARED_INPUT.with(|input| map.borrow().just_pressed(&action, &input.borrow()))))
+    }
+
+    /// True on the frame the last held source of `action` goes up.
+    fn action_just_released(&self, action: Value) -> Result<bool, Error> {
+        let action = symbol_or_string(action)?;
+        Ok(!self.input_captured()
+            && ACTION_MAP.with(|map| SHARED_INPUT.with(|input| map.borrow().just_released(&action, &input.borrow()))))
+    }
+
+    fn mouse_button_just_pressed(&self, button: String) -> bool {
+        !self.input_captured() && self.raw_mouse_button_just_pressed(button)
+    }
+
+    fn raw_key_pressed(&self, key: String) -> bool {
         SHARED_INPUT.with(|input| input.borrow().key_pressed(&key))
     }
 
-    fn key_just_pressed(&self, key: String) -> bool {
+    fn raw_key_just_pressed(&self, key: String) -> bool {
         SHARED_INPUT.with(|input| input.borrow().key_just_pressed(&key))
     }
 
-    fn key_just_released(&self, key: String) -> bool {
+    fn raw_key_just_released(&self, key: String) -> bool {
         SHARED_INPUT.with(|input| input.borrow().key_just_released(&key))
     }
 
-    fn mouse_button_pressed(&self, button: String) -> bool {
+    fn raw_mouse_button_pressed(&self, button: String) -> bool {
         SHARED_INPUT.with(|input| input.borrow().mouse_button_pressed(&button))
     }
 
-    fn mouse_button_just_pressed(&self, button: String) -> bool {
+    fn raw_mouse_button_just_pressed(&self, button: String) -> bool {
         SHARED_INPUT.with(|input| input.borrow().mouse_button_just_pressed(&button))
     }
 
@@ -220,6 +1063,81 @@ impl RubyRenderApp {
         array
     }
 
+    /// Scrolling this frame as `[x, y]` in lines; zero while input is captured.
+    fn scroll_delta(&self) -> RArray {
+        scroll_array(|input| input.scroll_delta)
+    }
+
+    fn mouse_scroll_lines(&self) -> RArray {
+        scroll_array(|input| input.scroll_line_delta)
+    }
+
+    fn mouse_scroll_pixels(&self) -> RArray {
+        scroll_array(|input| input.scroll_pixel_delta)
+    }
+
+    /// Fingers on the screen as `{id:, phase:, position: [x, y],
+    /// previous_position: [x, y], force:}`, in id order. A released touch is
+    /// listed once, on the frame it ended or was canceled. Empty while input
+    /// is captured like the mouse state.
+    fn touches(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if INPUT_CAPTURED.with(|captured| *captured.borrow()) {
+            return Ok(ruby.ary_new());
+        }
+        SHARED_INPUT.with(|input| touches_to_array(&ruby, &input.borrow().touches))
+    }
+
+    /// `touch_just_pressed?` for any finger, `touch_just_pressed?(id)` for one.
+    fn touch_just_pressed(&self, args: &[Value]) -> Result<bool, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if args.len() > 1 {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("wrong number of arguments (given {}, expected 0..1)", args.len()),
+            ));
+        }
+        let id = args.first().map(|value| u64::try_convert(*value)).transpose()?;
+        if INPUT_CAPTURED.with(|captured| *captured.borrow()) {
+            return Ok(false);
+        }
+        Ok(SHARED_INPUT.with(|input| input.borrow().touches.just_pressed(id)))
+    }
+
+    /// `[x, y]` of the touch, or nil once it is no longer listed.
+    /// Characters typed since the last frame while text input is enabled.
+    /// Backspace, delete, enter and tab appear as "\b", "\x7F", "\n" and "\t".
+    fn typed_text(&self) -> String {
+        if INPUT_CAPTURED.with(|captured| *captured.borrow()) {
+            return String::new();
+        }
+        SHARED_INPUT.with(|input| input.borrow().typed_text.as_str().to_string())
+    }
+
+    /// Starts or stops collecting typed characters; applies next frame.
+    fn set_text_input_enabled(&self, enabled: bool) -> bool {
+        TEXT_INPUT_ENABLED.with(|current| *current.borrow_mut() = enabled);
+        PENDING_TEXT_INPUT_ENABLED.with(|pending| *pending.borrow_mut() = Some(enabled));
+        enabled
+    }
+
+    fn text_input_enabled(&self) -> bool {
+        TEXT_INPUT_ENABLED.with(|enabled| *enabled.borrow())
+    }
+
+    fn touch_position(&self, id: u64) -> Option<(f64, f64)> {
+        if INPUT_CAPTURED.with(|captured| *captured.borrow()) {
+            return None;
+        }
+        SHARED_INPUT.with(|input| {
+            input
+                .borrow()
+                .touches
+                .position(id)
+                .map(|(x, y)| (x as f64, y as f64))
+        })
+    }
+
     fn mouse_delta(&self) -> RArray {
         let ruby = Ruby::get().expect("Ruby runtime not available");
         let (dx, dy) = SHARED_INPUT.with(|input| input.borrow().mouse_delta);
@@ -230,72 +1148,29 @@ impl RubyRenderApp {
     }
 
     fn pressed_keys(&self) -> RArray {
-        let ruby = Ruby::get().expect("Ruby runtime not available");
-        let keys = SHARED_INPUT.with(|input| input.borrow().get_pressed_keys());
-        let array = ruby.ary_new_capa(keys.len());
-        for key in keys {
-            let _ = array.push(key);
+        if self.input_captured() {
+            let ruby = Ruby::get().expect("Ruby runtime not available");
+            return ruby.ary_new();
         }
-        array
+        self.raw_pressed_keys()
     }
 
-    fn gamepads_state(&self) -> Result<RArray, Error> {
+    fn raw_pressed_keys(&self) -> RArray {
         let ruby = Ruby::get().expect("Ruby runtime not available");
-        let mut states = SHARED_INPUT.with(|input| input.borrow().gamepad_states());
-        states.sort_by_key(|state| state.id);
-
-        let id_sym = ruby.to_symbol("id");
-        let name_sym = ruby.to_symbol("name");
-        let buttons_pressed_sym = ruby.to_symbol("buttons_pressed");
-        let buttons_just_pressed_sym = ruby.to_symbol("buttons_just_pressed");
-        let buttons_just_released_sym = ruby.to_symbol("buttons_just_released");
-        let axes_sym = ruby.to_symbol("axes");
-
-        let result = ruby.ary_new_capa(states.len());
-
-        for state in states {
-            let hash = ruby.hash_new();
-            hash.aset(id_sym, state.id)?;
-            hash.aset(name_sym, state.name)?;
-
-            let mut buttons_pressed: Vec<_> = state.buttons_pressed.into_iter().collect();
-            buttons_pressed.sort();
-            let buttons_pressed_array = ruby.ary_new_capa(buttons_pressed.len());
-            for button in buttons_pressed {
-                buttons_pressed_array.push(button)?;
+        SHARED_INPUT.with(|input| {
+            let input = input.borrow();
+            let array = ruby.ary_new_capa(input.keys_pressed.len());
+            for key in &input.keys_pressed {
+                let _ = array.push(key.as_str());
             }
-            hash.aset(buttons_pressed_sym, buttons_pressed_array)?;
+            array
+        })
+    }
 
-            let mut buttons_just_pressed: Vec<_> = state.buttons_just_pressed.into_iter().collect();
-            buttons_just_pressed.sort();
-            let buttons_just_pressed_array = ruby.ary_new_capa(buttons_just_pressed.len());
-            for button in buttons_just_pressed {
-                buttons_just_pressed_array.push(button)?;
-            }
-            hash.aset(buttons_just_pressed_sym, buttons_just_pressed_array)?;
-
-            let mut buttons_just_released: Vec<_> =
-                state.buttons_just_released.into_iter().collect();
-            buttons_just_released.sort();
-            let buttons_just_released_array = ruby.ary_new_capa(buttons_just_released.len());
-            for button in buttons_just_released {
-                buttons_just_released_array.push(button)?;
-            }
-            hash.aset(buttons_just_released_sym, buttons_just_released_array)?;
-
-            let axes_hash = ruby.hash_new();
-            let mut axes_entries: Vec<_> = state.axes.into_iter().collect();
-            axes_entries.sort_by(|left, right| left.0.cmp(&right.0));
-            for (axis, value) in axes_entries {
-                axes_hash.aset(axis, value as f64)?;
-            }
-            hash.aset(axes_sym, axes_hash)?;
-
-            result.push(hash)?;
-        }
-
-        Ok(result)
-    }
+    fn gamepads_state(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        SHARED_INPUT.with(|input| gamepads_to_array(&ruby, &input.borrow().gamepads))
+    }
 
     fn sync_sprite(
         &self,
@@ -318,11 +1193,78 @@ impl RubyRenderApp {
         Ok(())
     }
 
-    fn remove_sprite(&self, ruby_entity_id: u64) -> Result<(), Error> {
+    /// `sync_sprites_batch([[id, sprite_hash, transform_hash], ...])`: one
+    /// crossing for a frame's sprites. Every entry is parsed before any is
+    /// queued, so a bad entry raises without syncing the others. Returns the
+    /// number of sprites queued.
+    fn sync_sprites_batch(&self, entries: RArray) -> Result<usize, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let mut parsed = Vec::with_capacity(entries.len());
+        for (index, entry) in entries.into_iter().enumerate() {
+            let entry = RArray::try_convert(entry)?;
+            if entry.len() != 3 {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!(
+                        "sync_sprites_batch entry {} must be [id, sprite, transform], got {} elements",
+                        index,
+                        entry.len()
+                    ),
+                ));
+            }
+            let ruby_entity_id: u64 = entry.entry(0)?;
+            let sprite_hash: RHash = entry.entry(1)?;
+            let transform_hash: RHash = entry.entry(2)?;
+            parsed.push((
+                ruby_entity_id,
+                parse_sprite_data(&ruby, &sprite_hash)?,
+                parse_transform_data(&ruby, &transform_hash)?,
+            ));
+        }
+
         PENDING_SPRITES.with(|sprites| {
-            sprites
-                .borrow_mut()
-                .remove_sprite_standalone(ruby_entity_id);
+            let mut sprites = sprites.borrow_mut();
+            for (ruby_entity_id, sprite_data, transform_data) in &parsed {
+                sprites.sync_sprite_standalone(*ruby_entity_id, sprite_data, transform_data);
+            }
+        });
+
+        Ok(parsed.len())
+    }
+
+    /// `remove_sprite(id)` or `remove_sprite(id, defer: frames)`.
+    fn remove_sprite(&self, args: &[Value]) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let Some(&id_value) = args.first() else {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                "wrong number of arguments (given 0, expected 1..2)",
+            ));
+        };
+        let ruby_entity_id = u64::try_convert(id_value)?;
+        let defer = match args.get(1) {
+            Some(value) => {
+                let hash: RHash = TryConvert::try_convert(*value)?;
+                get_hash_value::<u32>(&ruby, &hash, &keys::DEFER)?
+            }
+            None => None,
+        };
+
+        PENDING_SPRITES.with(|sprites| {
+            let mut sprites = sprites.borrow_mut();
+            match defer {
+                Some(frames) => sprites.remove_sprite_deferred_standalone(ruby_entity_id, frames),
+                None => sprites.remove_sprite_standalone(ruby_entity_id),
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Sets how many frames `remove_sprite` without `defer:` keeps sprites visible.
+    fn set_remove_defer(&self, frames: u32) -> Result<(), Error> {
+        PENDING_SPRITES.with(|sprites| {
+            sprites.borrow_mut().set_remove_defer_standalone(frames);
         });
 
         Ok(())
@@ -336,6 +1278,70 @@ impl RubyRenderApp {
         Ok(())
     }
 
+    fn apply_palette(&self, palette: &MagnusPalette, ruby_entity_ids: Vec<u64>) -> Result<(), Error> {
+        let assignments = palette.assignments(&ruby_entity_ids);
+        PENDING_SPRITES.with(|sprites| {
+            let mut sprites = sprites.borrow_mut();
+            for (ruby_entity_id, color) in assignments {
+                sprites.set_color_standalone(ruby_entity_id, color.to_array());
+            }
+        });
+        Ok(())
+    }
+
+    /// Kicks a mesh-mode sprite's vertex springs along `(x, y)`.
+    fn apply_impulse(&self, ruby_entity_id: u64, x: f64, y: f64, strength: f64) -> Result<(), Error> {
+        PENDING_SPRITES.with(|sprites| {
+            sprites.borrow_mut().apply_impulse_standalone(
+                ruby_entity_id,
+                [x as f32, y as f32],
+                strength as f32,
+            );
+        });
+        Ok(())
+    }
+
+    /// Draws a synced sprite above the others on its layer.
+    fn bring_to_front(&self, ruby_entity_id: u64) {
+        PENDING_SPRITES.with(|sprites| {
+            sprites
+                .borrow_mut()
+                .set_draw_order_standalone(ruby_entity_id, DrawOrderChange::Front);
+        });
+    }
+
+    /// Draws a synced sprite below the others on its layer.
+    fn send_to_back(&self, ruby_entity_id: u64) {
+        PENDING_SPRITES.with(|sprites| {
+            sprites
+                .borrow_mut()
+                .set_draw_order_standalone(ruby_entity_id, DrawOrderChange::Back);
+        });
+    }
+
+    /// Moves a synced sprite to `index` on its layer, 0 being the back.
+    fn set_draw_order(&self, ruby_entity_id: u64, index: usize) {
+        PENDING_SPRITES.with(|sprites| {
+            sprites
+                .borrow_mut()
+                .set_draw_order_standalone(ruby_entity_id, DrawOrderChange::Index(index));
+        });
+    }
+
+    /// Index of a synced sprite on its layer as of the last frame, or nil.
+    fn draw_order(&self, ruby_entity_id: u64) -> Option<usize> {
+        SHARED_DRAW_ORDER.with(|shared| shared.borrow().1.get(&ruby_entity_id).copied())
+    }
+
+    fn clear_sprite_color(&self, ruby_entity_id: u64) -> Result<(), Error> {
+        PENDING_SPRITES.with(|sprites| {
+            sprites
+                .borrow_mut()
+                .clear_color_standalone(ruby_entity_id);
+        });
+        Ok(())
+    }
+
     fn sync_text(
         &self,
         ruby_entity_id: u64,
@@ -343,6 +1349,12 @@ impl RubyRenderApp {
         transform_hash: RHash,
     ) -> Result<(), Error> {
         let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !self.text_sync_enabled() {
+            return Err(Error::new(
+                ruby.exception_runtime_error(),
+                "Text sync is disabled for this RenderApp (text_sync: false)",
+            ));
+        }
         let text_data = parse_text_data(&ruby, &text_hash)?;
         let transform_data = parse_text_transform_data(&ruby, &transform_hash)?;
 
@@ -356,6 +1368,9 @@ impl RubyRenderApp {
     }
 
     fn remove_text(&self, ruby_entity_id: u64) -> Result<(), Error> {
+        if !self.text_sync_enabled() {
+            return Ok(());
+        }
         PENDING_TEXTS.with(|texts| {
             texts.borrow_mut().remove_text_standalone(ruby_entity_id);
         });
@@ -364,6 +1379,9 @@ impl RubyRenderApp {
     }
 
     fn clear_texts(&self) -> Result<(), Error> {
+        if !self.text_sync_enabled() {
+            return Ok(());
+        }
         PENDING_TEXTS.with(|texts| {
             texts.borrow_mut().clear_standalone();
         });
@@ -371,6 +1389,98 @@ impl RubyRenderApp {
         Ok(())
     }
 
+    /// `floating_text(x, y, content, { color_r:, ..., rise:, duration:, font_size: })`:
+    /// a text that rises, fades and removes itself on the render side.
+    fn floating_text(&self, x: f64, y: f64, content: String, options: RHash) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !self.text_sync_enabled() {
+            return Err(Error::new(
+                ruby.exception_runtime_error(),
+                "Text sync is disabled for this RenderApp (text_sync: false)",
+            ));
+        }
+        let request = parse_floating_text_request(&ruby, x, y, content, &options)?;
+
+        PENDING_FLOATING_TEXTS.with(|floating_texts| {
+            floating_texts.borrow_mut().spawn_standalone(request);
+        });
+
+        Ok(())
+    }
+
+    /// Floating texts alive at once; the oldest are removed past it.
+    fn set_floating_text_cap(&self, cap: usize) -> Result<(), Error> {
+        PENDING_FLOATING_TEXTS.with(|floating_texts| {
+            floating_texts.borrow_mut().set_cap_standalone(cap);
+        });
+        Ok(())
+    }
+
+    /// Applies sprite syncs for `group` only every `divisor`th frame, each
+    /// time with the latest values queued since; takes effect next frame.
+    fn set_group_update_divisor(&self, group: String, divisor: u32) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if divisor == 0 {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                "group update divisor must be at least 1",
+            ));
+        }
+        PENDING_GROUP_THROTTLE.with(|throttle| {
+            throttle.borrow_mut().set_divisor_standalone(&group, divisor);
+        });
+        Ok(())
+    }
+
+    /// Throttled groups whose syncs go through this frame.
+    fn due_groups(&self) -> Vec<String> {
+        SHARED_GROUP_SCHEDULE.with(|schedule| {
+            let (schedule, frame) = &*schedule.borrow();
+            schedule.due_groups(*frame)
+        })
+    }
+
+    /// False only on the frames a throttled group's syncs are held back.
+    fn group_due(&self, group: String) -> bool {
+        SHARED_GROUP_SCHEDULE.with(|schedule| {
+            let (schedule, frame) = &*schedule.borrow();
+            schedule.is_due(Some(&group), *frame)
+        })
+    }
+
+    /// Puts behaviors and jelly springs to sleep once they move less than
+    /// `sleep_epsilon` world units a frame for `frames` frames in a row;
+    /// motion above `wake_epsilon` starts the count over. `frames` 0 turns
+    /// sleeping off and wakes everything. Takes effect next frame.
+    fn set_sleep_thresholds(&self, sleep_epsilon: f64, wake_epsilon: f64, frames: u32) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        for (name, value) in [("sleep", sleep_epsilon), ("wake", wake_epsilon)] {
+            if !(value.is_finite() && value >= 0.0) {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("{} threshold must be a non-negative number, got {}", name, value),
+                ));
+            }
+        }
+        PENDING_SLEEP.with(|sleep| {
+            sleep.borrow_mut().set_config_standalone(SleepConfig::new(
+                sleep_epsilon as f32,
+                wake_epsilon as f32,
+                frames,
+            ));
+        });
+        Ok(())
+    }
+
+    /// Wakes every sleeping entity next frame.
+    fn wake_all(&self) {
+        PENDING_SLEEP.with(|sleep| sleep.borrow_mut().wake_all_standalone());
+    }
+
+    fn sleeping_count(&self) -> usize {
+        SHARED_SLEEPING_COUNT.with(|count| *count.borrow())
+    }
+
     fn sync_mesh(
         &self,
         ruby_entity_id: u64,
@@ -378,6 +1488,12 @@ impl RubyRenderApp {
         transform_hash: RHash,
     ) -> Result<(), Error> {
         let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !self.mesh_sync_enabled() {
+            return Err(Error::new(
+                ruby.exception_runtime_error(),
+                "Mesh sync is disabled for this RenderApp (mesh_sync: false)",
+            ));
+        }
         let mesh_data = parse_mesh_data(&ruby, &mesh_hash)?;
         let transform_data = parse_mesh_transform_data(&ruby, &transform_hash)?;
 
@@ -386,176 +1502,1845 @@ impl RubyRenderApp {
                 .borrow_mut()
                 .sync_mesh_standalone(ruby_entity_id, &mesh_data, &transform_data);
         });
+        MESH_SHAPES.with(|shapes| {
+            shapes.borrow_mut().insert(ruby_entity_id, mesh_data);
+        });
 
         Ok(())
     }
 
     fn remove_mesh(&self, ruby_entity_id: u64) -> Result<(), Error> {
+        if !self.mesh_sync_enabled() {
+            return Ok(());
+        }
         PENDING_MESHES.with(|meshes| {
             meshes.borrow_mut().remove_mesh_standalone(ruby_entity_id);
         });
+        MESH_SHAPES.with(|shapes| {
+            shapes.borrow_mut().remove(&ruby_entity_id);
+        });
+
+        Ok(())
+    }
+
+    fn clear_meshes(&self) -> Result<(), Error> {
+        if !self.mesh_sync_enabled() {
+            return Ok(());
+        }
+        PENDING_MESHES.with(|meshes| {
+            meshes.borrow_mut().clear_standalone();
+        });
+        MESH_SHAPES.with(|shapes| shapes.borrow_mut().clear());
+
+        Ok(())
+    }
+
+    /// Triangles covering a synced mesh as `[x0, y0, x1, y1, x2, y2, ...]` in
+    /// local space, or nil if no mesh is synced under `ruby_entity_id`.
+    fn mesh_triangles(&self, ruby_entity_id: u64) -> Result<Option<RArray>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let Some(triangles) = MESH_SHAPES.with(|shapes| {
+            shapes.borrow().get(&ruby_entity_id).map(shape_triangles)
+        }) else {
+            return Ok(None);
+        };
+        triangles_to_array(&ruby, &triangles).map(Some)
+    }
+
+    fn text_sync_enabled(&self) -> bool {
+        TEXT_SYNC_ENABLED.with(|enabled| *enabled.borrow())
+    }
+
+    fn mesh_sync_enabled(&self) -> bool {
+        MESH_SYNC_ENABLED.with(|enabled| *enabled.borrow())
+    }
+
+    fn spin(&self, ruby_entity_id: u64, radians_per_second: f64) -> Result<(), Error> {
+        queue_behavior(
+            ruby_entity_id,
+            Behavior::Spin {
+                radians_per_second: radians_per_second as f32,
+            },
+        );
+        Ok(())
+    }
+
+    fn pulse(&self, ruby_entity_id: u64, options: RHash) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        queue_behavior(ruby_entity_id, parse_pulse(&ruby, &options)?);
+        Ok(())
+    }
+
+    fn bob(&self, ruby_entity_id: u64, options: RHash) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        queue_behavior(ruby_entity_id, parse_bob(&ruby, &options)?);
+        Ok(())
+    }
+
+    fn clear_behaviors(&self, ruby_entity_id: u64) -> Result<(), Error> {
+        PENDING_BEHAVIORS.with(|behaviors| {
+            behaviors.borrow_mut().clear_standalone(ruby_entity_id);
+        });
+        Ok(())
+    }
+
+    /// Sets the camera-scale levels of detail for a synced sprite.
+    fn set_lod(&self, ruby_entity_id: u64, levels: RArray) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let levels = parse_lod_levels(&ruby, levels)?;
+        PENDING_LODS.with(|lods| {
+            lods.borrow_mut().set_standalone(ruby_entity_id, levels);
+        });
+        Ok(())
+    }
+
+    fn clear_lod(&self, ruby_entity_id: u64) -> Result<(), Error> {
+        PENDING_LODS.with(|lods| {
+            lods.borrow_mut().clear_standalone(ruby_entity_id);
+        });
+        Ok(())
+    }
+
+    /// Sets the hysteresis margin, as a fraction of the boundary scale.
+    fn set_lod_margin(&self, margin: f64) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if margin < 0.0 {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("LOD margin must not be negative, got {}", margin),
+            ));
+        }
+        PENDING_LODS.with(|lods| {
+            lods.borrow_mut().set_margin_standalone(margin as f32);
+        });
+        Ok(())
+    }
+
+    /// Active level index of an entity, or nil before its level is first selected.
+    fn lod_level(&self, ruby_entity_id: u64) -> Option<usize> {
+        SHARED_LOD_LEVELS.with(|levels| {
+            levels
+                .borrow()
+                .iter()
+                .find(|(id, _)| *id == ruby_entity_id)
+                .map(|(_, level)| *level)
+        })
+    }
+
+    /// Moves a sprite group (String/Symbol) or layer (Integer) at `factor`
+    /// times the camera's speed.
+    fn set_parallax_factor(&self, target: Value, factor_x: f64, factor_y: f64) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let target = parse_parallax_target(&ruby, target)?;
+        PENDING_PARALLAX.with(|parallax| {
+            parallax
+                .borrow_mut()
+                .set_factor_standalone(target, (factor_x as f32, factor_y as f32));
+        });
+        Ok(())
+    }
+
+    fn clear_parallax(&self, target: Value) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let target = parse_parallax_target(&ruby, target)?;
+        PENDING_PARALLAX.with(|parallax| {
+            parallax.borrow_mut().clear_standalone(target);
+        });
+        Ok(())
+    }
+
+    /// Rounds every sprite and text onto physical pixels at render time.
+    fn set_pixel_perfect(&self, enabled: bool) {
+        PENDING_PIXEL_SNAP.with(|pixel_snap| {
+            pixel_snap.borrow_mut().set_global_standalone(enabled);
+        });
+    }
+
+    /// Overrides the global pixel-perfect setting for one entity's sprite and text.
+    fn set_entity_pixel_perfect(&self, ruby_entity_id: u64, enabled: bool) {
+        PENDING_PIXEL_SNAP.with(|pixel_snap| {
+            pixel_snap
+                .borrow_mut()
+                .set_entity_standalone(ruby_entity_id, enabled);
+        });
+    }
+
+    /// Registers a pickable rectangle:
+    /// `register_hit_region(id, x, y, width, height, layer: 0, screen_space: false)`.
+    fn register_hit_region(&self, args: &[Value]) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (id, [x, y, width, height], layer, screen_space) = parse_hit_region_args(&ruby, args)?;
+        let region = HitRegion {
+            x,
+            y,
+            width,
+            height,
+            layer: layer.unwrap_or(0),
+            screen_space: screen_space.unwrap_or(false),
+        };
+        PENDING_HIT_REGIONS.with(|hit_regions| {
+            hit_regions.borrow_mut().register_standalone(id, region);
+        });
+        Ok(())
+    }
+
+    /// Moves or resizes a region; `layer:` and `screen_space:` are kept unless given.
+    fn update_hit_region(&self, args: &[Value]) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (id, [x, y, width, height], layer, screen_space) = parse_hit_region_args(&ruby, args)?;
+        let update = HitRegionUpdate {
+            x,
+            y,
+            width,
+            height,
+            layer,
+            screen_space,
+        };
+        PENDING_HIT_REGIONS.with(|hit_regions| {
+            hit_regions.borrow_mut().update_standalone(id, update);
+        });
+        Ok(())
+    }
+
+    fn remove_hit_region(&self, id: u64) {
+        PENDING_HIT_REGIONS.with(|hit_regions| {
+            hit_regions.borrow_mut().remove_standalone(id);
+        });
+    }
+
+    fn play_clip(&self, handle: u64, clip: RHash) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let clip = parse_keyframe_clip(&ruby, &clip)?;
+        PENDING_CLIPS.with(|clips| {
+            clips.borrow_mut().play_standalone(handle, clip);
+        });
+        Ok(())
+    }
+
+    fn stop_clip(&self, handle: u64) -> Result<(), Error> {
+        PENDING_CLIPS.with(|clips| {
+            clips.borrow_mut().stop_standalone(handle);
+        });
+        Ok(())
+    }
+
+    fn seek_clip(&self, handle: u64, time: f64) -> Result<(), Error> {
+        PENDING_CLIPS.with(|clips| {
+            clips.borrow_mut().seek_standalone(handle, time as f32);
+        });
+        Ok(())
+    }
+
+    /// `enable_drag(id, constrain_to: nil, snap_back: false, drag_layer: 100)`:
+    /// lets the mouse pick up the synced sprite `id` and drop it again; see
+    /// `drain_drag_events`.
+    fn enable_drag(&self, args: &[Value]) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (id, config) = parse_drag_args(&ruby, args)?;
+        PENDING_DRAG.with(|drag| drag.borrow_mut().enable_standalone(id, config));
+        Ok(())
+    }
+
+    /// Stops `id` being draggable, putting it down where it is if held.
+    fn disable_drag(&self, id: u64) {
+        PENDING_DRAG.with(|drag| drag.borrow_mut().disable_standalone(id));
+    }
+
+    /// Drags finished since the last call, oldest first:
+    /// `{id:, position:, dropped_at:, origin:, drop_target:, hit_region:, snapped_back:}`.
+    fn drain_drag_events(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let events: Vec<DragEvent> = SHARED_DRAG_EVENTS.with(|events| events.borrow_mut().drain(..).collect());
+        let result = ruby.ary_new_capa(events.len());
+        for event in &events {
+            result.push(drag_event_to_hash(&ruby, event)?)?;
+        }
+        Ok(result)
+    }
+
+    fn drain_finished_clips(&self) -> Vec<u64> {
+        SHARED_FINISHED_CLIPS.with(|finished| finished.borrow_mut().drain(..).collect())
+    }
+
+    fn clip_skipped_tracks(&self) -> u64 {
+        SHARED_CLIP_SKIPPED_TRACKS.with(|skipped| *skipped.borrow())
+    }
+
+    /// Starts recording sprite transforms for rewind, replacing any earlier buffer.
+    fn enable_rewind(&self, window_secs: f64, snapshot_hz: f64) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !(window_secs.is_finite() && window_secs > 0.0) || !(snapshot_hz.is_finite() && snapshot_hz > 0.0) {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                "rewind seconds and snapshot_hz must be positive",
+            ));
+        }
+        push_rewind_operation(RewindOperation::Enable {
+            window_secs: window_secs as f32,
+            snapshot_hz: snapshot_hz as f32,
+        });
+        Ok(())
+    }
+
+    fn disable_rewind(&self) {
+        push_rewind_operation(RewindOperation::Disable);
+    }
+
+    fn rewind_to(&self, seconds_ago: f64) {
+        push_rewind_operation(RewindOperation::RewindTo {
+            seconds_ago: seconds_ago as f32,
+        });
+    }
+
+    fn resume_from_rewind(&self) {
+        push_rewind_operation(RewindOperation::Resume);
+    }
+
+    fn rewinding(&self) -> bool {
+        SHARED_REWIND_STATS.with(|stats| stats.borrow().is_some_and(|stats| stats.rewinding))
+    }
+
+    fn rewind_stats(&self) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        SHARED_REWIND_STATS.with(|stats| {
+            stats
+                .borrow()
+                .as_ref()
+                .map(|stats| stats_to_hash(&ruby, stats))
+                .transpose()
+        })
+    }
+
+    /// Whether the extension was built with the `determinism_audit` feature.
+    fn determinism_audit(&self) -> bool {
+        cfg!(feature = "determinism_audit")
+    }
+
+    /// Checksum of the last audited frame, or nil before the first one.
+    fn frame_checksum(&self) -> Option<u64> {
+        SHARED_CHECKSUMS.with(|checksums| checksums.borrow().last().copied())
+    }
+
+    fn checksum_history(&self) -> Vec<u64> {
+        SHARED_CHECKSUMS.with(|checksums| checksums.borrow().clone())
+    }
+
+    /// Compares audited frames against `recorded`, now and on every later frame.
+    fn compare_recorded_checksums(&self, recorded: Vec<u64>) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !cfg!(feature = "determinism_audit") {
+            return Err(Error::new(
+                ruby.exception_runtime_error(),
+                "bevy-ruby was built without the determinism_audit feature",
+            ));
+        }
+        let divergence = SHARED_CHECKSUMS.with(|checksums| {
+            bevy_ruby::compare_checksum_streams(&recorded, &checksums.borrow(), false)
+        });
+        SHARED_CHECKSUM_DIVERGENCE.with(|shared| *shared.borrow_mut() = divergence);
+        PENDING_RECORDED_CHECKSUMS.with(|pending| *pending.borrow_mut() = Some(recorded));
+        divergence
+            .map(|divergence| divergence_to_hash(&ruby, &divergence))
+            .transpose()
+    }
+
+    fn checksum_divergence(&self) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        SHARED_CHECKSUM_DIVERGENCE.with(|divergence| {
+            divergence
+                .borrow()
+                .map(|divergence| divergence_to_hash(&ruby, &divergence))
+                .transpose()
+        })
+    }
+
+    /// Whether `blend: :add` on texts changes how they are blended.
+    fn text_blend_supported(&self) -> bool {
+        TEXT_BLEND_SUPPORTED
+    }
+
+    fn is_initialized(&self) -> bool {
+        RENDER_STATE.with(|state| state.borrow().is_some())
+    }
+
+    /// Seconds since the previous frame; a small fixed step on the first frame.
+    fn delta_time(&self) -> f64 {
+        SHARED_FRAME_TIME.with(|time| time.borrow().0 as f64)
+    }
+
+    /// Seconds since the render app started.
+    fn elapsed_time(&self) -> f64 {
+        SHARED_FRAME_TIME.with(|time| time.borrow().1)
+    }
+
+    /// Bevy frame number of the current update block, starting at 0.
+    fn frame_count(&self) -> u32 {
+        SHARED_FRAME_COUNT.with(|count| *count.borrow())
+    }
+
+    /// Times each bridge system from the next frame; off by default.
+    fn set_detailed_stats(&self, enabled: bool) {
+        DETAILED_STATS.with(|detailed| *detailed.borrow_mut() = enabled);
+        PENDING_DETAILED_STATS.with(|pending| *pending.borrow_mut() = Some(enabled));
+        if !enabled {
+            SHARED_SYSTEM_TIMINGS.with(|timings| *timings.borrow_mut() = Default::default());
+        }
+    }
+
+    fn detailed_stats(&self) -> bool {
+        DETAILED_STATS.with(|detailed| *detailed.borrow())
+    }
+
+    /// `start_journal(path, max_mb: 50)`: appends every operation Ruby sends
+    /// from this frame on to `path`, replacing an earlier journal.
+    fn start_journal(&self, args: &[Value]) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let journal = create_journal(&ruby, args)?;
+        PENDING_JOURNAL.with(|pending| *pending.borrow_mut() = Some(Some(journal)));
+        Ok(())
+    }
+
+    /// Stops journaling before this frame's operations.
+    fn stop_journal(&self) {
+        PENDING_JOURNAL.with(|pending| *pending.borrow_mut() = Some(None));
+    }
+
+    /// Queues the operations of the journal at `path` one recorded frame per update.
+    fn replay_journal(&self, path: String) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let replay = load_journal(&ruby, &path)?;
+        PENDING_JOURNAL_REPLAY.with(|pending| *pending.borrow_mut() = Some(replay));
+        Ok(())
+    }
+
+    /// Whether a replay was still running at the start of this frame.
+    fn replaying_journal(&self) -> bool {
+        PENDING_JOURNAL_REPLAY.with(|pending| pending.borrow().is_some())
+            || SHARED_JOURNAL_REPLAYING.with(|replaying| *replaying.borrow())
+    }
+
+    /// `{systems: {name => micros}, systems_max: {name => micros},
+    /// sleeping_count:}` for the previous frame; the timings are empty while
+    /// detailed stats are off.
+    fn stats(&self) -> Result<RHash, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        SHARED_SYSTEM_TIMINGS.with(|timings| {
+            let timings = timings.borrow();
+            let hash = ruby.hash_new_capa(3);
+            hash.aset(*keys::SYSTEMS, timings_to_hash(&ruby, &timings.0)?)?;
+            hash.aset(*keys::SYSTEMS_MAX, timings_to_hash(&ruby, &timings.1)?)?;
+            hash.aset(*keys::SLEEPING_COUNT, self.sleeping_count())?;
+            Ok(hash)
+        })
+    }
+
+    /// Also stops `camera_follow_entity`, so the set position holds.
+    fn set_camera_position(&self, x: f64, y: f64, z: f64) -> Result<(), Error> {
+        stop_camera_follow();
+        CAMERA_POSITION.with(|p| {
+            *p.borrow_mut() = (x as f32, y as f32, z as f32);
+        });
+        CAMERA_DIRTY.with(|d| {
+            *d.borrow_mut() = true;
+        });
+        Ok(())
+    }
+
+    /// `camera_follow_entity(id)` or `camera_follow_entity(id, offset: [x, y])`:
+    /// keeps the camera `offset` away from a synced sprite or mesh, read after
+    /// that frame's syncs so the camera never trails it by a frame.
+    fn camera_follow_entity(&self, args: &[Value]) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (id, options) = match args {
+            [id] => (u64::try_convert(*id)?, None),
+            [id, options] => (u64::try_convert(*id)?, Some(RHash::try_convert(*options)?)),
+            _ => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("wrong number of arguments (given {}, expected 1..2)", args.len()),
+                ));
+            }
+        };
+        let offset = match options {
+            Some(options) => get_hash_value::<Vec<f64>>(&ruby, &options, &keys::OFFSET)?,
+            None => None,
+        };
+        let offset = match offset.as_deref() {
+            None => (0.0, 0.0),
+            Some(&[x, y]) if x.is_finite() && y.is_finite() => (x as f32, y as f32),
+            Some(offset) => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("camera follow offset must be two finite numbers, got {:?}", offset),
+                ));
+            }
+        };
+
+        let follow = CameraFollow {
+            ruby_entity_id: id,
+            offset,
+        };
+        CAMERA_FOLLOW.with(|current| *current.borrow_mut() = Some(follow));
+        PENDING_CAMERA_FOLLOW.with(|pending| *pending.borrow_mut() = Some(Some(follow)));
+        Ok(())
+    }
+
+    /// Stops following; the camera stays where the follow left it.
+    fn stop_camera_follow(&self) {
+        stop_camera_follow();
+    }
+
+    /// `{id:, offset: [x, y]}` of the followed entity, or nil.
+    fn camera_follow(&self) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let Some(follow) = CAMERA_FOLLOW.with(|current| *current.borrow()) else {
+            return Ok(None);
+        };
+        let hash = ruby.hash_new_capa(2);
+        hash.aset(*keys::ID, follow.ruby_entity_id)?;
+        hash.aset(*keys::OFFSET, (follow.offset.0 as f64, follow.offset.1 as f64))?;
+        Ok(Some(hash))
+    }
+
+    /// `{sprites:, camera:}`: the frames in which sprite operations and the
+    /// camera transform were last applied, nil before the first. Both match
+    /// when a camera change and sprite changes are queued in one callback.
+    fn applied_frames(&self) -> Result<RHash, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (sprites, camera) = SHARED_APPLIED_FRAMES.with(|frames| *frames.borrow());
+        let hash = ruby.hash_new_capa(2);
+        hash.aset(*keys::SPRITES, sprites)?;
+        hash.aset(*keys::CAMERA, camera)?;
+        Ok(hash)
+    }
+
+    fn get_camera_position(&self) -> RArray {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (x, y, z) = CAMERA_POSITION.with(|p| *p.borrow());
+        let array = ruby.ary_new_capa(3);
+        let _ = array.push(x as f64);
+        let _ = array.push(y as f64);
+        let _ = array.push(z as f64);
+        array
+    }
+
+    /// Clamps `scale` to the zoom limits before it reaches the camera.
+    fn set_camera_scale(&self, scale: f64) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !scale.is_finite() {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("camera scale must be finite, got {}", scale),
+            ));
+        }
+        let mut zoom = camera_zoom();
+        zoom.set_zoom(scale as f32);
+        store_camera_scale(zoom.current);
+        Ok(())
+    }
+
+    /// Sets the range `set_camera_scale`, `zoom_in` and `zoom_out` clamp to,
+    /// re-clamping the current scale.
+    fn set_zoom_limits(&self, min: f64, max: f64) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !(min.is_finite() && max.is_finite() && min > 0.0 && min <= max) {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("zoom limits must be positive with min <= max, got {} and {}", min, max),
+            ));
+        }
+        CAMERA_ZOOM_LIMITS.with(|limits| *limits.borrow_mut() = (min as f32, max as f32));
+
+        let zoom = camera_zoom();
+        let clamped = zoom.current.clamp(zoom.min, zoom.max);
+        if clamped != zoom.current {
+            store_camera_scale(clamped);
+        }
+        Ok(())
+    }
+
+    fn zoom_limits(&self) -> (f64, f64) {
+        let (min, max) = CAMERA_ZOOM_LIMITS.with(|limits| *limits.borrow());
+        (min as f64, max as f64)
+    }
+
+    /// Shrinks the camera scale by `amount`, down to the minimum zoom limit.
+    fn zoom_in(&self, amount: f64) -> f64 {
+        let mut zoom = camera_zoom();
+        zoom.zoom_in(amount as f32);
+        store_camera_scale(zoom.current);
+        zoom.current as f64
+    }
+
+    /// Grows the camera scale by `amount`, up to the maximum zoom limit.
+    fn zoom_out(&self, amount: f64) -> f64 {
+        let mut zoom = camera_zoom();
+        zoom.zoom_out(amount as f32);
+        store_camera_scale(zoom.current);
+        zoom.current as f64
+    }
+
+    fn get_camera_scale(&self) -> f64 {
+        CAMERA_SCALE.with(|s| *s.borrow()) as f64
+    }
+
+    /// Sets camera position, z-rotation and uniform scale from one transform
+    /// hash, so all three reach the camera in the same frame.
+    fn set_camera_transform(&self, transform: RHash) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let transform = transform_from_hash(&ruby, &transform)?;
+        stop_camera_follow();
+        let translation = transform.translation();
+        CAMERA_POSITION.with(|p| {
+            *p.borrow_mut() = (translation.x(), translation.y(), translation.z());
+        });
+        CAMERA_ROTATION.with(|r| {
+            *r.borrow_mut() = transform.rotation_z();
+        });
+        let mut zoom = camera_zoom();
+        zoom.set_zoom(transform.uniform_scale());
+        store_camera_scale(zoom.current);
+        Ok(())
+    }
+
+    fn get_camera_rotation(&self) -> f64 {
+        CAMERA_ROTATION.with(|r| *r.borrow()) as f64
+    }
+
+    /// Shakes the camera by up to `intensity` units, decaying to rest over
+    /// `duration` seconds; a new shake replaces a running one.
+    fn shake_camera(&self, intensity: f64, duration: f64) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !intensity.is_finite() || !duration.is_finite() || intensity < 0.0 || duration < 0.0 {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!(
+                    "camera shake intensity and duration must be non-negative, got {} and {}",
+                    intensity, duration
+                ),
+            ));
+        }
+        PENDING_CAMERA_SHAKE.with(|shake| {
+            *shake.borrow_mut() = Some((intensity as f32, duration as f32));
+        });
+        Ok(())
+    }
+
+    fn queue_gamepad_rumble(
+        &self,
+        gamepad_id: u64,
+        strong_motor: f64,
+        weak_motor: f64,
+        duration_secs: f64,
+    ) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let duration_secs = rumble_duration(&ruby, duration_secs)?;
+        let stop = strong_motor <= 0.0 && weak_motor <= 0.0;
+        let command = GamepadRumbleCommand {
+            gamepad_id,
+            strong_motor: strong_motor as f32,
+            weak_motor: weak_motor as f32,
+            duration_secs,
+            stop,
+        };
+
+        PENDING_GAMEPAD_RUMBLE.with(|rumbles| {
+            rumbles.borrow_mut().push(PendingRumble::Command(command));
+        });
+        Ok(())
+    }
+
+    /// Queues a stop for every gamepad that is connected or was sent a
+    /// rumble, dropping their patterns, after the requests queued before it.
+    fn stop_all_rumble(&self) {
+        PENDING_GAMEPAD_RUMBLE.with(|rumbles| rumbles.borrow_mut().push(PendingRumble::StopAll));
+    }
+
+    /// Whether the gamepad was rumbling, or had a pattern queued, at the
+    /// start of this frame: overlapping requests count until the latest one
+    /// ends, and a stop ends them all.
+    fn is_rumble_active(&self, gamepad_id: u64) -> bool {
+        SHARED_ACTIVE_RUMBLE.with(|active| active.borrow().binary_search(&gamepad_id).is_ok())
+    }
+
+    /// Rumbles the weak motor gently for `duration` seconds, after any
+    /// pattern already playing on the gamepad.
+    fn rumble_light(&self, gamepad_id: u64, duration: f64) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let duration = rumble_duration(&ruby, duration)?;
+        queue_rumble_pattern(gamepad_id, vec![RumbleSegment::light(duration)]);
+        Ok(())
+    }
+
+    /// Rumbles both motors at full strength for `duration` seconds, after
+    /// any pattern already playing on the gamepad.
+    fn rumble_heavy(&self, gamepad_id: u64, duration: f64) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let duration = rumble_duration(&ruby, duration)?;
+        queue_rumble_pattern(gamepad_id, vec![RumbleSegment::heavy(duration)]);
+        Ok(())
+    }
+
+    /// Queues `[[strong, weak, secs], ...]`, or a built-in pattern by name,
+    /// to play back to back after any pattern already playing on the
+    /// gamepad. Segments with both motors at 0 are pauses.
+    fn rumble_pattern(&self, gamepad_id: u64, pattern: Value) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let segments = parse_rumble_pattern(&ruby, pattern)?;
+        queue_rumble_pattern(gamepad_id, segments);
+        Ok(())
+    }
+
+    /// Queues a gamepad-driven picking pointer and returns its picking pointer ID.
+    fn enable_virtual_cursor(&self, options: RHash) -> Result<String, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let defaults = VirtualCursorConfig::default();
+
+        let gamepad_slot: Option<usize> = get_hash_value(&ruby, &options, &keys::GAMEPAD_SLOT)?;
+        let speed: Option<f64> = get_hash_value(&ruby, &options, &keys::SPEED)?;
+        let dead_zone: Option<f64> = get_hash_value(&ruby, &options, &keys::DEAD_ZONE)?;
+        let exponent: Option<f64> = get_hash_value(&ruby, &options, &keys::EXPONENT)?;
+        let button = match get_hash_value::<Value>(&ruby, &options, &keys::BUTTON)? {
+            Some(value) => symbol_or_string(value)?,
+            None => defaults.button.clone(),
+        };
+        let sprite = match get_hash_value::<RHash>(&ruby, &options, &keys::SPRITE)? {
+            Some(hash) => parse_sprite_data(&ruby, &hash)?,
+            None => defaults.sprite.clone(),
+        };
+
+        let config = VirtualCursorConfig {
+            gamepad_slot: gamepad_slot.unwrap_or(defaults.gamepad_slot),
+            speed: speed.map_or(defaults.speed, |speed| speed as f32),
+            curve: StickCurve::new(
+                dead_zone.map_or(defaults.curve.dead_zone, |value| value as f32),
+                exponent.map_or(defaults.curve.exponent, |value| value as f32),
+            ),
+            button,
+            sprite,
+        };
+        let pointer_id = config.pointer_id();
+
+        PENDING_VIRTUAL_CURSOR.with(|operations| {
+            operations
+                .borrow_mut()
+                .push(VirtualCursorOperation::Enable(Box::new(config)));
+        });
+        Ok(pointer_id)
+    }
+
+    fn disable_virtual_cursor(&self) -> Result<(), Error> {
+        PENDING_VIRTUAL_CURSOR.with(|operations| {
+            operations
+                .borrow_mut()
+                .push(VirtualCursorOperation::Disable);
+        });
+        SHARED_VIRTUAL_CURSOR_POSITION.with(|position| {
+            *position.borrow_mut() = None;
+        });
+        Ok(())
+    }
+
+    fn virtual_cursor_position(&self) -> Option<RArray> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (x, y) = SHARED_VIRTUAL_CURSOR_POSITION.with(|position| *position.borrow())?;
+        let array = ruby.ary_new_capa(2);
+        let _ = array.push(x as f64);
+        let _ = array.push(y as f64);
+        Some(array)
+    }
+
+    fn set_accessibility_enabled(&self, enabled: bool) -> Result<(), Error> {
+        PENDING_ACCESSIBILITY_ENABLED.with(|pending| {
+            *pending.borrow_mut() = Some(enabled);
+        });
+        Ok(())
+    }
+
+    /// Switches the primary window to `:windowed`, `:fullscreen` or `:borderless`.
+    fn set_window_mode(&self, mode: Value) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        request_window_mode(parse_window_mode(&ruby, mode)?);
+        Ok(())
+    }
+
+    /// Fullscreen from windowed, windowed from either fullscreen mode.
+    fn toggle_fullscreen(&self) {
+        let fullscreen = WINDOW_MODE.with(|mode| *mode.borrow() != WindowDisplayMode::Windowed);
+        self.set_fullscreen(!fullscreen);
+    }
+
+    fn window_mode(&self) -> Symbol {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        ruby.to_symbol(WINDOW_MODE.with(|mode| mode.borrow().name()))
+    }
+
+    /// `set_clear_color(r, g, b)` or `set_clear_color(r, g, b, a)`.
+    ///
+    /// Before `run` this sets the initial background; afterwards it applies next frame.
+    fn set_clear_color(&self, args: &[Value]) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let components = args
+            .iter()
+            .map(|value| f64::try_convert(*value))
+            .collect::<Result<Vec<_>, _>>()?;
+        let rgba = parse_clear_color(&ruby, &components)?;
+        CLEAR_COLOR.with(|color| *color.borrow_mut() = Some(rgba));
+
+        if !with_idle_render_app(|render_app| render_app.set_clear_color(rgba)) {
+            PENDING_CLEAR_COLOR.with(|pending| *pending.borrow_mut() = Some(rgba));
+        }
+        Ok(())
+    }
+
+    /// The last background color set, or nil for Bevy's default.
+    fn clear_color(&self) -> Option<Vec<f64>> {
+        CLEAR_COLOR.with(|color| {
+            color
+                .borrow()
+                .map(|rgba| rgba.iter().map(|&value| value as f64).collect())
+        })
+    }
+
+    /// Caps the frame rate; nil or 0 removes the cap. Applies next frame while running.
+    fn set_max_fps(&self, max_fps: Option<f64>) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let max_fps = parse_max_fps(&ruby, max_fps)?;
+        MAX_FPS.with(|current| *current.borrow_mut() = max_fps);
+
+        if !with_idle_render_app(|render_app| render_app.set_max_fps(max_fps)) {
+            PENDING_MAX_FPS.with(|pending| *pending.borrow_mut() = Some(max_fps));
+        }
+        Ok(())
+    }
+
+    /// The frame rate cap, or nil when uncapped.
+    fn max_fps(&self) -> Option<f64> {
+        MAX_FPS.with(|max_fps| max_fps.borrow().map(|fps| fps as f64))
+    }
+
+    /// Before `run` this sets the launch title; afterwards it applies next frame.
+    fn set_window_title(&self, title: String) {
+        if !with_idle_render_app(|render_app| render_app.set_window_title(title.clone())) {
+            PENDING_WINDOW_TITLE.with(|pending| *pending.borrow_mut() = Some(title));
+        }
+    }
+
+    /// Shows or hides the cursor over the primary window; applies next frame.
+    fn set_cursor_visible(&self, visible: bool) {
+        CURSOR_VISIBLE.with(|current| *current.borrow_mut() = visible);
+        PENDING_CURSOR_VISIBLE.with(|pending| *pending.borrow_mut() = Some(visible));
+    }
+
+    fn cursor_visible(&self) -> bool {
+        CURSOR_VISIBLE.with(|visible| *visible.borrow())
+    }
+
+    /// Grabs the cursor with `:none`, `:confined` or `:locked`; applies next frame.
+    ///
+    /// `mouse_delta` reads raw motion, so it keeps reporting while locked.
+    fn set_cursor_grab(&self, mode: Value) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let grab = parse_cursor_grab(&ruby, mode)?;
+        CURSOR_GRAB.with(|current| *current.borrow_mut() = grab);
+        PENDING_CURSOR_GRAB.with(|pending| *pending.borrow_mut() = Some(grab));
+        Ok(())
+    }
+
+    fn cursor_grab(&self) -> Symbol {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        ruby.to_symbol(CURSOR_GRAB.with(|grab| grab.borrow().name()))
+    }
+
+    /// Warnings kept since the last drain as `{category:, message:, count:, first_frame:}`.
+    ///
+    /// Raises `Bevy::WarningError` for the first warning of a `:raise` category.
+    fn drain_warnings(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        WARNING_POLICIES.with(|policies| {
+            let policies = policies.borrow();
+            SHARED_WARNINGS.with(|warnings| {
+                drain_warning_records(&ruby, &mut warnings.borrow_mut(), |category| {
+                    policies.get(category).copied().unwrap_or_default()
+                })
+            })
+        })
+    }
+
+    /// `set_warning_policy(category, :silent | :collect | :raise)`.
+    fn set_warning_policy(&self, category: Value, policy: Value) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let category = symbol_or_string(category)?;
+        let policy = parse_warning_policy(&ruby, policy)?;
+        WARNING_POLICIES.with(|policies| policies.borrow_mut().insert(category.clone(), policy));
+        PENDING_WARNING_POLICIES.with(|pending| pending.borrow_mut().push((category, policy)));
+        Ok(())
+    }
+
+    /// Sets the logical window size; before `run` this sets the launch size.
+    fn set_window_size(&self, width: f64, height: f64) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !width.is_finite() || !height.is_finite() || width <= 0.0 || height <= 0.0 {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("window size must be positive, got {}x{}", width, height),
+            ));
+        }
+        let size = (width as f32, height as f32);
+        WINDOW_SIZE.with(|current| *current.borrow_mut() = size);
+        if !with_idle_render_app(|render_app| render_app.set_window_size(size.0, size.1)) {
+            PENDING_WINDOW_SIZE.with(|pending| *pending.borrow_mut() = Some(size));
+        }
+        Ok(())
+    }
+
+    /// Logical `[width, height]` of the window, or the requested size before the first frame.
+    fn window_size(&self) -> Vec<f64> {
+        let metrics = SHARED_WINDOW_METRICS.with(|metrics| *metrics.borrow());
+        let (width, height) = if metrics.width > 0.0 && metrics.height > 0.0 {
+            (metrics.width, metrics.height)
+        } else {
+            WINDOW_SIZE.with(|size| *size.borrow())
+        };
+        vec![width as f64, height as f64]
+    }
+
+    fn set_fullscreen(&self, fullscreen: bool) {
+        request_window_mode(if fullscreen {
+            WindowDisplayMode::Fullscreen
+        } else {
+            WindowDisplayMode::Windowed
+        });
+    }
+
+    /// True for both exclusive and borderless fullscreen.
+    fn is_fullscreen(&self) -> bool {
+        WINDOW_MODE.with(|mode| *mode.borrow() != WindowDisplayMode::Windowed)
+    }
+
+    /// True while a screen reader or other assistive technology is connected.
+    fn accessibility_active(&self) -> bool {
+        SHARED_ACCESSIBILITY_ACTIVE.with(|active| *active.borrow())
+    }
+
+    /// `{accessible_label:, accessible_role:}` of the node a synced sprite or
+    /// text had at the end of the previous frame, or nil without one.
+    fn accessibility_node(&self, ruby_entity_id: u64) -> Result<Option<RHash>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        SHARED_ACCESSIBILITY_NODES.with(|nodes| {
+            let nodes = nodes.borrow();
+            let Ok(index) = nodes.binary_search_by_key(&ruby_entity_id, |(id, _)| *id) else {
+                return Ok(None);
+            };
+            let data = &nodes[index].1;
+            let hash = ruby.hash_new_capa(2);
+            hash.aset(*keys::ACCESSIBLE_LABEL, data.label.as_str())?;
+            hash.aset(*keys::ACCESSIBLE_ROLE, ruby.to_symbol(data.role.name()))?;
+            Ok(Some(hash))
+        })
+    }
+
+    fn drain_window_events(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let kind_sym = *keys::KIND;
+        let window_id_sym = *keys::WINDOW_ID;
+        let path_sym = *keys::PATH;
+
+        SHARED_WINDOW_EVENTS.with(|window_events| {
+            let mut window_events = window_events.borrow_mut();
+            let result = ruby.ary_new_capa(window_events.len());
+            for event in window_events.drain(..) {
+                let hash = ruby.hash_new_capa(if event.path.is_some() { 3 } else { 2 });
+                hash.aset(kind_sym, event.kind)?;
+                hash.aset(window_id_sym, event.window_id)?;
+                if let Some(path) = event.path {
+                    hash.aset(path_sym, path)?;
+                }
+                result.push(hash)?;
+            }
+            Ok(result)
+        })
+    }
+
+    /// Returns the window size and safe-area insets as `[top, right, bottom, left]`.
+    fn drain_file_drops(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let kind_sym = *keys::KIND;
+        let path_sym = *keys::PATH;
+
+        SHARED_FILE_DROPS.with(|drops| {
+            let mut drops = drops.borrow_mut();
+            let result = ruby.ary_new_capa(drops.len());
+            for drop in drops.drain(..) {
+                let hash = ruby.hash_new_capa(2);
+                hash.aset(kind_sym, drop.kind)?;
+                hash.aset(path_sym, drop.path)?;
+                result.push(hash)?;
+            }
+            Ok(result)
+        })
+    }
+
+    /// `{id:, name:, kind: :connected | :disconnected}` for every gamepad
+    /// connect and disconnect since the last drain, oldest first, so a
+    /// reconnect within one frame shows up as both. Ids match `gamepads_state`.
+    fn drain_gamepad_connection_events(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let id_sym = *keys::ID;
+        let name_sym = *keys::NAME;
+        let kind_sym = *keys::KIND;
+
+        SHARED_GAMEPAD_CONNECTIONS.with(|connections| {
+            let mut connections = connections.borrow_mut();
+            let result = ruby.ary_new_capa(connections.len());
+            for connection in connections.drain(..) {
+                let hash = ruby.hash_new_capa(3);
+                hash.aset(id_sym, connection.id)?;
+                hash.aset(name_sym, connection.name)?;
+                hash.aset(kind_sym, ruby.to_symbol(&connection.kind))?;
+                result.push(hash)?;
+            }
+            Ok(result)
+        })
+    }
+
+    fn window_metrics(&self) -> Result<RHash, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let metrics = SHARED_WINDOW_METRICS.with(|metrics| *metrics.borrow());
+
+        let hash = ruby.hash_new_capa(3);
+        hash.aset(*keys::WIDTH, metrics.width as f64)?;
+        hash.aset(*keys::HEIGHT, metrics.height as f64)?;
+
+        let insets = ruby.ary_new_capa(4);
+        insets.push(metrics.inset_top as f64)?;
+        insets.push(metrics.inset_right as f64)?;
+        insets.push(metrics.inset_bottom as f64)?;
+        insets.push(metrics.inset_left as f64)?;
+        hash.aset(*keys::INSETS, insets)?;
+
+        Ok(hash)
+    }
+
+    /// Returns `[id, x, y, rotation, ...]` for synced sprites, stride 4.
+    fn transforms_snapshot(&self, args: &[Value]) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let group = parse_snapshot_group(&ruby, args)?;
+
+        SHARED_SPRITE_TRANSFORMS.with(|transforms| {
+            let transforms = transforms.borrow();
+            let result = ruby.ary_new_capa(transforms.len() * 4);
+            for sample in snapshot_samples(&transforms, group.as_deref()) {
+                result.push(sample.ruby_entity_id as f64)?;
+                result.push(sample.x as f64)?;
+                result.push(sample.y as f64)?;
+                result.push(sample.rotation as f64)?;
+            }
+            Ok(result)
+        })
+    }
+
+    /// Returns `[id, x, y, ...]` for synced sprites, stride 3.
+    fn positions_snapshot(&self, args: &[Value]) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let group = parse_snapshot_group(&ruby, args)?;
+
+        SHARED_SPRITE_TRANSFORMS.with(|transforms| {
+            let transforms = transforms.borrow();
+            let result = ruby.ary_new_capa(transforms.len() * 3);
+            for sample in snapshot_samples(&transforms, group.as_deref()) {
+                result.push(sample.ruby_entity_id as f64)?;
+                result.push(sample.x as f64)?;
+                result.push(sample.y as f64)?;
+            }
+            Ok(result)
+        })
+    }
+
+    /// `raycast(x1, y1, x2, y2, group: nil)`: `[id, x, y]` of the first synced
+    /// sprite (in `group`, if given) the segment touches from `(x1, y1)`, or nil.
+    fn raycast(&self, args: &[Value]) -> Result<Option<(u64, f64, f64)>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !(4..=5).contains(&args.len()) {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("wrong number of arguments (given {}, expected 4..5)", args.len()),
+            ));
+        }
+        let mut coordinates = [0.0; 4];
+        for (coordinate, value) in coordinates.iter_mut().zip(args) {
+            *coordinate = f64::try_convert(*value)?;
+        }
+        let (start, end) = parse_points(&ruby, coordinates)?;
+        let group = parse_snapshot_group(&ruby, &args[4..])?;
+        Ok(with_sight_grid(group, |grid| grid.raycast(start, end, &[])).map(hit_to_tuple))
+    }
+
+    /// `line_of_sight?(id_a, id_b, blocking_group: nil)`: whether the line
+    /// between two synced sprites' centers is clear of every other sprite
+    /// (in `blocking_group`, if given); nil unless both are synced.
+    fn line_of_sight(&self, args: &[Value]) -> Result<Option<bool>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        if !(2..=3).contains(&args.len()) {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("wrong number of arguments (given {}, expected 2..3)", args.len()),
+            ));
+        }
+        let id_a = u64::try_convert(args[0])?;
+        let id_b = u64::try_convert(args[1])?;
+        let group = match args.get(2) {
+            Some(value) => {
+                let hash: RHash = TryConvert::try_convert(*value)?;
+                match hash.get(*keys::BLOCKING_GROUP) {
+                    Some(group) if !group.is_nil() => Some(symbol_or_string(group)?),
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+
+        let centers = SHARED_SPRITE_TRANSFORMS.with(|transforms| {
+            let transforms = transforms.borrow();
+            let center = |id: u64| {
+                transforms
+                    .binary_search_by_key(&id, |sample| sample.ruby_entity_id)
+                    .ok()
+                    .map(|index| (transforms[index].x, transforms[index].y))
+            };
+            center(id_a).zip(center(id_b))
+        });
+        let Some((a, b)) = centers else {
+            return Ok(None);
+        };
+        Ok(Some(with_sight_grid(group, |grid| grid.raycast(a, b, &[id_a, id_b])).is_none()))
+    }
+
+    fn drain_picking_events(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let kind_sym = *keys::KIND;
+        let target_id_sym = *keys::TARGET_ID;
+        let pointer_id_sym = *keys::POINTER_ID;
+        let position_sym = *keys::POSITION;
+        let button_sym = *keys::BUTTON;
+        let camera_id_sym = *keys::CAMERA_ID;
+        let depth_sym = *keys::DEPTH;
+        let hit_position_sym = *keys::HIT_POSITION;
+        let hit_normal_sym = *keys::HIT_NORMAL;
+        let hit_region_sym = *keys::HIT_REGION;
+        let delta_sym = *keys::DELTA;
+        let distance_sym = *keys::DISTANCE;
+
+        SHARED_PICKING_EVENTS.with(|picking_events| {
+            let mut picking_events = picking_events.borrow_mut();
+            let result = ruby.ary_new_capa(picking_events.len());
+            for event in picking_events.drain(..) {
+                let optional_fields = [
+                    event.button.is_some(),
+                    event.camera_id.is_some(),
+                    event.depth.is_some(),
+                    event.hit_position.is_some(),
+                    event.hit_normal.is_some(),
+                    event.hit_region,
+                    event.delta.is_some(),
+                    event.distance.is_some(),
+                ];
+                let hash = ruby.hash_new_capa(4 + optional_fields.iter().filter(|set| **set).count());
+                hash.aset(kind_sym, event.kind)?;
+                hash.aset(target_id_sym, event.target_id)?;
+                hash.aset(pointer_id_sym, event.pointer_id)?;
+
+                let position = ruby.ary_new_capa(2);
+                position.push(event.pointer_position.0 as f64)?;
+                position.push(event.pointer_position.1 as f64)?;
+                hash.aset(position_sym, position)?;
+
+                if let Some(button) = event.button {
+                    hash.aset(button_sym, button)?;
+                }
+
+                if let Some(camera_id) = event.camera_id {
+                    hash.aset(camera_id_sym, camera_id)?;
+                }
+
+                if let Some(depth) = event.depth {
+                    hash.aset(depth_sym, depth as f64)?;
+                }
+
+                if let Some((x, y, z)) = event.hit_position {
+                    let hit_position = ruby.ary_new_capa(3);
+                    hit_position.push(x as f64)?;
+                    hit_position.push(y as f64)?;
+                    hit_position.push(z as f64)?;
+                    hash.aset(hit_position_sym, hit_position)?;
+                }
+
+                if let Some((x, y, z)) = event.hit_normal {
+                    let hit_normal = ruby.ary_new_capa(3);
+                    hit_normal.push(x as f64)?;
+                    hit_normal.push(y as f64)?;
+                    hit_normal.push(z as f64)?;
+                    hash.aset(hit_normal_sym, hit_normal)?;
+                }
+
+                if event.hit_region {
+                    hash.aset(hit_region_sym, true)?;
+                }
+
+                if let Some((x, y)) = event.delta {
+                    hash.aset(delta_sym, (x as f64, y as f64))?;
+                }
+
+                if let Some((x, y)) = event.distance {
+                    hash.aset(distance_sym, (x as f64, y as f64))?;
+                }
+
+                result.push(hash)?;
+            }
+            Ok(result)
+        })
+    }
+}
+
+pub(crate) fn get_hash_value<T: HashValue>(ruby: &Ruby, hash: &RHash, key: &LazyId) -> Result<Option<T>, Error> {
+    hash_value(ruby, hash, LazyId::get_inner_with(key, ruby))
+}
+
+fn get_required_hash_value<T: HashValue>(ruby: &Ruby, hash: &RHash, key: &LazyId) -> Result<T, Error> {
+    match get_hash_value(ruby, hash, key)? {
+        Some(value) => Ok(value),
+        None => Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("missing keyword: :{}", LazyId::get_inner_with(key, ruby).name()?),
+        )),
+    }
+}
+
+pub(crate) fn symbol_or_string(value: Value) -> Result<String, Error> {
+    if let Ok(sym) = Symbol::try_convert(value) {
+        return Ok(sym.name()?.to_string());
+    }
+    String::try_convert(value)
+}
+
+/// An Integer names a layer; a String or Symbol names a sprite group.
+pub(crate) fn parse_parallax_target(ruby: &Ruby, value: Value) -> Result<ParallaxTarget, Error> {
+    if let Some(layer) = Integer::from_value(value) {
+        return Ok(ParallaxTarget::Layer(layer.to_i32()?));
+    }
+    symbol_or_string(value).map(ParallaxTarget::Group).map_err(|_| {
+        Error::new(
+            ruby.exception_type_error(),
+            format!("parallax target must be a group name or a layer Integer, got {}", value.inspect()),
+        )
+    })
+}
+
+/// Splits `id, x, y, width, height[, layer:, screen_space:]` for the hit region methods.
+pub(crate) fn parse_hit_region_args(
+    ruby: &Ruby,
+    args: &[Value],
+) -> Result<(u64, [f32; 4], Option<i32>, Option<bool>), Error> {
+    let (positional, options) = match args.len() {
+        5 => (args, None),
+        6 => (&args[..5], Some(RHash::try_convert(args[5])?)),
+        given => {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("wrong number of arguments (given {}, expected 5..6)", given),
+            ));
+        }
+    };
+
+    let id = u64::try_convert(positional[0])?;
+    let mut rect = [0.0f32; 4];
+    for (slot, value) in rect.iter_mut().zip(&positional[1..]) {
+        *slot = f64::try_convert(*value)? as f32;
+    }
+    if rect.iter().any(|value| !value.is_finite()) || rect[2] < 0.0 || rect[3] < 0.0 {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!(
+                "hit region needs finite coordinates and a non-negative size, got {:?}",
+                rect
+            ),
+        ));
+    }
+    let (layer, screen_space) = match options {
+        Some(options) => (
+            get_hash_value(ruby, &options, &keys::LAYER)?,
+            get_hash_value(ruby, &options, &keys::SCREEN_SPACE)?,
+        ),
+        None => (None, None),
+    };
+    Ok((id, rect, layer, screen_space))
+}
+
+/// `{scale_min:, scale_max:, period:}`.
+pub(crate) fn parse_pulse(ruby: &Ruby, options: &RHash) -> Result<Behavior, Error> {
+    let scale_min: f64 = get_required_hash_value(ruby, options, &keys::SCALE_MIN)?;
+    let scale_max: f64 = get_required_hash_value(ruby, options, &keys::SCALE_MAX)?;
+    let period: f64 = get_required_hash_value(ruby, options, &keys::PERIOD)?;
+    Ok(Behavior::Pulse {
+        scale_min: scale_min as f32,
+        scale_max: scale_max as f32,
+        period: period as f32,
+    })
+}
+
+/// `{amplitude:, period:, axis: :y}`.
+pub(crate) fn parse_bob(ruby: &Ruby, options: &RHash) -> Result<Behavior, Error> {
+    let amplitude: f64 = get_required_hash_value(ruby, options, &keys::AMPLITUDE)?;
+    let period: f64 = get_required_hash_value(ruby, options, &keys::PERIOD)?;
+    let axis = match get_hash_value::<Value>(ruby, options, &keys::AXIS)? {
+        Some(value) => {
+            let name = symbol_or_string(value)?;
+            BehaviorAxis::from_name(&name).ok_or_else(|| {
+                Error::new(
+                    ruby.exception_arg_error(),
+                    format!("Unknown bob axis: {}", name),
+                )
+            })?
+        }
+        None => BehaviorAxis::Y,
+    };
+    Ok(Behavior::Bob {
+        amplitude: amplitude as f32,
+        period: period as f32,
+        axis,
+    })
+}
+
+/// `(id)` or `(id, {constrain_to:, snap_back:, drag_layer:})`, where
+/// `constrain_to` is `[x, y, width, height]` or nil.
+pub(crate) fn parse_drag_args(ruby: &Ruby, args: &[Value]) -> Result<(u64, DragConfig), Error> {
+    let (id, options) = match args {
+        [id] => (u64::try_convert(*id)?, None),
+        [id, options] => (u64::try_convert(*id)?, Some(RHash::try_convert(*options)?)),
+        _ => {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("wrong number of arguments (given {}, expected 1..2)", args.len()),
+            ));
+        }
+    };
+    let mut config = DragConfig::default();
+    let Some(options) = options else {
+        return Ok((id, config));
+    };
+
+    config.constrain_to = match get_hash_value::<Vec<f64>>(ruby, &options, &keys::CONSTRAIN_TO)?.as_deref() {
+        None => None,
+        Some(&[x, y, width, height])
+            if [x, y, width, height].iter().all(|value| value.is_finite()) && width >= 0.0 && height >= 0.0 =>
+        {
+            Some(DragBounds {
+                x: x as f32,
+                y: y as f32,
+                width: width as f32,
+                height: height as f32,
+            })
+        }
+        Some(rect) => {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!(
+                    "constrain_to must be [x, y, width, height] with a non-negative size, got {:?}",
+                    rect
+                ),
+            ));
+        }
+    };
+    if let Some(snap_back) = get_hash_value(ruby, &options, &keys::SNAP_BACK)? {
+        config.snap_back = snap_back;
+    }
+    if let Some(drag_layer) = get_hash_value(ruby, &options, &keys::DRAG_LAYER)? {
+        config.drag_layer = drag_layer;
+    }
+    Ok((id, config))
+}
+
+fn parse_snapshot_group(ruby: &Ruby, args: &[Value]) -> Result<Option<String>, Error> {
+    match args.first() {
+        Some(value) => {
+            let hash: RHash = TryConvert::try_convert(*value)?;
+            match hash.get(*keys::GROUP) {
+                Some(group) if !group.is_nil() => Ok(Some(symbol_or_string(group)?)),
+                _ => Ok(None),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+fn parse_window_event_filter(ruby: &Ruby, kinds: RArray) -> Result<WindowEventFilter, Error> {
+    let mut filter = WindowEventFilter {
+        close_requested: false,
+        file_drag_and_drop: false,
+    };
+    for kind in kinds {
+        match symbol_or_string(kind)?.as_str() {
+            "close_requested" => filter.close_requested = true,
+            "file_drag_and_drop" => filter.file_drag_and_drop = true,
+            other => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("unknown window event: {}", other),
+                ));
+            }
+        }
+    }
+    Ok(filter)
+}
+
+fn scroll_array(delta: impl Fn(&InputState) -> (f32, f32)) -> RArray {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    let (x, y) = if INPUT_CAPTURED.with(|captured| *captured.borrow()) {
+        (0.0, 0.0)
+    } else {
+        SHARED_INPUT.with(|input| delta(&input.borrow()))
+    };
+    let array = ruby.ary_new_capa(2);
+    let _ = array.push(x);
+    let _ = array.push(y);
+    array
+}
+
+fn parse_window_mode(ruby: &Ruby, value: Value) -> Result<WindowDisplayMode, Error> {
+    let name = symbol_or_string(value)?;
+    WindowDisplayMode::from_name(&name).ok_or_else(|| {
+        Error::new(
+            ruby.exception_arg_error(),
+            format!("unknown window mode: {} (expected windowed, fullscreen or borderless)", name),
+        )
+    })
+}
+
+fn parse_cursor_grab(ruby: &Ruby, value: Value) -> Result<CursorGrab, Error> {
+    let name = symbol_or_string(value)?;
+    CursorGrab::from_name(&name).ok_or_else(|| {
+        Error::new(
+            ruby.exception_arg_error(),
+            format!(
+                "unknown cursor grab mode: {} (expected {})",
+                name,
+                CursorGrab::NAMES.join(", ")
+            ),
+        )
+    })
+}
+
+fn parse_present_mode(ruby: &Ruby, value: Value) -> Result<WindowPresentMode, Error> {
+    let name = symbol_or_string(value)?;
+    WindowPresentMode::from_name(&name).ok_or_else(|| {
+        Error::new(
+            ruby.exception_arg_error(),
+            format!(
+                "unknown present mode: {} (expected {})",
+                name,
+                WindowPresentMode::NAMES.join(", ")
+            ),
+        )
+    })
+}
+
+/// Accepts `[r, g, b]` or `[r, g, b, a]`; alpha defaults to 1.0.
+fn parse_clear_color(ruby: &Ruby, components: &[f64]) -> Result<[f32; 4], Error> {
+    match *components {
+        [r, g, b] => Ok([r as f32, g as f32, b as f32, 1.0]),
+        [r, g, b, a] => Ok([r as f32, g as f32, b as f32, a as f32]),
+        _ => Err(Error::new(
+            ruby.exception_arg_error(),
+            format!(
+                "clear color needs 3 or 4 components (given {})",
+                components.len()
+            ),
+        )),
+    }
+}
 
-        Ok(())
+/// Validates a `max_fps` value; nil and 0 both mean uncapped.
+fn parse_max_fps(ruby: &Ruby, max_fps: Option<f64>) -> Result<Option<f32>, Error> {
+    match max_fps {
+        Some(fps) if !(fps.is_finite() && fps >= 0.0) => Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("max_fps must be a positive number, nil or 0, got {}", fps),
+        )),
+        Some(fps) if fps > 0.0 => Ok(Some(fps as f32)),
+        _ => Ok(None),
     }
+}
 
-    fn clear_meshes(&self) -> Result<(), Error> {
-        PENDING_MESHES.with(|meshes| {
-            meshes.borrow_mut().clear_standalone();
-        });
+fn parse_msaa(ruby: &Ruby, samples: i64) -> Result<WindowMsaa, Error> {
+    u32::try_from(samples)
+        .ok()
+        .and_then(WindowMsaa::from_samples)
+        .ok_or_else(|| {
+            Error::new(
+                ruby.exception_arg_error(),
+                format!(
+                    "msaa must be one of {:?} samples, got {}",
+                    WindowMsaa::SAMPLE_COUNTS,
+                    samples
+                ),
+            )
+        })
+}
 
-        Ok(())
-    }
+/// A rumble request in the order Ruby queued it.
+enum PendingRumble {
+    Command(GamepadRumbleCommand),
+    Pattern(u64, Vec<RumbleSegment>),
+    /// Stops every gamepad the bridge knows of when drained.
+    StopAll,
+}
 
-    fn is_initialized(&self) -> bool {
-        RENDER_STATE.with(|state| state.borrow().is_some())
+fn queue_rumble_pattern(gamepad_id: u64, segments: Vec<RumbleSegment>) {
+    PENDING_GAMEPAD_RUMBLE.with(|rumbles| {
+        rumbles.borrow_mut().push(PendingRumble::Pattern(gamepad_id, segments));
+    });
+}
+
+/// Narrows seconds to `f32`, rejecting durations that are negative, not a
+/// number, or too long for a `Duration` once narrowed (e.g. `1e300`).
+fn rumble_duration(ruby: &Ruby, duration: f64) -> Result<f32, Error> {
+    let secs = duration as f32;
+    if duration < 0.0 || std::time::Duration::try_from_secs_f32(secs).is_err() {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("rumble duration must be a non-negative number of seconds, got {}", duration),
+        ));
     }
+    Ok(secs)
+}
 
-    fn set_camera_position(&self, x: f64, y: f64, z: f64) -> Result<(), Error> {
-        CAMERA_POSITION.with(|p| {
-            *p.borrow_mut() = (x as f32, y as f32, z as f32);
-        });
-        CAMERA_DIRTY.with(|d| {
-            *d.borrow_mut() = true;
+/// Parses a pattern name (String or Symbol) or an Array of `[strong, weak, secs]`.
+fn parse_rumble_pattern(ruby: &Ruby, pattern: Value) -> Result<Vec<RumbleSegment>, Error> {
+    let Ok(segments) = RArray::try_convert(pattern) else {
+        let name = symbol_or_string(pattern)?;
+        return RumbleSegment::named_pattern(&name).ok_or_else(|| {
+            Error::new(
+                ruby.exception_arg_error(),
+                format!(
+                    "unknown rumble pattern {:?}; expected one of {}",
+                    name,
+                    RUMBLE_PATTERN_NAMES.join(", ")
+                ),
+            )
         });
-        Ok(())
+    };
+    if segments.is_empty() {
+        return Err(Error::new(ruby.exception_arg_error(), "rumble pattern is empty"));
     }
-
-    fn get_camera_position(&self) -> RArray {
-        let ruby = Ruby::get().expect("Ruby runtime not available");
-        let (x, y, z) = CAMERA_POSITION.with(|p| *p.borrow());
-        let array = ruby.ary_new_capa(3);
-        let _ = array.push(x as f64);
-        let _ = array.push(y as f64);
-        let _ = array.push(z as f64);
-        array
+    let mut parsed = Vec::with_capacity(segments.len());
+    for segment in segments.into_iter() {
+        let values = Vec::<f64>::try_convert(segment)?;
+        let &[strong, weak, duration] = values.as_slice() else {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("rumble segments are [strong, weak, secs], got {} values", values.len()),
+            ));
+        };
+        if !(strong.is_finite() && weak.is_finite()) {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("rumble strengths must be finite, got {} and {}", strong, weak),
+            ));
+        }
+        parsed.push(RumbleSegment::new(strong as f32, weak as f32, rumble_duration(ruby, duration)?));
     }
+    Ok(parsed)
+}
 
-    fn set_camera_scale(&self, scale: f64) -> Result<(), Error> {
-        CAMERA_SCALE.with(|s| {
-            *s.borrow_mut() = scale as f32;
-        });
-        CAMERA_DIRTY.with(|d| {
-            *d.borrow_mut() = true;
-        });
-        Ok(())
+/// Marks the app as closing and calls the `on_exit` block, if one is still
+/// registered, with `reason`.
+fn call_on_exit(reason: ExitReason) {
+    SHARED_CLOSING.with(|closing| *closing.borrow_mut() = true);
+    let Some(proc) = ON_EXIT_CALLBACK.with(|callback| callback.borrow_mut().take()) else {
+        return;
+    };
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    if let Err(error) = proc.call::<_, Value>((ruby.to_symbol(reason.name()),)) {
+        record_run_error(error);
     }
+}
 
-    fn get_camera_scale(&self) -> f64 {
-        CAMERA_SCALE.with(|s| *s.borrow()) as f64
-    }
+/// Keeps the first error raised during `run`.
+fn record_run_error(error: Error) {
+    RUN_ERROR.with(|run_error| {
+        let mut run_error = run_error.borrow_mut();
+        if run_error.is_none() {
+            *run_error = Some(error);
+        }
+    });
+}
 
-    fn queue_gamepad_rumble(
-        &self,
-        gamepad_id: u64,
-        strong_motor: f64,
-        weak_motor: f64,
-        duration_secs: f64,
-    ) -> Result<(), Error> {
-        let stop = strong_motor <= 0.0 && weak_motor <= 0.0;
-        let command = GamepadRumbleCommand {
-            gamepad_id,
-            strong_motor: strong_motor as f32,
-            weak_motor: weak_motor as f32,
-            duration_secs: duration_secs as f32,
-            stop,
-        };
+/// Resets the per-app thread-locals when `run` ends, however it ends.
+struct AppTeardown;
 
-        PENDING_GAMEPAD_RUMBLE.with(|rumbles| {
-            rumbles.borrow_mut().push(command);
-        });
-        Ok(())
+impl Drop for AppTeardown {
+    fn drop(&mut self) {
+        reset_app_state();
     }
+}
 
-    fn drain_picking_events(&self) -> Result<RArray, Error> {
-        let ruby = Ruby::get().expect("Ruby runtime not available");
-        let kind_sym = ruby.to_symbol("kind");
-        let target_id_sym = ruby.to_symbol("target_id");
-        let pointer_id_sym = ruby.to_symbol("pointer_id");
-        let position_sym = ruby.to_symbol("position");
-        let button_sym = ruby.to_symbol("button");
-        let camera_id_sym = ruby.to_symbol("camera_id");
-        let depth_sym = ruby.to_symbol("depth");
-        let hit_position_sym = ruby.to_symbol("hit_position");
-        let hit_normal_sym = ruby.to_symbol("hit_normal");
-
-        let events = SHARED_PICKING_EVENTS.with(|picking_events| {
-            let mut picking_events = picking_events.borrow_mut();
-            picking_events.drain(..).collect::<Vec<_>>()
-        });
-
-        let result = ruby.ary_new_capa(events.len());
+/// Drops the render app and everything queued for it, so the next
+/// `RenderApp` starts from defaults.
+///
+/// Snapshots of the last frame are kept for inspection after `run` returns
+/// and cleared by `reset_shared_state` when the next app is created.
+fn reset_app_state() {
+    RUBY_CALLBACK.with(|cb| *cb.borrow_mut() = None);
+    // Skipped if still borrowed, which only happens while unwinding out of `run`
+    // before its borrow is released.
+    let render_state = RENDER_STATE.with(|state| state.try_borrow_mut().ok().and_then(|mut state| state.take()));
+    drop(render_state);
+    SHOULD_STOP.with(|stop| *stop.borrow_mut() = false);
+    ON_EXIT_CALLBACK.with(|callback| *callback.borrow_mut() = None);
+    RUN_ERROR.with(|error| *error.borrow_mut() = None);
+
+    PENDING_DETAILED_STATS.with(|pending| *pending.borrow_mut() = None);
+    DETAILED_STATS.with(|enabled| *enabled.borrow_mut() = false);
+    PENDING_JOURNAL.with(|pending| *pending.borrow_mut() = None);
+    PENDING_JOURNAL_REPLAY.with(|pending| *pending.borrow_mut() = None);
+    PENDING_SPRITES.with(|pending| *pending.borrow_mut() = SpriteSync::new());
+    PENDING_TEXTS.with(|pending| *pending.borrow_mut() = TextSync::new());
+    PENDING_MESHES.with(|pending| *pending.borrow_mut() = MeshSync::new());
+    MESH_SHAPES.with(|shapes| shapes.borrow_mut().clear());
+    PENDING_BEHAVIORS.with(|pending| *pending.borrow_mut() = EntityBehaviors::new());
+    PENDING_LODS.with(|pending| *pending.borrow_mut() = EntityLods::new());
+    PENDING_PARALLAX.with(|pending| *pending.borrow_mut() = Parallax::new());
+    PENDING_PIXEL_SNAP.with(|pending| *pending.borrow_mut() = PixelSnap::new());
+    PENDING_HIT_REGIONS.with(|pending| *pending.borrow_mut() = HitRegions::new());
+    PENDING_CLIPS.with(|pending| *pending.borrow_mut() = ClipPlayer::new());
+    PENDING_DRAG.with(|pending| *pending.borrow_mut() = DragGestures::new());
+    PENDING_FLOATING_TEXTS.with(|pending| *pending.borrow_mut() = FloatingTexts::new());
+    PENDING_GROUP_THROTTLE.with(|pending| *pending.borrow_mut() = GroupThrottle::new());
+    PENDING_SLEEP.with(|pending| *pending.borrow_mut() = MotionSleep::new());
+    PENDING_REWIND_OPERATIONS.with(|pending| pending.borrow_mut().clear());
+    PENDING_RECORDED_CHECKSUMS.with(|pending| *pending.borrow_mut() = None);
+    PENDING_GAMEPAD_RUMBLE.with(|pending| pending.borrow_mut().clear());
+    PENDING_AUDIO.with(|pending| pending.borrow_mut().clear());
+    PENDING_VIRTUAL_CURSOR.with(|pending| pending.borrow_mut().clear());
+    PENDING_ACCESSIBILITY_ENABLED.with(|pending| *pending.borrow_mut() = None);
+    PENDING_WINDOW_MODE.with(|pending| *pending.borrow_mut() = None);
+    PENDING_CLEAR_COLOR.with(|pending| *pending.borrow_mut() = None);
+    PENDING_MAX_FPS.with(|pending| *pending.borrow_mut() = None);
+    PENDING_WINDOW_TITLE.with(|pending| *pending.borrow_mut() = None);
+    PENDING_WINDOW_SIZE.with(|pending| *pending.borrow_mut() = None);
+    PENDING_CURSOR_VISIBLE.with(|pending| *pending.borrow_mut() = None);
+    PENDING_CURSOR_GRAB.with(|pending| *pending.borrow_mut() = None);
+    PENDING_TEXT_INPUT_ENABLED.with(|pending| *pending.borrow_mut() = None);
+    PENDING_WARNING_POLICIES.with(|pending| pending.borrow_mut().clear());
+
+    CAMERA_POSITION.with(|position| *position.borrow_mut() = (0.0, 0.0, 0.0));
+    CAMERA_SCALE.with(|scale| *scale.borrow_mut() = 1.0);
+    CAMERA_ZOOM_LIMITS.with(|limits| *limits.borrow_mut() = (0.1, 10.0));
+    CAMERA_ROTATION.with(|rotation| *rotation.borrow_mut() = 0.0);
+    CAMERA_DIRTY.with(|dirty| *dirty.borrow_mut() = false);
+    PENDING_CAMERA_SHAKE.with(|shake| *shake.borrow_mut() = None);
+    CAMERA_FOLLOW.with(|follow| *follow.borrow_mut() = None);
+    PENDING_CAMERA_FOLLOW.with(|follow| *follow.borrow_mut() = None);
+    SHARED_APPLIED_FRAMES.with(|frames| *frames.borrow_mut() = (None, None));
+    INPUT_CAPTURED.with(|captured| *captured.borrow_mut() = false);
+    ACTION_MAP.with(|map| map.borrow_mut().clear());
+    CURSOR_VISIBLE.with(|visible| *visible.borrow_mut() = true);
+    CURSOR_GRAB.with(|grab| *grab.borrow_mut() = CursorGrab::None);
+    TEXT_INPUT_ENABLED.with(|enabled| *enabled.borrow_mut() = false);
+    WARNING_POLICIES.with(|policies| policies.borrow_mut().clear());
+}
 
-        for event in events {
-            let hash = ruby.hash_new();
-            hash.aset(kind_sym, event.kind)?;
-            hash.aset(target_id_sym, event.target_id)?;
-            hash.aset(pointer_id_sym, event.pointer_id)?;
+/// Clears what the previous app reported, before a new app's first frame.
+fn reset_shared_state() {
+    SHARED_CLOSING.with(|closing| *closing.borrow_mut() = false);
+    SHARED_GROUP_SCHEDULE.with(|schedule| *schedule.borrow_mut() = (GroupSchedule::new(), 0));
+    SHARED_SLEEPING_COUNT.with(|count| *count.borrow_mut() = 0);
+    SHARED_INPUT.with(|input| *input.borrow_mut() = InputState::new());
+    SHARED_FRAME_TIME.with(|time| *time.borrow_mut() = (FIRST_FRAME_DELTA_SECS, 0.0));
+    SHARED_FRAME_COUNT.with(|count| *count.borrow_mut() = 0);
+    SHARED_FRAME_PIXELS.with(|pixels| *pixels.borrow_mut() = None);
+    SHARED_JOURNAL_REPLAYING.with(|replaying| *replaying.borrow_mut() = false);
+    SHARED_SYSTEM_TIMINGS.with(|timings| *timings.borrow_mut() = (BTreeMap::new(), BTreeMap::new()));
+    SHARED_LOD_LEVELS.with(|levels| levels.borrow_mut().clear());
+    SHARED_FINISHED_CLIPS.with(|finished| finished.borrow_mut().clear());
+    SHARED_DRAG_EVENTS.with(|events| events.borrow_mut().clear());
+    SHARED_CLIP_SKIPPED_TRACKS.with(|skipped| *skipped.borrow_mut() = 0);
+    SHARED_REWIND_STATS.with(|stats| *stats.borrow_mut() = None);
+    SHARED_CHECKSUMS.with(|checksums| checksums.borrow_mut().clear());
+    SHARED_CHECKSUM_DIVERGENCE.with(|divergence| *divergence.borrow_mut() = None);
+    SHARED_PICKING_EVENTS.with(|events| events.borrow_mut().clear());
+    SHARED_WINDOW_EVENTS.with(|events| events.borrow_mut().clear());
+    SHARED_FILE_DROPS.with(|drops| drops.borrow_mut().clear());
+    SHARED_GAMEPAD_CONNECTIONS.with(|connections| connections.borrow_mut().clear());
+    SHARED_ACTIVE_RUMBLE.with(|active| active.borrow_mut().clear());
+    SHARED_WINDOW_METRICS.with(|metrics| *metrics.borrow_mut() = WindowMetrics::default());
+    SHARED_SPRITE_TRANSFORMS.with(|transforms| transforms.borrow_mut().clear());
+    SHARED_DRAW_ORDER.with(|shared| *shared.borrow_mut() = (None, HashMap::new()));
+    SHARED_SIGHT_GRIDS.with(|grids| grids.borrow_mut().clear());
+    SHARED_VIRTUAL_CURSOR_POSITION.with(|position| *position.borrow_mut() = None);
+    SHARED_ACCESSIBILITY_ACTIVE.with(|active| *active.borrow_mut() = false);
+    SHARED_ACCESSIBILITY_NODES.with(|nodes| nodes.borrow_mut().clear());
+    SHARED_WARNINGS.with(|warnings| warnings.borrow_mut().clear());
+}
 
-            let position = ruby.ary_new_capa(2);
-            position.push(event.pointer_position.0 as f64)?;
-            position.push(event.pointer_position.1 as f64)?;
-            hash.aset(position_sym, position)?;
+/// The current camera scale with the stored zoom limits.
+fn camera_zoom() -> CameraZoom {
+    let (min, max) = CAMERA_ZOOM_LIMITS.with(|limits| *limits.borrow());
+    CameraZoom::new(CAMERA_SCALE.with(|scale| *scale.borrow())).with_limits(min, max)
+}
 
-            if let Some(button) = event.button {
-                hash.aset(button_sym, button)?;
-            }
+/// Clears the follow target, both here and in the bridge on the next frame.
+fn stop_camera_follow() {
+    let following = CAMERA_FOLLOW.with(|follow| follow.borrow_mut().take()).is_some();
+    let pending = PENDING_CAMERA_FOLLOW.with(|pending| pending.borrow().is_some());
+    if following || pending {
+        PENDING_CAMERA_FOLLOW.with(|pending| *pending.borrow_mut() = Some(None));
+    }
+}
 
-            if let Some(camera_id) = event.camera_id {
-                hash.aset(camera_id_sym, camera_id)?;
-            }
+fn store_camera_scale(scale: f32) {
+    CAMERA_SCALE.with(|s| *s.borrow_mut() = scale);
+    CAMERA_DIRTY.with(|d| *d.borrow_mut() = true);
+}
 
-            if let Some(depth) = event.depth {
-                hash.aset(depth_sym, depth as f64)?;
+/// Runs `f` on the render app while it is not running; returns false otherwise.
+///
+/// During `run` the render state stays borrowed, so changes have to be queued
+/// for the bridge instead.
+fn with_idle_render_app(f: impl FnOnce(&mut RenderApp)) -> bool {
+    RENDER_STATE.with(|state| match state.try_borrow_mut() {
+        Ok(mut state) => match state.as_mut() {
+            Some(state) => {
+                f(&mut state.render_app);
+                true
             }
+            None => false,
+        },
+        Err(_) => false,
+    })
+}
 
-            if let Some((x, y, z)) = event.hit_position {
-                let hit_position = ruby.ary_new_capa(3);
-                hit_position.push(x as f64)?;
-                hit_position.push(y as f64)?;
-                hit_position.push(z as f64)?;
-                hash.aset(hit_position_sym, hit_position)?;
-            }
+fn request_window_mode(mode: WindowDisplayMode) {
+    WINDOW_MODE.with(|current| *current.borrow_mut() = mode);
+    PENDING_WINDOW_MODE.with(|pending| *pending.borrow_mut() = Some(mode));
+}
 
-            if let Some((x, y, z)) = event.hit_normal {
-                let hit_normal = ruby.ary_new_capa(3);
-                hit_normal.push(x as f64)?;
-                hit_normal.push(y as f64)?;
-                hit_normal.push(z as f64)?;
-                hash.aset(hit_normal_sym, hit_normal)?;
-            }
+fn push_rewind_operation(op: RewindOperation) {
+    PENDING_REWIND_OPERATIONS.with(|ops| ops.borrow_mut().push(op));
+}
 
-            result.push(hash)?;
-        }
+/// Runs `f` on the blocker grid of `group`, building it from the latest
+/// sprite snapshot on first use.
+fn with_sight_grid<R>(group: Option<String>, f: impl FnOnce(&SightGrid) -> R) -> R {
+    SHARED_SIGHT_GRIDS.with(|grids| {
+        let mut grids = grids.borrow_mut();
+        let grid = grids.entry(group).or_insert_with_key(|group| {
+            let mut grid = SightGrid::new(DEFAULT_SIGHT_CELL_SIZE);
+            SHARED_SPRITE_TRANSFORMS.with(|transforms| {
+                for sample in snapshot_samples(&transforms.borrow(), group.as_deref()) {
+                    grid.insert(sample.ruby_entity_id, sample.bounds());
+                }
+            });
+            grid
+        });
+        f(grid)
+    })
+}
 
-        Ok(result)
-    }
+fn snapshot_samples<'a>(
+    samples: &'a [SpriteTransformSample],
+    group: Option<&'a str>,
+) -> impl Iterator<Item = &'a SpriteTransformSample> {
+    samples
+        .iter()
+        .filter(move |sample| group.is_none_or(|group| sample.group.as_deref() == Some(group)))
 }
 
-fn get_hash_value<T: TryConvert>(ruby: &Ruby, hash: &RHash, key: &str) -> Result<Option<T>, Error> {
-    let sym = ruby.to_symbol(key);
-    match hash.get(sym) {
-        Some(val) => {
-            if val.is_nil() {
-                Ok(None)
-            } else {
-                Ok(Some(TryConvert::try_convert(val)?))
+fn parse_keyframe_clip(ruby: &Ruby, hash: &RHash) -> Result<KeyframeClip, Error> {
+    let speed: Option<f64> = get_hash_value(ruby, hash, &keys::SPEED)?;
+    let track_hashes: RArray = get_required_hash_value(ruby, hash, &keys::TRACKS)?;
+
+    let mut tracks = Vec::with_capacity(track_hashes.len());
+    for track_value in track_hashes {
+        let track: RHash = TryConvert::try_convert(track_value)?;
+        let ruby_entity_id: u64 = get_required_hash_value(ruby, &track, &keys::ENTITY_ID)?;
+        let property_name = symbol_or_string(get_required_hash_value(ruby, &track, &keys::PROPERTY)?)?;
+        let property = ClipProperty::from_name(&property_name).ok_or_else(|| {
+            Error::new(
+                ruby.exception_arg_error(),
+                format!("Unknown clip property: {}", property_name),
+            )
+        })?;
+        let easing = match get_hash_value::<Value>(ruby, &track, &keys::EASING)? {
+            Some(value) => {
+                let name = symbol_or_string(value)?;
+                ClipEasing::from_name(&name).ok_or_else(|| {
+                    Error::new(
+                        ruby.exception_arg_error(),
+                        format!(
+                            "Unknown clip easing: {} (expected one of {})",
+                            name,
+                            ClipEasing::names().join(", ")
+                        ),
+                    )
+                })?
             }
-        }
-        None => Ok(None),
+            None => ClipEasing::default(),
+        };
+        let keyframes: Vec<(f64, f64)> = get_required_hash_value(ruby, &track, &keys::KEYFRAMES)?;
+
+        tracks.push(ClipTrack::new(
+            ruby_entity_id,
+            property,
+            easing,
+            keyframes
+                .into_iter()
+                .map(|(time, value)| (time as f32, value as f32))
+                .collect(),
+        ));
     }
+
+    Ok(KeyframeClip::new(tracks, speed.unwrap_or(1.0) as f32))
+}
+
+/// Queues a `Bevy::Audio` playback change for the render app.
+pub(crate) fn queue_audio(command: AudioCommand) {
+    PENDING_AUDIO.with(|commands| commands.borrow_mut().push(command));
 }
 
-fn parse_sprite_data(ruby: &Ruby, hash: &RHash) -> Result<SpriteData, Error> {
-    let color_r: Option<f64> = get_hash_value(ruby, hash, "color_r")?;
-    let color_g: Option<f64> = get_hash_value(ruby, hash, "color_g")?;
-    let color_b: Option<f64> = get_hash_value(ruby, hash, "color_b")?;
-    let color_a: Option<f64> = get_hash_value(ruby, hash, "color_a")?;
-    let flip_x: Option<bool> = get_hash_value(ruby, hash, "flip_x")?;
-    let flip_y: Option<bool> = get_hash_value(ruby, hash, "flip_y")?;
-    let anchor_x: Option<f64> = get_hash_value(ruby, hash, "anchor_x")?;
-    let anchor_y: Option<f64> = get_hash_value(ruby, hash, "anchor_y")?;
-    let custom_size_x: Option<f64> = get_hash_value(ruby, hash, "custom_size_x")?;
-    let custom_size_y: Option<f64> = get_hash_value(ruby, hash, "custom_size_y")?;
+fn queue_behavior(ruby_entity_id: u64, behavior: Behavior) {
+    PENDING_BEHAVIORS.with(|behaviors| {
+        behaviors
+            .borrow_mut()
+            .add_standalone(ruby_entity_id, behavior);
+    });
+}
+
+pub(crate) fn parse_sprite_data(ruby: &Ruby, hash: &RHash) -> Result<SpriteData, Error> {
+    let color_r: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_R)?;
+    let color_g: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_G)?;
+    let color_b: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_B)?;
+    let color_a: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_A)?;
+    let flip_x: Option<bool> = get_hash_value(ruby, hash, &keys::FLIP_X)?;
+    let flip_y: Option<bool> = get_hash_value(ruby, hash, &keys::FLIP_Y)?;
+    let anchor_x: Option<f64> = get_hash_value(ruby, hash, &keys::ANCHOR_X)?;
+    let anchor_y: Option<f64> = get_hash_value(ruby, hash, &keys::ANCHOR_Y)?;
+    let custom_size_x: Option<f64> = get_hash_value(ruby, hash, &keys::CUSTOM_SIZE_X)?;
+    let custom_size_y: Option<f64> = get_hash_value(ruby, hash, &keys::CUSTOM_SIZE_Y)?;
+    let group: Option<Value> = get_hash_value(ruby, hash, &keys::GROUP)?;
+    let texture_path: Option<String> = get_hash_value(ruby, hash, &keys::TEXTURE_PATH)?;
+    let mesh_mode: Option<bool> = get_hash_value(ruby, hash, &keys::MESH_MODE)?;
+    let z_index: Option<i32> = get_hash_value(ruby, hash, &keys::Z_INDEX)?;
+    let atlas_rect = match get_hash_value::<Vec<f64>>(ruby, hash, &keys::ATLAS_RECT)?.as_deref() {
+        None => None,
+        Some(&[x, y, width, height]) if width > 0.0 && height > 0.0 => Some((x, y, width, height)),
+        Some(rect) => {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("atlas_rect must be [x, y, width, height] with a positive size, got {:?}", rect),
+            ));
+        }
+    };
 
     let has_custom_size = custom_size_x.is_some() || custom_size_y.is_some();
 
@@ -571,17 +3356,101 @@ fn parse_sprite_data(ruby: &Ruby, hash: &RHash) -> Result<SpriteData, Error> {
         has_custom_size,
         custom_size_x: custom_size_x.unwrap_or(0.0) as f32,
         custom_size_y: custom_size_y.unwrap_or(0.0) as f32,
+        group: group.map(symbol_or_string).transpose()?,
+        accessibility: parse_accessibility(ruby, hash, AccessibleRole::Image, "")?,
+        texture_path,
+        mesh_mode: mesh_mode.unwrap_or(false),
+        jelly: parse_jelly_config(ruby, hash)?,
+        z_index,
+        atlas_rect: atlas_rect.map(|(x, y, width, height)| (x as f32, y as f32, width as f32, height as f32)),
+    })
+}
+
+/// Reads `subdivisions:`, `stiffness:` and `damping:`, defaulting each one.
+pub(crate) fn parse_jelly_config(ruby: &Ruby, hash: &RHash) -> Result<JellyConfig, Error> {
+    let defaults = JellyConfig::default();
+    let subdivisions: Option<u32> = get_hash_value(ruby, hash, &keys::SUBDIVISIONS)?;
+    let stiffness: Option<f64> = get_hash_value(ruby, hash, &keys::STIFFNESS)?;
+    let damping: Option<f64> = get_hash_value(ruby, hash, &keys::DAMPING)?;
+    Ok(JellyConfig {
+        subdivisions: subdivisions.unwrap_or(defaults.subdivisions),
+        stiffness: stiffness.map_or(defaults.stiffness, |stiffness| stiffness as f32),
+        damping: damping.map_or(defaults.damping, |damping| damping as f32),
     })
 }
 
-fn parse_transform_data(ruby: &Ruby, hash: &RHash) -> Result<TransformData, Error> {
-    let x: Option<f64> = get_hash_value(ruby, hash, "x")?;
-    let y: Option<f64> = get_hash_value(ruby, hash, "y")?;
-    let z: Option<f64> = get_hash_value(ruby, hash, "z")?;
-    let rotation: Option<f64> = get_hash_value(ruby, hash, "rotation")?;
-    let scale_x: Option<f64> = get_hash_value(ruby, hash, "scale_x")?;
-    let scale_y: Option<f64> = get_hash_value(ruby, hash, "scale_y")?;
-    let scale_z: Option<f64> = get_hash_value(ruby, hash, "scale_z")?;
+/// Reads `[{max_scale:, sprite:, hide:}, ...]`; bounded levels must ascend and
+/// only the last level may omit `max_scale`.
+pub(crate) fn parse_lod_levels(ruby: &Ruby, levels: RArray) -> Result<Vec<LodLevel>, Error> {
+    let mut parsed: Vec<LodLevel> = Vec::with_capacity(levels.len());
+    for value in levels.into_iter() {
+        let hash: RHash = TryConvert::try_convert(value)?;
+        let max_scale = get_hash_value::<f64>(ruby, &hash, &keys::MAX_SCALE)?.map(|scale| scale as f32);
+        if let Some(previous) = parsed.last() {
+            let Some(previous_max) = previous.max_scale else {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    "only the last LOD level may omit max_scale",
+                ));
+            };
+            if max_scale.is_some_and(|max_scale| max_scale <= previous_max) {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    "LOD levels must be ordered by ascending max_scale",
+                ));
+            }
+        }
+        let sprite = match get_hash_value::<RHash>(ruby, &hash, &keys::SPRITE)? {
+            Some(sprite) => Some(parse_sprite_data(ruby, &sprite)?),
+            None => None,
+        };
+        parsed.push(LodLevel {
+            max_scale,
+            sprite,
+            hide: get_hash_value(ruby, &hash, &keys::HIDE)?.unwrap_or(false),
+        });
+    }
+    Ok(parsed)
+}
+
+/// Reads `accessible_label` / `accessible_role`; either key opts the entity in.
+fn parse_accessibility(
+    ruby: &Ruby,
+    hash: &RHash,
+    default_role: AccessibleRole,
+    default_label: &str,
+) -> Result<Option<AccessibilityData>, Error> {
+    let label: Option<String> = get_hash_value(ruby, hash, &keys::ACCESSIBLE_LABEL)?;
+    let role = match get_hash_value::<Value>(ruby, hash, &keys::ACCESSIBLE_ROLE)? {
+        Some(value) if !value.is_nil() => {
+            let name = symbol_or_string(value)?;
+            Some(AccessibleRole::from_name(&name).ok_or_else(|| {
+                Error::new(
+                    ruby.exception_arg_error(),
+                    format!("unknown accessible role: {}", name),
+                )
+            })?)
+        }
+        _ => None,
+    };
+
+    if label.is_none() && role.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(AccessibilityData::new(
+        label.unwrap_or_else(|| default_label.to_string()),
+        role.unwrap_or(default_role),
+    )))
+}
+
+pub(crate) fn parse_transform_data(ruby: &Ruby, hash: &RHash) -> Result<TransformData, Error> {
+    let x: Option<f64> = get_hash_value(ruby, hash, &keys::X)?;
+    let y: Option<f64> = get_hash_value(ruby, hash, &keys::Y)?;
+    let z: Option<f64> = get_hash_value(ruby, hash, &keys::Z)?;
+    let rotation: Option<f64> = get_hash_value(ruby, hash, &keys::ROTATION)?;
+    let scale_x: Option<f64> = get_hash_value(ruby, hash, &keys::SCALE_X)?;
+    let scale_y: Option<f64> = get_hash_value(ruby, hash, &keys::SCALE_Y)?;
+    let scale_z: Option<f64> = get_hash_value(ruby, hash, &keys::SCALE_Z)?;
 
     let angle = rotation.unwrap_or(0.0) as f32;
     let half_angle = angle / 2.0;
@@ -601,31 +3470,101 @@ fn parse_transform_data(ruby: &Ruby, hash: &RHash) -> Result<TransformData, Erro
     })
 }
 
-fn parse_text_data(ruby: &Ruby, hash: &RHash) -> Result<TextData, Error> {
-    let content: Option<String> = get_hash_value(ruby, hash, "content")?;
-    let font_size: Option<f64> = get_hash_value(ruby, hash, "font_size")?;
-    let color_r: Option<f64> = get_hash_value(ruby, hash, "color_r")?;
-    let color_g: Option<f64> = get_hash_value(ruby, hash, "color_g")?;
-    let color_b: Option<f64> = get_hash_value(ruby, hash, "color_b")?;
-    let color_a: Option<f64> = get_hash_value(ruby, hash, "color_a")?;
+pub(crate) fn parse_text_data(ruby: &Ruby, hash: &RHash) -> Result<TextData, Error> {
+    let content: Option<String> = get_hash_value(ruby, hash, &keys::CONTENT)?;
+    let font_size: Option<f64> = get_hash_value(ruby, hash, &keys::FONT_SIZE)?;
+    let color_r: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_R)?;
+    let color_g: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_G)?;
+    let color_b: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_B)?;
+    let color_a: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_A)?;
+
+    let content = content.unwrap_or_default();
+    let accessibility = parse_accessibility(ruby, hash, AccessibleRole::Label, &content)?;
+    let glow = parse_text_glow(ruby, hash)?;
+    let blend = match get_hash_value::<Value>(ruby, hash, &keys::BLEND)? {
+        Some(value) if !value.is_nil() => {
+            let name = symbol_or_string(value)?;
+            TextBlend::from_name(&name).ok_or_else(|| {
+                Error::new(
+                    ruby.exception_arg_error(),
+                    format!("unknown text blend: {}", name),
+                )
+            })?
+        }
+        _ => TextBlend::Alpha,
+    };
+    let justify = match get_hash_value::<Value>(ruby, hash, &keys::JUSTIFY)? {
+        Some(value) if !value.is_nil() => {
+            let name = symbol_or_string(value)?;
+            TextJustify::from_name(&name).ok_or_else(|| {
+                Error::new(
+                    ruby.exception_arg_error(),
+                    format!("unknown text justify: {}", name),
+                )
+            })?
+        }
+        _ => TextJustify::Left,
+    };
+    let linebreak = match get_hash_value::<Value>(ruby, hash, &keys::LINEBREAK)? {
+        Some(value) if !value.is_nil() => {
+            let name = symbol_or_string(value)?;
+            TextLineBreak::from_name(&name).ok_or_else(|| {
+                Error::new(
+                    ruby.exception_arg_error(),
+                    format!("unknown text linebreak: {}", name),
+                )
+            })?
+        }
+        _ => TextLineBreak::Word,
+    };
 
     Ok(TextData {
-        content: content.unwrap_or_default(),
+        content,
         font_size: font_size.unwrap_or(24.0) as f32,
         color_r: color_r.unwrap_or(1.0) as f32,
         color_g: color_g.unwrap_or(1.0) as f32,
         color_b: color_b.unwrap_or(1.0) as f32,
         color_a: color_a.unwrap_or(1.0) as f32,
+        accessibility,
+        glow,
+        blend,
+        justify,
+        linebreak,
     })
 }
 
-fn parse_text_transform_data(ruby: &Ruby, hash: &RHash) -> Result<TextTransformData, Error> {
-    let x: Option<f64> = get_hash_value(ruby, hash, "x")?;
-    let y: Option<f64> = get_hash_value(ruby, hash, "y")?;
-    let z: Option<f64> = get_hash_value(ruby, hash, "z")?;
-    let scale_x: Option<f64> = get_hash_value(ruby, hash, "scale_x")?;
-    let scale_y: Option<f64> = get_hash_value(ruby, hash, "scale_y")?;
-    let scale_z: Option<f64> = get_hash_value(ruby, hash, "scale_z")?;
+fn parse_text_glow(ruby: &Ruby, hash: &RHash) -> Result<Option<TextGlow>, Error> {
+    let Some(radius) = get_hash_value::<f64>(ruby, hash, &keys::GLOW_RADIUS)? else {
+        return Ok(None);
+    };
+    let intensity: f64 = get_hash_value(ruby, hash, &keys::GLOW_INTENSITY)?.unwrap_or(1.0);
+    if !radius.is_finite() || radius < 0.0 || !intensity.is_finite() {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            "glow radius must be a non-negative number and intensity finite",
+        ));
+    }
+
+    let channel = |key: &LazyId| -> Result<f32, Error> {
+        Ok(get_hash_value::<f64>(ruby, hash, key)?.unwrap_or(1.0) as f32)
+    };
+    Ok(Some(TextGlow {
+        color_r: channel(&keys::GLOW_R)?,
+        color_g: channel(&keys::GLOW_G)?,
+        color_b: channel(&keys::GLOW_B)?,
+        color_a: channel(&keys::GLOW_A)?,
+        radius: radius as f32,
+        intensity: intensity as f32,
+    }))
+}
+
+pub(crate) fn parse_text_transform_data(ruby: &Ruby, hash: &RHash) -> Result<TextTransformData, Error> {
+    let x: Option<f64> = get_hash_value(ruby, hash, &keys::X)?;
+    let y: Option<f64> = get_hash_value(ruby, hash, &keys::Y)?;
+    let z: Option<f64> = get_hash_value(ruby, hash, &keys::Z)?;
+    let scale_x: Option<f64> = get_hash_value(ruby, hash, &keys::SCALE_X)?;
+    let scale_y: Option<f64> = get_hash_value(ruby, hash, &keys::SCALE_Y)?;
+    let scale_z: Option<f64> = get_hash_value(ruby, hash, &keys::SCALE_Z)?;
 
     Ok(TextTransformData {
         translation_x: x.unwrap_or(0.0) as f32,
@@ -637,31 +3576,69 @@ fn parse_text_transform_data(ruby: &Ruby, hash: &RHash) -> Result<TextTransformD
     })
 }
 
-fn parse_mesh_data(ruby: &Ruby, hash: &RHash) -> Result<MeshData, Error> {
-    let shape_type_val: Option<i64> = get_hash_value(ruby, hash, "shape_type")?;
+pub(crate) fn parse_mesh_data(ruby: &Ruby, hash: &RHash) -> Result<MeshData, Error> {
+    let shape_type_val: Option<i64> = get_hash_value(ruby, hash, &keys::SHAPE_TYPE)?;
     let shape_type = match shape_type_val.unwrap_or(0) {
         0 => ShapeType::Rectangle,
         1 => ShapeType::Circle,
         2 => ShapeType::RegularPolygon,
         3 => ShapeType::Line,
         4 => ShapeType::Ellipse,
+        5 => ShapeType::Triangle,
         _ => ShapeType::Rectangle,
     };
 
-    let color_r: Option<f64> = get_hash_value(ruby, hash, "color_r")?;
-    let color_g: Option<f64> = get_hash_value(ruby, hash, "color_g")?;
-    let color_b: Option<f64> = get_hash_value(ruby, hash, "color_b")?;
-    let color_a: Option<f64> = get_hash_value(ruby, hash, "color_a")?;
-    let width: Option<f64> = get_hash_value(ruby, hash, "width")?;
-    let height: Option<f64> = get_hash_value(ruby, hash, "height")?;
-    let radius: Option<f64> = get_hash_value(ruby, hash, "radius")?;
-    let sides: Option<i64> = get_hash_value(ruby, hash, "sides")?;
-    let line_start_x: Option<f64> = get_hash_value(ruby, hash, "line_start_x")?;
-    let line_start_y: Option<f64> = get_hash_value(ruby, hash, "line_start_y")?;
-    let line_end_x: Option<f64> = get_hash_value(ruby, hash, "line_end_x")?;
-    let line_end_y: Option<f64> = get_hash_value(ruby, hash, "line_end_y")?;
-    let thickness: Option<f64> = get_hash_value(ruby, hash, "thickness")?;
-    let fill: Option<bool> = get_hash_value(ruby, hash, "fill")?;
+    let color_r: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_R)?;
+    let color_g: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_G)?;
+    let color_b: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_B)?;
+    let color_a: Option<f64> = get_hash_value(ruby, hash, &keys::COLOR_A)?;
+    let width: Option<f64> = get_hash_value(ruby, hash, &keys::WIDTH)?;
+    let height: Option<f64> = get_hash_value(ruby, hash, &keys::HEIGHT)?;
+    let radius: Option<f64> = get_hash_value(ruby, hash, &keys::RADIUS)?;
+    let sides: Option<i64> = get_hash_value(ruby, hash, &keys::SIDES)?;
+    let line_start_x: Option<f64> = get_hash_value(ruby, hash, &keys::LINE_START_X)?;
+    let line_start_y: Option<f64> = get_hash_value(ruby, hash, &keys::LINE_START_Y)?;
+    let line_end_x: Option<f64> = get_hash_value(ruby, hash, &keys::LINE_END_X)?;
+    let line_end_y: Option<f64> = get_hash_value(ruby, hash, &keys::LINE_END_Y)?;
+    let thickness: Option<f64> = get_hash_value(ruby, hash, &keys::THICKNESS)?;
+    let fill: Option<bool> = get_hash_value(ruby, hash, &keys::FILL)?;
+    let line_cap = match get_hash_value::<Value>(ruby, hash, &keys::LINE_CAP)? {
+        Some(value) if !value.is_nil() => {
+            let name = symbol_or_string(value)?;
+            LineCapStyle::from_name(&name).ok_or_else(|| {
+                Error::new(ruby.exception_arg_error(), format!("unknown line cap: {}", name))
+            })?
+        }
+        _ => LineCapStyle::default(),
+    };
+    let line_join = match get_hash_value::<Value>(ruby, hash, &keys::LINE_JOIN)? {
+        Some(value) if !value.is_nil() => {
+            let name = symbol_or_string(value)?;
+            LineJoinStyle::from_name(&name).ok_or_else(|| {
+                Error::new(ruby.exception_arg_error(), format!("unknown line join: {}", name))
+            })?
+        }
+        _ => LineJoinStyle::default(),
+    };
+    let gradient_color = match get_hash_value::<Vec<f64>>(ruby, hash, &keys::GRADIENT_COLOR)?.as_deref() {
+        None => None,
+        Some(&[r, g, b, a]) => Some([r as f32, g as f32, b as f32, a as f32]),
+        Some(color) => {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("gradient_color must be [r, g, b, a], got {:?}", color),
+            ));
+        }
+    };
+    let gradient_direction = match get_hash_value::<Value>(ruby, hash, &keys::GRADIENT_DIRECTION)? {
+        Some(value) if !value.is_nil() => {
+            let name = symbol_or_string(value)?;
+            GradientDirection::from_name(&name).ok_or_else(|| {
+                Error::new(ruby.exception_arg_error(), format!("unknown gradient direction: {}", name))
+            })?
+        }
+        _ => GradientDirection::default(),
+    };
 
     Ok(MeshData {
         shape_type,
@@ -679,17 +3656,21 @@ fn parse_mesh_data(ruby: &Ruby, hash: &RHash) -> Result<MeshData, Error> {
         line_end_y: line_end_y.unwrap_or(0.0) as f32,
         thickness: thickness.unwrap_or(2.0) as f32,
         fill: fill.unwrap_or(true),
+        line_cap,
+        line_join,
+        gradient_color,
+        gradient_direction,
     })
 }
 
-fn parse_mesh_transform_data(ruby: &Ruby, hash: &RHash) -> Result<MeshTransformData, Error> {
-    let x: Option<f64> = get_hash_value(ruby, hash, "x")?;
-    let y: Option<f64> = get_hash_value(ruby, hash, "y")?;
-    let z: Option<f64> = get_hash_value(ruby, hash, "z")?;
-    let rotation: Option<f64> = get_hash_value(ruby, hash, "rotation")?;
-    let scale_x: Option<f64> = get_hash_value(ruby, hash, "scale_x")?;
-    let scale_y: Option<f64> = get_hash_value(ruby, hash, "scale_y")?;
-    let scale_z: Option<f64> = get_hash_value(ruby, hash, "scale_z")?;
+pub(crate) fn parse_mesh_transform_data(ruby: &Ruby, hash: &RHash) -> Result<MeshTransformData, Error> {
+    let x: Option<f64> = get_hash_value(ruby, hash, &keys::X)?;
+    let y: Option<f64> = get_hash_value(ruby, hash, &keys::Y)?;
+    let z: Option<f64> = get_hash_value(ruby, hash, &keys::Z)?;
+    let rotation: Option<f64> = get_hash_value(ruby, hash, &keys::ROTATION)?;
+    let scale_x: Option<f64> = get_hash_value(ruby, hash, &keys::SCALE_X)?;
+    let scale_y: Option<f64> = get_hash_value(ruby, hash, &keys::SCALE_Y)?;
+    let scale_z: Option<f64> = get_hash_value(ruby, hash, &keys::SCALE_Z)?;
 
     let angle = rotation.unwrap_or(0.0) as f32;
     let half_angle = angle / 2.0;
@@ -715,9 +3696,29 @@ pub fn define(ruby: &Ruby, module: &magnus::RModule) -> Result<(), Error> {
     class.define_singleton_method("new", function!(RubyRenderApp::new, -1))?;
     class.define_method("initialize!", method!(RubyRenderApp::initialize, 0))?;
     class.define_method("run", method!(RubyRenderApp::run_with_block, 0))?;
+    class.define_method("close", method!(RubyRenderApp::close, 0))?;
     class.define_method("stop!", method!(RubyRenderApp::stop, 0))?;
+    class.define_method("read_pixels", method!(RubyRenderApp::read_pixels, 0))?;
+    class.define_method("dump_debug_bundle", method!(RubyRenderApp::dump_debug_bundle, -1))?;
     class.define_method("should_close?", method!(RubyRenderApp::should_close, 0))?;
+    class.define_method("on_exit", method!(RubyRenderApp::on_exit, 0))?;
     class.define_method("initialized?", method!(RubyRenderApp::is_initialized, 0))?;
+    class.define_method("delta_time", method!(RubyRenderApp::delta_time, 0))?;
+    class.define_method("elapsed_time", method!(RubyRenderApp::elapsed_time, 0))?;
+    class.define_method("frame_count", method!(RubyRenderApp::frame_count, 0))?;
+    class.define_method(
+        "set_detailed_stats",
+        method!(RubyRenderApp::set_detailed_stats, 1),
+    )?;
+    class.define_method("detailed_stats?", method!(RubyRenderApp::detailed_stats, 0))?;
+    class.define_method("start_journal", method!(RubyRenderApp::start_journal, -1))?;
+    class.define_method("stop_journal", method!(RubyRenderApp::stop_journal, 0))?;
+    class.define_method("replay_journal", method!(RubyRenderApp::replay_journal, 1))?;
+    class.define_method(
+        "replaying_journal?",
+        method!(RubyRenderApp::replaying_journal, 0),
+    )?;
+    class.define_method("stats", method!(RubyRenderApp::stats, 0))?;
 
     class.define_method("key_pressed?", method!(RubyRenderApp::key_pressed, 1))?;
     class.define_method(
@@ -728,6 +3729,23 @@ pub fn define(ruby: &Ruby, module: &magnus::RModule) -> Result<(), Error> {
         "key_just_released?",
         method!(RubyRenderApp::key_just_released, 1),
     )?;
+    class.define_method(
+        "key_held_duration",
+        method!(RubyRenderApp::key_held_duration, 1),
+    )?;
+    class.define_method("bind_action", method!(RubyRenderApp::bind_action, -1))?;
+    class.define_method("unbind_action", method!(RubyRenderApp::unbind_action, 1))?;
+    class.define_method("action_binding", method!(RubyRenderApp::action_binding, 1))?;
+    class.define_method("actions", method!(RubyRenderApp::action_names, 0))?;
+    class.define_method("action_pressed?", method!(RubyRenderApp::action_pressed, 1))?;
+    class.define_method(
+        "action_just_pressed?",
+        method!(RubyRenderApp::action_just_pressed, 1),
+    )?;
+    class.define_method(
+        "action_just_released?",
+        method!(RubyRenderApp::action_just_released, 1),
+    )?;
     class.define_method(
         "mouse_button_pressed?",
         method!(RubyRenderApp::mouse_button_pressed, 1),
@@ -738,13 +3756,84 @@ pub fn define(ruby: &Ruby, module: &magnus::RModule) -> Result<(), Error> {
     )?;
     class.define_method("mouse_position", method!(RubyRenderApp::mouse_position, 0))?;
     class.define_method("mouse_delta", method!(RubyRenderApp::mouse_delta, 0))?;
+    class.define_method("scroll_delta", method!(RubyRenderApp::scroll_delta, 0))?;
+    deprecated!(class.as_value(), "mouse_scroll" => "scroll_delta", "2.0.0")?;
+    class.define_method(
+        "mouse_scroll_lines",
+        method!(RubyRenderApp::mouse_scroll_lines, 0),
+    )?;
+    class.define_method(
+        "mouse_scroll_pixels",
+        method!(RubyRenderApp::mouse_scroll_pixels, 0),
+    )?;
+    class.define_method("touches", method!(RubyRenderApp::touches, 0))?;
+    class.define_method("touch_just_pressed?", method!(RubyRenderApp::touch_just_pressed, -1))?;
+    class.define_method("touch_position", method!(RubyRenderApp::touch_position, 1))?;
+    class.define_method("typed_text", method!(RubyRenderApp::typed_text, 0))?;
+    class.define_method(
+        "text_input_enabled=",
+        method!(RubyRenderApp::set_text_input_enabled, 1),
+    )?;
+    class.define_method("text_input_enabled?", method!(RubyRenderApp::text_input_enabled, 0))?;
     class.define_method("pressed_keys", method!(RubyRenderApp::pressed_keys, 0))?;
+    class.define_method(
+        "set_input_captured",
+        method!(RubyRenderApp::set_input_captured, 1),
+    )?;
+    class.define_method("input_captured?", method!(RubyRenderApp::input_captured, 0))?;
+    class.define_method("raw_key_pressed?", method!(RubyRenderApp::raw_key_pressed, 1))?;
+    class.define_method(
+        "raw_key_just_pressed?",
+        method!(RubyRenderApp::raw_key_just_pressed, 1),
+    )?;
+    class.define_method(
+        "raw_key_just_released?",
+        method!(RubyRenderApp::raw_key_just_released, 1),
+    )?;
+    class.define_method(
+        "raw_mouse_button_pressed?",
+        method!(RubyRenderApp::raw_mouse_button_pressed, 1),
+    )?;
+    class.define_method(
+        "raw_mouse_button_just_pressed?",
+        method!(RubyRenderApp::raw_mouse_button_just_pressed, 1),
+    )?;
+    class.define_method("raw_pressed_keys", method!(RubyRenderApp::raw_pressed_keys, 0))?;
     class.define_method("gamepads_state", method!(RubyRenderApp::gamepads_state, 0))?;
 
     class.define_method("sync_sprite", method!(RubyRenderApp::sync_sprite, 3))?;
-    class.define_method("remove_sprite", method!(RubyRenderApp::remove_sprite, 1))?;
+    class.define_method("sync_sprites_batch", method!(RubyRenderApp::sync_sprites_batch, 1))?;
+    class.define_method("remove_sprite", method!(RubyRenderApp::remove_sprite, -1))?;
+    class.define_method("set_remove_defer", method!(RubyRenderApp::set_remove_defer, 1))?;
     class.define_method("clear_sprites", method!(RubyRenderApp::clear_sprites, 0))?;
+    class.define_method("apply_palette", method!(RubyRenderApp::apply_palette, 2))?;
+    class.define_method(
+        "clear_sprite_color",
+        method!(RubyRenderApp::clear_sprite_color, 1),
+    )?;
+    class.define_method("apply_impulse", method!(RubyRenderApp::apply_impulse, 4))?;
+    class.define_method("bring_to_front", method!(RubyRenderApp::bring_to_front, 1))?;
+    class.define_method("send_to_back", method!(RubyRenderApp::send_to_back, 1))?;
+    class.define_method("set_draw_order", method!(RubyRenderApp::set_draw_order, 2))?;
+    class.define_method("draw_order", method!(RubyRenderApp::draw_order, 1))?;
 
+    class.define_method("floating_text", method!(RubyRenderApp::floating_text, 4))?;
+    class.define_method(
+        "set_floating_text_cap",
+        method!(RubyRenderApp::set_floating_text_cap, 1),
+    )?;
+    class.define_method(
+        "set_group_update_divisor",
+        method!(RubyRenderApp::set_group_update_divisor, 2),
+    )?;
+    class.define_method("due_groups", method!(RubyRenderApp::due_groups, 0))?;
+    class.define_method("group_due?", method!(RubyRenderApp::group_due, 1))?;
+    class.define_method(
+        "set_sleep_thresholds",
+        method!(RubyRenderApp::set_sleep_thresholds, 3),
+    )?;
+    class.define_method("wake_all", method!(RubyRenderApp::wake_all, 0))?;
+    class.define_method("sleeping_count", method!(RubyRenderApp::sleeping_count, 0))?;
     class.define_method("sync_text", method!(RubyRenderApp::sync_text, 3))?;
     class.define_method("remove_text", method!(RubyRenderApp::remove_text, 1))?;
     class.define_method("clear_texts", method!(RubyRenderApp::clear_texts, 0))?;
@@ -752,6 +3841,46 @@ pub fn define(ruby: &Ruby, module: &magnus::RModule) -> Result<(), Error> {
     class.define_method("sync_mesh", method!(RubyRenderApp::sync_mesh, 3))?;
     class.define_method("remove_mesh", method!(RubyRenderApp::remove_mesh, 1))?;
     class.define_method("clear_meshes", method!(RubyRenderApp::clear_meshes, 0))?;
+    class.define_method("mesh_triangles", method!(RubyRenderApp::mesh_triangles, 1))?;
+    class.define_method(
+        "text_sync_enabled?",
+        method!(RubyRenderApp::text_sync_enabled, 0),
+    )?;
+    class.define_method(
+        "mesh_sync_enabled?",
+        method!(RubyRenderApp::mesh_sync_enabled, 0),
+    )?;
+
+    class.define_method("spin", method!(RubyRenderApp::spin, 2))?;
+    class.define_method("pulse", method!(RubyRenderApp::pulse, 2))?;
+    class.define_method("bob", method!(RubyRenderApp::bob, 2))?;
+    class.define_method(
+        "clear_behaviors",
+        method!(RubyRenderApp::clear_behaviors, 1),
+    )?;
+    class.define_method("set_lod", method!(RubyRenderApp::set_lod, 2))?;
+    class.define_method("clear_lod", method!(RubyRenderApp::clear_lod, 1))?;
+    class.define_method(
+        "set_lod_margin",
+        method!(RubyRenderApp::set_lod_margin, 1),
+    )?;
+    class.define_method("lod_level", method!(RubyRenderApp::lod_level, 1))?;
+    class.define_method(
+        "set_parallax_factor",
+        method!(RubyRenderApp::set_parallax_factor, 3),
+    )?;
+    class.define_method("clear_parallax", method!(RubyRenderApp::clear_parallax, 1))?;
+    class.define_method("set_pixel_perfect", method!(RubyRenderApp::set_pixel_perfect, 1))?;
+    class.define_method(
+        "set_entity_pixel_perfect",
+        method!(RubyRenderApp::set_entity_pixel_perfect, 2),
+    )?;
+    class.define_method(
+        "register_hit_region",
+        method!(RubyRenderApp::register_hit_region, -1),
+    )?;
+    class.define_method("update_hit_region", method!(RubyRenderApp::update_hit_region, -1))?;
+    class.define_method("remove_hit_region", method!(RubyRenderApp::remove_hit_region, 1))?;
 
     class.define_method(
         "set_camera_position",
@@ -766,14 +3895,152 @@ pub fn define(ruby: &Ruby, module: &magnus::RModule) -> Result<(), Error> {
         method!(RubyRenderApp::set_camera_scale, 1),
     )?;
     class.define_method("camera_scale", method!(RubyRenderApp::get_camera_scale, 0))?;
+    class.define_method("set_zoom_limits", method!(RubyRenderApp::set_zoom_limits, 2))?;
+    class.define_method("zoom_limits", method!(RubyRenderApp::zoom_limits, 0))?;
+    class.define_method("zoom_in", method!(RubyRenderApp::zoom_in, 1))?;
+    class.define_method("zoom_out", method!(RubyRenderApp::zoom_out, 1))?;
+    class.define_method(
+        "set_camera_transform",
+        method!(RubyRenderApp::set_camera_transform, 1),
+    )?;
+    class.define_method(
+        "camera_rotation",
+        method!(RubyRenderApp::get_camera_rotation, 0),
+    )?;
+    class.define_method("shake_camera", method!(RubyRenderApp::shake_camera, 2))?;
+    class.define_method(
+        "camera_follow_entity",
+        method!(RubyRenderApp::camera_follow_entity, -1),
+    )?;
+    class.define_method("stop_camera_follow", method!(RubyRenderApp::stop_camera_follow, 0))?;
+    class.define_method("camera_follow", method!(RubyRenderApp::camera_follow, 0))?;
+    class.define_method("applied_frames", method!(RubyRenderApp::applied_frames, 0))?;
     class.define_method(
         "queue_gamepad_rumble",
         method!(RubyRenderApp::queue_gamepad_rumble, 4),
     )?;
+    class.define_method("rumble_light", method!(RubyRenderApp::rumble_light, 2))?;
+    class.define_method("rumble_heavy", method!(RubyRenderApp::rumble_heavy, 2))?;
+    class.define_method("rumble_pattern", method!(RubyRenderApp::rumble_pattern, 2))?;
+    class.define_method("stop_all_rumble", method!(RubyRenderApp::stop_all_rumble, 0))?;
+    class.define_method("rumble_active?", method!(RubyRenderApp::is_rumble_active, 1))?;
     class.define_method(
         "drain_picking_events",
         method!(RubyRenderApp::drain_picking_events, 0),
     )?;
+    class.define_method("window_metrics", method!(RubyRenderApp::window_metrics, 0))?;
+    class.define_method("set_window_mode", method!(RubyRenderApp::set_window_mode, 1))?;
+    class.define_method("toggle_fullscreen", method!(RubyRenderApp::toggle_fullscreen, 0))?;
+    class.define_method("window_mode", method!(RubyRenderApp::window_mode, 0))?;
+    class.define_method("set_fullscreen", method!(RubyRenderApp::set_fullscreen, 1))?;
+    class.define_method("set_clear_color", method!(RubyRenderApp::set_clear_color, -1))?;
+    class.define_method("clear_color", method!(RubyRenderApp::clear_color, 0))?;
+    class.define_method("set_max_fps", method!(RubyRenderApp::set_max_fps, 1))?;
+    class.define_method("max_fps", method!(RubyRenderApp::max_fps, 0))?;
+    class.define_method("set_window_title", method!(RubyRenderApp::set_window_title, 1))?;
+    class.define_method("drain_warnings", method!(RubyRenderApp::drain_warnings, 0))?;
+    class.define_method("set_warning_policy", method!(RubyRenderApp::set_warning_policy, 2))?;
+    class.define_method("set_cursor_visible", method!(RubyRenderApp::set_cursor_visible, 1))?;
+    class.define_method("cursor_visible?", method!(RubyRenderApp::cursor_visible, 0))?;
+    class.define_method("set_cursor_grab", method!(RubyRenderApp::set_cursor_grab, 1))?;
+    class.define_method("cursor_grab", method!(RubyRenderApp::cursor_grab, 0))?;
+    class.define_method("set_window_size", method!(RubyRenderApp::set_window_size, 2))?;
+    class.define_method("window_size", method!(RubyRenderApp::window_size, 0))?;
+    class.define_method("fullscreen?", method!(RubyRenderApp::is_fullscreen, 0))?;
+    class.define_method(
+        "set_accessibility_enabled",
+        method!(RubyRenderApp::set_accessibility_enabled, 1),
+    )?;
+    class.define_method(
+        "accessibility_active?",
+        method!(RubyRenderApp::accessibility_active, 0),
+    )?;
+    class.define_method(
+        "accessibility_node",
+        method!(RubyRenderApp::accessibility_node, 1),
+    )?;
+    class.define_method("play_clip", method!(RubyRenderApp::play_clip, 2))?;
+    class.define_method("stop_clip", method!(RubyRenderApp::stop_clip, 1))?;
+    class.define_method("seek_clip", method!(RubyRenderApp::seek_clip, 2))?;
+    class.define_method("enable_drag", method!(RubyRenderApp::enable_drag, -1))?;
+    class.define_method("disable_drag", method!(RubyRenderApp::disable_drag, 1))?;
+    class.define_method("drain_drag_events", method!(RubyRenderApp::drain_drag_events, 0))?;
+    class.define_method(
+        "drain_finished_clips",
+        method!(RubyRenderApp::drain_finished_clips, 0),
+    )?;
+    class.define_method(
+        "clip_skipped_tracks",
+        method!(RubyRenderApp::clip_skipped_tracks, 0),
+    )?;
+    class.define_method(
+        "text_blend_supported?",
+        method!(RubyRenderApp::text_blend_supported, 0),
+    )?;
+    class.define_method(
+        "determinism_audit?",
+        method!(RubyRenderApp::determinism_audit, 0),
+    )?;
+    class.define_method("frame_checksum", method!(RubyRenderApp::frame_checksum, 0))?;
+    class.define_method(
+        "checksum_history",
+        method!(RubyRenderApp::checksum_history, 0),
+    )?;
+    class.define_method(
+        "compare_recorded_checksums",
+        method!(RubyRenderApp::compare_recorded_checksums, 1),
+    )?;
+    class.define_method(
+        "checksum_divergence",
+        method!(RubyRenderApp::checksum_divergence, 0),
+    )?;
+    class.define_method("enable_rewind", method!(RubyRenderApp::enable_rewind, 2))?;
+    class.define_method("disable_rewind", method!(RubyRenderApp::disable_rewind, 0))?;
+    class.define_method("rewind_to", method!(RubyRenderApp::rewind_to, 1))?;
+    class.define_method(
+        "resume_from_rewind",
+        method!(RubyRenderApp::resume_from_rewind, 0),
+    )?;
+    class.define_method("rewinding?", method!(RubyRenderApp::rewinding, 0))?;
+    class.define_method("rewind_stats", method!(RubyRenderApp::rewind_stats, 0))?;
+    class.define_method(
+        "drain_file_drops",
+        method!(RubyRenderApp::drain_file_drops, 0),
+    )?;
+    class.define_method(
+        "drain_gamepad_connection_events",
+        method!(RubyRenderApp::drain_gamepad_connection_events, 0),
+    )?;
+    class.define_method(
+        "enable_virtual_cursor",
+        method!(RubyRenderApp::enable_virtual_cursor, 1),
+    )?;
+    class.define_method(
+        "disable_virtual_cursor",
+        method!(RubyRenderApp::disable_virtual_cursor, 0),
+    )?;
+    class.define_method(
+        "virtual_cursor_position",
+        method!(RubyRenderApp::virtual_cursor_position, 0),
+    )?;
+    class.define_method(
+        "drain_window_events",
+        method!(RubyRenderApp::drain_window_events, 0),
+    )?;
+    class.define_method(
+        "transforms_snapshot",
+        method!(RubyRenderApp::transforms_snapshot, -1),
+    )?;
+    class.define_method(
+        "positions_snapshot",
+        method!(RubyRenderApp::positions_snapshot, -1),
+    )?;
+    class.define_method("raycast", method!(RubyRenderApp::raycast, -1))?;
+    class.define_method("line_of_sight?", method!(RubyRenderApp::line_of_sight, -1))?;
+
+    capabilities::register("rendering", cfg!(feature = "rendering"));
+    capabilities::register("picking", cfg!(feature = "rendering"));
+    capabilities::register("texture_sprites", cfg!(feature = "rendering"));
 
     Ok(())
 }
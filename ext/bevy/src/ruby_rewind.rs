@@ -0,0 +1,127 @@
+use bevy_ruby::{Rewind, RewindFrame, RewindSample, RewindStats};
+use magnus::{Error, RArray, RHash, RModule, Ruby, function, method, prelude::*};
+use std::cell::RefCell;
+
+use crate::keys;
+
+/// Standalone rewind recorder, fed with packed `[id, x, y, rotation, ...]` snapshots.
+#[magnus::wrap(class = "Bevy::RewindBuffer", free_immediately, size)]
+pub struct MagnusRewindBuffer {
+    inner: RefCell<Rewind>,
+}
+
+impl MagnusRewindBuffer {
+    fn new(window_secs: f64, snapshot_hz: f64) -> Self {
+        Self {
+            inner: RefCell::new(Rewind::new(window_secs as f32, snapshot_hz as f32)),
+        }
+    }
+
+    fn update(&self, delta_secs: f64, live: Vec<f64>) -> Result<RHash, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let live = unpack_samples(&ruby, &live)?;
+        let frame = self.inner.borrow_mut().update(delta_secs as f32, &live);
+        frame_to_hash(&ruby, &frame)
+    }
+
+    fn sample(&self, seconds_ago: f64) -> Result<Option<RArray>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        self.inner
+            .borrow()
+            .buffer()
+            .sample(seconds_ago as f32)
+            .map(|samples| pack_samples(&ruby, &samples))
+            .transpose()
+    }
+
+    fn rewind_to(&self, seconds_ago: f64) {
+        self.inner.borrow_mut().rewind_to(seconds_ago as f32);
+    }
+
+    fn resume(&self) {
+        self.inner.borrow_mut().resume();
+    }
+
+    fn is_rewinding(&self) -> bool {
+        self.inner.borrow().is_rewinding()
+    }
+
+    fn size(&self) -> usize {
+        self.inner.borrow().buffer().len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.inner.borrow().buffer().capacity()
+    }
+
+    fn stats(&self) -> Result<RHash, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        stats_to_hash(&ruby, &self.inner.borrow().stats())
+    }
+}
+
+unsafe impl Send for MagnusRewindBuffer {}
+
+fn unpack_samples(ruby: &Ruby, packed: &[f64]) -> Result<Vec<RewindSample>, Error> {
+    if packed.len() % 4 != 0 {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            "snapshot must be packed as [id, x, y, rotation, ...]",
+        ));
+    }
+    Ok(packed
+        .chunks_exact(4)
+        .map(|chunk| RewindSample {
+            ruby_entity_id: chunk[0] as u64,
+            x: chunk[1] as f32,
+            y: chunk[2] as f32,
+            rotation: chunk[3] as f32,
+        })
+        .collect())
+}
+
+pub fn pack_samples(ruby: &Ruby, samples: &[RewindSample]) -> Result<RArray, Error> {
+    let result = ruby.ary_new_capa(samples.len() * 4);
+    for sample in samples {
+        result.push(sample.ruby_entity_id as f64)?;
+        result.push(sample.x as f64)?;
+        result.push(sample.y as f64)?;
+        result.push(sample.rotation as f64)?;
+    }
+    Ok(result)
+}
+
+fn frame_to_hash(ruby: &Ruby, frame: &RewindFrame) -> Result<RHash, Error> {
+    let hash = ruby.hash_new_capa(3);
+    hash.aset(*keys::WRITE, pack_samples(ruby, &frame.writes)?)?;
+    hash.aset(*keys::HIDE, ruby.ary_from_vec(frame.hide.clone()))?;
+    hash.aset(*keys::SHOW, ruby.ary_from_vec(frame.show.clone()))?;
+    Ok(hash)
+}
+
+pub fn stats_to_hash(ruby: &Ruby, stats: &RewindStats) -> Result<RHash, Error> {
+    let hash = ruby.hash_new_capa(7);
+    hash.aset(*keys::SNAPSHOTS, stats.snapshots)?;
+    hash.aset(*keys::CAPACITY, stats.capacity)?;
+    hash.aset(*keys::MEMORY_BYTES, stats.memory_bytes)?;
+    hash.aset(*keys::MAX_MEMORY_BYTES, stats.max_memory_bytes)?;
+    hash.aset(*keys::COVERED_SECONDS, stats.covered_secs as f64)?;
+    hash.aset(*keys::TRUNCATED_SNAPSHOTS, stats.truncated_snapshots)?;
+    hash.aset(*keys::REWINDING, stats.rewinding)?;
+    Ok(hash)
+}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let rewind_class = module.define_class("RewindBuffer", ruby.class_object())?;
+    rewind_class.define_singleton_method("new", function!(MagnusRewindBuffer::new, 2))?;
+    rewind_class.define_method("update", method!(MagnusRewindBuffer::update, 2))?;
+    rewind_class.define_method("sample", method!(MagnusRewindBuffer::sample, 1))?;
+    rewind_class.define_method("rewind_to", method!(MagnusRewindBuffer::rewind_to, 1))?;
+    rewind_class.define_method("resume", method!(MagnusRewindBuffer::resume, 0))?;
+    rewind_class.define_method("rewinding?", method!(MagnusRewindBuffer::is_rewinding, 0))?;
+    rewind_class.define_method("size", method!(MagnusRewindBuffer::size, 0))?;
+    rewind_class.define_method("capacity", method!(MagnusRewindBuffer::capacity, 0))?;
+    rewind_class.define_method("stats", method!(MagnusRewindBuffer::stats, 0))?;
+
+    Ok(())
+}
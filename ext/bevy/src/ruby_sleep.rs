@@ -0,0 +1,71 @@
+use bevy_ruby::{SleepConfig, SleepTracker};
+use magnus::{Error, RModule, Ruby, function, method, prelude::*};
+use std::cell::RefCell;
+
+/// The sleep bookkeeping behind `App#set_sleep_thresholds`, fed motion by
+/// hand instead of by the behavior and jelly systems.
+#[magnus::wrap(class = "Bevy::SleepTracker", free_immediately, size)]
+pub struct MagnusSleepTracker {
+    inner: RefCell<SleepTracker>,
+    config: SleepConfig,
+}
+
+impl MagnusSleepTracker {
+    /// `new(sleep_epsilon, wake_epsilon, frames)`.
+    fn new(sleep_epsilon: f64, wake_epsilon: f64, frames: u32) -> Self {
+        Self {
+            inner: RefCell::new(SleepTracker::new()),
+            config: SleepConfig::new(sleep_epsilon as f32, wake_epsilon as f32, frames),
+        }
+    }
+
+    fn insert(&self, id: u64) {
+        self.inner.borrow_mut().insert(id);
+    }
+
+    fn remove(&self, id: u64) {
+        self.inner.borrow_mut().remove(id);
+    }
+
+    /// `observe(id, motion)`: true when this frame's motion put `id` to sleep.
+    fn observe(&self, id: u64, motion: f64) -> bool {
+        self.inner.borrow_mut().observe(id, motion as f32, &self.config)
+    }
+
+    fn wake(&self, id: u64) -> bool {
+        self.inner.borrow_mut().wake(id)
+    }
+
+    fn wake_all(&self) -> usize {
+        self.inner.borrow_mut().wake_all()
+    }
+
+    fn is_asleep(&self, id: u64) -> bool {
+        self.inner.borrow().is_asleep(id)
+    }
+
+    fn awake_ids(&self) -> Vec<u64> {
+        self.inner.borrow().awake_ids()
+    }
+
+    fn sleeping_count(&self) -> usize {
+        self.inner.borrow().sleeping_count()
+    }
+}
+
+unsafe impl Send for MagnusSleepTracker {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let tracker_class = module.define_class("SleepTracker", ruby.class_object())?;
+    tracker_class.define_singleton_method("new", function!(MagnusSleepTracker::new, 3))?;
+    tracker_class.define_method("insert", method!(MagnusSleepTracker::insert, 1))?;
+    tracker_class.define_method("remove", method!(MagnusSleepTracker::remove, 1))?;
+    tracker_class.define_method("observe", method!(MagnusSleepTracker::observe, 2))?;
+    tracker_class.define_method("wake", method!(MagnusSleepTracker::wake, 1))?;
+    tracker_class.define_method("wake_all", method!(MagnusSleepTracker::wake_all, 0))?;
+    tracker_class.define_method("asleep?", method!(MagnusSleepTracker::is_asleep, 1))?;
+    tracker_class.define_method("awake_ids", method!(MagnusSleepTracker::awake_ids, 0))?;
+    tracker_class.define_method("sleeping_count", method!(MagnusSleepTracker::sleeping_count, 0))?;
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use bevy_ruby::{SYSTEM_TIMING_WINDOW, SystemTimings};
+use magnus::{Error, RHash, RModule, Ruby, TryConvert, Value, function, method, prelude::*};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// `{system_name => micros}` with symbol keys.
+pub(crate) fn timings_to_hash(ruby: &Ruby, timings: &BTreeMap<String, u64>) -> Result<RHash, Error> {
+    let hash = ruby.hash_new_capa(timings.len());
+    for (name, micros) in timings {
+        hash.aset(ruby.to_symbol(name), *micros)?;
+    }
+    Ok(hash)
+}
+
+/// Per-system timing bookkeeping, fed the same way the render bridge feeds it.
+#[magnus::wrap(class = "Bevy::SystemTimings", free_immediately, size)]
+pub struct MagnusSystemTimings {
+    inner: RefCell<SystemTimings>,
+}
+
+impl MagnusSystemTimings {
+    /// `new` or `new(window)`; starts disabled like the render bridge.
+    fn new(args: &[Value]) -> Result<Self, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let window = match args {
+            [] => SYSTEM_TIMING_WINDOW,
+            [window] => usize::try_convert(*window)?,
+            _ => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("wrong number of arguments (given {}, expected 0..1)", args.len()),
+                ));
+            }
+        };
+        Ok(Self {
+            inner: RefCell::new(SystemTimings::new(window)),
+        })
+    }
+
+    fn enabled(&self) -> bool {
+        self.inner.borrow().is_enabled()
+    }
+
+    fn set_enabled(&self, enabled: bool) {
+        self.inner.borrow_mut().set_enabled(enabled);
+    }
+
+    fn record(&self, name: String, micros: u64) {
+        self.inner.borrow_mut().record(&name, micros);
+    }
+
+    fn end_frame(&self) {
+        self.inner.borrow_mut().end_frame();
+    }
+
+    fn frame(&self) -> Result<RHash, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        timings_to_hash(&ruby, self.inner.borrow().frame())
+    }
+
+    fn max(&self) -> Result<RHash, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        timings_to_hash(&ruby, &self.inner.borrow().max())
+    }
+}
+
+unsafe impl Send for MagnusSystemTimings {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let timings_class = module.define_class("SystemTimings", ruby.class_object())?;
+    timings_class.define_singleton_method("new", function!(MagnusSystemTimings::new, -1))?;
+    timings_class.define_method("enabled?", method!(MagnusSystemTimings::enabled, 0))?;
+    timings_class.define_method("enabled=", method!(MagnusSystemTimings::set_enabled, 1))?;
+    timings_class.define_method("record", method!(MagnusSystemTimings::record, 2))?;
+    timings_class.define_method("end_frame", method!(MagnusSystemTimings::end_frame, 0))?;
+    timings_class.define_method("frame", method!(MagnusSystemTimings::frame, 0))?;
+    timings_class.define_method("max", method!(MagnusSystemTimings::max, 0))?;
+
+    Ok(())
+}
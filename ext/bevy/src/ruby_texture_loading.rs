@@ -0,0 +1,123 @@
+use bevy_ruby::{PendingTextures, TEXTURE_PLACEHOLDER_NAMES, TextureLoadSource, TextureLoadState, TexturePlaceholder};
+use magnus::{Error, RArray, RHash, RModule, Ruby, Symbol, Value, function, method, prelude::*};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::ruby_render_app::symbol_or_string;
+
+pub(crate) fn parse_texture_placeholder(ruby: &Ruby, value: Value) -> Result<TexturePlaceholder, Error> {
+    let name = symbol_or_string(value)?;
+    TexturePlaceholder::from_name(&name).ok_or_else(|| {
+        Error::new(
+            ruby.exception_arg_error(),
+            format!(
+                "unknown texture placeholder: {} (expected one of {})",
+                name,
+                TEXTURE_PLACEHOLDER_NAMES.join(", ")
+            ),
+        )
+    })
+}
+
+fn load_state_name(state: TextureLoadState) -> &'static str {
+    match state {
+        TextureLoadState::Loading => "loading",
+        TextureLoadState::Loaded => "loaded",
+        TextureLoadState::Failed => "failed",
+    }
+}
+
+/// Load states given as `{ path => :loading | :loaded | :failed }`; paths
+/// left out are still loading.
+struct HashLoadStates(HashMap<String, TextureLoadState>);
+
+impl TextureLoadSource for HashLoadStates {
+    fn load_state(&self, path: &str) -> TextureLoadState {
+        self.0.get(path).copied().unwrap_or(TextureLoadState::Loading)
+    }
+}
+
+fn parse_load_states(ruby: &Ruby, states: RHash) -> Result<HashLoadStates, Error> {
+    let mut parsed = HashMap::new();
+    states.foreach(|path: String, state: Value| {
+        let state = match symbol_or_string(state)?.as_str() {
+            "loading" => TextureLoadState::Loading,
+            "loaded" => TextureLoadState::Loaded,
+            "failed" => TextureLoadState::Failed,
+            other => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("unknown load state: {} (expected loading, loaded or failed)", other),
+                ));
+            }
+        };
+        parsed.insert(path, state);
+        Ok(magnus::r_hash::ForEach::Continue)
+    })?;
+    Ok(HashLoadStates(parsed))
+}
+
+/// The bookkeeping sprites use while their textures load, driven by load
+/// states from Ruby instead of the asset server.
+#[magnus::wrap(class = "Bevy::PendingTextures", free_immediately, size)]
+pub struct MagnusPendingTextures {
+    inner: RefCell<PendingTextures>,
+}
+
+impl MagnusPendingTextures {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(PendingTextures::new()),
+        }
+    }
+
+    fn wait(&self, ruby_entity_id: u64, path: String) {
+        self.inner.borrow_mut().wait(ruby_entity_id, &path);
+    }
+
+    fn forget(&self, ruby_entity_id: u64) -> bool {
+        self.inner.borrow_mut().forget(ruby_entity_id)
+    }
+
+    fn waiting_on(&self, ruby_entity_id: u64) -> Option<String> {
+        self.inner.borrow().waiting_on(ruby_entity_id).map(str::to_owned)
+    }
+
+    fn is_failed(&self, path: String) -> bool {
+        self.inner.borrow().is_failed(&path)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    /// `poll({ path => state })`: `[[path, :loaded | :failed, [ids]], ...]`
+    /// for the paths that settled.
+    fn poll(&self, states: RHash) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let source = parse_load_states(&ruby, states)?;
+        let resolutions = self.inner.borrow_mut().poll(&source);
+
+        let result = ruby.ary_new_capa(resolutions.len());
+        for resolution in resolutions {
+            let state: Symbol = ruby.to_symbol(load_state_name(resolution.state));
+            result.push((resolution.path, state, resolution.ruby_entity_ids))?;
+        }
+        Ok(result)
+    }
+}
+
+unsafe impl Send for MagnusPendingTextures {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let pending_class = module.define_class("PendingTextures", ruby.class_object())?;
+    pending_class.define_singleton_method("new", function!(MagnusPendingTextures::new, 0))?;
+    pending_class.define_method("wait", method!(MagnusPendingTextures::wait, 2))?;
+    pending_class.define_method("forget", method!(MagnusPendingTextures::forget, 1))?;
+    pending_class.define_method("waiting_on", method!(MagnusPendingTextures::waiting_on, 1))?;
+    pending_class.define_method("failed?", method!(MagnusPendingTextures::is_failed, 1))?;
+    pending_class.define_method("size", method!(MagnusPendingTextures::len, 0))?;
+    pending_class.define_method("poll", method!(MagnusPendingTextures::poll, 1))?;
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use bevy_ruby::{TouchPhase, TouchState, TouchStates};
+use magnus::{Error, RArray, RHash, RModule, Ruby, Value, function, method, prelude::*};
+use std::cell::RefCell;
+
+use crate::keys;
+use crate::ruby_render_app::symbol_or_string;
+
+fn position_array(ruby: &Ruby, (x, y): (f32, f32)) -> Result<RArray, Error> {
+    let array = ruby.ary_new_capa(2);
+    array.push(x as f64)?;
+    array.push(y as f64)?;
+    Ok(array)
+}
+
+/// `{ id:, phase:, position: [x, y], previous_position: [x, y], force: }`.
+pub(crate) fn touch_to_hash(ruby: &Ruby, touch: &TouchState) -> Result<RHash, Error> {
+    let hash = ruby.hash_new_capa(5);
+    hash.aset(*keys::ID, touch.id)?;
+    hash.aset(*keys::PHASE, touch.phase.name())?;
+    hash.aset(*keys::POSITION, position_array(ruby, touch.position)?)?;
+    hash.aset(*keys::PREVIOUS_POSITION, position_array(ruby, touch.previous_position)?)?;
+    hash.aset(*keys::FORCE, touch.force.map(f64::from))?;
+    Ok(hash)
+}
+
+pub(crate) fn touches_to_array(ruby: &Ruby, touches: &TouchStates) -> Result<RArray, Error> {
+    let array = ruby.ary_new_capa(touches.len());
+    for touch in touches.iter() {
+        array.push(touch_to_hash(ruby, touch)?)?;
+    }
+    Ok(array)
+}
+
+/// The per-finger bookkeeping behind `RenderApp#touches`, fed events by hand
+/// instead of by the render loop.
+#[magnus::wrap(class = "Bevy::TouchStates", free_immediately, size)]
+pub struct MagnusTouchStates {
+    inner: RefCell<TouchStates>,
+}
+
+impl MagnusTouchStates {
+    fn new() -> Self {
+        Self {
+            inner: RefCell::new(TouchStates::new()),
+        }
+    }
+
+    fn begin_frame(&self) {
+        self.inner.borrow_mut().begin_frame();
+    }
+
+    /// `apply(id, phase, x, y, force)`; `force` may be nil.
+    fn apply(&self, id: u64, phase: Value, x: f64, y: f64, force: Option<f64>) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let name = symbol_or_string(phase)?;
+        let phase = TouchPhase::from_name(&name).ok_or_else(|| {
+            Error::new(
+                ruby.exception_arg_error(),
+                format!("unknown touch phase: {} (expected started, moved, stationary, ended or canceled)", name),
+            )
+        })?;
+        self.inner
+            .borrow_mut()
+            .apply(id, phase, (x as f32, y as f32), force.map(|force| force as f32));
+        Ok(())
+    }
+
+    fn touches(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        touches_to_array(&ruby, &self.inner.borrow())
+    }
+
+    fn just_pressed(&self, id: Option<u64>) -> bool {
+        self.inner.borrow().just_pressed(id)
+    }
+
+    fn position(&self, id: u64) -> Option<(f64, f64)> {
+        self.inner
+            .borrow()
+            .position(id)
+            .map(|(x, y)| (x as f64, y as f64))
+    }
+
+    fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    fn clear(&self) {
+        self.inner.borrow_mut().clear();
+    }
+}
+
+unsafe impl Send for MagnusTouchStates {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let touch_class = module.define_class("TouchStates", ruby.class_object())?;
+    touch_class.define_singleton_method("new", function!(MagnusTouchStates::new, 0))?;
+    touch_class.define_method("begin_frame", method!(MagnusTouchStates::begin_frame, 0))?;
+    touch_class.define_method("apply", method!(MagnusTouchStates::apply, 5))?;
+    touch_class.define_method("touches", method!(MagnusTouchStates::touches, 0))?;
+    touch_class.define_method("just_pressed?", method!(MagnusTouchStates::just_pressed, 1))?;
+    touch_class.define_method("position", method!(MagnusTouchStates::position, 1))?;
+    touch_class.define_method("size", method!(MagnusTouchStates::len, 0))?;
+    touch_class.define_method("clear", method!(MagnusTouchStates::clear, 0))?;
+
+    Ok(())
+}
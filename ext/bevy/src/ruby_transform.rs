@@ -0,0 +1,235 @@
+use bevy_ruby::{RubyTransform, RubyVec3};
+use magnus::{
+    Error, RModule, Ruby, TryConvert, Value, function, method, prelude::*, typed_data::Obj,
+};
+use std::cell::RefCell;
+
+use crate::ruby_math::{MagnusMat4, MagnusQuat, MagnusVec3};
+
+/// Translation, rotation and scale, kept as a quaternion rather than a 2D angle.
+///
+/// Mutating methods return `self` so they can be chained.
+#[magnus::wrap(class = "Bevy::Transform", free_immediately, size)]
+pub struct MagnusTransform {
+    inner: RefCell<RubyTransform>,
+}
+
+impl MagnusTransform {
+    fn from_xyz(x: f64, y: f64, z: f64) -> Self {
+        Self::from(RubyTransform::from_xyz(x as f32, y as f32, z as f32))
+    }
+
+    fn identity() -> Self {
+        Self::from(RubyTransform::identity())
+    }
+
+    fn from_translation(translation: &MagnusVec3) -> Self {
+        Self::from(RubyTransform::from_translation(translation.inner()))
+    }
+
+    fn from_rotation(rotation: &MagnusQuat) -> Self {
+        Self::from(RubyTransform::from_rotation(rotation.inner()))
+    }
+
+    fn from_scale(scale: &MagnusVec3) -> Self {
+        Self::from(RubyTransform::from_scale(scale.inner()))
+    }
+
+    fn from_translation_rotation_scale(
+        translation: &MagnusVec3,
+        rotation: &MagnusQuat,
+        scale: &MagnusVec3,
+    ) -> Self {
+        Self::from(RubyTransform::from_translation_rotation_scale(
+            translation.inner(),
+            rotation.inner(),
+            scale.inner(),
+        ))
+    }
+
+    fn translation(&self) -> MagnusVec3 {
+        MagnusVec3::from(self.inner.borrow().translation())
+    }
+
+    fn rotation(&self) -> MagnusQuat {
+        MagnusQuat::from(self.inner.borrow().rotation())
+    }
+
+    fn scale(&self) -> MagnusVec3 {
+        MagnusVec3::from(self.inner.borrow().scale())
+    }
+
+    fn set_translation(&self, translation: &MagnusVec3) {
+        self.inner.borrow_mut().set_translation(translation.inner());
+    }
+
+    fn set_rotation(&self, rotation: &MagnusQuat) {
+        self.inner.borrow_mut().set_rotation(rotation.inner());
+    }
+
+    fn set_scale(&self, scale: &MagnusVec3) {
+        self.inner.borrow_mut().set_scale(scale.inner());
+    }
+
+    fn translate(rb_self: Obj<Self>, delta: &MagnusVec3) -> Obj<Self> {
+        rb_self.inner.borrow_mut().translate(delta.inner());
+        rb_self
+    }
+
+    fn rotate(rb_self: Obj<Self>, rotation: &MagnusQuat) -> Obj<Self> {
+        rb_self.inner.borrow_mut().rotate(rotation.inner());
+        rb_self
+    }
+
+    fn rotate_x(rb_self: Obj<Self>, angle: f64) -> Obj<Self> {
+        rb_self.inner.borrow_mut().rotate_x(angle as f32);
+        rb_self
+    }
+
+    fn rotate_y(rb_self: Obj<Self>, angle: f64) -> Obj<Self> {
+        rb_self.inner.borrow_mut().rotate_y(angle as f32);
+        rb_self
+    }
+
+    fn rotate_z(rb_self: Obj<Self>, angle: f64) -> Obj<Self> {
+        rb_self.inner.borrow_mut().rotate_z(angle as f32);
+        rb_self
+    }
+
+    /// `look_at(target)` or `look_at(target, up)`; `up` defaults to +Y.
+    fn look_at(rb_self: Obj<Self>, args: &[Value]) -> Result<Obj<Self>, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (target, up) = match args {
+            [target] => (<&MagnusVec3>::try_convert(*target)?.inner(), RubyVec3::new(0.0, 1.0, 0.0)),
+            [target, up] => (
+                <&MagnusVec3>::try_convert(*target)?.inner(),
+                <&MagnusVec3>::try_convert(*up)?.inner(),
+            ),
+            _ => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("wrong number of arguments (given {}, expected 1..2)", args.len()),
+                ));
+            }
+        };
+        rb_self.inner.borrow_mut().look_at(target, up);
+        Ok(rb_self)
+    }
+
+    fn forward(&self) -> MagnusVec3 {
+        MagnusVec3::from(self.inner.borrow().forward())
+    }
+
+    fn back(&self) -> MagnusVec3 {
+        MagnusVec3::from(self.inner.borrow().back())
+    }
+
+    fn left(&self) -> MagnusVec3 {
+        MagnusVec3::from(self.inner.borrow().left())
+    }
+
+    fn right(&self) -> MagnusVec3 {
+        MagnusVec3::from(self.inner.borrow().right())
+    }
+
+    fn up(&self) -> MagnusVec3 {
+        MagnusVec3::from(self.inner.borrow().up())
+    }
+
+    fn down(&self) -> MagnusVec3 {
+        MagnusVec3::from(self.inner.borrow().down())
+    }
+
+    fn mul(&self, other: &MagnusTransform) -> Self {
+        Self::from(self.inner.borrow().mul(&other.inner.borrow()))
+    }
+
+    fn transform_point(&self, point: &MagnusVec3) -> MagnusVec3 {
+        MagnusVec3::from(self.inner.borrow().transform_point(point.inner()))
+    }
+
+    fn inverse_transform_point(&self, point: &MagnusVec3) -> MagnusVec3 {
+        MagnusVec3::from(self.inner.borrow().inverse_transform_point(point.inner()))
+    }
+
+    fn compute_matrix(&self) -> MagnusMat4 {
+        MagnusMat4::from(self.inner.borrow().compute_matrix())
+    }
+
+    /// Rotation around the Z axis in radians.
+    fn rotation_z(&self) -> f64 {
+        self.inner.borrow().rotation_z() as f64
+    }
+
+    fn eq(&self, other: Value) -> bool {
+        <&MagnusTransform>::try_convert(other)
+            .is_ok_and(|other| *self.inner.borrow() == *other.inner.borrow())
+    }
+
+    fn copy(&self) -> Self {
+        Self::from(self.inner())
+    }
+
+    pub fn inner(&self) -> RubyTransform {
+        *self.inner.borrow()
+    }
+}
+
+impl From<RubyTransform> for MagnusTransform {
+    fn from(inner: RubyTransform) -> Self {
+        Self {
+            inner: RefCell::new(inner),
+        }
+    }
+}
+
+unsafe impl Send for MagnusTransform {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let transform_class = module.define_class("Transform", ruby.class_object())?;
+    transform_class.define_singleton_method("from_xyz", function!(MagnusTransform::from_xyz, 3))?;
+    transform_class.define_singleton_method("identity", function!(MagnusTransform::identity, 0))?;
+    transform_class.define_singleton_method(
+        "from_translation",
+        function!(MagnusTransform::from_translation, 1),
+    )?;
+    transform_class.define_singleton_method(
+        "from_rotation",
+        function!(MagnusTransform::from_rotation, 1),
+    )?;
+    transform_class.define_singleton_method("from_scale", function!(MagnusTransform::from_scale, 1))?;
+    transform_class.define_singleton_method(
+        "from_translation_rotation_scale",
+        function!(MagnusTransform::from_translation_rotation_scale, 3),
+    )?;
+    transform_class.define_method("translation", method!(MagnusTransform::translation, 0))?;
+    transform_class.define_method("rotation", method!(MagnusTransform::rotation, 0))?;
+    transform_class.define_method("scale", method!(MagnusTransform::scale, 0))?;
+    transform_class.define_method("translation=", method!(MagnusTransform::set_translation, 1))?;
+    transform_class.define_method("rotation=", method!(MagnusTransform::set_rotation, 1))?;
+    transform_class.define_method("scale=", method!(MagnusTransform::set_scale, 1))?;
+    transform_class.define_method("translate", method!(MagnusTransform::translate, 1))?;
+    transform_class.define_method("rotate", method!(MagnusTransform::rotate, 1))?;
+    transform_class.define_method("rotate_x", method!(MagnusTransform::rotate_x, 1))?;
+    transform_class.define_method("rotate_y", method!(MagnusTransform::rotate_y, 1))?;
+    transform_class.define_method("rotate_z", method!(MagnusTransform::rotate_z, 1))?;
+    transform_class.define_method("look_at", method!(MagnusTransform::look_at, -1))?;
+    transform_class.define_method("forward", method!(MagnusTransform::forward, 0))?;
+    transform_class.define_method("back", method!(MagnusTransform::back, 0))?;
+    transform_class.define_method("left", method!(MagnusTransform::left, 0))?;
+    transform_class.define_method("right", method!(MagnusTransform::right, 0))?;
+    transform_class.define_method("up", method!(MagnusTransform::up, 0))?;
+    transform_class.define_method("down", method!(MagnusTransform::down, 0))?;
+    transform_class.define_method("*", method!(MagnusTransform::mul, 1))?;
+    transform_class.define_method("transform_point", method!(MagnusTransform::transform_point, 1))?;
+    transform_class.define_method(
+        "inverse_transform_point",
+        method!(MagnusTransform::inverse_transform_point, 1),
+    )?;
+    transform_class.define_method("compute_matrix", method!(MagnusTransform::compute_matrix, 0))?;
+    transform_class.define_method("rotation_z", method!(MagnusTransform::rotation_z, 0))?;
+    transform_class.define_method("==", method!(MagnusTransform::eq, 1))?;
+    transform_class.define_method("dup", method!(MagnusTransform::copy, 0))?;
+
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use bevy_ruby::{DEFAULT_WARNING_CAPACITY, DEFAULT_WARNING_INTERVAL_SECS, WarningLog, WarningPolicy, WarningRecord};
+use magnus::{
+    Error, ExceptionClass, RArray, RHash, RModule, Ruby, TryConvert, Value, function, method, prelude::*,
+};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use crate::keys;
+use crate::ruby_render_app::{get_hash_value, symbol_or_string};
+
+pub(crate) fn parse_warning_policy(ruby: &Ruby, value: Value) -> Result<WarningPolicy, Error> {
+    let name = symbol_or_string(value)?;
+    WarningPolicy::from_name(&name).ok_or_else(|| {
+        Error::new(
+            ruby.exception_arg_error(),
+            format!(
+                "unknown warning policy: {} (expected {})",
+                name,
+                WarningPolicy::NAMES.join(", ")
+            ),
+        )
+    })
+}
+
+pub(crate) fn warning_to_hash(ruby: &Ruby, record: &WarningRecord) -> Result<RHash, Error> {
+    let hash = ruby.hash_new_capa(4);
+    hash.aset(*keys::CATEGORY, ruby.to_symbol(&record.category))?;
+    hash.aset(*keys::MESSAGE, record.message.as_str())?;
+    hash.aset(*keys::COUNT, record.count)?;
+    hash.aset(*keys::FIRST_FRAME, record.first_frame)?;
+    Ok(hash)
+}
+
+fn warning_error(ruby: &Ruby, record: &WarningRecord) -> Error {
+    let message = if record.count > 1 {
+        format!("{}: {} ({} times)", record.category, record.message, record.count)
+    } else {
+        format!("{}: {}", record.category, record.message)
+    };
+    let class = ruby
+        .define_module("Bevy")
+        .and_then(|module| module.const_get::<_, ExceptionClass>("WarningError"))
+        .unwrap_or_else(|_| ruby.exception_runtime_error());
+    Error::new(class, message)
+}
+
+/// Converts queued records to `{category:, message:, count:, first_frame:}`
+/// hashes, skipping silenced categories.
+///
+/// A record whose category raises is taken off the queue and raised as
+/// `Bevy::WarningError`; the records after it stay queued for the next drain.
+pub(crate) fn drain_warning_records(
+    ruby: &Ruby,
+    queue: &mut VecDeque<WarningRecord>,
+    policy: impl Fn(&str) -> WarningPolicy,
+) -> Result<RArray, Error> {
+    let array = ruby.ary_new_capa(queue.len());
+    while let Some(record) = queue.pop_front() {
+        match policy(&record.category) {
+            WarningPolicy::Silent => {}
+            WarningPolicy::Collect => array.push(warning_to_hash(ruby, &record)?)?,
+            WarningPolicy::Raise => return Err(warning_error(ruby, &record)),
+        }
+    }
+    Ok(array)
+}
+
+/// Appends newly drained records, keeping at most the newest `capacity`.
+pub(crate) fn queue_warning_records(queue: &mut VecDeque<WarningRecord>, records: Vec<WarningRecord>, capacity: usize) {
+    queue.extend(records);
+    while queue.len() > capacity {
+        queue.pop_front();
+    }
+}
+
+/// The warning buffer of the render bridge, usable without a window.
+#[magnus::wrap(class = "Bevy::WarningLog", free_immediately, size)]
+pub struct MagnusWarningLog {
+    log: RefCell<WarningLog>,
+    queue: RefCell<VecDeque<WarningRecord>>,
+}
+
+impl MagnusWarningLog {
+    /// `new` or `new(capacity:, interval:)`.
+    fn new(args: &[Value]) -> Result<Self, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (capacity, interval) = match args {
+            [] => (None, None),
+            [options] => {
+                let options = RHash::try_convert(*options)?;
+                (
+                    get_hash_value::<usize>(&ruby, &options, &keys::CAPACITY)?,
+                    get_hash_value::<f64>(&ruby, &options, &keys::INTERVAL)?,
+                )
+            }
+            _ => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("wrong number of arguments (given {}, expected 0..1)", args.len()),
+                ));
+            }
+        };
+        Ok(Self {
+            log: RefCell::new(WarningLog::new(
+                capacity.unwrap_or(DEFAULT_WARNING_CAPACITY),
+                interval.unwrap_or(DEFAULT_WARNING_INTERVAL_SECS),
+            )),
+            queue: RefCell::new(VecDeque::new()),
+        })
+    }
+
+    /// Starts a frame `elapsed` seconds after launch.
+    fn begin_frame(&self, elapsed: f64) {
+        self.log.borrow_mut().begin_frame(elapsed);
+    }
+
+    fn push(&self, category: Value, message: String, dedupe_key: String) -> Result<(), Error> {
+        let category = symbol_or_string(category)?;
+        self.log.borrow_mut().push(&category, message, &dedupe_key);
+        Ok(())
+    }
+
+    fn set_policy(&self, category: Value, policy: Value) -> Result<(), Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let category = symbol_or_string(category)?;
+        let policy = parse_warning_policy(&ruby, policy)?;
+        self.log.borrow_mut().set_policy(&category, policy);
+        Ok(())
+    }
+
+    fn drain(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let (records, capacity) = {
+            let mut log = self.log.borrow_mut();
+            (log.drain(), log.capacity())
+        };
+        let mut queue = self.queue.borrow_mut();
+        queue_warning_records(&mut queue, records, capacity);
+        let log = self.log.borrow();
+        drain_warning_records(&ruby, &mut queue, |category| log.policy(category))
+    }
+}
+
+unsafe impl Send for MagnusWarningLog {}
+
+pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
+    let log_class = module.define_class("WarningLog", ruby.class_object())?;
+    log_class.define_singleton_method("new", function!(MagnusWarningLog::new, -1))?;
+    log_class.define_method("begin_frame", method!(MagnusWarningLog::begin_frame, 1))?;
+    log_class.define_method("push", method!(MagnusWarningLog::push, 3))?;
+    log_class.define_method("set_policy", method!(MagnusWarningLog::set_policy, 2))?;
+    log_class.define_method("drain", method!(MagnusWarningLog::drain, 0))?;
+
+    Ok(())
+}
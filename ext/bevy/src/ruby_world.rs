@@ -1,9 +1,11 @@
-use bevy_ruby::WorldWrapper;
-use magnus::{function, method, prelude::*, Error, RArray, RModule, Ruby};
+use bevy_ruby::{WorldSnapshot, WorldSpriteSync, WorldSyncBinding, WorldSyncOperation, WorldWrapper};
+use magnus::{function, method, prelude::*, Error, RArray, RHash, RModule, Ruby, Value};
 use std::cell::RefCell;
+use std::collections::HashMap;
 
 use crate::ruby_component::RubyComponent;
 use crate::ruby_entity::RubyEntity;
+use crate::ruby_query::RubyQueryBuilder;
 
 #[magnus::wrap(class = "Bevy::World", free_immediately, size)]
 pub struct RubyWorld {
@@ -66,32 +68,295 @@ impl RubyWorld {
             .map_err(|e| Error::new(Ruby::get().unwrap().exception_runtime_error(), e.to_string()))
     }
 
+    fn remove(&self, entity: &RubyEntity, type_name: String) -> Result<(), Error> {
+        self.inner
+            .borrow()
+            .remove_component(entity.inner(), &type_name)
+            .map_err(|e| Error::new(Ruby::get().unwrap().exception_runtime_error(), e.to_string()))
+    }
+
+    fn get_or_insert(
+        &self,
+        entity: &RubyEntity,
+        type_name: String,
+        default: &RubyComponent,
+    ) -> Result<RubyComponent, Error> {
+        self.inner
+            .borrow()
+            .get_or_insert_component(entity.inner(), &type_name, default.inner())
+            .map(RubyComponent::from_dynamic)
+            .map_err(|e| Error::new(Ruby::get().unwrap().exception_runtime_error(), e.to_string()))
+    }
+
     fn has_component(&self, entity: &RubyEntity, type_name: String) -> bool {
         self.inner.borrow().has_component(entity.inner(), &type_name)
     }
 
-    fn query(&self, type_names: RArray) -> Result<RArray, Error> {
+    /// Accepts an Array of component type names or a `Bevy::QueryBuilder`,
+    /// whose `with` and `without` filters are applied too.
+    fn query(&self, query: Value) -> Result<RArray, Error> {
         let ruby = Ruby::get().unwrap();
-        let mut names: Vec<String> = Vec::new();
+        let (fetch, with, without) = match <&RubyQueryBuilder>::try_convert(query) {
+            Ok(builder) => {
+                let builder = builder.inner();
+                (
+                    builder.fetch_components().to_vec(),
+                    builder.with_components().to_vec(),
+                    builder.without_components().to_vec(),
+                )
+            }
+            Err(_) => {
+                let type_names = RArray::try_convert(query)?;
+                let mut names: Vec<String> = Vec::new();
+                for item in type_names.into_iter() {
+                    names.push(String::try_convert(item)?);
+                }
+                (names, Vec::new(), Vec::new())
+            }
+        };
 
-        for item in type_names.into_iter() {
-            names.push(String::try_convert(item)?);
+        let as_refs = |names: &[String]| names.iter().map(|s| s.as_str()).collect::<Vec<&str>>();
+        let entities = self
+            .inner
+            .borrow()
+            .query_entities_filtered(&as_refs(&fetch), &as_refs(&with), &as_refs(&without));
+
+        let result = ruby.ary_new();
+        for entity in entities {
+            result.push(RubyEntity::new(entity))?;
         }
 
-        let name_refs: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
-        let entities = self.inner.borrow().query_entities_with(&name_refs);
+        Ok(result)
+    }
 
-        let result = ruby.ary_new();
+    fn query_changed(&self, type_name: String) -> Result<RArray, Error> {
+        let ruby = Ruby::get().unwrap();
+        let entities = self.inner.borrow().query_changed(&type_name);
+
+        let result = ruby.ary_new_capa(entities.len());
         for entity in entities {
             result.push(RubyEntity::new(entity))?;
         }
 
         Ok(result)
     }
+
+    /// Entities whose `type_name` component was stored after `since_generation`.
+    fn changed_entities(&self, type_name: String, since_generation: u64) -> Result<RArray, Error> {
+        let ruby = Ruby::get().unwrap();
+        let entities = self.inner.borrow().changed_entities(&type_name, since_generation);
+
+        let result = ruby.ary_new_capa(entities.len());
+        for entity in entities {
+            result.push(RubyEntity::new(entity))?;
+        }
+
+        Ok(result)
+    }
+
+    fn generation(&self) -> u64 {
+        self.inner.borrow().generation()
+    }
+
+    fn count(&self) -> usize {
+        self.inner.borrow().count()
+    }
+
+    fn all_entities(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().unwrap();
+        let entities = self.inner.borrow().all_entities();
+
+        let result = ruby.ary_new_capa(entities.len());
+        for entity in entities {
+            result.push(RubyEntity::new(entity))?;
+        }
+
+        Ok(result)
+    }
+
+    fn entity_count(&self) -> usize {
+        self.inner.borrow().entity_count()
+    }
+
+    fn world_hash(&self) -> u64 {
+        self.inner.borrow().world_hash()
+    }
+
+    fn snapshot(&self) -> RubyWorldSnapshot {
+        RubyWorldSnapshot {
+            inner: self.inner.borrow().snapshot(),
+        }
+    }
 }
 
 unsafe impl Send for RubyWorld {}
 
+/// Read-only copy of a world's native components, safe to read from other threads.
+#[magnus::wrap(class = "Bevy::WorldSnapshot", free_immediately, size)]
+pub struct RubyWorldSnapshot {
+    inner: WorldSnapshot,
+}
+
+impl RubyWorldSnapshot {
+    fn entity_exists(&self, entity: &RubyEntity) -> bool {
+        self.inner.entity_exists(entity.inner())
+    }
+
+    fn get(&self, entity: &RubyEntity, type_name: String) -> Result<RubyComponent, Error> {
+        self.inner
+            .get_component(entity.inner(), &type_name)
+            .map(RubyComponent::from_dynamic)
+            .map_err(|e| Error::new(Ruby::get().unwrap().exception_runtime_error(), e.to_string()))
+    }
+
+    fn has_component(&self, entity: &RubyEntity, type_name: String) -> bool {
+        self.inner.has_component(entity.inner(), &type_name)
+    }
+
+    fn query(&self, type_names: Vec<String>) -> Result<RArray, Error> {
+        let ruby = Ruby::get().unwrap();
+        let names: Vec<&str> = type_names.iter().map(|s| s.as_str()).collect();
+        let entities = self.inner.query_entities_with(&names);
+
+        let result = ruby.ary_new_capa(entities.len());
+        for entity in entities {
+            result.push(RubyEntity::new(entity))?;
+        }
+
+        Ok(result)
+    }
+
+    fn count(&self) -> usize {
+        self.inner.count()
+    }
+
+    fn shared_with(&self, world: &RubyWorld, entity: &RubyEntity, type_name: String) -> bool {
+        self.inner
+            .shares_component_with(&world.inner.borrow(), entity.inner(), &type_name)
+    }
+}
+
+// Holds only Arc-shared component data: no Ruby objects and no world reference.
+unsafe impl Send for RubyWorldSnapshot {}
+
+#[magnus::wrap(class = "Bevy::WorldSpriteBinding", free_immediately, size)]
+pub struct RubyWorldSpriteBinding {
+    inner: RefCell<WorldSpriteSync>,
+}
+
+impl RubyWorldSpriteBinding {
+    fn new(component_type: String, transform_from: String, fields: RHash) -> Result<Self, Error> {
+        let ruby = Ruby::get().expect("Ruby runtime not available");
+        let mut binding = WorldSyncBinding {
+            component_type,
+            transform_from,
+            ..Default::default()
+        };
+
+        let fields: HashMap<String, String> = fields.to_hash_map()?;
+        for (key, field) in fields {
+            let target = match key.as_str() {
+                "color" => &mut binding.fields.color,
+                "size" => &mut binding.fields.size,
+                "flip_x" => &mut binding.fields.flip_x,
+                "flip_y" => &mut binding.fields.flip_y,
+                "x" => &mut binding.fields.x,
+                "y" => &mut binding.fields.y,
+                "z" => &mut binding.fields.z,
+                "rotation" => &mut binding.fields.rotation,
+                _ => {
+                    return Err(Error::new(
+                        ruby.exception_arg_error(),
+                        format!("Unknown auto_sync field: {}", key),
+                    ));
+                }
+            };
+            *target = field;
+        }
+
+        Ok(Self {
+            inner: RefCell::new(WorldSpriteSync::new(binding)),
+        })
+    }
+
+    fn component_type(&self) -> String {
+        self.inner.borrow().binding.component_type.clone()
+    }
+
+    fn transform_from(&self) -> String {
+        self.inner.borrow().binding.transform_from.clone()
+    }
+
+    fn synced_ids(&self) -> Vec<u64> {
+        self.inner.borrow().synced_ids()
+    }
+
+    fn plan(&self, world: &RubyWorld) -> Result<RHash, Error> {
+        let operations = self.inner.borrow_mut().plan(&world.inner.borrow());
+        operations_to_hash(operations)
+    }
+
+    fn release(&self) -> Result<RHash, Error> {
+        let operations = self.inner.borrow_mut().release();
+        operations_to_hash(operations)
+    }
+}
+
+unsafe impl Send for RubyWorldSpriteBinding {}
+
+/// Converts derived operations to `{ sync: [{ id:, sprite:, transform: }], remove: [ids] }`,
+/// with `sprite` and `transform` shaped like the hashes `RenderApp#sync_sprite` takes.
+fn operations_to_hash(operations: Vec<WorldSyncOperation>) -> Result<RHash, Error> {
+    let ruby = Ruby::get().expect("Ruby runtime not available");
+    let syncs = ruby.ary_new();
+    let removals = ruby.ary_new();
+
+    for operation in operations {
+        match operation {
+            WorldSyncOperation::Sync {
+                ruby_entity_id,
+                sprite_data,
+                x,
+                y,
+                z,
+                rotation,
+            } => {
+                let sprite = ruby.hash_new();
+                sprite.aset(ruby.to_symbol("color_r"), sprite_data.color_r)?;
+                sprite.aset(ruby.to_symbol("color_g"), sprite_data.color_g)?;
+                sprite.aset(ruby.to_symbol("color_b"), sprite_data.color_b)?;
+                sprite.aset(ruby.to_symbol("color_a"), sprite_data.color_a)?;
+                sprite.aset(ruby.to_symbol("flip_x"), sprite_data.flip_x)?;
+                sprite.aset(ruby.to_symbol("flip_y"), sprite_data.flip_y)?;
+                if sprite_data.has_custom_size {
+                    sprite.aset(ruby.to_symbol("custom_size_x"), sprite_data.custom_size_x)?;
+                    sprite.aset(ruby.to_symbol("custom_size_y"), sprite_data.custom_size_y)?;
+                }
+
+                let transform = ruby.hash_new();
+                transform.aset(ruby.to_symbol("x"), x)?;
+                transform.aset(ruby.to_symbol("y"), y)?;
+                transform.aset(ruby.to_symbol("z"), z)?;
+                transform.aset(ruby.to_symbol("rotation"), rotation)?;
+
+                let entry = ruby.hash_new();
+                entry.aset(ruby.to_symbol("id"), ruby_entity_id)?;
+                entry.aset(ruby.to_symbol("sprite"), sprite)?;
+                entry.aset(ruby.to_symbol("transform"), transform)?;
+                syncs.push(entry)?;
+            }
+            WorldSyncOperation::Remove { ruby_entity_id } => {
+                removals.push(ruby_entity_id)?;
+            }
+        }
+    }
+
+    let result = ruby.hash_new();
+    result.aset(ruby.to_symbol("sync"), syncs)?;
+    result.aset(ruby.to_symbol("remove"), removals)?;
+    Ok(result)
+}
+
 pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     let class = module.define_class("World", ruby.class_object())?;
     class.define_singleton_method("new", function!(RubyWorld::new, 0))?;
@@ -101,7 +366,33 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     class.define_method("despawn_native", method!(RubyWorld::despawn, 1))?;
     class.define_method("insert", method!(RubyWorld::insert, 2))?;
     class.define_method("get", method!(RubyWorld::get, 2))?;
+    class.define_method("get_or_insert", method!(RubyWorld::get_or_insert, 3))?;
+    class.define_method("remove", method!(RubyWorld::remove, 2))?;
     class.define_method("has_component?", method!(RubyWorld::has_component, 2))?;
     class.define_method("query", method!(RubyWorld::query, 1))?;
+    class.define_method("query_changed", method!(RubyWorld::query_changed, 1))?;
+    class.define_method("changed_entities", method!(RubyWorld::changed_entities, 2))?;
+    class.define_method("generation", method!(RubyWorld::generation, 0))?;
+    class.define_method("count", method!(RubyWorld::count, 0))?;
+    class.define_method("all_entities", method!(RubyWorld::all_entities, 0))?;
+    class.define_method("entity_count", method!(RubyWorld::entity_count, 0))?;
+    class.define_method("world_hash", method!(RubyWorld::world_hash, 0))?;
+    class.define_method("snapshot", method!(RubyWorld::snapshot, 0))?;
+
+    let snapshot_class = module.define_class("WorldSnapshot", ruby.class_object())?;
+    snapshot_class.define_method("entity_exists?", method!(RubyWorldSnapshot::entity_exists, 1))?;
+    snapshot_class.define_method("get", method!(RubyWorldSnapshot::get, 2))?;
+    snapshot_class.define_method("has_component?", method!(RubyWorldSnapshot::has_component, 2))?;
+    snapshot_class.define_method("query", method!(RubyWorldSnapshot::query, 1))?;
+    snapshot_class.define_method("count", method!(RubyWorldSnapshot::count, 0))?;
+    snapshot_class.define_method("shared_with?", method!(RubyWorldSnapshot::shared_with, 3))?;
+
+    let binding_class = module.define_class("WorldSpriteBinding", ruby.class_object())?;
+    binding_class.define_singleton_method("new", function!(RubyWorldSpriteBinding::new, 3))?;
+    binding_class.define_method("component_type", method!(RubyWorldSpriteBinding::component_type, 0))?;
+    binding_class.define_method("transform_from", method!(RubyWorldSpriteBinding::transform_from, 0))?;
+    binding_class.define_method("synced_ids", method!(RubyWorldSpriteBinding::synced_ids, 0))?;
+    binding_class.define_method("plan", method!(RubyWorldSpriteBinding::plan, 1))?;
+    binding_class.define_method("release", method!(RubyWorldSpriteBinding::release, 0))?;
     Ok(())
 }
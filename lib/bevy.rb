@@ -13,6 +13,7 @@ require_relative 'bevy/transform'
 require_relative 'bevy/camera'
 require_relative 'bevy/material'
 require_relative 'bevy/sprite'
+require_relative 'bevy/palette'
 require_relative 'bevy/sprite_sheet'
 require_relative 'bevy/input'
 require_relative 'bevy/audio'
@@ -72,6 +73,12 @@ module Bevy
 
   class InvalidTypeError < ConversionError; end
 
+  # Raised when a warning of a category with the `:raise` policy is drained.
+  class WarningError < Error; end
+
+  # Raised by a renamed method's old name while `Bevy.deprecations = :raise`.
+  class DeprecationError < Error; end
+
   class World
     MESH_CLASSES = [
       Mesh::Rectangle, Mesh::Circle, Mesh::RegularPolygon,
@@ -132,6 +139,7 @@ module Bevy
 
     def despawn(entity)
       entity_id = entity.id
+      ParentRemoval.apply(self, entity) if hierarchy_components.key?(entity_id)
       despawn_native(entity)
       despawned_entity_ids << entity_id
       mesh_components.delete(entity_id)
@@ -309,12 +317,11 @@ module Bevy
       all_entities.find { |e| e.id == entity_id }
     end
 
-    def all_entities
-      @mesh_components.keys.map { |id| entity_from_id(id) }.compact
-    end
-
-    def entity_from_id(entity_id)
-      Entity.new(entity_id)
+    # True unless +previous_hash+, a `world_hash` taken earlier, still
+    # matches; nil counts as dirty. Meshes and hierarchy components kept on
+    # the Ruby side are not part of the hash.
+    def dirty_since?(previous_hash)
+      previous_hash.nil? || world_hash != previous_hash
     end
 
     private
@@ -153,9 +153,19 @@ module Bevy
       when :ease_out_bounce then ease_out_bounce(t)
       when :ease_in_out_bounce then ease_in_out_bounce(t)
       when Proc then easing.call(t)
-      else linear(t)
+      else shared(easing, t)
       end
     end
+
+    # Curves without a method above, such as :quart_in or :circ_out, come
+    # from Bevy::Ease; anything it does not know eases linearly.
+    def self.shared(easing, t)
+      return linear(t) unless easing.is_a?(Symbol) || easing.is_a?(String)
+
+      Ease.apply(easing, t)
+    rescue ArgumentError
+      linear(t)
+    end
   end
 
   class Keyframe
@@ -594,4 +604,89 @@ module Bevy
       'TweenGroup'
     end
   end
+
+  # Collects keyframe tracks for `App#play_clip`; the render app plays the
+  # compiled clip natively. `sample` mirrors the native interpolation.
+  class ClipBuilder
+    PROPERTIES = %i[x y rotation scale alpha].freeze
+    STEP_EASINGS = %i[step hold].freeze
+
+    Track = Struct.new(:entity_id, :property, :easing, :keyframes)
+
+    attr_reader :tracks
+    attr_accessor :speed
+
+    def initialize(speed: 1.0)
+      @speed = speed.to_f
+      @tracks = []
+    end
+
+    def track(entity_or_id, property, keyframes, ease: :linear)
+      property = property.to_sym
+      easing = ease.to_sym
+      raise ArgumentError, "Unknown clip property: #{property}" unless PROPERTIES.include?(property)
+      unless self.class.easing?(easing)
+        raise ArgumentError,
+              "Unknown clip easing: #{easing} (expected one of #{(Ease.names + STEP_EASINGS).join(', ')})"
+      end
+
+      entity_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      sorted = keyframes.map { |time, value| [[time.to_f, 0.0].max, value.to_f] }.sort_by(&:first)
+      @tracks << Track.new(entity_id.to_i, property, easing, sorted)
+      self
+    end
+
+    def empty?
+      @tracks.empty?
+    end
+
+    def duration
+      @tracks.map { |track| track.keyframes.last&.first || 0.0 }.max || 0.0
+    end
+
+    def sample(entity_or_id, property, time)
+      entity_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      track = @tracks.find { |t| t.entity_id == entity_id.to_i && t.property == property.to_sym }
+      track && self.class.sample_track(track, time.to_f)
+    end
+
+    def to_native
+      {
+        speed: @speed,
+        tracks: @tracks.map do |track|
+          {
+            entity_id: track.entity_id,
+            property: track.property.to_s,
+            easing: track.easing.to_s,
+            keyframes: track.keyframes
+          }
+        end
+      }
+    end
+
+    def self.sample_track(track, time)
+      keyframes = track.keyframes
+      return nil if keyframes.empty?
+      return keyframes.first.last if time <= keyframes.first.first
+      return keyframes.last.last if time >= keyframes.last.first
+
+      next_index = keyframes.index { |key_time, _| key_time > time }
+      start_time, start_value = keyframes[next_index - 1]
+      end_time, end_value = keyframes[next_index]
+      span = end_time - start_time
+      return end_value if span <= 0.0
+
+      start_value + (end_value - start_value) * ease(track.easing, (time - start_time) / span)
+    end
+
+    def self.ease(easing, t)
+      STEP_EASINGS.include?(easing) ? 0.0 : Ease.apply(easing, t.clamp(0.0, 1.0))
+    end
+
+    def self.easing?(easing)
+      STEP_EASINGS.include?(easing) || !Ease.apply(easing, 0.0).nil?
+    rescue ArgumentError
+      false
+    end
+  end
 end
@@ -43,6 +43,9 @@ module Bevy
   end
 
   class SystemContext
+    TRANSFORM_SNAPSHOT_STRIDE = 4
+    POSITION_SNAPSHOT_STRIDE = 3
+
     attr_reader :world, :resources, :events, :time, :keyboard, :mouse, :gamepads, :app
 
     def initialize(world:, resources:, events:, time:, keyboard:, mouse:, gamepads:, app:, render_app: nil)
@@ -101,7 +104,7 @@ module Bevy
       if @render_app
         @render_app.key_pressed?(key)
       else
-        @keyboard.pressed?(key)
+        !input_captured? && @keyboard.pressed?(key)
       end
     end
 
@@ -109,16 +112,24 @@ module Bevy
       if @render_app
         @render_app.key_just_pressed?(key)
       else
-        @keyboard.just_pressed?(key)
+        !input_captured? && @keyboard.just_pressed?(key)
       end
     end
 
+    # Seconds the key has been held, 0.0 when it is not pressed. Only the
+    # render app keeps the timers, so this is 0.0 without one.
+    def key_held_duration(key)
+      return 0.0 unless @render_app.respond_to?(:key_held_duration)
+
+      @render_app.key_held_duration(key).to_f
+    end
+
     def mouse_pressed?(button)
       button_str = button.to_s.upcase
       if @render_app
         @render_app.mouse_button_pressed?(button_str)
       else
-        @mouse.pressed?(button_str)
+        !input_captured? && @mouse.pressed?(button_str)
       end
     end
 
@@ -127,10 +138,116 @@ module Bevy
       if @render_app
         @render_app.mouse_button_just_pressed?(button_str)
       else
-        @mouse.just_pressed?(button_str)
+        !input_captured? && @mouse.just_pressed?(button_str)
+      end
+    end
+
+    # Reads the key even while input is captured, e.g. for a focused text field.
+    def raw_key_pressed?(key)
+      if @render_app
+        @render_app.raw_key_pressed?(key)
+      else
+        @keyboard.pressed?(key)
+      end
+    end
+
+    def raw_key_just_pressed?(key)
+      if @render_app
+        @render_app.raw_key_just_pressed?(key)
+      else
+        @keyboard.just_pressed?(key)
+      end
+    end
+
+    def raw_mouse_pressed?(button)
+      button_str = button.to_s.upcase
+      if @render_app
+        @render_app.raw_mouse_button_pressed?(button_str)
+      else
+        @mouse.pressed?(button_str)
       end
     end
 
+    def set_input_captured(captured)
+      @app.set_input_captured(captured)
+    end
+
+    def input_captured?
+      @app.input_captured?
+    end
+
+    def bind_action(action, **sources)
+      @app.bind_action(action, **sources)
+    end
+
+    def action_pressed?(action)
+      @app.action_pressed?(action)
+    end
+
+    def action_just_pressed?(action)
+      @app.action_just_pressed?(action)
+    end
+
+    def action_just_released?(action)
+      @app.action_just_released?(action)
+    end
+
+    def set_window_mode(mode)
+      @app.set_window_mode(mode)
+    end
+
+    def window_mode
+      @app.window_mode
+    end
+
+    def set_fullscreen(fullscreen)
+      @app.set_fullscreen(fullscreen)
+    end
+
+    def fullscreen?
+      @app.fullscreen?
+    end
+
+    def toggle_fullscreen
+      @app.toggle_fullscreen
+    end
+
+    def set_clear_color(*color)
+      @app.set_clear_color(*color)
+    end
+
+    def set_max_fps(fps)
+      @app.set_max_fps(fps)
+    end
+
+    def set_window_title(title)
+      @app.set_window_title(title)
+    end
+
+    def set_window_size(width, height)
+      @app.set_window_size(width, height)
+    end
+
+    def set_cursor_visible(visible)
+      @app.set_cursor_visible(visible)
+    end
+
+    def cursor_visible?
+      @app.cursor_visible?
+    end
+
+    def set_cursor_grab(mode)
+      @app.set_cursor_grab(mode)
+    end
+
+    def cursor_grab
+      @app.cursor_grab
+    end
+
+    def window_size
+      @app.window_size
+    end
+
     def mouse_position
       if @render_app
         pos = @render_app.mouse_position
@@ -140,6 +257,27 @@ module Bevy
       end
     end
 
+    # Mouse movement this frame, y up. Raw motion with a render app, so it
+    # keeps reporting while the cursor is grabbed or outside the window.
+    def mouse_delta
+      if @render_app.respond_to?(:mouse_delta)
+        delta = @render_app.mouse_delta
+        Vec2.new(delta[0], delta[1])
+      else
+        @mouse.delta
+      end
+    end
+
+    # Scrolling this frame in lines; positive y scrolls up.
+    def mouse_scroll
+      if @render_app.respond_to?(:scroll_delta)
+        scroll = @render_app.scroll_delta
+        Vec2.new(scroll[0], scroll[1])
+      else
+        @mouse.scroll_delta
+      end
+    end
+
     def gamepad(gamepad_id = nil)
       if gamepad_id
         @gamepads.get(gamepad_id)
@@ -237,7 +375,97 @@ module Bevy
 
     def picked?(entity_or_id, kind: nil)
       target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
-      picking_events(kind).any? { |event| event.target_id == target_id.to_i }
+      picking_events(kind).any? { |event| !event.hit_region? && event.target_id == target_id.to_i }
+    end
+
+    def hit_region_events(kind = nil)
+      picking_events(kind).select(&:hit_region?)
+    end
+
+    def hit_region_picked?(id, kind: nil)
+      hit_region_events(kind).any? { |event| event.target_id == id.to_i }
+    end
+
+    def virtual_cursor_picking_events(kind = nil)
+      pointer_id = @app.virtual_cursor_pointer_id
+      return [] unless pointer_id
+
+      picking_events(kind).select { |event| event.pointer_id == pointer_id }
+    end
+
+    def virtual_cursor_position
+      @app.virtual_cursor_position
+    end
+
+    # nil until the first rendered frame reports window metrics.
+    def safe_area
+      @app.safe_area
+    end
+
+    # File drag-and-drop entries received this frame.
+    def file_drops
+      @app.file_drops
+    end
+
+    # Gamepad connects and disconnects received this frame.
+    def gamepad_connection_events
+      @app.gamepad_connection_events
+    end
+
+    def stop_all_rumble
+      @app.stop_all_rumble
+    end
+
+    def rumble_active?(gamepad_id)
+      @app.rumble_active?(gamepad_id)
+    end
+
+    def asset_registry
+      @app.asset_registry
+    end
+
+    # Hot-reload changes to registered assets found this frame.
+    def asset_changes
+      @app.asset_changes
+    end
+
+    # Fingers on the screen this frame, separate from the mouse state.
+    def touches
+      @app.touches
+    end
+
+    def touch_just_pressed?(id = nil)
+      @app.touch_just_pressed?(id)
+    end
+
+    def touch_position(id)
+      @app.touch_position(id)
+    end
+
+    # Characters typed this frame; see `App#set_text_input_enabled`.
+    def typed_text
+      @app.typed_text
+    end
+
+    def set_text_input_enabled(enabled)
+      @app.set_text_input_enabled(enabled)
+    end
+
+    def text_input_enabled?
+      @app.text_input_enabled?
+    end
+
+    def dropped_file_paths
+      file_drops.select(&:dropped?).map(&:path)
+    end
+
+    def usable_size
+      area = safe_area
+      area ? area.usable_size : Vec2.zero
+    end
+
+    def accessibility_active?
+      @app.accessibility_active?
     end
 
     def camera_position
@@ -271,10 +499,257 @@ module Bevy
 
     alias camera_zoom camera_scale
     alias set_camera_zoom set_camera_scale
+
+    # Range `set_camera_scale`, `zoom_in` and `zoom_out` clamp the scale to;
+    # 0.1..10.0 until set.
+    def set_zoom_limits(min, max)
+      @render_app&.set_zoom_limits(min.to_f, max.to_f)
+    end
+
+    def zoom_limits
+      @render_app ? @render_app.zoom_limits : [0.1, 10.0]
+    end
+
+    # Zooms in by shrinking the camera scale by `amount`; returns the new scale.
+    def zoom_in(amount)
+      @render_app ? @render_app.zoom_in(amount.to_f) : 1.0
+    end
+
+    # Zooms out by growing the camera scale by `amount`; returns the new scale.
+    def zoom_out(amount)
+      @render_app ? @render_app.zoom_out(amount.to_f) : 1.0
+    end
+
+    def camera_rotation
+      @render_app ? @render_app.camera_rotation : 0.0
+    end
+
+    # Shakes the camera on top of `camera_position`, which keeps its value;
+    # the shake decays to rest over `duration` seconds.
+    def shake_camera(intensity, duration)
+      @render_app&.shake_camera(intensity.to_f, duration.to_f)
+    end
+
+    # Applies the translation, z-rotation and uniform scale of a `Transform`
+    # (or its sync hash) to the camera in one step.
+    def set_camera_transform(transform)
+      return unless @render_app
+
+      @render_app.set_camera_transform(transform.respond_to?(:to_sync_hash) ? transform.to_sync_hash : transform)
+    end
+    Bevy.deprecate_method(self, :sync_camera_to_transform, :set_camera_transform, '2.0.0')
+
+    # Keeps the camera `offset` away from a synced sprite or mesh, read after
+    # the frame's syncs so the camera never trails it by a frame. Setting the
+    # camera position or transform stops following.
+    def camera_follow_entity(entity_or_id, offset: [0.0, 0.0])
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      offset = [offset.x, offset.y] if offset.respond_to?(:x)
+      @render_app&.camera_follow_entity(target_id.to_i, offset: offset.map(&:to_f))
+    end
+
+    def stop_camera_follow
+      @render_app&.stop_camera_follow
+    end
+
+    # `{id:, offset: [x, y]}` of the followed entity, or nil.
+    def camera_follow
+      @render_app&.camera_follow
+    end
+
+    def floating_text(x, y, text, **options)
+      @app.floating_text(x, y, text, **options)
+    end
+
+    def group_due?(group)
+      @app.group_due?(group)
+    end
+
+    def spin(entity_or_id, radians_per_second)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.spin(target_id.to_i, radians_per_second.to_f)
+    end
+
+    def pulse(entity_or_id, scale_min:, scale_max:, period:)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.pulse(target_id.to_i, scale_min: scale_min.to_f, scale_max: scale_max.to_f, period: period.to_f)
+    end
+
+    def bob(entity_or_id, amplitude:, period:, axis: :y)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.bob(target_id.to_i, amplitude: amplitude.to_f, period: period.to_f, axis: axis)
+    end
+
+    def clear_behaviors(entity_or_id)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.clear_behaviors(target_id.to_i)
+    end
+
+    # Wobbles a sprite synced with `jelly:` by kicking its vertices along
+    # `(x, y)`; the edges move up to `strength` units per second. Plain
+    # sprites ignore it.
+    def apply_impulse(entity_or_id, x, y, strength)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.apply_impulse(target_id.to_i, x.to_f, y.to_f, strength.to_f)
+    end
+
+    # Draws a synced sprite above the other sprites of its `z_index` layer,
+    # still below every higher layer.
+    def bring_to_front(entity_or_id)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.bring_to_front(target_id.to_i)
+    end
+
+    def send_to_back(entity_or_id)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.send_to_back(target_id.to_i)
+    end
+
+    # Moves a synced sprite to `index` within its layer, 0 drawing below the rest.
+    def set_draw_order(entity_or_id, index)
+      raise ArgumentError, "draw order index must be non-negative, got #{index}" if index.negative?
+
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.set_draw_order(target_id.to_i, index.to_i)
+    end
+
+    # Index of a synced sprite within its layer as of the last frame, or nil.
+    def draw_order(entity_or_id)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.draw_order(target_id.to_i)
+    end
+
+    # Switches a synced sprite between levels as the camera scale changes.
+    # Each level is `{ max_scale:, sprite:, hide: }`; `sprite` takes a `Sprite`
+    # or its sync hash, and the last level may leave out `max_scale`.
+    def set_lod(entity_or_id, levels:)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.set_lod(target_id.to_i, levels.map { |level| lod_level_hash(level) })
+    end
+
+    def clear_lod(entity_or_id)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.clear_lod(target_id.to_i)
+    end
+
+    # Fraction of a boundary scale the camera must pass before the level switches.
+    def set_lod_margin(margin)
+      @render_app&.set_lod_margin(margin.to_f)
+    end
+
+    # Index of the active level, or nil before the first frame selected one.
+    def lod_level(entity_or_id)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.lod_level(target_id.to_i)
+    end
+
+    def set_parallax_factor(group_or_layer, factor_x, factor_y)
+      @app.set_parallax_factor(group_or_layer, factor_x, factor_y)
+    end
+
+    def clear_parallax(group_or_layer)
+      @app.clear_parallax(group_or_layer)
+    end
+
+    def set_pixel_perfect(enabled)
+      @app.set_pixel_perfect(enabled)
+    end
+
+    def set_entity_pixel_perfect(entity_or_id, enabled)
+      @app.set_entity_pixel_perfect(entity_or_id, enabled)
+    end
+
+    def register_hit_region(id, x, y, width, height, layer: 0, screen_space: false)
+      @app.register_hit_region(id, x, y, width, height, layer: layer, screen_space: screen_space)
+    end
+
+    def update_hit_region(id, x, y, width, height, layer: nil, screen_space: nil)
+      @app.update_hit_region(id, x, y, width, height, layer: layer, screen_space: screen_space)
+    end
+
+    def remove_hit_region(id)
+      @app.remove_hit_region(id)
+    end
+
+    def enable_drag(entity_or_id, constrain_to: nil, snap_back: false, drag_layer: 100)
+      @app.enable_drag(entity_or_id, constrain_to: constrain_to, snap_back: snap_back, drag_layer: drag_layer)
+    end
+
+    def disable_drag(entity_or_id)
+      @app.disable_drag(entity_or_id)
+    end
+
+    # `{id:, position:, dropped_at:, origin:, drop_target:, hit_region:, snapped_back:}`
+    # for each drag released this frame.
+    def drag_events
+      @app.drag_events
+    end
+
+    # Flat [x0, y0, x1, y1, x2, y2, ...] triangles of a synced mesh in local space.
+    def mesh_triangles(entity_or_id)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.mesh_triangles(target_id.to_i)
+    end
+
+    # Flat [id, x, y, rotation, ...] of synced sprites as applied by the previous frame.
+    def transforms_snapshot(group: nil)
+      return [] unless @render_app
+
+      @render_app.transforms_snapshot(group: group&.to_s)
+    end
+
+    # Flat [id, x, y, ...] of synced sprites as applied by the previous frame.
+    def positions_snapshot(group: nil)
+      return [] unless @render_app
+
+      @render_app.positions_snapshot(group: group&.to_s)
+    end
+
+    # [id, x, y] of the first synced sprite the segment touches, or nil.
+    def raycast(x1, y1, x2, y2, group: nil)
+      return nil unless @render_app
+
+      @render_app.raycast(x1.to_f, y1.to_f, x2.to_f, y2.to_f, group: group&.to_s)
+    end
+
+    # Whether no other synced sprite lies between the centers of a and b;
+    # nil unless both were synced by the previous frame.
+    def line_of_sight?(entity_or_id_a, entity_or_id_b, blocking_group: nil)
+      return nil unless @render_app
+
+      id_a = entity_or_id_a.respond_to?(:id) ? entity_or_id_a.id : entity_or_id_a
+      id_b = entity_or_id_b.respond_to?(:id) ? entity_or_id_b.id : entity_or_id_b
+      @render_app.line_of_sight?(id_a.to_i, id_b.to_i, blocking_group: blocking_group&.to_s)
+    end
+
+    def rewind_to(seconds_ago)
+      @app.rewind_to(seconds_ago)
+    end
+
+    def resume_from_rewind
+      @app.resume_from_rewind
+    end
+
+    def rewinding?
+      @app.rewinding?
+    end
+
+    def frame_checksum
+      @app.frame_checksum
+    end
+
+    private
+
+    def lod_level_hash(level)
+      sprite = level[:sprite]
+      level.merge(sprite: sprite.respond_to?(:to_sync_hash) ? sprite.to_sync_hash : sprite).compact
+    end
   end
 
   class App
-    attr_reader :world, :resources, :events, :render_app, :time, :fixed_time, :keyboard, :mouse, :gamepads
+    attr_reader :world, :resources, :events, :render_app, :time, :fixed_time, :keyboard, :mouse, :gamepads,
+                :safe_area, :virtual_cursor_pointer_id, :file_drops, :gamepad_connection_events, :touches,
+                :asset_changes, :typed_text, :drag_events
 
     GAMEPAD_BUTTONS = [
       GamepadButton::SOUTH,
@@ -305,11 +780,28 @@ module Bevy
       GamepadAxis::RIGHT_TRIGGER
     ].freeze
 
-    def initialize(render: false, window: {})
+    WINDOW_MODES = %i[windowed fullscreen borderless].freeze
+    PRESENT_MODES = %i[fifo immediate mailbox auto_no_vsync].freeze
+    MSAA_SAMPLES = [1, 2, 4, 8].freeze
+    TEXTURE_PLACEHOLDERS = %i[white checkerboard transparent].freeze
+    CURSOR_GRAB_MODES = %i[none confined locked].freeze
+    WARNING_POLICIES = %i[silent collect raise].freeze
+    # Warnings kept between `drain_warnings` calls; older ones are dropped first.
+    WARNING_BUFFER = 256
+    DEFAULT_WINDOW_TITLE = 'Bevy Ruby'
+    DEFAULT_WINDOW_SIZE = [800.0, 600.0].freeze
+    DEFAULT_FLOATING_TEXT_CAP = 64
+    DEFAULT_SLEEP_EPSILON = 1.0e-4
+    DEFAULT_SLEEP_FRAMES = 30
+
+    def initialize(render: false, window: {}, sync_systems: {})
       @world = World.new
       @resources = Resources.new
       @events = EventRegistry.new
       @events.register(PickingEvent)
+      @events.register(SafeAreaChanged)
+      @events.register(WindowCloseRequested)
+      @events.register(FileDragAndDrop)
       @systems = Hash.new { |h, k| h[k] = [] }
       @plugins = []
       @running = false
@@ -320,7 +812,55 @@ module Bevy
       @gamepads = Gamepads.new
       @render_enabled = render
       @window_config = window
+      @window_mode = normalize_window_mode(window.fetch(:mode, :windowed))
+      @present_mode = normalize_present_mode(window)
+      @msaa = normalize_msaa(window[:msaa])
+      @texture_placeholder = normalize_texture_placeholder(window.fetch(:texture_placeholder, :white))
+      @clear_color = window[:clear_color] && clear_color_components(window[:clear_color])
+      @max_fps = normalize_max_fps(window[:max_fps])
+      @sync_systems = sync_systems
       @render_app = nil
+      @safe_area = nil
+      @file_drops = []
+      @gamepad_connection_events = []
+      @touches = []
+      @typed_text = ''
+      @text_input_enabled = false
+      @asset_registry = nil
+      @asset_changes = []
+      @clips = {}
+      @next_clip_handle = 0
+      @virtual_cursor_options = nil
+      @virtual_cursor_pointer_id = nil
+      @bound_world = nil
+      @world_binding = nil
+      @remove_defer = 0
+      @floating_text_cap = DEFAULT_FLOATING_TEXT_CAP
+      @group_update_divisors = {}
+      @sleep_thresholds = nil
+      @accessibility_enabled = true
+      @input_captured = false
+      @action_bindings = {}
+      @cursor_visible = true
+      @cursor_grab = :none
+      @detailed_stats = false
+      @parallax_factors = {}
+      @pixel_perfect = window.fetch(:pixel_perfect, false) ? true : false
+      @decorations = window.fetch(:decorations, true) ? true : false
+      @transparent = window.fetch(:transparent, false) ? true : false
+      @pixel_perfect_entities = {}
+      @hit_regions = {}
+      @draggables = {}
+      @drag_events = []
+      @warnings = []
+      @warning_policies = {}
+      @rewind_options = nil
+      @rewinding = false
+      @recorded_checksums = nil
+      @journal = nil
+      @journal_replay = nil
+      @debug_bundle_key = nil
+      @on_exit = nil
 
       yield self if block_given?
     end
@@ -382,6 +922,7 @@ module Bevy
 
     def update
       @time.update
+      Audio.update(@time.delta_seconds)
       accumulate_fixed_time
 
       run_schedule(Schedule::FIRST)
@@ -404,35 +945,1108 @@ module Bevy
       @running
     end
 
-    private
+    # Calls the block once with :user_close, :stop_called or :error as the
+    # app stops. With rendering it runs in the last frame, before the window
+    # and the Bevy world go away, so state can still be read and saved.
+    def on_exit(&block)
+      raise ArgumentError, 'on_exit requires a block' unless block
 
-    def run_startup_systems
-      context = build_context
-      @systems[Schedule::STARTUP].each { |s| s.run(context) }
+      @on_exit = block
+      apply_on_exit if @render_app
+      self
     end
 
-    def run_main_loop
-      while @running
-        update
-        sleep(0.001)
-      end
+    def build_clip(speed: 1.0)
+      ClipBuilder.new(speed: speed)
     end
 
-    def run_render_loop
-      @render_app = RenderApp.new(@window_config)
-      @render_app.initialize!
+    # Plays a clip on the render app (once it is running) and returns its
+    # handle. `on_complete` is called when the clip reaches its end, but not
+    # when it is stopped.
+    def play_clip(clip, on_complete: nil)
+      @next_clip_handle += 1
+      handle = @next_clip_handle
+      @clips[handle] = { clip: clip, on_complete: on_complete, started: false, seek: nil }
+      start_clip(handle)
+      handle
+    end
 
-      @render_app.run do
-        sync_input_from_bevy
-        update
-        sync_sprites_to_bevy
+    def stop_clip(handle)
+      entry = @clips.delete(handle)
+      @render_app.stop_clip(handle) if entry && entry[:started] && @render_app.respond_to?(:stop_clip)
+      self
+    end
+
+    def seek_clip(handle, time)
+      entry = @clips[handle]
+      return self unless entry
+
+      if entry[:started]
+        @render_app.seek_clip(handle, time.to_f)
+      else
+        entry[:seek] = time.to_f
       end
+      self
+    end
 
-      @running = false
+    def clip_playing?(handle)
+      @clips.key?(handle)
     end
 
-    def sync_input_from_bevy
-      return unless @render_app
+    # Clip tracks skipped because their entity was removed while playing.
+    def clip_skipped_tracks
+      @render_app.respond_to?(:clip_skipped_tracks) ? @render_app.clip_skipped_tracks : 0
+    end
+
+    # Steers a picking pointer with a gamepad stick. The cursor is applied when
+    # the render app starts if called earlier; picking events it causes carry
+    # `virtual_cursor_pointer_id`.
+    def enable_virtual_cursor(gamepad_slot: 0, speed: 600.0, sprite: nil, button: GamepadButton::SOUTH,
+                              curve: StickCurve.new)
+      @virtual_cursor_options = {
+        gamepad_slot: gamepad_slot,
+        speed: speed.to_f,
+        button: button.to_s,
+        **curve.to_h
+      }
+      if sprite
+        @virtual_cursor_options[:sprite] = sprite.respond_to?(:to_sync_hash) ? sprite.to_sync_hash : sprite
+      end
+      apply_virtual_cursor
+      self
+    end
+
+    def disable_virtual_cursor
+      @virtual_cursor_options = nil
+      @virtual_cursor_pointer_id = nil
+      @render_app.disable_virtual_cursor if @render_app.respond_to?(:disable_virtual_cursor)
+      self
+    end
+
+    def virtual_cursor_enabled?
+      !@virtual_cursor_options.nil?
+    end
+
+    # Position relative to the window center with y up, like `mouse.position`.
+    def virtual_cursor_position
+      return nil unless @render_app.respond_to?(:virtual_cursor_position)
+
+      position = @render_app.virtual_cursor_position
+      position && Vec2.new(position[0], position[1])
+    end
+
+    # Stops or resumes sending accessibility nodes of synced sprites and texts
+    # to assistive technologies.
+    def set_accessibility_enabled(enabled)
+      @accessibility_enabled = enabled ? true : false
+      apply_accessibility_enabled
+      self
+    end
+
+    def accessibility_enabled?
+      @accessibility_enabled
+    end
+
+    # True while a screen reader or other assistive technology is connected.
+    def accessibility_active?
+      @render_app.respond_to?(:accessibility_active?) ? @render_app.accessibility_active? : false
+    end
+
+    # `{ accessible_label:, accessible_role: }` of the node a synced sprite or
+    # text had as of the last frame, or nil without one.
+    def accessibility_node(entity_or_id)
+      target_id = entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id
+      @render_app&.accessibility_node(target_id.to_i)
+    end
+
+    # While captured (e.g. a text field has focus), the gameplay key and mouse
+    # button queries report nothing pressed; the `raw_` queries keep working.
+    def set_input_captured(captured)
+      @input_captured = captured ? true : false
+      apply_input_captured
+      self
+    end
+
+    def input_captured?
+      @input_captured
+    end
+
+    # Binds a named action to keys, mouse buttons and gamepad buttons (on any
+    # gamepad), replacing its previous sources, e.g.
+    # `bind_action(:jump, keys: [KeyCode::SPACE], gamepad_buttons: ['South'])`.
+    # An action is pressed while any source is. Rebinding applies immediately.
+    def bind_action(action, keys: [], mouse_buttons: [], gamepad_buttons: [])
+      binding = {
+        keys: Array(keys).map(&:to_s),
+        mouse_buttons: Array(mouse_buttons).map { |button| button.to_s.upcase },
+        gamepad_buttons: Array(gamepad_buttons).map(&:to_s)
+      }
+      unknown = binding[:keys] - KeyCode.names
+      raise ArgumentError, "unknown key(s) for action #{action}: #{unknown.join(', ')}" unless unknown.empty?
+
+      @render_app.bind_action(action.to_s, binding) if @render_app.respond_to?(:bind_action)
+      @action_bindings[action.to_s] = binding
+      self
+    end
+
+    def unbind_action(action)
+      @render_app.unbind_action(action.to_s) if @render_app.respond_to?(:unbind_action)
+      !@action_bindings.delete(action.to_s).nil?
+    end
+
+    # `{ keys:, mouse_buttons:, gamepad_buttons: }` of an action, or nil when unbound.
+    def action_binding(action)
+      @action_bindings[action.to_s]&.transform_values(&:dup)
+    end
+
+    def actions
+      @action_bindings.keys
+    end
+
+    # Whether any source of the action is held; false without a render app
+    # or while input is captured.
+    def action_pressed?(action)
+      @render_app.respond_to?(:action_pressed?) && @render_app.action_pressed?(action.to_s)
+    end
+
+    def action_just_pressed?(action)
+      @render_app.respond_to?(:action_just_pressed?) && @render_app.action_just_pressed?(action.to_s)
+    end
+
+    def action_just_released?(action)
+      @render_app.respond_to?(:action_just_released?) && @render_app.action_just_released?(action.to_s)
+    end
+
+    # Assets registered here are checked for changes once a frame while hot
+    # reload is enabled; read what changed from `asset_changes`.
+    def asset_registry
+      @asset_registry ||= AssetRegistry.new
+    end
+
+    # Whether a finger touched down this frame; pass an id to ask about one touch.
+    def touch_just_pressed?(id = nil)
+      @touches.any? { |touch| touch[:phase] == 'started' && (id.nil? || touch[:id] == id) }
+    end
+
+    # [x, y] of the touch with `id`, or nil when it is not on the screen.
+    def touch_position(id)
+      @touches.find { |touch| touch[:id] == id }&.fetch(:position)
+    end
+
+    # While enabled, `typed_text` collects the characters typed each frame,
+    # following the keyboard layout and shift state. Key presses are still
+    # reported either way. Kept until the render app starts.
+    def set_text_input_enabled(enabled)
+      @text_input_enabled = enabled ? true : false
+      @render_app.text_input_enabled = @text_input_enabled if @render_app.respond_to?(:text_input_enabled=)
+      @typed_text = '' unless @text_input_enabled
+      self
+    end
+
+    def text_input_enabled?
+      @text_input_enabled
+    end
+
+    # Switches between `:windowed`, `:fullscreen` (exclusive) and `:borderless`.
+    # Before the render app starts this sets the launch mode, like `window: { mode: }`.
+    def set_window_mode(mode)
+      @window_mode = normalize_window_mode(mode)
+      @render_app.set_window_mode(@window_mode) if @render_app.respond_to?(:set_window_mode)
+      self
+    end
+
+    attr_reader :window_mode
+
+    # Fixed when the window opens; `nil` leaves Bevy's default (`:fifo`, vsync on).
+    attr_reader :present_mode
+
+    # Samples per pixel of the camera, fixed when the window opens; `nil` leaves Bevy's default (4).
+    attr_reader :msaa
+
+    # What sprites show while their texture loads, fixed when the window opens.
+    attr_reader :texture_placeholder
+
+    # Title bar and borders, fixed when the window opens.
+    def decorations?
+      @decorations
+    end
+
+    # Whether the desktop shows through the clear color, fixed when the window opens.
+    def transparent?
+      @transparent
+    end
+
+    def set_fullscreen(fullscreen)
+      set_window_mode(fullscreen ? :fullscreen : :windowed)
+    end
+
+    def fullscreen?
+      @window_mode != :windowed
+    end
+
+    # Fullscreen from windowed, windowed from `:fullscreen` or `:borderless`.
+    def toggle_fullscreen
+      set_fullscreen(!fullscreen?)
+    end
+
+    # Background color as a `Bevy::Color` or `r, g, b(, a)`. Before the render
+    # app starts this sets the initial color, like `window: { clear_color: }`.
+    def set_clear_color(*color)
+      @clear_color = clear_color_components(color)
+      @render_app.set_clear_color(*@clear_color) if @render_app.respond_to?(:set_clear_color)
+      self
+    end
+
+    attr_reader :clear_color
+
+    # Caps the frame rate by sleeping at the end of each frame, for games
+    # that would otherwise spin a core with vsync off. nil or 0 removes the
+    # cap; like `window: { max_fps: }` before the render app starts.
+    def set_max_fps(fps)
+      @max_fps = normalize_max_fps(fps)
+      @render_app.set_max_fps(@max_fps) if @render_app.respond_to?(:set_max_fps)
+      self
+    end
+
+    attr_reader :max_fps
+
+    # `[width, height, rgba]` of the latest frame read back in headless mode
+    # (`window: { headless: true }`), or `nil` before the first one arrives.
+    def read_pixels
+      @render_app.read_pixels if @render_app.respond_to?(:read_pixels)
+    end
+
+    # Stops the rumble and drops the patterns of every known gamepad,
+    # including requests made earlier this frame that are not forwarded yet.
+    def stop_all_rumble
+      @gamepads.clear_pending_rumbles
+      @render_app.stop_all_rumble if @render_app.respond_to?(:stop_all_rumble)
+      self
+    end
+
+    # Whether the gamepad was rumbling, or had a pattern queued, at the start
+    # of this frame. Requests made during the frame count from the next one.
+    def rumble_active?(gamepad_id)
+      return false unless @render_app.respond_to?(:rumble_active?)
+
+      @render_app.rumble_active?(gamepad_id)
+    end
+
+    # Moves the sprites of a group (String/Symbol) or layer (Integer, the
+    # sprite's z rounded) at `factor` times the camera's speed: 1.0 is normal,
+    # 0.0 stays fixed to the camera and above 1.0 moves faster. Positions stay
+    # as authored; the offset is added after behaviors and clips, and a group
+    # factor wins over a layer factor. Kept until the render app starts.
+    def set_parallax_factor(group_or_layer, factor_x, factor_y)
+      target = parallax_target(group_or_layer)
+      @parallax_factors[target] = [factor_x.to_f, factor_y.to_f]
+      if @render_app.respond_to?(:set_parallax_factor)
+        @render_app.set_parallax_factor(target, factor_x.to_f, factor_y.to_f)
+      end
+      self
+    end
+
+    # Puts the sprites of a group or layer back at their authored positions.
+    def clear_parallax(group_or_layer)
+      target = parallax_target(group_or_layer)
+      @parallax_factors.delete(target)
+      @render_app.clear_parallax(target) if @render_app.respond_to?(:clear_parallax)
+      self
+    end
+
+    # `[factor_x, factor_y]`, or nil when the group or layer has none.
+    def parallax_factor(group_or_layer)
+      @parallax_factors[parallax_target(group_or_layer)]
+    end
+
+    # Draws every sprite and text with its origin on a whole physical pixel,
+    # so pixel art doesn't shimmer as the camera moves. Only the rendered
+    # translation is rounded, after clips, behaviors and parallax; positions
+    # stay as authored. Like `window: { pixel_perfect: true }` before the
+    # render app starts.
+    def set_pixel_perfect(enabled)
+      @pixel_perfect = enabled ? true : false
+      @render_app.set_pixel_perfect(@pixel_perfect) if @render_app.respond_to?(:set_pixel_perfect)
+      self
+    end
+
+    def pixel_perfect?
+      @pixel_perfect
+    end
+
+    # Turns pixel-perfect drawing on or off for one entity's sprite and text,
+    # whatever the global setting. Kept until the render app starts.
+    def set_entity_pixel_perfect(entity_or_id, enabled)
+      target_id = (entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id).to_i
+      @pixel_perfect_entities[target_id] = enabled ? true : false
+      if @render_app.respond_to?(:set_entity_pixel_perfect)
+        @render_app.set_entity_pixel_perfect(target_id, @pixel_perfect_entities[target_id])
+      end
+      self
+    end
+
+    def entity_pixel_perfect?(entity_or_id)
+      target_id = (entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id).to_i
+      @pixel_perfect_entities.fetch(target_id, @pixel_perfect)
+    end
+
+    # Makes a rectangle pickable without rendering anything. Picking events on
+    # it carry the region ID as target_id and report hit_region? as true.
+    # (x, y) is the bottom-left corner in world units, or the top-left corner
+    # in window pixels with `screen_space: true`; `layer` orders it against
+    # sprites like a z position.
+    def register_hit_region(id, x, y, width, height, layer: 0, screen_space: false)
+      region = { x: x.to_f, y: y.to_f, width: width.to_f, height: height.to_f,
+                 layer: layer.to_i, screen_space: screen_space ? true : false }
+      @hit_regions[id.to_i] = region
+      send_hit_region(:register_hit_region, id.to_i, region)
+      self
+    end
+
+    # Moves or resizes a registered region; `layer` and `screen_space` are
+    # kept unless given. Unknown IDs are ignored.
+    def update_hit_region(id, x, y, width, height, layer: nil, screen_space: nil)
+      region = @hit_regions[id.to_i]
+      return self unless region
+
+      region.merge!(x: x.to_f, y: y.to_f, width: width.to_f, height: height.to_f)
+      region[:layer] = layer.to_i unless layer.nil?
+      region[:screen_space] = screen_space ? true : false unless screen_space.nil?
+      send_hit_region(:update_hit_region, id.to_i, region)
+      self
+    end
+
+    def remove_hit_region(id)
+      @hit_regions.delete(id.to_i)
+      @render_app.remove_hit_region(id.to_i) if @render_app.respond_to?(:remove_hit_region)
+      self
+    end
+
+    # `{x:, y:, width:, height:, layer:, screen_space:}`, or nil when unregistered.
+    def hit_region(id)
+      @hit_regions[id.to_i]&.dup
+    end
+
+    # Lets the left mouse button pick up a synced sprite and move it, keeping
+    # the grab offset. While held it draws on `drag_layer` and stays inside
+    # `constrain_to` ([x, y, width, height] in world units, bottom-left
+    # corner); with `snap_back: true` it eases back to where it was picked up
+    # on release. Every release is reported by `drag_events`, and a sprite left
+    # where it was dropped has its Transform moved there.
+    def enable_drag(entity_or_id, constrain_to: nil, snap_back: false, drag_layer: 100)
+      target_id = (entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id).to_i
+      @draggables[target_id] = { constrain_to: constrain_to&.map(&:to_f), snap_back: snap_back ? true : false,
+                                 drag_layer: drag_layer.to_i }
+      @render_app.enable_drag(target_id, @draggables[target_id]) if @render_app.respond_to?(:enable_drag)
+      self
+    end
+
+    # A sprite disabled while held is put down where it is, without an event.
+    def disable_drag(entity_or_id)
+      target_id = (entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id).to_i
+      @draggables.delete(target_id)
+      @render_app.disable_drag(target_id) if @render_app.respond_to?(:disable_drag)
+      self
+    end
+
+    def drag_enabled?(entity_or_id)
+      @draggables.key?((entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id).to_i)
+    end
+
+    # Hides or shows the cursor over the window; kept until the render app starts.
+    def set_cursor_visible(visible)
+      @cursor_visible = visible ? true : false
+      @render_app.set_cursor_visible(@cursor_visible) if @render_app.respond_to?(:set_cursor_visible)
+      self
+    end
+
+    def cursor_visible?
+      @cursor_visible
+    end
+
+    # `:none` frees the cursor, `:confined` keeps it inside the window and
+    # `:locked` pins it in place for mouse-look; `mouse_delta` keeps reporting
+    # raw motion while locked. Kept until the render app starts.
+    def set_cursor_grab(mode)
+      mode = mode.to_sym
+      unless CURSOR_GRAB_MODES.include?(mode)
+        raise ArgumentError, "unknown cursor grab mode: #{mode} (expected #{CURSOR_GRAB_MODES.join(', ')})"
+      end
+
+      @cursor_grab = mode
+      @render_app.set_cursor_grab(@cursor_grab) if @render_app.respond_to?(:set_cursor_grab)
+      self
+    end
+
+    attr_reader :cursor_grab
+
+    # Times each bridge system (syncs, camera, clips, ...) for `stats`.
+    # Off by default; kept until the render app starts.
+    def set_detailed_stats(enabled)
+      @detailed_stats = enabled ? true : false
+      @render_app.set_detailed_stats(@detailed_stats) if @render_app.respond_to?(:set_detailed_stats)
+      self
+    end
+
+    def detailed_stats?
+      @detailed_stats
+    end
+
+    # `{ systems: { name => micros }, systems_max: { name => micros },
+    # sleeping_count: }` for the previous frame, the maxima covering the last
+    # 120 frames. The timings are empty unless detailed stats are on.
+    def stats
+      return { systems: {}, systems_max: {}, sleeping_count: 0 } unless @render_app.respond_to?(:stats)
+
+      @render_app.stats
+    end
+
+    # Title bar text. Before the render app starts this sets the launch title,
+    # like `window: { title: }`.
+    def set_window_title(title)
+      @window_config = @window_config.merge(title: title.to_s)
+      @render_app.set_window_title(title.to_s) if @render_app.respond_to?(:set_window_title)
+      self
+    end
+
+    def window_title
+      @window_config.fetch(:title, DEFAULT_WINDOW_TITLE)
+    end
+
+    # Logical window size. Before the render app starts this sets the launch
+    # size, like `window: { width:, height: }`.
+    def set_window_size(width, height)
+      width = width.to_f
+      height = height.to_f
+      raise ArgumentError, "window size must be positive, got #{width}x#{height}" unless width.positive? && height.positive?
+
+      @window_config = @window_config.merge(width: width, height: height)
+      @render_app.set_window_size(width, height) if @render_app.respond_to?(:set_window_size)
+      self
+    end
+
+    # `[width, height]` in logical pixels; the live size once the window is open.
+    def window_size
+      return @render_app.window_size if @render_app.respond_to?(:window_size)
+
+      [
+        @window_config.fetch(:width, DEFAULT_WINDOW_SIZE[0]).to_f,
+        @window_config.fetch(:height, DEFAULT_WINDOW_SIZE[1]).to_f
+      ]
+    end
+
+    attr_reader :remove_defer, :floating_text_cap
+
+    # How warnings of a category (`:audio`, `:window`, `:determinism`, ...)
+    # are handled: `:silent` drops them, `:collect` keeps them for
+    # `drain_warnings` and `:raise` raises `Bevy::WarningError` from the frame
+    # that drains them.
+    def set_warning_policy(category, policy)
+      policy = policy.to_sym
+      unless WARNING_POLICIES.include?(policy)
+        raise ArgumentError, "unknown warning policy: #{policy} (expected #{WARNING_POLICIES.join(', ')})"
+      end
+
+      @warning_policies[category.to_sym] = policy
+      @render_app.set_warning_policy(category.to_sym, policy) if @render_app.respond_to?(:set_warning_policy)
+      self
+    end
+
+    def warning_policy(category)
+      @warning_policies.fetch(category.to_sym, :collect)
+    end
+
+    # Warnings collected since the last call, as
+    # `{ category:, message:, count:, first_frame: }` hashes. `count` includes
+    # repeats that were rate limited into the same entry.
+    def drain_warnings
+      drained = @warnings
+      @warnings = []
+      drained
+    end
+
+    # Keeps sprites of despawned entities visible for `frames` more frames, so
+    # a replacement synced in the same frame never leaves a one-frame hole.
+    # Re-syncing the same id within the window cancels the removal.
+    def set_remove_defer(frames)
+      @remove_defer = Integer(frames)
+      raise ArgumentError, 'remove defer must not be negative' if @remove_defer.negative?
+
+      apply_remove_defer
+      self
+    end
+
+    # Shows `text` at `x`, `y` rising `rise` pixels and fading out over
+    # `duration` seconds, then removes it; nothing is returned to track.
+    # Texts spawned at the same spot in quick succession stack upward.
+    def floating_text(x, y, text, color: Color.white, rise: 40, duration: 0.8, font_size: 18)
+      return unless @render_app
+
+      red, green, blue, alpha = floating_text_color(color)
+      @render_app.floating_text(
+        x.to_f, y.to_f, text.to_s,
+        { color_r: red, color_g: green, color_b: blue, color_a: alpha,
+          rise: rise.to_f, duration: duration.to_f, font_size: font_size.to_f }
+      )
+      nil
+    end
+
+    # Most floating texts alive at once; past it the oldest are removed.
+    def set_floating_text_cap(cap)
+      @floating_text_cap = Integer(cap)
+      raise ArgumentError, 'floating text cap must not be negative' if @floating_text_cap.negative?
+
+      apply_floating_text_cap
+      self
+    end
+
+    # Applies sprite syncs for sprites in `group` only every `divisor`th
+    # frame, each time with the latest values synced since. Systems can skip
+    # the group's work on the frames in between by checking `group_due?`.
+    # A divisor of 1 updates the group every frame again.
+    def set_group_update_divisor(group, divisor)
+      divisor = Integer(divisor)
+      raise ArgumentError, 'group update divisor must be at least 1' if divisor < 1
+
+      if divisor == 1
+        @group_update_divisors.delete(group.to_s)
+      else
+        @group_update_divisors[group.to_s] = divisor
+      end
+      @render_app.set_group_update_divisor(group.to_s, divisor) if @render_app.respond_to?(:set_group_update_divisor)
+      self
+    end
+
+    def group_update_divisor(group)
+      @group_update_divisors.fetch(group.to_s, 1)
+    end
+
+    # False on the frames the bridge holds back syncs for a throttled group;
+    # always true for groups without a divisor and without a render app.
+    def group_due?(group)
+      return true unless @render_app.respond_to?(:group_due?)
+
+      @render_app.group_due?(group.to_s)
+    end
+
+    # Behaviors and jelly springs that move less than `sleep` world units a
+    # frame for `frames` frames in a row fall asleep and are skipped until an
+    # operation targets them again. Motion above `wake` (defaults to `sleep`)
+    # starts the count over. `frames: 0` turns sleeping off.
+    def set_sleep_thresholds(sleep: DEFAULT_SLEEP_EPSILON, wake: nil, frames: DEFAULT_SLEEP_FRAMES)
+      thresholds = { sleep: Float(sleep), wake: Float(wake || sleep), frames: Integer(frames) }
+      raise ArgumentError, 'sleep thresholds must not be negative' if thresholds.values.any?(&:negative?)
+
+      thresholds[:wake] = thresholds[:sleep] if thresholds[:wake] < thresholds[:sleep]
+      @sleep_thresholds = thresholds
+      apply_sleep_thresholds
+      self
+    end
+
+    def sleep_thresholds
+      @sleep_thresholds || { sleep: DEFAULT_SLEEP_EPSILON, wake: DEFAULT_SLEEP_EPSILON, frames: DEFAULT_SLEEP_FRAMES }
+    end
+
+    # Wakes every sleeping entity next frame, for checking whether sleeping
+    # is behind a rendering difference.
+    def wake_all
+      @render_app.wake_all if @render_app.respond_to?(:wake_all)
+      self
+    end
+
+    # Records the transforms of synced sprites `snapshot_hz` times per second,
+    # keeping the last `seconds` for `rewind_to`.
+    def enable_rewind(seconds:, snapshot_hz: 10)
+      raise ArgumentError, 'rewind seconds must be positive' unless seconds.positive?
+      raise ArgumentError, 'snapshot_hz must be positive' unless snapshot_hz.positive?
+
+      @rewind_options = { seconds: seconds.to_f, snapshot_hz: snapshot_hz.to_f }
+      @rewinding = false
+      apply_rewind
+      self
+    end
+
+    def disable_rewind
+      @rewind_options = nil
+      @rewinding = false
+      @render_app.disable_rewind if @render_app.respond_to?(:disable_rewind)
+      self
+    end
+
+    def rewind_enabled?
+      !@rewind_options.nil?
+    end
+
+    # Shows sprites as they were `seconds_ago`, interpolating between snapshots,
+    # until `resume_from_rewind`. Sprites spawned since then are hidden; sprites
+    # removed since then are not brought back.
+    def rewind_to(seconds_ago)
+      raise ArgumentError, 'rewind is not enabled' unless rewind_enabled?
+
+      @rewinding = true
+      @render_app.rewind_to(seconds_ago.to_f) if @render_app.respond_to?(:rewind_to)
+      self
+    end
+
+    def resume_from_rewind
+      return self unless @rewinding
+
+      @rewinding = false
+      @render_app.resume_from_rewind if @render_app.respond_to?(:resume_from_rewind)
+      self
+    end
+
+    def rewinding?
+      @rewinding
+    end
+
+    # Snapshot count, capacity, memory use and covered seconds of the rewind buffer.
+    def rewind_stats
+      return nil unless rewind_enabled? && @render_app.respond_to?(:rewind_stats)
+
+      @render_app.rewind_stats
+    end
+
+    # Whether the native extension checksums every frame (the `determinism_audit`
+    # cargo feature).
+    def determinism_audit?
+      @render_app.respond_to?(:determinism_audit?) && @render_app.determinism_audit?
+    end
+
+    # Checksum of the input delivered and sync operations queued in the last
+    # audited frame, or nil without `determinism_audit`.
+    def frame_checksum
+      return nil unless @render_app.respond_to?(:frame_checksum)
+
+      @render_app.frame_checksum
+    end
+
+    def checksum_history
+      return [] unless @render_app.respond_to?(:checksum_history)
+
+      @render_app.checksum_history
+    end
+
+    # Writes the checksums of every audited frame so far, one hex value per line.
+    def save_checksums(path)
+      File.write(path, Bevy::DeterminismAudit.dump(checksum_history))
+      self
+    end
+
+    # Compares this run against checksums saved by `save_checksums`, now and on
+    # every later frame. Logs and returns the first divergence as
+    # `{ frame:, expected:, actual: }`, or nil while the runs agree.
+    def compare_checksums(path)
+      @recorded_checksums = Bevy::DeterminismAudit.parse(File.read(path))
+      return nil unless @render_app
+
+      apply_recorded_checksums
+    end
+
+    def checksum_divergence
+      return nil unless @render_app.respond_to?(:checksum_divergence)
+
+      @render_app.checksum_divergence
+    end
+
+    # Appends every operation sent to the render app (sprite, text and mesh
+    # syncs, removals, clears and camera changes) to `path` as JSON lines
+    # tagged with their frame. Past `max_mb` megabytes the file is rotated to
+    # `path.1`, and an older `path.1` to `path.2`.
+    def start_journal(path, max_mb: 50)
+      @journal = { path: path.to_s, max_mb: max_mb }
+      apply_journal if @render_app
+      self
+    end
+
+    def stop_journal
+      @journal = nil
+      @render_app.stop_journal if @render_app.respond_to?(:stop_journal)
+      self
+    end
+
+    def journaling?
+      !@journal.nil?
+    end
+
+    # Writes a debug bundle for a bug report into the directory `path`: the
+    # render app's render state, input, stats, camera, window and a
+    # screenshot when frames are read back, plus the world's entities, the
+    # collected warnings and the newest journal lines. A section that fails
+    # is recorded in `manifest.json` instead of stopping the dump, and log
+    # sections keep their newest `max_log_bytes`. Returns
+    # `{ path:, sections: [{ name:, status:, ... }] }`.
+    def dump_debug_bundle(path, max_log_bytes: nil)
+      options = max_log_bytes ? { max_log_bytes: Integer(max_log_bytes) } : {}
+      if @render_app.respond_to?(:dump_debug_bundle)
+        return @render_app.dump_debug_bundle(path.to_s, options) { |bundle| collect_debug_sections(bundle) }
+      end
+
+      bundle = DebugBundle.new(options)
+      collect_debug_sections(bundle)
+      bundle.write(path.to_s)
+    end
+
+    # Dumps a debug bundle into a new timestamped directory under `directory`
+    # whenever `key` is pressed, even while input is captured. `nil` unbinds it.
+    def set_debug_bundle_key(key, directory: 'debug_bundles')
+      @debug_bundle_key = key && { key: key.to_s, directory: directory.to_s }
+      self
+    end
+
+    def debug_bundle_key
+      @debug_bundle_key && @debug_bundle_key[:key]
+    end
+
+    # Feeds the operations of a journal written by `start_journal` back to the
+    # render app, one recorded frame per update. Meant for a headless app
+    # that spawns nothing itself.
+    def replay_journal(path)
+      @journal_replay = path.to_s
+      apply_journal_replay if @render_app
+      self
+    end
+
+    def replaying_journal?
+      @render_app.respond_to?(:replaying_journal?) && @render_app.replaying_journal?
+    end
+
+    # Binds a standalone `Bevy::World` whose entities `auto_sync` mirrors as
+    # sprites. Sprites synced from a previously bound world are removed.
+    def bind_world(world)
+      release_world_binding
+      @bound_world = world
+      self
+    end
+
+    attr_reader :bound_world
+
+    # Each frame, syncs a sprite for every bound-world entity having both
+    # `component_type` and `transform_from`, and removes it once either
+    # component is gone or the entity is despawned. `fields` renames the
+    # fields read, e.g. `fields: { color: :tint, x: :left }`.
+    def auto_sync(component_type: 'Renderable', transform_from: 'Position', fields: {})
+      release_world_binding
+      @world_binding = WorldSpriteBinding.new(
+        component_type.to_s,
+        transform_from.to_s,
+        fields.to_h { |key, field| [key.to_s, field.to_s] }
+      )
+      self
+    end
+
+    def auto_sync_enabled?
+      !@bound_world.nil? && !@world_binding.nil?
+    end
+
+    private
+
+    def run_startup_systems
+      context = build_context
+      @systems[Schedule::STARTUP].each { |s| s.run(context) }
+    end
+
+    def run_main_loop
+      while @running
+        update
+        sleep(0.001)
+      end
+      @on_exit&.call(:stop_called)
+    end
+
+    def run_render_loop
+      @render_app = RenderApp.new(render_app_config)
+      @render_app.initialize!
+      apply_virtual_cursor
+      apply_remove_defer if @remove_defer.positive?
+      apply_floating_text_cap unless @floating_text_cap == DEFAULT_FLOATING_TEXT_CAP
+      apply_group_update_divisors
+      apply_sleep_thresholds if @sleep_thresholds
+      apply_accessibility_enabled unless @accessibility_enabled
+      apply_input_captured if @input_captured
+      apply_action_bindings
+      apply_text_input_enabled if @text_input_enabled
+      apply_cursor
+      apply_parallax
+      apply_pixel_perfect
+      apply_hit_regions
+      apply_draggables
+      apply_detailed_stats
+      apply_warning_policies
+      apply_rewind
+      apply_recorded_checksums if @recorded_checksums
+      apply_journal if @journal
+      apply_journal_replay if @journal_replay
+      apply_on_exit if @on_exit
+      @clips.each_key { |handle| start_clip(handle) }
+
+      @render_app.run do
+        sync_input_from_bevy
+        update
+        sync_sprites_to_bevy
+      end
+    ensure
+      # Releases the native app even when setup raised, so a later run can create a new one.
+      @render_app.close if @render_app.respond_to?(:close)
+      @running = false
+    end
+
+    def apply_on_exit
+      return unless @render_app.respond_to?(:on_exit)
+
+      handler = @on_exit
+      @render_app.on_exit { |reason| handler.call(reason) }
+    end
+
+    def start_clip(handle)
+      entry = @clips[handle]
+      return unless entry && !entry[:started] && @render_app.respond_to?(:play_clip)
+
+      @render_app.play_clip(handle, entry[:clip].to_native)
+      @render_app.seek_clip(handle, entry[:seek]) if entry[:seek]
+      entry[:started] = true
+    end
+
+    def sync_clips_from_bevy
+      return unless @render_app.respond_to?(:drain_finished_clips)
+
+      Array(@render_app.drain_finished_clips).each do |handle|
+        entry = @clips.delete(handle)
+        entry[:on_complete]&.call(handle) if entry
+      end
+    end
+
+    # Ruby re-syncs every sprite each frame, so a sprite left where it was
+    # dropped keeps its place only once its Transform moves there too.
+    def sync_drag_events_from_bevy
+      return unless @render_app.respond_to?(:drain_drag_events)
+
+      @drag_events = Array(@render_app.drain_drag_events)
+      dropped = @drag_events.reject { |event| event[:snapped_back] }.to_h { |event| [event[:id], event[:position]] }
+      return if dropped.empty?
+
+      @world.each(Transform) do |entity, transform|
+        x, y = dropped[entity.id]
+        next unless x
+
+        transform.translation = Vec3.new(x, y, transform.translation.z)
+        @world.insert_component(entity, transform)
+      end
+    end
+
+    def apply_virtual_cursor
+      return unless @virtual_cursor_options && @render_app.respond_to?(:enable_virtual_cursor)
+
+      @virtual_cursor_pointer_id = @render_app.enable_virtual_cursor(@virtual_cursor_options)
+    end
+
+    def apply_accessibility_enabled
+      return unless @render_app.respond_to?(:set_accessibility_enabled)
+
+      @render_app.set_accessibility_enabled(@accessibility_enabled)
+    end
+
+    def apply_rewind
+      return unless @rewind_options && @render_app.respond_to?(:enable_rewind)
+
+      @render_app.enable_rewind(@rewind_options[:seconds], @rewind_options[:snapshot_hz])
+    end
+
+    def apply_journal
+      return unless @render_app.respond_to?(:start_journal)
+
+      @render_app.start_journal(@journal[:path], max_mb: @journal[:max_mb])
+    end
+
+    def apply_journal_replay
+      return unless @render_app.respond_to?(:replay_journal)
+
+      @render_app.replay_journal(@journal_replay)
+      @journal_replay = nil
+    end
+
+    def apply_recorded_checksums
+      return nil unless @render_app.respond_to?(:compare_recorded_checksums)
+
+      divergence = @render_app.compare_recorded_checksums(@recorded_checksums)
+      if divergence
+        warn "Determinism audit: frame #{divergence[:frame]} diverged " \
+             "(expected #{divergence[:expected].inspect}, got #{divergence[:actual].inspect})"
+      end
+      divergence
+    end
+
+    def clear_color_components(color)
+      color = color.first if color.length == 1
+      color = color.to_a if color.respond_to?(:r)
+      color = Array(color).map(&:to_f)
+      raise ArgumentError, 'clear color needs 3 or 4 components' unless [3, 4].include?(color.length)
+
+      color.length == 3 ? color + [1.0] : color
+    end
+
+    def normalize_max_fps(fps)
+      return nil if fps.nil?
+
+      fps = Float(fps)
+      raise ArgumentError, "max_fps must be a positive number, nil or 0, got #{fps}" unless fps.finite? && fps >= 0
+
+      fps.zero? ? nil : fps
+    end
+
+    def normalize_window_mode(mode)
+      mode = mode.to_sym
+      mode = :borderless if mode == :borderless_fullscreen
+      raise ArgumentError, "unknown window mode: #{mode}" unless WINDOW_MODES.include?(mode)
+
+      mode
+    end
+
+    # `present_mode:` wins over the `vsync:` shorthand (`true` is `:fifo`, `false` is `:auto_no_vsync`).
+    def normalize_present_mode(window)
+      if window.key?(:present_mode)
+        mode = window[:present_mode].to_sym
+        unless PRESENT_MODES.include?(mode)
+          raise ArgumentError, "unknown present mode: #{mode} (expected #{PRESENT_MODES.join(', ')})"
+        end
+
+        mode
+      elsif window.key?(:vsync)
+        window[:vsync] ? :fifo : :auto_no_vsync
+      end
+    end
+
+    def normalize_msaa(samples)
+      return nil if samples.nil?
+
+      unless samples.is_a?(Integer) && MSAA_SAMPLES.include?(samples)
+        raise ArgumentError, "msaa must be one of #{MSAA_SAMPLES.join(', ')}, got #{samples.inspect}"
+      end
+
+      samples
+    end
+
+    def normalize_texture_placeholder(placeholder)
+      placeholder = placeholder.to_sym
+      unless TEXTURE_PLACEHOLDERS.include?(placeholder)
+        raise ArgumentError,
+              "texture_placeholder must be one of #{TEXTURE_PLACEHOLDERS.join(', ')}, got #{placeholder.inspect}"
+      end
+
+      placeholder
+    end
+
+    def apply_input_captured
+      return unless @render_app.respond_to?(:set_input_captured)
+
+      @render_app.set_input_captured(@input_captured)
+    end
+
+    def apply_action_bindings
+      return unless @render_app.respond_to?(:bind_action)
+
+      @action_bindings.each { |action, binding| @render_app.bind_action(action, binding) }
+    end
+
+    def apply_warning_policies
+      return unless @render_app.respond_to?(:set_warning_policy)
+
+      @warning_policies.each { |category, policy| @render_app.set_warning_policy(category, policy) }
+    end
+
+    def apply_detailed_stats
+      @render_app.set_detailed_stats(true) if @detailed_stats && @render_app.respond_to?(:set_detailed_stats)
+    end
+
+    def apply_parallax
+      return unless @render_app.respond_to?(:set_parallax_factor)
+
+      @parallax_factors.each { |target, (factor_x, factor_y)| @render_app.set_parallax_factor(target, factor_x, factor_y) }
+    end
+
+    def apply_pixel_perfect
+      return unless @render_app.respond_to?(:set_entity_pixel_perfect)
+
+      @pixel_perfect_entities.each { |target_id, enabled| @render_app.set_entity_pixel_perfect(target_id, enabled) }
+    end
+
+    def apply_hit_regions
+      @hit_regions.each { |id, region| send_hit_region(:register_hit_region, id, region) }
+    end
+
+    def apply_draggables
+      return unless @render_app.respond_to?(:enable_drag)
+
+      @draggables.each { |target_id, options| @render_app.enable_drag(target_id, options) }
+    end
+
+    def send_hit_region(method_name, id, region)
+      return unless @render_app.respond_to?(method_name)
+
+      @render_app.public_send(method_name, id, region[:x], region[:y], region[:width], region[:height],
+                              { layer: region[:layer], screen_space: region[:screen_space] })
+    end
+
+    def parallax_target(group_or_layer)
+      group_or_layer.is_a?(Integer) ? group_or_layer : group_or_layer.to_s
+    end
+
+    def apply_text_input_enabled
+      @render_app.text_input_enabled = true if @render_app.respond_to?(:text_input_enabled=)
+    end
+
+    def apply_cursor
+      @render_app.set_cursor_visible(false) if !@cursor_visible && @render_app.respond_to?(:set_cursor_visible)
+      @render_app.set_cursor_grab(@cursor_grab) if @cursor_grab != :none && @render_app.respond_to?(:set_cursor_grab)
+    end
+
+    def apply_remove_defer
+      @render_app.set_remove_defer(@remove_defer) if @render_app.respond_to?(:set_remove_defer)
+    end
+
+    def apply_floating_text_cap
+      @render_app.set_floating_text_cap(@floating_text_cap) if @render_app.respond_to?(:set_floating_text_cap)
+    end
+
+    def apply_sleep_thresholds
+      return unless @render_app.respond_to?(:set_sleep_thresholds)
+
+      @render_app.set_sleep_thresholds(*@sleep_thresholds.values_at(:sleep, :wake, :frames))
+    end
+
+    def apply_group_update_divisors
+      return unless @render_app.respond_to?(:set_group_update_divisor)
+
+      @group_update_divisors.each { |group, divisor| @render_app.set_group_update_divisor(group, divisor) }
+    end
+
+    def floating_text_color(color)
+      color = Color.from_hex(color) if color.is_a?(String)
+      components = Array(color.respond_to?(:r) ? color.to_a : color).map(&:to_f)
+      raise ArgumentError, 'floating text color needs 3 or 4 components' unless [3, 4].include?(components.length)
+
+      components.length == 3 ? components + [1.0] : components
+    end
+
+    def render_app_config
+      config = @window_config.except(
+        :vsync, :present_mode, :max_fps, :pixel_perfect, :texture_placeholder, :decorations, :transparent
+      ).merge(
+        mode: @window_mode,
+        text_sync: @sync_systems.fetch(:text, true),
+        mesh_sync: @sync_systems.fetch(:mesh, true)
+      )
+      config = config.merge(present_mode: @present_mode) if @present_mode
+      config = config.merge(max_fps: @max_fps) if @max_fps
+      config = config.merge(pixel_perfect: true) if @pixel_perfect
+      config = config.merge(texture_placeholder: @texture_placeholder) if @texture_placeholder != :white
+      config = config.merge(decorations: false) unless @decorations
+      config = config.merge(transparent: true) if @transparent
+      @clear_color ? config.merge(clear_color: @clear_color) : config
+    end
+
+    def sync_input_from_bevy
+      return unless @render_app
 
       clear_input_state
 
@@ -447,46 +2061,95 @@ module Bevy
 
       mouse_pos = @render_app.mouse_position
       @mouse.set_position(mouse_pos[0], mouse_pos[1]) if mouse_pos
+      @mouse.set_delta(*@render_app.mouse_delta) if @render_app.respond_to?(:mouse_delta)
 
-      @mouse.reset
+      @mouse.reset_buttons
       %w[LEFT RIGHT MIDDLE].each do |button|
         @mouse.press(button) if @render_app.mouse_button_pressed?(button)
       end
+      @mouse.set_scroll(*@render_app.scroll_delta) if @render_app.respond_to?(:scroll_delta)
 
+      sync_gamepad_connections_from_bevy
       sync_gamepads_from_bevy
       sync_picking_events_from_bevy
+      sync_window_from_bevy
+      sync_window_events_from_bevy
+      sync_file_drops_from_bevy
+      sync_touches_from_bevy
+      sync_typed_text_from_bevy
+      poll_asset_changes
+      sync_clips_from_bevy
+      sync_drag_events_from_bevy
+      sync_warnings_from_bevy
+      dump_debug_bundle_on_key
     end
 
     def sync_sprites_to_bevy
       return unless @render_app
 
+      text_sync = render_subsystem_enabled?(:text_sync_enabled?)
+      mesh_sync = render_subsystem_enabled?(:mesh_sync_enabled?)
+
       @world.despawned_entity_ids.each do |entity_id|
         @render_app.remove_sprite(entity_id)
-        @render_app.remove_text(entity_id)
-        @render_app.remove_mesh(entity_id)
+        @render_app.remove_text(entity_id) if text_sync
+        @render_app.remove_mesh(entity_id) if mesh_sync
       end
       @world.clear_despawned_entity_ids
 
+      entries = []
       @world.each(Sprite, Transform) do |entity, sprite, transform|
-        @render_app.sync_sprite(
-          entity.id,
-          sprite.to_sync_hash,
-          transform.to_sync_hash
-        )
+        entries << [entity.id, sprite.to_sync_hash, transform.to_sync_hash]
       end
+      sync_sprite_entries(entries)
 
-      @world.each(Text2d, Transform) do |entity, text, transform|
-        @render_app.sync_text(
-          entity.id,
-          text.to_sync_hash,
-          transform.to_sync_hash
-        )
+      sync_bound_world_to_bevy
+
+      if text_sync
+        @world.each(Text2d, Transform) do |entity, text, transform|
+          @render_app.sync_text(
+            entity.id,
+            text.to_sync_hash,
+            transform.to_sync_hash
+          )
+        end
       end
 
-      sync_mesh_shapes
+      sync_mesh_shapes if mesh_sync
       sync_gamepad_rumble_to_bevy
     end
 
+    def sync_bound_world_to_bevy
+      return unless auto_sync_enabled?
+
+      apply_world_sync(@world_binding.plan(@bound_world))
+    end
+
+    def release_world_binding
+      apply_world_sync(@world_binding.release) if @world_binding && @render_app
+    end
+
+    def apply_world_sync(operations)
+      operations[:remove].each { |entity_id| @render_app.remove_sprite(entity_id) }
+      sync_sprite_entries(operations[:sync].map { |entry| entry.values_at(:id, :sprite, :transform) })
+    end
+
+    # Sends `[id, sprite_hash, transform_hash]` entries in one batch call when
+    # the render app supports it, one `sync_sprite` call each otherwise.
+    def sync_sprite_entries(entries)
+      return if entries.empty?
+
+      if @render_app.respond_to?(:sync_sprites_batch)
+        @render_app.sync_sprites_batch(entries)
+      else
+        entries.each { |id, sprite, transform| @render_app.sync_sprite(id, sprite, transform) }
+      end
+    end
+
+    def render_subsystem_enabled?(predicate)
+      !@render_app.respond_to?(predicate) || @render_app.public_send(predicate)
+    end
+
     def sync_mesh_shapes
       return unless @render_app
 
@@ -512,6 +2175,12 @@ module Bevy
       end
     end
 
+    def sync_gamepad_connections_from_bevy
+      return unless @render_app.respond_to?(:drain_gamepad_connection_events)
+
+      @gamepad_connection_events = Array(@render_app.drain_gamepad_connection_events)
+    end
+
     def sync_gamepads_from_bevy
       return unless @render_app.respond_to?(:gamepads_state)
 
@@ -530,6 +2199,7 @@ module Bevy
 
         pressed_buttons = Array(state[:buttons_pressed] || state['buttons_pressed']).map(&:to_s)
         axes = state[:axes] || state['axes'] || {}
+        gamepad.battery = state.key?(:battery) ? state[:battery] : state['battery']
 
         buttons_to_sync = (GAMEPAD_BUTTONS + gamepad.known_buttons + pressed_buttons).uniq
         buttons_to_sync.each do |button|
@@ -554,18 +2224,120 @@ module Bevy
 
       @gamepads.each do |gamepad|
         rumble = gamepad.pending_rumble
-        next unless rumble
-
-        @render_app.queue_gamepad_rumble(
-          gamepad.id,
-          rumble.strong_magnitude,
-          rumble.weak_magnitude,
-          rumble.duration
-        )
+        patterns = gamepad.pending_rumble_patterns
+        next if rumble.nil? && patterns.empty?
+
+        if rumble
+          @render_app.queue_gamepad_rumble(
+            gamepad.id,
+            rumble.strong_magnitude,
+            rumble.weak_magnitude,
+            rumble.duration
+          )
+        end
+        if @render_app.respond_to?(:rumble_pattern)
+          patterns.each { |pattern| @render_app.rumble_pattern(gamepad.id, pattern) }
+        end
         gamepad.clear_pending_rumble
       end
     end
 
+    def sync_window_from_bevy
+      return unless @render_app.respond_to?(:window_metrics)
+
+      safe_area = SafeArea.from_metrics(@render_app.window_metrics)
+      return if safe_area == @safe_area
+
+      previous = @safe_area
+      @safe_area = safe_area
+      @events.writer(SafeAreaChanged)&.send(SafeAreaChanged.new(safe_area: safe_area, previous: previous))
+    end
+
+    def sync_warnings_from_bevy
+      return unless @render_app.respond_to?(:drain_warnings)
+
+      @warnings.concat(Array(@render_app.drain_warnings))
+      @warnings.shift(@warnings.length - WARNING_BUFFER) if @warnings.length > WARNING_BUFFER
+    end
+
+    def dump_debug_bundle_on_key
+      return unless @debug_bundle_key && @render_app.raw_key_just_pressed?(@debug_bundle_key[:key])
+
+      stamp = ::Time.now.strftime('%Y%m%d-%H%M%S-%L')
+      dump_debug_bundle(File.join(@debug_bundle_key[:directory], "bundle-#{stamp}"))
+    end
+
+    def collect_debug_sections(bundle)
+      bundle.set_info(:ruby_version, RUBY_VERSION)
+      bundle.set_info(:gem_version, VERSION)
+      bundle.collect(:entities) { debug_entities }
+      bundle.collect(:warnings) { @warnings }
+      bundle.collect_log(:journal) { journal_tail(bundle.max_log_bytes) }
+    end
+
+    def debug_entities
+      sprites = []
+      @world.each(Sprite, Transform) do |entity, sprite, transform|
+        sprites << { id: entity.id, sprite: sprite.to_sync_hash, transform: transform.to_sync_hash }
+      end
+      texts = []
+      @world.each(Text2d, Transform) do |entity, text, transform|
+        texts << { id: entity.id, text: text.to_sync_hash, transform: transform.to_sync_hash }
+      end
+      { entity_count: @world.count, sprites: sprites, texts: texts }
+    end
+
+    # The journal's last `max_bytes` as lines, without the partial first line.
+    def journal_tail(max_bytes)
+      return nil unless @journal && File.exist?(@journal[:path])
+
+      File.open(@journal[:path], 'rb') do |file|
+        offset = [file.size - max_bytes, 0].max
+        file.seek(offset)
+        lines = file.read.lines(chomp: true)
+        lines.shift if offset.positive?
+        lines
+      end
+    end
+
+    def sync_file_drops_from_bevy
+      return unless @render_app.respond_to?(:drain_file_drops)
+
+      @file_drops = Array(@render_app.drain_file_drops).map { |data| FileDrop.from_native(data) }
+    end
+
+    def sync_touches_from_bevy
+      return unless @render_app.respond_to?(:touches)
+
+      @touches = Array(@render_app.touches)
+    end
+
+    def sync_typed_text_from_bevy
+      return unless @text_input_enabled && @render_app.respond_to?(:typed_text)
+
+      @typed_text = @render_app.typed_text.to_s
+    end
+
+    def poll_asset_changes
+      @asset_changes = @asset_registry ? Array(@asset_registry.check_for_changes) : []
+    end
+
+    def sync_window_events_from_bevy
+      return unless @render_app.respond_to?(:drain_window_events)
+
+      Array(@render_app.drain_window_events).each do |event_data|
+        kind = (event_data[:kind] || event_data['kind']).to_s
+        window_id = (event_data[:window_id] || event_data['window_id']).to_i
+
+        if kind == 'close_requested'
+          @events.writer(WindowCloseRequested)&.send(WindowCloseRequested.new(window_id: window_id))
+        else
+          path = event_data[:path] || event_data['path']
+          @events.writer(FileDragAndDrop)&.send(FileDragAndDrop.new(kind: kind, window_id: window_id, path: path))
+        end
+      end
+    end
+
     def sync_picking_events_from_bevy
       return unless @render_app.respond_to?(:drain_picking_events)
 
@@ -586,6 +2358,9 @@ module Bevy
         depth = event_data[:depth] || event_data['depth']
         hit_position = event_data[:hit_position] || event_data['hit_position']
         hit_normal = event_data[:hit_normal] || event_data['hit_normal']
+        hit_region = event_data[:hit_region] || event_data['hit_region']
+        delta = event_data[:delta] || event_data['delta']
+        distance = event_data[:distance] || event_data['distance']
 
         writer.send(
           PickingEvent.new(
@@ -597,7 +2372,10 @@ module Bevy
             camera_id: camera_id.nil? ? nil : camera_id.to_i,
             depth: depth.nil? ? nil : depth.to_f,
             hit_position: to_vec3_or_nil(hit_position),
-            hit_normal: to_vec3_or_nil(hit_normal)
+            hit_normal: to_vec3_or_nil(hit_normal),
+            hit_region: hit_region ? true : false,
+            delta: delta && to_vec2(delta),
+            distance: distance && to_vec2(distance)
           )
         )
       end
@@ -478,68 +478,8 @@ module Bevy
   end
 
   class AudioQueue
-    attr_accessor :loop_queue, :shuffle
-    attr_reader :current_index
-
-    def initialize
-      @tracks = []
-      @current_index = 0
-      @loop_queue = false
-      @shuffle = false
-    end
-
-    def add(path)
-      @tracks << path
-    end
-
-    def add_all(paths)
-      @tracks.concat(paths)
-    end
-
-    def current
-      @tracks[@current_index]
-    end
-
-    def next
-      return nil if @tracks.empty?
-
-      @current_index += 1
-      if @current_index >= @tracks.size
-        return nil unless @loop_queue
-
-        @current_index = 0
-      end
-      current
-    end
-
-    def previous
-      return nil if @tracks.empty?
-
-      if @current_index.zero?
-        return nil unless @loop_queue
-
-        @current_index = @tracks.size - 1
-      else
-        @current_index -= 1
-      end
-      current
-    end
-
-    def clear
-      @tracks.clear
-      @current_index = 0
-    end
-
-    def size
-      @tracks.size
-    end
-
     def empty?
-      @tracks.empty?
-    end
-
-    def to_a
-      @tracks.dup
+      size.zero?
     end
   end
 end
@@ -195,5 +195,23 @@ module Bevy
     attribute :depth, :float, default: nil
     attribute :hit_position, :vec3, default: nil
     attribute :hit_normal, :vec3, default: nil
+    attribute :hit_region, :boolean, default: false
+    attribute :delta, :vec2, default: nil
+    attribute :distance, :vec2, default: nil
+
+    DRAG_KINDS = %w[drag_start drag drag_end].freeze
+
+    def drag?
+      DRAG_KINDS.include?(kind)
+    end
+
+    def custom_pointer?
+      pointer_id.start_with?('Custom(')
+    end
+
+    # True when target_id is a hit region ID rather than an entity.
+    def hit_region?
+      hit_region ? true : false
+    end
   end
 end
@@ -2,10 +2,21 @@
 
 module Bevy
   class Parent
-    attr_reader :entity
+    # What happens to a child when its parent is despawned:
+    # `:despawn` it too, detach it keeping its world position
+    # (`:detach_keep_world`) or its local transform (`:detach_keep_local`),
+    # or attach it to the removed parent's parent (`:reparent_up`), keeping
+    # its world position.
+    ON_PARENT_REMOVED = %i[despawn detach_keep_world detach_keep_local reparent_up].freeze
 
-    def initialize(entity)
+    attr_reader :entity, :on_parent_removed
+
+    def initialize(entity, on_parent_removed: :detach_keep_world)
       @entity = entity
+      @on_parent_removed = on_parent_removed.to_sym
+      return if ON_PARENT_REMOVED.include?(@on_parent_removed)
+
+      raise ArgumentError, "unknown on_parent_removed policy: #{on_parent_removed}"
     end
 
     def type_name
@@ -74,8 +85,8 @@ module Bevy
       self
     end
 
-    def set_parent(parent_entity)
-      insert_component(Parent.new(parent_entity))
+    def set_parent(parent_entity, on_parent_removed: :detach_keep_world)
+      insert_component(Parent.new(parent_entity, on_parent_removed: on_parent_removed))
       self
     end
 
@@ -131,6 +142,51 @@ module Bevy
       Mat4.from_scale_rotation_translation(@scale, @rotation, @translation)
     end
 
+    # Global transform of a child whose local transform is `local`.
+    def mul_transform(local)
+      scaled_local = Vec3.new(
+        local.translation.x * @scale.x,
+        local.translation.y * @scale.y,
+        local.translation.z * @scale.z
+      )
+      rotated = @rotation.mul_vec3(scaled_local)
+
+      GlobalTransform.new(
+        translation: Vec3.new(
+          @translation.x + rotated.x,
+          @translation.y + rotated.y,
+          @translation.z + rotated.z
+        ),
+        rotation: @rotation * local.rotation,
+        scale: Vec3.new(
+          @scale.x * local.scale.x,
+          @scale.y * local.scale.y,
+          @scale.z * local.scale.z
+        )
+      )
+    end
+
+    # Local transform that puts a child of `parent` at this global transform.
+    def relative_to(parent)
+      Transform.new(
+        translation: parent.inverse_transform_point(@translation),
+        rotation: parent.rotation.inverse * @rotation,
+        scale: Vec3.new(
+          @scale.x / parent.scale.x,
+          @scale.y / parent.scale.y,
+          @scale.z / parent.scale.z
+        )
+      )
+    end
+
+    def to_transform
+      Transform.new(
+        translation: Vec3.new(@translation.x, @translation.y, @translation.z),
+        rotation: @rotation,
+        scale: Vec3.new(@scale.x, @scale.y, @scale.z)
+      )
+    end
+
     def transform_point(point)
       rotated = @rotation.mul_vec3(Vec3.new(
         point.x * @scale.x,
@@ -361,30 +417,83 @@ module Bevy
     end
 
     def compute_global_transform(parent_global, local_transform)
-      parent_scale = parent_global.scale
-      parent_rotation = parent_global.rotation
+      parent_global.mul_transform(local_transform)
+    end
+  end
 
-      scaled_local = Vec3.new(
-        local_transform.translation.x * parent_scale.x,
-        local_transform.translation.y * parent_scale.y,
-        local_transform.translation.z * parent_scale.z
-      )
+  # Applies each child's `Parent#on_parent_removed` policy before its
+  # parent is despawned; `World#despawn` runs it for every entity in a hierarchy.
+  class ParentRemoval
+    def self.apply(world, entity)
+      new(world).apply(entity)
+    end
+
+    def initialize(world)
+      @world = world
+    end
 
-      rotated = parent_rotation.mul_vec3(scaled_local)
+    def apply(entity)
+      grandparent = component(entity, Parent)&.entity
+      component(grandparent, Children)&.remove(entity) if grandparent
 
-      GlobalTransform.new(
-        translation: Vec3.new(
-          parent_global.translation.x + rotated.x,
-          parent_global.translation.y + rotated.y,
-          parent_global.translation.z + rotated.z
-        ),
-        rotation: parent_rotation * local_transform.rotation,
-        scale: Vec3.new(
-          parent_global.scale.x * local_transform.scale.x,
-          parent_global.scale.y * local_transform.scale.y,
-          parent_global.scale.z * local_transform.scale.z
-        )
-      )
+      children = component(entity, Children)
+      return unless children
+
+      children.entities.dup.each do |child|
+        parent = component(child, Parent)
+        next unless parent && parent.entity.id == entity.id
+
+        case parent.on_parent_removed
+        when :despawn
+          @world.despawn(child)
+        when :detach_keep_local
+          @world.remove_component(child, Parent)
+        when :detach_keep_world
+          detach_keep_world(child)
+        when :reparent_up
+          grandparent ? reparent(child, entity, grandparent, parent) : detach_keep_world(child)
+        end
+      end
+      children.entities.clear
+    end
+
+    # Composes local transforms from the root down; entities without a
+    # Transform count as identity.
+    def global_transform(entity)
+      local = component(entity, Transform) || Transform.identity
+      parent = component(entity, Parent)
+      base = parent ? global_transform(parent.entity) : GlobalTransform.identity
+      base.mul_transform(local)
+    end
+
+    private
+
+    def detach_keep_world(child)
+      global = global_transform(child) if component(child, Transform)
+      @world.remove_component(child, Parent)
+      @world.insert_component(child, global.to_transform) if global
+    end
+
+    def reparent(child, removed, grandparent, parent)
+      local = component(child, Transform)
+      if local
+        removed_local = component(removed, Transform) || Transform.identity
+        @world.insert_component(child, GlobalTransform.from_transform(removed_local).mul_transform(local).to_transform)
+      end
+      @world.insert_component(child, Parent.new(grandparent, on_parent_removed: parent.on_parent_removed))
+
+      siblings = component(grandparent, Children)
+      if siblings
+        siblings.add(child)
+      else
+        @world.insert_component(grandparent, Children.new([child]))
+      end
+    end
+
+    def component(entity, component_class)
+      @world.get_component(entity, component_class)
+    rescue StandardError
+      nil
     end
   end
 
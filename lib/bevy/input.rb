@@ -149,6 +149,7 @@ module Bevy
       @just_pressed = {}
       @just_released = {}
       @position = Vec2.zero
+      @position_known = false
       @delta = Vec2.zero
       @scroll_delta = Vec2.zero
     end
@@ -175,11 +176,17 @@ module Bevy
       @just_released[button] == true
     end
 
+    # The first position after `new` or `reset` reports no movement.
     def set_position(x, y)
-      old_x = @position.x
-      old_y = @position.y
+      @delta = @position_known ? Vec2.new(x - @position.x, y - @position.y) : Vec2.zero
       @position = Vec2.new(x, y)
-      @delta = Vec2.new(x - old_x, y - old_y)
+      @position_known = true
+    end
+
+    # Overrides the movement derived from positions, e.g. with raw motion
+    # that keeps reporting while the cursor is grabbed or outside the window.
+    def set_delta(x, y)
+      @delta = Vec2.new(x, y)
     end
 
     def set_scroll(x, y)
@@ -194,13 +201,19 @@ module Bevy
     end
 
     def reset
-      @pressed.clear
-      @just_pressed.clear
-      @just_released.clear
+      reset_buttons
       @position = Vec2.zero
+      @position_known = false
       @delta = Vec2.zero
       @scroll_delta = Vec2.zero
     end
+
+    # Releases every button without forgetting where the cursor was.
+    def reset_buttons
+      @pressed.clear
+      @just_pressed.clear
+      @just_released.clear
+    end
   end
 
   module GamepadButton
@@ -260,6 +273,36 @@ module Bevy
     end
   end
 
+  # Response curve for stick-driven movement such as the virtual cursor.
+  # Deflection inside the dead zone is ignored and the rest is raised to
+  # `exponent`, so small tilts move slowly near the center.
+  class StickCurve
+    attr_reader :dead_zone, :exponent
+
+    def initialize(dead_zone: 0.15, exponent: 2.0)
+      @dead_zone = dead_zone.to_f.clamp(0.0, 0.99)
+      @exponent = [exponent.to_f, 0.01].max
+    end
+
+    def apply(x, y)
+      length = Math.sqrt(x * x + y * y)
+      return [0.0, 0.0] if length <= @dead_zone
+
+      normalized = ([length, 1.0].min - @dead_zone) / (1.0 - @dead_zone)
+      scale = (normalized**@exponent) / length
+      [x * scale, y * scale]
+    end
+
+    def apply_vec2(vec)
+      x, y = apply(vec.x, vec.y)
+      Vec2.new(x, y)
+    end
+
+    def to_h
+      { dead_zone: @dead_zone, exponent: @exponent }
+    end
+  end
+
   class RumbleRequest
     attr_reader :strong_magnitude, :weak_magnitude, :duration
 
@@ -283,7 +326,7 @@ module Bevy
   end
 
   class GamepadInput
-    attr_reader :id, :name
+    attr_reader :id, :name, :battery
     attr_accessor :stick_dead_zone
 
     def initialize(id = 0, name: nil)
@@ -297,6 +340,20 @@ module Bevy
       @axis_dead_zones = {}
       @stick_dead_zone = DeadZone.new
       @pending_rumble = nil
+      @pending_rumble_patterns = []
+      @battery = nil
+    end
+
+    def battery=(level)
+      @battery = level&.to_f&.clamp(0.0, 1.0)
+    end
+
+    def battery_known?
+      !@battery.nil?
+    end
+
+    def low_battery?(threshold = 0.2)
+      battery_known? && @battery <= threshold
     end
 
     def press(button)
@@ -414,12 +471,25 @@ module Bevy
       @pending_rumble = RumbleRequest.new
     end
 
+    # Queues `[[strong, weak, secs], ...]`, or a built-in pattern name
+    # (:double_pulse, :heartbeat, :ramp_up, :ramp_down), to play after the
+    # patterns already queued. Segments with both motors at 0 are pauses.
+    def rumble_pattern(pattern)
+      @pending_rumble_patterns << pattern
+      self
+    end
+
     def pending_rumble
       @pending_rumble
     end
 
+    def pending_rumble_patterns
+      @pending_rumble_patterns.dup
+    end
+
     def clear_pending_rumble
       @pending_rumble = nil
+      @pending_rumble_patterns.clear
     end
 
     def clear_just_pressed
@@ -7,17 +7,51 @@ module Bevy
     SHAPE_REGULAR_POLYGON = 2
     SHAPE_LINE = 3
     SHAPE_ELLIPSE = 4
+    SHAPE_TRIANGLE = 5
+
+    # Two-color fill for the filled shapes, running from +color+ at the top
+    # (or left) to +gradient_color+ at the bottom (or right). Outlines and
+    # lines ignore it.
+    module GradientFill
+      GRADIENT_DIRECTIONS = %i[vertical horizontal].freeze
+
+      attr_accessor :gradient_color
+      attr_reader :gradient_direction
+
+      def gradient_direction=(direction)
+        direction = direction.to_sym
+        raise ArgumentError, "Unknown gradient direction: #{direction}" unless GRADIENT_DIRECTIONS.include?(direction)
+
+        @gradient_direction = direction
+      end
+
+      private
+
+      def gradient_mesh_data
+        return {} unless @gradient_color
+
+        {
+          gradient_color: [@gradient_color.r, @gradient_color.g, @gradient_color.b, @gradient_color.a],
+          gradient_direction: @gradient_direction.to_s
+        }
+      end
+    end
 
     class Rectangle
+      include GradientFill
+
       attr_accessor :width, :height, :color, :fill, :thickness, :transform
 
-      def initialize(width:, height:, color: Color.white, fill: true, thickness: 2.0)
+      def initialize(width:, height:, color: Color.white, fill: true, thickness: 2.0,
+                     gradient_color: nil, gradient_direction: :vertical)
         @width = width.to_f
         @height = height.to_f
         @color = color
         @fill = fill
         @thickness = thickness.to_f
         @transform = Transform.identity
+        @gradient_color = gradient_color
+        self.gradient_direction = gradient_direction
       end
 
       def shape_type
@@ -41,7 +75,7 @@ module Bevy
           line_end_y: 0.0,
           thickness: @thickness,
           fill: @fill
-        }
+        }.merge(gradient_mesh_data)
       end
 
       def type_name
@@ -50,14 +84,19 @@ module Bevy
     end
 
     class Circle
+      include GradientFill
+
       attr_accessor :radius, :color, :fill, :thickness, :transform
 
-      def initialize(radius:, color: Color.white, fill: true, thickness: 2.0)
+      def initialize(radius:, color: Color.white, fill: true, thickness: 2.0,
+                     gradient_color: nil, gradient_direction: :vertical)
         @radius = radius.to_f
         @color = color
         @fill = fill
         @thickness = thickness.to_f
         @transform = Transform.identity
+        @gradient_color = gradient_color
+        self.gradient_direction = gradient_direction
       end
 
       def diameter
@@ -85,7 +124,7 @@ module Bevy
           line_end_y: 0.0,
           thickness: @thickness,
           fill: @fill
-        }
+        }.merge(gradient_mesh_data)
       end
 
       def type_name
@@ -94,15 +133,20 @@ module Bevy
     end
 
     class RegularPolygon
+      include GradientFill
+
       attr_accessor :radius, :sides, :color, :fill, :thickness, :transform
 
-      def initialize(radius:, sides:, color: Color.white, fill: true, thickness: 2.0)
+      def initialize(radius:, sides:, color: Color.white, fill: true, thickness: 2.0,
+                     gradient_color: nil, gradient_direction: :vertical)
         @radius = radius.to_f
         @sides = [sides.to_i, 3].max
         @color = color
         @fill = fill
         @thickness = thickness.to_f
         @transform = Transform.identity
+        @gradient_color = gradient_color
+        self.gradient_direction = gradient_direction
       end
 
       def shape_type
@@ -126,7 +170,7 @@ module Bevy
           line_end_y: 0.0,
           thickness: @thickness,
           fill: @fill
-        }
+        }.merge(gradient_mesh_data)
       end
 
       def type_name
@@ -135,8 +179,14 @@ module Bevy
     end
 
     class Triangle < RegularPolygon
-      def initialize(radius:, color: Color.white, fill: true, thickness: 2.0)
-        super(radius: radius, sides: 3, color: color, fill: fill, thickness: thickness)
+      def initialize(radius:, color: Color.white, fill: true, thickness: 2.0,
+                     gradient_color: nil, gradient_direction: :vertical)
+        super(radius: radius, sides: 3, color: color, fill: fill, thickness: thickness,
+              gradient_color: gradient_color, gradient_direction: gradient_direction)
+      end
+
+      def shape_type
+        SHAPE_TRIANGLE
       end
 
       def type_name
@@ -145,8 +195,10 @@ module Bevy
     end
 
     class Hexagon < RegularPolygon
-      def initialize(radius:, color: Color.white, fill: true, thickness: 2.0)
-        super(radius: radius, sides: 6, color: color, fill: fill, thickness: thickness)
+      def initialize(radius:, color: Color.white, fill: true, thickness: 2.0,
+                     gradient_color: nil, gradient_direction: :vertical)
+        super(radius: radius, sides: 6, color: color, fill: fill, thickness: thickness,
+              gradient_color: gradient_color, gradient_direction: gradient_direction)
       end
 
       def type_name
@@ -155,14 +207,35 @@ module Bevy
     end
 
     class Line
+      LINE_CAPS = %i[butt round square].freeze
+      LINE_JOINS = %i[miter round bevel].freeze
+
       attr_accessor :start_point, :end_point, :color, :thickness, :transform
+      attr_reader :line_cap, :line_join
 
-      def initialize(start_point:, end_point:, color: Color.white, thickness: 2.0)
+      def initialize(start_point:, end_point:, color: Color.white, thickness: 2.0, line_cap: :butt,
+                     line_join: :miter)
         @start_point = start_point
         @end_point = end_point
         @color = color
         @thickness = thickness.to_f
         @transform = Transform.identity
+        self.line_cap = line_cap
+        self.line_join = line_join
+      end
+
+      def line_cap=(cap)
+        cap = cap.to_sym
+        raise ArgumentError, "Unknown line cap: #{cap}" unless LINE_CAPS.include?(cap)
+
+        @line_cap = cap
+      end
+
+      def line_join=(join)
+        join = join.to_sym
+        raise ArgumentError, "Unknown line join: #{join}" unless LINE_JOINS.include?(join)
+
+        @line_join = join
       end
 
       def length
@@ -204,7 +277,9 @@ module Bevy
           line_end_x: @end_point.x,
           line_end_y: @end_point.y,
           thickness: @thickness,
-          fill: false
+          fill: false,
+          line_cap: @line_cap.to_s,
+          line_join: @line_join.to_s
         }
       end
 
@@ -214,15 +289,20 @@ module Bevy
     end
 
     class Ellipse
+      include GradientFill
+
       attr_accessor :width, :height, :color, :fill, :thickness, :transform
 
-      def initialize(width:, height:, color: Color.white, fill: true, thickness: 2.0)
+      def initialize(width:, height:, color: Color.white, fill: true, thickness: 2.0,
+                     gradient_color: nil, gradient_direction: :vertical)
         @width = width.to_f
         @height = height.to_f
         @color = color
         @fill = fill
         @thickness = thickness.to_f
         @transform = Transform.identity
+        @gradient_color = gradient_color
+        self.gradient_direction = gradient_direction
       end
 
       def shape_type
@@ -246,7 +326,7 @@ module Bevy
           line_end_y: 0.0,
           thickness: @thickness,
           fill: @fill
-        }
+        }.merge(gradient_mesh_data)
       end
 
       def type_name
@@ -0,0 +1,45 @@
+# frozen_string_literal: true
+
+module Bevy
+  class Palette
+    include Enumerable
+
+    def each(&block)
+      return enum_for(:each) unless block
+
+      colors.each(&block)
+    end
+
+    def empty?
+      size.zero?
+    end
+
+    # Assigns consecutive rotated entries to the given sprites in one native call.
+    # The colors override each sprite's synced color until `release(app, ids)`.
+    def apply_to(app, ids)
+      render_app = resolve_render_app(app)
+      return self unless render_app
+
+      render_app.apply_palette(self, entity_ids(ids))
+      self
+    end
+
+    def release(app, ids)
+      render_app = resolve_render_app(app)
+      return self unless render_app
+
+      entity_ids(ids).each { |id| render_app.clear_sprite_color(id) }
+      self
+    end
+
+    private
+
+    def resolve_render_app(app)
+      app.respond_to?(:render_app) ? app.render_app : app
+    end
+
+    def entity_ids(ids)
+      Array(ids).map { |entity_or_id| (entity_or_id.respond_to?(:id) ? entity_or_id.id : entity_or_id).to_i }
+    end
+  end
+end
@@ -51,6 +51,7 @@ module Bevy
       @entity_data.clear
 
       world.all_entities.each do |entity|
+        next unless world.mesh_components[entity.id]&.any?
         next if block_given? && !filter.call(entity)
 
         entity_components = {}
@@ -2,14 +2,42 @@
 
 module Bevy
   class Sprite
-    attr_reader :color, :flip_x, :flip_y, :custom_size, :anchor
+    JELLY_KEYS = %i[subdivisions stiffness damping].freeze
 
-    def initialize(color: nil, flip_x: false, flip_y: false, custom_size: nil, anchor: nil)
+    attr_reader :color, :flip_x, :flip_y, :custom_size, :anchor, :group, :accessible_label, :accessible_role,
+                :texture_path, :jelly, :z_index, :atlas_rect
+
+    # `accessible_label` / `accessible_role` (`:button`, `:label` or `:image`)
+    # expose the sprite to screen readers; the role defaults to `:image`.
+    # `texture_path` is an image file under the assets directory, tinted by
+    # `color`; without it the sprite is a plain colored quad.
+    # `jelly` draws the sprite as a subdivided mesh that wobbles when kicked
+    # with `apply_impulse`: pass `true` for the defaults or a hash of
+    # `subdivisions`, `stiffness` and `damping`.
+    # `z_index` is a draw layer independent of the transform: sprites on a
+    # higher layer render above lower ones whatever their transform z.
+    # `atlas_rect` draws only part of the texture: `[x, y, width, height]` in
+    # pixels from its top-left corner, or a `Rect` such as
+    # `TextureAtlasLayout#get_texture_rect` returns.
+    def initialize(color: nil, flip_x: false, flip_y: false, custom_size: nil, anchor: nil, group: nil,
+                   accessible_label: nil, accessible_role: nil, texture_path: nil, jelly: nil, z_index: nil,
+                   atlas_rect: nil)
       @color = color || Color.white
       @flip_x = flip_x
       @flip_y = flip_y
       @custom_size = custom_size
       @anchor = anchor || Vec2.new(0.5, 0.5)
+      @group = group&.to_s
+      @accessible_label = accessible_label&.to_s
+      @accessible_role = accessible_role&.to_sym
+      @texture_path = texture_path&.to_s
+      @jelly = normalize_jelly(jelly)
+      @z_index = z_index&.to_i
+      @atlas_rect = normalize_atlas_rect(atlas_rect)
+    end
+
+    def mesh_mode?
+      !@jelly.nil?
     end
 
     def type_name
@@ -22,7 +50,14 @@ module Bevy
         flip_x: @flip_x,
         flip_y: @flip_y,
         custom_size: @custom_size,
-        anchor: @anchor
+        anchor: @anchor,
+        group: @group,
+        accessible_label: @accessible_label,
+        accessible_role: @accessible_role,
+        texture_path: @texture_path,
+        jelly: @jelly,
+        z_index: @z_index,
+        atlas_rect: @atlas_rect
       )
     end
 
@@ -32,7 +67,14 @@ module Bevy
         flip_x: flip_x,
         flip_y: @flip_y,
         custom_size: @custom_size,
-        anchor: @anchor
+        anchor: @anchor,
+        group: @group,
+        accessible_label: @accessible_label,
+        accessible_role: @accessible_role,
+        texture_path: @texture_path,
+        jelly: @jelly,
+        z_index: @z_index,
+        atlas_rect: @atlas_rect
       )
     end
 
@@ -42,7 +84,14 @@ module Bevy
         flip_x: @flip_x,
         flip_y: flip_y,
         custom_size: @custom_size,
-        anchor: @anchor
+        anchor: @anchor,
+        group: @group,
+        accessible_label: @accessible_label,
+        accessible_role: @accessible_role,
+        texture_path: @texture_path,
+        jelly: @jelly,
+        z_index: @z_index,
+        atlas_rect: @atlas_rect
       )
     end
 
@@ -52,7 +101,14 @@ module Bevy
         flip_x: @flip_x,
         flip_y: @flip_y,
         custom_size: size,
-        anchor: @anchor
+        anchor: @anchor,
+        group: @group,
+        accessible_label: @accessible_label,
+        accessible_role: @accessible_role,
+        texture_path: @texture_path,
+        jelly: @jelly,
+        z_index: @z_index,
+        atlas_rect: @atlas_rect
       )
     end
 
@@ -62,7 +118,116 @@ module Bevy
         flip_x: @flip_x,
         flip_y: @flip_y,
         custom_size: @custom_size,
-        anchor: anchor
+        anchor: anchor,
+        group: @group,
+        accessible_label: @accessible_label,
+        accessible_role: @accessible_role,
+        texture_path: @texture_path,
+        jelly: @jelly,
+        z_index: @z_index,
+        atlas_rect: @atlas_rect
+      )
+    end
+
+    def with_group(group)
+      self.class.new(
+        color: @color,
+        flip_x: @flip_x,
+        flip_y: @flip_y,
+        custom_size: @custom_size,
+        anchor: @anchor,
+        group: group,
+        accessible_label: @accessible_label,
+        accessible_role: @accessible_role,
+        texture_path: @texture_path,
+        jelly: @jelly,
+        z_index: @z_index,
+        atlas_rect: @atlas_rect
+      )
+    end
+
+    def with_accessibility(label, role: @accessible_role)
+      self.class.new(
+        color: @color,
+        flip_x: @flip_x,
+        flip_y: @flip_y,
+        custom_size: @custom_size,
+        anchor: @anchor,
+        group: @group,
+        accessible_label: label,
+        accessible_role: role,
+        texture_path: @texture_path,
+        jelly: @jelly,
+        z_index: @z_index,
+        atlas_rect: @atlas_rect
+      )
+    end
+
+    def with_texture_path(texture_path)
+      self.class.new(
+        color: @color,
+        flip_x: @flip_x,
+        flip_y: @flip_y,
+        custom_size: @custom_size,
+        anchor: @anchor,
+        group: @group,
+        accessible_label: @accessible_label,
+        accessible_role: @accessible_role,
+        texture_path: texture_path,
+        jelly: @jelly,
+        z_index: @z_index,
+        atlas_rect: @atlas_rect
+      )
+    end
+
+    def with_jelly(jelly)
+      self.class.new(
+        color: @color,
+        flip_x: @flip_x,
+        flip_y: @flip_y,
+        custom_size: @custom_size,
+        anchor: @anchor,
+        group: @group,
+        accessible_label: @accessible_label,
+        accessible_role: @accessible_role,
+        texture_path: @texture_path,
+        jelly: jelly,
+        z_index: @z_index,
+        atlas_rect: @atlas_rect
+      )
+    end
+
+    def with_z_index(z_index)
+      self.class.new(
+        color: @color,
+        flip_x: @flip_x,
+        flip_y: @flip_y,
+        custom_size: @custom_size,
+        anchor: @anchor,
+        group: @group,
+        accessible_label: @accessible_label,
+        accessible_role: @accessible_role,
+        texture_path: @texture_path,
+        jelly: @jelly,
+        z_index: z_index,
+        atlas_rect: @atlas_rect
+      )
+    end
+
+    def with_atlas_rect(atlas_rect)
+      self.class.new(
+        color: @color,
+        flip_x: @flip_x,
+        flip_y: @flip_y,
+        custom_size: @custom_size,
+        anchor: @anchor,
+        group: @group,
+        accessible_label: @accessible_label,
+        accessible_role: @accessible_role,
+        texture_path: @texture_path,
+        jelly: @jelly,
+        z_index: @z_index,
+        atlas_rect: atlas_rect
       )
     end
 
@@ -83,6 +248,16 @@ module Bevy
       else
         native['has_custom_size'] = false
       end
+      native['group'] = @group if @group
+      native['accessible_label'] = @accessible_label if @accessible_label
+      native['accessible_role'] = @accessible_role.to_s if @accessible_role
+      native['texture_path'] = @texture_path if @texture_path
+      if @jelly
+        native['mesh_mode'] = true
+        @jelly.each { |key, value| native[key.to_s] = value }
+      end
+      native['z_index'] = @z_index if @z_index
+      native['atlas_rect'] = @atlas_rect if @atlas_rect
       native
     end
 
@@ -98,12 +273,20 @@ module Bevy
         native['anchor_y'] || 0.5
       )
       custom_size = (Vec2.new(native['custom_size_x'], native['custom_size_y']) if native['has_custom_size'])
+      jelly = (JELLY_KEYS.to_h { |key| [key, native[key.to_s]] }.compact if native['mesh_mode'])
       new(
         color: color,
         flip_x: native['flip_x'] || false,
         flip_y: native['flip_y'] || false,
         custom_size: custom_size,
-        anchor: anchor
+        anchor: anchor,
+        group: native['group'],
+        accessible_label: native['accessible_label'],
+        accessible_role: native['accessible_role'],
+        texture_path: native['texture_path'],
+        jelly: jelly,
+        z_index: native['z_index'],
+        atlas_rect: native['atlas_rect']
       )
     end
 
@@ -115,6 +298,13 @@ module Bevy
         anchor: @anchor.to_a
       }
       h[:custom_size] = @custom_size.to_a if @custom_size
+      h[:group] = @group if @group
+      h[:accessible_label] = @accessible_label if @accessible_label
+      h[:accessible_role] = @accessible_role if @accessible_role
+      h[:texture_path] = @texture_path if @texture_path
+      h[:jelly] = @jelly if @jelly
+      h[:z_index] = @z_index if @z_index
+      h[:atlas_rect] = @atlas_rect if @atlas_rect
       h
     end
 
@@ -133,8 +323,48 @@ module Bevy
         h[:custom_size_x] = @custom_size.x
         h[:custom_size_y] = @custom_size.y
       end
+      h[:group] = @group if @group
+      h[:accessible_label] = @accessible_label if @accessible_label
+      h[:accessible_role] = @accessible_role.to_s if @accessible_role
+      h[:texture_path] = @texture_path if @texture_path
+      h.merge!(mesh_mode: true, **@jelly) if @jelly
+      h[:z_index] = @z_index if @z_index
+      h[:atlas_rect] = @atlas_rect if @atlas_rect
       h
     end
+
+    private
+
+    def normalize_atlas_rect(atlas_rect)
+      return nil if atlas_rect.nil?
+
+      rect = if atlas_rect.respond_to?(:min) && atlas_rect.respond_to?(:max)
+               [atlas_rect.min.x, atlas_rect.min.y, atlas_rect.max.x - atlas_rect.min.x,
+                atlas_rect.max.y - atlas_rect.min.y]
+             else
+               Array(atlas_rect)
+             end
+      raise ArgumentError, "atlas_rect must be [x, y, width, height], got #{atlas_rect.inspect}" unless rect.size == 4
+
+      rect = rect.map(&:to_f)
+      raise ArgumentError, 'atlas_rect needs a positive width and height' unless rect[2].positive? && rect[3].positive?
+
+      rect.freeze
+    end
+
+    def normalize_jelly(jelly)
+      case jelly
+      when nil, false then nil
+      when true then {}
+      when Hash
+        unknown = jelly.keys.map(&:to_sym) - JELLY_KEYS
+        raise ArgumentError, "unknown jelly option(s): #{unknown.join(', ')}" unless unknown.empty?
+
+        jelly.transform_keys(&:to_sym).compact
+      else
+        raise ArgumentError, "jelly must be true, false or a Hash, got #{jelly.class}"
+      end
+    end
   end
 
   class SpriteBundle
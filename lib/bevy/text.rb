@@ -2,12 +2,40 @@
 
 module Bevy
   class Text2d
-    attr_reader :content, :font_size, :color
-
-    def initialize(content, font_size: 24.0, color: Color.white)
+    BLEND_MODES = %i[alpha add].freeze
+    JUSTIFY_MODES = %i[left center right].freeze
+    LINEBREAK_MODES = %i[word char none].freeze
+
+    attr_reader :content, :font_size, :color, :accessible_label, :accessible_role, :glow, :blend,
+                :justify, :linebreak
+
+    # Setting `accessible_label` or `accessible_role` (`:button`, `:label` or
+    # `:image`) exposes the text to screen readers. The label defaults to the
+    # content and the role to `:label`.
+    #
+    # `glow: { color:, radius:, intensity: 1.0 }` draws offset copies of the
+    # text behind it, fading out over `radius` pixels.
+    #
+    # `justify` (`:left`, `:center` or `:right`) aligns the lines against each
+    # other; the block itself stays centered on the transform. `linebreak`
+    # breaks lines at words (`:word`), anywhere (`:char`) or only at newlines
+    # (`:none`).
+    def initialize(content, font_size: 24.0, color: Color.white, accessible_label: nil, accessible_role: nil,
+                   glow: nil, blend: :alpha, justify: :left, linebreak: :word)
       @content = content.to_s
       @font_size = font_size.to_f
       @color = color
+      @accessible_label = accessible_label&.to_s
+      @accessible_role = accessible_role&.to_sym
+      @glow = normalize_glow(glow)
+      @blend = blend.to_sym
+      raise ArgumentError, "unknown text blend: #{blend}" unless BLEND_MODES.include?(@blend)
+
+      @justify = justify.to_sym
+      raise ArgumentError, "unknown text justify: #{justify}" unless JUSTIFY_MODES.include?(@justify)
+
+      @linebreak = linebreak.to_sym
+      raise ArgumentError, "unknown text linebreak: #{linebreak}" unless LINEBREAK_MODES.include?(@linebreak)
     end
 
     def type_name
@@ -15,19 +43,42 @@ module Bevy
     end
 
     def with_content(content)
-      self.class.new(content, font_size: @font_size, color: @color)
+      self.class.new(content, font_size: @font_size, color: @color, **carried_options)
     end
 
     def with_font_size(font_size)
-      self.class.new(@content, font_size: font_size, color: @color)
+      self.class.new(@content, font_size: font_size, color: @color, **carried_options)
     end
 
     def with_color(color)
-      self.class.new(@content, font_size: @font_size, color: color)
+      self.class.new(@content, font_size: @font_size, color: color, **carried_options)
+    end
+
+    def with_accessibility(label, role: @accessible_role)
+      self.class.new(@content, font_size: @font_size, color: @color,
+                               **carried_options, accessible_label: label, accessible_role: role)
+    end
+
+    def with_glow(glow)
+      self.class.new(@content, font_size: @font_size, color: @color, **carried_options, glow: glow)
+    end
+
+    # `:add` is rendered as alpha blending where the renderer cannot change
+    # the text blend state; see `RenderApp#text_blend_supported?`.
+    def with_blend(blend)
+      self.class.new(@content, font_size: @font_size, color: @color, **carried_options, blend: blend)
+    end
+
+    def with_justify(justify)
+      self.class.new(@content, font_size: @font_size, color: @color, **carried_options, justify: justify)
+    end
+
+    def with_linebreak(linebreak)
+      self.class.new(@content, font_size: @font_size, color: @color, **carried_options, linebreak: linebreak)
     end
 
     def to_sync_hash
-      {
+      h = {
         content: @content,
         font_size: @font_size,
         color_r: @color.r,
@@ -35,6 +86,13 @@ module Bevy
         color_b: @color.b,
         color_a: @color.a
       }
+      h[:accessible_label] = @accessible_label if @accessible_label
+      h[:accessible_role] = @accessible_role.to_s if @accessible_role
+      h.merge!(glow_fields) if @glow
+      h[:blend] = @blend.to_s unless @blend == :alpha
+      h[:justify] = @justify.to_s unless @justify == :left
+      h[:linebreak] = @linebreak.to_s unless @linebreak == :word
+      h
     end
 
     def to_native
@@ -45,6 +103,12 @@ module Bevy
       native['color_g'] = @color.g
       native['color_b'] = @color.b
       native['color_a'] = @color.a
+      native['accessible_label'] = @accessible_label if @accessible_label
+      native['accessible_role'] = @accessible_role.to_s if @accessible_role
+      glow_fields.each { |key, value| native[key.to_s] = value } if @glow
+      native['blend'] = @blend.to_s
+      native['justify'] = @justify.to_s
+      native['linebreak'] = @linebreak.to_s
       native
     end
 
@@ -58,9 +122,64 @@ module Bevy
       new(
         native['content'] || '',
         font_size: native['font_size'] || 24.0,
-        color: color
+        color: color,
+        accessible_label: native['accessible_label'],
+        accessible_role: native['accessible_role'],
+        glow: glow_from_native(native),
+        blend: native['blend'] || :alpha,
+        justify: native['justify'] || :left,
+        linebreak: native['linebreak'] || :word
       )
     end
+
+    def self.glow_from_native(native)
+      return nil unless native['glow_radius']
+
+      {
+        color: Color.rgba(native['glow_r'] || 1.0, native['glow_g'] || 1.0, native['glow_b'] || 1.0,
+                          native['glow_a'] || 1.0),
+        radius: native['glow_radius'],
+        intensity: native['glow_intensity'] || 1.0
+      }
+    end
+    private_class_method :glow_from_native
+
+    private
+
+    def carried_options
+      {
+        accessible_label: @accessible_label,
+        accessible_role: @accessible_role,
+        glow: @glow,
+        blend: @blend,
+        justify: @justify,
+        linebreak: @linebreak
+      }
+    end
+
+    def normalize_glow(glow)
+      return nil if glow.nil?
+
+      radius = glow.fetch(:radius).to_f
+      raise ArgumentError, 'glow radius must not be negative' if radius.negative?
+
+      {
+        color: glow[:color] || @color,
+        radius: radius,
+        intensity: (glow[:intensity] || 1.0).to_f
+      }
+    end
+
+    def glow_fields
+      {
+        glow_r: @glow[:color].r,
+        glow_g: @glow[:color].g,
+        glow_b: @glow[:color].b,
+        glow_a: @glow[:color].a,
+        glow_radius: @glow[:radius],
+        glow_intensity: @glow[:intensity]
+      }
+    end
   end
 
   class TextStyle
@@ -29,6 +29,7 @@ module Bevy
     attr_accessor :cursor_visible, :cursor_grab_mode
     attr_accessor :focused, :visible, :always_on_top
     attr_accessor :resolution_scale_factor
+    attr_accessor :min_width, :min_height
 
     def initialize(
       title: 'Bevy App',
@@ -53,6 +54,14 @@ module Bevy
       @visible = true
       @always_on_top = false
       @resolution_scale_factor = 1.0
+      @min_width = nil
+      @min_height = nil
+    end
+
+    def set_min_size(width, height)
+      @min_width = width.to_f
+      @min_height = height.to_f
+      self
     end
 
     def set_title(title)
@@ -159,11 +168,144 @@ module Bevy
         resizable: @resizable,
         decorations: @decorations,
         cursor_visible: @cursor_visible,
-        cursor_grab_mode: @cursor_grab_mode
+        cursor_grab_mode: @cursor_grab_mode,
+        min_width: @min_width,
+        min_height: @min_height
       }
     end
   end
 
+  # Drawable window area excluding OS insets (title bars, notches).
+  # Edges are in window-centered logical pixels with y up, matching mouse_position.
+  class SafeArea
+    attr_reader :window_width, :window_height, :top, :right, :bottom, :left
+
+    def initialize(window_width:, window_height:, top: 0.0, right: 0.0, bottom: 0.0, left: 0.0)
+      @window_width = window_width.to_f
+      @window_height = window_height.to_f
+      @top = [top.to_f, 0.0].max
+      @right = [right.to_f, 0.0].max
+      @bottom = [bottom.to_f, 0.0].max
+      @left = [left.to_f, 0.0].max
+    end
+
+    def self.full(width, height)
+      new(window_width: width, window_height: height)
+    end
+
+    def self.from_metrics(metrics)
+      top, right, bottom, left = Array(metrics[:insets] || metrics['insets'])
+      new(
+        window_width: metrics[:width] || metrics['width'] || 0.0,
+        window_height: metrics[:height] || metrics['height'] || 0.0,
+        top: top || 0.0,
+        right: right || 0.0,
+        bottom: bottom || 0.0,
+        left: left || 0.0
+      )
+    end
+
+    def insets?
+      [@top, @right, @bottom, @left].any?(&:positive?)
+    end
+
+    def usable_size
+      Vec2.new(
+        [@window_width - @left - @right, 0.0].max,
+        [@window_height - @top - @bottom, 0.0].max
+      )
+    end
+
+    def min_x
+      (-@window_width / 2.0) + @left
+    end
+
+    def max_x
+      [(@window_width / 2.0) - @right, min_x].max
+    end
+
+    def min_y
+      (-@window_height / 2.0) + @bottom
+    end
+
+    def max_y
+      [(@window_height / 2.0) - @top, min_y].max
+    end
+
+    def center
+      Vec2.new((min_x + max_x) / 2.0, (min_y + max_y) / 2.0)
+    end
+
+    def contains?(point)
+      point.x.between?(min_x, max_x) && point.y.between?(min_y, max_y)
+    end
+
+    def to_a
+      [@window_width, @window_height, @top, @right, @bottom, @left]
+    end
+
+    def ==(other)
+      other.is_a?(SafeArea) && to_a == other.to_a
+    end
+    alias eql? ==
+
+    def hash
+      to_a.hash
+    end
+  end
+
+  class SafeAreaChanged
+    attr_reader :safe_area, :previous
+
+    def initialize(safe_area:, previous: nil)
+      @safe_area = safe_area
+      @previous = previous
+    end
+
+    def type_name
+      'SafeAreaChanged'
+    end
+  end
+
+  # Resolves a screen-relative position (e.g. a HUD corner) against a SafeArea.
+  # Offsets are added in screen space (x right, y up).
+  class ScreenAnchor
+    POSITIONS = {
+      top_left: [-1, 1], top: [0, 1], top_right: [1, 1],
+      left: [-1, 0], center: [0, 0], right: [1, 0],
+      bottom_left: [-1, -1], bottom: [0, -1], bottom_right: [1, -1]
+    }.freeze
+
+    attr_reader :position, :offset, :anchor_to_safe_area
+
+    def initialize(position = :center, offset: nil, anchor_to_safe_area: false)
+      @position = position.to_sym
+      raise ArgumentError, "Unknown anchor position: #{position}" unless POSITIONS.key?(@position)
+
+      @offset = offset || Vec2.zero
+      @anchor_to_safe_area = anchor_to_safe_area
+    end
+
+    def resolve(safe_area)
+      area = @anchor_to_safe_area ? safe_area : SafeArea.full(safe_area.window_width, safe_area.window_height)
+      fx, fy = POSITIONS[@position]
+      Vec2.new(
+        pick(fx, area.min_x, area.max_x) + @offset.x,
+        pick(fy, area.min_y, area.max_y) + @offset.y
+      )
+    end
+
+    private
+
+    def pick(factor, min, max)
+      case factor
+      when -1 then min
+      when 1 then max
+      else (min + max) / 2.0
+      end
+    end
+  end
+
   class PrimaryWindow
     attr_reader :window
 
@@ -247,6 +389,70 @@ module Bevy
     end
   end
 
+  class FileDragAndDrop
+    DROPPED = 'file_dropped'
+    HOVERED = 'file_hovered'
+    HOVER_CANCELLED = 'file_hover_cancelled'
+
+    attr_reader :kind, :window_id, :path
+
+    def initialize(kind:, window_id:, path: nil)
+      @kind = kind.to_s
+      @window_id = window_id
+      @path = path
+    end
+
+    def dropped?
+      @kind == DROPPED
+    end
+
+    def hovered?
+      @kind == HOVERED
+    end
+
+    def cancelled?
+      @kind == HOVER_CANCELLED
+    end
+
+    def type_name
+      'FileDragAndDrop'
+    end
+  end
+
+  # A file dragged onto the window, as drained by `RenderApp#drain_file_drops`.
+  class FileDrop
+    KINDS = %w[dropped hovered cancelled].freeze
+
+    attr_reader :kind, :path
+
+    def initialize(kind:, path: nil)
+      @kind = kind.to_s
+      raise ArgumentError, "Unknown file drop kind: #{@kind}" unless KINDS.include?(@kind)
+
+      @path = path
+    end
+
+    def self.from_native(data)
+      new(kind: data[:kind] || data['kind'], path: data[:path] || data['path'])
+    end
+
+    def dropped?
+      @kind == 'dropped'
+    end
+
+    def hovered?
+      @kind == 'hovered'
+    end
+
+    def cancelled?
+      @kind == 'cancelled'
+    end
+
+    def to_h
+      { kind: @kind, path: @path }
+    end
+  end
+
   class WindowCreated
     attr_reader :window_id
 
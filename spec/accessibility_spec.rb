@@ -0,0 +1,61 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::RenderApp, :gpu do
+  let(:transform) { Bevy::Transform.new.to_sync_hash }
+
+  def sprite(**accessibility)
+    Bevy::Sprite.new(custom_size: Bevy::Vec2.new(4.0, 4.0), **accessibility).to_sync_hash
+  end
+
+  # Runs `frames` frames, calling `script` with each frame number before
+  # recording the accessibility node of entity 1. Operations queued in frame
+  # n are applied before frame n + 1 is recorded.
+  def nodes_by_frame(frames:, &script)
+    app = described_class.new(headless: true, width: 32.0, height: 32.0, frames: frames)
+    nodes = []
+    app.run do
+      script.call(app, app.frame_count)
+      nodes << app.accessibility_node(1)
+    end
+    nodes
+  ensure
+    app&.close
+  end
+
+  it 'adds a node once a synced sprite gets accessibility metadata' do
+    nodes = nodes_by_frame(frames: 4) do |app, frame|
+      app.sync_sprite(1, sprite, transform) if frame.zero?
+      app.sync_sprite(1, sprite(accessible_label: 'Play', accessible_role: :button), transform) if frame == 1
+    end
+
+    expect(nodes).to eq([nil, nil, { accessible_label: 'Play', accessible_role: :button },
+                         { accessible_label: 'Play', accessible_role: :button }])
+  end
+
+  it 'rebuilds the node when the label changes' do
+    nodes = nodes_by_frame(frames: 3) do |app, frame|
+      app.sync_sprite(1, sprite(accessible_label: 'Lives: 3'), transform) if frame.zero?
+      app.sync_sprite(1, sprite(accessible_label: 'Lives: 2'), transform) if frame == 1
+    end
+
+    expect(nodes.drop(1)).to eq([{ accessible_label: 'Lives: 3', accessible_role: :image },
+                                 { accessible_label: 'Lives: 2', accessible_role: :image }])
+  end
+
+  it 'removes the node when the metadata is cleared' do
+    nodes = nodes_by_frame(frames: 3) do |app, frame|
+      app.sync_sprite(1, sprite(accessible_label: 'Logo'), transform) if frame.zero?
+      app.sync_sprite(1, sprite, transform) if frame == 1
+    end
+
+    expect(nodes.drop(1)).to eq([{ accessible_label: 'Logo', accessible_role: :image }, nil])
+  end
+
+  it 'gives a text its content as the label' do
+    nodes = nodes_by_frame(frames: 2) do |app, frame|
+      app.sync_text(1, Bevy::Text2d.new('Start', accessible_role: :button).to_sync_hash, transform) if frame.zero?
+    end
+
+    expect(nodes.last).to eq({ accessible_label: 'Start', accessible_role: :button })
+  end
+end
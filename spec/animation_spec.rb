@@ -54,6 +54,10 @@ RSpec.describe Bevy::Easing do
     it 'defaults to linear for unknown easing' do
       expect(described_class.apply(:unknown, 0.5)).to eq(0.5)
     end
+
+    it 'falls back to Bevy::Ease for curves without a method' do
+      expect(described_class.apply(:quart_in, 0.5)).to be_within(1e-6).of(0.0625)
+    end
   end
 end
 
@@ -603,3 +607,61 @@ RSpec.describe Bevy::TweenGroup do
     end
   end
 end
+
+RSpec.describe Bevy::ClipBuilder do
+  let(:clip) { described_class.new }
+
+  it 'interpolates between sorted keyframes' do
+    clip.track(1, :x, [[0.4, 0.0], [0.0, -300.0]])
+    expect(clip.sample(1, :x, 0.2)).to be_within(0.001).of(-150.0)
+    expect(clip.tracks.first.keyframes.map(&:first)).to eq([0.0, 0.4])
+  end
+
+  it 'applies the track easing' do
+    clip.track(1, :alpha, [[0.0, 0.0], [1.0, 1.0]], ease: :ease_out)
+    expect(clip.sample(1, :alpha, 0.5)).to be_within(0.001).of(0.875)
+  end
+
+  it 'holds values in step mode' do
+    clip.track(2, :scale, [[0.0, 1.0], [0.5, 2.0], [1.0, 3.0]], ease: :step)
+    expect(clip.sample(2, :scale, 0.49)).to eq(1.0)
+    expect(clip.sample(2, :scale, 0.5)).to eq(2.0)
+    expect(clip.sample(2, :scale, 0.99)).to eq(2.0)
+  end
+
+  it 'holds the end values outside the keyframe range' do
+    clip.track(1, :y, [[0.2, 5.0], [0.6, 10.0]])
+    expect(clip.sample(1, :y, 0.0)).to eq(5.0)
+    expect(clip.sample(1, :y, 5.0)).to eq(10.0)
+  end
+
+  it 'treats single and coincident keyframes as zero-duration tracks' do
+    clip.track(1, :rotation, [[0.0, 1.5]])
+    clip.track(2, :x, [[0.3, 1.0], [0.3, 4.0]])
+    expect(clip.sample(1, :rotation, 10.0)).to eq(1.5)
+    expect(clip.sample(2, :x, 0.3)).to eq(4.0)
+    expect(clip.duration).to eq(0.3)
+  end
+
+  it 'rejects unknown properties and easings' do
+    expect { clip.track(1, :width, [[0.0, 1.0]]) }.to raise_error(ArgumentError)
+    expect { clip.track(1, :x, [[0.0, 1.0]], ease: :wobble) }.to raise_error(ArgumentError)
+  end
+
+  it 'accepts every Bevy::Ease curve and lists them when the easing is unknown' do
+    clip.track(1, :x, [[0.0, 0.0], [1.0, 1.0]], ease: :bounce_out)
+    expect(clip.sample(1, :x, 0.5)).to be_within(1e-5).of(Bevy::Ease.apply(:bounce_out, 0.5))
+    expect { clip.track(1, :y, [[0.0, 1.0]], ease: :wobble) }
+      .to raise_error(ArgumentError, /Unknown clip easing: wobble \(expected one of linear, .*bounce_in_out, step, hold\)/)
+  end
+
+  it 'compiles to the native clip hash' do
+    entity = instance_double(Bevy::Entity, id: 7)
+    clip.speed = 2.0
+    clip.track(entity, :x, [[0.0, -300], [0.4, 0]], ease: :ease_out)
+    expect(clip.to_native).to eq(
+      speed: 2.0,
+      tracks: [{ entity_id: 7, property: 'x', easing: 'ease_out', keyframes: [[0.0, -300.0], [0.4, 0.0]] }]
+    )
+  end
+end
@@ -1,5 +1,7 @@
 # frozen_string_literal: true
 
+require 'tempfile'
+
 class TestResource < Bevy::ResourceDSL
   attribute :counter, :integer, default: 0
 end
@@ -107,6 +109,36 @@ RSpec.describe Bevy::SystemContext do
       keyboard.press(Bevy::KeyCode::SPACE)
       expect(context.key_pressed?(Bevy::KeyCode::SPACE)).to be true
     end
+
+    it 'reports nothing pressed while input is captured' do
+      keyboard.press(Bevy::KeyCode::SPACE)
+      mouse.press('LEFT')
+      context.set_input_captured(true)
+
+      expect(context.input_captured?).to be true
+      expect(context.key_pressed?(Bevy::KeyCode::SPACE)).to be false
+      expect(context.mouse_pressed?(:left)).to be false
+      expect(context.raw_key_pressed?(Bevy::KeyCode::SPACE)).to be true
+      expect(context.raw_mouse_pressed?(:left)).to be true
+    end
+  end
+
+  describe '#key_held_duration' do
+    it 'reads the hold time from the render app' do
+      render_app = double('render_app', key_held_duration: 1.25)
+      context = described_class.new(
+        world: world, resources: resources, events: events, time: time, keyboard: keyboard,
+        mouse: mouse, gamepads: gamepads, app: app, render_app: render_app
+      )
+
+      expect(context.key_held_duration('SPACE')).to eq(1.25)
+      expect(render_app).to have_received(:key_held_duration).with('SPACE')
+    end
+
+    it 'is 0.0 without a render app' do
+      keyboard.press(Bevy::KeyCode::SPACE)
+      expect(context.key_held_duration(Bevy::KeyCode::SPACE)).to eq(0.0)
+    end
   end
 
   describe '#mouse_position' do
@@ -118,6 +150,101 @@ RSpec.describe Bevy::SystemContext do
     end
   end
 
+  describe '#mouse_scroll' do
+    it 'returns the scroll delta without a render app' do
+      mouse.set_scroll(0.0, 2.0)
+      expect(context.mouse_scroll.y).to eq(2.0)
+    end
+
+    it 'reads the frame scroll from the render app' do
+      render_app = double('render_app', scroll_delta: [0.5, -3.0])
+      context = described_class.new(
+        world: world, resources: resources, events: events, time: time,
+        keyboard: keyboard, mouse: mouse, gamepads: gamepads, app: app, render_app: render_app
+      )
+
+      expect(context.mouse_scroll.to_a).to eq([0.5, -3.0])
+    end
+  end
+
+  describe '#set_camera_transform' do
+    let(:render_app) { double('render_app') }
+
+    it 'sends the whole transform to the render app in one call' do
+      allow(render_app).to receive(:set_camera_transform)
+      context = described_class.new(
+        world: world, resources: resources, events: events, time: time,
+        keyboard: keyboard, mouse: mouse, gamepads: gamepads, app: app, render_app: render_app
+      )
+      transform = Bevy::Transform.new(
+        translation: Bevy::Vec3.new(10.0, 20.0, 5.0),
+        scale: Bevy::Vec3.new(2.0, 2.0, 1.0)
+      )
+
+      context.set_camera_transform(transform)
+
+      expect(render_app).to have_received(:set_camera_transform).with(
+        hash_including(x: 10.0, y: 20.0, z: 5.0, scale_x: 2.0, scale_y: 2.0)
+      )
+    end
+
+    it 'is a no-op without a render app' do
+      expect(context.set_camera_transform(Bevy::Transform.identity)).to be_nil
+      expect(context.camera_rotation).to eq(0.0)
+    end
+  end
+
+  describe '#shake_camera' do
+    it 'forwards the shake to the render app as floats' do
+      render_app = double('render_app')
+      allow(render_app).to receive(:shake_camera)
+      context = described_class.new(
+        world: world, resources: resources, events: events, time: time,
+        keyboard: keyboard, mouse: mouse, gamepads: gamepads, app: app, render_app: render_app
+      )
+
+      context.shake_camera(8, 0.25)
+
+      expect(render_app).to have_received(:shake_camera).with(8.0, 0.25)
+    end
+
+    it 'is a no-op without a render app' do
+      expect(context.shake_camera(8, 0.25)).to be_nil
+    end
+  end
+
+  describe 'zoom helpers' do
+    let(:render_app) { double('render_app') }
+    let(:render_context) do
+      described_class.new(
+        world: world, resources: resources, events: events, time: time,
+        keyboard: keyboard, mouse: mouse, gamepads: gamepads, app: app, render_app: render_app
+      )
+    end
+
+    it 'forwards zoom limits to the render app as floats' do
+      allow(render_app).to receive(:set_zoom_limits)
+
+      render_context.set_zoom_limits(1, 4)
+
+      expect(render_app).to have_received(:set_zoom_limits).with(1.0, 4.0)
+    end
+
+    it 'returns the clamped scale from zoom_in and zoom_out' do
+      allow(render_app).to receive(:zoom_in).with(0.5).and_return(0.5)
+      allow(render_app).to receive(:zoom_out).with(2.0).and_return(2.5)
+
+      expect(render_context.zoom_in(0.5)).to eq(0.5)
+      expect(render_context.zoom_out(2)).to eq(2.5)
+    end
+
+    it 'reports the default limits and scale without a render app' do
+      expect(context.zoom_limits).to eq([0.1, 10.0])
+      expect(context.zoom_in(0.5)).to eq(1.0)
+      expect(context.zoom_out(0.5)).to eq(1.0)
+    end
+  end
+
   describe 'gamepad helpers' do
     before do
       gamepads.connect(0)
@@ -378,167 +505,1984 @@ RSpec.describe Bevy::App do
     end
   end
 
-  describe 'render gamepad synchronization' do
+  describe 'sync system configuration' do
     let(:render_app) { double('render_app') }
 
-    before do
-      allow(render_app).to receive(:pressed_keys).and_return([])
-      allow(render_app).to receive(:mouse_position).and_return([0.0, 0.0])
-      allow(render_app).to receive(:mouse_button_pressed?).and_return(false)
+    it 'passes disabled sync systems to the render app config' do
+      app = described_class.new(render: true, window: { title: 'Game' }, sync_systems: { text: false })
+
+      expect(app.send(:render_app_config)).to eq(title: 'Game', text_sync: false, mesh_sync: true)
     end
 
-    it 'syncs gamepad button and axis state from render app' do
+    it 'skips text and mesh sync when the render app has them disabled' do
       app = described_class.new(render: true)
-      allow(render_app).to receive(:gamepads_state).and_return([
-                                                            {
-                                                              id: 1,
-                                                              name: 'Pad One',
-                                                              buttons_pressed: [Bevy::GamepadButton::SOUTH],
-                                                              axes: {
-                                                                Bevy::GamepadAxis::LEFT_STICK_X => 0.5,
-                                                                Bevy::GamepadAxis::LEFT_STICK_Y => -0.25
-                                                              }
-                                                            }
-                                                          ])
+      app.world.spawn_entity(Bevy::Text2d.new('score'), Bevy::Transform.identity)
+      allow(render_app).to receive(:text_sync_enabled?).and_return(false)
+      allow(render_app).to receive(:mesh_sync_enabled?).and_return(false)
+      allow(render_app).to receive(:sync_text)
+      allow(render_app).to receive(:sync_mesh)
       app.instance_variable_set(:@render_app, render_app)
 
-      app.send(:sync_input_from_bevy)
+      app.send(:sync_sprites_to_bevy)
 
-      gamepad = app.gamepads.get(1)
-      expect(gamepad).not_to be_nil
-      expect(gamepad.pressed?(Bevy::GamepadButton::SOUTH)).to be true
-      expect(gamepad.just_pressed?(Bevy::GamepadButton::SOUTH)).to be true
-      expect(gamepad.axis_raw(Bevy::GamepadAxis::LEFT_STICK_X)).to be_within(0.001).of(0.5)
-      expect(gamepad.axis_raw(Bevy::GamepadAxis::LEFT_STICK_Y)).to be_within(0.001).of(-0.25)
+      expect(render_app).not_to have_received(:sync_text)
+      expect(render_app).not_to have_received(:sync_mesh)
     end
+  end
 
-    it 'disconnects removed gamepads and tracks button release transitions' do
+  describe 'batched sprite sync' do
+    let(:render_app) { double('render_app', remove_sprite: nil) }
+    let(:batches) { [] }
+
+    def spawn_sprites(app, count)
+      Array.new(count) do |i|
+        app.world.spawn_entity(Bevy::Sprite.new, Bevy::Transform.new(translation: Bevy::Vec3.new(i.to_f, 0.0, 0.0)))
+      end
+    end
+
+    it 'sends every world sprite in one call' do
       app = described_class.new(render: true)
-      allow(render_app).to receive(:gamepads_state).and_return(
-        [
-          {
-            id: 1,
-            name: 'Pad One',
-            buttons_pressed: [Bevy::GamepadButton::SOUTH],
-            axes: {}
-          }
-        ],
-        [
-          {
-            id: 1,
-            name: 'Pad One',
-            buttons_pressed: [],
-            axes: {}
-          }
-        ],
-        []
-      )
+      entities = spawn_sprites(app, 3)
+      allow(render_app).to receive(:sync_sprites_batch) { |entries| batches << entries }
       app.instance_variable_set(:@render_app, render_app)
 
-      app.send(:sync_input_from_bevy)
-      app.send(:sync_input_from_bevy)
+      app.send(:sync_sprites_to_bevy)
 
-      gamepad = app.gamepads.get(1)
-      expect(gamepad.pressed?(Bevy::GamepadButton::SOUTH)).to be false
-      expect(gamepad.just_released?(Bevy::GamepadButton::SOUTH)).to be true
+      expect(batches.length).to eq(1)
+      expect(batches.first.map(&:first)).to match_array(entities.map(&:id))
+      id, sprite, transform = batches.first.find { |entry| entry.first == entities.last.id }
+      expect(id).to eq(entities.last.id)
+      expect(sprite).to eq(Bevy::Sprite.new.to_sync_hash)
+      expect(transform).to include(x: 2.0, y: 0.0)
+    end
 
-      app.send(:sync_input_from_bevy)
-      expect(app.gamepads.get(1)).to be_nil
+    it 'skips the call when there is nothing to sync' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:sync_sprites_batch)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_sprites_to_bevy)
+
+      expect(render_app).not_to have_received(:sync_sprites_batch)
+    end
+
+    it 'crosses into the render app once per frame instead of once per sprite' do
+      calls = Hash.new(0)
+      batched = double('render_app')
+      allow(batched).to receive(:sync_sprites_batch) { calls[:batched] += 1 }
+      single = double('render_app')
+      allow(single).to receive(:sync_sprite) { calls[:single] += 1 }
+
+      [batched, single].each do |native|
+        app = described_class.new(render: true)
+        spawn_sprites(app, 1000)
+        app.instance_variable_set(:@render_app, native)
+        app.send(:sync_sprites_to_bevy)
+      end
+
+      expect(calls).to eq(batched: 1, single: 1000)
     end
   end
 
-  describe 'gamepad rumble forwarding' do
+  describe 'accessibility' do
     let(:render_app) { double('render_app') }
 
-    before do
-      allow(render_app).to receive(:queue_gamepad_rumble)
+    it 'is inactive without a render app' do
+      expect(described_class.new.accessibility_active?).to be false
     end
 
-    it 'forwards pending rumble requests to render app and clears them' do
+    it 'reports whether an assistive technology is connected' do
       app = described_class.new(render: true)
+      allow(render_app).to receive(:accessibility_active?).and_return(true)
       app.instance_variable_set(:@render_app, render_app)
-      app.gamepads.connect(2)
-      gamepad = app.gamepads.get(2)
-      gamepad.rumble(Bevy::RumbleRequest.new(strong: 0.8, weak: 0.3, duration: 0.5))
 
-      app.send(:sync_gamepad_rumble_to_bevy)
+      expect(app.accessibility_active?).to be true
+      expect(app.send(:build_context).accessibility_active?).to be true
+    end
 
-      expect(render_app).to have_received(:queue_gamepad_rumble).with(2, 0.8, 0.3, 0.5)
-      expect(gamepad.pending_rumble).to be_nil
+    it 'forwards the toggle to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_accessibility_enabled)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.set_accessibility_enabled(false)
+
+      expect(app.accessibility_enabled?).to be false
+      expect(render_app).to have_received(:set_accessibility_enabled).with(false)
     end
 
-    it 'does not call render app when there is no pending rumble' do
+    it 'reads an entity\'s accessibility node from the render app' do
       app = described_class.new(render: true)
+      node = { accessible_label: 'Play', accessible_role: :button }
+      allow(render_app).to receive(:accessibility_node).with(7).and_return(node)
       app.instance_variable_set(:@render_app, render_app)
-      app.gamepads.connect(3)
 
-      app.send(:sync_gamepad_rumble_to_bevy)
+      expect(app.accessibility_node(Struct.new(:id).new(7))).to eq(node)
+    end
+  end
 
-      expect(render_app).not_to have_received(:queue_gamepad_rumble)
+  describe '#set_input_captured' do
+    let(:render_app) { double('render_app') }
+
+    it 'stores the capture until the render app starts' do
+      app = described_class.new(render: true)
+
+      app.set_input_captured(true)
+
+      expect(app.input_captured?).to be true
+    end
+
+    it 'forwards the capture to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_input_captured)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.set_input_captured(true)
+      app.set_input_captured(false)
+
+      expect(render_app).to have_received(:set_input_captured).with(true).ordered
+      expect(render_app).to have_received(:set_input_captured).with(false).ordered
     end
   end
 
-  describe 'render picking synchronization' do
+  describe 'input actions' do
     let(:render_app) { double('render_app') }
 
-    before do
-      allow(render_app).to receive(:pressed_keys).and_return([])
-      allow(render_app).to receive(:mouse_position).and_return([0.0, 0.0])
-      allow(render_app).to receive(:mouse_button_pressed?).and_return(false)
-      allow(render_app).to receive(:gamepads_state).and_return([])
+    it 'stores bindings until the render app starts' do
+      app = described_class.new(render: true)
+
+      app.bind_action(:jump, keys: [Bevy::KeyCode::SPACE], mouse_buttons: [:left], gamepad_buttons: ['South'])
+
+      expect(app.actions).to eq(['jump'])
+      expect(app.action_binding(:jump)).to eq(keys: ['SPACE'], mouse_buttons: ['LEFT'], gamepad_buttons: ['South'])
+      expect(app.action_pressed?(:jump)).to be false
     end
 
-    it 'imports picking events from render app into the event registry' do
-      allow(render_app).to receive(:drain_picking_events).and_return([
-                                                                      {
-                                                                        kind: 'over',
-                                                                        target_id: 77,
-                                                                        pointer_id: 'Mouse',
-                                                                        position: [10.5, 20.25],
-                                                                        camera_id: 5,
-                                                                        depth: 1.5,
-                                                                        hit_position: [1.0, 2.0, 3.0],
-                                                                        hit_normal: [0.0, 0.0, 1.0]
-                                                                      },
-                                                                      {
-                                                                        kind: 'click',
-                                                                        target_id: 77,
-                                                                        pointer_id: 'Mouse',
-                                                                        button: 'Primary',
-                                                                        position: [10.5, 20.25]
-                                                                      }
-                                                                    ])
+    it 'replaces the sources of a rebound action on the running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:bind_action)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.bind_action('jump', keys: [Bevy::KeyCode::SPACE])
+      app.bind_action('jump', keys: [Bevy::KeyCode::W], gamepad_buttons: ['South'])
+
+      expect(render_app).to have_received(:bind_action)
+        .with('jump', { keys: ['W'], mouse_buttons: [], gamepad_buttons: ['South'] })
+      expect(app.action_binding('jump')[:keys]).to eq(['W'])
+    end
 
+    it 'asks the render app whether an action is pressed' do
       app = described_class.new(render: true)
+      allow(render_app).to receive_messages(action_pressed?: true, action_just_pressed?: false,
+                                            action_just_released?: false)
       app.instance_variable_set(:@render_app, render_app)
 
-      app.send(:sync_input_from_bevy)
+      expect(app.action_pressed?(:jump)).to be true
+      expect(app.action_just_pressed?(:jump)).to be false
+      expect(render_app).to have_received(:action_pressed?).with('jump')
+    end
 
-      reader = app.events.reader(Bevy::PickingEvent)
-      events = reader.read
-      expect(events.map(&:kind)).to include('over', 'click')
+    it 'rejects unknown key names' do
+      expect { described_class.new.bind_action(:jump, keys: ['SPACEBAR']) }.to raise_error(ArgumentError, /SPACEBAR/)
+    end
 
-      over_event = events.find { |event| event.kind == 'over' }
-      expect(over_event.target_id).to eq(77)
-      expect(over_event.pointer_id).to eq('Mouse')
-      expect(over_event.position).to be_a(Bevy::Vec2)
-      expect(over_event.position.x).to be_within(0.001).of(10.5)
-      expect(over_event.position.y).to be_within(0.001).of(20.25)
-      expect(over_event.camera_id).to eq(5)
-      expect(over_event.depth).to be_within(0.001).of(1.5)
-      expect(over_event.hit_position).to be_a(Bevy::Vec3)
-      expect(over_event.hit_normal).to be_a(Bevy::Vec3)
+    it 'forgets unbound actions' do
+      app = described_class.new
+      app.bind_action(:fire, mouse_buttons: ['LEFT'])
+
+      expect(app.unbind_action(:fire)).to be true
+      expect(app.unbind_action(:fire)).to be false
+      expect(app.action_binding(:fire)).to be_nil
     end
+  end
 
-    it 'handles empty picking event batches' do
-      allow(render_app).to receive(:drain_picking_events).and_return([])
+  describe 'window mode' do
+    let(:render_app) { double('render_app') }
+
+    it 'launches with the configured mode' do
+      app = described_class.new(render: true, window: { mode: :borderless_fullscreen })
 
+      expect(app.window_mode).to eq(:borderless)
+      expect(app.send(:render_app_config)[:mode]).to eq(:borderless)
+    end
+
+    it 'rejects unknown modes' do
+      expect { described_class.new.set_window_mode(:tiled) }.to raise_error(ArgumentError)
+    end
+
+    it 'toggles fullscreen on a running render app' do
       app = described_class.new(render: true)
+      allow(render_app).to receive(:set_window_mode)
       app.instance_variable_set(:@render_app, render_app)
+      context = app.send(:build_context)
 
-      expect { app.send(:sync_input_from_bevy) }.not_to raise_error
-      reader = app.events.reader(Bevy::PickingEvent)
-      expect(reader.read).to be_empty
+      context.toggle_fullscreen
+      expect(context.fullscreen?).to be true
+      context.toggle_fullscreen
+
+      expect(app.window_mode).to eq(:windowed)
+      expect(render_app).to have_received(:set_window_mode).with(:fullscreen).ordered
+      expect(render_app).to have_received(:set_window_mode).with(:windowed).ordered
+    end
+
+    it 'toggles back to windowed from borderless' do
+      app = described_class.new(window: { mode: :borderless })
+
+      app.toggle_fullscreen
+
+      expect(app.window_mode).to eq(:windowed)
+    end
+  end
+
+  describe 'headless rendering' do
+    it 'passes the headless options to the render app' do
+      app = described_class.new(render: true, window: { headless: true, frames: 3 })
+
+      expect(app.send(:render_app_config)).to include(headless: true, frames: 3)
+    end
+
+    it 'reads pixels back from the render app' do
+      app = described_class.new(render: true)
+      render_app = double('render_app', read_pixels: [2, 1, "\xFF".b * 8])
+      app.instance_variable_set(:@render_app, render_app)
+
+      expect(app.read_pixels).to eq([2, 1, "\xFF".b * 8])
+    end
+
+    it 'returns nil without a render app' do
+      expect(described_class.new.read_pixels).to be_nil
+    end
+  end
+
+  describe 'render app lifecycle' do
+    it 'closes the render app when setup raises so the app can run again' do
+      app = described_class.new(render: true, window: { headless: true })
+      failing = double('render_app', close: nil)
+      allow(failing).to receive(:initialize!).and_raise(RuntimeError, 'setup failed')
+      retried = double('render_app', close: nil)
+      allow(retried).to receive(:initialize!).and_raise(RuntimeError, 'setup failed again')
+      allow(Bevy::RenderApp).to receive(:new).and_return(failing, retried)
+
+      expect { app.run }.to raise_error(RuntimeError, 'setup failed')
+      expect(failing).to have_received(:close)
+      expect(app.running?).to be(false)
+
+      expect { app.run }.to raise_error(RuntimeError, 'setup failed again')
+      expect(Bevy::RenderApp).to have_received(:new).twice
+    end
+
+    it 'forwards on_exit to the render app' do
+      app = described_class.new(render: true)
+      exit_block = nil
+      render_app = double('render_app')
+      allow(render_app).to receive(:on_exit) { |&block| exit_block = block }
+      app.instance_variable_set(:@render_app, render_app)
+      reasons = []
+
+      app.on_exit { |reason| reasons << reason }
+      exit_block.call(:user_close)
+
+      expect(reasons).to eq([:user_close])
+    end
+
+    it 'calls on_exit with :stop_called when the main loop stops' do
+      app = described_class.new
+      reasons = []
+      app.on_exit { |reason| reasons << reason }
+      app.add_update_system { app.stop }
+
+      app.run
+
+      expect(reasons).to eq([:stop_called])
+    end
+
+    it 'requires a block for on_exit' do
+      expect { described_class.new.on_exit }.to raise_error(ArgumentError)
+    end
+  end
+
+  describe 'msaa' do
+    it 'leaves the sample count to Bevy by default' do
+      app = described_class.new(render: true)
+
+      expect(app.msaa).to be_nil
+      expect(app.send(:render_app_config)).not_to have_key(:msaa)
+    end
+
+    it 'passes a valid sample count to the render app' do
+      app = described_class.new(render: true, window: { msaa: 8 })
+
+      expect(app.msaa).to eq(8)
+      expect(app.send(:render_app_config)[:msaa]).to eq(8)
+    end
+
+    it 'rejects unsupported sample counts' do
+      [0, 3, 16, 4.0, '4'].each do |samples|
+        expect { described_class.new(window: { msaa: samples }) }.to raise_error(ArgumentError, /msaa/)
+      end
+    end
+  end
+
+  describe 'window decorations and transparency' do
+    it 'keeps a decorated, opaque window by default' do
+      app = described_class.new(render: true)
+
+      expect(app).to be_decorations
+      expect(app).not_to be_transparent
+      expect(app.send(:render_app_config)).not_to include(:decorations, :transparent)
+    end
+
+    it 'passes an undecorated, transparent window to the render app' do
+      app = described_class.new(render: true, window: { decorations: false, transparent: true })
+
+      expect(app).not_to be_decorations
+      expect(app).to be_transparent
+      expect(app.send(:render_app_config)).to include(decorations: false, transparent: true)
+    end
+
+    it 'keeps the clear color alpha for compositing over the desktop' do
+      app = described_class.new(render: true, window: { transparent: true, clear_color: [0.0, 0.0, 0.0, 0.25] })
+
+      expect(app.send(:render_app_config)[:clear_color]).to eq([0.0, 0.0, 0.0, 0.25])
+    end
+  end
+
+  describe 'texture placeholder' do
+    it 'draws loading textures white by default' do
+      app = described_class.new(render: true)
+
+      expect(app.texture_placeholder).to eq(:white)
+      expect(app.send(:render_app_config)).not_to have_key(:texture_placeholder)
+    end
+
+    it 'passes the configured placeholder to the render app as a symbol' do
+      app = described_class.new(render: true, window: { texture_placeholder: 'checkerboard' })
+
+      expect(app.texture_placeholder).to eq(:checkerboard)
+      expect(app.send(:render_app_config)[:texture_placeholder]).to eq(:checkerboard)
+    end
+
+    it 'rejects unknown placeholders' do
+      expect do
+        described_class.new(window: { texture_placeholder: :magenta })
+      end.to raise_error(ArgumentError, /texture_placeholder/)
+    end
+  end
+
+  describe 'present mode' do
+    it 'leaves the present mode unset by default' do
+      app = described_class.new(render: true)
+
+      expect(app.present_mode).to be_nil
+      expect(app.send(:render_app_config)).not_to have_key(:present_mode)
+    end
+
+    it 'passes the configured present mode to the render app' do
+      app = described_class.new(render: true, window: { present_mode: 'mailbox' })
+
+      expect(app.present_mode).to eq(:mailbox)
+      expect(app.send(:render_app_config)[:present_mode]).to eq(:mailbox)
+    end
+
+    it 'maps vsync to fifo or auto_no_vsync' do
+      expect(described_class.new(window: { vsync: true }).present_mode).to eq(:fifo)
+
+      app = described_class.new(render: true, window: { vsync: false })
+      expect(app.present_mode).to eq(:auto_no_vsync)
+      expect(app.send(:render_app_config)).not_to have_key(:vsync)
+    end
+
+    it 'prefers present_mode over vsync' do
+      app = described_class.new(window: { vsync: true, present_mode: :immediate })
+
+      expect(app.present_mode).to eq(:immediate)
+    end
+
+    it 'rejects unknown present modes and lists the valid ones' do
+      expect { described_class.new(window: { present_mode: :adaptive }) }
+        .to raise_error(ArgumentError, /unknown present mode: adaptive \(expected fifo, immediate, mailbox, auto_no_vsync\)/)
+    end
+  end
+
+  describe '#set_max_fps' do
+    let(:render_app) { double('render_app') }
+
+    it 'passes the cap to the render app config' do
+      app = described_class.new(render: true, window: { max_fps: 30 })
+      expect(app.max_fps).to eq(30.0)
+      expect(app.send(:render_app_config)[:max_fps]).to eq(30.0)
+
+      app.set_max_fps(0)
+      expect(app.max_fps).to be_nil
+      expect(app.send(:render_app_config)).not_to have_key(:max_fps)
+    end
+
+    it 'forwards a runtime change, with nil removing the cap' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_max_fps)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:build_context).set_max_fps(60)
+      app.set_max_fps(nil)
+
+      expect(render_app).to have_received(:set_max_fps).with(60.0).ordered
+      expect(render_app).to have_received(:set_max_fps).with(nil).ordered
+    end
+
+    it 'rejects negative caps' do
+      expect { described_class.new.set_max_fps(-1) }.to raise_error(ArgumentError, /max_fps/)
+    end
+  end
+
+  describe '#set_clear_color' do
+    let(:render_app) { double('render_app') }
+
+    it 'sets the initial color before the render app starts' do
+      app = described_class.new(render: true)
+
+      app.set_clear_color(0.1, 0.2, 0.3)
+
+      expect(app.clear_color).to eq([0.1, 0.2, 0.3, 1.0])
+      expect(app.send(:render_app_config)[:clear_color]).to eq([0.1, 0.2, 0.3, 1.0])
+    end
+
+    it 'accepts the window option and a Bevy::Color' do
+      app = described_class.new(render: true, window: { clear_color: [1, 0, 0] })
+      expect(app.clear_color).to eq([1.0, 0.0, 0.0, 1.0])
+
+      app.set_clear_color(Bevy::Color.rgba(0.0, 0.0, 1.0, 0.5))
+      expect(app.clear_color).to eq([0.0, 0.0, 1.0, 0.5])
+    end
+
+    it 'forwards to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_clear_color)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:build_context).set_clear_color(0.0, 0.5, 0.0, 1.0)
+
+      expect(render_app).to have_received(:set_clear_color).with(0.0, 0.5, 0.0, 1.0)
+    end
+
+    it 'rejects colors with the wrong number of components' do
+      expect { described_class.new.set_clear_color(0.1, 0.2) }.to raise_error(ArgumentError)
+    end
+
+    it 'leaves the config without a color by default' do
+      expect(described_class.new.send(:render_app_config)).not_to have_key(:clear_color)
+    end
+  end
+
+  describe 'window title and size' do
+    let(:render_app) { double('render_app') }
+
+    it 'sets the launch title and size before the render app starts' do
+      app = described_class.new(render: true, window: { title: 'Menu' })
+      expect(app.window_title).to eq('Menu')
+
+      app.set_window_title('Level 1').set_window_size(1280, 720)
+
+      expect(app.window_title).to eq('Level 1')
+      expect(app.window_size).to eq([1280.0, 720.0])
+      expect(app.send(:render_app_config)).to include(title: 'Level 1', width: 1280.0, height: 720.0)
+    end
+
+    it 'defaults to the native launch size and title' do
+      app = described_class.new
+
+      expect(app.window_title).to eq('Bevy Ruby')
+      expect(app.window_size).to eq([800.0, 600.0])
+    end
+
+    it 'forwards to a running render app and reads its live size' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_window_title)
+      allow(render_app).to receive(:set_window_size)
+      allow(render_app).to receive(:window_size).and_return([1024.0, 768.0])
+      app.instance_variable_set(:@render_app, render_app)
+      context = app.send(:build_context)
+
+      context.set_window_title('Score: 10')
+      context.set_window_size(1024, 768)
+
+      expect(render_app).to have_received(:set_window_title).with('Score: 10')
+      expect(render_app).to have_received(:set_window_size).with(1024.0, 768.0)
+      expect(context.window_size).to eq([1024.0, 768.0])
+    end
+
+    it 'rejects non-positive sizes' do
+      expect { described_class.new.set_window_size(0, 600) }.to raise_error(ArgumentError)
+    end
+  end
+
+  describe 'cursor control' do
+    let(:render_app) { double('render_app') }
+
+    it 'starts visible and free' do
+      app = described_class.new
+
+      expect(app.cursor_visible?).to be true
+      expect(app.cursor_grab).to eq(:none)
+    end
+
+    it 'forwards visibility and grab to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_cursor_visible)
+      allow(render_app).to receive(:set_cursor_grab)
+      app.instance_variable_set(:@render_app, render_app)
+      context = app.send(:build_context)
+
+      context.set_cursor_visible(false)
+      context.set_cursor_grab('locked')
+
+      expect(render_app).to have_received(:set_cursor_visible).with(false)
+      expect(render_app).to have_received(:set_cursor_grab).with(:locked)
+      expect(context.cursor_visible?).to be false
+      expect(context.cursor_grab).to eq(:locked)
+    end
+
+    it 'applies settings made before the render app starts' do
+      app = described_class.new(render: true)
+      app.set_cursor_visible(false).set_cursor_grab(:confined)
+      allow(render_app).to receive(:set_cursor_visible)
+      allow(render_app).to receive(:set_cursor_grab)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_cursor)
+
+      expect(render_app).to have_received(:set_cursor_visible).with(false)
+      expect(render_app).to have_received(:set_cursor_grab).with(:confined)
+    end
+
+    it 'rejects unknown grab modes' do
+      expect { described_class.new.set_cursor_grab(:captured) }.to raise_error(ArgumentError, /captured/)
+    end
+  end
+
+  describe 'detailed stats' do
+    let(:render_app) { double('render_app') }
+
+    it 'is off by default and reports empty stats without a render app' do
+      app = described_class.new
+
+      expect(app.detailed_stats?).to be false
+      expect(app.stats).to eq(systems: {}, systems_max: {}, sleeping_count: 0)
+    end
+
+    it 'enables timing when the render app starts and reads its stats' do
+      app = described_class.new(render: true)
+      app.set_detailed_stats(true)
+      allow(render_app).to receive(:set_detailed_stats)
+      allow(render_app).to receive(:stats).and_return(systems: { sprite_sync: 42 }, systems_max: { sprite_sync: 90 })
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_detailed_stats)
+
+      expect(render_app).to have_received(:set_detailed_stats).with(true)
+      expect(app.stats[:systems]).to eq(sprite_sync: 42)
+    end
+  end
+
+  describe 'parallax' do
+    let(:render_app) { double('render_app') }
+
+    it 'forwards group and layer factors to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_parallax_factor)
+      allow(render_app).to receive(:clear_parallax)
+      app.instance_variable_set(:@render_app, render_app)
+      context = app.send(:build_context)
+
+      context.set_parallax_factor(:hills, 0.5, 1)
+      context.set_parallax_factor(-3, 0, 0)
+      context.clear_parallax(:hills)
+
+      expect(render_app).to have_received(:set_parallax_factor).with('hills', 0.5, 1.0)
+      expect(render_app).to have_received(:set_parallax_factor).with(-3, 0.0, 0.0)
+      expect(render_app).to have_received(:clear_parallax).with('hills')
+      expect(app.parallax_factor('hills')).to be_nil
+      expect(app.parallax_factor(-3)).to eq([0.0, 0.0])
+    end
+
+    it 'applies factors set before the render app starts' do
+      app = described_class.new(render: true)
+      app.set_parallax_factor('sky', 0.0, 0.0)
+      allow(render_app).to receive(:set_parallax_factor)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_parallax)
+
+      expect(render_app).to have_received(:set_parallax_factor).with('sky', 0.0, 0.0)
+    end
+  end
+
+  describe 'pixel perfect' do
+    let(:render_app) { double('render_app') }
+
+    it 'passes the global setting through the render app config' do
+      app = described_class.new(render: true, window: { pixel_perfect: true })
+      expect(app).to be_pixel_perfect
+      expect(app.send(:render_app_config)[:pixel_perfect]).to be(true)
+
+      app.set_pixel_perfect(false)
+      expect(app.send(:render_app_config)).not_to have_key(:pixel_perfect)
+    end
+
+    it 'forwards global and per-entity settings to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_pixel_perfect)
+      allow(render_app).to receive(:set_entity_pixel_perfect)
+      app.instance_variable_set(:@render_app, render_app)
+      context = app.send(:build_context)
+
+      context.set_pixel_perfect(true)
+      context.set_entity_pixel_perfect(double('entity', id: 4), false)
+
+      expect(render_app).to have_received(:set_pixel_perfect).with(true)
+      expect(render_app).to have_received(:set_entity_pixel_perfect).with(4, false)
+      expect(app.entity_pixel_perfect?(4)).to be(false)
+      expect(app.entity_pixel_perfect?(5)).to be(true)
+    end
+
+    it 'applies entity settings made before the render app starts' do
+      app = described_class.new(render: true)
+      app.set_entity_pixel_perfect(9, true)
+      allow(render_app).to receive(:set_entity_pixel_perfect)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_pixel_perfect)
+
+      expect(render_app).to have_received(:set_entity_pixel_perfect).with(9, true)
+    end
+  end
+
+  describe 'hit regions' do
+    let(:render_app) { double('render_app') }
+
+    it 'forwards regions to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:register_hit_region)
+      allow(render_app).to receive(:update_hit_region)
+      allow(render_app).to receive(:remove_hit_region)
+      app.instance_variable_set(:@render_app, render_app)
+      context = app.send(:build_context)
+
+      context.register_hit_region(1, 10, 20, 30, 40, layer: 2)
+      context.update_hit_region(1, 15, 20, 30, 40, screen_space: true)
+      context.register_hit_region(2, 0, 0, 5, 5)
+      context.remove_hit_region(2)
+
+      expect(render_app).to have_received(:register_hit_region)
+        .with(1, 10.0, 20.0, 30.0, 40.0, { layer: 2, screen_space: false })
+      expect(render_app).to have_received(:update_hit_region)
+        .with(1, 15.0, 20.0, 30.0, 40.0, { layer: 2, screen_space: true })
+      expect(render_app).to have_received(:remove_hit_region).with(2)
+      expect(app.hit_region(1)).to eq(x: 15.0, y: 20.0, width: 30.0, height: 40.0, layer: 2, screen_space: true)
+      expect(app.hit_region(2)).to be_nil
+    end
+
+    it 'registers regions added before the render app starts' do
+      app = described_class.new(render: true)
+      app.register_hit_region(7, 0, 0, 100, 50, screen_space: true)
+      allow(render_app).to receive(:register_hit_region)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_hit_regions)
+
+      expect(render_app).to have_received(:register_hit_region)
+        .with(7, 0.0, 0.0, 100.0, 50.0, { layer: 0, screen_space: true })
+    end
+
+    it 'separates region events from entity picking' do
+      allow(render_app).to receive(:pressed_keys).and_return([])
+      allow(render_app).to receive(:mouse_position).and_return([0.0, 0.0])
+      allow(render_app).to receive(:mouse_button_pressed?).and_return(false)
+      allow(render_app).to receive(:gamepads_state).and_return([])
+      allow(render_app).to receive(:drain_picking_events).and_return(
+        [
+          { kind: 'click', target_id: 3, pointer_id: 'Mouse', button: 'Primary', position: [1.0, 1.0],
+            hit_region: true },
+          { kind: 'over', target_id: 4, pointer_id: 'Mouse', position: [1.0, 1.0] }
+        ]
+      )
+      app = described_class.new(render: true)
+      app.instance_variable_set(:@render_app, render_app)
+      app.send(:sync_input_from_bevy)
+
+      context = app.send(:build_context)
+      expect(context.hit_region_events.map(&:target_id)).to eq([3])
+      expect(context.hit_region_picked?(3, kind: :click)).to be true
+      expect(context.picked?(3)).to be false
+      expect(context.picked?(4)).to be true
+    end
+  end
+
+  describe 'dragging' do
+    let(:render_app) { double('render_app') }
+
+    it 'forwards drag settings to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:enable_drag)
+      allow(render_app).to receive(:disable_drag)
+      app.instance_variable_set(:@render_app, render_app)
+      context = app.send(:build_context)
+
+      context.enable_drag(3, constrain_to: [0, 0, 200, 100], snap_back: true)
+      context.enable_drag(4, drag_layer: 7)
+      context.disable_drag(4)
+
+      expect(render_app).to have_received(:enable_drag)
+        .with(3, { constrain_to: [0.0, 0.0, 200.0, 100.0], snap_back: true, drag_layer: 100 })
+      expect(render_app).to have_received(:enable_drag)
+        .with(4, { constrain_to: nil, snap_back: false, drag_layer: 7 })
+      expect(render_app).to have_received(:disable_drag).with(4)
+      expect(app.drag_enabled?(3)).to be(true)
+      expect(app.drag_enabled?(4)).to be(false)
+    end
+
+    it 'enables sprites made draggable before the render app starts' do
+      app = described_class.new(render: true)
+      app.enable_drag(5, snap_back: true)
+      allow(render_app).to receive(:enable_drag)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_draggables)
+
+      expect(render_app).to have_received(:enable_drag)
+        .with(5, { constrain_to: nil, snap_back: true, drag_layer: 100 })
+    end
+
+    it 'moves the Transform of a sprite left where it was dropped' do
+      app = described_class.new(render: true)
+      kept = app.world.spawn_entity(Bevy::Sprite.new, Bevy::Transform.new(translation: Bevy::Vec3.new(1.0, 2.0, 3.0)))
+      snapped = app.world.spawn_entity(Bevy::Sprite.new, Bevy::Transform.identity)
+      allow(render_app).to receive(:drain_drag_events).and_return(
+        [
+          { id: kept.id, position: [40.0, 50.0], dropped_at: [40.0, 50.0], origin: [1.0, 2.0], drop_target: 9,
+            hit_region: true, snapped_back: false },
+          { id: snapped.id, position: [0.0, 0.0], dropped_at: [30.0, 30.0], origin: [0.0, 0.0], drop_target: nil,
+            hit_region: false, snapped_back: true }
+        ]
+      )
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_drag_events_from_bevy)
+
+      kept_translation = app.world.get_component(kept, Bevy::Transform).translation
+      expect([kept_translation.x, kept_translation.y, kept_translation.z]).to eq([40.0, 50.0, 3.0])
+      snapped_translation = app.world.get_component(snapped, Bevy::Transform).translation
+      expect([snapped_translation.x, snapped_translation.y]).to eq([0.0, 0.0])
+      expect(app.drag_events.map { |event| event[:drop_target] }).to eq([9, nil])
+    end
+  end
+
+  describe 'warnings' do
+    let(:render_app) { double('render_app') }
+    let(:warning) { { category: :audio, message: 'no backend', count: 1, first_frame: 3 } }
+
+    it 'collects warnings drained from the render app each frame' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:drain_warnings).and_return([warning])
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_warnings_from_bevy)
+
+      expect(app.drain_warnings).to eq([warning])
+      expect(app.drain_warnings).to be_empty
+    end
+
+    it 'keeps only the newest warnings when they are not drained' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:drain_warnings).and_return([warning] * 200)
+      app.instance_variable_set(:@render_app, render_app)
+
+      2.times { app.send(:sync_warnings_from_bevy) }
+
+      expect(app.drain_warnings.length).to eq(described_class::WARNING_BUFFER)
+    end
+
+    it 'lets a raise policy surface from the frame' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:drain_warnings).and_raise(Bevy::WarningError, 'audio: no backend')
+      app.instance_variable_set(:@render_app, render_app)
+
+      expect { app.send(:sync_warnings_from_bevy) }.to raise_error(Bevy::WarningError)
+    end
+
+    it 'forwards policies set before and after the render app starts' do
+      app = described_class.new(render: true)
+      app.set_warning_policy(:audio, :silent)
+      allow(render_app).to receive(:set_warning_policy)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_warning_policies)
+      app.set_warning_policy('determinism', 'raise')
+
+      expect(render_app).to have_received(:set_warning_policy).with(:audio, :silent)
+      expect(render_app).to have_received(:set_warning_policy).with(:determinism, :raise)
+      expect(app.warning_policy(:determinism)).to eq(:raise)
+      expect(app.warning_policy(:window)).to eq(:collect)
+    end
+
+    it 'rejects unknown policies' do
+      expect { described_class.new.set_warning_policy(:audio, :loud) }.to raise_error(ArgumentError)
+    end
+  end
+
+  describe 'rewind' do
+    let(:render_app) { double('render_app') }
+
+    it 'requires rewind to be enabled first' do
+      expect { described_class.new.rewind_to(1.0) }.to raise_error(ArgumentError)
+    end
+
+    it 'validates the window' do
+      expect { described_class.new.enable_rewind(seconds: 0) }.to raise_error(ArgumentError)
+    end
+
+    it 'applies stored options when the render app starts' do
+      app = described_class.new(render: true)
+      app.enable_rewind(seconds: 5, snapshot_hz: 20)
+      allow(render_app).to receive(:enable_rewind)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_rewind)
+
+      expect(render_app).to have_received(:enable_rewind).with(5.0, 20.0)
+    end
+
+    it 'forwards playback to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive_messages(enable_rewind: nil, rewind_to: nil, resume_from_rewind: nil)
+      app.instance_variable_set(:@render_app, render_app)
+      context = app.send(:build_context)
+
+      app.enable_rewind(seconds: 3)
+      context.rewind_to(1.5)
+      expect(context.rewinding?).to be true
+
+      context.resume_from_rewind
+      expect(app.rewinding?).to be false
+      expect(render_app).to have_received(:enable_rewind).with(3.0, 10.0)
+      expect(render_app).to have_received(:rewind_to).with(1.5)
+      expect(render_app).to have_received(:resume_from_rewind).once
+    end
+  end
+
+  describe 'determinism audit' do
+    let(:render_app) { double('render_app') }
+    let(:file) { Tempfile.new(['checksums', '.txt']) }
+
+    after { file.unlink }
+
+    it 'saves the checksum history one hex value per line' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:checksum_history).and_return([1, 255])
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.save_checksums(file.path)
+
+      expect(File.read(file.path)).to eq("0000000000000001\n00000000000000ff\n")
+    end
+
+    it 'applies a recorded stream when the render app starts' do
+      app = described_class.new(render: true)
+      file.write("0000000000000001\n")
+      file.close
+      app.compare_checksums(file.path)
+      allow(render_app).to receive(:compare_recorded_checksums).and_return(nil)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_recorded_checksums)
+
+      expect(render_app).to have_received(:compare_recorded_checksums).with([1])
+    end
+
+    it 'warns about and returns the first divergence' do
+      app = described_class.new(render: true)
+      divergence = { frame: 2, expected: 5, actual: 6 }
+      allow(render_app).to receive(:compare_recorded_checksums).and_return(divergence)
+      app.instance_variable_set(:@render_app, render_app)
+      file.write(Bevy::DeterminismAudit.dump([5, 5, 5]))
+      file.close
+
+      expect { expect(app.compare_checksums(file.path)).to eq(divergence) }
+        .to output(/frame 2 diverged/).to_stderr
+    end
+
+    it 'has no checksum without a render app' do
+      expect(described_class.new.frame_checksum).to be_nil
+    end
+  end
+
+  describe 'operation journal' do
+    let(:render_app) { double('render_app', start_journal: nil, stop_journal: nil, replay_journal: nil) }
+
+    it 'starts a journal requested before the render app exists' do
+      app = described_class.new(render: true)
+      app.start_journal('tmp/journal.jsonl', max_mb: 5)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_journal)
+
+      expect(app.journaling?).to be true
+      expect(render_app).to have_received(:start_journal).with('tmp/journal.jsonl', max_mb: 5)
+    end
+
+    it 'forwards start and stop to a running render app' do
+      app = described_class.new(render: true)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.start_journal('journal.jsonl')
+      app.stop_journal
+
+      expect(app.journaling?).to be false
+      expect(render_app).to have_received(:start_journal).with('journal.jsonl', max_mb: 50)
+      expect(render_app).to have_received(:stop_journal)
+    end
+
+    it 'replays a journal once the render app starts' do
+      app = described_class.new(render: true)
+      app.replay_journal('journal.jsonl')
+      app.instance_variable_set(:@render_app, render_app)
+      allow(render_app).to receive(:replaying_journal?).and_return(true)
+
+      app.send(:apply_journal_replay)
+
+      expect(render_app).to have_received(:replay_journal).with('journal.jsonl')
+      expect(app.replaying_journal?).to be true
+    end
+  end
+
+  describe '#set_remove_defer' do
+    let(:render_app) { double('render_app') }
+
+    it 'stores the deferral until the render app starts' do
+      app = described_class.new(render: true)
+
+      app.set_remove_defer(2)
+
+      expect(app.remove_defer).to eq(2)
+    end
+
+    it 'forwards the deferral to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_remove_defer)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.set_remove_defer(1)
+
+      expect(render_app).to have_received(:set_remove_defer).with(1)
+    end
+
+    it 'rejects negative frame counts' do
+      expect { described_class.new.set_remove_defer(-1) }.to raise_error(ArgumentError)
+    end
+  end
+
+  describe '#floating_text' do
+    let(:render_app) { double('render_app') }
+
+    it 'does nothing without a render app' do
+      expect(described_class.new.floating_text(0, 0, '12')).to be_nil
+    end
+
+    it 'forwards the text with defaults and returns nothing' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:floating_text)
+      app.instance_variable_set(:@render_app, render_app)
+
+      expect(app.floating_text(10, 20, 99)).to be_nil
+      expect(render_app).to have_received(:floating_text).with(
+        10.0, 20.0, '99',
+        { color_r: 1.0, color_g: 1.0, color_b: 1.0, color_a: 1.0, rise: 40.0, duration: 0.8, font_size: 18.0 }
+      )
+    end
+
+    it 'accepts hex strings and component arrays as the color' do
+      app = described_class.new(render: true)
+      sent = []
+      allow(render_app).to receive(:floating_text) { |*args| sent << args.last }
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.floating_text(0, 0, 'crit', color: '#ff0000', rise: 60, duration: 1.2, font_size: 24)
+      app.floating_text(0, 0, 'heal', color: [0.0, 1.0, 0.0])
+
+      expect(sent.first).to include(color_r: 1.0, color_g: 0.0, color_b: 0.0, rise: 60.0, duration: 1.2, font_size: 24.0)
+      expect(sent.last).to include(color_r: 0.0, color_g: 1.0, color_b: 0.0, color_a: 1.0)
+      expect { app.floating_text(0, 0, 'bad', color: [1.0]) }.to raise_error(ArgumentError)
+    end
+
+    it 'is available from a system context' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:floating_text)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:build_context).floating_text(1, 2, '3', color: [1.0, 1.0, 0.0])
+
+      expect(render_app).to have_received(:floating_text)
+    end
+  end
+
+  describe '#set_floating_text_cap' do
+    let(:render_app) { double('render_app') }
+
+    it 'stores the cap until the render app starts' do
+      app = described_class.new(render: true)
+
+      expect(app.floating_text_cap).to eq(64)
+      app.set_floating_text_cap(10)
+      expect(app.floating_text_cap).to eq(10)
+    end
+
+    it 'forwards the cap to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_floating_text_cap)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.set_floating_text_cap(8)
+
+      expect(render_app).to have_received(:set_floating_text_cap).with(8)
+    end
+
+    it 'rejects negative caps' do
+      expect { described_class.new.set_floating_text_cap(-1) }.to raise_error(ArgumentError)
+    end
+  end
+
+  describe '#set_group_update_divisor' do
+    let(:render_app) { double('render_app') }
+
+    it 'stores divisors until the render app starts' do
+      app = described_class.new(render: true)
+
+      expect(app.group_update_divisor(:enemies)).to eq(1)
+      app.set_group_update_divisor(:enemies, 3)
+      expect(app.group_update_divisor('enemies')).to eq(3)
+      app.set_group_update_divisor(:enemies, 1)
+      expect(app.group_update_divisor(:enemies)).to eq(1)
+    end
+
+    it 'forwards the divisor to a running render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_group_update_divisor)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.set_group_update_divisor(:enemies, 4)
+
+      expect(render_app).to have_received(:set_group_update_divisor).with('enemies', 4)
+    end
+
+    it 'rejects divisors below 1' do
+      expect { described_class.new.set_group_update_divisor(:enemies, 0) }.to raise_error(ArgumentError)
+    end
+  end
+
+  describe '#group_due?' do
+    let(:render_app) { double('render_app') }
+
+    it 'asks the render app whether the group is due this frame' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:group_due?).with('enemies').and_return(false)
+      app.instance_variable_set(:@render_app, render_app)
+      context = app.send(:build_context)
+
+      expect(context.group_due?(:enemies)).to be(false)
+    end
+
+    it 'is always due without a render app' do
+      app = described_class.new
+      context = app.send(:build_context)
+
+      expect(context.group_due?(:enemies)).to be(true)
+    end
+  end
+
+  describe '#set_sleep_thresholds' do
+    let(:render_app) { double('render_app') }
+
+    it 'keeps the defaults until thresholds are set' do
+      app = described_class.new(render: true)
+
+      expect(app.sleep_thresholds).to eq(sleep: 1.0e-4, wake: 1.0e-4, frames: 30)
+      app.set_sleep_thresholds(sleep: 0.01, wake: 0.05, frames: 10)
+      expect(app.sleep_thresholds).to eq(sleep: 0.01, wake: 0.05, frames: 10)
+    end
+
+    it 'raises the wake threshold to the sleep threshold' do
+      app = described_class.new.set_sleep_thresholds(sleep: 0.5, wake: 0.1)
+
+      expect(app.sleep_thresholds[:wake]).to eq(0.5)
+    end
+
+    it 'forwards thresholds set before and after the render app starts' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:set_sleep_thresholds)
+      app.set_sleep_thresholds(frames: 0)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_sleep_thresholds)
+      app.set_sleep_thresholds(sleep: 0.002, frames: 5)
+
+      expect(render_app).to have_received(:set_sleep_thresholds).with(1.0e-4, 1.0e-4, 0).ordered
+      expect(render_app).to have_received(:set_sleep_thresholds).with(0.002, 0.002, 5).ordered
+    end
+
+    it 'rejects negative thresholds' do
+      expect { described_class.new.set_sleep_thresholds(sleep: -1.0) }.to raise_error(ArgumentError)
+      expect { described_class.new.set_sleep_thresholds(frames: -1) }.to raise_error(ArgumentError)
+    end
+
+    it 'wakes everything through the render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:wake_all)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.wake_all
+
+      expect(render_app).to have_received(:wake_all)
+    end
+  end
+
+  describe '#apply_impulse' do
+    let(:render_app) { double('render_app') }
+
+    it 'forwards the impulse for an entity' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:apply_impulse)
+      app.instance_variable_set(:@render_app, render_app)
+      context = app.send(:build_context)
+
+      context.apply_impulse(double('entity', id: 7), 1, 0, 40)
+
+      expect(render_app).to have_received(:apply_impulse).with(7, 1.0, 0.0, 40.0)
+    end
+
+    it 'does nothing without a render app' do
+      context = described_class.new.send(:build_context)
+      expect { context.apply_impulse(7, 1, 0, 40) }.not_to raise_error
+    end
+  end
+
+  describe 'draw order' do
+    let(:render_app) { double('render_app') }
+
+    it 'forwards reordering within a layer for an entity' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive_messages(bring_to_front: nil, send_to_back: nil, set_draw_order: nil,
+                                            draw_order: 2)
+      app.instance_variable_set(:@render_app, render_app)
+      context = app.send(:build_context)
+
+      context.bring_to_front(double('entity', id: 7))
+      context.send_to_back(8)
+      context.set_draw_order(9, 1)
+
+      expect(render_app).to have_received(:bring_to_front).with(7)
+      expect(render_app).to have_received(:send_to_back).with(8)
+      expect(render_app).to have_received(:set_draw_order).with(9, 1)
+      expect(context.draw_order(9)).to eq(2)
+    end
+
+    it 'rejects a negative index' do
+      context = described_class.new.send(:build_context)
+      expect { context.set_draw_order(7, -1) }.to raise_error(ArgumentError, /non-negative/)
+    end
+
+    it 'does nothing without a render app' do
+      context = described_class.new.send(:build_context)
+      expect { context.bring_to_front(7) }.not_to raise_error
+      expect(context.draw_order(7)).to be_nil
+    end
+  end
+
+  describe 'world binding' do
+    let(:render_app) { double('render_app') }
+    let(:bound) { Bevy::World.new }
+    let(:app) { described_class.new(render: true) }
+    let(:synced) { [] }
+    let(:removed) { [] }
+
+    def renderable(fields = {})
+      Bevy::Component.from_hash('Renderable', { color: [1.0, 0.0, 0.0], size: [16.0, 8.0] }.merge(fields))
+    end
+
+    def position(x, y)
+      Bevy::Component.from_hash('Position', { x: x, y: y })
+    end
+
+    def bound_id(entity)
+      entity.id | (1 << 62)
+    end
+
+    def frame
+      synced.clear
+      removed.clear
+      app.send(:sync_sprites_to_bevy)
+    end
+
+    before do
+      allow(render_app).to receive(:sync_sprite) { |id, sprite, transform| synced << [id, sprite, transform] }
+      allow(render_app).to receive(:remove_sprite) { |id| removed << id }
+      app.instance_variable_set(:@render_app, render_app)
+      app.bind_world(bound).auto_sync(component_type: 'Renderable', transform_from: 'Position')
+    end
+
+    it 'syncs entities having both components with derived ids' do
+      player = bound.spawn_with([renderable, position(10.0, 20.0)])
+      bound.spawn_with([position(0.0, 0.0)])
+
+      frame
+
+      expect(synced.length).to eq(1)
+      id, sprite, transform = synced.first
+      expect(id).to eq(bound_id(player))
+      expect(sprite).to include(color_r: 1.0, color_g: 0.0, color_b: 0.0, color_a: 1.0,
+                                custom_size_x: 16.0, custom_size_y: 8.0)
+      expect(transform).to include(x: 10.0, y: 20.0, z: 0.0, rotation: 0.0)
+    end
+
+    it 'follows updates and despawns across frames' do
+      player = bound.spawn_with([renderable, position(0.0, 0.0)])
+      enemy = bound.spawn_with([renderable(color: '#0000ff'), position(5.0, 5.0)])
+      frame
+
+      bound.insert(player, position(4.0, 2.0))
+      frame
+      expect(synced.find { |id, _, _| id == bound_id(player) }[2]).to include(x: 4.0, y: 2.0)
+      expect(synced.find { |id, _, _| id == bound_id(enemy) }[1]).to include(color_b: 1.0)
+      expect(removed).to be_empty
+
+      bound.despawn(player)
+      frame
+      expect(synced.map(&:first)).to eq([bound_id(enemy)])
+      expect(removed).to eq([bound_id(player)])
+
+      bound.despawn(enemy)
+      frame
+      expect(synced).to be_empty
+      expect(removed).to eq([bound_id(enemy)])
+    end
+
+    it 'reads renamed fields' do
+      app.auto_sync(component_type: 'Renderable', transform_from: 'Position',
+                    fields: { color: :tint, x: :left })
+      bound.spawn_with([renderable(tint: [0.0, 1.0, 0.0, 0.5]),
+                        Bevy::Component.from_hash('Position', { left: 7.0, y: 1.0 })])
+
+      frame
+
+      _, sprite, transform = synced.first
+      expect(sprite).to include(color_g: 1.0, color_a: 0.5)
+      expect(transform).to include(x: 7.0, y: 1.0)
+    end
+
+    it 'removes synced sprites when another world is bound' do
+      entity = bound.spawn_with([renderable, position(0.0, 0.0)])
+      frame
+
+      app.bind_world(Bevy::World.new)
+
+      expect(removed).to eq([bound_id(entity)])
+    end
+
+    it 'rejects unknown field names' do
+      expect { app.auto_sync(fields: { texture: :image }) }.to raise_error(ArgumentError)
+    end
+  end
+
+  describe 'render window synchronization' do
+    let(:render_app) { double('render_app') }
+
+    it 'replaces file drops with the entries drained this frame' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:drain_file_drops).and_return(
+        [{ kind: 'hovered', path: '/tmp/a.png' }, { kind: 'dropped', path: '/tmp/a.png' }],
+        []
+      )
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_file_drops_from_bevy)
+      expect(app.file_drops.map(&:kind)).to eq(%w[hovered dropped])
+      expect(app.send(:build_context).dropped_file_paths).to eq(['/tmp/a.png'])
+
+      app.send(:sync_file_drops_from_bevy)
+      expect(app.file_drops).to be_empty
+    end
+
+    it 'replaces gamepad connection events with the ones drained this frame' do
+      app = described_class.new(render: true)
+      events = [
+        { id: 3, name: 'Pad', kind: :disconnected },
+        { id: 3, name: 'Pad', kind: :connected }
+      ]
+      allow(render_app).to receive(:drain_gamepad_connection_events).and_return(events, [])
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_gamepad_connections_from_bevy)
+      expect(app.gamepad_connection_events).to eq(events)
+      expect(app.send(:build_context).gamepad_connection_events.map { |event| event[:kind] })
+        .to eq(%i[disconnected connected])
+
+      app.send(:sync_gamepad_connections_from_bevy)
+      expect(app.gamepad_connection_events).to be_empty
+    end
+
+    it 'replaces touches with the events reported this frame' do
+      app = described_class.new(render: true)
+      touch = { id: 7, position: [120.0, 48.0], phase: 'started' }
+      allow(render_app).to receive(:touches).and_return([touch], [])
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_touches_from_bevy)
+      expect(app.touches).to eq([touch])
+      expect(app.send(:build_context).touches).to eq([touch])
+      expect(app.mouse.pressed?('LEFT')).to be(false)
+
+      app.send(:sync_touches_from_bevy)
+      expect(app.touches).to be_empty
+    end
+
+    it 'polls the asset registry for changes once a frame' do
+      app = described_class.new
+      registry = app.asset_registry
+      allow(registry).to receive(:check_for_changes)
+        .and_return([{ id: 0, path: 'level.json', change_type: 'modified' }], [])
+
+      app.send(:poll_asset_changes)
+      expect(app.send(:build_context).asset_changes).to eq([{ id: 0, path: 'level.json', change_type: 'modified' }])
+
+      app.send(:poll_asset_changes)
+      expect(app.asset_changes).to be_empty
+    end
+
+    it 'reports no asset changes before the registry is used' do
+      app = described_class.new
+      app.send(:poll_asset_changes)
+
+      expect(app.asset_changes).to eq([])
+    end
+
+    it 'answers touch queries from the touches reported this frame' do
+      app = described_class.new(render: true)
+      touches = [
+        { id: 1, phase: 'moved', position: [10.0, 20.0], previous_position: [8.0, 20.0], force: nil },
+        { id: 2, phase: 'started', position: [50.0, 60.0], previous_position: [50.0, 60.0], force: 0.5 }
+      ]
+      allow(render_app).to receive(:touches).and_return(touches)
+      app.instance_variable_set(:@render_app, render_app)
+      app.send(:sync_touches_from_bevy)
+      context = app.send(:build_context)
+
+      expect(context.touch_just_pressed?).to be(true)
+      expect(context.touch_just_pressed?(2)).to be(true)
+      expect(context.touch_just_pressed?(1)).to be(false)
+      expect(context.touch_position(1)).to eq([10.0, 20.0])
+      expect(context.touch_position(3)).to be_nil
+    end
+
+    it 'reads typed text only while text input is enabled' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:typed_text).and_return("hi\b!\n")
+      allow(render_app).to receive(:text_input_enabled=)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_typed_text_from_bevy)
+      expect(app.typed_text).to eq('')
+
+      context = app.send(:build_context)
+      context.set_text_input_enabled(true)
+      app.send(:sync_typed_text_from_bevy)
+      expect(context.typed_text).to eq("hi\b!\n")
+      expect(context.text_input_enabled?).to be(true)
+
+      context.set_text_input_enabled(false)
+      expect(app.typed_text).to eq('')
+      expect(render_app).to have_received(:text_input_enabled=).with(true).ordered
+      expect(render_app).to have_received(:text_input_enabled=).with(false).ordered
+    end
+
+    it 'enables text input set before the render app starts' do
+      app = described_class.new(render: true).set_text_input_enabled(true)
+      allow(render_app).to receive(:text_input_enabled=)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:apply_text_input_enabled)
+
+      expect(render_app).to have_received(:text_input_enabled=).with(true)
+    end
+
+    it 'forwards drained Bevy window events as Ruby events' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:drain_window_events).and_return(
+        [
+          { kind: 'close_requested', window_id: 3 },
+          { kind: 'file_dropped', window_id: 3, path: '/tmp/level.json' }
+        ]
+      )
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_window_events_from_bevy)
+
+      expect(app.events.reader(Bevy::WindowCloseRequested).read.map(&:window_id)).to eq([3])
+      drop = app.events.reader(Bevy::FileDragAndDrop).read.first
+      expect(drop).to be_dropped
+      expect(drop.path).to eq('/tmp/level.json')
+    end
+
+    it 'tracks the safe area and emits SafeAreaChanged only on change' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:window_metrics).and_return(
+        { width: 800.0, height: 600.0, insets: [32.0, 0.0, 0.0, 0.0] }
+      )
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_window_from_bevy)
+      app.send(:sync_window_from_bevy)
+
+      expect(app.safe_area.usable_size.y).to eq(568.0)
+      events = app.events.reader(Bevy::SafeAreaChanged).read
+      expect(events.length).to eq(1)
+      expect(events.first.previous).to be_nil
+    end
+  end
+
+  describe 'render mouse synchronization' do
+    let(:render_app) { double('render_app', pressed_keys: [], mouse_button_pressed?: false, gamepads_state: []) }
+    let(:app) { described_class.new(render: true) }
+
+    def frame(position, delta = nil)
+      allow(render_app).to receive(:mouse_position).and_return(position)
+      allow(render_app).to receive(:mouse_delta).and_return(delta) if delta
+      app.send(:sync_input_from_bevy)
+      app.mouse.delta.to_a
+    end
+
+    before { app.instance_variable_set(:@render_app, render_app) }
+
+    it 'reports no movement on the first frame' do
+      expect(frame([120.0, 80.0])).to eq([0.0, 0.0])
+      expect(app.mouse.position.to_a).to eq([120.0, 80.0])
+    end
+
+    it 'reports no movement while the cursor is stationary' do
+      frame([10.0, 10.0])
+
+      expect(frame([10.0, 10.0])).to eq([0.0, 0.0])
+    end
+
+    it 'tracks the cursor across frames without raw motion' do
+      frame([10.0, 10.0])
+
+      expect(frame([14.0, 7.0])).to eq([4.0, -3.0])
+      expect(frame([14.0, 7.0])).to eq([0.0, 0.0])
+    end
+
+    it 'uses raw motion so the delta keeps reporting outside the window' do
+      frame([200.0, 150.0], [0.0, 0.0])
+
+      expect(frame([200.0, 150.0], [25.0, -5.0])).to eq([25.0, -5.0])
+      expect(app.mouse.position.to_a).to eq([200.0, 150.0])
+    end
+
+    it 'exposes the delta on the system context' do
+      allow(render_app).to receive(:mouse_delta).and_return([1.5, 2.5])
+
+      expect(app.send(:build_context).mouse_delta.to_a).to eq([1.5, 2.5])
+    end
+  end
+
+  describe 'render gamepad synchronization' do
+    let(:render_app) { double('render_app') }
+
+    before do
+      allow(render_app).to receive(:pressed_keys).and_return([])
+      allow(render_app).to receive(:mouse_position).and_return([0.0, 0.0])
+      allow(render_app).to receive(:mouse_button_pressed?).and_return(false)
+    end
+
+    it 'copies the frame scroll into the mouse state' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive_messages(gamepads_state: [], scroll_delta: [0.0, 1.5])
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_input_from_bevy)
+
+      expect(app.mouse.scroll_delta.y).to eq(1.5)
+    end
+
+    it 'syncs gamepad button and axis state from render app' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:gamepads_state).and_return([
+                                                            {
+                                                              id: 1,
+                                                              name: 'Pad One',
+                                                              buttons_pressed: [Bevy::GamepadButton::SOUTH],
+                                                              axes: {
+                                                                Bevy::GamepadAxis::LEFT_STICK_X => 0.5,
+                                                                Bevy::GamepadAxis::LEFT_STICK_Y => -0.25
+                                                              }
+                                                            }
+                                                          ])
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_input_from_bevy)
+
+      gamepad = app.gamepads.get(1)
+      expect(gamepad).not_to be_nil
+      expect(gamepad.pressed?(Bevy::GamepadButton::SOUTH)).to be true
+      expect(gamepad.just_pressed?(Bevy::GamepadButton::SOUTH)).to be true
+      expect(gamepad.axis_raw(Bevy::GamepadAxis::LEFT_STICK_X)).to be_within(0.001).of(0.5)
+      expect(gamepad.axis_raw(Bevy::GamepadAxis::LEFT_STICK_Y)).to be_within(0.001).of(-0.25)
+    end
+
+    it 'syncs the gamepad battery level, leaving it nil when unreported' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:gamepads_state).and_return([
+                                                            { id: 1, name: 'Pad One', battery: 0.4 },
+                                                            { id: 2, name: 'Pad Two', battery: nil }
+                                                          ])
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_input_from_bevy)
+
+      expect(app.gamepads.get(1).battery).to be_within(0.001).of(0.4)
+      expect(app.gamepads.get(2).battery).to be_nil
+    end
+
+    it 'disconnects removed gamepads and tracks button release transitions' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:gamepads_state).and_return(
+        [
+          {
+            id: 1,
+            name: 'Pad One',
+            buttons_pressed: [Bevy::GamepadButton::SOUTH],
+            axes: {}
+          }
+        ],
+        [
+          {
+            id: 1,
+            name: 'Pad One',
+            buttons_pressed: [],
+            axes: {}
+          }
+        ],
+        []
+      )
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_input_from_bevy)
+      app.send(:sync_input_from_bevy)
+
+      gamepad = app.gamepads.get(1)
+      expect(gamepad.pressed?(Bevy::GamepadButton::SOUTH)).to be false
+      expect(gamepad.just_released?(Bevy::GamepadButton::SOUTH)).to be true
+
+      app.send(:sync_input_from_bevy)
+      expect(app.gamepads.get(1)).to be_nil
+    end
+  end
+
+  describe 'gamepad rumble forwarding' do
+    let(:render_app) { double('render_app') }
+
+    before do
+      allow(render_app).to receive(:queue_gamepad_rumble)
+    end
+
+    it 'forwards pending rumble requests to render app and clears them' do
+      app = described_class.new(render: true)
+      app.instance_variable_set(:@render_app, render_app)
+      app.gamepads.connect(2)
+      gamepad = app.gamepads.get(2)
+      gamepad.rumble(Bevy::RumbleRequest.new(strong: 0.8, weak: 0.3, duration: 0.5))
+
+      app.send(:sync_gamepad_rumble_to_bevy)
+
+      expect(render_app).to have_received(:queue_gamepad_rumble).with(2, 0.8, 0.3, 0.5)
+      expect(gamepad.pending_rumble).to be_nil
+    end
+
+    it 'does not call render app when there is no pending rumble' do
+      app = described_class.new(render: true)
+      app.instance_variable_set(:@render_app, render_app)
+      app.gamepads.connect(3)
+
+      app.send(:sync_gamepad_rumble_to_bevy)
+
+      expect(render_app).not_to have_received(:queue_gamepad_rumble)
+    end
+
+    it 'forwards queued rumble patterns after the pending request' do
+      allow(render_app).to receive(:rumble_pattern)
+      app = described_class.new(render: true)
+      app.instance_variable_set(:@render_app, render_app)
+      app.gamepads.connect(4)
+      gamepad = app.gamepads.get(4)
+      gamepad.rumble_pattern(:ramp_up)
+      gamepad.rumble_pattern([[0.5, 0.5, 0.2]])
+
+      app.send(:sync_gamepad_rumble_to_bevy)
+
+      expect(render_app).not_to have_received(:queue_gamepad_rumble)
+      expect(render_app).to have_received(:rumble_pattern).with(4, :ramp_up).ordered
+      expect(render_app).to have_received(:rumble_pattern).with(4, [[0.5, 0.5, 0.2]]).ordered
+      expect(gamepad.pending_rumble_patterns).to be_empty
+    end
+
+    it 'drops requests not forwarded yet when stopping all rumble' do
+      allow(render_app).to receive(:stop_all_rumble)
+      app = described_class.new(render: true)
+      app.instance_variable_set(:@render_app, render_app)
+      app.gamepads.connect(5)
+      app.gamepads.get(5).rumble_strong(1.0, 2.0)
+      app.gamepads.get(5).rumble_pattern(:heartbeat)
+
+      app.stop_all_rumble
+      app.send(:sync_gamepad_rumble_to_bevy)
+
+      expect(render_app).to have_received(:stop_all_rumble)
+      expect(render_app).not_to have_received(:queue_gamepad_rumble)
+    end
+
+    it 'asks the render app whether a gamepad is rumbling' do
+      allow(render_app).to receive(:rumble_active?).with(6).and_return(true)
+      app = described_class.new(render: true)
+      app.instance_variable_set(:@render_app, render_app)
+
+      expect(app.rumble_active?(6)).to be true
+      expect(described_class.new.rumble_active?(6)).to be false
+    end
+  end
+
+  describe 'render picking synchronization' do
+    let(:render_app) { double('render_app') }
+
+    before do
+      allow(render_app).to receive(:pressed_keys).and_return([])
+      allow(render_app).to receive(:mouse_position).and_return([0.0, 0.0])
+      allow(render_app).to receive(:mouse_button_pressed?).and_return(false)
+      allow(render_app).to receive(:gamepads_state).and_return([])
+    end
+
+    it 'imports picking events from render app into the event registry' do
+      allow(render_app).to receive(:drain_picking_events).and_return([
+                                                                      {
+                                                                        kind: 'over',
+                                                                        target_id: 77,
+                                                                        pointer_id: 'Mouse',
+                                                                        position: [10.5, 20.25],
+                                                                        camera_id: 5,
+                                                                        depth: 1.5,
+                                                                        hit_position: [1.0, 2.0, 3.0],
+                                                                        hit_normal: [0.0, 0.0, 1.0]
+                                                                      },
+                                                                      {
+                                                                        kind: 'click',
+                                                                        target_id: 77,
+                                                                        pointer_id: 'Mouse',
+                                                                        button: 'Primary',
+                                                                        position: [10.5, 20.25]
+                                                                      }
+                                                                    ])
+
+      app = described_class.new(render: true)
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:sync_input_from_bevy)
+
+      reader = app.events.reader(Bevy::PickingEvent)
+      events = reader.read
+      expect(events.map(&:kind)).to include('over', 'click')
+
+      over_event = events.find { |event| event.kind == 'over' }
+      expect(over_event.target_id).to eq(77)
+      expect(over_event.pointer_id).to eq('Mouse')
+      expect(over_event.position).to be_a(Bevy::Vec2)
+      expect(over_event.position.x).to be_within(0.001).of(10.5)
+      expect(over_event.position.y).to be_within(0.001).of(20.25)
+      expect(over_event.camera_id).to eq(5)
+      expect(over_event.depth).to be_within(0.001).of(1.5)
+      expect(over_event.hit_position).to be_a(Bevy::Vec3)
+      expect(over_event.hit_normal).to be_a(Bevy::Vec3)
+    end
+
+    it 'carries move and drag deltas on picking events' do
+      allow(render_app).to receive(:drain_picking_events).and_return(
+        [
+          { kind: 'move', target_id: 77, pointer_id: 'Mouse', position: [12.0, 20.0], delta: [2.0, 0.0] },
+          { kind: 'drag_start', target_id: 77, pointer_id: 'Mouse', button: 'Primary', position: [12.0, 20.0] },
+          {
+            kind: 'drag', target_id: 77, pointer_id: 'Mouse', button: 'Primary', position: [15.0, 24.0],
+            delta: [3.0, 4.0], distance: [3.0, 4.0]
+          },
+          { kind: 'drag_end', target_id: 77, pointer_id: 'Mouse', button: 'Primary', position: [15.0, 24.0],
+            distance: [3.0, 4.0] }
+        ]
+      )
+      app = described_class.new(render: true)
+      app.instance_variable_set(:@render_app, render_app)
+      app.send(:sync_picking_events_from_bevy)
+      context = app.send(:build_context)
+
+      move = context.picking_events('move').first
+      expect(move.delta.to_a).to eq([2.0, 0.0])
+      expect(move.distance).to be_nil
+      expect(move).not_to be_drag
+
+      drags = context.picking_events.select(&:drag?)
+      expect(drags.map(&:kind)).to eq(%w[drag_start drag drag_end])
+      expect(drags[0].delta).to be_nil
+      expect(drags[1].delta.to_a).to eq([3.0, 4.0])
+      expect(drags[2].distance.to_a).to eq([3.0, 4.0])
+      expect(context.picked?(77, kind: 'drag')).to be(true)
+    end
+
+    it 'routes the virtual cursor through a custom picking pointer' do
+      pointer_id = 'Custom(62767275-6279-4000-8000-000000000001)'
+      app = described_class.new(render: true)
+      app.enable_virtual_cursor(gamepad_slot: 1, speed: 400, curve: Bevy::StickCurve.new(exponent: 3.0))
+      expect(app.virtual_cursor_pointer_id).to be_nil
+
+      expect(render_app).to receive(:enable_virtual_cursor).with(
+        gamepad_slot: 1, speed: 400.0, button: 'South', dead_zone: 0.15, exponent: 3.0
+      ).and_return(pointer_id)
+      allow(render_app).to receive(:drain_picking_events).and_return(
+        [
+          { kind: 'click', target_id: 8, pointer_id: pointer_id, button: 'Primary', position: [4.0, 2.0] },
+          { kind: 'click', target_id: 9, pointer_id: 'Mouse', button: 'Primary', position: [1.0, 1.0] }
+        ]
+      )
+      app.instance_variable_set(:@render_app, render_app)
+      app.send(:apply_virtual_cursor)
+      app.send(:sync_input_from_bevy)
+
+      context = app.send(:build_context)
+      events = context.virtual_cursor_picking_events(:click)
+      expect(events.map(&:target_id)).to eq([8])
+      expect(events.first).to be_custom_pointer
+
+      expect(render_app).to receive(:disable_virtual_cursor)
+      app.disable_virtual_cursor
+      expect(app).not_to be_virtual_cursor_enabled
+      expect(context.virtual_cursor_picking_events).to be_empty
+    end
+
+    it 'starts clips on the render app and completes them when finished' do
+      app = described_class.new(render: true)
+      completed = []
+      clip = app.build_clip.track(3, :x, [[0.0, -300.0], [0.4, 0.0]], ease: :ease_out)
+      handle = app.play_clip(clip, on_complete: ->(h) { completed << h })
+      expect(app).to be_clip_playing(handle)
+
+      expect(render_app).to receive(:play_clip).with(handle, clip.to_native)
+      expect(render_app).to receive(:seek_clip).with(handle, 9.0)
+      app.seek_clip(handle, 9.0)
+      app.instance_variable_set(:@render_app, render_app)
+      app.send(:start_clip, handle)
+
+      allow(render_app).to receive(:drain_finished_clips).and_return([handle])
+      app.send(:sync_clips_from_bevy)
+      expect(completed).to eq([handle])
+      expect(app).not_to be_clip_playing(handle)
+    end
+
+    it 'stops clips without calling on_complete' do
+      app = described_class.new(render: true)
+      allow(render_app).to receive(:play_clip)
+      app.instance_variable_set(:@render_app, render_app)
+      called = false
+      handle = app.play_clip(app.build_clip.track(1, :alpha, [[0.0, 1.0]]), on_complete: -> { called = true })
+
+      expect(render_app).to receive(:stop_clip).with(handle)
+      app.stop_clip(handle)
+      expect(app).not_to be_clip_playing(handle)
+      expect(called).to be false
+    end
+
+    it 'handles empty picking event batches' do
+      allow(render_app).to receive(:drain_picking_events).and_return([])
+
+      app = described_class.new(render: true)
+      app.instance_variable_set(:@render_app, render_app)
+
+      expect { app.send(:sync_input_from_bevy) }.not_to raise_error
+      reader = app.events.reader(Bevy::PickingEvent)
+      expect(reader.read).to be_empty
+    end
+  end
+end
+
+RSpec.describe Bevy::SystemContext do
+  describe 'render behaviors' do
+    let(:render_app) { double('render_app') }
+    let(:app) { Bevy::App.new(render: true) }
+    let(:context) do
+      app.instance_variable_set(:@render_app, render_app)
+      app.send(:build_context)
+    end
+
+    before do
+      allow(render_app).to receive(:spin)
+      allow(render_app).to receive(:pulse)
+      allow(render_app).to receive(:bob)
+      allow(render_app).to receive(:clear_behaviors)
+    end
+
+    it 'forwards spin with the entity id' do
+      entity = app.world.spawn
+      context.spin(entity, 3)
+
+      expect(render_app).to have_received(:spin).with(entity.id, 3.0)
+    end
+
+    it 'forwards pulse and bob options' do
+      context.pulse(7, scale_min: 0.9, scale_max: 1.1, period: 2)
+      context.bob(7, amplitude: 4, period: 1.5)
+
+      expect(render_app).to have_received(:pulse).with(7, scale_min: 0.9, scale_max: 1.1, period: 2.0)
+      expect(render_app).to have_received(:bob).with(7, amplitude: 4.0, period: 1.5, axis: :y)
+    end
+
+    it 'forwards clear_behaviors' do
+      context.clear_behaviors(7)
+
+      expect(render_app).to have_received(:clear_behaviors).with(7)
+    end
+
+    it 'is a no-op without a render app' do
+      headless = Bevy::App.new.send(:build_context)
+
+      expect { headless.spin(1, 1.0) }.not_to raise_error
+    end
+  end
+
+  describe 'levels of detail' do
+    let(:render_app) { double('render_app') }
+    let(:app) { Bevy::App.new(render: true) }
+    let(:context) do
+      app.instance_variable_set(:@render_app, render_app)
+      app.send(:build_context)
+    end
+
+    before do
+      allow(render_app).to receive(:set_lod)
+      allow(render_app).to receive(:clear_lod)
+      allow(render_app).to receive(:set_lod_margin)
+      allow(render_app).to receive(:lod_level).and_return(1)
+    end
+
+    it 'forwards levels with sprites converted to sync hashes' do
+      sprite = Bevy::Sprite.new(color: Bevy::Color.red)
+      context.set_lod(7, levels: [{ max_scale: 0.5, sprite: sprite }, { hide: true }])
+
+      expect(render_app).to have_received(:set_lod).with(
+        7, [{ max_scale: 0.5, sprite: sprite.to_sync_hash }, { hide: true }]
+      )
+    end
+
+    it 'forwards the margin, clearing and the current level' do
+      context.set_lod_margin(0.25)
+      context.clear_lod(7)
+
+      expect(context.lod_level(7)).to eq(1)
+      expect(render_app).to have_received(:set_lod_margin).with(0.25)
+      expect(render_app).to have_received(:clear_lod).with(7)
+    end
+
+    it 'is a no-op without a render app' do
+      headless = Bevy::App.new.send(:build_context)
+
+      expect(headless.lod_level(1)).to be_nil
+      expect { headless.set_lod(1, levels: [{ hide: true }]) }.not_to raise_error
+    end
+  end
+
+  describe 'mesh triangles' do
+    let(:render_app) { double('render_app') }
+    let(:app) { Bevy::App.new(render: true) }
+
+    it 'forwards the entity id to the render app' do
+      allow(render_app).to receive(:mesh_triangles).with(3).and_return([0.0, 0.0, 1.0, 0.0, 0.0, 1.0])
+      app.instance_variable_set(:@render_app, render_app)
+      entity = instance_double(Bevy::Entity, id: 3)
+
+      expect(app.send(:build_context).mesh_triangles(entity)).to eq([0.0, 0.0, 1.0, 0.0, 0.0, 1.0])
+    end
+
+    it 'is nil without a render app' do
+      expect(Bevy::App.new.send(:build_context).mesh_triangles(1)).to be_nil
+    end
+  end
+
+  describe 'transform snapshots' do
+    let(:render_app) { double('render_app') }
+    let(:app) { Bevy::App.new(render: true) }
+    let(:context) do
+      app.instance_variable_set(:@render_app, render_app)
+      app.send(:build_context)
+    end
+
+    it 'returns the packed [id, x, y, rotation] layout from the previous frame' do
+      allow(render_app).to receive(:transforms_snapshot).and_return([1.0, 10.0, 20.0, 0.5, 2.0, -5.0, 0.0, 0.0])
+
+      snapshot = context.transforms_snapshot
+
+      expect(render_app).to have_received(:transforms_snapshot).with(group: nil)
+      expect(snapshot.each_slice(described_class::TRANSFORM_SNAPSHOT_STRIDE).to_a).to eq(
+        [[1.0, 10.0, 20.0, 0.5], [2.0, -5.0, 0.0, 0.0]]
+      )
+    end
+
+    it 'passes the group filter through as a string' do
+      allow(render_app).to receive(:positions_snapshot).and_return([3.0, 1.0, 2.0])
+
+      snapshot = context.positions_snapshot(group: :enemies)
+
+      expect(render_app).to have_received(:positions_snapshot).with(group: 'enemies')
+      expect(snapshot.length % described_class::POSITION_SNAPSHOT_STRIDE).to eq(0)
+    end
+
+    it 'is empty without a render app' do
+      headless = Bevy::App.new.send(:build_context)
+
+      expect(headless.transforms_snapshot).to eq([])
+      expect(headless.positions_snapshot(group: :enemies)).to eq([])
+    end
+  end
+
+  describe 'line of sight' do
+    let(:render_app) { double('render_app') }
+    let(:app) { Bevy::App.new(render: true) }
+    let(:context) do
+      app.instance_variable_set(:@render_app, render_app)
+      app.send(:build_context)
+    end
+
+    it 'raycasts with float coordinates and a string group' do
+      allow(render_app).to receive(:raycast).and_return([7, 10.0, 0.0])
+
+      hit = context.raycast(0, 0, 100, 0, group: :walls)
+
+      expect(render_app).to have_received(:raycast).with(0.0, 0.0, 100.0, 0.0, group: 'walls')
+      expect(hit).to eq([7, 10.0, 0.0])
+    end
+
+    it 'checks line of sight between entities or ids' do
+      allow(render_app).to receive(:line_of_sight?).and_return(false)
+      guard = instance_double(Bevy::Entity, id: 3)
+
+      expect(context.line_of_sight?(guard, 5, blocking_group: :walls)).to be(false)
+      expect(render_app).to have_received(:line_of_sight?).with(3, 5, blocking_group: 'walls')
+    end
+
+    it 'is nil without a render app' do
+      headless = Bevy::App.new.send(:build_context)
+
+      expect(headless.raycast(0, 0, 1, 1)).to be_nil
+      expect(headless.line_of_sight?(1, 2)).to be_nil
     end
   end
 end
@@ -0,0 +1,108 @@
+# frozen_string_literal: true
+
+require 'tmpdir'
+
+RSpec.describe Bevy::AssetRegistry do
+  subject(:registry) { described_class.new }
+
+  let(:dir) { Dir.mktmpdir }
+
+  after { FileUtils.remove_entry(dir) }
+
+  def asset_file(name, content = 'v1')
+    path = File.join(dir, name)
+    File.write(path, content)
+    path
+  end
+
+  def touch_later(path)
+    later = File.mtime(path) + 5
+    File.utime(later, later, path)
+  end
+
+  it 'gives each path one ID and remembers its type' do
+    path = asset_file('hero.png')
+    id = registry.register(path, 'image')
+
+    expect(registry.register(path, 'image')).to eq(id)
+    expect(registry.path(id)).to eq(path)
+    expect(registry.asset_type(id)).to eq('image')
+    expect(registry.count).to eq(1)
+  end
+
+  it 'reports nothing while hot reload is disabled' do
+    path = asset_file('hero.png')
+    registry.register(path, 'image')
+    touch_later(path)
+
+    expect(registry.hot_reload_enabled?).to be(false)
+    expect(registry.check_for_changes).to eq([])
+  end
+
+  it 'reports a modified file once' do
+    path = asset_file('level.json')
+    id = registry.register(path, 'level')
+    registry.enable_hot_reload
+    touch_later(path)
+
+    expect(registry.check_for_changes).to eq([{ id: id, path: path, change_type: 'modified' }])
+    expect(registry.check_for_changes).to eq([])
+  end
+
+  it 'watches assets registered before hot reload was enabled' do
+    early = registry.register(asset_file('early.png'), 'image')
+    registry.enable_hot_reload
+    late = registry.register(asset_file('late.png'), 'image')
+    touch_later(registry.path(early))
+    touch_later(registry.path(late))
+
+    expect(registry.check_for_changes.map { |change| change[:id] }).to eq([early, late])
+  end
+
+  it 'reports a deleted file and its return' do
+    path = asset_file('hero.png')
+    id = registry.register(path, 'image')
+    registry.enable_hot_reload
+
+    File.delete(path)
+    expect(registry.check_for_changes).to eq([{ id: id, path: path, change_type: 'deleted' }])
+
+    asset_file('hero.png', 'v2')
+    expect(registry.check_for_changes).to eq([{ id: id, path: path, change_type: 'created' }])
+  end
+
+  it 'reports a file registered before it existed once it is created' do
+    path = File.join(dir, 'later.json')
+    id = registry.register(path, 'level')
+    registry.enable_hot_reload
+    expect(registry.check_for_changes).to eq([])
+
+    asset_file('later.json')
+    expect(registry.check_for_changes).to eq([{ id: id, path: path, change_type: 'created' }])
+  end
+
+  it 'stops reporting an unregistered asset' do
+    path = asset_file('hero.png')
+    id = registry.register(path, 'image')
+    registry.enable_hot_reload
+    registry.unregister(id)
+    touch_later(path)
+
+    expect(registry.check_for_changes).to eq([])
+    expect(registry.count).to eq(0)
+  end
+
+  it 'lists dependents to reload after a change, nearest first and once each' do
+    sheet = registry.register(asset_file('sheet.png'), 'image')
+    atlas = registry.register(asset_file('atlas.json'), 'atlas')
+    level = registry.register(asset_file('level.json'), 'level')
+    hud = registry.register(asset_file('hud.json'), 'ui')
+    registry.add_dependency(atlas, sheet)
+    registry.add_dependency(hud, sheet)
+    registry.add_dependency(level, atlas)
+    registry.add_dependency(level, hud)
+
+    expect(registry.reload_list(sheet)).to eq([sheet, atlas, hud, level])
+    expect(registry.reload_list(level)).to eq([level])
+  end
+end
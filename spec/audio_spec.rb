@@ -1,5 +1,7 @@
 # frozen_string_literal: true
 
+require 'tmpdir'
+
 RSpec.describe Bevy::AudioSource do
   describe '.new' do
     it 'creates an AudioSource with a path' do
@@ -587,6 +589,192 @@ RSpec.describe Bevy::AudioQueue do
       expect(queue.empty?).to be true
     end
   end
+
+  describe '#add_all' do
+    it 'appends tracks in order' do
+      queue.add('intro.ogg')
+      queue.add_all(%w[a.ogg b.ogg])
+
+      expect(queue.to_a).to eq(%w[intro.ogg a.ogg b.ogg])
+    end
+  end
+
+  describe '#shuffle=' do
+    let(:tracks) { (1..8).map { |i| "track#{i}.ogg" } }
+
+    before { queue.add_all(tracks) }
+
+    it 'plays every track once before the queue ends' do
+      queue.seed(3)
+      queue.shuffle = true
+      played = [queue.current]
+      while (track = queue.next)
+        played << track
+      end
+
+      expect(played.first).to eq('track1.ogg')
+      expect(played).to match_array(tracks)
+      expect(played).not_to eq(tracks)
+    end
+
+    it 'draws a new order each loop without repeating across the wrap' do
+      queue.seed(11)
+      queue.loop_queue = true
+      queue.shuffle = true
+      cycles = Array.new(4) { Array.new(tracks.size) { queue.next } }
+
+      cycles.each { |cycle| expect(cycle.uniq.size).to eq(tracks.size) }
+      cycles.each_cons(2) { |before, after| expect(after.first).not_to eq(before.last) }
+    end
+
+    it 'steps back through the shuffled order, including into the previous loop' do
+      queue.loop_queue = true
+      queue.shuffle = true
+      played = Array.new(tracks.size + 2) { queue.next }
+
+      expect(Array.new(4) { queue.previous }).to eq(played.reverse[1, 4])
+    end
+
+    it 'repeats the same order for the same seed' do
+      orders = Array.new(2) do
+        shuffled = described_class.new
+        shuffled.add_all(tracks)
+        shuffled.seed(42)
+        shuffled.shuffle = true
+        Array.new(tracks.size - 1) { shuffled.next }
+      end
+
+      expect(orders.first).to eq(orders.last)
+    end
+
+    it 'continues in order from the current track when turned off' do
+      queue.shuffle = true
+      queue.next
+      current = queue.current
+      queue.shuffle = false
+
+      expect(queue.shuffle).to be false
+      expect(queue.next).to eq(tracks[tracks.index(current) + 1])
+    end
+  end
+end
+
+RSpec.describe Bevy::Audio do
+  let(:tracks) { [] }
+
+  after do
+    tracks.each { |id| described_class.stop(id) }
+    described_class.set_master_volume(1.0)
+    described_class.set_channel_volume('music', 1.0)
+  end
+
+  def play(**settings)
+    described_class.play_with_settings('music.ogg', 'music', settings).tap { |id| tracks << id }
+  end
+
+  it 'returns increasing integer track IDs' do
+    first = described_class.play('jump.ogg', 'sfx').tap { |id| tracks << id }
+    second = described_class.play('land.ogg', 'sfx').tap { |id| tracks << id }
+
+    expect(first).to be_a(Integer)
+    expect(second).to be > first
+    expect(described_class).to be_playing(second)
+  end
+
+  it 'mixes master, channel and track volume' do
+    id = play(volume: 0.5)
+    described_class.set_channel_volume('music', 0.5)
+    described_class.set_master_volume(0.8)
+
+    expect(described_class.volume(id)).to be_within(1e-6).of(0.2)
+  end
+
+  it 'pauses and resumes a track' do
+    id = play
+    described_class.pause(id)
+    expect(described_class).not_to be_playing(id)
+
+    described_class.resume(id)
+    expect(described_class).to be_playing(id)
+  end
+
+  it 'fades in from silence' do
+    id = play(fade_in: 1.0)
+    expect(described_class.volume(id)).to eq(0.0)
+
+    described_class.update(0.5)
+    expect(described_class.volume(id)).to be_within(1e-6).of(0.5)
+  end
+
+  it 'stops a track once its fade-out completes' do
+    id = play
+    described_class.stop_with_fade(id, 1.0)
+    described_class.update(0.5)
+    expect(described_class.volume(id)).to be_within(1e-6).of(0.5)
+
+    described_class.update(0.6)
+    expect(described_class).not_to be_playing(id)
+    expect(described_class.volume(id)).to be_nil
+  end
+
+  it 'stops a track immediately' do
+    id = play
+    described_class.stop(id)
+
+    expect(described_class).not_to be_playing(id)
+  end
+
+  describe 'sound sets' do
+    around do |example|
+      Dir.mktmpdir do |dir|
+        @sounds = (1..5).map { |i| File.join(dir, "fs#{i}.ogg").tap { |path| File.write(path, '') } }
+        example.run
+      end
+    end
+
+    after { described_class.remove_sound_set('footstep') }
+
+    def play_set(**options)
+      described_class.play_sound_set('footstep', **options).tap { |id| tracks << id }
+    end
+
+    it 'never picks one of the last avoid_repeat members' do
+      described_class.register_sound_set('footstep', @sounds, avoid_repeat: 2)
+      paths = Array.new(200) { described_class.path(play_set) }
+
+      expect(paths.uniq).to match_array(@sounds)
+      paths.each_cons(3) { |*recent, path| expect(recent).not_to include(path) }
+    end
+
+    it 'keeps volume and speed within the variation' do
+      described_class.register_sound_set('footstep', @sounds)
+      ids = Array.new(100) { play_set(channel: 'sfx', volume: 0.5, volume_variation: 0.1, pitch_variation: 0.05) }
+
+      expect(ids.map { |id| described_class.volume(id) }).to all(be_between(0.45, 0.55))
+      expect(ids.map { |id| described_class.speed(id) }).to all(be_between(0.95, 1.05))
+      expect(ids.map { |id| described_class.volume(id) }.uniq.size).to be > 1
+    end
+
+    it 'replays the same picks for the same seed' do
+      picks = lambda do
+        described_class.register_sound_set('footstep', @sounds)
+        described_class.seed_sound_sets(7)
+        Array.new(20) { described_class.path(play_set(volume_variation: 0.2)) }
+      end
+
+      expect(picks.call).to eq(picks.call)
+    end
+
+    it 'rejects missing files and empty sets' do
+      expect { described_class.register_sound_set('footstep', ['missing.ogg']) }
+        .to raise_error(ArgumentError, /missing\.ogg/)
+      expect { described_class.register_sound_set('footstep', []) }.to raise_error(ArgumentError, /no sounds/)
+    end
+
+    it 'raises for an unknown set' do
+      expect { described_class.play_sound_set('nope') }.to raise_error(ArgumentError, /unknown sound set/)
+    end
+  end
 end
 
 RSpec.describe 'World with Audio' do
@@ -0,0 +1,136 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::Internal::EntityBehaviors do
+  subject(:behaviors) { described_class.new }
+
+  def translation(transform)
+    vector = transform.translation
+    [vector.x, vector.y, vector.z]
+  end
+
+  describe 'spin' do
+    it 'wraps the rotation into one turn' do
+      behaviors.spin(1, 1.0)
+      behaviors.advance((2 * Math::PI) + 0.5)
+
+      expect(behaviors.offset(1)[:rotation_z]).to be_within(1e-4).of(0.5)
+    end
+
+    it 'wraps a negative speed to a positive angle' do
+      behaviors.spin(1, -1.0)
+      behaviors.advance(0.5)
+
+      expect(behaviors.offset(1)[:rotation_z]).to be_within(1e-4).of((2 * Math::PI) - 0.5)
+    end
+  end
+
+  describe 'pulse' do
+    it 'eases the scale between its bounds over the period' do
+      behaviors.pulse(1, { scale_min: 1.0, scale_max: 2.0, period: 2.0 })
+      expect(behaviors.offset(1)[:scale]).to be_within(1e-5).of(1.0)
+
+      behaviors.advance(0.5)
+      expect(behaviors.offset(1)[:scale]).to be_within(1e-5).of(1.5)
+
+      behaviors.advance(0.5)
+      expect(behaviors.offset(1)[:scale]).to be_within(1e-5).of(2.0)
+
+      behaviors.advance(1.0)
+      expect(behaviors.offset(1)[:scale]).to be_within(1e-5).of(1.0)
+    end
+
+    it 'holds the minimum scale for a zero period' do
+      behaviors.pulse(1, { scale_min: 0.5, scale_max: 2.0, period: 0.0 })
+      behaviors.advance(0.3)
+
+      expect(behaviors.offset(1)[:scale]).to eq(0.5)
+    end
+  end
+
+  describe 'bob' do
+    it 'moves along its axis with a sine of the phase' do
+      behaviors.bob(1, { amplitude: 10.0, period: 4.0, axis: :x })
+
+      behaviors.advance(1.0)
+      expect(behaviors.offset(1)[:translation][0]).to be_within(1e-4).of(10.0)
+
+      behaviors.advance(2.0)
+      expect(behaviors.offset(1)[:translation][0]).to be_within(1e-4).of(-10.0)
+      expect(behaviors.offset(1)[:translation][1]).to eq(0.0)
+    end
+
+    it 'rejects an unknown axis' do
+      expect { behaviors.bob(1, { amplitude: 1.0, period: 1.0, axis: :w }) }
+        .to raise_error(ArgumentError, /Unknown bob axis/)
+    end
+  end
+
+  it 'stacks behaviors of different kinds on one entity' do
+    behaviors.spin(1, 2.0)
+    behaviors.pulse(1, { scale_min: 1.0, scale_max: 3.0, period: 2.0 })
+    behaviors.bob(1, { amplitude: 4.0, period: 2.0 })
+
+    behaviors.advance(0.5)
+    offset = behaviors.offset(1)
+
+    expect(offset[:rotation_z]).to be_within(1e-5).of(1.0)
+    expect(offset[:scale]).to be_within(1e-5).of(2.0)
+    expect(offset[:translation][1]).to be_within(1e-4).of(4.0)
+  end
+
+  it 'replaces a behavior of the same kind and keeps its phase' do
+    behaviors.spin(1, 1.0)
+    behaviors.advance(1.0)
+    behaviors.spin(1, 2.0)
+    behaviors.advance(1.0)
+
+    expect(behaviors.offset(1)[:rotation_z]).to be_within(1e-5).of(3.0)
+    expect(behaviors.size).to eq(1)
+  end
+
+  describe '#compose' do
+    it 'adds the offset to the authored transform' do
+      behaviors.bob(1, { amplitude: 10.0, period: 4.0 })
+      behaviors.pulse(1, { scale_min: 2.0, scale_max: 2.0, period: 1.0 })
+      behaviors.advance(1.0)
+
+      written = behaviors.compose(1, Bevy::Transform.from_xyz(5.0, 0.0, 0.0))
+
+      expect(translation(written)[0]).to eq(5.0)
+      expect(translation(written)[1]).to be_within(1e-4).of(10.0)
+      expect(written.scale.x).to eq(2.0)
+    end
+
+    it 'keeps the authored transform while its own write is still in place' do
+      behaviors.bob(1, { amplitude: 10.0, period: 4.0 })
+      behaviors.advance(1.0)
+      written = behaviors.compose(1, Bevy::Transform.from_xyz(5.0, 0.0, 0.0))
+
+      behaviors.advance(2.0)
+
+      recomposed = translation(behaviors.compose(1, written))
+      expect(recomposed[0]).to eq(5.0)
+      expect(recomposed[1]).to be_within(1e-4).of(-10.0)
+    end
+
+    it 're-captures the authored transform when something else wrote a new one' do
+      behaviors.bob(1, { amplitude: 10.0, period: 4.0 })
+      behaviors.advance(1.0)
+      behaviors.compose(1, Bevy::Transform.from_xyz(5.0, 0.0, 0.0))
+
+      written = behaviors.compose(1, Bevy::Transform.from_xyz(100.0, 0.0, 0.0))
+
+      expect(translation(written)[0]).to eq(100.0)
+      expect(translation(written)[1]).to be_within(1e-4).of(10.0)
+    end
+
+    it 'returns nil for an entity without behaviors' do
+      behaviors.spin(1, 1.0)
+      expect(behaviors.clear(1)).to be(true)
+
+      expect(behaviors.compose(1, Bevy::Transform.identity)).to be_nil
+      expect(behaviors.offset(1)).to be_nil
+      expect(behaviors.clear(1)).to be(false)
+    end
+  end
+end
@@ -0,0 +1,86 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::RenderApp, :gpu do
+  def headless_app(frames:)
+    described_class.new(headless: true, width: 32.0, height: 32.0, frames: frames)
+  end
+
+  def sync_at(app, x, y)
+    sprite = Bevy::Sprite.new(custom_size: Bevy::Vec2.new(4.0, 4.0)).to_sync_hash
+    transform = Bevy::Transform.new(translation: Bevy::Vec3.new(x, y, 0.0)).to_sync_hash
+    app.sync_sprite(1, sprite, transform)
+  end
+
+  def sprite_position(app)
+    _id, x, y = app.positions_snapshot.first(3)
+    x && [x, y]
+  end
+
+  it 'applies a camera move in the same frame as the sprite moves queued with it' do
+    app = headless_app(frames: 6)
+    applied = []
+
+    app.run do
+      step = app.frame_count.to_f
+      sync_at(app, step * 10.0, 0.0)
+      app.set_camera_position(step * 10.0, 0.0, 0.0)
+      applied << app.applied_frames
+    end
+
+    later = applied.drop(1)
+    expect(later).not_to be_empty
+    expect(later).to all(satisfy { |frames| frames[:camera] && frames[:camera] == frames[:sprites] })
+  ensure
+    app&.close
+  end
+
+  it 'keeps a followed sprite within the offset of the camera every frame' do
+    app = headless_app(frames: 8)
+    offset = [3.0, -2.0]
+    gaps = []
+
+    app.run do
+      step = app.frame_count.to_f
+      sync_at(app, step * 25.0, step * -15.0)
+      app.camera_follow_entity(1, offset: offset) if app.frame_count.zero?
+
+      position = sprite_position(app)
+      next unless position
+
+      camera = app.camera_position
+      gaps << [camera[0] - position[0], camera[1] - position[1]]
+    end
+
+    expect(gaps.length).to be >= 3
+    gaps.drop(1).each do |dx, dy|
+      expect(dx).to be_within(1e-3).of(offset[0])
+      expect(dy).to be_within(1e-3).of(offset[1])
+    end
+  ensure
+    app&.close
+  end
+
+  it 'stops following when the camera position is set' do
+    app = headless_app(frames: 2)
+    follows = []
+
+    app.run do
+      app.camera_follow_entity(1)
+      follows << app.camera_follow
+      app.set_camera_position(5.0, 5.0, 0.0)
+      follows << app.camera_follow
+    end
+
+    expect(follows.first(2)).to eq([{ id: 1, offset: [0.0, 0.0] }, nil])
+  ensure
+    app&.close
+  end
+
+  it 'rejects an offset that is not two finite numbers' do
+    app = headless_app(frames: 1)
+
+    expect { app.camera_follow_entity(1, offset: [1.0]) }.to raise_error(ArgumentError, /offset/)
+  ensure
+    app&.close
+  end
+end
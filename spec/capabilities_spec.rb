@@ -0,0 +1,44 @@
+# frozen_string_literal: true
+
+RSpec.describe 'Bevy capability introspection' do
+  let(:rendering_features) { %i[picking texture_sprites] }
+
+  it 'reports the crate and Bevy versions' do
+    expect(Bevy.version).to match(/\A\d+\.\d+\.\d+/)
+    expect(Bevy.bevy_version).to match(/\A0\.\d+/)
+  end
+
+  it 'returns a frozen hash of booleans keyed by symbols' do
+    capabilities = Bevy.capabilities
+
+    expect(capabilities).to be_frozen
+    expect(capabilities.keys).to all(be_a(Symbol))
+    expect(capabilities.values).to all(be(true).or(be(false)))
+    expect(capabilities.keys).to include(:rendering, :audio, :picking, :headless_mode, :texture_sprites,
+                                         :determinism_audit)
+  end
+
+  it 'answers capability? from the same table' do
+    Bevy.capabilities.each do |name, available|
+      expect(Bevy.capability?(name)).to eq(available)
+      expect(Bevy.capability?(name.to_s)).to eq(available)
+    end
+    expect(Bevy.capability?(:teleportation)).to be false
+  end
+
+  it 'always supports headless mode and has no audio backend' do
+    expect(Bevy.capabilities).to include(headless_mode: true, audio: false)
+  end
+
+  context 'when built with rendering', if: Bevy.capability?(:rendering) do
+    it 'enables the rendering subsystems' do
+      expect(Bevy.capabilities.slice(*rendering_features).values).to all(be true)
+    end
+  end
+
+  context 'when built without rendering', unless: Bevy.capability?(:rendering) do
+    it 'disables the rendering subsystems' do
+      expect(Bevy.capabilities.slice(*rendering_features).values).to all(be false)
+    end
+  end
+end
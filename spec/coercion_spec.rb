@@ -0,0 +1,106 @@
+# frozen_string_literal: true
+
+begin
+  require 'bigdecimal'
+rescue LoadError
+  # bigdecimal is a bundled gem from Ruby 3.4; its row is skipped without it.
+end
+
+RSpec.describe 'numeric coercion of data hashes' do
+  # Sprite data parsed through the same helper the render app uses.
+  def checksum(sprite)
+    audit = Bevy::DeterminismAudit.new
+    audit.record_sprite(1, sprite, { x: 0.0 })
+    audit.finish_frame
+  end
+
+  def with_coerce_strings(enabled)
+    previous = Bevy.coerce_strings
+    Bevy.coerce_strings = enabled
+    yield
+  ensure
+    Bevy.coerce_strings = previous
+  end
+
+  numbers = {
+    'Integer' => 1,
+    'Float' => 1.0,
+    'Rational' => Rational(2, 2)
+  }
+  numbers['BigDecimal'] = BigDecimal('1.0') if defined?(BigDecimal)
+
+  numbers.each do |class_name, value|
+    it "accepts #{class_name} where a float is expected" do
+      expect(checksum(anchor_x: value, custom_size_x: value * 64)).to eq(checksum(anchor_x: 1.0, custom_size_x: 64.0))
+    end
+  end
+
+  {
+    'Integer' => 2**200,
+    'Float' => 1e39,
+    'Rational' => Rational(10**50, 3)
+  }.each do |class_name, value|
+    it "raises RangeError naming the key for an out-of-range #{class_name}" do
+      expect { checksum(custom_size_x: value) }
+        .to raise_error(RangeError, /:custom_size_x: .* \(#{class_name}\) is out of range for a 32-bit float/)
+    end
+  end
+
+  it 'accepts values that only lose precision in a 32-bit float' do
+    expect { checksum(custom_size_x: 2**70) }.not_to raise_error
+  end
+
+  it 'passes explicit infinities through' do
+    expect { checksum(custom_size_x: Float::INFINITY) }.not_to raise_error
+  end
+
+  {
+    'Symbol' => :wide,
+    'Array' => [1.0]
+  }.each do |class_name, value|
+    it "raises TypeError naming the key and #{class_name}" do
+      expect { checksum(color_r: value) }
+        .to raise_error(TypeError, /:color_r: expected a number, got #{class_name}/)
+    end
+  end
+
+  it 'treats nil as a missing key' do
+    expect(checksum(color_r: nil)).to eq(checksum({}))
+  end
+
+  it 'names the key and class for non-numeric fields too' do
+    expect { checksum(texture_path: 5) }.to raise_error(TypeError, /:texture_path: Integer/)
+  end
+
+  describe 'Bevy.coerce_strings' do
+    it 'is off by default' do
+      expect(Bevy.coerce_strings).to be false
+    end
+
+    it 'rejects numeric strings while off and explains the flag' do
+      with_coerce_strings(false) do
+        expect { checksum(custom_size_x: '2') }
+          .to raise_error(TypeError, /:custom_size_x: expected a number, got String.*coerce_strings/)
+      end
+    end
+
+    it 'parses numeric strings while on' do
+      with_coerce_strings(true) do
+        expect(checksum(custom_size_x: ' 2.5 ', anchor_x: '1')).to eq(checksum(custom_size_x: 2.5, anchor_x: 1.0))
+      end
+    end
+
+    it 'still rejects strings that are not numbers' do
+      with_coerce_strings(true) do
+        expect { checksum(custom_size_x: 'wide') }
+          .to raise_error(ArgumentError, /:custom_size_x: "wide" \(String\) is not a number/)
+      end
+    end
+
+    it 'still range checks parsed strings' do
+      with_coerce_strings(true) do
+        expect { checksum(custom_size_x: '1e40') }.to raise_error(RangeError, /:custom_size_x:/)
+      end
+    end
+  end
+end
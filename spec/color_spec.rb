@@ -41,6 +41,25 @@ RSpec.describe Bevy::Color do
     end
   end
 
+  describe '.from_hsl' do
+    it 'creates opaque primaries from hue' do
+      expect(described_class.from_hsl(0, 1, 0.5).r).to be_within(0.001).of(1.0)
+      expect(described_class.from_hsl(120, 1, 0.5).g).to be_within(0.001).of(1.0)
+      expect(described_class.from_hsl(240, 1, 0.5).b).to be_within(0.001).of(1.0)
+      expect(described_class.from_hsl(240, 1, 0.5).a).to be_within(0.001).of(1.0)
+    end
+
+    it 'wraps the hue' do
+      wrapped = described_class.from_hsl(480, 1, 0.5).to_a
+      expect(wrapped.zip(described_class.from_hsl(120, 1, 0.5).to_a)).to all(satisfy { |a, b| (a - b).abs < 0.001 })
+    end
+
+    it 'gives grey without saturation' do
+      c = described_class.from_hsl(200, 0, 0.5)
+      expect([c.r, c.g, c.b]).to all(be_within(0.001).of(0.5))
+    end
+  end
+
   describe '.white' do
     it 'creates a white color' do
       c = described_class.white
@@ -118,6 +137,36 @@ RSpec.describe Bevy::Color do
     end
   end
 
+  describe '#lerp' do
+    it 'returns the endpoints at 0 and 1' do
+      from = described_class.new(1.0, 0.0, 0.0, 1.0)
+      to = described_class.new(0.0, 0.0, 1.0, 0.0)
+      expect(from.lerp(to, 0).to_a).to eq(from.to_a)
+      to_a = from.lerp(to, 1).to_a
+      expect(to_a.zip(to.to_a)).to all(satisfy { |a, b| (a - b).abs < 0.001 })
+    end
+
+    it 'interpolates alpha' do
+      expect(described_class.white.lerp(described_class.transparent, 0.5).a).to be_within(0.001).of(0.5)
+    end
+
+    it 'blends in linear space' do
+      mid = described_class.black.lerp(described_class.white, 0.5)
+      expect(mid.r).to be_within(0.01).of(0.735)
+    end
+
+    it 'clamps t' do
+      expect(described_class.black.lerp(described_class.white, 2.0).r).to be_within(0.001).of(1.0)
+      expect(described_class.black.lerp(described_class.white, -1.0).r).to be_within(0.001).of(0.0)
+    end
+
+    it 'leaves both colors unchanged' do
+      from = described_class.black
+      from.lerp(described_class.white, 0.5)
+      expect(from.r).to eq(0.0)
+    end
+  end
+
   describe '#to_a' do
     it 'converts to array' do
       c = described_class.new(0.1, 0.2, 0.3, 0.4)
@@ -61,6 +61,81 @@ RSpec.describe Bevy::Component do
       expect(hash[:y]).to eq(20)
     end
   end
+
+  describe '#generation' do
+    it 'starts at zero and grows with every set' do
+      component = described_class.new('Health')
+      expect(component.generation).to eq(0)
+
+      component['current'] = 100
+      component['current'] = 100
+      component['max'] = 100
+
+      expect(component.generation).to eq(3)
+    end
+
+    it 'is not part of the content hash' do
+      set_once = described_class.from_hash('Health', { current: 100 })
+      set_twice = described_class.from_hash('Health', { current: 1 })
+      set_twice['current'] = 100
+
+      expect(set_twice.generation).not_to eq(set_once.generation)
+      expect(set_twice.content_hash).to eq(set_once.content_hash)
+    end
+  end
+
+  describe '#content_hash' do
+    def nested(gold, tags)
+      described_class.from_hash('Inventory', { bag: { gold: gold, tags: tags } })
+    end
+
+    it 'matches the documented encoding across runs' do
+      expect(described_class.from_hash('Health', { current: 100, max: 100 }).content_hash)
+        .to eq(11_256_376_138_553_228_390)
+      expect(described_class.from_hash('Position', { x: 1.5, y: -2.0 }).content_hash)
+        .to eq(10_612_172_501_739_317_396)
+      expect(described_class.new('Player').content_hash).to eq(3_504_799_521_963_391_186)
+      expect(nested(3, [:rare, 'x', nil, true]).content_hash).to eq(6_149_893_834_553_301_145)
+    end
+
+    it 'does not depend on the order fields were set in' do
+      forward = described_class.new('Stats')
+      backward = described_class.new('Stats')
+      %w[a b c d e f g h].each_with_index { |key, i| forward[key] = i }
+      %w[a b c d e f g h].each_with_index.reverse_each { |key, i| backward[key] = i }
+
+      reordered = described_class.from_hash('Inventory', { bag: { tags: [1], gold: 3 } })
+
+      expect(forward.content_hash).to eq(backward.content_hash)
+      expect(nested(3, [1]).content_hash).to eq(reordered.content_hash)
+    end
+
+    it 'changes when a float changes by a single bit' do
+      one = described_class.from_hash('Position', { x: 1.5 })
+      other = described_class.from_hash('Position', { x: 1.5.next_float })
+
+      expect(one.content_hash).not_to eq(other.content_hash)
+    end
+
+    it 'tells apart values that compare equal across types or signs' do
+      hashes = [1, 1.0, '1', :'1', 0.0, -0.0].map { |value| described_class.from_hash('V', { v: value }).content_hash }
+
+      expect(hashes.uniq.length).to eq(hashes.length)
+    end
+
+    it 'changes when nested data changes' do
+      base = nested(3, [:rare]).content_hash
+
+      expect(nested(4, [:rare]).content_hash).not_to eq(base)
+      expect(nested(3, [:common]).content_hash).not_to eq(base)
+      expect(nested(3, %i[rare rare]).content_hash).not_to eq(base)
+      expect(nested(3, [[:rare]]).content_hash).not_to eq(base)
+    end
+
+    it 'includes the type name' do
+      expect(described_class.new('A').content_hash).not_to eq(described_class.new('B').content_hash)
+    end
+  end
 end
 
 RSpec.describe 'World with Components' do
@@ -146,5 +221,21 @@ RSpec.describe 'World with Components' do
       entities = world.query(%w[NonExistent])
       expect(entities).to be_empty
     end
+
+    it 'applies the with and without filters of a query builder' do
+      position = -> { Bevy::Component.from_hash('Position', { x: 0, y: 0 }) }
+      velocity = Bevy::Component.from_hash('Velocity', { x: 1, y: 0 })
+      frozen = Bevy::Component.from_hash('Frozen', {})
+
+      moving = world.spawn_with([position.call, velocity])
+      world.spawn_with([position.call, Bevy::Component.from_hash('Velocity', { x: 0, y: 1 }), frozen])
+      world.spawn_with([position.call])
+
+      builder = Bevy::QueryBuilder.new.fetch('Position').with('Velocity').without('Frozen')
+
+      expect(builder.with_components).to eq(['Velocity'])
+      expect(builder.without_components).to eq(['Frozen'])
+      expect(world.query(builder).map(&:id)).to eq([moving.id])
+    end
   end
 end
@@ -0,0 +1,117 @@
+# frozen_string_literal: true
+
+require 'json'
+require 'tmpdir'
+
+RSpec.describe Bevy::DebugBundle do
+  let(:dir) { Dir.mktmpdir }
+
+  after { FileUtils.remove_entry(dir) }
+
+  def manifest(path)
+    JSON.parse(File.read(File.join(path, 'manifest.json')), symbolize_names: true)
+  end
+
+  def section(result, name)
+    result[:sections].find { |entry| entry[:name] == name }
+  end
+
+  it 'writes the sections that work when another collector raises' do
+    bundle = described_class.new
+    bundle.collect(:stats) { { fps: 60 } }
+    bundle.collect(:broken) { raise 'collector exploded' }
+    path = File.join(dir, 'bundle')
+    result = bundle.write(path)
+
+    expect(section(result, 'stats')).to include(status: :written, file: 'stats.json')
+    expect(section(result, 'broken')).to include(status: :failed)
+    expect(section(result, 'broken')[:error]).to include('collector exploded')
+    expect(JSON.parse(File.read(File.join(path, 'stats.json')))).to eq('fps' => 60)
+    expect(manifest(path)[:sections].map { |entry| entry[:status] }).to eq(%w[written failed])
+  end
+
+  it 'skips a section whose collector returns nil' do
+    bundle = described_class.new
+    bundle.collect(:screenshot) { nil }
+    result = bundle.write(File.join(dir, 'bundle'))
+
+    expect(section(result, 'screenshot')).to include(status: :skipped)
+    expect(File.exist?(File.join(dir, 'bundle', 'screenshot.json'))).to be false
+  end
+
+  it 'records versions, capabilities and info in the manifest' do
+    bundle = described_class.new
+    bundle.set_info(:frame, 42)
+    path = File.join(dir, 'bundle')
+    bundle.write(path)
+
+    written = manifest(path)
+    expect(written[:info]).to include(:bevy_ruby_version, :bevy_version, frame: '42')
+    expect(written[:capabilities]).to be_a(Hash)
+  end
+
+  it 'keeps only the newest log lines within max_log_bytes' do
+    bundle = described_class.new(max_log_bytes: 13)
+    bundle.collect_log(:journal) { %w[first second third] }
+    path = File.join(dir, 'bundle')
+    result = bundle.write(path)
+
+    expect(File.read(File.join(path, 'journal.log'))).to eq("second\nthird\n")
+    expect(section(result, 'journal')).to include(dropped_lines: 1)
+  end
+
+  it 'writes images as PNG files' do
+    bundle = described_class.new
+    bundle.collect_image(:screenshot) { [2, 1, "\xFF".b * 8] }
+    path = File.join(dir, 'bundle')
+    bundle.write(path)
+
+    expect(File.binread(File.join(path, 'screenshot.png'), 8)).to eq("\x89PNG\r\n\x1A\n".b)
+  end
+
+  it 'requires a collector block' do
+    expect { described_class.new.collect(:stats) }.to raise_error(ArgumentError)
+  end
+
+  describe 'from an app' do
+    it 'dumps the world headlessly without a render app' do
+      app = Bevy::App.new
+      app.world.spawn_entity(Bevy::Sprite.new, Bevy::Transform.identity)
+      path = File.join(dir, 'bundle')
+      result = app.dump_debug_bundle(path)
+
+      entities = JSON.parse(File.read(File.join(path, 'entities.json')), symbolize_names: true)
+      expect(entities[:sprites].size).to eq(1)
+      expect(result[:sections].map { |entry| entry[:name] }).to include('entities', 'warnings', 'journal')
+      expect(manifest(path)[:info]).to include(ruby_version: RUBY_VERSION)
+    end
+
+    it 'adds its sections to the render app bundle' do
+      app = Bevy::App.new(render: true)
+      bundle = described_class.new
+      render_app = double('render_app')
+      allow(render_app).to receive(:dump_debug_bundle) do |path, _options, &block|
+        block.call(bundle)
+        bundle.write(path)
+      end
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.dump_debug_bundle(File.join(dir, 'bundle'), max_log_bytes: 1024)
+
+      expect(render_app).to have_received(:dump_debug_bundle).with(File.join(dir, 'bundle'), { max_log_bytes: 1024 })
+      expect(bundle.section_names).to include('entities', 'warnings', 'journal')
+    end
+
+    it 'dumps into a timestamped directory when the shortcut key is pressed' do
+      app = Bevy::App.new(render: true)
+      app.set_debug_bundle_key(Bevy::KeyCode::F12, directory: dir)
+      render_app = double('render_app', raw_key_just_pressed?: true, dump_debug_bundle: { sections: [] })
+      app.instance_variable_set(:@render_app, render_app)
+
+      app.send(:dump_debug_bundle_on_key)
+
+      expect(app.debug_bundle_key).to eq(Bevy::KeyCode::F12.to_s)
+      expect(render_app).to have_received(:dump_debug_bundle).with(a_string_starting_with(File.join(dir, 'bundle-')), {})
+    end
+  end
+end
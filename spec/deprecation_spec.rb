@@ -0,0 +1,104 @@
+# frozen_string_literal: true
+
+RSpec.describe 'Bevy deprecations' do
+  let(:renamed_class) do
+    Class.new do
+      def self.to_s
+        'Example'
+      end
+
+      def area(width, height: 1)
+        width * height
+      end
+
+      Bevy.deprecate_method(self, :surface, :area, '2.0.0')
+    end
+  end
+
+  before { Bevy.clear_deprecations }
+
+  after do
+    Bevy.deprecations = :collect
+    Bevy.clear_deprecations
+  end
+
+  it 'forwards the old name to the new one with its arguments' do
+    expect(renamed_class.new.surface(3, height: 2)).to eq(6)
+  end
+
+  it 'records one notice per call site' do
+    object = renamed_class.new
+    3.times { object.surface(1) }
+    object.surface(2)
+
+    notices = Bevy.deprecation_notices
+    expect(notices.size).to eq(2)
+    expect(notices.first).to include(
+      method: 'Example#surface', replacement: 'Example#area', removal_version: '2.0.0'
+    )
+    expect(notices.map { |notice| notice[:call_site] }).to all(include(__FILE__))
+    expect(notices.map { |notice| notice[:call_site] }.uniq.size).to eq(2)
+  end
+
+  it 'formats the report one line per call site' do
+    renamed_class.new.surface(1)
+    line = __LINE__ - 1
+
+    expect(Bevy.deprecation_report).to eq(
+      'Example#surface is deprecated and will be removed in 2.0.0; use Example#area instead ' \
+      "(called from #{Bevy.deprecation_notices.first[:call_site]})\n"
+    )
+    expect(Bevy.deprecation_report).to include("#{__FILE__}:#{line}")
+  end
+
+  it 'reports nothing when no deprecated method was called' do
+    expect(Bevy.deprecation_report).to eq('')
+    expect(Bevy.deprecation_notices).to eq([])
+  end
+
+  it 'raises on every call in raise mode' do
+    Bevy.deprecations = :raise
+    object = renamed_class.new
+
+    2.times do
+      expect { object.surface(1) }.to raise_error(Bevy::DeprecationError, /Example#surface is deprecated/)
+    end
+    expect(Bevy.deprecation_notices).to eq([])
+  end
+
+  it 'ignores calls in silent mode' do
+    Bevy.deprecations = :silent
+
+    expect(renamed_class.new.surface(4)).to eq(4)
+    expect(Bevy.deprecation_notices).to eq([])
+  end
+
+  it 'rejects unknown modes' do
+    expect { Bevy.deprecations = :loud }.to raise_error(ArgumentError, /unknown deprecation mode/)
+    expect(Bevy.deprecations).to eq(:collect)
+  end
+
+  it 'stops recording new call sites past the limit and counts them' do
+    object = renamed_class.new
+    eval("object.surface(1)\n" * 260, binding, 'many_sites.rb', 1)
+
+    expect(Bevy.deprecation_notices.size).to eq(256)
+    expect(Bevy.deprecation_report.lines.last).to eq("4 more call sites not recorded (limit 256)\n")
+  end
+
+  it 'records calls from other threads' do
+    object = renamed_class.new
+    Thread.new { object.surface(1) }.join
+
+    expect(Bevy.deprecation_notices.size).to eq(1)
+  end
+
+  it 'keeps the renamed methods in this release working' do
+    context = Bevy::App.new.send(:build_context)
+
+    context.sync_camera_to_transform(Bevy::Transform.new)
+
+    expect(Bevy.deprecation_notices.map { |notice| notice[:replacement] })
+      .to eq(['Bevy::SystemContext#set_camera_transform'])
+  end
+end
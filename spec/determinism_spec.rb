@@ -0,0 +1,111 @@
+# frozen_string_literal: true
+
+require 'tempfile'
+
+RSpec.describe Bevy::DeterminismAudit do
+  # A scripted run: each frame delivers input, then syncs sprites in `order`.
+  def run(frames, order: ->(ids, _frame) { ids })
+    audit = described_class.new
+    frames.times do |frame|
+      audit.record_input(frame.even? ? ['KeyA'] : [], frame.to_f, 0.0)
+      order.call([1, 2, 3], frame).each do |id|
+        audit.record_sprite(id, { color_r: 0.5 }, { x: id * frame.to_f, y: 0.0, rotation: 0.25 })
+      end
+      audit.record_remove_sprite(3) if frame == 4
+      audit.finish_frame
+    end
+    audit
+  end
+
+  it 'produces identical streams for identical runs' do
+    expect(run(10).history).to eq(run(10).history)
+    expect(described_class.compare(run(10).history, run(10).history)).to be_nil
+  end
+
+  it 'returns the checksum of the last finished frame' do
+    audit = run(3)
+
+    expect(audit.frame_checksum).to eq(audit.history.last)
+    expect(audit.history.size).to eq(3)
+  end
+
+  it 'detects an injected ordering difference at the frame it happened' do
+    swapped = run(10, order: ->(ids, frame) { frame == 6 ? ids.reverse : ids })
+
+    divergence = described_class.compare(run(10).history, swapped.history)
+
+    expect(divergence[:frame]).to eq(6)
+    expect(divergence[:expected]).not_to eq(divergence[:actual])
+  end
+
+  it 'treats -0.0 and 0.0 as the same value' do
+    zero = described_class.new
+    zero.record_sprite(1, {}, { x: 0.0 })
+    negative_zero = described_class.new
+    negative_zero.record_sprite(1, {}, { x: -0.0 })
+
+    expect(zero.finish_frame).to eq(negative_zero.finish_frame)
+  end
+
+  it 'hashes every sprite field that changes the frame' do
+    checksum = lambda do |sprite|
+      audit = described_class.new
+      audit.record_sprite(1, sprite, {})
+      audit.finish_frame
+    end
+
+    plain = checksum.call({})
+    [{ anchor_x: 0.0 }, { group: 'enemies' }, { mesh_mode: true }, { accessible_label: 'Play' }].each do |field|
+      expect(checksum.call(field)).not_to eq(plain), "#{field.keys.first} is not hashed"
+    end
+    expect(checksum.call({ accessible_label: 'Play' })).not_to eq(checksum.call({ accessible_label: 'Quit' }))
+  end
+
+  it 'reports a length mismatch where the shorter stream ends' do
+    divergence = described_class.compare(run(5).history, run(3).history)
+
+    expect(divergence).to eq(frame: 3, expected: run(5).history[3], actual: nil)
+  end
+
+  it 'compares against a recording as frames are finished' do
+    recorded = run(5).history
+    audit = run(3, order: ->(ids, frame) { frame == 2 ? ids.reverse : ids })
+
+    expect(audit.compare_to(recorded)[:frame]).to eq(2)
+    expect(audit.divergence[:frame]).to eq(2)
+  end
+
+  it 'checks each frame finished after compare_to against its recorded checksum' do
+    recorded = run(5).history
+    audit = run(2)
+    expect(audit.compare_to(recorded)).to be_nil
+
+    audit.record_sprite(1, { color_r: 0.5 }, { x: 99.0, y: 0.0 })
+    audit.finish_frame
+
+    expect(audit.divergence).to eq(frame: 2, expected: recorded[2], actual: audit.frame_checksum)
+  end
+
+  it 'does not count finishing past the end of the recording as a divergence' do
+    audit = run(3)
+    audit.compare_to(run(3).history)
+    audit.finish_frame
+
+    expect(audit.divergence).to be_nil
+  end
+
+  it 'round-trips a stream through dump and parse' do
+    history = run(4).history
+    file = Tempfile.new(['checksums', '.txt'])
+    file.write(described_class.dump(history))
+    file.close
+
+    expect(described_class.parse(File.read(file.path))).to eq(history)
+  ensure
+    file&.unlink
+  end
+
+  it 'rejects lines that are not hex checksums' do
+    expect { described_class.parse("00ff\nnot-a-checksum\n") }.to raise_error(ArgumentError)
+  end
+end
@@ -0,0 +1,114 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::DragGestures do
+  subject(:gestures) { described_class.new }
+
+  it 'keeps the grab offset while following the pointer' do
+    gestures.enable(1)
+
+    expect(gestures.press(1, [10.0, 20.0], [12.0, 25.0])).to be(true)
+    expect(gestures.dragging?).to be(true)
+    expect(gestures.held_id).to eq(1)
+    expect(gestures.drag_to([52.0, 5.0])).to eq([50.0, 0.0])
+    expect(gestures.position).to eq([50.0, 0.0])
+  end
+
+  it 'ignores presses on sprites that are not draggable' do
+    expect(gestures.press(1, [0.0, 0.0], [0.0, 0.0])).to be(false)
+    expect(gestures.drag_to([5.0, 5.0])).to be_nil
+    expect(gestures.release([5.0, 5.0])).to be_nil
+  end
+
+  it 'keeps the first sprite while a drag is in progress' do
+    gestures.enable(1)
+    gestures.enable(2)
+    gestures.press(1, [0.0, 0.0], [0.0, 0.0])
+
+    expect(gestures.press(2, [5.0, 5.0], [5.0, 5.0])).to be(false)
+    expect(gestures.held_id).to eq(1)
+  end
+
+  it 'clamps the sprite to its constraint rectangle' do
+    gestures.enable(1, { constrain_to: [0, 0, 100, 50] })
+    gestures.press(1, [10.0, 10.0], [10.0, 10.0])
+
+    expect(gestures.drag_to([150.0, -20.0])).to eq([100.0, 0.0])
+    expect(gestures.drag_to([40.0, 30.0])).to eq([40.0, 30.0])
+
+    event = gestures.release([-10.0, 80.0])
+    expect(event[:dropped_at]).to eq([0.0, 50.0])
+    expect(event[:position]).to eq([0.0, 50.0])
+  end
+
+  it 'reports where a sprite was dropped and what it landed on' do
+    gestures.enable(1)
+    gestures.press(1, [0.0, 0.0], [0.0, 0.0])
+    gestures.drag_to([20.0, 10.0])
+
+    event = gestures.release([30.0, 15.0], { drop_target: 7, hit_region: true })
+
+    expect(event).to eq(
+      id: 1, position: [30.0, 15.0], dropped_at: [30.0, 15.0], origin: [0.0, 0.0],
+      drop_target: 7, hit_region: true, snapped_back: false
+    )
+    expect(gestures.dragging?).to be(false)
+    expect(gestures.drain_events).to eq([event])
+    expect(gestures.drain_events).to be_empty
+  end
+
+  it 'returns a snap-back sprite to its origin' do
+    gestures.enable(1, { snap_back: true })
+    gestures.press(1, [5.0, 5.0], [5.0, 5.0])
+
+    event = gestures.release([60.0, 40.0])
+
+    expect(event[:snapped_back]).to be(true)
+    expect(event[:dropped_at]).to eq([60.0, 40.0])
+    expect(event[:position]).to eq([5.0, 5.0])
+    expect(event[:drop_target]).to be_nil
+  end
+
+  it 'releases at the last position when the pointer left the window' do
+    gestures.enable(1)
+    gestures.press(1, [0.0, 0.0], [0.0, 0.0])
+    gestures.drag_to([8.0, 9.0])
+
+    expect(gestures.release(nil)[:dropped_at]).to eq([8.0, 9.0])
+  end
+
+  it 'does not report the held sprite as its own drop target' do
+    gestures.enable(1)
+    gestures.press(1, [0.0, 0.0], [0.0, 0.0])
+
+    event = gestures.release([1.0, 1.0], { drop_target: 1 })
+
+    expect(event[:drop_target]).to be_nil
+    expect(event[:hit_region]).to be(false)
+  end
+
+  it 'ends a drag without an event when its sprite is disabled' do
+    gestures.enable(1)
+    gestures.press(1, [0.0, 0.0], [0.0, 0.0])
+
+    expect(gestures.disable(1)).to be(true)
+    expect(gestures.dragging?).to be(false)
+    expect(gestures.enabled?(1)).to be(false)
+    expect(gestures.drain_events).to be_empty
+    expect(gestures.disable(1)).to be(false)
+  end
+
+  it 'applies a new config to the held sprite' do
+    gestures.enable(1)
+    gestures.press(1, [0.0, 0.0], [0.0, 0.0])
+    gestures.enable(1, { constrain_to: [0, 0, 10, 10] })
+
+    expect(gestures.drag_to([30.0, 30.0])).to eq([10.0, 10.0])
+  end
+
+  it 'rejects invalid constraints and points' do
+    expect { gestures.enable(1, { constrain_to: [0, 0, -5, 5] }) }.to raise_error(ArgumentError, /constrain_to/)
+    expect { gestures.enable(1, { constrain_to: [0, 0, 5] }) }.to raise_error(ArgumentError, /constrain_to/)
+    gestures.enable(1)
+    expect { gestures.press(1, [0.0, 0.0], [Float::NAN, 0.0]) }.to raise_error(ArgumentError, /pointer/)
+  end
+end
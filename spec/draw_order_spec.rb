@@ -0,0 +1,199 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::DrawOrder do
+  subject(:order) { described_class.new }
+
+  def changes
+    order.take_changes.to_h
+  end
+
+  it 'keeps each layer in creation order until it is reordered' do
+    [1, 2, 3].each { |id| order.insert(id, 0) }
+    order.insert(4, 1)
+
+    expect(order.layer_ids(0)).to eq([1, 2, 3])
+    expect(order.index(3)).to eq(2)
+    expect(order.index(4)).to eq(0)
+    expect(order.ordered?(0)).to be(false)
+    expect(order.changes?).to be(false)
+  end
+
+  it 'returns false for an entity already on the layer' do
+    expect(order.insert(1, 0)).to be(true)
+    expect(order.insert(1, 0)).to be(false)
+  end
+
+  it 'moves an entity to the front or back of its layer' do
+    [1, 2, 3].each { |id| order.insert(id, 0) }
+
+    order.bring_to_front(1)
+    expect(order.layer_ids(0)).to eq([2, 3, 1])
+
+    order.send_to_back(3)
+    expect(order.layer_ids(0)).to eq([3, 2, 1])
+    expect(order.ordered?(0)).to be(true)
+  end
+
+  it 'clamps an explicit index to the layer' do
+    [1, 2, 3].each { |id| order.insert(id, 0) }
+
+    order.set_index(3, 0)
+    expect(order.layer_ids(0)).to eq([3, 1, 2])
+
+    order.set_index(3, 99)
+    expect(order.layer_ids(0)).to eq([1, 2, 3])
+  end
+
+  it 'ignores entities it does not track' do
+    expect(order.bring_to_front(9)).to be(false)
+    expect(order.index(9)).to be_nil
+    expect(order.remove(9)).to be(false)
+  end
+
+  it 'maps list positions to rising offsets' do
+    [1, 2, 3].each { |id| order.insert(id, 0) }
+    order.bring_to_front(1)
+
+    expect(changes.keys).to contain_exactly(3, 1)
+    expect(order.offset(2)).to eq(0.0)
+    expect(order.offset(2)).to be < order.offset(3)
+    expect(order.offset(3)).to be < order.offset(1)
+  end
+
+  it 'only reports entities whose offset moved' do
+    [1, 2, 3, 4].each { |id| order.insert(id, 0) }
+    order.bring_to_front(4)
+    changes
+
+    order.set_index(3, 1)
+    expect(changes.keys).to contain_exactly(2, 3)
+  end
+
+  it 'batches several moves into one reassignment' do
+    [1, 2, 3].each { |id| order.insert(id, 0) }
+    order.bring_to_front(1)
+    changes
+
+    order.bring_to_front(2)
+    order.bring_to_front(3)
+    order.send_to_back(1)
+
+    expect(order.layer_ids(0)).to eq([1, 2, 3])
+    expect(changes.keys).to contain_exactly(1, 2, 3)
+    expect(order.take_changes).to eq([])
+    expect(order.offset(1)).to eq(0.0)
+    expect(order.offset(1)).to be < order.offset(2)
+    expect(order.offset(2)).to be < order.offset(3)
+  end
+
+  it 'leaves layers it did not reorder alone' do
+    [1, 2].each { |id| order.insert(id, 0) }
+    [3, 4].each { |id| order.insert(id, 1) }
+    order.bring_to_front(3)
+
+    expect(changes.keys).to contain_exactly(3)
+    expect(order.offset(1)).to eq(0.0)
+    expect(order.offset(2)).to eq(0.0)
+  end
+
+  it 'puts new entities on top of a reordered layer' do
+    [1, 2].each { |id| order.insert(id, 0) }
+    order.send_to_back(2)
+    order.insert(3, 0)
+
+    expect(order.layer_ids(0)).to eq([2, 1, 3])
+  end
+
+  it 'closes the gap when an entity is removed' do
+    [1, 2, 3].each { |id| order.insert(id, 0) }
+    order.send_to_back(3)
+    changes
+    step = order.offset(1)
+
+    order.remove(3)
+    deltas = changes
+
+    expect(order.layer_ids(0)).to eq([1, 2])
+    expect(deltas.keys).to contain_exactly(1, 2)
+    expect(deltas.values).to all(be_within(1e-6).of(-step))
+  end
+
+  it 'resets the order of an entity moving to another layer to the top of that layer' do
+    [1, 2].each { |id| order.insert(id, 0) }
+    [3, 4].each { |id| order.insert(id, 1) }
+    order.send_to_back(1)
+    order.bring_to_front(3)
+    changes
+    offset = order.offset(1)
+
+    order.insert(1, 1)
+
+    expect(order.layer(1)).to eq(1)
+    expect(order.layer_ids(1)).to eq([4, 3, 1])
+    expect(order.layer_ids(0)).to eq([2])
+    expect(changes[1]).to be_within(1e-6).of(order.offset(1) - offset)
+  end
+
+  it 'drops the offset of an entity moving to a layer that was never reordered' do
+    [1, 2].each { |id| order.insert(id, 0) }
+    order.send_to_back(2)
+    changes
+    offset = order.offset(1)
+
+    order.insert(1, 5)
+
+    expect(changes[1]).to eq(-offset)
+    expect(order.offset(1)).to eq(0.0)
+  end
+
+  it 'stays consistent with many entities churning order every frame' do
+    ids = (1..200).to_a
+    ids.each { |id| order.insert(id, id % 3) }
+    applied = Hash.new(0.0)
+    random = Random.new(7)
+
+    30.times do
+      20.times do
+        id = ids.sample(random: random)
+        case random.rand(4)
+        when 0 then order.bring_to_front(id)
+        when 1 then order.send_to_back(id)
+        when 2 then order.set_index(id, random.rand(80))
+        else order.insert(id, random.rand(3))
+        end
+      end
+      order.take_changes.each { |id, delta| applied[id] += delta }
+
+      (0..2).each do |layer|
+        layer_ids = order.layer_ids(layer)
+        layer_ids.each_with_index { |id, index| expect(order.index(id)).to eq(index) }
+        offsets = layer_ids.map { |id| order.offset(id) }
+        expect(offsets).to eq(offsets.sort) if order.ordered?(layer)
+        expect(offsets.uniq.length).to eq(offsets.length) if order.ordered?(layer)
+      end
+      ids.each { |id| expect(applied[id]).to be_within(1e-4).of(order.offset(id)) }
+    end
+
+    expect(order.size).to eq(200)
+  end
+end
+
+RSpec.describe Bevy::RenderApp, :gpu do
+  it 'reorders synced sprites within their layer' do
+    app = described_class.new(headless: true, width: 32.0, height: 32.0, frames: 4)
+    sprite = Bevy::Sprite.new(custom_size: Bevy::Vec2.new(4.0, 4.0)).to_sync_hash
+    transform = Bevy::Transform.new.to_sync_hash
+    orders = []
+
+    app.run do
+      [1, 2, 3].each { |id| app.sync_sprite(id, sprite, transform) } if app.frame_count.zero?
+      app.bring_to_front(1) if app.frame_count == 1
+      orders << [1, 2, 3].map { |id| app.draw_order(id) }
+    end
+
+    expect(orders[1]).to eq([0, 1, 2])
+    expect(orders.last).to eq([2, 0, 1])
+  ensure
+    app&.close
+  end
+end
@@ -0,0 +1,147 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::Ease do
+  let(:samples) { (0..100).map { |i| i / 100.0 } }
+
+  it 'lists every curve once' do
+    expect(described_class.names.size).to eq(31)
+    expect(described_class.names.uniq.size).to eq(31)
+    expect(described_class.names).to include(:linear, :quad_in, :circ_in_out, :back_out, :elastic_in_out, :bounce_in)
+  end
+
+  it 'hits both endpoints exactly for every curve' do
+    described_class.names.each do |name|
+      expect(described_class.apply(name, 0.0)).to eq(0.0), "#{name}(0)"
+      expect(described_class.apply(name, 1.0)).to eq(1.0), "#{name}(1)"
+    end
+  end
+
+  it 'clamps inputs outside 0..1 to the endpoints' do
+    described_class.names.each do |name|
+      expect(described_class.apply(name, -0.5)).to eq(0.0), "#{name}(-0.5)"
+      expect(described_class.apply(name, 1.5)).to eq(1.0), "#{name}(1.5)"
+    end
+  end
+
+  it 'never decreases for the polynomial, sine, expo and circ curves' do
+    monotonic = described_class.names.grep(/\A(linear|quad|cubic|quart|quint|sine|expo|circ)/)
+    expect(monotonic.size).to eq(22)
+
+    monotonic.each do |name|
+      values = samples.map { |t| described_class.apply(name, t) }
+      values.each_cons(2) do |a, b|
+        expect(b).to be >= a, "#{name} decreased from #{a} to #{b}"
+      end
+    end
+  end
+
+  it 'mirrors the in curve for the out curve' do
+    %i[quad cubic quart quint sine expo circ back bounce].each do |family|
+      samples.each do |t|
+        expect(described_class.apply(:"#{family}_out", t))
+          .to be_within(1e-5).of(1.0 - described_class.apply(:"#{family}_in", 1.0 - t))
+      end
+    end
+  end
+
+  it 'passes through the midpoint for the symmetric in_out curves' do
+    %i[quad cubic quart quint sine expo circ back elastic bounce].each do |family|
+      expect(described_class.apply(:"#{family}_in_out", 0.5)).to be_within(1e-5).of(0.5)
+    end
+  end
+
+  it 'matches the textbook values' do
+    expect(described_class.apply(:linear, 0.25)).to eq(0.25)
+    expect(described_class.apply(:quad_in, 0.5)).to be_within(1e-6).of(0.25)
+    expect(described_class.apply(:cubic_out, 0.5)).to be_within(1e-6).of(0.875)
+    expect(described_class.apply(:quart_in, 0.5)).to be_within(1e-6).of(0.0625)
+    expect(described_class.apply(:quint_in, 0.5)).to be_within(1e-6).of(0.03125)
+    expect(described_class.apply(:sine_in_out, 0.25)).to be_within(1e-6).of((1.0 - Math.cos(Math::PI / 4.0)) / 2.0)
+    expect(described_class.apply(:expo_in, 0.5)).to be_within(1e-6).of(2.0**-5)
+    expect(described_class.apply(:circ_in, 0.6)).to be_within(1e-6).of(0.2)
+    expect(described_class.apply(:bounce_out, 0.5)).to be_within(1e-5).of(0.765625)
+  end
+
+  describe 'back' do
+    it 'dips below 0 going in and overshoots 1 going out' do
+      expect(samples.map { |t| described_class.apply(:back_in, t) }.min).to be < 0.0
+      expect(samples.map { |t| described_class.apply(:back_out, t) }.max).to be > 1.0
+    end
+
+    it 'dips further with a larger overshoot' do
+      default_dip = samples.map { |t| described_class.apply(:back_in, t) }.min
+      larger_dip = samples.map { |t| described_class.apply(:back_in, t, overshoot: 3.0) }.min
+      expect(larger_dip).to be < default_dip
+    end
+
+    it 'uses the standard overshoot by default' do
+      expect(described_class.apply(:back_in, 0.3)).to eq(described_class.apply(:back_in, 0.3, overshoot: 1.70158))
+    end
+
+    it 'reduces to cubic with no overshoot' do
+      samples.each do |t|
+        expect(described_class.apply(:back_in, t, overshoot: 0.0))
+          .to be_within(1e-6).of(described_class.apply(:cubic_in, t))
+      end
+    end
+
+    it 'keeps its endpoints for any overshoot' do
+      [0.0, 0.5, 4.0].each do |overshoot|
+        %i[back_in back_out back_in_out].each do |name|
+          expect(described_class.apply(name, 0.0, overshoot: overshoot)).to eq(0.0)
+          expect(described_class.apply(name, 1.0, overshoot: overshoot)).to eq(1.0)
+        end
+      end
+    end
+  end
+
+  describe 'elastic' do
+    it 'oscillates around the target' do
+      out = samples.map { |t| described_class.apply(:elastic_out, t) }
+      expect(out.max).to be > 1.0
+      expect(out.each_cons(2).count { |a, b| (a - 1.0) * (b - 1.0) < 0.0 }).to be >= 2
+    end
+
+    it 'changes shape with the period' do
+      expect(described_class.apply(:elastic_out, 0.2, period: 0.6))
+        .not_to be_within(1e-3).of(described_class.apply(:elastic_out, 0.2))
+      expect(described_class.apply(:elastic_out, 0.2)).to eq(described_class.apply(:elastic_out, 0.2, period: 0.3))
+    end
+
+    it 'keeps its endpoints for any period' do
+      [0.1, 0.3, 1.0].each do |period|
+        %i[elastic_in elastic_out elastic_in_out].each do |name|
+          expect(described_class.apply(name, 0.0, period: period)).to eq(0.0)
+          expect(described_class.apply(name, 1.0, period: period)).to eq(1.0)
+        end
+      end
+    end
+
+    it 'rejects a period that is not positive' do
+      expect { described_class.apply(:elastic_in, 0.5, period: 0.0) }.to raise_error(ArgumentError, /period/)
+    end
+  end
+
+  it 'stays within 0..1 for bounce' do
+    %i[bounce_in bounce_out bounce_in_out].each do |name|
+      expect(samples.map { |t| described_class.apply(name, t) }).to all(be_between(0.0, 1.0))
+    end
+  end
+
+  it 'rejects parameters the curve does not take' do
+    expect { described_class.apply(:quad_in, 0.5, overshoot: 2.0) }.to raise_error(ArgumentError, /overshoot/)
+    expect { described_class.apply(:back_in, 0.5, period: 0.5) }.to raise_error(ArgumentError, /period/)
+  end
+
+  it 'accepts strings and the older ease_in_quad style names' do
+    expect(described_class.apply('quad_in', 0.5)).to eq(described_class.apply(:quad_in, 0.5))
+    expect(described_class.apply(:ease_in_quad, 0.5)).to eq(described_class.apply(:quad_in, 0.5))
+    expect(described_class.apply(:ease_in_out_bounce, 0.3)).to eq(described_class.apply(:bounce_in_out, 0.3))
+    expect(described_class.apply(:ease_out, 0.5)).to eq(described_class.apply(:cubic_out, 0.5))
+  end
+
+  it 'raises for unknown names, listing the valid ones' do
+    expect { described_class.apply(:wobble, 0.5) }
+      .to raise_error(ArgumentError, /unknown easing: wobble \(expected one of linear, quad_in, .*bounce_in_out\)/)
+  end
+end
@@ -0,0 +1,57 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::EventBus do
+  let(:bus) { described_class.new.tap { |b| b.register('damage') } }
+
+  it 'returns sent events as hashes with symbol keys' do
+    bus.send('damage', target_id: 1, amount: 10.5, 'source' => :trap, tags: ['fire'])
+
+    expect(bus.read('damage')).to eq([{ target_id: 1, amount: 10.5, source: :trap, tags: ['fire'] }])
+  end
+
+  it 'reads each event once, oldest first' do
+    bus.send('damage', amount: 1)
+    bus.send('damage', amount: 2)
+
+    expect(bus.unread_count('damage')).to eq(2)
+    expect(bus.read('damage').map { |event| event[:amount] }).to eq([1, 2])
+    expect(bus.read('damage')).to be_empty
+
+    bus.send('damage', amount: 3)
+
+    expect(bus.read('damage')).to eq([{ amount: 3 }])
+  end
+
+  it 'keeps event types apart' do
+    bus.register('heal')
+    bus.send('heal', amount: 5)
+
+    expect(bus.read('damage')).to be_empty
+    expect(bus.read('heal')).to eq([{ amount: 5 }])
+  end
+
+  it 'keeps queued events when registering again' do
+    bus.send('damage', amount: 1)
+    bus.register('damage')
+
+    expect(bus.unread_count('damage')).to eq(1)
+  end
+
+  it 'clears queued events' do
+    bus.send('damage', amount: 1)
+    bus.clear('damage')
+
+    expect(bus.unread_count('damage')).to eq(0)
+    expect(bus.read('damage')).to be_empty
+  end
+
+  it 'rejects unregistered types' do
+    expect(bus.registered?('jump')).to be false
+    expect { bus.send('jump', height: 2) }.to raise_error(ArgumentError, /jump/)
+    expect { bus.read('jump') }.to raise_error(ArgumentError, /jump/)
+  end
+
+  it 'rejects values it cannot carry' do
+    expect { bus.send('damage', source: Object.new) }.to raise_error(TypeError)
+  end
+end
@@ -0,0 +1,122 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::FloatingTexts do
+  subject(:texts) { described_class.new }
+
+  def spawn(x = 0.0, y = 0.0, text = '12', **options)
+    texts.spawn(x, y, text, options)
+  end
+
+  it 'uses ids that cannot collide with entity or bound-world ids' do
+    id = spawn
+
+    expect(id & (1 << 61)).not_to eq(0)
+    expect(id & (1 << 62)).to eq(0)
+    expect(spawn).not_to eq(id)
+  end
+
+  it 'rises with an ease-out and fades to nothing over its duration' do
+    id = spawn(10.0, 20.0, '5', rise: 40.0, duration: 1.0)
+
+    start = texts.advance(0.0).first
+    expect(start).to include(id: id, x: 10.0, y: 20.0, alpha: 1.0)
+
+    halfway = texts.advance(0.5).first
+    expect(halfway[:y]).to be_within(1e-4).of(20.0 + (40.0 * 0.75))
+    expect(halfway[:alpha]).to be_within(1e-4).of(0.75)
+
+    late = texts.advance(0.4).first
+    expect(late[:y]).to be > halfway[:y]
+    expect(late[:alpha]).to be < halfway[:alpha]
+  end
+
+  it 'scales the fade by the color alpha' do
+    spawn(color_a: 0.5, duration: 1.0)
+
+    expect(texts.advance(0.0).first[:alpha]).to be_within(1e-4).of(0.5)
+  end
+
+  it 'removes a text once its duration has passed' do
+    id = spawn(duration: 0.8)
+
+    expect(texts.advance(0.5).map { |frame| frame[:id] }).to eq([id])
+    expect(texts.drain_removed).to eq([])
+    expect(texts.advance(0.3)).to eq([])
+    expect(texts.drain_removed).to eq([id])
+    expect(texts.drain_removed).to eq([])
+    expect(texts.size).to eq(0)
+  end
+
+  it 'stacks texts spawned at the same spot within the window' do
+    first = spawn(100.0, 50.0, font_size: 18.0)
+    second = spawn(103.0, 52.0, font_size: 18.0)
+    third = spawn(100.0, 50.0, font_size: 18.0)
+
+    expect([first, second, third].map { |id| texts.slot(id) }).to eq([0, 1, 2])
+    frames = texts.advance(0.0).to_h { |frame| [frame[:id], frame[:y]] }
+    expect(frames[second] - frames[first]).to be_within(1e-4).of(2.0 + 18.0)
+    expect(frames[third] - frames[first]).to be_within(1e-4).of(36.0)
+  end
+
+  it 'does not stack texts far apart' do
+    spawn(0.0, 0.0)
+    other = spawn(50.0, 0.0)
+
+    expect(texts.slot(other)).to eq(0)
+  end
+
+  it 'stops stacking on a text once it is older than the window' do
+    spawn
+    texts.advance(0.35)
+
+    expect(texts.slot(spawn)).to eq(0)
+  end
+
+  it 'reuses the lowest free slot' do
+    bottom = spawn(duration: 0.1)
+    middle = spawn(duration: 2.0)
+    texts.advance(0.15)
+
+    expect(texts.drain_removed).to eq([bottom])
+    expect(texts.slot(middle)).to eq(1)
+    expect(texts.slot(spawn)).to eq(0)
+    expect(texts.slot(spawn)).to eq(2)
+  end
+
+  it 'removes the oldest texts past the cap' do
+    texts.cap = 3
+    ids = Array.new(5) { |i| spawn(i * 100.0, 0.0) }
+
+    expect(texts.size).to eq(3)
+    expect(texts.drain_removed).to eq(ids.first(2))
+    expect(texts.advance(0.0).map { |frame| frame[:id] }).to eq(ids.last(3))
+  end
+
+  it 'evicts right away when the cap is lowered' do
+    ids = Array.new(4) { spawn }
+
+    texts.cap = 1
+
+    expect(texts.drain_removed).to eq(ids.first(3))
+    expect(texts.slot(ids.last)).to eq(3)
+  end
+
+  it 'defaults the cap to 64' do
+    expect(texts.cap).to eq(64)
+  end
+
+  it 'clears every text' do
+    ids = [spawn, spawn]
+
+    texts.clear
+
+    expect(texts.size).to eq(0)
+    expect(texts.drain_removed).to eq(ids)
+  end
+
+  it 'rejects a non-positive duration or font size' do
+    expect { spawn(duration: 0.0) }.to raise_error(ArgumentError, /duration/)
+    expect { spawn(font_size: -1.0) }.to raise_error(ArgumentError, /font_size/)
+    expect(texts.size).to eq(0)
+  end
+end
@@ -0,0 +1,103 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::Geometry do
+  def triangles(points)
+    described_class.triangulate(points).each_slice(6).map { |flat| flat.each_slice(2).to_a }
+  end
+
+  def area(triangle)
+    (a, b, c) = triangle
+    ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) / 2.0
+  end
+
+  def total_area(points)
+    triangles(points).sum { |triangle| area(triangle) }
+  end
+
+  describe '.triangulate' do
+    it 'returns a flat array of triangle vertex coordinates' do
+      flat = described_class.triangulate([[0, 0], [1, 0], [0, 1]])
+
+      expect(flat.size).to eq(6)
+      expect(flat.each_slice(2).to_a).to contain_exactly([0.0, 0.0], [1.0, 0.0], [0.0, 1.0])
+    end
+
+    it 'splits a convex polygon into n - 2 triangles' do
+      hexagon = Array.new(6) { |i| [Math.cos(i * Math::PI / 3), Math.sin(i * Math::PI / 3)] }
+
+      expect(triangles(hexagon).size).to eq(4)
+      expect(total_area(hexagon)).to be_within(1e-5).of(3 * Math.sqrt(3) / 2)
+    end
+
+    it 'covers a concave polygon exactly' do
+      l_shape = [[0, 0], [2, 0], [2, 1], [1, 1], [1, 2], [0, 2]]
+
+      expect(triangles(l_shape).size).to eq(4)
+      expect(total_area(l_shape)).to be_within(1e-6).of(3.0)
+    end
+
+    it 'keeps every triangle inside a comb-shaped polygon' do
+      comb = [[0, 0], [5, 0], [5, 3], [4, 3], [4, 1], [3, 1], [3, 3], [2, 3], [2, 1], [1, 1], [1, 3], [0, 3]]
+
+      expect(total_area(comb)).to be_within(1e-6).of(11.0)
+      triangles(comb).each do |triangle|
+        centroid = triangle.transpose.map { |axis| axis.sum / 3.0 }
+        inside_notch = centroid[1] > 1 && [(1..2), (3..4)].any? { |notch| notch.cover?(centroid[0]) }
+        expect(inside_notch).to be false
+      end
+    end
+
+    it 'winds triangles counter-clockwise for either input winding' do
+      clockwise = [[0, 0], [0, 1], [1, 1], [1, 0]]
+
+      expect(triangles(clockwise).map { |triangle| area(triangle) }).to all(be > 0)
+      expect(total_area(clockwise)).to be_within(1e-6).of(1.0)
+    end
+
+    it 'skips points on straight edges instead of emitting slivers' do
+      square = [[0, 0], [1, 0], [2, 0], [2, 2], [1, 2], [0, 2]]
+
+      expect(triangles(square).size).to eq(2)
+      expect(triangles(square).map { |triangle| area(triangle) }).to all(be > 0)
+    end
+
+    it 'rejects fewer than three points' do
+      expect { described_class.triangulate([[0, 0], [1, 0]]) }
+        .to raise_error(ArgumentError, /at least 3 points, got 2/)
+    end
+
+    it 'rejects collinear points' do
+      expect { described_class.triangulate([[0, 0], [1, 1], [2, 2]]) }
+        .to raise_error(ArgumentError, /degenerate/)
+    end
+
+    it 'rejects repeated points' do
+      expect { described_class.triangulate([[0, 0], [1, 0], [0, 0], [0, 1]]) }
+        .to raise_error(ArgumentError, /points 0 and 2 are the same point/)
+    end
+
+    it 'rejects crossing edges' do
+      bowtie = [[0, 0], [1, 1], [1, 0], [0, 1]]
+
+      expect { described_class.triangulate(bowtie) }.to raise_error(ArgumentError, /self-intersecting/)
+    end
+
+    it 'rejects edges that fold back onto each other' do
+      spike = [[0, 0], [2, 0], [1, 0], [1, 1]]
+
+      expect { described_class.triangulate(spike) }.to raise_error(ArgumentError, /self-intersecting/)
+    end
+
+    it 'rejects non-finite coordinates' do
+      expect { described_class.triangulate([[0, 0], [Float::NAN, 0], [0, 1]]) }
+        .to raise_error(ArgumentError, /point 1 is not finite/)
+    end
+
+    it 'does not support holes' do
+      outer = [[0, 0], [4, 0], [4, 4], [0, 4]]
+      hole = [[1, 1], [1, 2], [2, 2]]
+
+      expect { described_class.triangulate([outer, hole]) }.to raise_error(ArgumentError, /holes are not supported/)
+    end
+  end
+end
@@ -0,0 +1,114 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::GroupThrottle do
+  subject(:throttle) { described_class.new }
+
+  it 'is due every frame for groups without a divisor and for ungrouped sprites' do
+    expect((0..5).map { |frame| throttle.due?('enemies', frame) }).to all(be(true))
+    expect(throttle.due?(nil, 3)).to be(true)
+    expect(throttle.divisor('enemies')).to eq(1)
+  end
+
+  it 'is due on every Nth frame counted from the frame the divisor was set' do
+    throttle.set_divisor('enemies', 3, 10)
+
+    due = (10..17).select { |frame| throttle.due?('enemies', frame) }
+    expect(due).to eq([10, 13, 16])
+    expect(throttle.divisor('enemies')).to eq(3)
+  end
+
+  it 'lists the throttled groups due on a frame' do
+    throttle.set_divisor('enemies', 2, 0)
+    throttle.set_divisor('props', 3, 0)
+
+    expect(throttle.due_groups(0)).to eq(%w[enemies props])
+    expect(throttle.due_groups(2)).to eq(['enemies'])
+    expect(throttle.due_groups(3)).to eq(['props'])
+    expect(throttle.due_groups(1)).to eq([])
+  end
+
+  it 'passes syncs straight through on due frames' do
+    throttle.set_divisor('enemies', 2, 0)
+    throttle.sync(1, 'enemies', 5.0, 6.0)
+
+    expect(throttle.flush(0)).to eq([[1, 5.0, 6.0]])
+    expect(throttle.held_count).to eq(0)
+  end
+
+  it 'coalesces held syncs to the latest values for the next due frame' do
+    throttle.set_divisor('enemies', 3, 0)
+    throttle.flush(0)
+
+    throttle.sync(1, 'enemies', 1.0, 0.0)
+    expect(throttle.flush(1)).to eq([])
+    throttle.sync(1, 'enemies', 2.0, 0.0)
+    throttle.sync(1, 'enemies', 3.0, 0.0)
+    expect(throttle.flush(2)).to eq([])
+    expect(throttle.held_count).to eq(1)
+
+    expect(throttle.flush(3)).to eq([[1, 3.0, 0.0]])
+    expect(throttle.held_count).to eq(0)
+  end
+
+  it 'lets a sync on a due frame replace the held one' do
+    throttle.set_divisor('enemies', 2, 0)
+    throttle.sync(1, 'enemies', 1.0, 0.0)
+    throttle.flush(1)
+
+    throttle.sync(1, 'enemies', 9.0, 0.0)
+    expect(throttle.flush(2)).to eq([[1, 9.0, 0.0]])
+  end
+
+  it 'never holds back sprites in other groups' do
+    throttle.set_divisor('enemies', 4, 0)
+    throttle.sync(1, 'enemies', 1.0, 0.0)
+    throttle.sync(2, 'ui', 2.0, 0.0)
+    throttle.sync(3, nil, 3.0, 0.0)
+
+    expect(throttle.flush(1)).to eq([[2, 2.0, 0.0], [3, 3.0, 0.0]])
+  end
+
+  it 'drops the held sync of a removed sprite' do
+    throttle.set_divisor('enemies', 2, 0)
+    throttle.sync(1, 'enemies', 1.0, 0.0)
+    throttle.flush(1)
+
+    throttle.remove(1)
+    expect(throttle.flush(1)).to eq([])
+    expect(throttle.flush(2)).to eq([])
+    expect(throttle.held_count).to eq(0)
+  end
+
+  it 'reschedules from the frame a divisor changes at runtime' do
+    throttle.set_divisor('enemies', 4, 0)
+    throttle.sync(1, 'enemies', 1.0, 0.0)
+    throttle.flush(1)
+
+    throttle.set_divisor('enemies', 2, 2)
+
+    expect(throttle.flush(2)).to eq([[1, 1.0, 0.0]])
+    due = (2..8).select { |frame| throttle.due?('enemies', frame) }
+    expect(due).to eq([2, 4, 6, 8])
+  end
+
+  it 'releases held syncs once the divisor goes back to 1' do
+    throttle.set_divisor('enemies', 5, 0)
+    throttle.sync(1, 'enemies', 4.0, 0.0)
+    throttle.flush(1)
+
+    throttle.set_divisor('enemies', 1, 2)
+
+    expect(throttle.due_groups(2)).to eq([])
+    expect(throttle.flush(2)).to eq([[1, 4.0, 0.0]])
+  end
+
+  it 'releases the sync of a sprite that moves out of a throttled group' do
+    throttle.set_divisor('enemies', 3, 0)
+    throttle.sync(1, 'enemies', 1.0, 0.0)
+    throttle.flush(1)
+
+    throttle.sync(1, 'allies', 2.0, 0.0)
+    expect(throttle.flush(2)).to eq([[1, 2.0, 0.0]])
+    expect(throttle.held_count).to eq(0)
+  end
+end
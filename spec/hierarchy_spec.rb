@@ -6,6 +6,11 @@ RSpec.describe Bevy::Parent do
       entity = double('entity')
       parent = described_class.new(entity)
       expect(parent.entity).to eq(entity)
+      expect(parent.on_parent_removed).to eq(:detach_keep_world)
+    end
+
+    it 'rejects an unknown removal policy' do
+      expect { described_class.new(nil, on_parent_removed: :keep) }.to raise_error(ArgumentError, /keep/)
     end
   end
 
@@ -289,6 +294,115 @@ RSpec.describe Bevy::TransformPropagation do
   end
 end
 
+RSpec.describe Bevy::GlobalTransform do
+  let(:parent) do
+    described_class.new(
+      translation: Bevy::Vec3.new(10.0, 5.0, 0.0),
+      rotation: Bevy::Quat.from_rotation_z(Math::PI / 2),
+      scale: Bevy::Vec3.new(2.0, 2.0, 1.0)
+    )
+  end
+
+  describe '#mul_transform' do
+    it 'scales, rotates and then offsets the local transform' do
+      global = parent.mul_transform(Bevy::Transform.new(translation: Bevy::Vec3.new(1.0, 0.0, 0.0)))
+
+      expect(global.translation.x).to be_within(1e-5).of(10.0)
+      expect(global.translation.y).to be_within(1e-5).of(7.0)
+      expect(global.scale.x).to eq(2.0)
+    end
+  end
+
+  describe '#relative_to' do
+    it 'inverts #mul_transform' do
+      local = Bevy::Transform.new(
+        translation: Bevy::Vec3.new(3.0, -4.0, 1.0),
+        rotation: Bevy::Quat.from_rotation_z(0.3),
+        scale: Bevy::Vec3.new(0.5, 1.5, 1.0)
+      )
+      restored = parent.mul_transform(local).relative_to(parent)
+
+      expect(restored.translation.to_a.zip(local.translation.to_a)).to all(satisfy { |a, b| (a - b).abs < 1e-5 })
+      expect(restored.rotation.to_a.zip(local.rotation.to_a)).to all(satisfy { |a, b| (a - b).abs < 1e-5 })
+      expect(restored.scale.to_a).to eq(local.scale.to_a)
+    end
+  end
+end
+
+RSpec.describe Bevy::ParentRemoval do
+  let(:world) { Bevy::World.new }
+
+  def spawn(x, y, parent: nil, scale: 1.0, policy: :detach_keep_world)
+    transform = Bevy::Transform.new(translation: Bevy::Vec3.new(x, y, 0.0), scale: Bevy::Vec3.new(scale, scale, 1.0))
+    return world.spawn_entity(transform) unless parent
+
+    world.spawn_entity(transform, Bevy::Parent.new(parent, on_parent_removed: policy)).tap do |child|
+      children = world.get_component(parent, Bevy::Children) rescue nil
+      children ? children.add(child) : world.insert_component(parent, Bevy::Children.new([child]))
+    end
+  end
+
+  def translation(entity)
+    world.get_component(entity, Bevy::Transform).translation.to_a.first(2)
+  end
+
+  def parent_of(entity)
+    world.get_component(entity, Bevy::Parent).entity
+  rescue Bevy::ComponentNotFoundError
+    nil
+  end
+
+  let(:root) { spawn(10.0, 0.0) }
+  let(:middle) { spawn(5.0, 0.0, parent: root, scale: 2.0) }
+
+  it 'detaches a child at its world position by default' do
+    leaf = spawn(1.0, 1.0, parent: middle)
+    world.despawn(middle)
+
+    expect(parent_of(leaf)).to be_nil
+    expect(translation(leaf)).to eq([17.0, 2.0])
+    expect(world.get_component(leaf, Bevy::Transform).scale.x).to eq(2.0)
+    expect(world.get_component(root, Bevy::Children).entities).to be_empty
+  end
+
+  it 'detaches a child keeping its local transform' do
+    leaf = spawn(1.0, 1.0, parent: middle, policy: :detach_keep_local)
+    world.despawn(middle)
+
+    expect(parent_of(leaf)).to be_nil
+    expect(translation(leaf)).to eq([1.0, 1.0])
+  end
+
+  it 'reparents a child to the grandparent without moving it' do
+    leaf = spawn(1.0, 1.0, parent: middle, policy: :reparent_up)
+    world.despawn(middle)
+
+    expect(parent_of(leaf).id).to eq(root.id)
+    expect(translation(leaf)).to eq([7.0, 2.0])
+    expect(world.get_component(root, Bevy::Children).entities.map(&:id)).to eq([leaf.id])
+    expect(Bevy::ParentRemoval.new(world).global_transform(leaf).translation.to_a.first(2)).to eq([17.0, 2.0])
+  end
+
+  it 'detaches a root-level child at its world position when there is no grandparent' do
+    child = spawn(1.0, 1.0, parent: root, policy: :reparent_up)
+    world.despawn(root)
+
+    expect(parent_of(child)).to be_nil
+    expect(translation(child)).to eq([11.0, 1.0])
+  end
+
+  it 'despawns a child and applies the grandchildren policies' do
+    leaf = spawn(1.0, 1.0, parent: middle, policy: :despawn)
+    kept = spawn(0.5, 0.0, parent: leaf)
+    world.despawn(middle)
+
+    expect(world.despawned_entity_ids).to include(middle.id, leaf.id)
+    expect(world.despawned_entity_ids).not_to include(kept.id)
+    expect(parent_of(kept)).to be_nil
+    expect(translation(kept)).to eq([18.0, 2.0])
+  end
+end
+
 RSpec.describe Bevy::DespawnRecursive do
   describe '.despawn' do
     it 'despawns entity and all descendants' do
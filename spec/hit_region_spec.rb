@@ -0,0 +1,90 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::HitRegions do
+  let(:regions) { described_class.new }
+
+  it 'hits a world space region by the pointer world position' do
+    regions.register(1, -10.0, -10.0, 20.0, 20.0)
+
+    expect(regions.hit_test(400.0, 300.0, 0.0, 0.0)).to eq(1)
+    expect(regions.hit_test(400.0, 300.0, 15.0, 0.0)).to be_nil
+  end
+
+  it 'hits a screen space region by the pointer window position' do
+    regions.register(1, 0.0, 0.0, 100.0, 40.0, { screen_space: true })
+
+    expect(regions.hit_test(50.0, 20.0, 9000.0, 9000.0)).to eq(1)
+    expect(regions.hit_test(50.0, 60.0, 50.0, 20.0)).to be_nil
+  end
+
+  it 'ignores world space regions when the pointer has no world position' do
+    regions.register(1, 0.0, 0.0, 10.0, 10.0)
+    regions.register(2, 0.0, 0.0, 10.0, 10.0, { screen_space: true })
+
+    expect(regions.hit_test(5.0, 5.0, nil, nil)).to eq(2)
+  end
+
+  it 'includes the minimum edges and excludes the maximum edges' do
+    regions.register(1, 0.0, 0.0, 10.0, 10.0)
+    regions.register(2, 10.0, 0.0, 10.0, 10.0)
+
+    expect(regions.hit_test(0.0, 0.0, 0.0, 0.0)).to eq(1)
+    expect(regions.hit_test(0.0, 0.0, 10.0, 5.0)).to eq(2)
+    expect(regions.hit_test(0.0, 0.0, 20.0, 5.0)).to be_nil
+  end
+
+  it 'picks the highest layer where regions overlap' do
+    regions.register(1, 0.0, 0.0, 10.0, 10.0, { layer: 5 })
+    regions.register(2, 0.0, 0.0, 10.0, 10.0, { layer: -1 })
+
+    expect(regions.hit_test(0.0, 0.0, 5.0, 5.0)).to eq(1)
+  end
+
+  it 'picks the latest registration within a layer' do
+    regions.register(1, 0.0, 0.0, 10.0, 10.0)
+    regions.register(2, 5.0, 5.0, 10.0, 10.0)
+
+    expect(regions.hit_test(0.0, 0.0, 7.0, 7.0)).to eq(2)
+    expect(regions.hit_test(0.0, 0.0, 2.0, 2.0)).to eq(1)
+  end
+
+  it 'orders screen and world space regions by layer alike' do
+    regions.register(1, 0.0, 0.0, 10.0, 10.0, { layer: 1 })
+    regions.register(2, 0.0, 0.0, 10.0, 10.0, { screen_space: true })
+
+    expect(regions.hit_test(5.0, 5.0, 5.0, 5.0)).to eq(1)
+  end
+
+  it 'keeps registration order and unchanged options when updating' do
+    regions.register(1, 0.0, 0.0, 10.0, 10.0, { layer: 2, screen_space: true })
+    regions.register(2, 0.0, 0.0, 10.0, 10.0, { layer: 2, screen_space: true })
+
+    expect(regions.update(1, 50.0, 50.0, 10.0, 10.0)).to be true
+    expect(regions.region(1)).to eq(x: 50.0, y: 50.0, width: 10.0, height: 10.0, layer: 2, screen_space: true)
+    expect(regions.update(1, 0.0, 0.0, 10.0, 10.0)).to be true
+    expect(regions.hit_test(5.0, 5.0, nil, nil)).to eq(2)
+    expect(regions.update(3, 0.0, 0.0, 1.0, 1.0)).to be false
+  end
+
+  it 'replaces a region registered again under the same id' do
+    regions.register(1, 0.0, 0.0, 10.0, 10.0)
+    regions.register(1, 100.0, 100.0, 10.0, 10.0)
+
+    expect(regions.size).to eq(1)
+    expect(regions.hit_test(0.0, 0.0, 5.0, 5.0)).to be_nil
+    expect(regions.hit_test(0.0, 0.0, 105.0, 105.0)).to eq(1)
+  end
+
+  it 'stops hitting removed regions' do
+    regions.register(1, 0.0, 0.0, 10.0, 10.0)
+
+    expect(regions.remove(1)).to be true
+    expect(regions.remove(1)).to be false
+    expect(regions.hit_test(0.0, 0.0, 5.0, 5.0)).to be_nil
+  end
+
+  it 'rejects negative sizes and non-finite coordinates' do
+    expect { regions.register(1, 0.0, 0.0, -1.0, 10.0) }.to raise_error(ArgumentError, /non-negative size/)
+    expect { regions.register(1, Float::NAN, 0.0, 1.0, 10.0) }.to raise_error(ArgumentError, /finite/)
+  end
+end
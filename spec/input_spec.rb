@@ -8,6 +8,22 @@ RSpec.describe Bevy::KeyCode do
     expect(Bevy::KeyCode::LEFT).to eq('ArrowLeft')
     expect(Bevy::KeyCode::F1).to eq('F1')
   end
+
+  it 'names every Bevy key code with a distinct uppercase render-loop token' do
+    names = Bevy::KeyCode.names
+
+    expect(names.size).to eq(199)
+    expect(names).to all(match(/\A[A-Z0-9][A-Z0-9_]*\z/))
+    expect(names.uniq).to eq(names)
+  end
+
+  it 'keeps the established tokens and covers the keys added to the mapping' do
+    expect(Bevy::KeyCode.names).to include(
+      'A', '0', 'SPACE', 'ENTER', 'UP', 'BRACKET_LEFT', 'SHIFT', 'CONTROL', 'ALT',
+      'F5', 'F24', 'NUMPAD_1', 'NUMPAD_ENTER', 'MINUS', 'QUOTE', 'BACKQUOTE', 'HOME', 'END',
+      'PAGE_UP', 'PAGE_DOWN', 'INSERT', 'CAPS_LOCK', 'SHIFT_LEFT', 'SUPER_RIGHT', 'UNIDENTIFIED'
+    )
+  end
 end
 
 RSpec.describe Bevy::KeyboardInput do
@@ -77,6 +93,38 @@ RSpec.describe Bevy::KeyboardInput do
   end
 end
 
+RSpec.describe Bevy::Internal::KeyHolds do
+  subject(:holds) { described_class.new }
+
+  it 'accumulates the hold time across frames' do
+    holds.frame(1.0, ['SPACE'])
+    expect(holds.held_duration('SPACE')).to eq(0.0)
+
+    holds.frame(1.25, ['SPACE'])
+    holds.frame(1.5, %w[SPACE A])
+
+    expect(holds.held_duration('SPACE')).to eq(0.5)
+    expect(holds.held_duration('A')).to eq(0.0)
+  end
+
+  it 'resets to 0.0 on release and restarts on the next press' do
+    holds.frame(0.0, ['SPACE'])
+    holds.frame(2.0, ['SPACE'])
+    holds.frame(2.5, [])
+    expect(holds.held_duration('SPACE')).to eq(0.0)
+
+    holds.frame(3.0, ['SPACE'])
+    holds.frame(3.5, ['SPACE'])
+    expect(holds.held_duration('SPACE')).to eq(0.5)
+  end
+
+  it 'returns 0.0 for a key that was never pressed' do
+    holds.frame(4.0, ['SPACE'])
+
+    expect(holds.held_duration('NOT_A_KEY')).to eq(0.0)
+  end
+end
+
 RSpec.describe Bevy::MouseButton do
   it 'defines mouse buttons' do
     expect(Bevy::MouseButton::LEFT).to eq('Left')
@@ -110,6 +158,49 @@ RSpec.describe Bevy::MouseInput do
       expect(mouse.delta.x).to eq(10.0)
       expect(mouse.delta.y).to eq(-5.0)
     end
+
+    it 'reports no movement for the first position' do
+      mouse.set_position(300.0, -40.0)
+
+      expect(mouse.delta.to_a).to eq([0.0, 0.0])
+    end
+
+    it 'reports no movement while the cursor stays put' do
+      mouse.set_position(5.0, 5.0)
+      mouse.set_position(5.0, 5.0)
+
+      expect(mouse.delta.to_a).to eq([0.0, 0.0])
+    end
+
+    it 'starts over after reset' do
+      mouse.set_position(5.0, 5.0)
+      mouse.reset
+      mouse.set_position(50.0, 50.0)
+
+      expect(mouse.delta.to_a).to eq([0.0, 0.0])
+    end
+  end
+
+  describe '#set_delta' do
+    it 'overrides the movement derived from positions' do
+      mouse.set_position(0.0, 0.0)
+      mouse.set_position(10.0, 0.0)
+      mouse.set_delta(3.0, -2.0)
+
+      expect(mouse.delta.to_a).to eq([3.0, -2.0])
+      expect(mouse.position.x).to eq(10.0)
+    end
+  end
+
+  describe '#reset_buttons' do
+    it 'releases buttons and keeps the position' do
+      mouse.press(Bevy::MouseButton::LEFT)
+      mouse.set_position(20.0, 30.0)
+      mouse.reset_buttons
+
+      expect(mouse.pressed?(Bevy::MouseButton::LEFT)).to be false
+      expect(mouse.position.to_a).to eq([20.0, 30.0])
+    end
   end
 
   describe '#set_scroll' do
@@ -146,6 +237,36 @@ RSpec.describe Bevy::MouseInput do
   end
 end
 
+RSpec.describe Bevy::StickCurve do
+  it 'ignores deflection inside the dead zone' do
+    expect(described_class.new(dead_zone: 0.2).apply(0.1, 0.1)).to eq([0.0, 0.0])
+  end
+
+  it 'reaches full speed at full deflection' do
+    x, y = described_class.new.apply(0.0, 1.0)
+    expect(x).to eq(0.0)
+    expect(y).to be_within(0.0001).of(1.0)
+  end
+
+  it 'keeps small deflections slow and precise' do
+    curve = described_class.new(dead_zone: 0.15, exponent: 2.0)
+    x, = curve.apply(0.5, 0.0)
+    expect(x).to be_within(0.0001).of((0.35 / 0.85)**2)
+    expect(x).to be < described_class.new(dead_zone: 0.15, exponent: 1.0).apply(0.5, 0.0).first
+  end
+
+  it 'preserves the stick direction' do
+    result = described_class.new(exponent: 1.5).apply_vec2(Bevy::Vec2.new(-0.6, 0.6))
+    expect(result.x).to be < 0.0
+    expect(result.x).to be_within(0.0001).of(-result.y)
+  end
+
+  it 'clamps its settings' do
+    curve = described_class.new(dead_zone: 2.0, exponent: -1.0)
+    expect(curve.to_h).to eq(dead_zone: 0.99, exponent: 0.01)
+  end
+end
+
 RSpec.describe Bevy::DeadZone do
   describe '.new' do
     it 'creates with default values' do
@@ -286,6 +407,22 @@ RSpec.describe Bevy::GamepadInput do
     end
   end
 
+  describe '#battery' do
+    it 'is unknown by default' do
+      expect(gamepad.battery).to be_nil
+      expect(gamepad.battery_known?).to be false
+      expect(gamepad.low_battery?).to be false
+    end
+
+    it 'clamps reported levels to 0..1' do
+      gamepad.battery = 1.5
+      expect(gamepad.battery).to eq(1.0)
+
+      gamepad.battery = 0.1
+      expect(gamepad.low_battery?).to be true
+    end
+  end
+
   describe '#set_axis and #axis' do
     it 'tracks axis values' do
       gamepad.set_axis(Bevy::GamepadAxis::LEFT_STICK_X, 0.75)
@@ -397,6 +534,17 @@ RSpec.describe Bevy::GamepadInput do
     end
   end
 
+  describe '#rumble_pattern' do
+    it 'queues patterns in order until cleared' do
+      gamepad.rumble_pattern(:double_pulse).rumble_pattern([[1.0, 0.0, 0.1], [0.0, 0.0, 0.05]])
+
+      expect(gamepad.pending_rumble_patterns).to eq([:double_pulse, [[1.0, 0.0, 0.1], [0.0, 0.0, 0.05]]])
+
+      gamepad.clear_pending_rumble
+      expect(gamepad.pending_rumble_patterns).to be_empty
+    end
+  end
+
   describe '#left_trigger and #right_trigger' do
     it 'returns trigger values' do
       gamepad.set_axis(Bevy::GamepadAxis::LEFT_TRIGGER, 0.7)
@@ -0,0 +1,77 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::JellyMesh do
+  def points(mesh)
+    mesh.positions.each_slice(2).to_a
+  end
+
+  it 'starts at rest on a centered grid' do
+    mesh = described_class.new(64, 32, subdivisions: 2)
+
+    expect(mesh.columns).to eq(3)
+    expect(mesh).to be_resting
+    expect(points(mesh).first).to eq([-32.0, -16.0])
+    expect(points(mesh)[4]).to eq([0.0, 0.0])
+    expect(points(mesh).last).to eq([32.0, 16.0])
+  end
+
+  it 'clamps the subdivisions' do
+    expect(described_class.new(10, 10, subdivisions: 0).columns).to eq(2)
+    expect(described_class.new(10, 10, subdivisions: 1000).columns).to eq(33)
+  end
+
+  it 'moves the edges more than the center' do
+    mesh = described_class.new(64, 64, subdivisions: 2)
+    rest = points(mesh)
+
+    mesh.apply_impulse(1, 0, 50)
+    mesh.step(1.0 / 60)
+
+    moved = points(mesh).zip(rest).map { |(x, _), (rest_x, _)| x - rest_x }
+    expect(moved[4]).to eq(0.0)
+    expect(moved[0]).to be > 0.0
+  end
+
+  it 'loses energy every step and settles back to rest' do
+    mesh = described_class.new(64, 64, stiffness: 120, damping: 4)
+    mesh.apply_impulse(0, 1, 80)
+
+    mesh.step(1.0 / 60)
+    energies = Array.new(30) do
+      mesh.step(1.0 / 60)
+      mesh.energy
+    end
+    expect(energies.each_cons(2)).to all(satisfy { |before, after| after <= before })
+
+    600.times { mesh.step(1.0 / 60) }
+    expect(mesh).to be_resting
+    expect(mesh.positions).to eq(described_class.new(64, 64).positions)
+  end
+
+  it 'stays stable with a large step' do
+    mesh = described_class.new(64, 64, stiffness: 5000)
+    mesh.apply_impulse(1, 1, 100)
+
+    mesh.step(1.0)
+
+    expect(mesh.positions).to all(be_finite)
+  end
+
+  it 'ignores a zero direction' do
+    mesh = described_class.new(64, 64)
+    mesh.apply_impulse(0, 0, 100)
+
+    expect(mesh).to be_resting
+  end
+
+  it 'resets on demand' do
+    mesh = described_class.new(64, 64)
+    mesh.apply_impulse(1, 0, 100)
+    mesh.step(1.0 / 60)
+
+    mesh.reset
+
+    expect(mesh).to be_resting
+    expect(mesh.energy).to eq(0.0)
+  end
+end
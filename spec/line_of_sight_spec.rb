@@ -0,0 +1,146 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::SightGrid do
+  # Slab test over every box: fraction along the segment of the first touch.
+  def reference_fraction(segment, box)
+    x1, y1, x2, y2 = segment
+    enter = 0.0
+    exit = 1.0
+    [[x1, x2, box[0], box[2]], [y1, y2, box[1], box[3]]].each do |from, to, low, high|
+      delta = to - from
+      if delta.zero?
+        return nil if from < low || from > high
+
+        next
+      end
+      near, far = [(low - from) / delta, (high - from) / delta].minmax
+      enter = [enter, near].max
+      exit = [exit, far].min
+      return nil if enter > exit
+    end
+    enter
+  end
+
+  def reference_hits(segment, boxes, ignore = [])
+    hits = boxes.filter_map do |id, box|
+      next if ignore.include?(id)
+
+      fraction = reference_fraction(segment, box)
+      [id, fraction] if fraction
+    end
+    return [] if hits.empty?
+
+    closest = hits.map(&:last).min
+    hits.select { |_, fraction| (fraction - closest).abs < 1e-5 }
+  end
+
+  def random_boxes(rng, count, extent)
+    (1..count).to_h do |id|
+      x = rng.rand(-extent..extent)
+      y = rng.rand(-extent..extent)
+      [id, [x, y, x + rng.rand(0..40), y + rng.rand(0..40)]]
+    end
+  end
+
+  def build_grid(boxes, cell_size)
+    grid = described_class.new(cell_size: cell_size)
+    boxes.each { |id, box| grid.insert(id, *box.map(&:to_f)) }
+    grid
+  end
+
+  def expect_matches_reference(grid, boxes, segment, ignore: [])
+    hit = grid.raycast(*segment.map(&:to_f), ignore: ignore)
+    expected = reference_hits(segment.map(&:to_f), boxes, ignore)
+
+    if expected.empty?
+      expect(hit).to be_nil, "expected no hit for #{segment.inspect}, got #{hit.inspect}"
+      return
+    end
+    expect(hit).not_to be_nil, "expected a hit for #{segment.inspect}"
+    id, x, y = hit
+    expect(expected.map(&:first)).to include(id)
+    fraction = expected.first.last
+    expect(x).to be_within(1e-3).of(segment[0] + ((segment[2] - segment[0]) * fraction))
+    expect(y).to be_within(1e-3).of(segment[1] + ((segment[3] - segment[1]) * fraction))
+  end
+
+  it 'matches a brute-force reference over random boxes and segments' do
+    rng = Random.new(1516)
+
+    [3.0, 8.0, 16.0, 64.0].each do |cell_size|
+      20.times do
+        boxes = random_boxes(rng, rng.rand(1..30), 200)
+        grid = build_grid(boxes, cell_size)
+
+        20.times do
+          segment = Array.new(4) { rng.rand(-250..250) }
+          expect_matches_reference(grid, boxes, segment)
+        end
+      end
+    end
+  end
+
+  it 'matches the reference for axis-aligned and degenerate segments' do
+    rng = Random.new(7)
+    boxes = random_boxes(rng, 25, 100)
+    grid = build_grid(boxes, 16.0)
+
+    50.times do
+      x = rng.rand(-120..120)
+      y = rng.rand(-120..120)
+      expect_matches_reference(grid, boxes, [x, y, x, rng.rand(-120..120)])
+      expect_matches_reference(grid, boxes, [x, y, rng.rand(-120..120), y])
+      expect_matches_reference(grid, boxes, [x, y, x, y])
+    end
+  end
+
+  it 'matches the reference with ignored ids and boxes larger than the grid' do
+    rng = Random.new(42)
+    boxes = random_boxes(rng, 20, 100).merge(99 => [-5000, -10, 5000, 10])
+    grid = build_grid(boxes, 4.0)
+
+    30.times do
+      segment = Array.new(4) { rng.rand(-150..150) }
+      expect_matches_reference(grid, boxes, segment)
+      expect_matches_reference(grid, boxes, segment, ignore: [99, *boxes.keys.sample(3, random: rng)])
+    end
+  end
+
+  it 'reports the point where the segment enters the first box' do
+    grid = described_class.new
+    grid.insert(1, 50.0, -10.0, 60.0, 10.0)
+    grid.insert(2, 20.0, -10.0, 30.0, 10.0)
+
+    expect(grid.raycast(0.0, 0.0, 100.0, 0.0)).to eq([2, 20.0, 0.0])
+    expect(grid.raycast(0.0, 0.0, 100.0, 0.0, ignore: [2])).to eq([1, 50.0, 0.0])
+    expect(grid.raycast(0.0, 20.0, 100.0, 20.0)).to be_nil
+  end
+
+  it 'hits at the start when the segment begins inside a box' do
+    grid = described_class.new
+    grid.insert(4, -10.0, -10.0, 10.0, 10.0)
+
+    expect(grid.raycast(0.0, 0.0, 100.0, 100.0)).to eq([4, 0.0, 0.0])
+  end
+
+  it 'breaks ties toward the lower id' do
+    grid = described_class.new
+    grid.insert(9, 10.0, -5.0, 20.0, 5.0)
+    grid.insert(3, 10.0, -5.0, 20.0, 5.0)
+
+    expect(grid.raycast(0.0, 0.0, 30.0, 0.0).first).to eq(3)
+  end
+
+  it 'defaults the cell size and rejects a non-positive one' do
+    expect(described_class.new.cell_size).to eq(64.0)
+    expect { described_class.new(cell_size: 0.0) }.to raise_error(ArgumentError)
+  end
+
+  it 'rejects non-finite coordinates' do
+    grid = described_class.new
+
+    expect { grid.raycast(0.0, 0.0, Float::INFINITY, 0.0) }.to raise_error(ArgumentError)
+    expect { grid.insert(1, Float::NAN, 0.0, 1.0, 1.0) }.to raise_error(ArgumentError)
+    expect(grid.size).to eq(0)
+  end
+end
@@ -0,0 +1,64 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::LodSelector do
+  let(:levels) do
+    [
+      { max_scale: 0.5, sprite: { color_r: 1.0 } },
+      { max_scale: 2.0, sprite: { color_r: 0.5 } },
+      { hide: true }
+    ]
+  end
+
+  it 'selects the first level whose max_scale covers the scale' do
+    expect(described_class.new(levels, 0.0).update(0.25)).to eq(0)
+    expect(described_class.new(levels, 0.0).update(1.0)).to eq(1)
+    expect(described_class.new(levels, 0.0).update(8.0)).to eq(2)
+  end
+
+  it 'has no level before the first update' do
+    selector = described_class.new(levels)
+
+    expect(selector.current).to be_nil
+    expect(selector.margin).to be_within(1e-6).of(0.1)
+  end
+
+  it 'holds the current level within the margin past a boundary' do
+    selector = described_class.new(levels, 0.1)
+    selector.update(1.9)
+
+    expect(selector.update(2.15)).to eq(1)
+    expect(selector.update(2.25)).to eq(2)
+    expect(selector.update(1.85)).to eq(2)
+    expect(selector.update(1.75)).to eq(1)
+  end
+
+  it 'does not flicker when the scale oscillates around a boundary' do
+    selector = described_class.new(levels, 0.1)
+    selector.update(0.4)
+
+    picked = [0.49, 0.51, 0.49, 0.52, 0.48].map { |scale| selector.update(scale) }
+
+    expect(picked).to all(eq(0))
+  end
+
+  it 'switches immediately without a margin' do
+    selector = described_class.new(levels, 0.0)
+    selector.update(2.0)
+
+    expect(selector.update(2.01)).to eq(2)
+    expect(selector).to be_hidden
+  end
+
+  it 'selects the last level when the scale passes every bound' do
+    selector = described_class.new([{ max_scale: 1.0 }, { max_scale: 2.0 }], 0.0)
+
+    expect(selector.update(5.0)).to eq(1)
+  end
+
+  it 'rejects levels that are not ordered by max_scale' do
+    expect { described_class.new([{ max_scale: 2.0 }, { max_scale: 1.0 }]) }
+      .to raise_error(ArgumentError, /ascending/)
+    expect { described_class.new([{ hide: true }, { max_scale: 1.0 }]) }
+      .to raise_error(ArgumentError, /last LOD level/)
+  end
+end
@@ -0,0 +1,112 @@
+# frozen_string_literal: true
+
+RSpec.describe 'native hash marshaling' do
+  def allocations_per_call(calls = 200)
+    yield
+    GC.disable
+    before = GC.stat(:total_allocated_objects)
+    calls.times { yield }
+    (GC.stat(:total_allocated_objects) - before) / calls.to_f
+  ensure
+    GC.enable
+  end
+
+  # Arrays, hashes and strings in a returned structure; symbols, integers
+  # and floats are immediates.
+  def object_count(value)
+    case value
+    when Array then 1 + value.sum { |item| object_count(item) }
+    when Hash then 1 + value.sum { |key, item| object_count(key) + object_count(item) }
+    when String then 1
+    else 0
+    end
+  end
+
+  describe Bevy::Internal::GamepadStates do
+    let(:gamepads) do
+      described_class.new.tap do |gamepads|
+        [3, 1, 4, 2].each do |id|
+          gamepads.connect(id, "Pad #{id}")
+          gamepads.press(id, 'South')
+          gamepads.press(id, 'East')
+          gamepads.set_axis(id, 'LeftStickY', -0.25)
+          gamepads.set_axis(id, 'LeftStickX', 0.5)
+        end
+        gamepads.release(1, 'North')
+        gamepads.set_battery(2, 0.75)
+      end
+    end
+
+    it 'orders four gamepads by id' do
+      expect(gamepads.states.map { |state| state[:id] }).to eq([1, 2, 3, 4])
+    end
+
+    it 'keys each gamepad with symbols in a stable order' do
+      expect(gamepads.states.map(&:keys)).to all(
+        eq(%i[id name buttons_pressed buttons_just_pressed buttons_just_released axes battery])
+      )
+    end
+
+    it 'sorts buttons and axes by name' do
+      first, second = gamepads.states
+
+      expect(first).to include(name: 'Pad 1', buttons_pressed: %w[East South],
+                               buttons_just_pressed: %w[East South], buttons_just_released: ['North'],
+                               battery: nil)
+      expect(first[:axes]).to eq('LeftStickX' => 0.5, 'LeftStickY' => -0.25)
+      expect(second[:battery]).to eq(0.75)
+    end
+
+    it 'allocates only the objects it returns for four gamepads' do
+      expect(allocations_per_call { gamepads.states }).to be <= object_count(gamepads.states)
+    end
+  end
+
+  describe Bevy::RewindBuffer do
+    let(:buffer) do
+      Bevy::RewindBuffer.new(1.0, 10.0).tap do |buffer|
+        3.times { buffer.update(0.1, [1.0, 2.0, 3.0, 0.0]) }
+      end
+    end
+
+    it 'keys stats with symbols in a stable order' do
+      expect(buffer.stats.keys).to eq(
+        %i[snapshots capacity memory_bytes max_memory_bytes covered_seconds truncated_snapshots rewinding]
+      )
+    end
+
+    it 'keys playback frames with symbols' do
+      buffer.rewind_to(0.1)
+
+      expect(buffer.update(0.1, [1.0, 2.0, 3.0, 0.0]).keys).to eq(%i[write hide show])
+    end
+
+    it 'allocates only the stats hash per call' do
+      expect(allocations_per_call { buffer.stats }).to be <= 1
+    end
+  end
+
+  describe Bevy::DeterminismAudit do
+    it 'keys divergences with symbols' do
+      expect(described_class.compare([1, 2], [1, 3]).keys).to eq(%i[frame expected actual])
+    end
+
+    it 'reads sprite keys from symbol-keyed hashes' do
+      red = described_class.new
+      red.record_sprite(1, { color_r: 1.0, color_g: 0.0 }, { x: 0.0 })
+      default = described_class.new
+      default.record_sprite(1, {}, { x: 0.0 })
+
+      expect(red.finish_frame).not_to eq(default.finish_frame)
+    end
+  end
+
+  describe Bevy::LodSelector do
+    it 'reads level keys from symbol-keyed hashes' do
+      selector = described_class.new([{ max_scale: 1.0 }, { hide: true }], 0.0)
+
+      expect(selector.update(2.0)).to eq(1)
+      expect(selector).to be_hidden
+    end
+  end
+end
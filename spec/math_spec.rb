@@ -80,6 +80,77 @@ RSpec.describe Bevy::Vec2 do
     end
   end
 
+  describe '#lerp' do
+    it 'interpolates between vectors' do
+      v = described_class.new(0.0, 0.0).lerp(described_class.new(10.0, 20.0), 0.25)
+      expect(v.to_a).to eq([2.5, 5.0])
+    end
+
+    it 'extrapolates outside 0..1' do
+      v = described_class.new(0.0, 0.0).lerp(described_class.new(10.0, 0.0), 1.5)
+      expect(v.x).to eq(15.0)
+    end
+  end
+
+  describe '#reflect' do
+    it 'bounces off a surface normal' do
+      v = described_class.new(3.0, -4.0).reflect(described_class.new(0.0, 2.0))
+      expect(v.to_a).to eq([3.0, 4.0])
+    end
+  end
+
+  describe '#clamp_length' do
+    it 'shortens vectors longer than max' do
+      v = described_class.new(3.0, 4.0).clamp_length(2.5)
+      expect(v.x).to be_within(1e-6).of(1.5)
+      expect(v.y).to be_within(1e-6).of(2.0)
+    end
+
+    it 'leaves shorter vectors unchanged' do
+      expect(described_class.new(1.0, 1.0).clamp_length(5.0).to_a).to eq([1.0, 1.0])
+    end
+  end
+
+  describe '.from_angle' do
+    it 'points along +x at angle 0' do
+      expect(described_class.from_angle(0.0).to_a).to eq([1.0, 0.0])
+    end
+
+    it 'returns a unit vector counterclockwise from +x' do
+      v = described_class.from_angle(Math::PI / 2.0)
+      expect(v.x).to be_within(1e-6).of(0.0)
+      expect(v.y).to be_within(1e-6).of(1.0)
+    end
+  end
+
+  describe '#angle' do
+    it 'returns atan2(y, x)' do
+      expect(described_class.new(0.0, 2.0).angle).to be_within(1e-6).of(Math::PI / 2.0)
+      expect(described_class.new(-1.0, -1.0).angle).to be_within(1e-6).of(-3.0 * Math::PI / 4.0)
+    end
+
+    it 'round-trips through from_angle' do
+      expect(described_class.from_angle(2.5).angle).to be_within(1e-6).of(2.5)
+    end
+  end
+
+  describe '#rotate' do
+    it 'rotates counterclockwise and keeps the length' do
+      v = described_class.new(3.0, 0.0).rotate(Math::PI / 2.0)
+      expect(v.x).to be_within(1e-5).of(0.0)
+      expect(v.y).to be_within(1e-5).of(3.0)
+      expect(described_class.new(3.0, 4.0).rotate(1.0).length).to be_within(1e-5).of(5.0)
+    end
+  end
+
+  describe '#perp' do
+    it 'is (-y, x) and perpendicular to the vector' do
+      v = described_class.new(3.0, 4.0)
+      expect(v.perp.to_a).to eq([-4.0, 3.0])
+      expect(v.perp.dot(v)).to be_within(1e-6).of(0.0)
+    end
+  end
+
   describe '#to_a' do
     it 'converts to array' do
       v = described_class.new(1.0, 2.0)
@@ -145,6 +216,31 @@ RSpec.describe Bevy::Vec3 do
     end
   end
 
+  describe '#lerp' do
+    it 'interpolates between vectors' do
+      v = described_class.new(0.0, 0.0, 0.0).lerp(described_class.new(2.0, 4.0, 8.0), 0.5)
+      expect(v.to_a).to eq([1.0, 2.0, 4.0])
+    end
+
+    it 'extrapolates outside 0..1' do
+      v = described_class.new(1.0, 1.0, 1.0).lerp(described_class.new(2.0, 2.0, 2.0), -1.0)
+      expect(v.to_a).to eq([0.0, 0.0, 0.0])
+    end
+  end
+
+  describe '#reflect' do
+    it 'bounces off a surface normal' do
+      v = described_class.new(1.0, -2.0, 3.0).reflect(described_class.new(0.0, 1.0, 0.0))
+      expect(v.to_a).to eq([1.0, 2.0, 3.0])
+    end
+  end
+
+  describe '#clamp_length' do
+    it 'shortens vectors longer than max' do
+      expect(described_class.new(0.0, 0.0, 10.0).clamp_length(4.0).to_a).to eq([0.0, 0.0, 4.0])
+    end
+  end
+
   describe '#to_a' do
     it 'converts to array' do
       v = described_class.new(1.0, 2.0, 3.0)
@@ -208,3 +304,33 @@ RSpec.describe Bevy::Quat do
     end
   end
 end
+
+RSpec.describe Bevy::Mat4 do
+  describe '.identity' do
+    it 'leaves points unchanged' do
+      point = described_class.identity.transform_point3(Bevy::Vec3.new(1.0, 2.0, 3.0))
+      expect(point.to_a).to eq([1.0, 2.0, 3.0])
+    end
+  end
+
+  describe '#inverse' do
+    it 'undoes the transform' do
+      m = described_class.from_scale_rotation_translation(
+        Bevy::Vec3.new(2.0, 2.0, 2.0),
+        Bevy::Quat.from_rotation_z(Math::PI / 4),
+        Bevy::Vec3.new(5.0, 0.0, 0.0)
+      )
+      point = (m.inverse * m).transform_point3(Bevy::Vec3.new(1.0, 1.0, 0.0))
+      expect(point.x).to be_within(0.001).of(1.0)
+      expect(point.y).to be_within(0.001).of(1.0)
+    end
+  end
+
+  describe '#to_a' do
+    it 'returns 16 column-major elements' do
+      m = described_class.from_translation(Bevy::Vec3.new(1.0, 2.0, 3.0))
+      expect(m.to_a.length).to eq(16)
+      expect(m.to_a[12..14]).to eq([1.0, 2.0, 3.0])
+    end
+  end
+end
@@ -8,6 +8,7 @@ RSpec.describe Bevy::Mesh do
       expect(Bevy::Mesh::SHAPE_REGULAR_POLYGON).to eq(2)
       expect(Bevy::Mesh::SHAPE_LINE).to eq(3)
       expect(Bevy::Mesh::SHAPE_ELLIPSE).to eq(4)
+      expect(Bevy::Mesh::SHAPE_TRIANGLE).to eq(5)
     end
   end
 end
@@ -71,6 +72,35 @@ RSpec.describe Bevy::Mesh::Rectangle do
     end
   end
 
+  describe 'gradient fill' do
+    let(:blue) { Bevy::Color.new(0.0, 0.0, 1.0, 1.0) }
+
+    it 'leaves the gradient out of the mesh data by default' do
+      data = described_class.new(width: 100, height: 50).to_mesh_data
+
+      expect(data).not_to include(:gradient_color, :gradient_direction)
+    end
+
+    it 'passes the second color and direction to the mesh data' do
+      rect = described_class.new(width: 100, height: 50, gradient_color: blue, gradient_direction: :horizontal)
+
+      expect(rect.to_mesh_data).to include(gradient_color: [0.0, 0.0, 1.0, 1.0], gradient_direction: 'horizontal')
+    end
+
+    it 'defaults to a vertical gradient' do
+      rect = described_class.new(width: 100, height: 50, gradient_color: blue)
+
+      expect(rect.gradient_direction).to eq(:vertical)
+      expect(rect.to_mesh_data).to include(gradient_direction: 'vertical')
+    end
+
+    it 'rejects unknown directions' do
+      expect do
+        described_class.new(width: 100, height: 50, gradient_direction: :diagonal)
+      end.to raise_error(ArgumentError)
+    end
+  end
+
   describe '#type_name' do
     it 'returns Mesh::Rectangle' do
       rect = described_class.new(width: 100, height: 50)
@@ -172,6 +202,13 @@ RSpec.describe Bevy::Mesh::Triangle do
     end
   end
 
+  describe '#shape_type' do
+    it 'returns SHAPE_TRIANGLE' do
+      triangle = described_class.new(radius: 50)
+      expect(triangle.shape_type).to eq(Bevy::Mesh::SHAPE_TRIANGLE)
+    end
+  end
+
   describe '#type_name' do
     it 'returns Mesh::Triangle' do
       triangle = described_class.new(radius: 50)
@@ -188,6 +225,15 @@ RSpec.describe Bevy::Mesh::Hexagon do
     end
   end
 
+  describe '#to_mesh_data' do
+    it 'passes a gradient through to the polygon' do
+      color = Bevy::Color.new(0.0, 1.0, 0.0, 0.5)
+      hexagon = described_class.new(radius: 50, gradient_color: color, gradient_direction: 'horizontal')
+
+      expect(hexagon.to_mesh_data).to include(gradient_color: [0.0, 1.0, 0.0, 0.5], gradient_direction: 'horizontal')
+    end
+  end
+
   describe '#type_name' do
     it 'returns Mesh::Hexagon' do
       hexagon = described_class.new(radius: 50)
@@ -216,6 +262,29 @@ RSpec.describe Bevy::Mesh::Line do
     end
   end
 
+  describe 'line caps and joins' do
+    let(:start_pt) { Bevy::Vec2.new(0, 0) }
+    let(:end_pt) { Bevy::Vec2.new(100, 0) }
+
+    it 'defaults to butt caps and miter joins' do
+      data = described_class.new(start_point: start_pt, end_point: end_pt).to_mesh_data
+
+      expect(data).to include(line_cap: 'butt', line_join: 'miter')
+    end
+
+    it 'passes the configured cap and join to the mesh data' do
+      line = described_class.new(start_point: start_pt, end_point: end_pt, line_cap: :round, line_join: 'bevel')
+
+      expect(line.to_mesh_data).to include(line_cap: 'round', line_join: 'bevel')
+    end
+
+    it 'rejects unknown styles' do
+      expect do
+        described_class.new(start_point: start_pt, end_point: end_pt, line_cap: :arrow)
+      end.to raise_error(ArgumentError)
+    end
+  end
+
   describe '#length' do
     it 'calculates line length' do
       start_pt = Bevy::Vec2.new(0, 0)
@@ -319,3 +388,39 @@ RSpec.describe Bevy::Mesh::Ellipse do
     end
   end
 end
+
+RSpec.describe Bevy::RenderApp, :gpu do
+  def pixel(rgba, width, x, y)
+    rgba.byteslice((y * width + x) * 4, 4).bytes
+  end
+
+  it 'draws a vertical gradient from the top color to the bottom color' do
+    app = described_class.new(headless: true, width: 32.0, height: 32.0, frames: 4)
+    red = Bevy::Color.new(1.0, 0.0, 0.0, 1.0)
+    blue = Bevy::Color.new(0.0, 0.0, 1.0, 1.0)
+    rect = Bevy::Mesh::Rectangle.new(width: 32, height: 32, color: red, gradient_color: blue)
+    frame = nil
+
+    app.run do
+      app.sync_mesh(1, rect.to_mesh_data, Bevy::Transform.new.to_sync_hash)
+      frame = app.read_pixels || frame
+    end
+
+    width, height, rgba = frame
+    top = pixel(rgba, width, width / 2, 1)
+    bottom = pixel(rgba, width, width / 2, height - 2)
+    expect(top[0]).to be > top[2]
+    expect(bottom[2]).to be > bottom[0]
+  ensure
+    app&.close
+  end
+
+  it 'rejects a gradient color that is not four numbers' do
+    app = described_class.new(headless: true, width: 32.0, height: 32.0, frames: 1)
+    data = Bevy::Mesh::Rectangle.new(width: 8, height: 8).to_mesh_data.merge(gradient_color: [1.0, 0.0])
+
+    expect { app.sync_mesh(1, data, Bevy::Transform.new.to_sync_hash) }.to raise_error(ArgumentError, /gradient_color/)
+  ensure
+    app&.close
+  end
+end
@@ -0,0 +1,129 @@
+# frozen_string_literal: true
+
+require 'json'
+require 'tmpdir'
+
+RSpec.describe Bevy::OperationJournal do
+  around do |example|
+    Dir.mktmpdir do |dir|
+      @dir = dir
+      example.run
+    end
+  end
+
+  let(:path) { File.join(@dir, 'journal.jsonl') }
+
+  def lines(file = path)
+    File.readlines(file).map { |line| JSON.parse(line, symbolize_names: true) }
+  end
+
+  # A scripted session: returns `[frame, checksum]` for each recorded frame.
+  def record(journal, frames)
+    frames.times.map do |frame|
+      journal.record_sprite(1, { color_r: 0.25, z_index: 2 }, { x: frame * 1.5, y: 0.1, rotation: 0.3 })
+      journal.record_text(2, { content: "score #{frame}", font_size: 18.0 }, { x: 4.0, y: -2.0 })
+      journal.record_mesh(3, { shape_type: 1, radius: 12.5 }, { x: 0.0, y: frame.to_f })
+      journal.record_camera(frame * 2.0, 0.0, 0.0, 1.25, 0.0) if frame.odd?
+      journal.record_remove_sprite(4) if frame == 2
+      [frame, journal.finish_frame(frame)]
+    end
+  end
+
+  it 'writes one JSON object per operation tagged with its frame' do
+    journal = described_class.new(path)
+    journal.record_sprite(7, { color_r: 0.5, group: 'enemies' }, { x: 1.0, y: 2.0, scale_x: 3.0 })
+    journal.record_remove_sprite(7)
+    journal.record_clear_sprites
+    journal.finish_frame(12)
+    journal.close
+
+    sync, remove, clear = lines
+
+    expect(sync).to include(frame: 12, op: 'sprite_sync', id: 7)
+    expect(sync[:sprite]).to include(color: [0.5, 1, 1, 1], group: 'enemies', z_index: nil)
+    expect(sync[:transform]).to eq([1, 2, 0, 0, 0, 0, 1, 3, 1, 1])
+    expect(remove).to eq(frame: 12, op: 'sprite_remove', id: 7)
+    expect(clear).to eq(frame: 12, op: 'sprite_clear')
+  end
+
+  it 'serializes text, mesh and camera operations' do
+    journal = described_class.new(path)
+    journal.record_text(1, { content: "a \"quoted\"\nline", font_size: 32.0, blend: :add }, { x: 5.0 })
+    journal.record_remove_text(1)
+    journal.record_mesh(2, { shape_type: 3, line_end_x: 40.0, thickness: 4.0 }, { y: 6.0 })
+    journal.record_remove_mesh(2)
+    journal.record_camera(10.0, 20.0, 0.0, 2.0, 0.5)
+    journal.record_camera_shake(3.0, 0.25)
+    journal.finish_frame(0)
+    journal.close
+
+    text, remove_text, mesh, remove_mesh, camera, shake = lines
+
+    expect(text[:text]).to include(content: "a \"quoted\"\nline", font_size: 32, blend: 'add')
+    expect(text[:transform]).to eq([5, 0, 0, 1, 1, 1])
+    expect(remove_text).to eq(frame: 0, op: 'text_remove', id: 1)
+    expect(mesh[:mesh]).to include(shape: 'line', line: [0, 0, 40, 0], thickness: 4)
+    expect(remove_mesh).to eq(frame: 0, op: 'mesh_remove', id: 2)
+    expect(camera).to eq(frame: 0, op: 'camera', position: [10, 20, 0], scale: 2, rotation: 0.5)
+    expect(shake).to eq(frame: 0, op: 'camera_shake', intensity: 3, duration: 0.25)
+  end
+
+  it 'writes nothing for frames without operations' do
+    journal = described_class.new(path)
+    journal.finish_frame(0)
+    journal.close
+
+    expect(File.read(path)).to eq('')
+  end
+
+  it 'replays to the same operations, frame by frame' do
+    journal = described_class.new(path)
+    recorded = record(journal, 6)
+    journal.close
+
+    expect(described_class.frame_checksums(path)).to eq(recorded)
+  end
+
+  it 'keeps large IDs and float bits exact across a replay' do
+    journal = described_class.new(path)
+    journal.record_sprite(2**64 - 1, { color_r: 0.1 }, { x: 1.0 / 3.0, y: -0.0 })
+    recorded = [[3, journal.finish_frame(3)]]
+    journal.close
+
+    expect(lines.first[:id]).to eq(2**64 - 1)
+    expect(described_class.frame_checksums(path)).to eq(recorded)
+  end
+
+  it 'rotates past the size cap and keeps two older files' do
+    journal = described_class.new(path, max_mb: 0.004)
+    recorded = record(journal, 12)
+    journal.close
+
+    files = [path, "#{path}.1", "#{path}.2"]
+    expect(files).to all(satisfy { |file| File.exist?(file) })
+    expect(File.exist?("#{path}.3")).to be false
+    expect(files.map { |file| File.size(file) }).to all(be <= 0.004 * 1024 * 1024)
+
+    # Frames are never split, so the rotated files replay as a contiguous tail.
+    tail = files.reverse.flat_map { |file| described_class.frame_checksums(file) }
+    expect(tail).to eq(recorded.last(tail.size))
+  end
+
+  it 'removes rotated files of an earlier journal' do
+    File.write("#{path}.1", 'old')
+
+    described_class.new(path).close
+
+    expect(File.exist?("#{path}.1")).to be false
+  end
+
+  it 'rejects a non-positive size cap' do
+    expect { described_class.new(path, max_mb: 0) }.to raise_error(ArgumentError, /max_mb/)
+  end
+
+  it 'reports unparseable journals with their line number' do
+    File.write(path, "{\"frame\":1,\"op\":\"sprite_clear\"}\n{\"frame\":2,\"op\":\"teleport\"}\n")
+
+    expect { described_class.frame_checksums(path) }.to raise_error(ArgumentError, /line 2/)
+  end
+end
@@ -0,0 +1,85 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::Palette do
+  let(:palette) { described_class.new([Bevy::Color.red, [0.0, 1.0, 0.0], '#0000ff']) }
+
+  describe '.new' do
+    it 'accepts colors, component arrays, and hex strings' do
+      expect(palette.size).to eq(3)
+      expect(palette.color_at(1).g).to be_within(0.001).of(1.0)
+      expect(palette.color_at(2).b).to be_within(0.001).of(1.0)
+    end
+
+    it 'accepts a cycling speed' do
+      expect(described_class.new([Bevy::Color.red], speed: 4.0).speed).to eq(4.0)
+    end
+
+    it 'rejects unsupported color forms' do
+      expect { described_class.new([:red]) }.to raise_error(TypeError)
+    end
+  end
+
+  describe '#cycle' do
+    it 'rotates entries by speed * dt and wraps' do
+      palette.speed = 2.0
+      palette.cycle(0.5)
+      expect(palette.color_at(0).g).to be_within(0.001).of(1.0)
+
+      palette.cycle(1.0)
+      expect(palette.offset).to be_within(0.001).of(0.0)
+      expect(palette.color_at(0).r).to be_within(0.001).of(1.0)
+    end
+
+    it 'wraps negative indices' do
+      expect(palette.color_at(-1).b).to be_within(0.001).of(1.0)
+    end
+  end
+
+  describe '.gradient' do
+    it 'interpolates inclusively between endpoints' do
+      gradient = described_class.gradient(Bevy::Color.black, Bevy::Color.white, 3)
+      expect(gradient.size).to eq(3)
+      expect(gradient.color_at(0).r).to be_within(0.001).of(0.0)
+      expect(gradient.color_at(1).r).to be_within(0.001).of(0.5)
+      expect(gradient.color_at(2).r).to be_within(0.001).of(1.0)
+    end
+  end
+
+  describe '.from_hex_list' do
+    it 'parses every entry' do
+      expect(described_class.from_hex_list(%w[#ff0000 #00ff00]).size).to eq(2)
+    end
+
+    it 'raises on invalid hex' do
+      expect { described_class.from_hex_list(['nope']) }.to raise_error(ArgumentError)
+    end
+  end
+
+  describe '#apply_to' do
+    let(:render_app) { double('render_app') }
+
+    it 'enqueues one native patch call for all ids' do
+      app = Bevy::App.new(render: true)
+      app.instance_variable_set(:@render_app, render_app)
+      entity = app.world.spawn
+      allow(render_app).to receive(:apply_palette)
+
+      palette.apply_to(app, [entity, 7])
+
+      expect(render_app).to have_received(:apply_palette).with(palette, [entity.id, 7])
+    end
+
+    it 'releases color overrides' do
+      allow(render_app).to receive(:clear_sprite_color)
+
+      palette.release(render_app, [3, 4])
+
+      expect(render_app).to have_received(:clear_sprite_color).with(3)
+      expect(render_app).to have_received(:clear_sprite_color).with(4)
+    end
+
+    it 'is a no-op without a render app' do
+      expect { palette.apply_to(Bevy::App.new, [1]) }.not_to raise_error
+    end
+  end
+end
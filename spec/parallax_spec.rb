@@ -0,0 +1,101 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::ParallaxLayers do
+  let(:layers) { described_class.new }
+
+  # One frame of the render bridge: take last frame's offset off, let other
+  # motion move the authored position, then add this frame's offset.
+  def frame(layers, id, group, position, camera)
+    authored = layers.restore(id, *position)
+    authored = yield(authored) if block_given?
+    layers.apply(id, group, *authored, *camera)
+  end
+
+  it 'offsets by the camera position times one minus the factor' do
+    layers.set_factor(:hills, 0.5, 0.25)
+
+    expect(layers.apply(1, :hills, 10.0, 20.0, 0.0, 100.0, 40.0)).to eq([60.0, 50.0, 0.0])
+  end
+
+  it 'keeps factor 1.0 sprites where they were authored' do
+    layers.set_factor(:world, 1.0, 1.0)
+
+    expect(layers.apply(1, :world, 10.0, 20.0, 0.0, 100.0, 40.0)).to eq([10.0, 20.0, 0.0])
+  end
+
+  it 'pins factor 0.0 sprites to the camera' do
+    layers.set_factor(:sky, 0.0, 0.0)
+    position = [0.0, 0.0, 0.0]
+
+    [[0.0, 0.0], [30.0, 10.0], [-50.0, 5.0]].each do |camera|
+      position = frame(layers, 1, :sky, position, camera)
+      expect(position).to eq([camera[0], camera[1], 0.0])
+    end
+  end
+
+  it 'moves foreground layers faster than the camera' do
+    layers.set_factor(:fog, 1.5, 1.0)
+
+    expect(layers.apply(1, :fog, 0.0, 0.0, 0.0, 100.0, 0.0)).to eq([-50.0, 0.0, 0.0])
+  end
+
+  it 'does not accumulate offsets across frames' do
+    layers.set_factor(:hills, 0.5, 0.5)
+    position = [10.0, 20.0, 0.0]
+
+    3.times { position = frame(layers, 1, :hills, position, [40.0, 0.0]) }
+
+    expect(position).to eq([30.0, 20.0, 0.0])
+  end
+
+  it 'applies other motion to the authored position before the offset' do
+    layers.set_factor(:hills, 0.5, 0.5)
+    position = frame(layers, 1, :hills, [0.0, 0.0, 0.0], [100.0, 0.0])
+
+    position = frame(layers, 1, :hills, position, [100.0, 0.0]) { |x, y, z| [x + 5.0, y, z] }
+
+    expect(position).to eq([55.0, 0.0, 0.0])
+    expect(layers.restore(1, *position)).to eq([5.0, 0.0, 0.0])
+  end
+
+  it 'treats a position re-synced by Ruby as the new authored position' do
+    layers.set_factor(:hills, 0.5, 0.5)
+    frame(layers, 1, :hills, [0.0, 0.0, 0.0], [100.0, 0.0])
+
+    expect(layers.restore(1, 7.0, 8.0, 0.0)).to eq([7.0, 8.0, 0.0])
+  end
+
+  it 'restores authored positions once the factor is cleared' do
+    layers.set_factor(:hills, 0.5, 0.5)
+    position = frame(layers, 1, :hills, [10.0, 20.0, 0.0], [100.0, 100.0])
+
+    layers.clear(:hills)
+    position = frame(layers, 1, :hills, position, [100.0, 100.0])
+
+    expect(position).to eq([10.0, 20.0, 0.0])
+    expect(layers.factor(:hills)).to be_nil
+  end
+
+  it 'follows the new factor when it changes' do
+    layers.set_factor(:hills, 0.5, 0.5)
+    position = frame(layers, 1, :hills, [0.0, 0.0, 0.0], [100.0, 0.0])
+
+    layers.set_factor(:hills, 0.0, 0.0)
+    position = frame(layers, 1, :hills, position, [100.0, 0.0])
+
+    expect(position).to eq([100.0, 0.0, 0.0])
+  end
+
+  it 'selects layers by the rounded z and lets a group factor win' do
+    layers.set_factor(-2, 0.5, 0.5)
+    layers.set_factor('hills', 0.0, 0.0)
+
+    expect(layers.apply(1, nil, 0.0, 0.0, -1.75, 10.0, 0.0)).to eq([5.0, 0.0, -1.75])
+    expect(layers.apply(2, :hills, 0.0, 0.0, -2.0, 10.0, 0.0)).to eq([10.0, 0.0, -2.0])
+    expect(layers.apply(3, :trees, 0.0, 0.0, 0.0, 10.0, 0.0)).to eq([0.0, 0.0, 0.0])
+  end
+
+  it 'rejects targets that are neither group names nor layers' do
+    expect { layers.set_factor(1.5, 0.5, 0.5) }.to raise_error(TypeError, /parallax target/)
+  end
+end
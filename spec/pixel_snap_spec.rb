@@ -0,0 +1,102 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::PixelSnap do
+  let(:snap) { described_class.new }
+
+  def grid(camera: [0.0, 0.0], scale: 1.0, rotation: 0.0, scale_factor: 1.0, viewport: [800.0, 600.0])
+    { camera: camera, scale: scale, rotation: rotation, scale_factor: scale_factor, viewport: viewport }
+  end
+
+  def pixel_offset(x, y, grid)
+    described_class.to_physical(x, y, grid).map { |value| (value - value.round).abs }.max
+  end
+
+  # One frame of the render bridge: put last frame's authored position back,
+  # let other motion move it, then snap it for rendering.
+  def frame(snap, key, position, grid)
+    authored = snap.restore(key, *position)
+    authored = yield(authored) if block_given?
+    snap.apply(key, *authored, grid)
+  end
+
+  it 'maps the camera position to the viewport center, y down' do
+    expect(described_class.to_physical(10.0, 20.0, grid(camera: [10.0, 20.0]))).to eq([400.0, 300.0])
+    expect(described_class.to_physical(11.0, 21.0, grid(camera: [10.0, 20.0]))).to eq([401.0, 299.0])
+  end
+
+  it 'rounds to the nearest whole pixel at zoom 1' do
+    x, y = described_class.snap(10.4, -3.6, grid)
+
+    expect(x).to be_within(1e-5).of(10.0)
+    expect(y).to be_within(1e-5).of(-4.0)
+  end
+
+  it 'lands on physical pixels at zoom levels that are not integer pixel ratios' do
+    [0.37, 2.0 / 3.0, 1.5, 3.0].product([1.0, 1.25, 1.5, 2.0]).each do |scale, scale_factor|
+      [[800.0, 600.0], [801.0, 601.0]].each do |viewport|
+        pixel_grid = grid(camera: [13.37, -7.21], scale: scale, scale_factor: scale_factor, viewport: viewport)
+
+        50.times do |i|
+          x, y = described_class.snap((i * 1.713) - 40.0, 25.0 - (i * 0.917), pixel_grid)
+          expect(pixel_offset(x, y, pixel_grid)).to be < 1e-3
+        end
+      end
+    end
+  end
+
+  it 'snaps along the screen axes of a rotated camera' do
+    pixel_grid = grid(camera: [5.0, 5.0], rotation: 0.3, scale: 0.75)
+    x, y = described_class.snap(12.34, 56.78, pixel_grid)
+
+    expect(pixel_offset(x, y, pixel_grid)).to be < 1e-3
+  end
+
+  it 'leaves positions alone without a pixel grid' do
+    expect(described_class.snap(1.25, 2.5, grid(scale: 0.0))).to eq([1.25, 2.5])
+  end
+
+  it 'never drifts the authored position over 1000 frames of motion' do
+    pixel_grid = grid(camera: [0.3, 0.0], scale: 0.37, scale_factor: 1.25, viewport: [801.0, 600.0])
+    position = [0.0, 0.0, 1.0]
+    authored = nil
+    errors = []
+
+    1000.times do
+      position = frame(snap, 1, position, pixel_grid) do |x, y, z|
+        authored = [x + 0.0137, y - 0.0071, z]
+      end
+      rendered = described_class.to_physical(position[0], position[1], pixel_grid)
+      exact = described_class.to_physical(authored[0], authored[1], pixel_grid)
+      errors << (rendered[0] - exact[0])
+      expect((rendered[1] - exact[1]).abs).to be <= 0.5
+    end
+
+    snap.restore(1, *position).zip(authored).each { |restored, expected| expect(restored).to be_within(1e-5).of(expected) }
+    expect(authored[0]).to be_within(1e-3).of(13.7)
+    expect(errors.map(&:abs).max).to be <= 0.5
+    expect(errors.sum / errors.length).to be_within(0.05).of(0.0)
+  end
+
+  it 'treats a position re-synced by Ruby as the new authored position' do
+    frame(snap, 1, [0.3, 0.3, 0.0], grid)
+
+    expect(snap.restore(1, 7.0, 8.0, 0.0)).to eq([7.0, 8.0, 0.0])
+  end
+
+  it 'lets per-entity settings win over the global one' do
+    expect(snap.enabled?(1)).to be(false)
+
+    snap.set_global(true)
+    snap.set_entity(2, false)
+    expect(snap).to be_global
+    expect(snap.enabled?(1)).to be(true)
+    expect(snap.enabled?(2)).to be(false)
+
+    snap.clear_entity(2)
+    expect(snap.enabled?(2)).to be(true)
+  end
+
+  it 'requires a viewport' do
+    expect { described_class.snap(0.0, 0.0, { scale: 1.0 }) }.to raise_error(ArgumentError, /viewport/)
+  end
+end
@@ -0,0 +1,104 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::RenderApp, :gpu do
+  def headless_app(frames: 2)
+    described_class.new(headless: true, width: 32.0, height: 32.0, frames: frames)
+  end
+
+  it 'allows only one render app at a time' do
+    app = headless_app
+
+    expect { headless_app }.to raise_error(RuntimeError, /already exists/)
+  ensure
+    app&.close
+  end
+
+  it 'releases an unrun render app on close' do
+    headless_app.close
+
+    expect { headless_app.close }.not_to raise_error
+  end
+
+  it 'runs two short-lived apps one after the other' do
+    frames = []
+
+    2.times do
+      app = headless_app
+      count = 0
+      app.run { count += 1 }
+      frames << count
+    end
+
+    expect(frames).to all(be_positive)
+  end
+
+  it 'starts the second app from a clean state' do
+    first = headless_app
+    first.set_camera_position(10.0, 20.0, 0.0)
+    first.stop!
+    first.run {}
+
+    second = headless_app
+    positions = []
+    second.run { positions << second.camera_position }
+
+    expect(positions.first).to eq([0.0, 0.0, 0.0])
+    expect(second.frame_count).to be_positive
+  end
+
+  it 'refuses to run an app twice' do
+    app = headless_app
+    app.run {}
+
+    expect { app.run {} }.to raise_error(RuntimeError, /already run/)
+  end
+
+  it 'stops and re-raises when the frame block raises, releasing the app' do
+    app = headless_app(frames: 10)
+    reasons = []
+    app.on_exit { |reason| reasons << reason }
+    frames = 0
+
+    expect { app.run { frames += 1; raise 'boom' } }.to raise_error(RuntimeError, 'boom')
+    expect(frames).to eq(1)
+    expect(reasons).to eq([:error])
+    expect { headless_app.close }.not_to raise_error
+  end
+
+  it 'reports stop! to on_exit in the last frame, with should_close? already true' do
+    app = headless_app(frames: 10)
+    exits = []
+    app.on_exit { |reason| exits << [reason, app.should_close?, app.frame_count] }
+    closing = []
+
+    app.run do
+      closing << app.should_close?
+      app.stop! if app.frame_count == 2
+    end
+
+    expect(closing).to all(be(false))
+    expect(exits.length).to eq(1)
+    reason, should_close, frame = exits.first
+    expect(reason).to eq(:stop_called)
+    expect(should_close).to be(true)
+    expect(frame).to eq(2)
+  end
+
+  it 'calls on_exit once when a headless app runs out of frames' do
+    app = headless_app(frames: 3)
+    reasons = []
+    app.on_exit { |reason| reasons << reason }
+    app.run {}
+
+    expect(reasons).to eq([:stop_called])
+    expect(app.should_close?).to be(true)
+  end
+
+  it 'requires a block for on_exit' do
+    app = headless_app
+
+    expect { app.on_exit }.to raise_error(ArgumentError)
+  ensure
+    app&.close
+  end
+end
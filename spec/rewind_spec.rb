@@ -0,0 +1,128 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::RewindBuffer do
+  let(:buffer) { described_class.new(1.0, 10.0) }
+
+  def record(*snapshots)
+    snapshots.each { |snapshot| buffer.update(0.1, snapshot) }
+  end
+
+  def sample_x(samples, id)
+    samples.each_slice(4).find { |sample| sample[0] == id }&.at(1)
+  end
+
+  describe '#update' do
+    it 'records one snapshot per interval' do
+      record([1.0, 0.0, 0.0, 0.0], [1.0, 10.0, 0.0, 0.0])
+
+      expect(buffer.size).to eq(2)
+    end
+
+    it 'keeps memory bounded by dropping the oldest snapshots' do
+      30.times { |i| record([1.0, i.to_f, 0.0, 0.0]) }
+
+      stats = buffer.stats
+      expect(buffer.size).to eq(buffer.capacity)
+      expect(stats[:snapshots]).to eq(buffer.capacity)
+      expect(stats[:memory_bytes]).to be <= stats[:max_memory_bytes]
+      expect(sample_x(buffer.sample(100.0), 1)).to eq(30.0 - buffer.capacity)
+    end
+
+    it 'rejects snapshots that are not packed in fours' do
+      expect { buffer.update(0.1, [1.0, 2.0]) }.to raise_error(ArgumentError)
+    end
+  end
+
+  describe '#sample' do
+    it 'is nil before anything was recorded' do
+      expect(buffer.sample(0.5)).to be_nil
+    end
+
+    it 'interpolates between the two nearest snapshots' do
+      record([1.0, 0.0, 0.0, 0.0], [1.0, 10.0, 20.0, 0.0])
+
+      expect(sample_x(buffer.sample(0.05), 1)).to be_within(0.01).of(5.0)
+      expect(buffer.sample(0.05)[2]).to be_within(0.01).of(10.0)
+    end
+
+    it 'clamps to the recorded range' do
+      record([1.0, 0.0, 0.0, 0.0], [1.0, 10.0, 0.0, 0.0])
+
+      expect(sample_x(buffer.sample(100.0), 1)).to eq(0.0)
+      expect(sample_x(buffer.sample(0.0), 1)).to eq(10.0)
+    end
+
+    it 'interpolates rotation along the shortest arc' do
+      record([1.0, 0.0, 0.0, 3.0], [1.0, 0.0, 0.0, -3.0])
+
+      expect(buffer.sample(0.05)[3].abs).to be_within(0.01).of(Math::PI)
+    end
+
+    it 'uses the nearer snapshot for entities missing from the other' do
+      record([1.0, 0.0, 0.0, 0.0], [1.0, 10.0, 0.0, 0.0, 2.0, 5.0, 0.0, 0.0])
+
+      expect(sample_x(buffer.sample(0.08), 2)).to be_nil
+      expect(sample_x(buffer.sample(0.02), 2)).to eq(5.0)
+    end
+  end
+
+  describe 'playback' do
+    let(:live) { [1.0, 10.0, 0.0, 0.0, 2.0, 5.0, 0.0, 0.0] }
+
+    before do
+      record([1.0, 0.0, 0.0, 0.0, 3.0, 7.0, 0.0, 0.0], [1.0, 10.0, 0.0, 0.0, 2.0, 5.0, 0.0, 0.0])
+    end
+
+    it 'writes historical transforms and hides entities that did not exist yet' do
+      buffer.rewind_to(0.1)
+      frame = buffer.update(0.1, live)
+
+      expect(buffer).to be_rewinding
+      expect(frame[:write]).to eq([1.0, 0.0, 0.0, 0.0])
+      expect(frame[:hide]).to eq([2])
+      expect(frame[:show]).to eq([])
+    end
+
+    it 'does not resurrect entities that no longer exist' do
+      buffer.rewind_to(0.1)
+      frame = buffer.update(0.1, live)
+
+      expect(frame[:write].each_slice(4).map(&:first)).not_to include(3.0)
+    end
+
+    it 'pauses recording while rewinding' do
+      buffer.rewind_to(0.1)
+      3.times { buffer.update(0.1, live) }
+
+      expect(buffer.size).to eq(2)
+    end
+
+    it 'restores live transforms and shows hidden entities on resume' do
+      buffer.rewind_to(0.1)
+      buffer.update(0.1, live)
+      buffer.resume
+      frame = buffer.update(0.1, [1.0, 0.0, 0.0, 0.0, 2.0, 5.0, 0.0, 0.0])
+
+      expect(buffer).not_to be_rewinding
+      expect(frame[:write]).to eq([1.0, 10.0, 0.0, 0.0])
+      expect(frame[:show]).to eq([2])
+    end
+
+    it 'keeps a transform synced during playback as the live value' do
+      buffer.rewind_to(0.1)
+      buffer.update(0.1, live)
+      buffer.update(0.1, [1.0, 42.0, 0.0, 0.0, 2.0, 5.0, 0.0, 0.0])
+      buffer.resume
+      frame = buffer.update(0.1, [1.0, 0.0, 0.0, 0.0, 2.0, 5.0, 0.0, 0.0])
+
+      expect(frame[:write]).to eq([1.0, 42.0, 0.0, 0.0])
+    end
+
+    it 'reports playback in stats' do
+      buffer.rewind_to(0.05)
+
+      expect(buffer.stats[:rewinding]).to be true
+      expect(buffer.stats[:covered_seconds]).to be_within(0.01).of(0.1)
+    end
+  end
+end
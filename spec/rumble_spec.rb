@@ -0,0 +1,57 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::RenderApp, :gpu do
+  def headless_app(frames:)
+    described_class.new(headless: true, width: 32.0, height: 32.0, frames: frames)
+  end
+
+  it 'reports a rumble active until the latest overlapping request ends' do
+    app = headless_app(frames: 4)
+    active = []
+
+    app.run do
+      if app.frame_count.zero?
+        app.queue_gamepad_rumble(7, 1.0, 1.0, 30.0)
+        app.queue_gamepad_rumble(7, 0.5, 0.5, 0.0)
+      end
+      active << app.rumble_active?(7)
+    end
+
+    expect(active.first).to be false
+    expect(active.drop(1)).to all(be true)
+  ensure
+    app&.close
+  end
+
+  it 'stops every rumble and pattern with stop_all_rumble' do
+    app = headless_app(frames: 5)
+    active = []
+
+    app.run do
+      case app.frame_count
+      when 0
+        app.queue_gamepad_rumble(7, 1.0, 1.0, 30.0)
+        app.rumble_pattern(8, [[1.0, 1.0, 30.0]])
+      when 2
+        app.stop_all_rumble
+      end
+      active << [app.rumble_active?(7), app.rumble_active?(8)]
+    end
+
+    expect(active[1]).to eq([true, true])
+    expect(active.drop(3)).to all(eq([false, false]))
+  ensure
+    app&.close
+  end
+
+  it 'rejects durations too long for a Duration before queueing them' do
+    app = headless_app(frames: 1)
+
+    expect { app.rumble_light(7, 1e300) }.to raise_error(ArgumentError, /rumble duration/)
+    expect { app.rumble_pattern(7, [[1.0, 1.0, 1e300]]) }.to raise_error(ArgumentError, /rumble duration/)
+    expect { app.queue_gamepad_rumble(7, 1.0, 1.0, 1e300) }.to raise_error(ArgumentError, /rumble duration/)
+    expect { app.queue_gamepad_rumble(7, 1.0, 1.0, Float::NAN) }.to raise_error(ArgumentError, /rumble duration/)
+  ensure
+    app&.close
+  end
+end
@@ -67,6 +67,19 @@ RSpec.describe Bevy::DynamicScene do
     end
   end
 
+  describe '#capture_world' do
+    it 'skips entities without serialized components' do
+      world = Bevy::World.new
+      world.spawn_entity(Bevy::Transform.identity)
+      meshed = world.spawn_entity(Bevy::Mesh::Rectangle.new(width: 10.0, height: 20.0))
+
+      data = described_class.new.capture_world(world).to_data
+
+      expect(data[:entities].map { |entity| entity[:id] }).to eq([meshed.id])
+      expect(data[:entities].first[:components].keys).to eq(['Mesh::Rectangle'])
+    end
+  end
+
   describe '#load_data' do
     it 'loads data from hash' do
       scene = described_class.new
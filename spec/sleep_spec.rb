@@ -0,0 +1,109 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::SleepTracker do
+  subject(:tracker) { described_class.new(0.01, 0.05, 3) }
+
+  def observe_frames(id, motion, frames)
+    Array.new(frames) { tracker.observe(id, motion) }
+  end
+
+  it 'sleeps after the configured number of quiet frames' do
+    tracker.insert(1)
+
+    expect(observe_frames(1, 0.0, 3)).to eq([false, false, true])
+    expect(tracker.asleep?(1)).to be(true)
+    expect(tracker.awake_ids).to eq([])
+    expect(tracker.sleeping_count).to eq(1)
+  end
+
+  it 'starts the count over on motion above the wake threshold' do
+    tracker.insert(1)
+    observe_frames(1, 0.0, 2)
+    tracker.observe(1, 0.1)
+
+    expect(observe_frames(1, 0.0, 2)).to eq([false, false])
+    expect(tracker.observe(1, 0.0)).to be(true)
+  end
+
+  it 'neither counts nor resets motion between the two thresholds' do
+    tracker.insert(1)
+    observe_frames(1, 0.0, 2)
+
+    expect(observe_frames(1, 0.03, 5)).to all(be(false))
+    expect(tracker.observe(1, 0.0)).to be(true)
+  end
+
+  it 'leaves sleeping and untracked entities alone' do
+    tracker.insert(1)
+    observe_frames(1, 0.0, 3)
+
+    expect(tracker.observe(1, 1.0)).to be(false)
+    expect(tracker.asleep?(1)).to be(true)
+    expect(tracker.observe(2, 0.0)).to be(false)
+    expect(tracker.asleep?(2)).to be(false)
+  end
+
+  it 'wakes an entity back onto the awake list with a fresh count' do
+    tracker.insert(1)
+    observe_frames(1, 0.0, 3)
+
+    expect(tracker.wake(1)).to be(true)
+    expect(tracker.awake_ids).to eq([1])
+    expect(observe_frames(1, 0.0, 3)).to eq([false, false, true])
+  end
+
+  it 'resets the count of an awake entity that is woken' do
+    tracker.insert(1)
+    observe_frames(1, 0.0, 2)
+
+    expect(tracker.wake(1)).to be(false)
+    expect(observe_frames(1, 0.0, 2)).to eq([false, false])
+  end
+
+  it 'wakes everything at once' do
+    [1, 2, 3].each { |id| tracker.insert(id) }
+    [1, 3].each { |id| observe_frames(id, 0.0, 3) }
+
+    expect(tracker.wake_all).to eq(2)
+    expect(tracker.awake_ids).to eq([1, 2, 3])
+    expect(tracker.sleeping_count).to eq(0)
+  end
+
+  it 'forgets removed entities' do
+    tracker.insert(1)
+    observe_frames(1, 0.0, 3)
+    tracker.remove(1)
+
+    expect(tracker.sleeping_count).to eq(0)
+    expect(tracker.wake(1)).to be(false)
+    expect(tracker.awake_ids).to eq([])
+  end
+
+  it 'never sleeps with frames set to 0' do
+    never = described_class.new(0.01, 0.01, 0)
+    never.insert(1)
+
+    expect(Array.new(100) { never.observe(1, 0.0) }).to all(be(false))
+  end
+
+  it 'puts a jelly mesh to sleep once its springs settle and wakes it on an impulse' do
+    jelly = Bevy::JellyMesh.new(64.0, 64.0)
+    tracker.insert(7)
+    jelly.apply_impulse(1.0, 0.0, 200.0)
+
+    frames = 0
+    until tracker.asleep?(7) || frames > 600
+      jelly.step(1.0 / 60) unless tracker.asleep?(7)
+      tracker.observe(7, jelly.max_speed / 60)
+      frames += 1
+    end
+    expect(tracker.asleep?(7)).to be(true)
+    positions = jelly.positions
+
+    jelly.apply_impulse(0.0, 1.0, 200.0)
+    tracker.wake(7)
+    jelly.step(1.0 / 60)
+    expect(tracker.observe(7, jelly.max_speed / 60)).to be(false)
+    expect(jelly.positions).not_to eq(positions)
+  end
+end
@@ -15,6 +15,9 @@ RSpec.configure do |config|
   config.disable_monkey_patching!
   config.warnings = true
 
+  # Examples that boot the native renderer need a GPU adapter.
+  config.filter_run_excluding :gpu unless ENV['BEVY_GPU_SPECS']
+
   config.order = :random
   Kernel.srand config.seed
 end
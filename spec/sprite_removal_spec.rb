@@ -0,0 +1,74 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::RenderApp, :gpu do
+  let(:sprite) { Bevy::Sprite.new(custom_size: Bevy::Vec2.new(4.0, 4.0)).to_sync_hash }
+  let(:transform) { Bevy::Transform.new.to_sync_hash }
+
+  # Runs `frames` frames, calling `script` with each frame number before
+  # recording whether each of `ids` is still synced. Operations queued in
+  # frame n are applied before frame n + 1 is recorded.
+  def synced_frames(ids, frames:, &script)
+    app = described_class.new(headless: true, width: 32.0, height: 32.0, frames: frames)
+    synced = []
+    app.run do
+      script.call(app, app.frame_count)
+      synced << ids.map { |id| !app.draw_order(id).nil? }
+    end
+    synced
+  ensure
+    app&.close
+  end
+
+  it 'keeps a removed sprite for exactly the deferred frames' do
+    synced = synced_frames([1], frames: 7) do |app, frame|
+      app.sync_sprite(1, sprite, transform) if frame.zero?
+      app.remove_sprite(1, { defer: 3 }) if frame == 1
+    end
+
+    expect(synced.map(&:first)).to eq([false, true, true, true, true, false, false])
+  end
+
+  it 'removes a sprite right away when the deferral is zero' do
+    synced = synced_frames([1], frames: 4) do |app, frame|
+      app.sync_sprite(1, sprite, transform) if frame.zero?
+      app.remove_sprite(1, { defer: 0 }) if frame == 1
+    end
+
+    expect(synced.map(&:first)).to eq([false, true, false, false])
+  end
+
+  it 'uses set_remove_defer for removals without defer:' do
+    synced = synced_frames([1], frames: 6) do |app, frame|
+      if frame.zero?
+        app.set_remove_defer(2)
+        app.sync_sprite(1, sprite, transform)
+      end
+      app.remove_sprite(1) if frame == 1
+    end
+
+    expect(synced.map(&:first)).to eq([false, true, true, true, false, false])
+  end
+
+  it 'cancels the removal when the sprite is re-synced during the countdown' do
+    synced = synced_frames([1], frames: 8) do |app, frame|
+      app.sync_sprite(1, sprite, transform) if frame.zero? || frame == 2
+      app.remove_sprite(1, { defer: 3 }) if frame == 1
+    end
+
+    expect(synced.drop(1).map(&:first)).to all(be true)
+  end
+
+  it 'drops pending deferrals on clear' do
+    synced = synced_frames([1, 2], frames: 8) do |app, frame|
+      [1, 2].each { |id| app.sync_sprite(id, sprite, transform) } if frame.zero?
+      app.remove_sprite(1, { defer: 4 }) if frame == 1
+      if frame == 2
+        app.clear_sprites
+        app.sync_sprite(2, sprite, transform)
+      end
+    end
+
+    expect(synced[2]).to eq([true, true])
+    expect(synced.drop(3)).to all(eq([false, true]))
+  end
+end
@@ -41,6 +41,140 @@ RSpec.describe Bevy::Sprite do
     end
   end
 
+  describe '#group' do
+    it 'is carried through builders and the sync hash' do
+      s = described_class.new(group: :enemies).with_color(Bevy::Color.red)
+      expect(s.group).to eq('enemies')
+      expect(s.to_sync_hash[:group]).to eq('enemies')
+    end
+
+    it 'is omitted from the sync hash when unset' do
+      expect(described_class.new.to_sync_hash).not_to have_key(:group)
+    end
+  end
+
+  describe 'accessibility metadata' do
+    it 'is carried through builders and the sync hash' do
+      s = described_class.new(accessible_label: 'Play', accessible_role: :button).with_color(Bevy::Color.red)
+
+      expect(s.to_sync_hash).to include(accessible_label: 'Play', accessible_role: 'button')
+    end
+
+    it 'survives the native round trip' do
+      s = described_class.from_native(described_class.new.with_accessibility('Logo').to_native)
+
+      expect(s.accessible_label).to eq('Logo')
+      expect(s.accessible_role).to be_nil
+    end
+
+    it 'is omitted from the sync hash when unset' do
+      expect(described_class.new.to_sync_hash.keys).not_to include(:accessible_label, :accessible_role)
+    end
+  end
+
+  describe '#texture_path' do
+    it 'is carried through builders and the sync hash' do
+      s = described_class.new(texture_path: 'player.png').with_flip_x(true)
+
+      expect(s.texture_path).to eq('player.png')
+      expect(s.to_sync_hash[:texture_path]).to eq('player.png')
+      expect(s.to_h[:texture_path]).to eq('player.png')
+    end
+
+    it 'survives the native round trip' do
+      s = described_class.from_native(described_class.new.with_texture_path('tiles/grass.png').to_native)
+
+      expect(s.texture_path).to eq('tiles/grass.png')
+    end
+
+    it 'is omitted from the sync hash when unset' do
+      expect(described_class.new.to_sync_hash).not_to have_key(:texture_path)
+    end
+  end
+
+  describe 'jelly mesh mode' do
+    it 'is off by default' do
+      s = described_class.new
+      expect(s.mesh_mode?).to be false
+      expect(s.to_sync_hash).not_to have_key(:mesh_mode)
+    end
+
+    it 'uses the default tuning for true' do
+      s = described_class.new(jelly: true)
+      expect(s.mesh_mode?).to be true
+      expect(s.to_sync_hash).to include(mesh_mode: true)
+      expect(s.to_sync_hash).not_to have_key(:stiffness)
+    end
+
+    it 'is carried through builders and the sync hash' do
+      s = described_class.new(jelly: { subdivisions: 8, stiffness: 90.0 }).with_color(Bevy::Color.red)
+      expect(s.to_sync_hash).to include(mesh_mode: true, subdivisions: 8, stiffness: 90.0)
+      expect(s.to_h[:jelly]).to eq(subdivisions: 8, stiffness: 90.0)
+    end
+
+    it 'survives the native round trip' do
+      s = described_class.from_native(described_class.new.with_jelly(damping: 3.0).to_native)
+      expect(s.jelly).to eq(damping: 3.0)
+    end
+
+    it 'can be turned off again' do
+      expect(described_class.new(jelly: true).with_jelly(false).mesh_mode?).to be false
+    end
+
+    it 'rejects unknown options' do
+      expect { described_class.new(jelly: { bounciness: 2 }) }.to raise_error(ArgumentError, /bounciness/)
+    end
+  end
+
+  describe 'z_index' do
+    it 'is omitted from the sync hash when unset' do
+      s = described_class.new
+      expect(s.z_index).to be_nil
+      expect(s.to_sync_hash).not_to have_key(:z_index)
+    end
+
+    it 'is carried through builders and the sync hash' do
+      s = described_class.new(z_index: 3).with_color(Bevy::Color.red)
+      expect(s.to_sync_hash).to include(z_index: 3)
+      expect(s.to_h[:z_index]).to eq(3)
+      expect(s.with_z_index(-1).z_index).to eq(-1)
+    end
+
+    it 'survives the native round trip' do
+      s = described_class.from_native(described_class.new(z_index: 2).to_native)
+      expect(s.z_index).to eq(2)
+    end
+  end
+
+  describe 'atlas_rect' do
+    it 'is omitted from the sync hash when unset' do
+      expect(described_class.new.to_sync_hash).not_to have_key(:atlas_rect)
+    end
+
+    it 'is carried through builders and the sync hash as floats' do
+      s = described_class.new(texture_path: 'hero.png', atlas_rect: [32, 0, 16, 16]).with_flip_x(true)
+      expect(s.to_sync_hash).to include(atlas_rect: [32.0, 0.0, 16.0, 16.0])
+      expect(s.with_atlas_rect([48, 0, 16, 16]).atlas_rect).to eq([48.0, 0.0, 16.0, 16.0])
+      expect(s.with_atlas_rect(nil).to_sync_hash).not_to have_key(:atlas_rect)
+    end
+
+    it 'accepts a texture atlas layout rect' do
+      layout = Bevy::TextureAtlasLayout.from_grid(tile_size: Bevy::Vec2.new(16, 24), columns: 4, rows: 2)
+      s = described_class.new(atlas_rect: layout.get_texture_rect(5))
+      expect(s.atlas_rect).to eq([16.0, 24.0, 16.0, 24.0])
+    end
+
+    it 'rejects malformed or empty rects' do
+      expect { described_class.new(atlas_rect: [0, 0, 16]) }.to raise_error(ArgumentError, /x, y, width, height/)
+      expect { described_class.new(atlas_rect: [0, 0, 0, 16]) }.to raise_error(ArgumentError, /positive/)
+    end
+
+    it 'survives the native round trip' do
+      s = described_class.from_native(described_class.new(atlas_rect: [1, 2, 3, 4]).to_native)
+      expect(s.atlas_rect).to eq([1.0, 2.0, 3.0, 4.0])
+    end
+  end
+
   describe '#type_name' do
     it 'returns Sprite' do
       s = described_class.new
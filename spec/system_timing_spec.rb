@@ -0,0 +1,69 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::SystemTimings do
+  let(:timings) { described_class.new(3).tap { |t| t.enabled = true } }
+
+  it 'starts disabled and ignores timings until enabled' do
+    disabled = described_class.new
+    disabled.record('sprite_sync', 120)
+    disabled.end_frame
+
+    expect(disabled).not_to be_enabled
+    expect(disabled.frame).to be_empty
+    expect(disabled.max).to be_empty
+  end
+
+  it 'reports the last closed frame, summing repeated runs' do
+    timings.record('sprite_sync', 120)
+    timings.record('parallax', 10)
+    timings.record('parallax', 15)
+
+    expect(timings.frame).to be_empty
+
+    timings.end_frame
+
+    expect(timings.frame).to eq(sprite_sync: 120, parallax: 25)
+  end
+
+  it 'keeps the maximum over the rolling window' do
+    [40, 900, 60, 70].each do |micros|
+      timings.record('text_sync', micros)
+      timings.end_frame
+    end
+
+    expect(timings.max).to eq(text_sync: 900)
+
+    timings.record('text_sync', 80)
+    timings.end_frame
+
+    expect(timings.max).to eq(text_sync: 80)
+  end
+
+  it 'forgets systems that did not run within the window' do
+    timings.record('mesh_sync', 500)
+    timings.end_frame
+    2.times do
+      timings.record('sprite_sync', 5)
+      timings.end_frame
+    end
+
+    expect(timings.max).to eq(mesh_sync: 500, sprite_sync: 5)
+    expect(timings.frame).to eq(sprite_sync: 5)
+
+    timings.end_frame
+
+    expect(timings.max).to eq(sprite_sync: 5)
+  end
+
+  it 'drops everything when disabled' do
+    timings.record('camera_sync', 30)
+    timings.end_frame
+
+    timings.enabled = false
+    timings.record('camera_sync', 30)
+    timings.end_frame
+
+    expect(timings.frame).to be_empty
+    expect(timings.max).to be_empty
+  end
+end
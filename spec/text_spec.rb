@@ -0,0 +1,107 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::Text2d do
+  describe '#to_sync_hash' do
+    it 'contains content, size and color' do
+      hash = described_class.new('Score', font_size: 32, color: Bevy::Color.red).to_sync_hash
+
+      expect(hash).to include(content: 'Score', font_size: 32.0, color_r: 1.0, color_g: 0.0)
+    end
+
+    it 'omits accessibility keys unless set' do
+      expect(described_class.new('Score').to_sync_hash.keys).not_to include(:accessible_label, :accessible_role)
+    end
+  end
+
+  describe 'accessibility metadata' do
+    it 'is carried through builders' do
+      text = described_class.new('Start', accessible_role: :button).with_content('Start game')
+
+      expect(text.to_sync_hash).to include(content: 'Start game', accessible_role: 'button')
+    end
+
+    it 'sets the label and keeps the role with #with_accessibility' do
+      text = described_class.new('3', accessible_role: :label).with_accessibility('Lives: 3')
+
+      expect(text.accessible_label).to eq('Lives: 3')
+      expect(text.accessible_role).to eq(:label)
+    end
+
+    it 'survives the native round trip' do
+      native = described_class.new('Quit', accessible_label: 'Quit game', accessible_role: :button).to_native
+      text = described_class.from_native(native)
+
+      expect(text.accessible_label).to eq('Quit game')
+      expect(text.accessible_role).to eq(:button)
+    end
+  end
+
+  describe 'glow' do
+    let(:text) { described_class.new('+100', color: Bevy::Color.red, glow: { radius: 4 }) }
+
+    it 'defaults to the text color and full intensity' do
+      expect(text.to_sync_hash).to include(glow_r: 1.0, glow_g: 0.0, glow_radius: 4.0, glow_intensity: 1.0)
+    end
+
+    it 'omits glow keys without a glow' do
+      expect(described_class.new('+100').to_sync_hash.keys).not_to include(:glow_radius)
+    end
+
+    it 'is kept across content changes and replaced by #with_glow' do
+      grown = text.with_content('+200').with_glow(color: Bevy::Color.white, radius: 8, intensity: 0.5)
+
+      expect(grown.to_sync_hash).to include(content: '+200', glow_b: 1.0, glow_radius: 8.0, glow_intensity: 0.5)
+      expect(grown.with_glow(nil).glow).to be_nil
+    end
+
+    it 'rejects a negative radius' do
+      expect { described_class.new('x', glow: { radius: -1 }) }.to raise_error(ArgumentError)
+    end
+
+    it 'survives the native round trip' do
+      restored = described_class.from_native(text.with_blend(:add).to_native)
+
+      expect(restored.glow).to include(radius: 4.0, intensity: 1.0)
+      expect(restored.blend).to eq(:add)
+    end
+  end
+
+  describe 'blend' do
+    it 'is only sent when additive' do
+      expect(described_class.new('x').to_sync_hash.keys).not_to include(:blend)
+      expect(described_class.new('x', blend: :add).to_sync_hash[:blend]).to eq('add')
+    end
+
+    it 'rejects unknown modes' do
+      expect { described_class.new('x', blend: :multiply) }.to raise_error(ArgumentError)
+    end
+  end
+
+  describe 'alignment' do
+    it 'defaults to left-justified word wrapping and sends nothing' do
+      text = described_class.new('x')
+
+      expect(text.justify).to eq(:left)
+      expect(text.linebreak).to eq(:word)
+      expect(text.to_sync_hash.keys).not_to include(:justify, :linebreak)
+    end
+
+    it 'sends the justification and line breaking and keeps them across changes' do
+      text = described_class.new("Score\n100", justify: 'center').with_linebreak(:none).with_content("Score\n200")
+
+      expect(text.to_sync_hash).to include(justify: 'center', linebreak: 'none')
+    end
+
+    it 'survives the native round trip' do
+      restored = described_class.from_native(described_class.new('x').with_justify(:right).to_native)
+
+      expect(restored.justify).to eq(:right)
+      expect(restored.linebreak).to eq(:word)
+    end
+
+    it 'rejects unknown modes' do
+      expect { described_class.new('x', justify: :justified) }.to raise_error(ArgumentError, /justify/)
+      expect { described_class.new('x', linebreak: :hyphen) }.to raise_error(ArgumentError, /linebreak/)
+    end
+  end
+end
@@ -0,0 +1,68 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::PendingTextures do
+  subject(:pending) { described_class.new }
+
+  it 'keeps sprites waiting while their path is still loading' do
+    pending.wait(1, 'hero.png')
+
+    expect(pending.poll({})).to eq([])
+    expect(pending.poll({ 'hero.png' => :loading })).to eq([])
+    expect(pending.waiting_on(1)).to eq('hero.png')
+  end
+
+  it 'hands back every sprite waiting on a path once it loads' do
+    pending.wait(3, 'tiles.png')
+    pending.wait(1, 'tiles.png')
+    pending.wait(2, 'hero.png')
+
+    settled = pending.poll({ 'tiles.png' => :loaded })
+
+    expect(settled).to eq([['tiles.png', :loaded, [1, 3]]])
+    expect(pending.waiting_on(1)).to be_nil
+    expect(pending.waiting_on(2)).to eq('hero.png')
+    expect(pending.size).to eq(1)
+  end
+
+  it 'does not hand back a sprite removed before its load finished' do
+    pending.wait(1, 'boss.png')
+    pending.wait(2, 'boss.png')
+
+    expect(pending.forget(1)).to be(true)
+    expect(pending.forget(1)).to be(false)
+    expect(pending.poll({ 'boss.png' => :loaded })).to eq([['boss.png', :loaded, [2]]])
+  end
+
+  it 'drops a path once its last waiting sprite is removed' do
+    pending.wait(1, 'boss.png')
+    pending.forget(1)
+
+    expect(pending.size).to eq(0)
+    expect(pending.poll({ 'boss.png' => :loaded })).to eq([])
+  end
+
+  it 'moves a sprite re-synced with another path' do
+    pending.wait(1, 'old.png')
+    pending.wait(1, 'new.png')
+
+    expect(pending.poll({ 'old.png' => :loaded, 'new.png' => :loading })).to eq([])
+    expect(pending.poll({ 'new.png' => :loaded })).to eq([['new.png', :loaded, [1]]])
+  end
+
+  it 'remembers failed paths' do
+    pending.wait(4, 'missing.png')
+    pending.wait(5, 'hero.png')
+
+    settled = pending.poll({ 'missing.png' => :failed, 'hero.png' => 'loaded' })
+
+    expect(settled).to eq([['hero.png', :loaded, [5]], ['missing.png', :failed, [4]]])
+    expect(pending.failed?('missing.png')).to be(true)
+    expect(pending.failed?('hero.png')).to be(false)
+  end
+
+  it 'rejects unknown load states' do
+    pending.wait(1, 'hero.png')
+
+    expect { pending.poll({ 'hero.png' => :decoded }) }.to raise_error(ArgumentError, /load state/)
+  end
+end
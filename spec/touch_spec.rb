@@ -0,0 +1,99 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::TouchStates do
+  subject(:touches) { described_class.new }
+
+  it 'tracks each finger separately in id order' do
+    touches.apply(4, :started, 100.0, 200.0, nil)
+    touches.apply(2, :started, 10.0, 20.0, 0.25)
+
+    expect(touches.touches).to eq([
+      { id: 2, phase: 'started', position: [10.0, 20.0], previous_position: [10.0, 20.0], force: 0.25 },
+      { id: 4, phase: 'started', position: [100.0, 200.0], previous_position: [100.0, 200.0], force: nil }
+    ])
+    expect(touches.size).to eq(2)
+  end
+
+  it 'keeps the previous position when a finger moves' do
+    touches.apply(1, :started, 10.0, 10.0, nil)
+    touches.begin_frame
+    touches.apply(1, :moved, 15.0, 12.0, nil)
+
+    touch = touches.touches.first
+    expect(touch[:phase]).to eq('moved')
+    expect(touch[:position]).to eq([15.0, 12.0])
+    expect(touch[:previous_position]).to eq([10.0, 10.0])
+  end
+
+  it 'reports fingers that did not move as stationary' do
+    touches.apply(1, :started, 10.0, 10.0, nil)
+    touches.begin_frame
+
+    expect(touches.touches.first).to include(phase: 'stationary', previous_position: [10.0, 10.0])
+  end
+
+  it 'lists a released touch exactly once with an ended phase' do
+    touches.apply(1, :started, 10.0, 10.0, nil)
+    touches.apply(2, :started, 30.0, 30.0, nil)
+    touches.begin_frame
+    touches.apply(1, :ended, 11.0, 10.0, nil)
+
+    expect(touches.touches.map { |touch| [touch[:id], touch[:phase]] }).to eq([[1, 'ended'], [2, 'stationary']])
+
+    touches.begin_frame
+    expect(touches.touches.map { |touch| touch[:id] }).to eq([2])
+    expect(touches.position(1)).to be_nil
+  end
+
+  it 'lists a touch that started and ended within one frame' do
+    touches.apply(1, :started, 10.0, 10.0, nil)
+    touches.apply(1, :ended, 10.0, 10.0, nil)
+
+    expect(touches.touches.first[:phase]).to eq('ended')
+    touches.begin_frame
+    expect(touches.touches).to be_empty
+  end
+
+  it 'keeps a started touch started when it also moves that frame' do
+    touches.apply(1, :started, 10.0, 10.0, nil)
+    touches.apply(1, :moved, 12.0, 10.0, nil)
+
+    expect(touches.touches.first).to include(phase: 'started', position: [12.0, 10.0])
+  end
+
+  it 'reports just pressed only on the frame a finger touched down' do
+    touches.apply(3, :started, 0.0, 0.0, nil)
+
+    expect(touches.just_pressed?(nil)).to be(true)
+    expect(touches.just_pressed?(3)).to be(true)
+    expect(touches.just_pressed?(4)).to be(false)
+
+    touches.begin_frame
+    expect(touches.just_pressed?(nil)).to be(false)
+  end
+
+  it 'updates the force reported with each event' do
+    touches.apply(1, :started, 0.0, 0.0, 0.2)
+    touches.apply(1, :moved, 1.0, 0.0, 0.8)
+
+    expect(touches.touches.first[:force]).to be_within(1e-6).of(0.8)
+  end
+
+  it 'returns the position of a touch by id' do
+    touches.apply(5, :started, 7.0, 8.0, nil)
+
+    expect(touches.position(5)).to eq([7.0, 8.0])
+    expect(touches.position(6)).to be_nil
+  end
+
+  it 'rejects unknown phases' do
+    expect { touches.apply(1, :hovering, 0.0, 0.0, nil) }.to raise_error(ArgumentError, /unknown touch phase/)
+  end
+
+  it 'forgets every touch on clear' do
+    touches.apply(1, :started, 0.0, 0.0, nil)
+    touches.clear
+
+    expect(touches.touches).to eq([])
+  end
+end
@@ -130,6 +130,120 @@ RSpec.describe Bevy::Transform do
     end
   end
 
+  describe '#translate with a Vec3' do
+    it 'moves the transform in place and returns it' do
+      t = described_class.from_xyz(1.0, 0.0, 0.0)
+      result = t.translate(Bevy::Vec3.new(2.0, 3.0, 0.0))
+      expect(result).to equal(t)
+      expect(t.translation.to_a).to eq([3.0, 3.0, 0.0])
+    end
+  end
+
+  describe 'setters' do
+    it 'replaces translation, rotation and scale' do
+      t = described_class.identity
+      t.translation = Bevy::Vec3.new(4.0, 5.0, 6.0)
+      t.rotation = Bevy::Quat.from_rotation_z(Math::PI)
+      t.scale = Bevy::Vec3.new(2.0, 2.0, 1.0)
+      expect(t.translation.to_a).to eq([4.0, 5.0, 6.0])
+      expect(t.rotation_z.abs).to be_within(0.001).of(Math::PI)
+      expect(t.scale.x).to eq(2.0)
+    end
+
+    it 'returns copies from the getters' do
+      t = described_class.identity
+      t.translation.x = 9.0
+      expect(t.translation.x).to eq(0.0)
+    end
+  end
+
+  describe '#rotate_z' do
+    it 'accumulates rotation on the receiver' do
+      t = described_class.identity
+      t.rotate_z(Math::PI / 4).rotate_z(Math::PI / 4)
+      expect(t.rotation_z).to be_within(0.001).of(Math::PI / 2)
+    end
+  end
+
+  describe '#look_at' do
+    it 'points forward at the target' do
+      t = described_class.from_xyz(0.0, 0.0, 5.0)
+      t.look_at(Bevy::Vec3.new(0.0, 0.0, 0.0))
+      expect(t.forward.z).to be_within(0.001).of(-1.0)
+    end
+
+    it 'faces along X when the target is to the right' do
+      t = described_class.identity
+      t.look_at(Bevy::Vec3.new(10.0, 0.0, 0.0), Bevy::Vec3.new(0.0, 1.0, 0.0))
+      expect(t.forward.x).to be_within(0.001).of(1.0)
+    end
+  end
+
+  describe '#transform_point' do
+    it 'applies scale, rotation and translation' do
+      t = described_class.new(
+        translation: Bevy::Vec3.new(10.0, 0.0, 0.0),
+        rotation: Bevy::Quat.from_rotation_z(Math::PI / 2),
+        scale: Bevy::Vec3.new(2.0, 2.0, 2.0)
+      )
+      result = t.transform_point(Bevy::Vec3.new(1.0, 0.0, 0.0))
+      expect(result.x).to be_within(0.001).of(10.0)
+      expect(result.y).to be_within(0.001).of(2.0)
+    end
+  end
+
+  describe '#==' do
+    it 'compares values' do
+      expect(described_class.from_xyz(1.0, 2.0, 3.0)).to eq(described_class.from_xyz(1.0, 2.0, 3.0))
+      expect(described_class.from_xyz(1.0, 2.0, 3.0)).not_to eq(described_class.identity)
+    end
+
+    it 'keeps a dup independent' do
+      t = described_class.identity
+      copy = t.dup
+      copy.translate(Bevy::Vec3.new(1.0, 0.0, 0.0))
+      expect(t.translation.x).to eq(0.0)
+    end
+  end
+
+  describe '#inverse_transform_point' do
+    it 'undoes translation, rotation, and scale' do
+      t = described_class.new(
+        translation: Bevy::Vec3.new(10.0, -4.0, 0.0),
+        rotation: Bevy::Quat.from_rotation_z(Math::PI / 3),
+        scale: Bevy::Vec3.new(2.0, 0.5, 1.0)
+      )
+      local = Bevy::Vec3.new(3.0, 7.0, 1.0)
+      result = t.inverse_transform_point(t.transform_point(local))
+      expect(result.x).to be_within(0.001).of(3.0)
+      expect(result.y).to be_within(0.001).of(7.0)
+      expect(result.z).to be_within(0.001).of(1.0)
+    end
+
+    it 'maps zero-scale axes to zero' do
+      t = described_class.new(scale: Bevy::Vec3.new(0.0, 1.0, 1.0))
+      result = t.inverse_transform_point(Bevy::Vec3.new(5.0, 5.0, 0.0))
+      expect(result.x).to eq(0.0)
+      expect(result.y).to be_within(0.001).of(5.0)
+    end
+  end
+
+  describe '#compute_matrix' do
+    it 'agrees with transform_point' do
+      t = described_class.new(
+        translation: Bevy::Vec3.new(1.0, 2.0, 3.0),
+        rotation: Bevy::Quat.from_rotation_z(Math::PI / 2),
+        scale: Bevy::Vec3.new(2.0, 2.0, 2.0)
+      )
+      point = Bevy::Vec3.new(1.0, 0.0, 0.0)
+      expected = t.transform_point(point)
+      result = t.compute_matrix.transform_point3(point)
+      expect(result.x).to be_within(0.001).of(expected.x)
+      expect(result.y).to be_within(0.001).of(expected.y)
+      expect(result.z).to be_within(0.001).of(expected.z)
+    end
+  end
+
   describe '#to_native' do
     it 'converts to a Component' do
       t = described_class.from_xyz(1.0, 2.0, 3.0)
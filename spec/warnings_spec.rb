@@ -0,0 +1,79 @@
+# frozen_string_literal: true
+
+RSpec.describe Bevy::WarningLog do
+  let(:log) { described_class.new(interval: 5.0) }
+
+  it 'keeps the first occurrence immediately' do
+    log.begin_frame(0.0)
+    log.push(:sprite, 'unknown id 7', 'remove:7')
+
+    expect(log.drain).to eq([{ category: :sprite, message: 'unknown id 7', count: 1, first_frame: 1 }])
+  end
+
+  it 'counts repeats within the interval instead of keeping them' do
+    log.begin_frame(0.0)
+    log.push(:sprite, 'unknown id 7', 'remove:7')
+    log.drain
+
+    log.begin_frame(1.0)
+    3.times { log.push(:sprite, 'unknown id 7', 'remove:7') }
+
+    expect(log.drain).to be_empty
+  end
+
+  it 'folds suppressed repeats into one record once the interval passes' do
+    log.begin_frame(0.0)
+    log.push(:sprite, 'unknown id 7', 'remove:7')
+    log.drain
+    log.begin_frame(1.0)
+    2.times { log.push(:sprite, 'unknown id 7', 'remove:7') }
+    log.begin_frame(2.0)
+    log.push(:sprite, 'unknown id 7 again', 'remove:7')
+
+    log.begin_frame(6.0)
+
+    expect(log.drain).to eq([{ category: :sprite, message: 'unknown id 7 again', count: 3, first_frame: 2 }])
+  end
+
+  it 'rate limits each dedupe key separately' do
+    log.begin_frame(0.0)
+    log.push(:sprite, 'unknown id 7', 'remove:7')
+    log.push(:sprite, 'unknown id 8', 'remove:8')
+    log.push(:sprite, 'unknown id 7', 'remove:7')
+
+    expect(log.drain.map { |warning| warning[:message] }).to eq(['unknown id 7', 'unknown id 8'])
+  end
+
+  it 'bounds the buffer and reports what it dropped' do
+    small = described_class.new(capacity: 2, interval: 5.0)
+    small.begin_frame(0.0)
+    4.times { |index| small.push(:text, "missing font #{index}", "font:#{index}") }
+
+    drained = small.drain
+
+    expect(drained.map { |warning| warning[:message] }.first(2)).to eq(['missing font 2', 'missing font 3'])
+    expect(drained.last).to include(category: :warnings, count: 2)
+  end
+
+  it 'drops silenced categories without counting them' do
+    log.set_policy(:audio, :silent)
+    log.begin_frame(0.0)
+    log.push(:audio, 'no backend', 'audio')
+
+    expect(log.drain).to be_empty
+  end
+
+  it 'raises for categories with the raise policy and keeps later warnings' do
+    log.set_policy(:sprite, :raise)
+    log.begin_frame(0.0)
+    log.push(:sprite, 'unknown id 7', 'remove:7')
+    log.push(:audio, 'no backend', 'audio')
+
+    expect { log.drain }.to raise_error(Bevy::WarningError, 'sprite: unknown id 7')
+    expect(log.drain).to eq([{ category: :audio, message: 'no backend', count: 1, first_frame: 1 }])
+  end
+
+  it 'rejects unknown policies' do
+    expect { log.set_policy(:sprite, :loud) }.to raise_error(ArgumentError, /loud/)
+  end
+end
@@ -240,6 +240,28 @@ RSpec.describe Bevy::WindowFocused do
   end
 end
 
+RSpec.describe Bevy::FileDragAndDrop do
+  it 'exposes kind predicates' do
+    event = described_class.new(kind: described_class::HOVERED, window_id: 1, path: 'a.png')
+    expect(event).to be_hovered
+    expect(event).not_to be_dropped
+    expect(described_class.new(kind: described_class::HOVER_CANCELLED, window_id: 1)).to be_cancelled
+  end
+end
+
+RSpec.describe Bevy::FileDrop do
+  it 'builds from native hashes' do
+    drop = described_class.from_native(kind: 'dropped', path: '/tmp/map.tmx')
+    expect(drop).to be_dropped
+    expect(drop.to_h).to eq(kind: 'dropped', path: '/tmp/map.tmx')
+    expect(described_class.from_native(kind: 'cancelled', path: nil)).to be_cancelled
+  end
+
+  it 'rejects unknown kinds' do
+    expect { described_class.new(kind: 'thrown') }.to raise_error(ArgumentError)
+  end
+end
+
 RSpec.describe Bevy::Monitor do
   describe '.new' do
     it 'creates monitor info' do
@@ -290,3 +312,63 @@ RSpec.describe Bevy::WindowPlugin do
     end
   end
 end
+
+RSpec.describe Bevy::SafeArea do
+  let(:area) { described_class.new(window_width: 800, window_height: 600, top: 30, right: 10, bottom: 0, left: 20) }
+
+  describe '#usable_size' do
+    it 'subtracts insets from the window size' do
+      expect(area.usable_size.x).to eq(770.0)
+      expect(area.usable_size.y).to eq(570.0)
+    end
+
+    it 'never goes negative' do
+      cramped = described_class.new(window_width: 100, window_height: 100, left: 80, right: 80)
+      expect(cramped.usable_size.x).to eq(0.0)
+    end
+  end
+
+  describe 'edges' do
+    it 'uses window-centered coordinates with y up' do
+      expect(area.min_x).to eq(-380.0)
+      expect(area.max_x).to eq(390.0)
+      expect(area.max_y).to eq(270.0)
+      expect(area.min_y).to eq(-300.0)
+      expect(area.center.x).to eq(5.0)
+      expect(area.center.y).to eq(-15.0)
+    end
+  end
+
+  describe '.from_metrics' do
+    it 'reads [top, right, bottom, left] insets' do
+      from_metrics = described_class.from_metrics(width: 800.0, height: 600.0, insets: [30.0, 10.0, 0.0, 20.0])
+      expect(from_metrics).to eq(area)
+    end
+
+    it 'falls back to the full window without insets' do
+      full = described_class.from_metrics(width: 800.0, height: 600.0)
+      expect(full.insets?).to be false
+      expect(full.usable_size.x).to eq(800.0)
+    end
+  end
+end
+
+RSpec.describe Bevy::ScreenAnchor do
+  let(:area) { Bevy::SafeArea.new(window_width: 800, window_height: 600, top: 40, left: 20) }
+
+  it 'anchors to the raw window by default' do
+    position = described_class.new(:top_left).resolve(area)
+    expect(position.x).to eq(-400.0)
+    expect(position.y).to eq(300.0)
+  end
+
+  it 'anchors inside the safe area when requested' do
+    position = described_class.new(:top_left, offset: Bevy::Vec2.new(8.0, -8.0), anchor_to_safe_area: true).resolve(area)
+    expect(position.x).to eq(-372.0)
+    expect(position.y).to eq(252.0)
+  end
+
+  it 'rejects unknown positions' do
+    expect { described_class.new(:middle_earth) }.to raise_error(ArgumentError)
+  end
+end
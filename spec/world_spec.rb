@@ -51,4 +51,378 @@ RSpec.describe Bevy::World do
       expect(world.entity_exists?(entity)).to be false
     end
   end
+
+  describe '#all_entities' do
+    it 'returns every live entity in spawn order' do
+      world = described_class.new
+      first = world.spawn
+      second = world.spawn_entity(Bevy::Transform.new)
+
+      expect(world.all_entities.map(&:id)).to eq([first.id, second.id])
+      expect(world.all_entities).to all(be_a(Bevy::Entity))
+    end
+
+    it 'leaves out despawned entities' do
+      world = described_class.new
+      kept = world.spawn
+      world.despawn(world.spawn)
+
+      expect(world.all_entities.map(&:id)).to eq([kept.id])
+    end
+
+    it 'supports despawning everything' do
+      world = described_class.new
+      3.times { world.spawn }
+
+      world.all_entities.each { |entity| world.despawn(entity) }
+
+      expect(world.entity_count).to eq(0)
+    end
+  end
+
+  describe '#entity_count' do
+    it 'counts live entities' do
+      world = described_class.new
+      2.times { world.spawn }
+
+      expect(world.entity_count).to eq(2)
+      expect(world.entity_count).to eq(world.all_entities.length)
+    end
+  end
+
+  describe '#query_changed' do
+    it 'returns entities whose component was inserted since the last call' do
+      world = described_class.new
+      entity = world.spawn
+      world.insert(entity, Bevy::Component.new('Health'))
+
+      expect(world.query_changed('Health').map(&:id)).to eq([entity.id])
+    end
+
+    it 'drains the feed so unchanged entities are not returned again' do
+      world = described_class.new
+      world.spawn_with([Bevy::Component.new('Health')])
+      world.query_changed('Health')
+
+      expect(world.query_changed('Health')).to be_empty
+    end
+
+    it 'only reports the requested component type' do
+      world = described_class.new
+      world.spawn_with([Bevy::Component.new('Health')])
+
+      expect(world.query_changed('Velocity')).to be_empty
+    end
+
+    it 'skips entities despawned before the drain' do
+      world = described_class.new
+      entity = world.spawn_with([Bevy::Component.new('Health')])
+      world.despawn(entity)
+
+      expect(world.query_changed('Health')).to be_empty
+    end
+
+    it 'primes a type on its first call by returning every entity that has the component once' do
+      world = described_class.new
+      first = world.spawn_with([Bevy::Component.new('Health')])
+      3.times { world.insert(first, Bevy::Component.new('Health')) }
+      second = world.spawn_with([Bevy::Component.new('Health')])
+      world.query_changed('Velocity')
+
+      expect(world.query_changed('Health').map(&:id)).to eq([first.id, second.id])
+      expect(world.query_changed('Health')).to be_empty
+    end
+
+    it 'returns an entity touched several times since the last call once' do
+      world = described_class.new
+      entity = world.spawn_with([Bevy::Component.new('Health')])
+      world.query_changed('Health')
+      3.times { world.insert(entity, Bevy::Component.new('Health')) }
+
+      expect(world.query_changed('Health').map(&:id)).to eq([entity.id])
+    end
+
+    it 'forgets entities despawned after they were touched' do
+      world = described_class.new
+      world.query_changed('Health')
+      kept = world.spawn_with([Bevy::Component.new('Health')])
+      gone = world.spawn_with([Bevy::Component.new('Health')])
+      world.despawn(gone)
+
+      expect(world.query_changed('Health').map(&:id)).to eq([kept.id])
+    end
+  end
+
+  describe '#changed_entities' do
+    def health(current)
+      Bevy::Component.from_hash('Health', { current: current })
+    end
+
+    it 'returns entities whose component was stored after the given generation' do
+      world = described_class.new
+      hero = world.spawn_with([health(100)])
+      goblin = world.spawn_with([health(30)])
+      since = world.generation
+
+      world.insert(goblin, health(29))
+
+      expect(world.changed_entities('Health', since).map(&:id)).to eq([goblin.id])
+      expect(world.changed_entities('Health', 0).map(&:id)).to eq([hero.id, goblin.id].sort)
+    end
+
+    it 'does not drain, so every reader keeps its own generation' do
+      world = described_class.new
+      entity = world.spawn_with([health(100)])
+
+      2.times { expect(world.changed_entities('Health', 0).map(&:id)).to eq([entity.id]) }
+      expect(world.changed_entities('Health', world.generation)).to be_empty
+    end
+
+    it 'only reports the requested component type' do
+      world = described_class.new
+      entity = world.spawn_with([health(100)])
+      since = world.generation
+      world.insert(entity, Bevy::Component.new('Player'))
+
+      expect(world.changed_entities('Health', since)).to be_empty
+      expect(world.changed_entities('Player', since).map(&:id)).to eq([entity.id])
+    end
+
+    it 'skips despawned entities' do
+      world = described_class.new
+      entity = world.spawn_with([health(100)])
+      world.despawn(entity)
+
+      expect(world.changed_entities('Health', 0)).to be_empty
+    end
+  end
+
+  describe '#generation' do
+    it 'grows with every stored component' do
+      world = described_class.new
+      expect(world.generation).to eq(0)
+
+      entity = world.spawn_with([Bevy::Component.new('Health'), Bevy::Component.new('Player')])
+      after_spawn = world.generation
+      world.insert(entity, Bevy::Component.new('Player'))
+
+      expect(after_spawn).to eq(2)
+      expect(world.generation).to be > after_spawn
+    end
+
+    it 'stamps stored components past their own generation' do
+      world = described_class.new
+      entity = world.spawn_with([Bevy::Component.new('Health')])
+      component = world.get(entity, 'Health')
+      5.times { |i| component['current'] = i }
+
+      world.insert(entity, component)
+
+      expect(world.get(entity, 'Health').generation).to be > component.generation
+      expect(world.generation).to eq(world.get(entity, 'Health').generation)
+    end
+  end
+
+  describe '#get_or_insert' do
+    let(:default) { Bevy::Component.from_hash('Health', { current: 100 }) }
+
+    it 'returns the existing component untouched' do
+      world = described_class.new
+      entity = world.spawn_with([Bevy::Component.from_hash('Health', { current: 40 })])
+
+      expect(world.get_or_insert(entity, 'Health', default)['current']).to eq(40)
+      expect(world.get(entity, 'Health')['current']).to eq(40)
+    end
+
+    it 'inserts and returns the default when missing' do
+      world = described_class.new
+      entity = world.spawn
+
+      expect(world.get_or_insert(entity, 'Health', default)['current']).to eq(100)
+      expect(world.get(entity, 'Health')['current']).to eq(100)
+      expect(world.query_changed('Health').map(&:id)).to eq([entity.id])
+    end
+
+    it 'raises for a despawned entity' do
+      world = described_class.new
+      entity = world.spawn
+      world.despawn(entity)
+
+      expect { world.get_or_insert(entity, 'Health', default) }.to raise_error(RuntimeError)
+    end
+  end
+
+  describe '#remove' do
+    it 'removes one component and keeps the entity' do
+      world = described_class.new
+      entity = world.spawn_with([Bevy::Component.new('Health'), Bevy::Component.new('Velocity')])
+      world.remove(entity, 'Health')
+
+      expect(world.has_component?(entity, 'Health')).to be false
+      expect(world.has_component?(entity, 'Velocity')).to be true
+      expect(world.entity_exists?(entity)).to be true
+    end
+
+    it 'raises when the component is not present' do
+      world = described_class.new
+      entity = world.spawn
+
+      expect { world.remove(entity, 'Health') }.to raise_error(RuntimeError, /Health/)
+    end
+
+    it 'backs remove_component for native components' do
+      world = described_class.new
+      entity = world.spawn_entity(Bevy::Sprite.new, Bevy::Transform.identity)
+      world.remove_component(entity, Bevy::Sprite)
+
+      expect(world.has?(entity, Bevy::Sprite)).to be false
+      expect(world.has?(entity, Bevy::Transform)).to be true
+    end
+  end
+
+  describe '#world_hash' do
+    def health(current)
+      Bevy::Component.from_hash('Health', { current: current, max: 100 })
+    end
+
+    it 'matches the documented encoding across runs' do
+      empty = described_class.new
+      world = described_class.new
+      world.spawn_with([health(100)])
+
+      expect(empty.world_hash).to eq(12_161_962_213_042_174_405)
+      expect(world.world_hash).to eq(9_400_674_201_002_859_350)
+    end
+
+    it 'does not depend on the order components were added in' do
+      tag = Bevy::Component.new('Player')
+      first = described_class.new
+      second = described_class.new
+      first.spawn_with([health(100), tag])
+      second.spawn_with([tag, health(100)])
+
+      expect(first.world_hash).to eq(second.world_hash)
+    end
+
+    it 'stays the same when identical data is inserted again' do
+      world = described_class.new
+      entity = world.spawn_with([health(100)])
+      before = world.world_hash
+
+      world.insert(entity, health(100))
+
+      expect(world.world_hash).to eq(before)
+    end
+
+    it 'changes when any component value, component or entity changes' do
+      world = described_class.new
+      entity = world.spawn_with([health(100)])
+      hashes = [world.world_hash]
+
+      world.insert(entity, health(99))
+      hashes << world.world_hash
+      world.insert(entity, Bevy::Component.new('Player'))
+      hashes << world.world_hash
+      world.remove(entity, 'Player')
+      world.spawn
+      hashes << world.world_hash
+
+      expect(hashes.uniq.length).to eq(4)
+    end
+  end
+
+  describe '#dirty_since?' do
+    it 'is false until the world changes' do
+      world = described_class.new
+      entity = world.spawn_with([Bevy::Component.from_hash('Health', { current: 10 })])
+      saved = world.world_hash
+
+      expect(world.dirty_since?(saved)).to be false
+
+      world.insert(entity, Bevy::Component.from_hash('Health', { current: 9 }))
+
+      expect(world.dirty_since?(saved)).to be true
+    end
+
+    it 'treats a missing hash as dirty' do
+      expect(described_class.new.dirty_since?(nil)).to be true
+    end
+  end
+
+  describe '#snapshot' do
+    def health(current)
+      Bevy::Component.from_hash('Health', { current: current })
+    end
+
+    it 'is not changed by later inserts, removals or despawns' do
+      world = described_class.new
+      hero = world.spawn_with([health(100), Bevy::Component.new('Player')])
+      goblin = world.spawn_with([health(30)])
+      snapshot = world.snapshot
+
+      world.insert(hero, health(5))
+      world.remove(hero, 'Player')
+      world.despawn(goblin)
+      world.spawn_with([health(1)])
+
+      expect(snapshot.get(hero, 'Health')['current']).to eq(100)
+      expect(snapshot.has_component?(hero, 'Player')).to be true
+      expect(snapshot.entity_exists?(goblin)).to be true
+      expect(snapshot.count).to eq(2)
+      expect(world.get(hero, 'Health')['current']).to eq(5)
+    end
+
+    it 'shares component data until the world writes to it' do
+      world = described_class.new
+      hero = world.spawn_with([health(100), Bevy::Component.new('Player')])
+      snapshot = world.snapshot
+
+      expect(snapshot.shared_with?(world, hero, 'Health')).to be true
+      expect(snapshot.shared_with?(world, hero, 'Player')).to be true
+
+      world.insert(hero, health(90))
+
+      expect(snapshot.shared_with?(world, hero, 'Health')).to be false
+      expect(snapshot.shared_with?(world, hero, 'Player')).to be true
+      expect(world.snapshot.shared_with?(world, hero, 'Health')).to be true
+    end
+
+    it 'answers reads the same way as the world it was taken from' do
+      world = described_class.new
+      world.spawn
+      world.spawn_with([health(10), Bevy::Component.new('Enemy')])
+      world.spawn_with([Bevy::Component.new('Enemy')])
+      player = world.spawn_with([health(50)])
+      snapshot = world.snapshot
+
+      %w[Health Enemy].each do |type_name|
+        expect(snapshot.query([type_name]).map(&:id)).to match_array(world.query([type_name]).map(&:id))
+      end
+      expect(snapshot.query(%w[Health Enemy]).map(&:id)).to match_array(world.query(%w[Health Enemy]).map(&:id))
+      expect(snapshot.count).to eq(world.count)
+      expect(snapshot.get(player, 'Health')['current']).to eq(world.get(player, 'Health')['current'])
+      expect(snapshot.has_component?(player, 'Enemy')).to eq(world.has_component?(player, 'Enemy'))
+      expect { snapshot.get(player, 'Enemy') }.to raise_error(RuntimeError, /Enemy/)
+    end
+
+    it 'raises like the world for an entity it never saw' do
+      world = described_class.new
+      snapshot = world.snapshot
+      later = world.spawn
+
+      expect(snapshot.entity_exists?(later)).to be false
+      expect { snapshot.get(later, 'Health') }.to raise_error(RuntimeError)
+    end
+
+    it 'can be read from another thread while the world keeps changing' do
+      world = described_class.new
+      entities = Array.new(3) { |i| world.spawn_with([health(i)]) }
+      snapshot = world.snapshot
+
+      reader = Thread.new { entities.map { |entity| snapshot.get(entity, 'Health')['current'] } }
+      entities.each { |entity| world.insert(entity, health(-1)) }
+
+      expect(reader.value).to eq([0, 1, 2])
+    end
+  end
 end